@@ -0,0 +1,178 @@
+//! Adaptive refresh: refresh less often while the server is quiet and
+//! snap back to the fastest interval when activity spikes.
+//!
+//! ```toml
+//! [adaptive_refresh]
+//! enabled = true
+//! min_secs = 1
+//! max_secs = 15
+//! ```
+//!
+//! Each snapshot is read as busy, normal or quiet. Busy (blocking, many
+//! active queries, or TPS well above its recent average) drops straight to
+//! `min_secs`; quiet (at most one active query and hardly any TPS) lengthens
+//! the interval by half each time, up to `max_secs`; normal load eases back
+//! to the configured refresh interval one second at a time.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::PgSnapshot;
+
+/// The `[adaptive_refresh]` table of the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveRefreshConfig {
+    pub enabled: bool,
+    /// Interval under load
+    pub min_secs: u64,
+    /// Longest interval while quiet
+    pub max_secs: u64,
+}
+
+impl Default for AdaptiveRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_secs: 1,
+            max_secs: 15,
+        }
+    }
+}
+
+/// Active queries at which the server counts as busy
+const BUSY_ACTIVE: i64 = 8;
+/// TPS this many times its recent average counts as a spike
+const TPS_SPIKE_FACTOR: f64 = 2.0;
+/// Spikes below this TPS are noise on an idle server
+const TPS_SPIKE_FLOOR: f64 = 20.0;
+/// Below this TPS, with at most one active query, the server is quiet
+const QUIET_TPS: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Load {
+    Busy,
+    Normal,
+    Quiet,
+}
+
+/// How busy `snap` shows the server to be. `tps` is the current rate and
+/// `typical_tps` its recent average, when known.
+pub fn load(snap: &PgSnapshot, tps: Option<f64>, typical_tps: Option<f64>) -> Load {
+    let active = snap.summary.active_query_count;
+    let spike = match (tps, typical_tps) {
+        (Some(tps), Some(typical)) => tps >= TPS_SPIKE_FLOOR && tps >= typical * TPS_SPIKE_FACTOR,
+        _ => false,
+    };
+    if !snap.blocking_info.is_empty() || active >= BUSY_ACTIVE || spike {
+        Load::Busy
+    } else if active <= 1 && tps.map_or(true, |tps| tps < QUIET_TPS) {
+        Load::Quiet
+    } else {
+        Load::Normal
+    }
+}
+
+/// The interval adaptive refresh has settled on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdaptiveRefresh {
+    current: Option<u64>,
+}
+
+impl AdaptiveRefresh {
+    pub const fn current(&self) -> Option<u64> {
+        self.current
+    }
+
+    /// Interval for the refreshes after a snapshot showing `load`, with
+    /// `base_secs` the configured refresh interval.
+    pub fn observe(&mut self, config: &AdaptiveRefreshConfig, base_secs: u64, load: Load) -> u64 {
+        let min = config.min_secs.max(1);
+        let max = config.max_secs.max(min);
+        let base = base_secs.clamp(min, max);
+        let current = self.current.unwrap_or(base).clamp(min, max);
+        let next = match load {
+            Load::Busy => min,
+            Load::Quiet => (current + current.div_ceil(2)).min(max),
+            Load::Normal if current < base => current + 1,
+            Load::Normal => base,
+        };
+        self.current = Some(next);
+        next
+    }
+
+    /// Forget the adapted interval, e.g. when the mode is switched off.
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActivitySummary, BlockingInfo, BufferCacheStats};
+    use chrono::Utc;
+
+    fn snapshot(active_query_count: i64, blocked: bool) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc::now(),
+            blocking_info: if blocked {
+                vec![BlockingInfo {
+                    blocked_pid: 2,
+                    blocked_user: None,
+                    blocked_query: None,
+                    blocked_duration_secs: 1.0,
+                    blocker_pid: 1,
+                    blocker_user: None,
+                    blocker_query: None,
+                    blocker_state: None,
+                }]
+            } else {
+                vec![]
+            },
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                active_query_count,
+                total_backends: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn load_reads_blocking_active_queries_and_tps() {
+        assert_eq!(load(&snapshot(0, false), Some(1.0), Some(1.0)), Load::Quiet);
+        assert_eq!(load(&snapshot(0, false), None, None), Load::Quiet);
+        assert_eq!(load(&snapshot(3, false), Some(10.0), Some(10.0)), Load::Normal);
+        assert_eq!(load(&snapshot(0, true), Some(1.0), Some(1.0)), Load::Busy);
+        assert_eq!(load(&snapshot(BUSY_ACTIVE, false), Some(10.0), Some(10.0)), Load::Busy);
+        // A spike against the recent average, but not on an idle server
+        assert_eq!(load(&snapshot(2, false), Some(300.0), Some(100.0)), Load::Busy);
+        assert_eq!(load(&snapshot(2, false), Some(12.0), Some(3.0)), Load::Normal);
+    }
+
+    #[test]
+    fn quiet_slows_down_busy_snaps_back_normal_eases_to_base() {
+        let config = AdaptiveRefreshConfig {
+            enabled: true,
+            min_secs: 1,
+            max_secs: 10,
+        };
+        let mut adaptive = AdaptiveRefresh::default();
+        let quiet: Vec<u64> = (0..5).map(|_| adaptive.observe(&config, 2, Load::Quiet)).collect();
+        assert_eq!(quiet, vec![3, 5, 8, 10, 10]);
+
+        assert_eq!(adaptive.observe(&config, 2, Load::Normal), 2);
+        assert_eq!(adaptive.observe(&config, 2, Load::Busy), 1);
+        assert_eq!(adaptive.observe(&config, 3, Load::Normal), 2);
+        assert_eq!(adaptive.observe(&config, 3, Load::Normal), 3);
+
+        adaptive.reset();
+        assert_eq!(adaptive.current(), None);
+        // A base outside the bounds is pulled inside them
+        assert_eq!(adaptive.observe(&config, 30, Load::Normal), 10);
+    }
+}
@@ -0,0 +1,515 @@
+//! Alert rules evaluated against each snapshot.
+//!
+//! Rules are read from `config.toml` as `[[alerts]]` tables:
+//!
+//! ```toml
+//! [[alerts]]
+//! name = "Blocked queries"
+//! metric = "blocked_queries"
+//! above = 5
+//! for_secs = 30
+//! ```
+//!
+//! A rule fires once its metric has stayed past the threshold for
+//! `for_secs`, and resolves as soon as it is back within bounds. Time is
+//! taken from snapshot timestamps, so rules behave the same in replay.
+//! Queries running an ignored statement (`[ignore]` in the config) don't
+//! count towards blocked queries or the longest query.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::IgnoreList;
+use crate::db::models::PgSnapshot;
+
+/// Fired alerts kept for the alerts panel; the oldest resolved ones are
+/// dropped first, active ones never.
+const MAX_HISTORY: usize = 200;
+
+/// What a rule watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMetric {
+    /// Backends waiting on a lock held by another backend
+    BlockedQueries,
+    IdleInTransaction,
+    /// Duration of the longest running active query
+    LongestQuerySecs,
+    /// Highest replay lag across standbys
+    ReplicationLagSecs,
+    /// Highest percentage towards XID wraparound across databases
+    WraparoundPct,
+    Connections,
+    CacheHitPct,
+}
+
+impl AlertMetric {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::BlockedQueries => "blocked queries",
+            Self::IdleInTransaction => "idle in transaction",
+            Self::LongestQuerySecs => "longest query",
+            Self::ReplicationLagSecs => "replication lag",
+            Self::WraparoundPct => "wraparound",
+            Self::Connections => "connections",
+            Self::CacheHitPct => "cache hit",
+        }
+    }
+
+    /// Current value, or None when the snapshot has nothing to measure
+    /// (e.g. no standbys for replication lag). Backends running an ignored
+    /// statement are left out.
+    pub fn value(self, snap: &PgSnapshot, ignore: &IgnoreList) -> Option<f64> {
+        let ignored = |pid: i32| {
+            snap.active_queries
+                .iter()
+                .find(|q| q.pid == pid)
+                .and_then(|q| q.query_id)
+                .is_some_and(|id| ignore.is_statement_ignored(id))
+        };
+        match self {
+            Self::BlockedQueries => {
+                let mut pids: Vec<i32> = snap
+                    .blocking_info
+                    .iter()
+                    .map(|b| b.blocked_pid)
+                    .filter(|&pid| !ignored(pid))
+                    .collect();
+                pids.sort_unstable();
+                pids.dedup();
+                Some(pids.len() as f64)
+            }
+            Self::IdleInTransaction => Some(snap.summary.idle_in_transaction_count as f64),
+            Self::LongestQuerySecs => Some(
+                snap.active_queries
+                    .iter()
+                    .filter(|q| q.state.as_deref() == Some("active"))
+                    .filter(|q| !q.query_id.is_some_and(|id| ignore.is_statement_ignored(id)))
+                    .map(|q| q.duration_secs)
+                    .fold(0.0, f64::max),
+            ),
+            Self::ReplicationLagSecs => snap
+                .replication
+                .iter()
+                .filter_map(|r| r.replay_lag_secs)
+                .reduce(f64::max),
+            Self::WraparoundPct => snap
+                .wraparound
+                .iter()
+                .map(|w| w.pct_towards_wraparound)
+                .reduce(f64::max),
+            Self::Connections => Some(snap.summary.total_backends as f64),
+            Self::CacheHitPct => Some(snap.buffer_cache.hit_ratio * 100.0),
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Self::BlockedQueries | Self::IdleInTransaction | Self::Connections => {
+                format!("{value:.0}")
+            }
+            Self::LongestQuerySecs | Self::ReplicationLagSecs => format!("{value:.1}s"),
+            Self::WraparoundPct | Self::CacheHitPct => format!("{value:.1}%"),
+        }
+    }
+}
+
+/// One `[[alerts]]` entry from the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: AlertMetric,
+    /// Fire while the metric is above this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub above: Option<f64>,
+    /// Fire while the metric is below this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub below: Option<f64>,
+    /// How long the condition must hold before the alert fires
+    #[serde(default)]
+    pub for_secs: u64,
+}
+
+impl AlertRule {
+    /// Rules used when the config file has no `alerts` entry.
+    pub fn defaults() -> Vec<Self> {
+        let rule = |name: &str, metric, above, for_secs| Self {
+            name: name.into(),
+            metric,
+            above: Some(above),
+            below: None,
+            for_secs,
+        };
+        vec![
+            rule("Blocked queries", AlertMetric::BlockedQueries, 5.0, 30),
+            rule("Replication lag", AlertMetric::ReplicationLagSecs, 60.0, 60),
+            rule("Wraparound", AlertMetric::WraparoundPct, 50.0, 0),
+        ]
+    }
+
+    fn breached(&self, value: f64) -> bool {
+        self.above.is_some_and(|limit| value > limit) || self.below_limit(value)
+    }
+
+    fn below_limit(&self, value: f64) -> bool {
+        self.below.is_some_and(|limit| value < limit)
+    }
+
+    /// Whether `value` is further past the threshold than `peak`: lower for
+    /// a breach of `below`, higher otherwise.
+    fn worse(&self, value: f64, peak: f64) -> bool {
+        if self.below_limit(value) {
+            value < peak
+        } else {
+            value > peak
+        }
+    }
+
+    /// The threshold in words, e.g. `blocked queries > 5 for 30s`.
+    pub fn condition(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(limit) = self.above {
+            parts.push(format!("> {}", self.metric.format(limit)));
+        }
+        if let Some(limit) = self.below {
+            parts.push(format!("< {}", self.metric.format(limit)));
+        }
+        let mut text = format!("{} {}", self.metric.label(), parts.join(" or "));
+        if self.for_secs > 0 {
+            text.push_str(&format!(" for {}s", self.for_secs));
+        }
+        text
+    }
+}
+
+/// A rule that fired, and when it resolved.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub name: String,
+    pub condition: String,
+    pub fired_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Worst value seen while the alert was active
+    pub peak: String,
+    #[serde(skip)]
+    peak_value: f64,
+}
+
+impl Alert {
+    pub fn is_active(&self) -> bool {
+        self.resolved_at.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleState {
+    /// When the current breach started
+    breach_since: Option<DateTime<Utc>>,
+    firing: bool,
+}
+
+/// Active and resolved alerts for one connection.
+#[derive(Debug, Default)]
+pub struct Alerts {
+    /// Newest first
+    pub history: VecDeque<Alert>,
+    /// Keyed by rule name, so reordering or editing the rule list on a
+    /// config reload doesn't hand one rule's breach to another
+    states: HashMap<String, RuleState>,
+    last_eval: Option<DateTime<Utc>>,
+    /// An alert fired since the alerts panel was last looked at
+    pub unseen: bool,
+}
+
+impl Alerts {
+    /// Check every rule against `snap`, leaving out what `ignore` lists.
+    /// Returns the alerts that just fired.
+    pub fn evaluate(&mut self, rules: &[AlertRule], ignore: &IgnoreList, snap: &PgSnapshot) -> Vec<Alert> {
+        let now = snap.timestamp;
+        // Replay stepped backwards; start over rather than mix timelines
+        if self.last_eval.is_some_and(|last| now < last) {
+            *self = Self::default();
+        }
+        self.last_eval = Some(now);
+        // A rule that was removed can't resolve on its own
+        self.states.retain(|name, _| rules.iter().any(|r| &r.name == name));
+        for alert in self.history.iter_mut().filter(|a| a.is_active()) {
+            if !rules.iter().any(|r| r.name == alert.name) {
+                alert.resolved_at = Some(now);
+            }
+        }
+
+        let mut fired = Vec::new();
+        for rule in rules {
+            let value = rule.metric.value(snap, ignore);
+            let breached = value.is_some_and(|v| rule.breached(v));
+            let state = self.states.entry(rule.name.clone()).or_default();
+            let active = |a: &&mut Alert| a.name == rule.name && a.is_active();
+
+            if !breached {
+                if state.firing {
+                    if let Some(alert) = self.history.iter_mut().find(active) {
+                        alert.resolved_at = Some(now);
+                    }
+                }
+                *state = RuleState::default();
+                continue;
+            }
+
+            let value = value.unwrap_or_default();
+            let since = *state.breach_since.get_or_insert(now);
+            if state.firing {
+                if let Some(alert) = self.history.iter_mut().find(active) {
+                    if rule.worse(value, alert.peak_value) {
+                        alert.peak_value = value;
+                        alert.peak = rule.metric.format(value);
+                    }
+                }
+            } else if (now - since).num_seconds() >= rule.for_secs as i64 {
+                state.firing = true;
+                let alert = Alert {
+                    name: rule.name.clone(),
+                    condition: rule.condition(),
+                    fired_at: now,
+                    resolved_at: None,
+                    peak: rule.metric.format(value),
+                    peak_value: value,
+                };
+                fired.push(alert.clone());
+                self.history.push_front(alert);
+                self.unseen = true;
+            }
+        }
+        self.trim_history();
+        fired
+    }
+
+    /// Drop the oldest resolved alerts past `MAX_HISTORY`.
+    fn trim_history(&mut self) {
+        let mut excess = self.history.len().saturating_sub(MAX_HISTORY);
+        let mut i = self.history.len();
+        while excess > 0 && i > 0 {
+            i -= 1;
+            if !self.history[i].is_active() {
+                self.history.remove(i);
+                excess -= 1;
+            }
+        }
+    }
+
+    /// Show alerts evaluated elsewhere: `history` as it now stands, with
+    /// the alerts in `fired` new since the last.
+    pub fn show(&mut self, history: VecDeque<Alert>, fired: &[Alert]) {
+        self.history = history;
+        self.unseen |= !fired.is_empty();
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.history.iter().filter(|a| a.is_active()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActiveQuery, ActivitySummary, BlockingInfo, BufferCacheStats};
+    use chrono::TimeZone;
+
+    fn snapshot(secs: u32, blocked: &[i32]) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, secs).unwrap(),
+            blocking_info: blocked
+                .iter()
+                .map(|&pid| BlockingInfo {
+                    blocked_pid: pid,
+                    blocked_user: None,
+                    blocked_query: None,
+                    blocked_duration_secs: 1.0,
+                    blocker_pid: 1,
+                    blocker_user: None,
+                    blocker_query: None,
+                    blocker_state: None,
+                })
+                .collect(),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn blocked_rule() -> AlertRule {
+        AlertRule {
+            name: "Blocked".into(),
+            metric: AlertMetric::BlockedQueries,
+            above: Some(1.0),
+            below: None,
+            for_secs: 10,
+        }
+    }
+
+    #[test]
+    fn fires_after_holding_for_the_duration_then_resolves() {
+        let rules = [blocked_rule()];
+        let mut alerts = Alerts::default();
+
+        assert!(alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(0, &[2, 3])).is_empty());
+        assert!(alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(5, &[2, 3, 4])).is_empty());
+        let fired = alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(10, &[2, 3])).len();
+        assert_eq!(fired, 1);
+        assert_eq!(alerts.active_count(), 1);
+        assert_eq!(alerts.history[0].peak, "2");
+        assert!(alerts.unseen);
+
+        alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(12, &[2, 3, 4, 5]));
+        assert_eq!(alerts.history[0].peak, "4");
+
+        alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(14, &[]));
+        assert_eq!(alerts.active_count(), 0);
+        assert_eq!(alerts.history.len(), 1);
+        assert!(alerts.history[0].resolved_at.is_some());
+    }
+
+    #[test]
+    fn a_dip_restarts_the_wait() {
+        let rules = [blocked_rule()];
+        let mut alerts = Alerts::default();
+        alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(0, &[2, 3]));
+        alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(8, &[2]));
+        assert!(alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(12, &[2, 3])).is_empty());
+        assert_eq!(alerts.evaluate(&rules, &IgnoreList::default(), &snapshot(22, &[2, 3])).len(), 1);
+    }
+
+    #[test]
+    fn ignored_statements_do_not_count() {
+        let rules = [AlertRule {
+            for_secs: 0,
+            ..blocked_rule()
+        }];
+        let mut snap = snapshot(0, &[2, 3]);
+        snap.active_queries = [(2, 77), (3, 88)]
+            .into_iter()
+            .map(|(pid, query_id)| ActiveQuery {
+                pid,
+                state: Some("active".into()),
+                wait_event_type: Some("Lock".into()),
+                duration_secs: 600.0,
+                query_id: Some(query_id),
+                ..Default::default()
+            })
+            .collect();
+        let ignore = IgnoreList {
+            queryids: vec![77],
+            tables: vec![],
+        };
+        assert_eq!(AlertMetric::BlockedQueries.value(&snap, &ignore), Some(1.0));
+        assert!(Alerts::default().evaluate(&rules, &ignore, &snap).is_empty());
+        assert_eq!(Alerts::default().evaluate(&rules, &IgnoreList::default(), &snap).len(), 1);
+
+        let everything = IgnoreList {
+            queryids: vec![77, 88],
+            tables: vec![],
+        };
+        assert_eq!(AlertMetric::LongestQuerySecs.value(&snap, &everything), Some(0.0));
+        assert_eq!(AlertMetric::LongestQuerySecs.value(&snap, &ignore), Some(600.0));
+    }
+
+    #[test]
+    fn below_rules_track_the_lowest_value() {
+        let rules = [AlertRule {
+            name: "Cache".into(),
+            metric: AlertMetric::CacheHitPct,
+            above: None,
+            below: Some(95.0),
+            for_secs: 0,
+        }];
+        let at = |secs, ratio| {
+            let mut snap = snapshot(secs, &[]);
+            snap.buffer_cache.hit_ratio = ratio;
+            snap
+        };
+        let mut alerts = Alerts::default();
+        let ignore = IgnoreList::default();
+        assert_eq!(alerts.evaluate(&rules, &ignore, &at(0, 0.90)).len(), 1);
+        alerts.evaluate(&rules, &ignore, &at(5, 0.80));
+        alerts.evaluate(&rules, &ignore, &at(10, 0.85));
+        assert_eq!(alerts.history[0].peak, "80.0%");
+
+        // With both limits the peak follows the side that was breached
+        let both = [AlertRule {
+            above: Some(99.5),
+            ..rules[0].clone()
+        }];
+        let mut alerts = Alerts::default();
+        alerts.evaluate(&both, &ignore, &at(0, 0.90));
+        alerts.evaluate(&both, &ignore, &at(5, 0.70));
+        assert_eq!(alerts.history[0].peak, "70.0%");
+    }
+
+    #[test]
+    fn reordering_rules_keeps_each_rules_state() {
+        let cache = AlertRule {
+            name: "Cache".into(),
+            metric: AlertMetric::CacheHitPct,
+            above: None,
+            below: Some(95.0),
+            for_secs: 0,
+        };
+        let mut alerts = Alerts::default();
+        let ignore = IgnoreList::default();
+        alerts.evaluate(&[blocked_rule(), cache.clone()], &ignore, &snapshot(0, &[2, 3]));
+        // Reloaded config with the rules swapped: the blocked breach carries on
+        let fired = alerts.evaluate(&[cache.clone(), blocked_rule()], &ignore, &snapshot(10, &[2, 3]));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].name, "Blocked");
+
+        // Dropping the rule resolves its alert
+        alerts.evaluate(&[cache], &ignore, &snapshot(12, &[2, 3]));
+        assert_eq!(alerts.active_count(), 0);
+    }
+
+    #[test]
+    fn trimming_history_keeps_active_alerts() {
+        let rules = [AlertRule {
+            for_secs: 0,
+            ..blocked_rule()
+        }];
+        let mut alerts = Alerts::default();
+        let ignore = IgnoreList::default();
+        alerts.evaluate(&rules, &ignore, &snapshot(0, &[2, 3]));
+        let resolved = Alert {
+            name: "Earlier".into(),
+            resolved_at: Some(alerts.history[0].fired_at),
+            ..alerts.history[0].clone()
+        };
+        alerts.history.extend(std::iter::repeat_n(resolved, MAX_HISTORY));
+        // The active alert is now the oldest; resolved ones go instead
+        alerts.history.rotate_left(1);
+        alerts.evaluate(&rules, &ignore, &snapshot(3, &[2, 3]));
+        assert_eq!(alerts.history.len(), MAX_HISTORY);
+        assert_eq!(alerts.active_count(), 1);
+        assert_eq!(alerts.history.back().unwrap().name, "Blocked");
+    }
+
+    #[test]
+    fn rules_parse_from_toml() {
+        let toml = r#"
+            name = "Cache"
+            metric = "cache_hit_pct"
+            below = 95
+        "#;
+        let rule: AlertRule = toml::from_str(toml).unwrap();
+        assert_eq!(rule.metric, AlertMetric::CacheHitPct);
+        assert_eq!(rule.for_secs, 0);
+        assert_eq!(rule.condition(), "cache hit < 95.0%");
+        assert!(rule.breached(90.0));
+        assert!(!rule.breached(99.0));
+    }
+}
@@ -10,6 +10,42 @@ pub enum AppAction {
     ForceRefresh,
     RefreshBloat,
     SaveConfig,
+    SaveStarred,
+    SaveBaselines,
+    SaveBookmarks,
     RefreshIntervalChanged,
     ResetStatStatements,
+    /// `pg_drop_replication_slot` on this slot
+    DropReplicationSlot(String),
+    /// Write the top graphs to PNG/SVG files
+    ExportGraphs,
+    /// Write the current panel's visible rows to this file; `.svg`/`.png`
+    /// exports the graphs there instead
+    ExportPanel(std::path::PathBuf),
+    /// Write the metrics history behind the graphs to this CSV/JSON file
+    ExportHistory(std::path::PathBuf),
+    /// Run this command on a connection of its own
+    RunMaintenance(crate::maintenance::Maintenance),
+    /// Write generated maintenance SQL to this file
+    WriteScript { path: std::path::PathBuf, sql: String },
+    /// Put another monitored connection on screen (index into the switcher)
+    SwitchConnection(usize),
+    /// Plan this query text for the EXPLAIN overlay
+    Explain(String),
+    /// Try hypothetical indexes for this query text
+    AdviseIndexes(String),
+    /// Read this table's columns, indexes, foreign keys and triggers
+    DescribeTable { schema: String, table: String },
+    /// Read shared buffer contents from `pg_buffercache`
+    ReadBufferCache,
+    /// Read the privileges this role holds
+    ReadRoleGrants(String),
+    /// Read the relations driving this database's XID age
+    ReadWraparoundTables(String),
+    /// Measure this table's bloat exactly with `pgstattuple()`
+    MeasureBloat { schema: String, table: String },
+    /// Move the replay to the first snapshot at or after this point
+    SeekReplay(super::SeekTarget),
+    /// Write the bookmarked replay interval to a recording of its own
+    ExportExcerpt,
 }
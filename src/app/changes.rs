@@ -0,0 +1,375 @@
+//! What changed since the previous snapshot, for the `~` highlight mode.
+//!
+//! Each refresh is compared with the one before it, panel by panel: a
+//! backend that started a new query, a statement or table seen for the
+//! first time, or a counter that suddenly grew much faster than it did over
+//! the previous interval. The marks last until the next snapshot.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::db::models::{ActiveQuery, PgSnapshot, StatStatement, TableStat};
+
+/// How much faster than over the previous interval a counter has to grow
+/// to count as a jump.
+const JUMP_FACTOR: f64 = 3.0;
+
+/// Smallest per-refresh growth that can be a jump, so idle statements and
+/// tables don't flicker on a handful of calls.
+const MIN_JUMP_CALLS: f64 = 100.0;
+const MIN_JUMP_EXEC_MS: f64 = 1000.0;
+const MIN_JUMP_WRITES: f64 = 1000.0;
+const MIN_JUMP_SEQ_SCANS: f64 = 10.0;
+
+/// How a row differs from the previous snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    /// Not there before: a new backend, query, statement or table
+    New,
+    /// A key counter grew much faster than over the previous interval
+    Jumped,
+}
+
+/// A counter's value and how much it grew over the interval before, once
+/// there has been one.
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    value: f64,
+    growth: Option<f64>,
+}
+
+impl Counter {
+    fn new(value: f64) -> Self {
+        Self { value, growth: None }
+    }
+
+    /// Move to `value`; true when the growth is a jump. Nothing is until one
+    /// interval's growth is known to compare with. A counter that went
+    /// backwards was reset and starts over.
+    fn advance(&mut self, value: f64, min_jump: f64) -> bool {
+        let growth = value - self.value;
+        if growth < 0.0 {
+            *self = Self::new(value);
+            return false;
+        }
+        let jumped = self.growth.is_some_and(|prev| growth >= min_jump && growth >= JUMP_FACTOR * prev);
+        *self = Self { value, growth: Some(growth) };
+        jumped
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StatementCounters {
+    calls: Counter,
+    exec_ms: Counter,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TableCounters {
+    writes: Counter,
+    seq_scans: Counter,
+}
+
+/// Rows of the Queries, Statements and Table Stats panels that changed at
+/// the last refresh, and the counters to compare the next one with.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeTracker {
+    seen: bool,
+    query_starts: HashMap<i32, Option<DateTime<Utc>>>,
+    statement_counters: HashMap<i64, StatementCounters>,
+    table_counters: HashMap<String, TableCounters>,
+    queries: HashMap<i32, Change>,
+    statements: HashMap<i64, Change>,
+    tables: HashMap<String, Change>,
+}
+
+fn table_key(t: &TableStat) -> String {
+    format!("{}.{}", t.schemaname, t.relname)
+}
+
+impl ChangeTracker {
+    /// Compare `snap` with the snapshot before it. The first one only sets
+    /// the baseline; everything in it would otherwise be new.
+    pub fn observe(&mut self, snap: &PgSnapshot) {
+        let first = !self.seen;
+        self.seen = true;
+        self.observe_queries(&snap.active_queries, first);
+        self.observe_statements(&snap.stat_statements, first);
+        if snap.relation_stats_read_at.is_none() {
+            self.observe_tables(&snap.table_stats, first);
+        } else {
+            // Carried over unchanged; compare the next read with the last
+            self.tables.clear();
+        }
+    }
+
+    fn observe_queries(&mut self, queries: &[ActiveQuery], first: bool) {
+        self.queries.clear();
+        let mut starts = HashMap::with_capacity(queries.len());
+        for q in queries {
+            let started = match self.query_starts.get(&q.pid) {
+                None => !first,
+                // Same backend, different query
+                Some(prev) => *prev != q.query_start && q.query_start.is_some(),
+            };
+            if started {
+                self.queries.insert(q.pid, Change::New);
+            }
+            starts.insert(q.pid, q.query_start);
+        }
+        self.query_starts = starts;
+    }
+
+    fn observe_statements(&mut self, statements: &[StatStatement], first: bool) {
+        self.statements.clear();
+        let mut counters = HashMap::with_capacity(statements.len());
+        for s in statements {
+            let calls = s.calls as f64;
+            let change = match self.statement_counters.get(&s.queryid).copied() {
+                None => {
+                    counters.insert(
+                        s.queryid,
+                        StatementCounters {
+                            calls: Counter::new(calls),
+                            exec_ms: Counter::new(s.total_exec_time),
+                        },
+                    );
+                    (!first).then_some(Change::New)
+                }
+                Some(mut c) => {
+                    let calls_jumped = c.calls.advance(calls, MIN_JUMP_CALLS);
+                    let time_jumped = c.exec_ms.advance(s.total_exec_time, MIN_JUMP_EXEC_MS);
+                    counters.insert(s.queryid, c);
+                    (calls_jumped || time_jumped).then_some(Change::Jumped)
+                }
+            };
+            if let Some(change) = change {
+                self.statements.insert(s.queryid, change);
+            }
+        }
+        self.statement_counters = counters;
+    }
+
+    fn observe_tables(&mut self, tables: &[TableStat], first: bool) {
+        self.tables.clear();
+        let mut counters = HashMap::with_capacity(tables.len());
+        for t in tables {
+            let key = table_key(t);
+            let writes = (t.n_tup_ins + t.n_tup_upd + t.n_tup_del) as f64;
+            let seq_scans = t.seq_scan as f64;
+            let change = match self.table_counters.get(&key).copied() {
+                None => {
+                    counters.insert(
+                        key.clone(),
+                        TableCounters {
+                            writes: Counter::new(writes),
+                            seq_scans: Counter::new(seq_scans),
+                        },
+                    );
+                    (!first).then_some(Change::New)
+                }
+                Some(mut c) => {
+                    let writes_jumped = c.writes.advance(writes, MIN_JUMP_WRITES);
+                    let scans_jumped = c.seq_scans.advance(seq_scans, MIN_JUMP_SEQ_SCANS);
+                    counters.insert(key.clone(), c);
+                    (writes_jumped || scans_jumped).then_some(Change::Jumped)
+                }
+            };
+            if let Some(change) = change {
+                self.tables.insert(key, change);
+            }
+        }
+        self.table_counters = counters;
+    }
+
+    pub fn query(&self, pid: i32) -> Option<Change> {
+        self.queries.get(&pid).copied()
+    }
+
+    pub fn statement(&self, queryid: i64) -> Option<Change> {
+        self.statements.get(&queryid).copied()
+    }
+
+    pub fn table(&self, t: &TableStat) -> Option<Change> {
+        self.tables.get(&table_key(t)).copied()
+    }
+
+    /// Rows marked in any panel.
+    pub fn count(&self) -> usize {
+        self.queries.len() + self.statements.len() + self.tables.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActivitySummary, BufferCacheStats};
+    use chrono::TimeZone;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap() + chrono::Duration::seconds(secs)
+    }
+
+    fn query(pid: i32, started: Option<i64>) -> ActiveQuery {
+        ActiveQuery {
+            pid,
+            usename: Some("app".into()),
+            datname: Some("shop".into()),
+            state: Some("active".into()),
+            query_start: started.map(at),
+            duration_secs: 1.0,
+            query: Some("SELECT 1".into()),
+            ..Default::default()
+        }
+    }
+
+    fn statement(queryid: i64, calls: i64) -> StatStatement {
+        StatStatement {
+            queryid,
+            query: format!("SELECT {queryid}"),
+            calls,
+            total_exec_time: 0.0,
+            min_exec_time: 0.0,
+            mean_exec_time: 0.0,
+            max_exec_time: 0.0,
+            stddev_exec_time: 0.0,
+            rows: 0,
+            shared_blks_hit: 0,
+            shared_blks_read: 0,
+            shared_blks_dirtied: 0,
+            shared_blks_written: 0,
+            local_blks_hit: 0,
+            local_blks_read: 0,
+            local_blks_dirtied: 0,
+            local_blks_written: 0,
+            temp_blks_read: 0,
+            temp_blks_written: 0,
+            blk_read_time: 0.0,
+            blk_write_time: 0.0,
+            hit_ratio: 1.0,
+            windowed: false,
+        }
+    }
+
+    fn table(relname: &str, seq_scan: i64) -> TableStat {
+        TableStat {
+            schemaname: "public".into(),
+            relname: relname.into(),
+            total_size_bytes: 0,
+            table_size_bytes: 0,
+            indexes_size_bytes: 0,
+            seq_scan,
+            seq_tup_read: 0,
+            idx_scan: 0,
+            idx_tup_fetch: 0,
+            n_live_tup: 0,
+            n_dead_tup: 0,
+            dead_ratio: 0.0,
+            n_tup_ins: 0,
+            n_tup_upd: 0,
+            n_tup_del: 0,
+            n_tup_hot_upd: 0,
+            last_vacuum: None,
+            last_autovacuum: None,
+            last_analyze: None,
+            last_autoanalyze: None,
+            vacuum_count: 0,
+            autovacuum_count: 0,
+            bloat_bytes: None,
+            bloat_pct: None,
+            bloat_source: None,
+        }
+    }
+
+    fn snapshot(queries: Vec<ActiveQuery>, statements: Vec<StatStatement>, tables: Vec<TableStat>) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: at(0),
+            active_queries: queries,
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            table_stats: tables,
+            stat_statements: statements,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn first_snapshot_is_only_the_baseline() {
+        let mut tracker = ChangeTracker::default();
+        tracker.observe(&snapshot(
+            vec![query(1, Some(0))],
+            vec![statement(7, 10)],
+            vec![table("orders", 0)],
+        ));
+        assert_eq!(tracker.count(), 0);
+
+        tracker.observe(&snapshot(
+            vec![query(1, Some(0)), query(2, Some(1))],
+            vec![statement(7, 10), statement(8, 1)],
+            vec![table("orders", 0), table("items", 0)],
+        ));
+        assert_eq!(tracker.query(1), None);
+        assert_eq!(tracker.query(2), Some(Change::New));
+        assert_eq!(tracker.statement(7), None);
+        assert_eq!(tracker.statement(8), Some(Change::New));
+        assert_eq!(tracker.table(&table("items", 0)), Some(Change::New));
+        assert_eq!(tracker.count(), 3);
+    }
+
+    #[test]
+    fn a_new_query_start_on_the_same_backend_is_new() {
+        let mut tracker = ChangeTracker::default();
+        tracker.observe(&snapshot(vec![query(1, Some(0))], vec![], vec![]));
+        tracker.observe(&snapshot(vec![query(1, Some(0))], vec![], vec![]));
+        assert_eq!(tracker.query(1), None);
+
+        tracker.observe(&snapshot(vec![query(1, Some(5))], vec![], vec![]));
+        assert_eq!(tracker.query(1), Some(Change::New));
+
+        // No start time to compare (e.g. no permission to see it)
+        tracker.observe(&snapshot(vec![query(1, None)], vec![], vec![]));
+        assert_eq!(tracker.query(1), None);
+    }
+
+    #[test]
+    fn counters_jump_only_against_a_known_interval() {
+        let mut tracker = ChangeTracker::default();
+        let mut observe = |calls: i64| {
+            tracker.observe(&snapshot(vec![], vec![statement(7, calls)], vec![]));
+            tracker.statement(7)
+        };
+        observe(0);
+        // The first interval has nothing to compare with
+        assert_eq!(observe(5000), None);
+        // Three times the previous growth, and at least MIN_JUMP_CALLS
+        assert_eq!(observe(5000 + 14_999), None);
+        assert_eq!(observe(19_999 + 45_000), Some(Change::Jumped));
+        // Fast from almost nothing, but too few calls to matter
+        assert_eq!(observe(64_999), None);
+        assert_eq!(observe(64_999 + 99), None);
+    }
+
+    #[test]
+    fn reset_counters_start_over() {
+        let mut tracker = ChangeTracker::default();
+        let mut observe = |seq_scan: i64| {
+            let orders = table("orders", seq_scan);
+            tracker.observe(&snapshot(vec![], vec![], vec![orders.clone()]));
+            tracker.table(&orders)
+        };
+        observe(100);
+        assert_eq!(observe(102), None);
+        // pg_stat_reset(): went backwards, not a jump
+        assert_eq!(observe(0), None);
+        // The first interval after the reset isn't compared with the old one
+        assert_eq!(observe(50), None);
+        assert_eq!(observe(200), Some(Change::Jumped));
+    }
+}
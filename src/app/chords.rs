@@ -0,0 +1,74 @@
+//! Two-key chords.
+//!
+//! A prefix key pressed in normal mode arms a chord and brings up a hint
+//! listing what can follow; the next key picks a continuation or drops the
+//! chord. Continuations come from the panel registry where they can, so a
+//! new panel gets its `g` binding from its descriptor.
+
+use super::panels::BottomPanel;
+use super::registry::PANELS;
+
+/// What a completed chord does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordAction {
+    Panel(BottomPanel),
+}
+
+/// One key that may follow a prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChordBinding {
+    pub key: char,
+    pub label: &'static str,
+    pub action: ChordAction,
+}
+
+/// Prefix keys and what the hint calls them.
+const PREFIXES: [(char, &str); 1] = [('g', "Go to")];
+
+/// Title for the hint of `prefix`, or None if it starts no chord.
+pub fn prefix_title(prefix: char) -> Option<&'static str> {
+    PREFIXES.iter().find(|(p, _)| *p == prefix).map(|(_, title)| *title)
+}
+
+/// Keys that may follow `prefix`, in hint order.
+pub fn continuations(prefix: char) -> Vec<ChordBinding> {
+    match prefix {
+        'g' => PANELS
+            .iter()
+            .map(|d| ChordBinding {
+                key: d.goto,
+                label: d.title,
+                action: ChordAction::Panel(d.panel),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The binding for `key` after `prefix`.
+pub fn resolve(prefix: char, key: char) -> Option<ChordBinding> {
+    continuations(prefix).into_iter().find(|b| b.key == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_prefix_has_continuations() {
+        for (prefix, _) in PREFIXES {
+            assert!(!continuations(prefix).is_empty());
+        }
+        assert!(continuations('z').is_empty());
+        assert_eq!(prefix_title('z'), None);
+    }
+
+    #[test]
+    fn go_to_resolves_panel_letters() {
+        assert_eq!(
+            resolve('g', 't').map(|b| b.action),
+            Some(ChordAction::Panel(BottomPanel::TableStats))
+        );
+        assert_eq!(resolve('g', 'Z'), None);
+    }
+}
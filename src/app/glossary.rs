@@ -0,0 +1,959 @@
+//! Metric glossary.
+//!
+//! One entry per column or metric a panel shows: what it means, where
+//! Postgres reports it, and what a healthy value looks like. Entry names
+//! match the sort column labels so the glossary can open on the column the
+//! table is currently sorted by.
+
+use super::panels::BottomPanel;
+
+/// Explanation of a single column or metric.
+pub struct MetricDoc {
+    pub name: &'static str,
+    /// View or function the value is read from
+    pub source: &'static str,
+    pub meaning: &'static str,
+    pub healthy: &'static str,
+}
+
+const fn doc(
+    name: &'static str,
+    source: &'static str,
+    meaning: &'static str,
+    healthy: &'static str,
+) -> MetricDoc {
+    MetricDoc {
+        name,
+        source,
+        meaning,
+        healthy,
+    }
+}
+
+static QUERIES: [MetricDoc; 8] = [
+    doc(
+        "PID",
+        "pg_stat_activity.pid",
+        "Process ID of the backend running the query; use it with C/K to cancel or terminate.",
+        "-",
+    ),
+    doc(
+        "User",
+        "pg_stat_activity.usename",
+        "Role the session is logged in as.",
+        "-",
+    ),
+    doc(
+        "Duration",
+        "now() - pg_stat_activity.query_start",
+        "How long the current (or last, when idle) statement has been running.",
+        "Mostly under the warn threshold; long-running statements hold snapshots and locks.",
+    ),
+    doc(
+        "State",
+        "pg_stat_activity.state",
+        "active runs a statement; idle in transaction holds a transaction open between statements.",
+        "idle in transaction should be brief; long ones block vacuum and hold locks.",
+    ),
+    doc(
+        "Wait",
+        "pg_stat_activity.wait_event_type / wait_event",
+        "What the backend is waiting on, if anything (Lock, IO, LWLock, Client, ...).",
+        "Empty or brief IO waits; sustained Lock waits point at blocking chains.",
+    ),
+    doc(
+        "Database",
+        "pg_stat_activity.datname",
+        "Database the session is connected to.",
+        "-",
+    ),
+    doc(
+        "Blocker",
+        "blocking info: pg_blocking_pids() of each lock-waiting backend",
+        "PID of the backend holding the lock this query waits for (+N when several do); b in the inspect overlay jumps to it.",
+        "Empty; a PID that keeps showing up is the head of a blocking chain.",
+    ),
+    doc(
+        "spill",
+        "pg_stat_statements.temp_blks_written delta, matched on pg_stat_activity.query_id (PG14+)",
+        "The running statement's queryid wrote temp files since the last refresh: sorts or hashes outgrew work_mem.",
+        "Rare; frequent spills suggest raising work_mem for that workload or adding an index.",
+    ),
+];
+
+static BLOCKING: [MetricDoc; 3] = [
+    doc(
+        "Blocker",
+        "pg_blocking_pids()",
+        "Backend holding the lock that the blocked backend is waiting for.",
+        "No rows; a blocker that is idle in transaction is usually the root cause.",
+    ),
+    doc(
+        "Blocked",
+        "pg_stat_activity.pid",
+        "Backend waiting on a lock held by the blocker.",
+        "-",
+    ),
+    doc(
+        "Duration",
+        "now() - pg_stat_activity.query_start",
+        "How long the blocked statement has been waiting.",
+        "Seconds at most; compare against lock_timeout.",
+    ),
+];
+
+static LOCKS: [MetricDoc; 7] = [
+    doc(
+        "Status",
+        "pg_locks.granted",
+        "Whether the lock is held or the backend is still waiting for it.",
+        "Waiting rows should clear within seconds.",
+    ),
+    doc(
+        "Mode",
+        "pg_locks.mode",
+        "Lock strength, from AccessShareLock (plain SELECT) to AccessExclusiveLock (DDL, VACUUM FULL).",
+        "AccessExclusiveLock held for long blocks every reader of the table.",
+    ),
+    doc(
+        "Relation",
+        "pg_locks.relation",
+        "Table or index the lock is on; empty for transaction and tuple locks. Relations in other databases show as an OID.",
+        "-",
+    ),
+    doc(
+        "Holder",
+        "pg_blocking_pids()",
+        "Backend holding the lock: the row's own backend once granted, otherwise the ones it waits for.",
+        "-",
+    ),
+    doc(
+        "Waiter",
+        "pg_locks.granted = false",
+        "Backends waiting for this lock to be released.",
+        "None; a holder that is idle in transaction keeps its waiters stuck.",
+    ),
+    doc(
+        "Duration",
+        "now() - xact_start / query_start",
+        "Age of the holding transaction for held locks, time spent waiting for waiting ones.",
+        "Long-held strong locks and long waits are both worth a look.",
+    ),
+    doc(
+        "PID",
+        "pg_locks.pid",
+        "Backend the row belongs to.",
+        "-",
+    ),
+];
+
+static SLOTS: [MetricDoc; 6] = [
+    doc(
+        "Slot",
+        "pg_replication_slots.slot_name",
+        "Replication slot, physical (standbys) or logical (subscriptions, CDC tools).",
+        "-",
+    ),
+    doc(
+        "Active",
+        "pg_replication_slots.active",
+        "Whether a consumer is connected and reading from the slot.",
+        "Active, unless the consumer is known to be down for maintenance.",
+    ),
+    doc(
+        "WAL Retained",
+        "pg_current_wal_lsn() - restart_lsn",
+        "WAL the server must keep on disk until the slot's consumer catches up.",
+        "Small and steady; an inactive slot retaining more and more WAL will eventually fill the disk.",
+    ),
+    doc(
+        "Spill Bytes",
+        "pg_stat_replication_slots.spill_bytes (PG 14+)",
+        "Decoded data written to disk because a transaction exceeded logical_decoding_work_mem.",
+        "Low; steady growth suggests raising logical_decoding_work_mem.",
+    ),
+    doc(
+        "Spill Txns",
+        "pg_stat_replication_slots.spill_txns (PG 14+)",
+        "Transactions that spilled to disk while being decoded.",
+        "-",
+    ),
+    doc(
+        "Restart LSN",
+        "pg_replication_slots.restart_lsn",
+        "Oldest WAL position the slot still needs.",
+        "-",
+    ),
+];
+
+static LOGICAL_REPLICATION: [MetricDoc; 7] = [
+    doc(
+        "Worker",
+        "pg_stat_subscription.pid",
+        "Apply worker of the subscription; \"down\" when the subscription is enabled but no worker runs.",
+        "A PID for every enabled subscription; a worker that keeps dying shows up in Apply Err and the server log.",
+    ),
+    doc(
+        "Apply Lag",
+        "now() - pg_stat_subscription.latest_end_time",
+        "Time since the worker last reported applied WAL back to the publisher.",
+        "A few seconds; keepalives reset it even when nothing changes.",
+    ),
+    doc(
+        "Pending",
+        "received_lsn - latest_end_lsn",
+        "WAL received from the publisher but not yet reported as applied.",
+        "Small; steady growth means apply can't keep up.",
+    ),
+    doc(
+        "Last Msg",
+        "pg_stat_subscription.last_msg_receipt_time",
+        "Time since the last message arrived from the publisher's WAL sender.",
+        "Below wal_sender_timeout; longer means the connection is stuck.",
+    ),
+    doc(
+        "Apply Err",
+        "pg_stat_subscription_stats.apply_error_count (PG 15+)",
+        "Errors while applying changes. Apply restarts from the failed transaction after each one.",
+        "0; a rising count means the same change keeps failing.",
+    ),
+    doc(
+        "Sync Err",
+        "pg_stat_subscription_stats.sync_error_count (PG 15+)",
+        "Errors during the initial copy of a table.",
+        "0.",
+    ),
+    doc(
+        "Conflicts",
+        "pg_stat_subscription_stats.confl_* (PG 18+)",
+        "Conflicts met while applying: existing rows on insert, missing or locally changed rows on update and delete.",
+        "0 when the subscriber isn't written to locally.",
+    ),
+];
+
+static CONNECTIONS: [MetricDoc; 8] = [
+    doc(
+        "User",
+        "pg_stat_activity.usename",
+        "Role the backends logged in as; each row is one user, database and application.",
+        "-",
+    ),
+    doc(
+        "Application",
+        "pg_stat_activity.application_name",
+        "Name the client set when connecting; empty when it didn't.",
+        "-",
+    ),
+    doc(
+        "Total",
+        "count(*) from pg_stat_activity",
+        "Client backends in the group, whatever their state.",
+        "Well under max_connections; a group that keeps growing may be leaking connections.",
+    ),
+    doc(
+        "Active",
+        "state = 'active'",
+        "Backends running a statement right now.",
+        "Around the number of CPU cores or fewer.",
+    ),
+    doc(
+        "Idle",
+        "state = 'idle'",
+        "Connected backends waiting for the client's next statement.",
+        "A pool holds some open; hundreds suggest an oversized pool.",
+    ),
+    doc(
+        "Idle in Txn",
+        "state LIKE 'idle in transaction%'",
+        "Backends holding a transaction open between statements, including aborted ones.",
+        "0; they hold locks and stop vacuum from cleaning up.",
+    ),
+    doc(
+        "Waiting",
+        "wait_event_type = 'Lock'",
+        "Backends stuck waiting for a lock another backend holds.",
+        "0.",
+    ),
+    doc(
+        "Longest",
+        "max(now() - COALESCE(xact_start, query_start)), non-idle backends",
+        "Age of the oldest open transaction or running query in the group.",
+        "Seconds for OLTP traffic; long values point at the group to look into.",
+    ),
+];
+
+static SESSIONS: [MetricDoc; 7] = [
+    doc(
+        "Observed",
+        "time since the PID was first seen in pg_stat_activity",
+        "How long the session has been watched. Counts start at zero when pg_glimpse starts.",
+        "-",
+    ),
+    doc(
+        "Active",
+        "sampled pg_stat_activity.state = 'active'",
+        "Time between snapshots the backend spent running a query, counted from query_start \
+         when the query began after the previous snapshot.",
+        "Spread across sessions; one session holding most of it is the one doing the work.",
+    ),
+    doc(
+        "Active %",
+        "Active / Observed",
+        "Share of the watched time spent running queries.",
+        "Low for pooled web sessions; near 100% for batch jobs and stuck queries.",
+    ),
+    doc(
+        "Idle in Txn",
+        "sampled state LIKE 'idle in transaction%'",
+        "Time seen holding a transaction open between statements.",
+        "Near 0; it holds locks and stops vacuum from cleaning up.",
+    ),
+    doc(
+        "Xacts",
+        "changes of pg_stat_activity.xact_start",
+        "Transactions seen starting. Ones that begin and end between two snapshots are missed, \
+         so this is a lower bound.",
+        "-",
+    ),
+    doc(
+        "Queries",
+        "changes of pg_stat_activity.query_start",
+        "Queries seen starting, a lower bound like Xacts.",
+        "-",
+    ),
+    doc(
+        "Changes",
+        "changes of pg_stat_activity.state",
+        "State transitions seen, e.g. idle to active.",
+        "-",
+    ),
+];
+
+static WAIT_EVENTS: [MetricDoc; 2] = [
+    doc(
+        "Wait Event Type",
+        "pg_stat_activity.wait_event_type",
+        "Class of wait: Lock (heavyweight locks), LWLock (internal), IO, Client, IPC, Timeout.",
+        "Few waiters; IO is normal under load, growing Lock counts are not.",
+    ),
+    doc(
+        "Count",
+        "count(*) over pg_stat_activity",
+        "Number of backends currently waiting on this event type.",
+        "Small relative to active connections.",
+    ),
+];
+
+static TABLE_STATS: [MetricDoc; 8] = [
+    doc(
+        "Name",
+        "pg_stat_user_tables.schemaname / relname",
+        "Schema-qualified table name.",
+        "-",
+    ),
+    doc(
+        "Size",
+        "pg_total_relation_size()",
+        "Heap plus indexes and TOAST.",
+        "-",
+    ),
+    doc(
+        "Seq Scan",
+        "pg_stat_user_tables.seq_scan",
+        "Sequential scans since the last stats reset.",
+        "High counts on large tables suggest a missing index.",
+    ),
+    doc(
+        "Idx Scan",
+        "pg_stat_user_tables.idx_scan",
+        "Index scans since the last stats reset.",
+        "Dominates seq_scan on large, frequently queried tables.",
+    ),
+    doc(
+        "Dead Tuples",
+        "pg_stat_user_tables.n_dead_tup",
+        "Row versions deleted or updated but not yet vacuumed.",
+        "Falls back after each autovacuum run.",
+    ),
+    doc(
+        "Dead %",
+        "n_dead_tup / (n_live_tup + n_dead_tup)",
+        "Share of row versions in the table that are dead.",
+        "Below ~20% (the default autovacuum_vacuum_scale_factor).",
+    ),
+    doc(
+        "Bloat",
+        "estimate from pg_stats (refresh with b)",
+        "Estimated wasted space in the heap.",
+        "Under ~30%; higher needs VACUUM FULL or pg_repack to reclaim.",
+    ),
+    doc(
+        "Last Vacuum",
+        "pg_stat_user_tables.last_vacuum / last_autovacuum",
+        "Most recent manual or automatic vacuum.",
+        "Recent on tables with steady writes.",
+    ),
+];
+
+static REPLICATION: [MetricDoc; 5] = [
+    doc(
+        "State",
+        "pg_stat_replication.state",
+        "WAL sender state: startup, catchup, streaming, backup.",
+        "streaming.",
+    ),
+    doc(
+        "Write Lag",
+        "pg_stat_replication.write_lag",
+        "Time until the standby wrote recent WAL (not yet flushed).",
+        "Milliseconds.",
+    ),
+    doc(
+        "Flush Lag",
+        "pg_stat_replication.flush_lag",
+        "Time until the standby flushed recent WAL to disk.",
+        "Milliseconds; matters for synchronous_commit = on.",
+    ),
+    doc(
+        "Replay Lag",
+        "pg_stat_replication.replay_lag",
+        "Time until the standby applied recent WAL and made it visible to queries.",
+        "Under a few seconds; growing lag means the standby falls behind.",
+    ),
+    doc(
+        "WAL Retained",
+        "pg_current_wal_lsn() - pg_replication_slots.restart_lsn",
+        "WAL the primary keeps on disk for this slot.",
+        "Small and stable; an inactive slot retains WAL until the disk fills.",
+    ),
+];
+
+static VACUUM_PROGRESS: [MetricDoc; 3] = [
+    doc(
+        "Phase",
+        "pg_stat_progress_vacuum.phase",
+        "Current step: scanning heap, vacuuming indexes, vacuuming heap, truncating, ...",
+        "Repeated 'vacuuming indexes' passes mean maintenance_work_mem is too small.",
+    ),
+    doc(
+        "Progress",
+        "heap_blks_scanned / heap_blks_total",
+        "Share of the heap scanned so far.",
+        "-",
+    ),
+    doc(
+        "Dead Tuples",
+        "pg_stat_progress_vacuum.num_dead_tuples",
+        "Dead tuples collected in this pass.",
+        "-",
+    ),
+];
+
+static WRAPAROUND: [MetricDoc; 3] = [
+    doc(
+        "XID Age",
+        "age(pg_database.datfrozenxid)",
+        "Transactions since the oldest unfrozen XID in the database.",
+        "Well below autovacuum_freeze_max_age (200M by default).",
+    ),
+    doc(
+        "Remaining",
+        "2^31 - XID Age",
+        "Transactions left before Postgres refuses writes to prevent wraparound.",
+        "Hundreds of millions or more.",
+    ),
+    doc(
+        "% Used",
+        "XID Age / 2^31",
+        "How much of the XID space is consumed.",
+        "Under 50%; over 75% needs an urgent VACUUM FREEZE.",
+    ),
+];
+
+static INDEXES: [MetricDoc; 6] = [
+    doc(
+        "Name",
+        "pg_stat_user_indexes.indexrelname",
+        "Index name, listed with its table.",
+        "-",
+    ),
+    doc(
+        "Size",
+        "pg_relation_size(indexrelid)",
+        "On-disk size of the index.",
+        "-",
+    ),
+    doc(
+        "Scans",
+        "pg_stat_user_indexes.idx_scan",
+        "Index scans since the last stats reset.",
+        "Zero on a large index (on primary and standbys) means it is a removal candidate.",
+    ),
+    doc(
+        "Tup Read",
+        "pg_stat_user_indexes.idx_tup_read",
+        "Index entries returned by scans.",
+        "-",
+    ),
+    doc(
+        "Tup Fetch",
+        "pg_stat_user_indexes.idx_tup_fetch",
+        "Live heap rows fetched through the index.",
+        "Far below Tup Read means many dead or invisible rows.",
+    ),
+    doc(
+        "Bloat",
+        "estimate from pg_stats (refresh with b)",
+        "Estimated wasted space in the index.",
+        "Under ~30%; REINDEX CONCURRENTLY reclaims it.",
+    ),
+];
+
+static STATEMENTS: [MetricDoc; 12] = [
+    doc(
+        "Calls",
+        "pg_stat_statements.calls",
+        "Times the statement was executed.",
+        "-",
+    ),
+    doc(
+        "Total Time",
+        "pg_stat_statements.total_exec_time",
+        "Cumulative execution time; the statements costing the server the most.",
+        "No single statement dominating unexpectedly.",
+    ),
+    doc(
+        "Mean Time",
+        "pg_stat_statements.mean_exec_time",
+        "Average execution time per call.",
+        "Stable over time; jumps point at plan changes.",
+    ),
+    doc(
+        "Max Time",
+        "pg_stat_statements.max_exec_time",
+        "Slowest single execution.",
+        "Close to the mean; outliers suggest lock waits or cold cache.",
+    ),
+    doc(
+        "P95",
+        "pg_stat_monitor.resp_calls histogram",
+        "Time under which 95% of calls finished, interpolated within its histogram bucket (pg_stat_monitor only).",
+        "Close to the mean; far above it means a slow tail worth a look in the inspect overlay.",
+    ),
+    doc(
+        "Stddev",
+        "pg_stat_statements.stddev_exec_time",
+        "Spread of execution times.",
+        "Small relative to the mean.",
+    ),
+    doc(
+        "Rows",
+        "pg_stat_statements.rows",
+        "Rows returned or affected, summed over all calls.",
+        "-",
+    ),
+    doc(
+        "Hit %",
+        "shared_blks_hit / (shared_blks_hit + shared_blks_read)",
+        "Share of block requests served from shared buffers.",
+        "Above 99% for OLTP workloads.",
+    ),
+    doc(
+        "Reads",
+        "pg_stat_statements.shared_blks_read",
+        "Blocks read from disk or the OS cache.",
+        "Low for frequently executed statements.",
+    ),
+    doc(
+        "I/O Time",
+        "blk_read_time + blk_write_time",
+        "Time spent on block I/O (needs track_io_timing).",
+        "Small share of Total Time.",
+    ),
+    doc(
+        "Temp",
+        "pg_stat_statements.temp_blks_written",
+        "Blocks spilled to temporary files (sorts, hashes).",
+        "Zero; otherwise consider raising work_mem.",
+    ),
+    doc(
+        "Errors",
+        "pg_stat_monitor calls with elevel > 0",
+        "Calls that ended in an error (pg_stat_monitor only).",
+        "Zero; the server log has the messages.",
+    ),
+];
+
+static WAL_IO: [MetricDoc; 11] = [
+    doc(
+        "WAL Rate",
+        "pg_stat_wal.wal_bytes",
+        "WAL generated per second.",
+        "Tracks write load; spikes follow bulk writes and checkpoints (full-page writes).",
+    ),
+    doc(
+        "Buffers Full",
+        "pg_stat_wal.wal_buffers_full",
+        "Times WAL buffers filled and had to be written out early.",
+        "Zero or near zero; otherwise raise wal_buffers.",
+    ),
+    doc(
+        "Checkpoints",
+        "pg_stat_checkpointer / pg_stat_bgwriter",
+        "Checkpoints per minute, timed and requested.",
+        "Mostly timed; see Forced.",
+    ),
+    doc(
+        "Forced",
+        "checkpoints_req / num_requested",
+        "Checkpoints requested because WAL reached max_wal_size (or by CHECKPOINT).",
+        "Rare; frequent forced checkpoints mean max_wal_size is too small.",
+    ),
+    doc(
+        "Archive Failed",
+        "pg_stat_archiver.failed_count",
+        "WAL segments the archive_command failed to archive.",
+        "Zero; failures make WAL pile up on the primary.",
+    ),
+    doc(
+        "Backlog",
+        "pg_ls_archive_statusdir(), *.ready (PG12+)",
+        "WAL segments finished but not yet taken by archive_command.",
+        "0 or a few; a growing backlog means archiving can't keep up or is failing.",
+    ),
+    doc(
+        "pg_wal",
+        "sum(size) from pg_ls_waldir()",
+        "Size of the WAL directory. Needs pg_monitor. Flagged past max_wal_size or wal_dir_warn_mb.",
+        "Around max_wal_size or less; more means archiving, a replication slot or wal_keep_size holds WAL back.",
+    ),
+    doc(
+        "Backend",
+        "pg_stat_bgwriter.buffers_backend",
+        "Buffers written by backends themselves instead of the bgwriter or checkpointer.",
+        "Small share of all writes.",
+    ),
+    doc(
+        "Throttled",
+        "pg_stat_bgwriter.maxwritten_clean",
+        "Times the bgwriter stopped a round after writing bgwriter_lru_maxpages.",
+        "Near zero; otherwise raise bgwriter_lru_maxpages.",
+    ),
+    doc(
+        "Alloc",
+        "pg_stat_bgwriter.buffers_alloc",
+        "Buffers allocated per second.",
+        "-",
+    ),
+    doc(
+        "I/O by Backend",
+        "pg_stat_io reads, writes, extends, fsyncs (PG16+)",
+        "Blocks each backend type read, wrote and extended per second in each context (normal, vacuum, bulk read/write), and fsyncs it issued.",
+        "Writes mostly by the checkpointer and background writer; client backends writing or fsyncing means they are not keeping up.",
+    ),
+];
+
+static CHECKPOINTS: [MetricDoc; 6] = [
+    doc(
+        "Frequency",
+        "checkpoints_timed + checkpoints_req / num_timed + num_requested",
+        "Checkpoints per minute, with the forced (requested) ones drawn separately.",
+        "About one per checkpoint_timeout; forced ones mean max_wal_size is reached first.",
+    ),
+    doc(
+        "Interval",
+        "time between checkpoints seen this session",
+        "Average gap between the checkpoints that completed while watching.",
+        "Close to checkpoint_timeout; much shorter points at write bursts or a small max_wal_size.",
+    ),
+    doc(
+        "Write Time",
+        "checkpoint_write_time / write_time",
+        "Time spent writing dirty buffers out, spread over checkpoint_completion_target of the interval.",
+        "Long is fine as long as it is spread out; spikes follow bulk writes.",
+    ),
+    doc(
+        "Sync Time",
+        "checkpoint_sync_time / sync_time",
+        "Time spent in fsync at the end of each checkpoint.",
+        "Milliseconds; seconds point at a struggling disk.",
+    ),
+    doc(
+        "Buffers Written",
+        "buffers_checkpoint, buffers_clean, buffers_backend",
+        "Who wrote dirty buffers out: the checkpointer, the bgwriter, or backends needing a free buffer.",
+        "Mostly checkpointer and bgwriter; backend writes slow queries down.",
+    ),
+    doc(
+        "Recent",
+        "checkpoint counter deltas between refreshes",
+        "Checkpoints seen completing, with their write plus sync time and buffers written.",
+        "-",
+    ),
+];
+
+static OPERATIONS: [MetricDoc; 4] = [
+    doc(
+        "Phase",
+        "pg_stat_progress_{vacuum,create_index,cluster,analyze,basebackup}.phase",
+        "Current step of the command; each view names its own phases.",
+        "A CREATE INDEX CONCURRENTLY stuck 'waiting for old snapshots' is waiting on long transactions.",
+    ),
+    doc(
+        "Progress",
+        "blocks, tuples or bytes done / total in the phase",
+        "Share of the current phase done. Counters restart with each phase, and some phases report no total.",
+        "-",
+    ),
+    doc(
+        "Elapsed",
+        "now() - pg_stat_activity.query_start",
+        "Time since the command started; since connecting for a base backup.",
+        "-",
+    ),
+    doc(
+        "ETA",
+        "remaining work / rate since the phase was first seen",
+        "Estimated time left in the current phase, not the whole command.",
+        "-",
+    ),
+];
+
+static POOLER: [MetricDoc; 5] = [
+    doc(
+        "Clients",
+        "SHOW POOLS: cl_active, cl_waiting",
+        "Client connections to PgBouncer paired with a server connection, and those queued for one.",
+        "Waiting clients at 0; a steady queue means the pool is too small or queries hold servers too long.",
+    ),
+    doc(
+        "Servers",
+        "SHOW POOLS: sv_active, sv_idle, sv_used",
+        "Connections PgBouncer holds to Postgres: in use by a client, ready for one, or idle long enough \
+         to be checked before reuse.",
+        "Some idle servers left over; none idle while clients wait means the pool is exhausted.",
+    ),
+    doc(
+        "Max wait",
+        "SHOW POOLS: maxwait, maxwait_us",
+        "How long the oldest queued client has been waiting for a server connection.",
+        "Under a second; anything higher is felt as latency by the application.",
+    ),
+    doc(
+        "Avg query",
+        "SHOW STATS: avg_query_time",
+        "Mean query time over PgBouncer's last stats period, per database, as the pooler sees it.",
+        "Close to the server-side times in Statements; much higher points at the network or the pooler.",
+    ),
+    doc(
+        "Avg wait",
+        "SHOW STATS: avg_wait_time",
+        "Mean time clients spent queued for a server connection in the last stats period.",
+        "Near 0 µs.",
+    ),
+];
+
+static BACKENDS: [MetricDoc; 3] = [
+    doc(
+        "Type",
+        "pg_stat_activity.backend_type <> 'client backend'",
+        "What the process is: parallel worker, autovacuum worker, walsender, logical replication \
+         worker, or one of the server's own processes such as the checkpointer.",
+        "-",
+    ),
+    doc(
+        "Leader",
+        "pg_stat_activity.leader_pid (PG13+)",
+        "Client backend a parallel worker is running a query for. Enter on a worker opens the leader.",
+        "Workers come and go with the leader's query; many per leader means a wide parallel plan.",
+    ),
+    doc(
+        "Duration",
+        "now() - COALESCE(query_start, backend_start)",
+        "Time in the current query, or since the process started when it runs none.",
+        "-",
+    ),
+];
+
+static INDEX_HEALTH: [MetricDoc; 3] = [
+    doc(
+        "Issue",
+        "pg_index.indisvalid, pg_get_indexdef(), idx_scan",
+        "invalid: left by a failed CREATE INDEX CONCURRENTLY. duplicate: same columns, \
+         method and predicate as another index. overlapping: its columns lead another btree index. \
+         unused: no scans since stats reset; unique indexes are never reported.",
+        "No findings. Check replicas before dropping an index unused on the primary.",
+    ),
+    doc(
+        "Redundant With",
+        "pg_get_indexdef()",
+        "The index that already serves the lookups of this one.",
+        "-",
+    ),
+    doc(
+        "Reclaimable",
+        "sum(pg_relation_size(indexrelid))",
+        "Space freed by dropping every flagged index.",
+        "-",
+    ),
+];
+
+static AUTOVACUUM: [MetricDoc; 5] = [
+    doc(
+        "Workers",
+        "count(*) FROM pg_stat_activity WHERE backend_type = 'autovacuum worker'",
+        "Autovacuum workers running, against autovacuum_max_workers.",
+        "Below the maximum most of the time; pinned at it means tables queue for a worker.",
+    ),
+    doc(
+        "Dead / Trigger",
+        "n_dead_tup, autovacuum_vacuum_threshold + autovacuum_vacuum_scale_factor × n_live_tup",
+        "Dead tuples against the count that makes autovacuum pick the table up. Uses the \
+         server-wide settings, not per-table storage parameters.",
+        "Below the trigger, or briefly above it.",
+    ),
+    doc(
+        "Due For",
+        "Seen across snapshots",
+        "How long the table has been past its trigger with no autovacuum finishing on it. \
+         starved: longer than three autovacuum_naptime rounds (at least two minutes).",
+        "Under a naptime round. Starved tables point at busy workers or an old xmin horizon.",
+    ),
+    doc(
+        "Since Vacuum",
+        "greatest(last_vacuum, last_autovacuum)",
+        "Time since the table was last vacuumed, by hand or by autovacuum.",
+        "-",
+    ),
+    doc(
+        "Runs",
+        "autovacuum_count",
+        "Autovacuums that finished on the table since it was first seen this session.",
+        "-",
+    ),
+];
+
+static BUFFER_CACHE: [MetricDoc; 5] = [
+    doc(
+        "Cached",
+        "count(*) FROM pg_buffercache × block_size",
+        "Shared buffers holding pages of the relation.",
+        "-",
+    ),
+    doc(
+        "Share",
+        "relation buffers / used buffers",
+        "The relation's part of the buffers in use.",
+        "Hot tables and their indexes at the top.",
+    ),
+    doc(
+        "Of Rel",
+        "cached bytes / pg_relation_size()",
+        "How much of the relation is in shared buffers.",
+        "Near 100% for small, hot tables.",
+    ),
+    doc(
+        "Dirty",
+        "isdirty",
+        "Changed pages not yet written out by a checkpoint or the background writer.",
+        "Small next to the cached size.",
+    ),
+    doc(
+        "Usage count",
+        "usagecount",
+        "Clock sweep counter, 0-5, bumped on each access and lowered as the sweep passes. \
+         Pages at 0 are the first to be evicted.",
+        "Mostly high counts when shared_buffers fits the working set.",
+    ),
+];
+
+/// Glossary entries for the metrics a panel shows (empty for panels that
+/// only list catalog data).
+pub fn metrics_for(panel: BottomPanel) -> &'static [MetricDoc] {
+    match panel {
+        BottomPanel::Queries => &QUERIES,
+        BottomPanel::Blocking => &BLOCKING,
+        BottomPanel::Locks => &LOCKS,
+        BottomPanel::ConnectionGroups => &CONNECTIONS,
+        BottomPanel::Sessions => &SESSIONS,
+        BottomPanel::Pooler => &POOLER,
+        BottomPanel::WaitEvents => &WAIT_EVENTS,
+        BottomPanel::TableStats => &TABLE_STATS,
+        BottomPanel::Replication => &REPLICATION,
+        BottomPanel::Slots => &SLOTS,
+        BottomPanel::LogicalReplication => &LOGICAL_REPLICATION,
+        BottomPanel::VacuumProgress => &VACUUM_PROGRESS,
+        BottomPanel::AutovacuumHealth => &AUTOVACUUM,
+        BottomPanel::Operations => &OPERATIONS,
+        BottomPanel::Backends => &BACKENDS,
+        BottomPanel::Wraparound => &WRAPAROUND,
+        BottomPanel::Indexes => &INDEXES,
+        BottomPanel::IndexHealth => &INDEX_HEALTH,
+        BottomPanel::Statements => &STATEMENTS,
+        BottomPanel::WalIo => &WAL_IO,
+        BottomPanel::Checkpoints => &CHECKPOINTS,
+        BottomPanel::BufferCache => &BUFFER_CACHE,
+        BottomPanel::Settings
+        | BottomPanel::Extensions
+        | BottomPanel::Roles
+        | BottomPanel::Logs
+        | BottomPanel::Alerts => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        ConnectionGroupSortColumn, IndexSortColumn, LockSortColumn, SessionSortColumn, SlotSortColumn, SortColumn,
+        StatementSortColumn, TableStatSortColumn,
+    };
+
+    fn has_entry(panel: BottomPanel, name: &str) -> bool {
+        metrics_for(panel).iter().any(|d| d.name == name)
+    }
+
+    #[test]
+    fn every_sort_column_has_an_entry() {
+        let mut col = SortColumn::Duration;
+        for _ in 0..4 {
+            assert!(has_entry(BottomPanel::Queries, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = IndexSortColumn::Scans;
+        for _ in 0..5 {
+            assert!(has_entry(BottomPanel::Indexes, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = TableStatSortColumn::DeadTuples;
+        for _ in 0..6 {
+            assert!(has_entry(BottomPanel::TableStats, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = LockSortColumn::Status;
+        for _ in 0..5 {
+            assert!(has_entry(BottomPanel::Locks, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = ConnectionGroupSortColumn::Total;
+        for _ in 0..5 {
+            assert!(has_entry(BottomPanel::ConnectionGroups, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = SessionSortColumn::ActiveTime;
+        for _ in 0..5 {
+            assert!(has_entry(BottomPanel::Sessions, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = SlotSortColumn::Retained;
+        for _ in 0..4 {
+            assert!(has_entry(BottomPanel::Slots, col.label()), "{}", col.label());
+            col = col.next();
+        }
+        let mut col = StatementSortColumn::TotalTime;
+        for _ in 0..10 {
+            assert!(has_entry(BottomPanel::Statements, col.label()), "{}", col.label());
+            col = col.next();
+        }
+    }
+}
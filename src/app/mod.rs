@@ -1,26 +1,57 @@
 //! Application state and key handling.
 
 mod actions;
+mod changes;
+pub mod chords;
+pub mod glossary;
+mod plan_history;
 mod panels;
+mod prepare;
+pub mod query_progress;
+mod registry;
 mod sorting;
 mod state;
+pub mod settings_review;
+pub mod timeouts;
 
 pub use actions::AppAction;
-pub use panels::{BottomPanel, ConfirmAction, InspectTarget, ViewMode};
+pub use changes::{Change, ChangeTracker};
+pub use panels::{BottomPanel, ConfirmAction, InspectTarget, QueryScope, ViewMode};
+pub use plan_history::{NodeChange, PlanDiff, PlanHistory, PlanKey, PlanRun};
+pub use prepare::{PrepWatch, PreparedSnapshot, SnapshotPrep};
+pub use registry::{PanelDescriptor, PANELS};
 pub use sorting::{
-    IndexSortColumn, SortColumn, SortColumnTrait, StatementSortColumn, TableStatSortColumn,
+    ConnectionGroupSortColumn, IndexSortColumn, LockSortColumn, SessionSortColumn, SlotSortColumn, SortColumn,
+    SortColumnTrait, StatementSortColumn, TableStatSortColumn,
 };
-pub use state::{ConfigOverlay, ConnectionInfo, FilterState, MetricsHistory, PanelStates, RecordingsBrowser, ReplayState, TableViewState, UiFeedback};
+pub use state::{Annotation, BloatHistory, BloatSample, CheckpointEvent, CollectorStats, CompareState, ConfigOverlay, ConnectionEntry, ConnectionInfo, ConnectionSwitcher, CounterReset, ExplainView, FilterState, IndexAdviceView, IoRate, LatencyBuckets, LatencyEstimate, LatencySampler, MetricsHistory, RateTracker, MouseTargets, OverlayExtent, OverlaySearch, ProgressTracker, Reconnecting, LATENCY_BUCKET_BOUNDS, LATENCY_WINDOW_SECS, TempSpill, PanelStates, RateSeries, RecordingsBrowser, ReplayInterpolation, ReplayState, RoleGrantsView, RunningMaintenance, SeekTarget, SplitView, SqlView, StandbyLag, StatementTiming, TableSchemaView, TableViewState, UiFeedback, WaitSampler, WaitTypeShare, WalIoRates, WraparoundTablesView, WAIT_WINDOW_SECS};
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config as MatcherConfig, Matcher};
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
+use crate::adaptive_refresh::AdaptiveRefresh;
+use crate::alerts::Alerts;
+use crate::autovacuum::{freeze_sql, AutovacuumRow, AutovacuumSettings, AutovacuumTracker};
+use crate::baseline::StatementBaselines;
+use crate::bookmarks::Bookmarks;
 use crate::config::{AppConfig, ConfigItem};
-use crate::db::models::{PgSnapshot, ServerInfo};
-use crate::db::queries::{IndexBloat, TableBloat};
+use crate::db::models::{
+    ActiveQuery, BufferCacheReport, LogLine, IndexAdvice, IndexInfo, MeasuredBloat, OperationProgress, PgSnapshot, QueryPlan, ReplicationSlot, RoleGrant, ServerInfo, StatStatement, TableSchema, TableStat, WaitEventCount, WraparoundTable,
+};
+use crate::db::queries::{measured_bloat_estimates, IndexBloat, TableBloat};
+use crate::fingerprint::{self, FingerprintGroup};
+use crate::index_health::{self, IndexFinding};
+use crate::maintenance::Maintenance;
+use crate::server_log::{self, LevelFilter};
+use crate::recorder::{first_segment_name, UiEvent, UiEventKind};
+use crate::row_colors::{RowColorRule, RowColumn};
+use crate::session_stats::{SessionRow, SessionTracker};
+use crate::starred::StarredStatements;
 use crate::ui::theme;
 
 use sorting::{sort_by_key, sort_by_key_partial, Filterable};
@@ -31,6 +62,9 @@ const CLIPBOARD_PREVIEW_LEN: usize = 40;
 /// Number of items to jump when using Page Up/Down or Ctrl+u/Ctrl+d
 const PAGE_SIZE: usize = 10;
 
+/// Longest gap between the clicks of a double-click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
 pub struct App {
     // Core runtime
     pub running: bool,
@@ -47,6 +81,8 @@ pub struct App {
     pub server_info: ServerInfo,
     pub connection: ConnectionInfo,
     pub refresh_interval_secs: u64,
+    // Interval adaptive refresh settled on, when it is on
+    pub adaptive_refresh: AdaptiveRefresh,
 
     // UI feedback (errors, status, loading)
     pub feedback: UiFeedback,
@@ -54,15 +90,139 @@ pub struct App {
     pub config: AppConfig,
     pub config_overlay: ConfigOverlay,
 
-    pub filter: FilterState,
     pub replay: Option<ReplayState>,
     pub overlay_scroll: u16,
+    /// Set while drawing a scrollable overlay; None until one is drawn
+    pub overlay_extent: Cell<Option<OverlayExtent>>,
+    /// `/` search in the inspect overlay on screen
+    pub overlay_search: OverlaySearch,
+    /// Wrap, line number and sideways scroll state of the SQL viewer
+    pub sql_view: SqlView,
 
     // Recordings browser state
     pub recordings: RecordingsBrowser,
 
     // Graph panel collapsed ("zen mode")
     pub graphs_collapsed: bool,
+
+    // Second bottom pane of the `|` split view
+    pub split: Option<SplitView>,
+
+    // Starred statements and the note being edited
+    pub starred: StarredStatements,
+    /// Mean time baselines the Statements panel flags regressions against
+    pub baselines: StatementBaselines,
+    /// Replay intervals marked with `[` and `]`, listed in the recordings browser
+    pub bookmarks: Bookmarks,
+    /// Sampled latency percentiles for starred statements
+    pub latency: LatencySampler,
+    /// Temp spill rates attributed to running statements
+    pub temp_spill: TempSpill,
+    /// Rows changed since the previous snapshot, for highlight mode
+    pub changes: ChangeTracker,
+    pub note_input: String,
+    /// File name typed in the export prompt
+    pub export_input: String,
+    /// Text typed into the replay `:` prompt
+    pub seek_input: String,
+    /// Text typed into the `#` annotation prompt
+    pub annotation_input: String,
+    /// Notes made with `#` this session, or read from the replayed
+    /// recording, oldest first
+    pub annotations: Vec<Annotation>,
+    /// The export prompt writes the metrics history rather than the panel
+    pub export_history: bool,
+
+    // Show entries on the ignore list instead of hiding them
+    pub show_ignored: bool,
+
+    // Highlighted line in the column chooser
+    pub column_cursor: usize,
+
+    // Fetch every idle backend instead of the first `IDLE_BACKENDS_CAP`
+    pub show_all_backends: bool,
+
+    // Queries panel collapses backends running the same fingerprint
+    pub group_by_fingerprint: bool,
+
+    // Queries row showing its full query text inline, by PID
+    pub expanded_query: Option<i32>,
+    // First line of the expanded query shown when it is taller than the panel
+    pub expanded_scroll: usize,
+
+    // Connections group or wait event the Queries list is narrowed to
+    pub query_scope: Option<QueryScope>,
+
+    // Other monitored connections (filled in by the runtime)
+    pub switcher: ConnectionSwitcher,
+
+    // Contents of the EXPLAIN overlay
+    pub explain: Option<ExplainView>,
+    // Statement the EXPLAIN overlay is for, and every plan explained so far
+    explain_key: Option<PlanKey>,
+    pub plan_history: PlanHistory,
+
+    // Contents of the index advice overlay
+    pub index_advice: Option<IndexAdviceView>,
+
+    // Columns, indexes and the like of the inspected table
+    pub table_schema: Option<TableSchemaView>,
+
+    // Privileges of the inspected role
+    pub role_grants: Option<RoleGrantsView>,
+    pub wraparound_tables: Option<WraparoundTablesView>,
+
+    // Shared buffer contents, read on demand with `b` in the Buffer Cache panel
+    pub buffer_cache: Option<BufferCacheReport>,
+
+    // Server log lines for the Logs panel, oldest first; live ones come with
+    // each snapshot, replays rebuild them from the recording
+    pub server_log: Vec<LogLine>,
+    pub log_filter: LevelFilter,
+
+    // Settings panel narrowed to values that differ from their defaults
+    pub settings_changed_only: bool,
+
+    // Exact pgstattuple bloat, measured on request from the table inspect overlay
+    pub measured_bloat: HashMap<String, MeasuredBloat>,
+
+    // VACUUM, ANALYZE and REINDEX can be run from the inspect overlays
+    // (--allow-maintenance)
+    pub allow_maintenance: bool,
+
+    // Alert rules that fired on this connection
+    pub alerts: Alerts,
+
+    // Snapshot timings and hook results
+    pub collector: CollectorStats,
+
+    // Bloat estimates from each refresh, for the inspect trend
+    pub bloat_history: BloatHistory,
+
+    // Phase rates of running maintenance commands, for ETAs
+    pub progress: ProgressTracker,
+
+    // How long each table has waited for autovacuum
+    pub autovacuum: AutovacuumTracker,
+
+    // Per-session activity sampled across snapshots
+    pub sessions: SessionTracker,
+
+    // Whether pg_wal was past its warning size at the last snapshot
+    wal_dir_over: bool,
+
+    // Sub-second wait event samples for the Wait Events panel
+    pub wait_sampler: WaitSampler,
+
+    // Preparation state for snapshots handed to `update` unprepared
+    snapshot_prep: SnapshotPrep,
+
+    // Chord prefix pressed and waiting for its second key
+    pub pending_chord: Option<char>,
+
+    // Clickable regions of the last frame, and the last row clicked
+    pub mouse_targets: MouseTargets,
+    last_click: Option<(Instant, BottomPanel, usize)>,
 }
 
 impl App {
@@ -88,17 +248,75 @@ impl App {
             server_info,
             connection: ConnectionInfo::new(host, port, dbname, user),
             refresh_interval_secs: refresh,
+            adaptive_refresh: AdaptiveRefresh::default(),
             feedback: UiFeedback::new(),
             config,
             config_overlay: ConfigOverlay::new(),
-            filter: FilterState::default(),
             replay: None,
             overlay_scroll: 0,
+            overlay_extent: Cell::new(None),
+            overlay_search: OverlaySearch::default(),
+            sql_view: SqlView::default(),
             recordings: RecordingsBrowser::new(),
             graphs_collapsed: false,
+            split: None,
+            starred: StarredStatements::default(),
+            baselines: StatementBaselines::default(),
+            bookmarks: Bookmarks::default(),
+            latency: LatencySampler::default(),
+            temp_spill: TempSpill::default(),
+            changes: ChangeTracker::default(),
+            note_input: String::new(),
+            export_input: String::new(),
+            seek_input: String::new(),
+            annotation_input: String::new(),
+            annotations: Vec::new(),
+            export_history: false,
+            show_ignored: false,
+            column_cursor: 0,
+            show_all_backends: false,
+            expanded_query: None,
+            expanded_scroll: 0,
+            query_scope: None,
+            group_by_fingerprint: false,
+            switcher: ConnectionSwitcher::default(),
+            explain: None,
+            explain_key: None,
+            plan_history: PlanHistory::default(),
+            index_advice: None,
+            table_schema: None,
+            role_grants: None,
+            wraparound_tables: None,
+            buffer_cache: None,
+            server_log: Vec::new(),
+            log_filter: LevelFilter::default(),
+            settings_changed_only: false,
+            measured_bloat: HashMap::new(),
+            allow_maintenance: false,
+            alerts: Alerts::default(),
+            collector: CollectorStats::default(),
+            bloat_history: BloatHistory::default(),
+            progress: ProgressTracker::default(),
+            autovacuum: AutovacuumTracker::default(),
+            sessions: SessionTracker::default(),
+            wal_dir_over: false,
+            wait_sampler: WaitSampler::default(),
+            snapshot_prep: SnapshotPrep::default(),
+            pending_chord: None,
+            mouse_targets: MouseTargets::default(),
+            last_click: None,
         }
     }
 
+    /// The filter of the panel on screen.
+    pub fn filter(&self) -> &FilterState {
+        self.panels.filter(self.bottom_panel)
+    }
+
+    pub fn filter_mut(&mut self) -> &mut FilterState {
+        self.panels.filter_mut(self.bottom_panel)
+    }
+
     pub fn set_ssl_mode_label(&mut self, label: &str) {
         self.connection.set_ssl_mode(label);
     }
@@ -125,66 +343,141 @@ impl App {
         self.replay.is_some()
     }
 
-    pub fn update(&mut self, mut snapshot: PgSnapshot) {
-        // Update metrics history
-        self.metrics.push_snapshot_metrics(&snapshot);
-        self.metrics.calculate_rates(&snapshot);
-
-        // Preserve bloat data from previous snapshot
-        if let Some(ref old_snap) = self.snapshot {
-            // Build lookup maps from old snapshot's bloat data
-            let table_bloat: HashMap<String, (Option<i64>, Option<f64>)> = old_snap
-                .table_stats
-                .iter()
-                .filter(|t| t.bloat_pct.is_some())
-                .map(|t| {
-                    let key = format!("{}.{}", t.schemaname, t.relname);
-                    (key, (t.bloat_bytes, t.bloat_pct))
-                })
-                .collect();
-
-            let index_bloat: HashMap<String, (Option<i64>, Option<f64>)> = old_snap
-                .indexes
-                .iter()
-                .filter(|i| i.bloat_pct.is_some())
-                .map(|i| {
-                    let key = format!("{}.{}", i.schemaname, i.index_name);
-                    (key, (i.bloat_bytes, i.bloat_pct))
-                })
-                .collect();
-
-            // Apply to new snapshot
-            for table in &mut snapshot.table_stats {
-                let key = format!("{}.{}", table.schemaname, table.relname);
-                if let Some((bytes, pct)) = table_bloat.get(&key) {
-                    table.bloat_bytes = *bytes;
-                    table.bloat_pct = *pct;
-                }
-            }
+    /// Prepare `snapshot` here and show it. Live sources prepare in their
+    /// worker and hand over through [`Self::apply_prepared`] instead.
+    pub fn update(&mut self, snapshot: PgSnapshot) {
+        // Recorded snapshots already carry their bloat, so replay takes
+        // them as-is
+        let carry_bloat = self.replay.is_none();
+        self.snapshot_prep.watch(&self.prep_watch());
+        let prepared = self.snapshot_prep.prepare(snapshot, carry_bloat);
+        self.apply_prepared(prepared);
+    }
 
-            for index in &mut snapshot.indexes {
-                let key = format!("{}.{}", index.schemaname, index.index_name);
-                if let Some((bytes, pct)) = index_bloat.get(&key) {
-                    index.bloat_bytes = *bytes;
-                    index.bloat_pct = *pct;
-                }
-            }
+    /// Show a snapshot prepared off the UI thread.
+    pub fn apply_prepared(&mut self, prepared: PreparedSnapshot) {
+        let PreparedSnapshot {
+            snapshot,
+            temp_spill,
+            rates,
+            alerts,
+            fired_alerts,
+            notifications,
+            progress,
+            autovacuum,
+            sessions,
+            changes,
+            adaptive_refresh,
+        } = prepared;
+        self.metrics.push_snapshot_metrics(&snapshot);
+        let since = self.snapshot.as_ref().map(|s| s.timestamp);
+        let annotated = self
+            .annotations
+            .iter()
+            .any(|a| a.at <= snapshot.timestamp && since.map_or(true, |since| a.at > since));
+        self.metrics.annotation_marks.push(annotated);
+        if let Some(reset) = rates.reset {
+            self.feedback.status_message = Some(reset.describe());
+        }
+        self.metrics.push_rates(&rates);
+        self.temp_spill = temp_spill;
+        self.progress = progress;
+        self.autovacuum = autovacuum;
+        self.sessions = sessions;
+        self.changes = changes;
+        self.adaptive_refresh = adaptive_refresh;
+        let wal_dir_over = snapshot
+            .wal_dir
+            .zip(self.wal_dir_limit_bytes())
+            .filter(|(dir, limit)| dir.bytes > *limit);
+        if let Some((dir, limit)) = wal_dir_over.filter(|_| !self.wal_dir_over) {
+            self.feedback.status_message = Some(format!(
+                "pg_wal is {}, past {}: check archiving and replication slots",
+                crate::ui::util::format_bytes(dir.bytes),
+                crate::ui::util::format_bytes(limit)
+            ));
+        }
+        self.wal_dir_over = wal_dir_over.is_some();
+        self.alerts.show(alerts, &fired_alerts);
+        if let Some(alert) = fired_alerts.first() {
+            self.feedback.status_message = Some(format!("Alert: {} ({})", alert.name, alert.peak));
+        }
+        self.feedback.fired_alerts.extend(fired_alerts);
+        self.feedback.notifications.extend(notifications);
+        if self.replay.is_none() {
+            self.server_log.extend(snapshot.server_log.iter().cloned());
+            let excess = self.server_log.len().saturating_sub(server_log::PANEL_LINES);
+            self.server_log.drain(..excess);
         }
 
         self.snapshot = Some(snapshot);
         self.feedback.last_error = None;
     }
 
+    /// What snapshots are prepared against: alert rules and the settings
+    /// notifications, autovacuum and adaptive refresh follow.
+    pub fn prep_watch(&self) -> PrepWatch {
+        let live = self.replay.is_none();
+        PrepWatch {
+            rules: self.config.alerts.clone(),
+            ignore: self.config.ignore.clone(),
+            autovacuum: self.autovacuum_settings(),
+            notifications: live.then(|| self.config.notifications.clone()),
+            danger_duration_secs: self.config.danger_duration_secs,
+            adaptive_refresh: (live && self.config.adaptive_refresh.enabled)
+                .then(|| self.config.adaptive_refresh.clone()),
+            refresh_interval_secs: self.refresh_interval_secs,
+            tps_history: self.metrics.tps.capacity(),
+        }
+    }
+
+    /// Whether titles get their emoji: not in plain mode.
+    pub const fn show_emojis(&self) -> bool {
+        self.config.show_emojis && !self.config.plain()
+    }
+
+    /// Rows changed at the last refresh, while highlight mode is on.
+    pub fn highlighted_changes(&self) -> Option<&ChangeTracker> {
+        self.config.highlight_changes.then_some(&self.changes)
+    }
+
+    /// How often snapshots are taken right now: the configured interval, or
+    /// what adaptive refresh made of it.
+    pub fn effective_refresh_secs(&self) -> u64 {
+        match self.adaptive_refresh.current() {
+            Some(secs) if self.config.adaptive_refresh.enabled && self.replay.is_none() => secs,
+            _ => self.refresh_interval_secs,
+        }
+    }
+
     pub fn update_error(&mut self, err: String) {
         self.feedback.last_error = Some(err);
     }
 
+    /// The connection dropped; a reconnect attempt is scheduled.
+    pub fn set_reconnecting(&mut self, attempt: u32, retry_in_secs: u64, err: String) {
+        self.feedback.reconnecting = Some(Reconnecting {
+            attempt,
+            retry_in_secs,
+        });
+        self.feedback.last_error = Some(err);
+    }
+
+    /// The connection is back; snapshots resume with the next refresh.
+    pub fn set_reconnected(&mut self) {
+        self.feedback.reconnecting = None;
+        self.feedback.last_error = None;
+        self.feedback.status_message = Some("Reconnected".into());
+    }
+
     /// Apply bloat estimates to current snapshot's `table_stats` and indexes
     pub fn apply_bloat_data(
         &mut self,
         table_bloat: &HashMap<String, TableBloat>,
         index_bloat: &HashMap<String, IndexBloat>,
     ) {
+        self.bloat_history.record(chrono::Utc::now(), table_bloat, index_bloat);
+        self.snapshot_prep.remember_bloat(table_bloat, index_bloat);
         if let Some(ref mut snapshot) = self.snapshot {
             // Apply table bloat
             for table in &mut snapshot.table_stats {
@@ -210,8 +503,8 @@ impl App {
     /// Check if fuzzy filter should be applied for the given panel
     fn should_apply_filter(&self, panel: BottomPanel) -> bool {
         self.bottom_panel == panel
-            && !self.filter.text.is_empty()
-            && (self.filter.active || self.view_mode == ViewMode::Filter)
+            && !self.filter().text.is_empty()
+            && (self.filter().active || self.view_mode == ViewMode::Filter)
     }
 
     /// Build indices for items, optionally applying fuzzy filter.
@@ -220,7 +513,7 @@ impl App {
         if self.should_apply_filter(panel) {
             let mut matcher = Matcher::new(MatcherConfig::DEFAULT);
             let pattern =
-                Pattern::parse(&self.filter.text, CaseMatching::Ignore, Normalization::Smart);
+                Pattern::parse(&self.filter().text, CaseMatching::Ignore, Normalization::Smart);
             indices.retain(|&i| {
                 let haystack = items[i].filter_string();
                 let mut buf = Vec::new();
@@ -235,11 +528,31 @@ impl App {
         indices
     }
 
+    /// Rows of the Queries panel: one per backend, or per fingerprint group
+    /// (its longest-running member) when grouping is on.
     pub fn sorted_query_indices(&self) -> Vec<usize> {
+        if self.group_by_fingerprint {
+            return self.fingerprint_groups().into_iter().map(|g| g.index).collect();
+        }
+        self.listed_query_indices()
+    }
+
+    /// Fingerprint groups of the listed backends, in sort order.
+    pub fn fingerprint_groups(&self) -> Vec<FingerprintGroup> {
+        self.snapshot.as_ref().map_or_else(Vec::new, |snap| {
+            fingerprint::group(&snap.active_queries, &self.listed_query_indices())
+        })
+    }
+
+    /// Backends that pass the filter and connection group, sorted.
+    fn listed_query_indices(&self) -> Vec<usize> {
         let Some(snap) = &self.snapshot else {
             return vec![];
         };
         let mut indices = self.filtered_indices(&snap.active_queries, BottomPanel::Queries);
+        if let Some(scope) = &self.query_scope {
+            indices.retain(|&i| scope.contains(&snap.active_queries[i]));
+        }
 
         let asc = self.panels.queries.sort_ascending;
         let q = &snap.active_queries;
@@ -260,6 +573,145 @@ impl App {
         Some(snap.active_queries[real_idx].pid)
     }
 
+    /// PIDs holding the locks `pid` is waiting on, from the blocking info
+    pub fn blockers_of(&self, pid: i32) -> Vec<i32> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut pids: Vec<i32> = snap
+            .blocking_info
+            .iter()
+            .filter(|b| b.blocked_pid == pid)
+            .map(|b| b.blocker_pid)
+            .collect();
+        pids.sort_unstable();
+        pids.dedup();
+        pids
+    }
+
+    /// Indexes worth dropping or rebuilding, as listed in the Index Health panel.
+    pub fn index_findings(&self) -> Vec<IndexFinding> {
+        self.snapshot
+            .as_ref()
+            .map_or_else(Vec::new, |s| index_health::findings(&s.indexes))
+    }
+
+    /// The server's autovacuum settings, or the Postgres defaults until
+    /// they have been read.
+    pub fn autovacuum_settings(&self) -> AutovacuumSettings {
+        AutovacuumSettings::from_settings(&self.server_info.settings)
+    }
+
+    /// Tables as listed in the Autovacuum Health panel, starved ones first.
+    pub fn autovacuum_rows(&self) -> Vec<AutovacuumRow> {
+        self.snapshot.as_ref().map_or_else(Vec::new, |s| {
+            self.autovacuum.rows(s, &self.autovacuum_settings())
+        })
+    }
+
+    /// Size past which `pg_wal` is flagged: the configured warning, else the
+    /// server's `max_wal_size` once settings have been read.
+    pub fn wal_dir_limit_bytes(&self) -> Option<i64> {
+        if self.config.wal_dir_warn_mb > 0 {
+            return Some(self.config.wal_dir_warn_mb as i64 * 1024 * 1024);
+        }
+        let setting = self.server_info.settings.iter().find(|s| s.name == "max_wal_size")?;
+        let value: i64 = setting.setting.parse().ok()?;
+        let unit = match setting.unit.as_deref() {
+            Some("kB") => 1024,
+            Some("GB") => 1024 * 1024 * 1024,
+            _ => 1024 * 1024,
+        };
+        Some(value * unit)
+    }
+
+    /// `pg_wal` size as a share of [`Self::wal_dir_limit_bytes`].
+    pub fn wal_dir_fill(&self) -> Option<f64> {
+        let dir = self.snapshot.as_ref()?.wal_dir?;
+        let limit = self.wal_dir_limit_bytes()?;
+        Some(dir.bytes as f64 / limit.max(1) as f64)
+    }
+
+    /// Sessions as listed in the Sessions panel, in its sort order.
+    pub fn session_rows(&self) -> Vec<SessionRow> {
+        let mut rows = self.sessions.rows();
+        let asc = self.panels.sessions.sort_ascending;
+        let ordered = |ord: std::cmp::Ordering| if asc { ord } else { ord.reverse() };
+        match self.panels.sessions.sort_column {
+            SessionSortColumn::ActiveTime => rows.sort_by(|a, b| ordered(a.active_secs.total_cmp(&b.active_secs))),
+            SessionSortColumn::IdleInTxnTime => {
+                rows.sort_by(|a, b| ordered(a.idle_in_txn_secs.total_cmp(&b.idle_in_txn_secs)));
+            }
+            SessionSortColumn::Xacts => rows.sort_by(|a, b| ordered(a.xacts.cmp(&b.xacts))),
+            SessionSortColumn::Queries => rows.sort_by(|a, b| ordered(a.queries.cmp(&b.queries))),
+            SessionSortColumn::StateChanges => rows.sort_by(|a, b| ordered(a.state_changes.cmp(&b.state_changes))),
+        }
+        rows
+    }
+
+    /// Sessions idle in transaction for longer than the watchdog allows,
+    /// with how long they have been idle, longest first. Idle time counts
+    /// from the last state change, not from the start of the last query.
+    /// Empty while the watchdog is off.
+    pub fn idle_txn_offenders(&self) -> Vec<(&ActiveQuery, f64)> {
+        let threshold = self.config.idle_txn_watchdog_secs;
+        let Some(snap) = self.snapshot.as_ref().filter(|_| threshold > 0) else {
+            return vec![];
+        };
+        let mut offenders: Vec<(&ActiveQuery, f64)> = snap
+            .active_queries
+            .iter()
+            .filter(|q| {
+                matches!(q.state.as_deref(), Some("idle in transaction" | "idle in transaction (aborted)"))
+            })
+            .map(|q| (q, q.state_secs(snap.timestamp)))
+            .filter(|&(_, idle)| idle >= threshold as f64)
+            .collect();
+        offenders.sort_by(|a, b| b.1.total_cmp(&a.1));
+        offenders
+    }
+
+    /// Ask to terminate every watchdog offender. A cancel does nothing to a
+    /// session that is idle, so ending the session is the only way to close
+    /// its transaction.
+    fn confirm_terminate_idle_txn(&mut self) {
+        if self.config.idle_txn_watchdog_secs == 0 {
+            self.feedback.status_message =
+                Some("Idle transaction watchdog is off; set a threshold in the config (,)".into());
+            return;
+        }
+        let pids: Vec<i32> = self.idle_txn_offenders().iter().map(|(q, _)| q.pid).collect();
+        if pids.is_empty() {
+            self.feedback.status_message = Some("No sessions idle in transaction past the watchdog threshold".into());
+        } else {
+            self.view_mode = ViewMode::Confirm(ConfirmAction::KillBatch(pids));
+        }
+    }
+
+    /// Close the overlay and select `pid` in the Queries panel
+    fn jump_to_query(&mut self, pid: i32) {
+        let position = self.snapshot.as_ref().and_then(|snap| {
+            self.sorted_query_indices()
+                .iter()
+                .position(|&i| snap.active_queries[i].pid == pid)
+        });
+        self.overlay_scroll = 0;
+        self.view_mode = ViewMode::Normal;
+        if let Some(row) = position {
+            self.panels.queries.state.select(Some(row));
+            self.feedback.status_message = Some(format!("Jumped to blocker PID {pid}"));
+        } else if self.filter().active {
+            self.feedback.status_message =
+                Some(format!("Blocker PID {pid} is hidden by the filter"));
+        } else if self.group_by_fingerprint {
+            self.feedback.status_message =
+                Some(format!("Blocker PID {pid} is collapsed into its fingerprint group; f ungroups"));
+        } else {
+            self.feedback.status_message =
+                Some(format!("Blocker PID {pid} is not in the Queries list"));
+        }
+    }
+
     pub fn selected_index_key(&self) -> Option<String> {
         let snap = self.snapshot.as_ref()?;
         let idx = self.panels.indexes.selected().or(Some(0))?;
@@ -277,156 +729,814 @@ impl App {
         Some(snap.stat_statements[real_idx].queryid)
     }
 
-    pub fn selected_table_key(&self) -> Option<String> {
-        let snap = self.snapshot.as_ref()?;
-        let idx = self.panels.table_stats.selected().or(Some(0))?;
-        let indices = self.sorted_table_stat_indices();
-        let &real_idx = indices.get(idx)?;
-        let table = &snap.table_stats[real_idx];
-        Some(format!("{}.{}", table.schemaname, table.relname))
+    /// Statements in the current snapshot that match the ignore list.
+    pub fn ignored_statement_count(&self) -> usize {
+        self.snapshot.as_ref().map_or(0, |s| {
+            s.stat_statements
+                .iter()
+                .filter(|st| self.config.ignore.is_statement_ignored(st.queryid))
+                .count()
+        })
     }
 
-    pub fn selected_replication_pid(&self) -> Option<i32> {
-        let snap = self.snapshot.as_ref()?;
-        let sel = self.panels.replication.selected().or(Some(0))?;
-        Some(snap.replication.get(sel)?.pid)
+    /// Tables in the current snapshot that match the ignore list.
+    pub fn ignored_table_count(&self) -> usize {
+        self.snapshot.as_ref().map_or(0, |s| {
+            s.table_stats
+                .iter()
+                .filter(|t| self.config.ignore.is_table_ignored(&t.schemaname, &t.relname))
+                .count()
+        })
     }
 
-    pub fn selected_blocking_pid(&self) -> Option<i32> {
-        let snap = self.snapshot.as_ref()?;
-        let sel = self.panels.blocking.selected().or(Some(0))?;
-        Some(snap.blocking_info.get(sel)?.blocked_pid)
+    /// Ask for the plan of `query` and open the EXPLAIN overlay.
+    fn request_explain(&mut self, query: Option<String>, queryid: Option<i64>) {
+        let Some(query) = query.filter(|q| !q.trim().is_empty()) else {
+            self.feedback.status_message = Some("No query text to explain".into());
+            return;
+        };
+        self.overlay_scroll = 0;
+        self.explain_key = Some(PlanKey::new(queryid, &query));
+        self.explain = Some(ExplainView::Loading(query.clone()));
+        self.view_mode = ViewMode::Explain;
+        self.feedback.pending_action = Some(AppAction::Explain(query));
     }
 
-    pub fn selected_vacuum_pid(&self) -> Option<i32> {
-        let snap = self.snapshot.as_ref()?;
-        let sel = self.panels.vacuum.selected().or(Some(0))?;
-        Some(snap.vacuum_progress.get(sel)?.pid)
+    fn explain_selected_query(&mut self) {
+        let selected = self.selected_query_pid().and_then(|pid| {
+            self.snapshot
+                .as_ref()?
+                .active_queries
+                .iter()
+                .find(|q| q.pid == pid)
+                .map(|q| (q.query.clone(), q.query_id))
+        });
+        let (query, queryid) = selected.unwrap_or_default();
+        self.request_explain(query, queryid);
     }
 
-    pub fn selected_wraparound_datname(&self) -> Option<String> {
-        let snap = self.snapshot.as_ref()?;
-        let sel = self.panels.wraparound.selected().or(Some(0))?;
-        Some(snap.wraparound.get(sel)?.datname.clone())
+    fn explain_selected_statement(&mut self) {
+        let query = self.selected_statement_queryid().and_then(|queryid| {
+            self.snapshot
+                .as_ref()?
+                .stat_statements
+                .iter()
+                .find(|s| s.queryid == queryid)
+                .map(|s| s.query.clone())
+        });
+        self.request_explain(query, self.selected_statement_queryid());
     }
 
-    pub fn selected_setting_name(&self) -> Option<String> {
-        let indices = self.sorted_settings_indices();
-        let idx = self.panels.settings.selected().or(Some(0))?;
-        let &real_idx = indices.get(idx)?;
-        Some(self.server_info.settings[real_idx].name.clone())
+    /// Fill the EXPLAIN overlay with the plan (or why there is none).
+    pub fn set_explain_result(&mut self, result: Result<QueryPlan, String>) {
+        let query = match &self.explain {
+            Some(ExplainView::Loading(query)) => query.clone(),
+            // Closed or replaced while planning
+            _ => return,
+        };
+        self.explain = Some(match result {
+            Ok(plan) => {
+                if let Some(key) = self.explain_key.clone() {
+                    self.record_plan(key, plan.clone());
+                }
+                ExplainView::Plan(plan)
+            }
+            Err(error) => ExplainView::Failed { query, error },
+        });
     }
 
-    pub fn selected_extension_name(&self) -> Option<String> {
-        let indices = self.sorted_extensions_indices();
-        let idx = self.panels.extensions.selected().or(Some(0))?;
-        let &real_idx = indices.get(idx)?;
-        Some(self.server_info.extensions_list[real_idx].name.clone())
+    /// Keep `plan` in the session's plan history; a plan that changed shape
+    /// since the statement was last explained goes on the activity trail.
+    fn record_plan(&mut self, key: PlanKey, plan: QueryPlan) {
+        let at = chrono::Utc::now();
+        let Some(diff) = self.plan_history.push(key.clone(), plan, at) else {
+            return;
+        };
+        if diff.flipped() {
+            let statement = key.describe();
+            self.feedback.status_message = Some(format!("Plan changed for {statement}"));
+            self.feedback.ui_events.push(UiEvent {
+                timestamp: at,
+                kind: UiEventKind::PlanChanged {
+                    statement,
+                    summary: diff.summary(),
+                },
+                via: None,
+            });
+        }
     }
 
-    /// Get PIDs of all queries matching the current filter
-    pub fn get_filtered_pids(&self) -> Vec<i32> {
-        let Some(snap) = &self.snapshot else {
-            return vec![];
-        };
-        let indices = self.sorted_query_indices();
-        indices
-            .iter()
-            .map(|&i| snap.active_queries[i].pid)
-            .collect()
+    /// Earlier plans of the statement in the EXPLAIN overlay, oldest first,
+    /// ending with the one shown.
+    pub fn explain_history(&self) -> &[PlanRun] {
+        self.explain_key
+            .as_ref()
+            .map_or(&[], |key| self.plan_history.runs(key))
     }
 
-    pub fn sorted_index_indices(&self) -> Vec<usize> {
-        let Some(snap) = &self.snapshot else {
-            return vec![];
+    /// Ask hypopg what indexes would do for the selected statement.
+    fn advise_selected_statement(&mut self) {
+        if !self.server_info.extensions.hypopg {
+            self.feedback.status_message = Some("Index advice needs the hypopg extension".into());
+            return;
+        }
+        let Some(query) = self.selected_statement_queryid().and_then(|queryid| {
+            self.snapshot
+                .as_ref()?
+                .stat_statements
+                .iter()
+                .find(|s| s.queryid == queryid)
+                .map(|s| s.query.clone())
+        }) else {
+            return;
         };
-        let mut indices = self.filtered_indices(&snap.indexes, BottomPanel::Indexes);
+        self.overlay_scroll = 0;
+        self.index_advice = Some(IndexAdviceView::Loading(query.clone()));
+        self.view_mode = ViewMode::IndexAdvice;
+        self.feedback.pending_action = Some(AppAction::AdviseIndexes(query));
+    }
 
-        let asc = self.panels.indexes.sort_ascending;
-        let idx = &snap.indexes;
-        match self.panels.indexes.sort_column {
-            IndexSortColumn::Scans => sort_by_key(&mut indices, idx, asc, |x| x.idx_scan),
-            IndexSortColumn::Size => sort_by_key(&mut indices, idx, asc, |x| x.index_size_bytes),
-            IndexSortColumn::Name => sort_by_key(&mut indices, idx, asc, |x| x.index_name.clone()),
-            IndexSortColumn::TupRead => sort_by_key(&mut indices, idx, asc, |x| x.idx_tup_read),
-            IndexSortColumn::TupFetch => sort_by_key(&mut indices, idx, asc, |x| x.idx_tup_fetch),
-        }
-        indices
+    /// Fill the index advice overlay.
+    pub fn set_index_advice_result(&mut self, result: Result<IndexAdvice, String>) {
+        let query = match &self.index_advice {
+            Some(IndexAdviceView::Loading(query)) => query.clone(),
+            // Closed or replaced while planning
+            _ => return,
+        };
+        self.index_advice = Some(match result {
+            Ok(advice) => IndexAdviceView::Advice(advice),
+            Err(error) => IndexAdviceView::Failed { query, error },
+        });
     }
 
-    pub fn sorted_stmt_indices(&self) -> Vec<usize> {
-        let Some(snap) = &self.snapshot else {
-            return vec![];
+    /// Ask for the definition of the table inspected as `key`.
+    fn request_table_schema(&mut self, key: &str) {
+        let Some(table) = self.snapshot.as_ref().and_then(|snap| {
+            snap.table_stats
+                .iter()
+                .find(|t| format!("{}.{}", t.schemaname, t.relname) == key)
+        }) else {
+            return;
         };
-        let mut indices = self.filtered_indices(&snap.stat_statements, BottomPanel::Statements);
+        self.feedback.pending_action = Some(AppAction::DescribeTable {
+            schema: table.schemaname.clone(),
+            table: table.relname.clone(),
+        });
+        self.table_schema = Some(TableSchemaView::Loading(key.to_string()));
+    }
 
-        let asc = self.panels.statements.sort_ascending;
-        let s = &snap.stat_statements;
-        match self.panels.statements.sort_column {
-            StatementSortColumn::TotalTime => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.total_exec_time)
-            }
-            StatementSortColumn::MeanTime => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.mean_exec_time)
-            }
-            StatementSortColumn::MaxTime => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.max_exec_time)
-            }
-            StatementSortColumn::Stddev => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.stddev_exec_time)
-            }
-            StatementSortColumn::Calls => sort_by_key(&mut indices, s, asc, |x| x.calls),
-            StatementSortColumn::Rows => sort_by_key(&mut indices, s, asc, |x| x.rows),
-            StatementSortColumn::HitRatio => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.hit_ratio)
-            }
-            StatementSortColumn::SharedReads => {
-                sort_by_key(&mut indices, s, asc, |x| x.shared_blks_read)
-            }
-            StatementSortColumn::IoTime => {
-                sort_by_key_partial(&mut indices, s, asc, |x| x.blk_read_time + x.blk_write_time)
-            }
-            StatementSortColumn::Temp => {
-                sort_by_key(&mut indices, s, asc, |x| x.temp_blks_read + x.temp_blks_written)
-            }
+    pub fn set_table_schema(&mut self, key: String, result: Result<TableSchema, String>) {
+        // Closed or moved on to another table meanwhile
+        if self.table_schema != Some(TableSchemaView::Loading(key.clone())) {
+            return;
         }
-        indices
+        self.table_schema = Some(match result {
+            Ok(schema) => TableSchemaView::Loaded { key, schema },
+            Err(error) => TableSchemaView::Failed { key, error },
+        });
     }
 
-    pub fn sorted_table_stat_indices(&self) -> Vec<usize> {
-        let Some(snap) = &self.snapshot else {
-            return vec![];
-        };
-        let mut indices = self.filtered_indices(&snap.table_stats, BottomPanel::TableStats);
+    /// Ask for the privileges held by `role`.
+    fn request_role_grants(&mut self, role: &str) {
+        self.feedback.pending_action = Some(AppAction::ReadRoleGrants(role.to_string()));
+        self.role_grants = Some(RoleGrantsView::Loading(role.to_string()));
+    }
 
-        let asc = self.panels.table_stats.sort_ascending;
-        let t = &snap.table_stats;
-        match self.panels.table_stats.sort_column {
-            TableStatSortColumn::DeadTuples => sort_by_key(&mut indices, t, asc, |x| x.n_dead_tup),
-            TableStatSortColumn::Size => sort_by_key(&mut indices, t, asc, |x| x.total_size_bytes),
-            TableStatSortColumn::Name => sort_by_key(&mut indices, t, asc, |x| x.relname.clone()),
-            TableStatSortColumn::SeqScan => sort_by_key(&mut indices, t, asc, |x| x.seq_scan),
-            TableStatSortColumn::IdxScan => sort_by_key(&mut indices, t, asc, |x| x.idx_scan),
-            TableStatSortColumn::DeadRatio => {
-                sort_by_key_partial(&mut indices, t, asc, |x| x.dead_ratio)
-            }
+    pub fn set_role_grants(&mut self, role: String, result: Result<Vec<RoleGrant>, String>) {
+        // Closed or moved on to another role meanwhile
+        if self.role_grants != Some(RoleGrantsView::Loading(role.clone())) {
+            return;
         }
-        indices
+        self.role_grants = Some(match result {
+            Ok(grants) => RoleGrantsView::Loaded { role, grants },
+            Err(error) => RoleGrantsView::Failed { role, error },
+        });
     }
 
-    pub fn sorted_settings_indices(&self) -> Vec<usize> {
-        // Settings are already sorted by category, name from the query
-        self.filtered_indices(&self.server_info.settings, BottomPanel::Settings)
+    /// Ask for the relations driving `datname`'s XID age.
+    fn request_wraparound_tables(&mut self, datname: &str) {
+        self.feedback.pending_action = Some(AppAction::ReadWraparoundTables(datname.to_string()));
+        self.wraparound_tables = Some(WraparoundTablesView::Loading(datname.to_string()));
     }
 
-    pub fn sorted_extensions_indices(&self) -> Vec<usize> {
-        // Extensions are already sorted by name from the query
-        self.filtered_indices(&self.server_info.extensions_list, BottomPanel::Extensions)
+    pub fn set_wraparound_tables(&mut self, datname: String, result: Result<Vec<WraparoundTable>, String>) {
+        // Closed or moved on to another database meanwhile
+        if self.wraparound_tables != Some(WraparoundTablesView::Loading(datname.clone())) {
+            return;
+        }
+        self.wraparound_tables = Some(match result {
+            Ok(tables) => WraparoundTablesView::Loaded { datname, tables },
+            Err(error) => WraparoundTablesView::Failed { datname, error },
+        });
     }
 
-    fn copy_to_clipboard(&mut self, text: &str) {
-        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
-            Ok(()) => {
+    /// `VACUUM (FREEZE)` statements for `datname`'s freeze candidates, one
+    /// per line, once its tables have been read.
+    pub fn wraparound_freeze_sql(&self, datname: &str) -> Option<String> {
+        let Some(WraparoundTablesView::Loaded { datname: loaded, tables }) = &self.wraparound_tables else {
+            return None;
+        };
+        if loaded != datname {
+            return None;
+        }
+        let candidates = self.autovacuum_settings().freeze_candidates(tables);
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates.into_iter().map(freeze_sql).collect::<Vec<_>>().join("\n"))
+    }
+
+    fn read_buffer_cache(&mut self) {
+        if !self.server_info.extensions.pg_buffercache {
+            self.feedback.status_message = Some("pg_buffercache is not installed".into());
+            return;
+        }
+        self.feedback.pending_action = Some(AppAction::ReadBufferCache);
+        self.feedback.status_message = Some("Reading shared buffers...".into());
+        self.feedback.buffer_cache_loading = true;
+    }
+
+    pub fn set_buffer_cache(&mut self, result: Result<BufferCacheReport, String>) {
+        self.feedback.buffer_cache_loading = false;
+        match result {
+            Ok(report) => {
+                self.feedback.status_message = Some(format!(
+                    "Buffer cache read ({} of {} buffers in use)",
+                    report.total_buffers - report.unused_buffers,
+                    report.total_buffers
+                ));
+                self.buffer_cache = Some(report);
+                self.panels.buffer_cache.select(Some(0));
+            }
+            Err(e) => {
+                self.feedback.status_message = Some(format!("Reading pg_buffercache failed: {e}"));
+            }
+        }
+    }
+
+    /// Measure the bloat of the table inspected as `key` with `pgstattuple()`.
+    fn measure_table_bloat(&mut self, key: &str) {
+        if self.replay.is_some() {
+            return;
+        }
+        if !self.server_info.extensions.pgstattuple {
+            self.feedback.status_message = Some("pgstattuple is not installed".into());
+            return;
+        }
+        let Some(table) = self.inspected_table(key) else {
+            return;
+        };
+        self.feedback.pending_action = Some(AppAction::MeasureBloat {
+            schema: table.schemaname.clone(),
+            table: table.relname.clone(),
+        });
+        self.feedback.status_message = Some(format!("Measuring {key} with pgstattuple..."));
+        self.feedback.bloat_loading = true;
+    }
+
+    /// Maintenance SQL for the inspected table or index: to the clipboard,
+    /// or with `to_file` to a `.sql` file in the exports directory.
+    fn generate_maintenance(&mut self, maintenance: Option<Maintenance>, to_file: bool) {
+        let Some(maintenance) = maintenance else {
+            return;
+        };
+        let Some(sql) = maintenance.sql(self.server_info.major_version()) else {
+            self.feedback.status_message =
+                Some(format!("Can't rebuild {} from its definition", maintenance.relation()));
+            return;
+        };
+        if to_file {
+            let name = format!(
+                "{}_{}.sql",
+                maintenance.file_stem(),
+                chrono::Local::now().format("%Y%m%d_%H%M%S")
+            );
+            let path = crate::export::default_exports_dir().join(name);
+            self.feedback.pending_action = Some(AppAction::WriteScript { path, sql: sql + "\n" });
+        } else {
+            self.copy_to_clipboard(&sql);
+        }
+    }
+
+    /// Ask before running a maintenance command, unless it can't run here.
+    fn confirm_maintenance(&mut self, maintenance: Option<Maintenance>) {
+        let Some(maintenance) = maintenance else {
+            return;
+        };
+        if self.replay.is_some() {
+            return;
+        }
+        if !maintenance.runnable(self.server_info.major_version()) {
+            if matches!(maintenance, Maintenance::Reindex { .. }) {
+                self.feedback.status_message =
+                    Some("REINDEX blocks writes before PostgreSQL 12: copy it with r and run it yourself".into());
+            }
+            return;
+        }
+        if !self.allow_maintenance {
+            self.feedback.status_message = Some(format!(
+                "Start with --allow-maintenance to run {} from here",
+                maintenance.label()
+            ));
+            return;
+        }
+        if let Some(running) = &self.feedback.maintenance {
+            self.feedback.status_message =
+                Some(format!("{} is still running", running.command.describe()));
+            return;
+        }
+        self.view_mode = ViewMode::Confirm(ConfirmAction::RunMaintenance(maintenance));
+    }
+
+    /// A maintenance command was handed to its connection.
+    pub fn start_maintenance(&mut self, command: Maintenance) {
+        self.feedback.maintenance = Some(RunningMaintenance {
+            command,
+            started: Instant::now(),
+            pid: None,
+            progress: None,
+        });
+    }
+
+    /// The maintenance command ended; say how it went.
+    pub fn finish_maintenance(&mut self, command: &Maintenance, result: Result<(), String>) {
+        let elapsed = self
+            .feedback
+            .maintenance
+            .take()
+            .map_or(0.0, |running| running.started.elapsed().as_secs_f64());
+        self.feedback.status_message = Some(match result {
+            Ok(()) => format!("{} finished in {}", command.describe(), crate::ui::util::format_duration(elapsed)),
+            Err(e) => format!("{} failed: {e}", command.describe()),
+        });
+    }
+
+    /// Progress view row of the running maintenance command, once its
+    /// backend shows up there. Read on the server running it, which with
+    /// `--primary` is not the one in the snapshot.
+    pub fn maintenance_progress(&self) -> Option<&OperationProgress> {
+        self.feedback.maintenance.as_ref()?.progress.as_ref()
+    }
+
+    fn inspected_table(&self, key: &str) -> Option<&TableStat> {
+        self.snapshot
+            .as_ref()?
+            .table_stats
+            .iter()
+            .find(|t| format!("{}.{}", t.schemaname, t.relname) == key)
+    }
+
+    fn inspected_index(&self, key: &str) -> Option<&IndexInfo> {
+        self.snapshot
+            .as_ref()?
+            .indexes
+            .iter()
+            .find(|i| format!("{}.{}", i.schemaname, i.index_name) == key)
+    }
+
+    pub fn set_measured_bloat(&mut self, schema: &str, table: &str, result: Result<MeasuredBloat, String>) {
+        self.feedback.bloat_loading = false;
+        let key = format!("{schema}.{table}");
+        match result {
+            Ok(measured) => {
+                let (tables, indexes) = measured_bloat_estimates(schema, table, &measured);
+                self.apply_bloat_data(&tables, &indexes);
+                self.feedback.status_message = Some(format!(
+                    "Measured {key}: {:.1}% bloat ({})",
+                    measured.bloat_pct(),
+                    crate::ui::util::format_bytes(measured.bloat_bytes())
+                ));
+                self.measured_bloat.insert(key, measured);
+            }
+            Err(e) => {
+                self.feedback.status_message = Some(format!("pgstattuple on {key} failed: {e}"));
+            }
+        }
+    }
+
+    /// `e` explains in the panels that list SQL; elsewhere it exports graphs.
+    /// How many times slower than its baseline `stmt` runs, when that is
+    /// past the configured regression factor.
+    pub fn statement_regression(&self, stmt: &StatStatement) -> Option<f64> {
+        self.baselines.regression(stmt, self.config.regression_factor)
+    }
+
+    /// Take new baselines from every statement in the current snapshot.
+    fn rebaseline_statements(&mut self) {
+        let Some(snap) = &self.snapshot else {
+            return;
+        };
+        let count = self.baselines.rebaseline(&snap.stat_statements, snap.timestamp);
+        self.feedback.status_message = Some(format!("Baselined {count} statements"));
+        self.feedback.pending_action = Some(AppAction::SaveBaselines);
+    }
+
+    pub fn e_explains(&self) -> bool {
+        self.replay.is_none()
+            && matches!(self.bottom_panel, BottomPanel::Queries | BottomPanel::Statements)
+    }
+
+    fn toggle_show_all_backends(&mut self) {
+        self.show_all_backends = !self.show_all_backends;
+        self.feedback.status_message = Some(
+            if self.show_all_backends {
+                "Fetching all idle connections"
+            } else {
+                "Capping idle connections"
+            }
+            .to_string(),
+        );
+        self.feedback.pending_action = Some(AppAction::ForceRefresh);
+    }
+
+    fn toggle_fingerprint_grouping(&mut self) {
+        self.group_by_fingerprint = !self.group_by_fingerprint;
+        self.panels.queries.select_first();
+        self.feedback.status_message = Some(
+            if self.group_by_fingerprint {
+                "Grouping queries that differ only in literals"
+            } else {
+                "Showing every backend"
+            }
+            .to_string(),
+        );
+    }
+
+    fn toggle_show_ignored(&mut self) {
+        self.show_ignored = !self.show_ignored;
+        self.feedback.status_message = Some(
+            if self.show_ignored {
+                "Showing ignored entries"
+            } else {
+                "Hiding ignored entries"
+            }
+            .to_string(),
+        );
+    }
+
+    pub fn selected_table_key(&self) -> Option<String> {
+        let snap = self.snapshot.as_ref()?;
+        let idx = self.panels.table_stats.selected().or(Some(0))?;
+        let indices = self.sorted_table_stat_indices();
+        let &real_idx = indices.get(idx)?;
+        let table = &snap.table_stats[real_idx];
+        Some(format!("{}.{}", table.schemaname, table.relname))
+    }
+
+    pub fn selected_replication_pid(&self) -> Option<i32> {
+        let snap = self.snapshot.as_ref()?;
+        let sel = self.panels.replication.selected().or(Some(0))?;
+        Some(snap.replication.get(sel)?.pid)
+    }
+
+    pub fn selected_blocking_pid(&self) -> Option<i32> {
+        let snap = self.snapshot.as_ref()?;
+        let sel = self.panels.blocking.selected().or(Some(0))?;
+        Some(snap.blocking_info.get(sel)?.blocked_pid)
+    }
+
+    pub fn selected_vacuum_pid(&self) -> Option<i32> {
+        let snap = self.snapshot.as_ref()?;
+        let sel = self.panels.vacuum.selected().or(Some(0))?;
+        Some(snap.vacuum_progress.get(sel)?.pid)
+    }
+
+    pub fn selected_wraparound_datname(&self) -> Option<String> {
+        let snap = self.snapshot.as_ref()?;
+        let sel = self.panels.wraparound.selected().or(Some(0))?;
+        Some(snap.wraparound.get(sel)?.datname.clone())
+    }
+
+    pub fn selected_setting_name(&self) -> Option<String> {
+        let indices = self.sorted_settings_indices();
+        let idx = self.panels.settings.selected().or(Some(0))?;
+        let &real_idx = indices.get(idx)?;
+        Some(self.server_info.settings[real_idx].name.clone())
+    }
+
+    pub fn selected_extension_name(&self) -> Option<String> {
+        let indices = self.sorted_extensions_indices();
+        let idx = self.panels.extensions.selected().or(Some(0))?;
+        let &real_idx = indices.get(idx)?;
+        Some(self.server_info.extensions_list[real_idx].name.clone())
+    }
+
+    pub fn selected_role_name(&self) -> Option<String> {
+        let indices = self.sorted_role_indices();
+        let idx = self.panels.roles.selected().or(Some(0))?;
+        let &real_idx = indices.get(idx)?;
+        Some(self.server_info.roles[real_idx].name.clone())
+    }
+
+    /// Get PIDs of all queries matching the current filter
+    pub fn get_filtered_pids(&self) -> Vec<i32> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        // Every member of a fingerprint group, not just the row shown
+        let indices = self.listed_query_indices();
+        indices
+            .iter()
+            .map(|&i| snap.active_queries[i].pid)
+            .collect()
+    }
+
+    pub fn sorted_index_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.indexes, BottomPanel::Indexes);
+
+        let asc = self.panels.indexes.sort_ascending;
+        let idx = &snap.indexes;
+        match self.panels.indexes.sort_column {
+            IndexSortColumn::Scans => sort_by_key(&mut indices, idx, asc, |x| x.idx_scan),
+            IndexSortColumn::Size => sort_by_key(&mut indices, idx, asc, |x| x.index_size_bytes),
+            IndexSortColumn::Name => sort_by_key(&mut indices, idx, asc, |x| x.index_name.clone()),
+            IndexSortColumn::TupRead => sort_by_key(&mut indices, idx, asc, |x| x.idx_tup_read),
+            IndexSortColumn::TupFetch => sort_by_key(&mut indices, idx, asc, |x| x.idx_tup_fetch),
+        }
+        indices
+    }
+
+    pub fn sorted_stmt_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.stat_statements, BottomPanel::Statements);
+        if !self.show_ignored {
+            let ignore = &self.config.ignore;
+            indices.retain(|&i| !ignore.is_statement_ignored(snap.stat_statements[i].queryid));
+        }
+
+        let asc = self.panels.statements.sort_ascending;
+        let s = &snap.stat_statements;
+        match self.panels.statements.sort_column {
+            StatementSortColumn::TotalTime => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.total_exec_time)
+            }
+            StatementSortColumn::MeanTime => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.mean_exec_time)
+            }
+            StatementSortColumn::MaxTime => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.max_exec_time)
+            }
+            StatementSortColumn::Stddev => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.stddev_exec_time)
+            }
+            StatementSortColumn::Calls => sort_by_key(&mut indices, s, asc, |x| x.calls),
+            StatementSortColumn::Rows => sort_by_key(&mut indices, s, asc, |x| x.rows),
+            StatementSortColumn::HitRatio => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.hit_ratio)
+            }
+            StatementSortColumn::SharedReads => {
+                sort_by_key(&mut indices, s, asc, |x| x.shared_blks_read)
+            }
+            StatementSortColumn::IoTime => {
+                sort_by_key_partial(&mut indices, s, asc, |x| x.blk_read_time + x.blk_write_time)
+            }
+            StatementSortColumn::Temp => {
+                sort_by_key(&mut indices, s, asc, |x| x.temp_blks_read + x.temp_blks_written)
+            }
+        }
+        indices
+    }
+
+    pub fn sorted_table_stat_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.table_stats, BottomPanel::TableStats);
+        if !self.show_ignored {
+            let ignore = &self.config.ignore;
+            let t = &snap.table_stats;
+            indices.retain(|&i| !ignore.is_table_ignored(&t[i].schemaname, &t[i].relname));
+        }
+
+        let asc = self.panels.table_stats.sort_ascending;
+        let t = &snap.table_stats;
+        match self.panels.table_stats.sort_column {
+            TableStatSortColumn::DeadTuples => sort_by_key(&mut indices, t, asc, |x| x.n_dead_tup),
+            TableStatSortColumn::Size => sort_by_key(&mut indices, t, asc, |x| x.total_size_bytes),
+            TableStatSortColumn::Name => sort_by_key(&mut indices, t, asc, |x| x.relname.clone()),
+            TableStatSortColumn::SeqScan => sort_by_key(&mut indices, t, asc, |x| x.seq_scan),
+            TableStatSortColumn::IdxScan => sort_by_key(&mut indices, t, asc, |x| x.idx_scan),
+            TableStatSortColumn::DeadRatio => {
+                sort_by_key_partial(&mut indices, t, asc, |x| x.dead_ratio)
+            }
+        }
+        indices
+    }
+
+    pub fn sorted_lock_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.locks, BottomPanel::Locks);
+        let asc = self.panels.locks.sort_ascending;
+        let l = &snap.locks;
+        match self.panels.locks.sort_column {
+            // Waiting first; the query already orders each group by duration
+            LockSortColumn::Status => sort_by_key(&mut indices, l, asc, |x| !x.granted),
+            LockSortColumn::Duration => sort_by_key_partial(&mut indices, l, asc, |x| x.duration_secs),
+            LockSortColumn::Mode => sort_by_key(&mut indices, l, asc, |x| x.mode.clone()),
+            LockSortColumn::Relation => sort_by_key(&mut indices, l, asc, |x| x.relation.clone()),
+            LockSortColumn::Pid => sort_by_key(&mut indices, l, asc, |x| x.pid),
+        }
+        indices
+    }
+
+    /// Backends waiting for the lock at `idx` in the snapshot's lock list.
+    pub fn lock_waiters(&self, idx: usize) -> Vec<i32> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let Some(lock) = snap.locks.get(idx).filter(|l| l.granted) else {
+            return vec![];
+        };
+        let mut pids: Vec<i32> = snap
+            .locks
+            .iter()
+            .filter(|w| {
+                !w.granted
+                    && w.locktype == lock.locktype
+                    && w.relation == lock.relation
+                    && w.blocking_pids.contains(&lock.pid)
+            })
+            .map(|w| w.pid)
+            .collect();
+        pids.sort_unstable();
+        pids.dedup();
+        pids
+    }
+
+    /// First backend holding the selected lock, for jumping to its query.
+    pub fn selected_lock_holder(&self) -> Option<i32> {
+        let snap = self.snapshot.as_ref()?;
+        let idx = self.panels.locks.selected().or(Some(0))?;
+        let &real_idx = self.sorted_lock_indices().get(idx)?;
+        snap.locks[real_idx].holder_pids().first().copied()
+    }
+
+    pub fn sorted_connection_group_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.connection_groups, BottomPanel::ConnectionGroups);
+        let asc = self.panels.connection_groups.sort_ascending;
+        let g = &snap.connection_groups;
+        match self.panels.connection_groups.sort_column {
+            ConnectionGroupSortColumn::Total => sort_by_key(&mut indices, g, asc, |x| x.total),
+            ConnectionGroupSortColumn::Active => sort_by_key(&mut indices, g, asc, |x| x.active),
+            ConnectionGroupSortColumn::IdleInTransaction => {
+                sort_by_key(&mut indices, g, asc, |x| x.idle_in_transaction);
+            }
+            ConnectionGroupSortColumn::Longest => sort_by_key_partial(&mut indices, g, asc, |x| x.longest_secs),
+            ConnectionGroupSortColumn::User => sort_by_key(&mut indices, g, asc, |x| x.usename.clone()),
+        }
+        indices
+    }
+
+    /// Narrow the Queries list to the selected Connections group.
+    fn drill_into_connection_group(&mut self) {
+        let Some(snap) = &self.snapshot else {
+            return;
+        };
+        let idx = self.panels.connection_groups.selected().unwrap_or(0);
+        let Some(&real_idx) = self.sorted_connection_group_indices().get(idx) else {
+            return;
+        };
+        let group = snap.connection_groups[real_idx].clone();
+        let listed = snap.active_queries.iter().filter(|q| group.contains(q)).count();
+        let mut message = format!("Queries from {}; Backspace shows all", group.label());
+        if listed < group.total as usize && !self.show_all_backends && self.replay.is_none() {
+            message.push_str(&format!(" ({listed} of {} listed, a fetches all)", group.total));
+        }
+        self.feedback.status_message = Some(message);
+        self.query_scope = Some(QueryScope::Group(group));
+        self.panels.queries.select_first();
+        self.go_to_panel(BottomPanel::Queries);
+    }
+
+    /// Backends counted under a Wait Events row, longest running first.
+    pub fn wait_event_backends(&self, wait_event_type: &str, wait_event: &str) -> Vec<&ActiveQuery> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let counted = WaitEventCount {
+            wait_event_type: wait_event_type.to_string(),
+            wait_event: wait_event.to_string(),
+            count: 0,
+        };
+        let mut backends: Vec<&ActiveQuery> =
+            snap.active_queries.iter().filter(|q| counted.contains(q)).collect();
+        backends.sort_by(|a, b| b.duration_secs.total_cmp(&a.duration_secs));
+        backends
+    }
+
+    /// Narrow the Queries list to the backends in the inspected wait event.
+    fn drill_into_wait_event(&mut self, wait_event_type: &str, wait_event: &str) {
+        let pids: Vec<i32> = self
+            .wait_event_backends(wait_event_type, wait_event)
+            .iter()
+            .map(|q| q.pid)
+            .collect();
+        if pids.is_empty() {
+            self.feedback.status_message = Some(format!("No backends waiting on {wait_event} right now"));
+            return;
+        }
+        let label = if wait_event_type == wait_event {
+            wait_event.to_string()
+        } else {
+            format!("{wait_event_type}:{wait_event}")
+        };
+        self.feedback.status_message = Some(format!("Queries waiting on {label}; Backspace shows all"));
+        self.query_scope = Some(QueryScope::Pids { label, pids });
+        self.overlay_scroll = 0;
+        self.view_mode = ViewMode::Normal;
+        self.panels.queries.select_first();
+        self.go_to_panel(BottomPanel::Queries);
+    }
+
+    pub fn sorted_slot_indices(&self) -> Vec<usize> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut indices = self.filtered_indices(&snap.replication_slots, BottomPanel::Slots);
+        let asc = self.panels.slots.sort_ascending;
+        let s = &snap.replication_slots;
+        match self.panels.slots.sort_column {
+            SlotSortColumn::Retained => sort_by_key(&mut indices, s, asc, |x| x.wal_retained_bytes),
+            SlotSortColumn::Spill => sort_by_key(&mut indices, s, asc, |x| x.spill_bytes),
+            // Inactive first: those are the ones holding WAL for nobody
+            SlotSortColumn::Active => sort_by_key(&mut indices, s, asc, |x| !x.active),
+            SlotSortColumn::Name => sort_by_key(&mut indices, s, asc, |x| x.slot_name.clone()),
+        }
+        indices
+    }
+
+    pub fn selected_slot(&self) -> Option<&ReplicationSlot> {
+        let snap = self.snapshot.as_ref()?;
+        let idx = self.panels.slots.selected().or(Some(0))?;
+        let &real_idx = self.sorted_slot_indices().get(idx)?;
+        snap.replication_slots.get(real_idx)
+    }
+
+    pub fn sorted_settings_indices(&self) -> Vec<usize> {
+        // Settings are already sorted by category, name from the query
+        let mut indices = self.filtered_indices(&self.server_info.settings, BottomPanel::Settings);
+        if self.settings_changed_only {
+            indices.retain(|&i| self.server_info.settings[i].is_changed());
+        }
+        indices
+    }
+
+    pub fn sorted_role_settings_indices(&self) -> Vec<usize> {
+        // Overrides are already sorted by name, role, database from the query
+        self.filtered_indices(&self.server_info.role_settings, BottomPanel::Settings)
+    }
+
+    /// Rows of the Logs panel: newest message first, through the level
+    /// filter and the panel's fuzzy filter.
+    pub fn log_indices(&self) -> Vec<usize> {
+        let matching: HashSet<usize> =
+            self.filtered_indices(&self.server_log, BottomPanel::Logs).into_iter().collect();
+        server_log::visible(&self.server_log, self.log_filter)
+            .into_iter()
+            .filter(|i| matching.contains(i))
+            .collect()
+    }
+
+    pub fn sorted_extensions_indices(&self) -> Vec<usize> {
+        // Extensions are already sorted by name from the query
+        self.filtered_indices(&self.server_info.extensions_list, BottomPanel::Extensions)
+    }
+
+    pub fn sorted_role_indices(&self) -> Vec<usize> {
+        // Roles are already sorted by name from the query
+        self.filtered_indices(&self.server_info.roles, BottomPanel::Roles)
+    }
+
+    /// Backends per database connected as `role`, busiest first.
+    pub fn role_connections(&self, role: &str) -> Vec<(String, i64)> {
+        let Some(snap) = &self.snapshot else {
+            return vec![];
+        };
+        let mut by_db: HashMap<String, i64> = HashMap::new();
+        for group in &snap.connection_groups {
+            if group.usename.as_deref() == Some(role) {
+                let db = group.datname.clone().unwrap_or_else(|| "-".into());
+                *by_db.entry(db).or_default() += group.total;
+            }
+        }
+        let mut counts: Vec<(String, i64)> = by_db.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str) {
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
+            Ok(()) => {
                 let preview: String = text.chars().take(CLIPBOARD_PREVIEW_LEN).collect();
                 let suffix = if text.len() > CLIPBOARD_PREVIEW_LEN { "..." } else { "" };
                 self.feedback.status_message = Some(format!("Copied: {preview}{suffix}"));
@@ -475,13 +1585,80 @@ impl App {
     fn switch_panel(&mut self, target: BottomPanel) {
         if self.bottom_panel == target {
             // Toggle back to Queries
-            self.bottom_panel = BottomPanel::Queries;
+            self.go_to_panel(BottomPanel::Queries);
         } else {
-            self.bottom_panel = target;
+            self.go_to_panel(target);
+        }
+    }
+
+    /// Show `target`, staying there if it is already on screen.
+    fn go_to_panel(&mut self, target: BottomPanel) {
+        // Asking for the panel in the other pane trades the two
+        if let Some(split) = self.split.as_mut().filter(|split| split.other == target) {
+            split.other = self.bottom_panel;
+        }
+        self.bottom_panel = target;
+        if self.bottom_panel == BottomPanel::Alerts {
+            self.alerts.unseen = false;
         }
-        // Clear filter state when switching panels
-        self.filter.clear();
         self.view_mode = ViewMode::Normal;
+        self.log_ui_event(UiEventKind::PanelSwitch {
+            panel: self.bottom_panel.label().to_string(),
+        });
+    }
+
+    /// `|`: show a second panel beside the focused one, Blocking next to
+    /// Queries and Queries next to anything else, or go back to one.
+    fn toggle_split(&mut self) {
+        if self.split.take().is_some() {
+            self.feedback.status_message = None;
+            return;
+        }
+        let other = if self.bottom_panel == BottomPanel::Queries {
+            BottomPanel::Blocking
+        } else {
+            BottomPanel::Queries
+        };
+        self.split = Some(SplitView { other, focus_right: false });
+        self.feedback.status_message = Some("Split view: Ctrl+w switches pane, | closes".into());
+    }
+
+    /// `Ctrl+w`: move focus to the other pane of the split view.
+    fn switch_split_focus(&mut self) {
+        let Some(split) = self.split.as_mut() else {
+            return;
+        };
+        split.focus_right = !split.focus_right;
+        let other = split.other;
+        self.go_to_panel(other);
+    }
+
+    /// Queue an operator action for the recorder (live mode only).
+    pub fn log_ui_event(&mut self, kind: UiEventKind) {
+        if self.replay.is_none() {
+            self.feedback.ui_events.push(UiEvent {
+                timestamp: chrono::Utc::now(),
+                kind,
+                via: None,
+            });
+        }
+    }
+
+    /// Queue an action that ran against the database, naming the
+    /// connection that ran it when that isn't the monitored one: only
+    /// maintenance goes to `--primary`.
+    pub fn log_action_event(&mut self, kind: UiEventKind) {
+        if self.replay.is_none() {
+            let via = match kind {
+                UiEventKind::Maintenance { .. } => self.connection.actions_via.clone(),
+                _ => None,
+            };
+            self.feedback.ui_events.push(UiEvent {
+                timestamp: chrono::Utc::now(),
+                kind,
+                via,
+            });
+        }
     }
 
     fn reset_panel_selection(&mut self) {
@@ -514,10 +1691,26 @@ impl App {
                     self.view_mode = ViewMode::Inspect(InspectTarget::Query(pid));
                 }
             }
+            (KeyCode::Char('o'), _) => {
+                if let Some(pid) = self.selected_query_pid() {
+                    self.expanded_query = (self.expanded_query != Some(pid)).then_some(pid);
+                    self.expanded_scroll = 0;
+                }
+            }
+            (KeyCode::Down, KeyModifiers::SHIFT) if self.expanded_query.is_some() => {
+                // Clamped to the text's length when drawn
+                self.expanded_scroll += 1;
+            }
+            (KeyCode::Up, KeyModifiers::SHIFT) if self.expanded_query.is_some() => {
+                self.expanded_scroll = self.expanded_scroll.saturating_sub(1);
+            }
+            (KeyCode::Char('a'), _) if self.replay.is_none() => self.toggle_show_all_backends(),
+            (KeyCode::Char('f'), _) => self.toggle_fingerprint_grouping(),
+            (KeyCode::Char('e'), _) if self.replay.is_none() => self.explain_selected_query(),
             (KeyCode::Char('K'), _) if self.replay.is_none() => {
                 if let Some(pid) = self.selected_query_pid() {
                     let filtered_pids = self.get_filtered_pids();
-                    if self.filter.active && filtered_pids.len() > 1 {
+                    if self.filter().active && filtered_pids.len() > 1 {
                         // Multiple matches - show choice dialog
                         self.view_mode = ViewMode::Confirm(ConfirmAction::KillChoice {
                             selected_pid: pid,
@@ -532,7 +1725,7 @@ impl App {
             (KeyCode::Char('C'), _) if self.replay.is_none() => {
                 if let Some(pid) = self.selected_query_pid() {
                     let filtered_pids = self.get_filtered_pids();
-                    if self.filter.active && filtered_pids.len() > 1 {
+                    if self.filter().active && filtered_pids.len() > 1 {
                         // Multiple matches - show choice dialog
                         self.view_mode = ViewMode::Confirm(ConfirmAction::CancelChoice {
                             selected_pid: pid,
@@ -544,6 +1737,7 @@ impl App {
                     }
                 }
             }
+            (KeyCode::Char('X'), _) if self.replay.is_none() => self.confirm_terminate_idle_txn(),
             (KeyCode::Char('s'), _) => {
                 self.panels.queries.cycle_sort();
                 self.panels.queries.select_first();
@@ -601,137 +1795,495 @@ impl App {
         }
     }
 
-    fn handle_statements_key(&mut self, key: KeyEvent) {
+    fn handle_statements_key(&mut self, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.panels.statements.select_prev();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                let max = self.sorted_stmt_indices().len();
+                self.panels.statements.select_next(max);
+            }
+            (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
+                self.panels.statements.select_page_up(PAGE_SIZE);
+            }
+            (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
+                let max = self.sorted_stmt_indices().len();
+                self.panels.statements.select_page_down(max, PAGE_SIZE);
+            }
+            (KeyCode::Enter, _) => {
+                if let Some(queryid) = self.selected_statement_queryid() {
+                    self.overlay_scroll = 0;
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Statement(queryid));
+                }
+            }
+            (KeyCode::Char('s'), _) => {
+                self.panels.statements.cycle_sort();
+                self.panels.statements.select_first();
+                self.feedback.status_message = Some(format!(
+                    "Sort: {} {}",
+                    self.panels.statements.sort_column.label(),
+                    if self.panels.statements.sort_ascending {
+                        "\u{2191}"
+                    } else {
+                        "\u{2193}"
+                    }
+                ));
+            }
+            (KeyCode::Char('X'), _) if self.replay.is_none() => {
+                self.view_mode = ViewMode::Confirm(ConfirmAction::ResetStatStatements);
+            }
+            (KeyCode::Char('e'), _) if self.replay.is_none() => self.explain_selected_statement(),
+            (KeyCode::Char('h'), _) if self.replay.is_none() => self.advise_selected_statement(),
+            (KeyCode::Char('*'), _) => {
+                if let Some(queryid) = self.selected_statement_queryid() {
+                    let msg = if self.starred.toggle(queryid) {
+                        format!("Starred statement {queryid}")
+                    } else {
+                        format!("Unstarred statement {queryid}")
+                    };
+                    self.feedback.status_message = Some(msg);
+                    self.feedback.pending_action = Some(AppAction::SaveStarred);
+                }
+            }
+            (KeyCode::Char('b'), _) => self.rebaseline_statements(),
+            (KeyCode::Char('n'), _) => {
+                if let Some(queryid) = self.selected_statement_queryid() {
+                    self.note_input = self.starred.note(queryid).unwrap_or_default().to_string();
+                    self.view_mode = ViewMode::EditNote(queryid);
+                }
+            }
+            (KeyCode::Char('-'), _) => {
+                if let Some(queryid) = self.selected_statement_queryid() {
+                    let msg = if self.config.ignore.toggle_statement(queryid) {
+                        format!("Ignoring statement {queryid} (H to show ignored)")
+                    } else {
+                        format!("Statement {queryid} no longer ignored")
+                    };
+                    self.feedback.status_message = Some(msg);
+                    self.feedback.pending_action = Some(AppAction::SaveConfig);
+                    let max = self.sorted_stmt_indices().len();
+                    self.panels.statements.clamp_selection(max);
+                }
+            }
+            (KeyCode::Char('H'), _) => self.toggle_show_ignored(),
+            _ => {}
+        }
+    }
+
+    fn handle_table_stats_key(&mut self, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.panels.table_stats.select_prev();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                let max = self.sorted_table_stat_indices().len();
+                self.panels.table_stats.select_next(max);
+            }
+            (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
+                self.panels.table_stats.select_page_up(PAGE_SIZE);
+            }
+            (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
+                let max = self.sorted_table_stat_indices().len();
+                self.panels.table_stats.select_page_down(max, PAGE_SIZE);
+            }
+            (KeyCode::Enter, _) => {
+                if let Some(key) = self.selected_table_key() {
+                    self.overlay_scroll = 0;
+                    if self.replay.is_none() {
+                        self.request_table_schema(&key);
+                    }
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Table(key));
+                }
+            }
+            (KeyCode::Char('s'), _) => {
+                self.panels.table_stats.cycle_sort();
+                self.panels.table_stats.select_first();
+                self.feedback.status_message = Some(format!(
+                    "Sort: {} {}",
+                    self.panels.table_stats.sort_column.label(),
+                    if self.panels.table_stats.sort_ascending {
+                        "\u{2191}"
+                    } else {
+                        "\u{2193}"
+                    }
+                ));
+            }
+            (KeyCode::Char('b'), _) if self.replay.is_none() => {
+                self.feedback.pending_action = Some(AppAction::RefreshBloat);
+                self.feedback.status_message = Some("Refreshing bloat estimates...".to_string());
+                self.feedback.bloat_loading = true;
+            }
+            (KeyCode::Char('-'), _) => {
+                if let Some(key) = self.selected_table_key() {
+                    let (schema, table) = key.split_once('.').unwrap_or(("", &key));
+                    let msg = if self.config.ignore.toggle_table(schema, table) {
+                        format!("Ignoring table {key} (H to show ignored)")
+                    } else {
+                        format!("Table {key} no longer ignored")
+                    };
+                    self.feedback.status_message = Some(msg);
+                    self.feedback.pending_action = Some(AppAction::SaveConfig);
+                    let max = self.sorted_table_stat_indices().len();
+                    self.panels.table_stats.clamp_selection(max);
+                }
+            }
+            (KeyCode::Char('H'), _) => self.toggle_show_ignored(),
+            _ => {}
+        }
+    }
+
+    fn handle_replication_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Replication.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.replication, key, len, PAGE_SIZE) {
+            if let Some(pid) = self.selected_replication_pid() {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Inspect(InspectTarget::Replication(pid));
+            }
+        }
+    }
+
+    fn handle_blocking_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Blocking.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.blocking, key, len, PAGE_SIZE) {
+            if let Some(pid) = self.selected_blocking_pid() {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Inspect(InspectTarget::Blocking(pid));
+            }
+        }
+    }
+
+    fn handle_locks_key(&mut self, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.panels.locks.select_prev();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                let max = self.sorted_lock_indices().len();
+                self.panels.locks.select_next(max);
+            }
+            (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
+                self.panels.locks.select_page_up(PAGE_SIZE);
+            }
+            (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
+                let max = self.sorted_lock_indices().len();
+                self.panels.locks.select_page_down(max, PAGE_SIZE);
+            }
+            (KeyCode::Enter, _) => {
+                let Some(pid) = self.selected_lock_holder() else {
+                    return;
+                };
+                let listed = self
+                    .snapshot
+                    .as_ref()
+                    .is_some_and(|s| s.active_queries.iter().any(|q| q.pid == pid));
+                if listed {
+                    self.overlay_scroll = 0;
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Query(pid));
+                } else {
+                    self.feedback.status_message =
+                        Some(format!("Holder PID {pid} is not in the Queries list"));
+                }
+            }
+            (KeyCode::Char('s'), _) => {
+                self.panels.locks.cycle_sort();
+                self.panels.locks.select_first();
+                // Names read best A-Z, everything else worst first
+                self.panels.locks.sort_ascending = matches!(
+                    self.panels.locks.sort_column,
+                    LockSortColumn::Mode | LockSortColumn::Relation | LockSortColumn::Pid
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_connection_groups_key(&mut self, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.panels.connection_groups.select_prev();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
+                let max = self.sorted_connection_group_indices().len();
+                self.panels.connection_groups.select_next(max);
+            }
+            (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
+                self.panels.connection_groups.select_page_up(PAGE_SIZE);
+            }
+            (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
+                let max = self.sorted_connection_group_indices().len();
+                self.panels.connection_groups.select_page_down(max, PAGE_SIZE);
+            }
+            (KeyCode::Enter, _) => self.drill_into_connection_group(),
+            (KeyCode::Char('s'), _) => {
+                self.panels.connection_groups.cycle_sort();
+                self.panels.connection_groups.select_first();
+                self.panels.connection_groups.sort_ascending =
+                    self.panels.connection_groups.sort_column == ConnectionGroupSortColumn::User;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_slots_key(&mut self, key: KeyEvent) {
         match (key.code, key.modifiers) {
             (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
-                self.panels.statements.select_prev();
+                self.panels.slots.select_prev();
             }
             (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
-                let max = self.sorted_stmt_indices().len();
-                self.panels.statements.select_next(max);
+                let max = self.sorted_slot_indices().len();
+                self.panels.slots.select_next(max);
             }
             (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
-                self.panels.statements.select_page_up(PAGE_SIZE);
+                self.panels.slots.select_page_up(PAGE_SIZE);
             }
             (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
-                let max = self.sorted_stmt_indices().len();
-                self.panels.statements.select_page_down(max, PAGE_SIZE);
+                let max = self.sorted_slot_indices().len();
+                self.panels.slots.select_page_down(max, PAGE_SIZE);
             }
             (KeyCode::Enter, _) => {
-                if let Some(queryid) = self.selected_statement_queryid() {
+                if let Some(slot) = self.selected_slot() {
+                    let name = slot.slot_name.clone();
                     self.overlay_scroll = 0;
-                    self.view_mode = ViewMode::Inspect(InspectTarget::Statement(queryid));
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Slot(name));
                 }
             }
             (KeyCode::Char('s'), _) => {
-                self.panels.statements.cycle_sort();
-                self.panels.statements.select_first();
-                self.feedback.status_message = Some(format!(
-                    "Sort: {} {}",
-                    self.panels.statements.sort_column.label(),
-                    if self.panels.statements.sort_ascending {
-                        "\u{2191}"
-                    } else {
-                        "\u{2193}"
-                    }
-                ));
-            }
-            (KeyCode::Char('X'), _) if self.replay.is_none() => {
-                self.view_mode = ViewMode::Confirm(ConfirmAction::ResetStatStatements);
+                self.panels.slots.cycle_sort();
+                self.panels.slots.select_first();
+                self.panels.slots.sort_ascending = self.panels.slots.sort_column == SlotSortColumn::Name;
             }
+            (KeyCode::Char('X'), _) if self.replay.is_none() => self.confirm_drop_selected_slot(),
             _ => {}
         }
     }
 
-    fn handle_table_stats_key(&mut self, key: KeyEvent) {
+    /// Ask before dropping the selected slot. Only inactive slots qualify:
+    /// dropping one in use would fail, and cutting off a live consumer is
+    /// never what the operator wants.
+    fn confirm_drop_selected_slot(&mut self) {
+        let Some(slot) = self.selected_slot() else {
+            return;
+        };
+        let name = slot.slot_name.clone();
+        if slot.active {
+            self.feedback.status_message = Some(format!(
+                "Slot {name} is in use; stop its consumer before dropping it"
+            ));
+        } else if slot.temporary {
+            self.feedback.status_message = Some(format!(
+                "Slot {name} is temporary and goes away with its session"
+            ));
+        } else {
+            self.view_mode = ViewMode::Confirm(ConfirmAction::DropSlot(name));
+        }
+    }
+
+    fn handle_vacuum_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::VacuumProgress.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.vacuum, key, len, PAGE_SIZE) {
+            if let Some(pid) = self.selected_vacuum_pid() {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Inspect(InspectTarget::Vacuum(pid));
+            }
+        }
+    }
+
+    fn handle_wraparound_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Wraparound.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.wraparound, key, len, PAGE_SIZE) {
+            if let Some(datname) = self.selected_wraparound_datname() {
+                self.overlay_scroll = 0;
+                // relfrozenxid can only be read from the database connected to
+                if self.replay.is_none() && datname == self.connection.dbname {
+                    self.request_wraparound_tables(&datname);
+                }
+                self.view_mode = ViewMode::Inspect(InspectTarget::Wraparound(datname));
+            }
+        }
+    }
+
+    fn handle_operations_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Operations.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.operations, key, len, PAGE_SIZE) {
+            let Some(snap) = &self.snapshot else {
+                return;
+            };
+            let selected = self.panels.operations.selected().unwrap_or(0);
+            let Some(pid) = snap.operations_in_progress().get(selected).map(|op| op.pid) else {
+                return;
+            };
+            if snap.active_queries.iter().any(|q| q.pid == pid) {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Inspect(InspectTarget::Query(pid));
+            } else {
+                self.feedback.status_message = Some(format!("PID {pid} is not in the Queries list"));
+            }
+        }
+    }
+
+    fn handle_wait_events_key(&mut self, key: KeyEvent) {
+        if self.config.wait_sampler && self.replay.is_none() {
+            // The sampled profile has no rows to select
+            return;
+        }
+        let len = (BottomPanel::WaitEvents.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.wait_events, key, len, PAGE_SIZE) {
+            let Some(snap) = &self.snapshot else {
+                return;
+            };
+            let selected = self.panels.wait_events.selected().unwrap_or(0);
+            let Some(wait) = snap.wait_events.get(selected) else {
+                return;
+            };
+            self.overlay_scroll = 0;
+            self.view_mode = ViewMode::Inspect(InspectTarget::WaitEvent(
+                wait.wait_event_type.clone(),
+                wait.wait_event.clone(),
+            ));
+        }
+    }
+
+    fn handle_pooler_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Pooler.descriptor().row_count)(self);
+        // Pools have nothing more to inspect
+        PanelStates::simple_nav(&mut self.panels.pooler, key, len, PAGE_SIZE);
+    }
+
+    fn handle_logical_replication_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::LogicalReplication.descriptor().row_count)(self);
+        PanelStates::simple_nav(&mut self.panels.logical_replication, key, len, PAGE_SIZE);
+    }
+
+    fn handle_backends_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Backends.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.backends, key, len, PAGE_SIZE) {
+            let Some(snap) = &self.snapshot else {
+                return;
+            };
+            let selected = self.panels.backends.selected().unwrap_or(0);
+            let Some(worker) = snap.background_workers.get(selected) else {
+                return;
+            };
+            // A parallel worker opens the query it is helping with
+            let target = worker.leader_pid.filter(|&leader| leader != worker.pid);
+            match target {
+                Some(leader) if snap.active_queries.iter().any(|q| q.pid == leader) => {
+                    self.overlay_scroll = 0;
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Query(leader));
+                }
+                Some(leader) => {
+                    self.feedback.status_message = Some(format!("Leader PID {leader} is not in the Queries list"));
+                }
+                None => {
+                    self.feedback.status_message =
+                        Some(format!("PID {} is a {} with no leader query", worker.pid, worker.backend_type));
+                }
+            }
+        }
+    }
+
+    fn handle_sessions_key(&mut self, key: KeyEvent) {
         match (key.code, key.modifiers) {
             (KeyCode::Up | KeyCode::Char('k'), KeyModifiers::NONE) => {
-                self.panels.table_stats.select_prev();
+                self.panels.sessions.select_prev();
             }
             (KeyCode::Down | KeyCode::Char('j'), KeyModifiers::NONE) => {
-                let max = self.sorted_table_stat_indices().len();
-                self.panels.table_stats.select_next(max);
+                let max = self.session_rows().len();
+                self.panels.sessions.select_next(max);
             }
             (KeyCode::PageUp | KeyCode::Char('u'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageUp) => {
-                self.panels.table_stats.select_page_up(PAGE_SIZE);
+                self.panels.sessions.select_page_up(PAGE_SIZE);
             }
             (KeyCode::PageDown | KeyCode::Char('d'), m) if m.contains(KeyModifiers::CONTROL) || matches!(key.code, KeyCode::PageDown) => {
-                let max = self.sorted_table_stat_indices().len();
-                self.panels.table_stats.select_page_down(max, PAGE_SIZE);
+                let max = self.session_rows().len();
+                self.panels.sessions.select_page_down(max, PAGE_SIZE);
             }
             (KeyCode::Enter, _) => {
-                if let Some(key) = self.selected_table_key() {
+                let idx = self.panels.sessions.selected().unwrap_or(0);
+                if let Some(row) = self.session_rows().get(idx) {
                     self.overlay_scroll = 0;
-                    self.view_mode = ViewMode::Inspect(InspectTarget::Table(key));
+                    self.view_mode = ViewMode::Inspect(InspectTarget::Query(row.pid));
                 }
             }
             (KeyCode::Char('s'), _) => {
-                self.panels.table_stats.cycle_sort();
-                self.panels.table_stats.select_first();
-                self.feedback.status_message = Some(format!(
-                    "Sort: {} {}",
-                    self.panels.table_stats.sort_column.label(),
-                    if self.panels.table_stats.sort_ascending {
-                        "\u{2191}"
-                    } else {
-                        "\u{2193}"
-                    }
-                ));
-            }
-            (KeyCode::Char('b'), _) if self.replay.is_none() => {
-                self.feedback.pending_action = Some(AppAction::RefreshBloat);
-                self.feedback.status_message = Some("Refreshing bloat estimates...".to_string());
-                self.feedback.bloat_loading = true;
+                self.panels.sessions.cycle_sort();
+                self.panels.sessions.select_first();
             }
             _ => {}
         }
     }
 
-    fn handle_replication_key(&mut self, key: KeyEvent) {
-        let len = self.snapshot.as_ref().map_or(0, |s| s.replication.len());
-        if PanelStates::simple_nav(&mut self.panels.replication, key, len, PAGE_SIZE) {
-            if let Some(pid) = self.selected_replication_pid() {
+    fn handle_index_health_key(&mut self, key: KeyEvent) {
+        let findings = self.index_findings();
+        if PanelStates::simple_nav(&mut self.panels.index_health, key, findings.len(), PAGE_SIZE) {
+            let selected = self.panels.index_health.selected().unwrap_or(0);
+            if let Some(finding) = findings.get(selected) {
                 self.overlay_scroll = 0;
-                self.view_mode = ViewMode::Inspect(InspectTarget::Replication(pid));
+                self.view_mode = ViewMode::Inspect(InspectTarget::Index(finding.key()));
             }
         }
     }
 
-    fn handle_blocking_key(&mut self, key: KeyEvent) {
-        let len = self.snapshot.as_ref().map_or(0, |s| s.blocking_info.len());
-        if PanelStates::simple_nav(&mut self.panels.blocking, key, len, PAGE_SIZE) {
-            if let Some(pid) = self.selected_blocking_pid() {
+    fn handle_autovacuum_key(&mut self, key: KeyEvent) {
+        let tables = self.autovacuum_rows();
+        if PanelStates::simple_nav(&mut self.panels.autovacuum, key, tables.len(), PAGE_SIZE) {
+            let selected = self.panels.autovacuum.selected().unwrap_or(0);
+            if let Some(table) = tables.get(selected) {
+                let key = table.key();
                 self.overlay_scroll = 0;
-                self.view_mode = ViewMode::Inspect(InspectTarget::Blocking(pid));
+                if self.replay.is_none() {
+                    self.request_table_schema(&key);
+                }
+                self.view_mode = ViewMode::Inspect(InspectTarget::Table(key));
             }
         }
     }
 
-    fn handle_vacuum_key(&mut self, key: KeyEvent) {
-        let len = self
-            .snapshot
-            .as_ref()
-            .map_or(0, |s| s.vacuum_progress.len());
-        if PanelStates::simple_nav(&mut self.panels.vacuum, key, len, PAGE_SIZE) {
-            if let Some(pid) = self.selected_vacuum_pid() {
-                self.overlay_scroll = 0;
-                self.view_mode = ViewMode::Inspect(InspectTarget::Vacuum(pid));
+    fn handle_buffer_cache_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('b') {
+            if self.replay.is_none() {
+                self.read_buffer_cache();
             }
+            return;
+        }
+        let relations = self.buffer_cache.as_ref().map_or(&[][..], |r| &r.relations[..]);
+        if PanelStates::simple_nav(&mut self.panels.buffer_cache, key, relations.len(), PAGE_SIZE) {
+            let selected = self.panels.buffer_cache.selected().unwrap_or(0);
+            let Some(rel) = relations.get(selected) else {
+                return;
+            };
+            let key = format!("{}.{}", rel.schemaname, rel.relname);
+            let target = match rel.relkind.as_str() {
+                "r" | "p" | "m" => InspectTarget::Table(key),
+                "i" | "I" => InspectTarget::Index(key),
+                _ => return,
+            };
+            self.overlay_scroll = 0;
+            self.view_mode = ViewMode::Inspect(target);
         }
     }
 
-    fn handle_wraparound_key(&mut self, key: KeyEvent) {
-        let len = self.snapshot.as_ref().map_or(0, |s| s.wraparound.len());
-        if PanelStates::simple_nav(&mut self.panels.wraparound, key, len, PAGE_SIZE) {
-            if let Some(datname) = self.selected_wraparound_datname() {
+    fn handle_settings_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('d') => {
+                self.settings_changed_only = !self.settings_changed_only;
+                self.panels.settings.select(Some(0));
+                self.feedback.status_message = Some(if self.settings_changed_only {
+                    "Settings: changed from default only".into()
+                } else {
+                    "Settings: all".into()
+                });
+                return;
+            }
+            KeyCode::Char('o') => {
                 self.overlay_scroll = 0;
-                self.view_mode = ViewMode::Inspect(InspectTarget::Wraparound(datname));
+                self.view_mode = ViewMode::SettingsReview;
+                return;
             }
+            _ => {}
         }
-    }
-
-    fn handle_settings_key(&mut self, key: KeyEvent) {
-        let len = self.sorted_settings_indices().len();
+        let len = (BottomPanel::Settings.descriptor().row_count)(self);
         if PanelStates::simple_nav(&mut self.panels.settings, key, len, PAGE_SIZE) {
             if let Some(name) = self.selected_setting_name() {
                 self.overlay_scroll = 0;
@@ -741,7 +2293,7 @@ impl App {
     }
 
     fn handle_extensions_key(&mut self, key: KeyEvent) {
-        let len = self.sorted_extensions_indices().len();
+        let len = (BottomPanel::Extensions.descriptor().row_count)(self);
         if PanelStates::simple_nav(&mut self.panels.extensions, key, len, PAGE_SIZE) {
             if let Some(name) = self.selected_extension_name() {
                 self.overlay_scroll = 0;
@@ -750,19 +2302,140 @@ impl App {
         }
     }
 
+    fn handle_roles_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Roles.descriptor().row_count)(self);
+        if PanelStates::simple_nav(&mut self.panels.roles, key, len, PAGE_SIZE) {
+            if let Some(name) = self.selected_role_name() {
+                self.overlay_scroll = 0;
+                if self.replay.is_none() {
+                    self.request_role_grants(&name);
+                }
+                self.view_mode = ViewMode::Inspect(InspectTarget::Role(name));
+            }
+        }
+    }
+
+    fn handle_logs_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('f') {
+            self.log_filter = self.log_filter.next();
+            self.panels.logs.select(Some(0));
+            self.feedback.status_message = Some(format!("Logs: {}", self.log_filter.label()));
+            return;
+        }
+        let len = (BottomPanel::Logs.descriptor().row_count)(self);
+        // Nothing to inspect; Enter is ignored
+        PanelStates::simple_nav(&mut self.panels.logs, key, len, PAGE_SIZE);
+    }
+
+    fn handle_alerts_key(&mut self, key: KeyEvent) {
+        let len = (BottomPanel::Alerts.descriptor().row_count)(self);
+        // Nothing to inspect; Enter is ignored
+        PanelStates::simple_nav(&mut self.panels.alerts, key, len, PAGE_SIZE);
+    }
+
     fn handle_panel_key(&mut self, key: KeyEvent) {
-        match self.bottom_panel {
-            BottomPanel::Queries => self.handle_queries_key(key),
-            BottomPanel::Indexes => self.handle_indexes_key(key),
-            BottomPanel::Statements => self.handle_statements_key(key),
-            BottomPanel::TableStats => self.handle_table_stats_key(key),
-            BottomPanel::Replication => self.handle_replication_key(key),
-            BottomPanel::Blocking => self.handle_blocking_key(key),
-            BottomPanel::VacuumProgress => self.handle_vacuum_key(key),
-            BottomPanel::Wraparound => self.handle_wraparound_key(key),
-            BottomPanel::Settings => self.handle_settings_key(key),
-            BottomPanel::Extensions => self.handle_extensions_key(key),
-            BottomPanel::WalIo | BottomPanel::WaitEvents => {}
+        let descriptor = self.bottom_panel.descriptor();
+        if key.code == KeyCode::Char('c')
+            && key.modifiers == KeyModifiers::NONE
+            && !descriptor.columns.is_empty()
+        {
+            self.column_cursor = 0;
+            self.view_mode = ViewMode::Columns;
+            return;
+        }
+        (descriptor.handle_key)(self, key);
+    }
+
+    /// Which of `panel`'s chooser columns are shown, in display order.
+    pub fn visible_columns(&self, panel: BottomPanel) -> Vec<bool> {
+        let descriptor = panel.descriptor();
+        self.config.hidden_columns.visible(descriptor.id, descriptor.columns)
+    }
+
+    fn handle_columns_key(&mut self, key: KeyEvent) {
+        let descriptor = self.bottom_panel.descriptor();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'c') => {
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.column_cursor > 0 => {
+                self.column_cursor -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.column_cursor + 1 < descriptor.columns.len() => {
+                self.column_cursor += 1;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let Some(column) = descriptor.columns.get(self.column_cursor) else {
+                    return;
+                };
+                let hidden = &mut self.config.hidden_columns;
+                let was_hidden = hidden.is_hidden(descriptor.id, column);
+                let shown = hidden.toggle(descriptor.id, column, descriptor.columns);
+                self.feedback.status_message = Some(if was_hidden {
+                    format!("Showing {column}")
+                } else if shown {
+                    format!("{column} is the last column shown")
+                } else {
+                    format!("Hiding {column}")
+                });
+                self.feedback.pending_action = Some(AppAction::SaveConfig);
+            }
+            _ => {}
+        }
+    }
+
+    /// Wheel moves like the arrow keys; clicks switch panels, select rows
+    /// and seek the replay timeline.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.handle_key(KeyEvent::from(KeyCode::Down)),
+            MouseEventKind::ScrollUp => self.handle_key(KeyEvent::from(KeyCode::Up)),
+            MouseEventKind::Down(MouseButton::Left) if self.view_mode == ViewMode::Normal => {
+                self.pending_chord = None;
+                self.handle_click(mouse.column, mouse.row);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_click(&mut self, x: u16, y: u16) {
+        let at = ratatui::layout::Position::new(x, y);
+        let tab = self.mouse_targets.tabs.iter().find(|(area, _)| area.contains(at));
+        if let Some(&(_, panel)) = tab {
+            self.go_to_panel(panel);
+            return;
+        }
+        if let Some(bar) = self.mouse_targets.timeline.filter(|bar| bar.contains(at)) {
+            let fraction = f64::from(x - bar.x) / f64::from(bar.width.saturating_sub(1).max(1));
+            self.feedback.pending_action = Some(AppAction::SeekReplay(SeekTarget::Fraction(fraction)));
+            return;
+        }
+        if self.mouse_targets.split_pane.is_some_and(|pane| pane.contains(at)) {
+            self.switch_split_focus();
+            return;
+        }
+        let targets = &self.mouse_targets;
+        if !targets.panel.contains(at) {
+            return;
+        }
+        let panel = self.bottom_panel;
+        let len = (panel.descriptor().row_count)(self);
+        let Some(state) = self.panels.table_state_mut(panel) else {
+            return;
+        };
+        let Some(row) = targets.row_at(y, state.offset()).filter(|&row| row < len) else {
+            return;
+        };
+        state.select(Some(row));
+        let now = Instant::now();
+        let double = self
+            .last_click
+            .is_some_and(|(t, p, r)| p == panel && r == row && now.duration_since(t) <= DOUBLE_CLICK);
+        if double {
+            self.last_click = None;
+            self.handle_panel_key(KeyEvent::from(KeyCode::Enter));
+        } else {
+            self.last_click = Some((now, panel, row));
         }
     }
 
@@ -808,41 +2481,29 @@ impl App {
     /// Handle overlay scroll keys, returns true if handled
     fn handle_overlay_scroll(&mut self, key: KeyEvent) -> bool {
         const PAGE_SIZE: u16 = 10;
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.overlay_scroll = self.overlay_scroll.saturating_sub(1);
-                true
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.overlay_scroll = self.overlay_scroll.saturating_add(1);
-                true
-            }
-            KeyCode::PageUp => {
-                self.overlay_scroll = self.overlay_scroll.saturating_sub(PAGE_SIZE);
-                true
-            }
-            KeyCode::PageDown => {
-                self.overlay_scroll = self.overlay_scroll.saturating_add(PAGE_SIZE);
-                true
-            }
+        // Until the overlay has been drawn its height is unknown; scroll
+        // freely and let the renderer clamp
+        let (max_scroll, page) = self
+            .overlay_extent
+            .get()
+            .map_or((u16::MAX, PAGE_SIZE), |e| (e.max_scroll, e.page));
+        let scroll = self.overlay_scroll.min(max_scroll);
+        self.overlay_scroll = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => scroll.saturating_add(1).min(max_scroll),
+            KeyCode::PageUp => scroll.saturating_sub(page),
+            KeyCode::PageDown => scroll.saturating_add(page).min(max_scroll),
             KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.overlay_scroll = self.overlay_scroll.saturating_sub(PAGE_SIZE);
-                true
+                scroll.saturating_sub(page)
             }
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.overlay_scroll = self.overlay_scroll.saturating_add(PAGE_SIZE);
-                true
-            }
-            KeyCode::Char('g') => {
-                self.overlay_scroll = 0;
-                true
+                scroll.saturating_add(page).min(max_scroll)
             }
-            KeyCode::Char('G') => {
-                self.overlay_scroll = u16::MAX;
-                true
-            }
-            _ => false,
-        }
+            KeyCode::Char('g') => 0,
+            KeyCode::Char('G') => max_scroll,
+            _ => return false,
+        };
+        true
     }
 
     /// Get the text to copy for the current inspect overlay
@@ -873,6 +2534,7 @@ impl App {
                 let r = snap.replication.iter().find(|r| r.pid == *pid)?;
                 Some(r.application_name.clone().unwrap_or_default())
             }
+            InspectTarget::Slot(name) => Some(name.clone()),
             InspectTarget::Table(key) => {
                 Some(key.clone())
             }
@@ -886,21 +2548,156 @@ impl App {
                 let vac = snap.vacuum_progress.iter().find(|v| v.pid == *pid)?;
                 Some(vac.table_name.clone())
             }
-            InspectTarget::Wraparound(datname) => {
-                Some(datname.clone())
+            InspectTarget::Wraparound(datname) => {
+                Some(self.wraparound_freeze_sql(datname).unwrap_or_else(|| datname.clone()))
+            }
+            InspectTarget::Settings(name) => {
+                let s = self.server_info.settings.iter().find(|s| s.name == *name)?;
+                Some(format!("{} = {}", s.name, s.setting))
+            }
+            InspectTarget::Extensions(name) | InspectTarget::Role(name) => {
+                Some(name.clone())
+            }
+            InspectTarget::WaitEvent(wait_event_type, wait_event) => {
+                let pids: Vec<String> = self
+                    .wait_event_backends(wait_event_type, wait_event)
+                    .iter()
+                    .map(|q| q.pid.to_string())
+                    .collect();
+                Some(pids.join(", "))
+            }
+        }
+    }
+
+    /// SQL behind an inspect overlay, for the full-screen viewer.
+    pub fn inspect_sql(&self, target: &InspectTarget) -> Option<String> {
+        let snap = self.snapshot.as_ref()?;
+        let sql = match target {
+            InspectTarget::Query(pid) => snap.active_queries.iter().find(|q| q.pid == *pid)?.query.clone()?,
+            InspectTarget::Statement(queryid) => {
+                snap.stat_statements.iter().find(|s| s.queryid == *queryid)?.query.clone()
+            }
+            InspectTarget::Blocking(blocked_pid) => snap
+                .blocking_info
+                .iter()
+                .find(|b| b.blocked_pid == *blocked_pid)?
+                .blocked_query
+                .clone()?,
+            InspectTarget::Index(key) => snap
+                .indexes
+                .iter()
+                .find(|i| format!("{}.{}", i.schemaname, i.index_name) == *key)?
+                .index_definition
+                .clone(),
+            _ => return None,
+        };
+        (!sql.trim().is_empty()).then_some(sql)
+    }
+
+    fn open_sql_view(&mut self) {
+        let ViewMode::Inspect(target) = &self.view_mode else {
+            return;
+        };
+        if self.inspect_sql(target).is_none() {
+            self.feedback.status_message = Some("No SQL to show full screen".into());
+            return;
+        }
+        self.sql_view.inspect_scroll = self.overlay_scroll;
+        self.sql_view.hscroll = 0;
+        self.overlay_scroll = 0;
+        self.view_mode = ViewMode::SqlView(target.clone());
+    }
+
+    /// Keys of the full-screen SQL viewer. Esc, `q` or `f` go back to the
+    /// inspect overlay it was opened from.
+    fn handle_sql_view_key(&mut self, key: KeyEvent, target: InspectTarget) {
+        const HSCROLL_STEP: u16 = 8;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'f') => {
+                self.overlay_scroll = self.sql_view.inspect_scroll;
+                self.view_mode = ViewMode::Inspect(target);
+            }
+            KeyCode::Char('w') => {
+                self.sql_view.wrap = !self.sql_view.wrap;
+                self.sql_view.hscroll = 0;
+                self.overlay_scroll = 0;
+            }
+            KeyCode::Char('n') => self.sql_view.line_numbers = !self.sql_view.line_numbers,
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.sql_view.hscroll = self.sql_view.hscroll.saturating_sub(HSCROLL_STEP);
+            }
+            KeyCode::Right | KeyCode::Char('l') if !self.sql_view.wrap => {
+                self.sql_view.hscroll = self
+                    .sql_view
+                    .hscroll
+                    .saturating_add(HSCROLL_STEP)
+                    .min(self.sql_view.max_hscroll.get());
+            }
+            KeyCode::Home | KeyCode::Char('0') => self.sql_view.hscroll = 0,
+            KeyCode::Char('y') => {
+                if let Some(sql) = self.inspect_sql(&target) {
+                    self.copy_to_clipboard(&sql);
+                }
+            }
+            _ => {
+                self.handle_overlay_scroll(key);
+            }
+        }
+    }
+
+    /// Unified handler for all inspect overlay key events.
+    fn handle_inspect_overlay_key(&mut self, key: KeyEvent) {
+        if self.overlay_search.editing {
+            self.handle_overlay_search_key(key);
+            return;
+        }
+        match key.code {
+            KeyCode::Char('/') => {
+                self.overlay_search.start();
+                return;
+            }
+            KeyCode::Char('n' | 'N') if self.overlay_search.is_active() => {
+                if let Some(offset) = self.overlay_search.step(key.code == KeyCode::Char('n')) {
+                    self.scroll_overlay_to(offset);
+                }
+                return;
             }
-            InspectTarget::Settings(name) => {
-                let s = self.server_info.settings.iter().find(|s| s.name == *name)?;
-                Some(format!("{} = {}", s.name, s.setting))
+            _ => {}
+        }
+        self.handle_inspect_action_key(key);
+        // Whatever closed the overlay takes its search along
+        if !matches!(self.view_mode, ViewMode::Inspect(_)) {
+            self.overlay_search.clear();
+        }
+    }
+
+    /// Typing a `/` search pattern: Enter jumps to the first match below
+    /// the top of the view, Esc drops the search.
+    fn handle_overlay_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.overlay_search.clear(),
+            KeyCode::Enter => {
+                self.overlay_search.editing = false;
+                if let Some(offset) = self.overlay_search.first_from(self.overlay_scroll) {
+                    self.scroll_overlay_to(offset);
+                }
             }
-            InspectTarget::Extensions(name) => {
-                Some(name.clone())
+            KeyCode::Backspace if self.overlay_search.query.is_empty() => self.overlay_search.clear(),
+            KeyCode::Backspace => {
+                self.overlay_search.query.pop();
             }
+            KeyCode::Char(c) => self.overlay_search.query.push(c),
+            _ => {}
         }
     }
 
-    /// Unified handler for all inspect overlay key events.
-    fn handle_inspect_overlay_key(&mut self, key: KeyEvent) {
+    /// Scroll so the line at `offset` shows with a little context above.
+    fn scroll_overlay_to(&mut self, offset: u16) {
+        let max_scroll = self.overlay_extent.get().map_or(u16::MAX, |e| e.max_scroll);
+        self.overlay_scroll = offset.saturating_sub(2).min(max_scroll);
+    }
+
+    fn handle_inspect_action_key(&mut self, key: KeyEvent) {
         // Query inspect allows Enter to close (legacy behavior)
         let query_pid = match &self.view_mode {
             ViewMode::Inspect(InspectTarget::Query(pid)) => Some(*pid),
@@ -926,6 +2723,79 @@ impl App {
             return;
         }
 
+        if key.code == KeyCode::Char('?') {
+            self.open_glossary();
+            return;
+        }
+
+        if key.code == KeyCode::Char('f') {
+            self.open_sql_view();
+            return;
+        }
+
+        if let (ViewMode::Inspect(InspectTarget::Table(table)), KeyCode::Char('m')) = (&self.view_mode, key.code) {
+            let table = table.clone();
+            self.measure_table_bloat(&table);
+            return;
+        }
+
+        // Ctrl runs it instead, with --allow-maintenance
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            let run = match (&self.view_mode, key.code) {
+                (ViewMode::Inspect(InspectTarget::Table(table)), KeyCode::Char('v')) => {
+                    Some(self.inspected_table(table).map(Maintenance::vacuum))
+                }
+                (ViewMode::Inspect(InspectTarget::Table(table)), KeyCode::Char('a')) => {
+                    Some(self.inspected_table(table).map(Maintenance::analyze))
+                }
+                (ViewMode::Inspect(InspectTarget::Index(index)), KeyCode::Char('r')) => {
+                    Some(self.inspected_index(index).map(Maintenance::reindex))
+                }
+                _ => None,
+            };
+            if let Some(run) = run {
+                self.confirm_maintenance(run);
+                return;
+            }
+        }
+
+        // Maintenance SQL: lowercase copies it, uppercase writes a file
+        let maintenance = match (&self.view_mode, key.code) {
+            (ViewMode::Inspect(InspectTarget::Table(table)), KeyCode::Char('v' | 'V')) => {
+                Some(self.inspected_table(table).map(Maintenance::vacuum))
+            }
+            (ViewMode::Inspect(InspectTarget::Table(table)), KeyCode::Char('a' | 'A')) => {
+                Some(self.inspected_table(table).map(Maintenance::analyze))
+            }
+            (ViewMode::Inspect(InspectTarget::Index(index)), KeyCode::Char('r' | 'R')) => {
+                Some(self.inspected_index(index).map(Maintenance::reindex))
+            }
+            (ViewMode::Inspect(InspectTarget::Index(index)), KeyCode::Char('c' | 'C')) => {
+                Some(self.inspected_index(index).map(Maintenance::rebuild))
+            }
+            _ => None,
+        };
+        if let Some(maintenance) = maintenance {
+            let to_file = matches!(key.code, KeyCode::Char(c) if c.is_ascii_uppercase());
+            self.generate_maintenance(maintenance, to_file);
+            return;
+        }
+
+        if let (ViewMode::Inspect(InspectTarget::WaitEvent(wait_event_type, wait_event)), KeyCode::Enter) =
+            (&self.view_mode, key.code)
+        {
+            let (wait_event_type, wait_event) = (wait_event_type.clone(), wait_event.clone());
+            self.drill_into_wait_event(&wait_event_type, &wait_event);
+            return;
+        }
+
+        if let (Some(pid), KeyCode::Char('b')) = (query_pid, key.code) {
+            if let Some(&blocker) = self.blockers_of(pid).first() {
+                self.jump_to_query(blocker);
+            }
+            return;
+        }
+
         // Kill/Cancel only available for query inspect in live mode
         if let Some(pid) = query_pid {
             if self.replay.is_none() {
@@ -952,29 +2822,62 @@ impl App {
                 self.feedback.pending_action = Some(AppAction::SaveConfig);
                 self.view_mode = ViewMode::Normal;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.config_overlay.selected > 0 {
-                    self.config_overlay.selected -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.config_overlay.selected > 0 => {
+                self.config_overlay.selected -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.config_overlay.selected < ConfigItem::ALL.len() - 1 {
-                    self.config_overlay.selected += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.config_overlay.selected
+                    < ConfigItem::ALL.len() + self.config.row_colors.len() - 1 =>
+            {
+                self.config_overlay.selected += 1;
             }
+            // Add a row coloring rule and select it
+            KeyCode::Char('a') => {
+                self.config.row_colors.push(RowColorRule::new(RowColumn::MeanExecTime));
+                self.config_overlay.selected = ConfigItem::ALL.len() + self.config.row_colors.len() - 1;
+            }
+            _ if self.selected_row_rule().is_some() => self.handle_row_rule_key(key),
             KeyCode::Left | KeyCode::Char('h') => {
                 self.config_adjust(-1);
             }
             KeyCode::Right | KeyCode::Char('l') => {
                 self.config_adjust(1);
             }
-            KeyCode::Enter => {
-                // Enter edit mode for RecordingsDir
-                if ConfigItem::ALL[self.config_overlay.selected] == ConfigItem::RecordingsDir {
-                    self.config_overlay.input_buffer =
-                        self.config.recordings_dir.clone().unwrap_or_default();
-                    self.view_mode = ViewMode::ConfigEditRecordingsDir;
-                }
+            // Enter edit mode for RecordingsDir
+            KeyCode::Enter
+                if ConfigItem::ALL[self.config_overlay.selected] == ConfigItem::RecordingsDir =>
+            {
+                self.config_overlay.input_buffer =
+                    self.config.recordings_dir.clone().unwrap_or_default();
+                self.view_mode = ViewMode::ConfigEditRecordingsDir;
+            }
+            _ => {}
+        }
+    }
+
+    /// The row coloring rule selected in the config overlay, listed after
+    /// the settings.
+    fn selected_row_rule(&mut self) -> Option<&mut RowColorRule> {
+        let i = self.config_overlay.selected.checked_sub(ConfigItem::ALL.len())?;
+        self.config.row_colors.get_mut(i)
+    }
+
+    fn handle_row_rule_key(&mut self, key: KeyEvent) {
+        let Some(rule) = self.selected_row_rule() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => rule.step(-1),
+            KeyCode::Right | KeyCode::Char('l') => rule.step(1),
+            KeyCode::Enter => rule.cycle_column(),
+            KeyCode::Char(' ') => rule.flip(),
+            KeyCode::Char('c') => rule.color = rule.color.next(),
+            KeyCode::Char('x') | KeyCode::Delete => {
+                let i = self.config_overlay.selected - ConfigItem::ALL.len();
+                self.config.row_colors.remove(i);
+                self.config_overlay.selected = self.config_overlay.selected.min(
+                    ConfigItem::ALL.len() + self.config.row_colors.len() - 1,
+                );
             }
             _ => {}
         }
@@ -1008,34 +2911,260 @@ impl App {
         }
     }
 
+    fn handle_edit_note_key(&mut self, key: KeyEvent, queryid: i64) {
+        match key.code {
+            KeyCode::Esc => {
+                self.note_input.clear();
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Enter => {
+                self.starred.set_note(queryid, &self.note_input);
+                self.note_input.clear();
+                self.view_mode = ViewMode::Normal;
+                self.feedback.status_message = Some(format!("Saved note for statement {queryid}"));
+                self.feedback.pending_action = Some(AppAction::SaveStarred);
+            }
+            KeyCode::Backspace => {
+                self.note_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.note_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn open_export_prompt(&mut self) {
+        let desc = self.bottom_panel.descriptor();
+        if (desc.export_rows)(self).is_empty() {
+            // The graphs' history may still be worth having
+            if self.metrics.connections.as_vec().is_empty() {
+                self.feedback.status_message = Some(format!("Nothing to export in {}", desc.title));
+                return;
+            }
+            self.export_history = true;
+        } else {
+            self.export_history = false;
+        }
+        self.export_input = self.default_export_path("csv");
+        self.view_mode = ViewMode::ExportPrompt;
+    }
+
+    /// `<exports dir>/<panel id or "history">_<time>.<ext>`
+    fn default_export_path(&self, ext: &str) -> String {
+        let prefix = if self.export_history {
+            "history"
+        } else {
+            self.bottom_panel.descriptor().id
+        };
+        let name = format!(
+            "{prefix}_{}.{ext}",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        );
+        crate::export::default_exports_dir()
+            .join(name)
+            .display()
+            .to_string()
+    }
+
+    fn handle_export_prompt_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.export_input.clear();
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Enter => {
+                let path = PathBuf::from(self.export_input.trim());
+                self.export_input.clear();
+                self.view_mode = ViewMode::Normal;
+                if path.as_os_str().is_empty() {
+                    return;
+                }
+                self.feedback.pending_action = Some(if self.export_history {
+                    AppAction::ExportHistory(path)
+                } else {
+                    AppAction::ExportPanel(path)
+                });
+            }
+            // Switch between the panel's rows and the metrics history
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let has_rows = !(self.bottom_panel.descriptor().export_rows)(self).is_empty();
+                if self.export_history && !has_rows {
+                    return;
+                }
+                self.export_history = !self.export_history;
+                let ext = match PathBuf::from(&self.export_input).extension().and_then(|e| e.to_str()) {
+                    Some("json") => "json",
+                    _ => "csv",
+                };
+                self.export_input = self.default_export_path(ext);
+            }
+            // Cycle the extension, which picks the format
+            KeyCode::Tab => {
+                let path = PathBuf::from(&self.export_input);
+                let next = match path.extension().and_then(|e| e.to_str()) {
+                    Some("csv") => "json",
+                    Some("json") if !self.export_history => "svg",
+                    _ => "csv",
+                };
+                self.export_input = path.with_extension(next).display().to_string();
+            }
+            KeyCode::Backspace => {
+                self.export_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.export_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// `#` prompt: Enter keeps the note with the current time, in the
+    /// session and in the recording.
+    fn handle_annotate_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.annotation_input.clear();
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Enter => {
+                let text = std::mem::take(&mut self.annotation_input).trim().to_string();
+                self.view_mode = ViewMode::Normal;
+                if !text.is_empty() {
+                    self.add_annotation(text, chrono::Utc::now());
+                }
+            }
+            KeyCode::Backspace => {
+                self.annotation_input.pop();
+            }
+            KeyCode::Char(c) => self.annotation_input.push(c),
+            _ => {}
+        }
+    }
+
+    pub fn add_annotation(&mut self, text: String, at: chrono::DateTime<chrono::Utc>) {
+        self.feedback.status_message = Some(format!("Annotated: {text}"));
+        self.feedback.ui_events.push(UiEvent {
+            timestamp: at,
+            kind: UiEventKind::Annotation { text: text.clone() },
+            via: None,
+        });
+        self.annotations.push(Annotation { at, text });
+    }
+
+    fn handle_seek_prompt_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.seek_input.clear();
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.seek_input);
+                self.view_mode = ViewMode::Normal;
+                if input.trim().is_empty() {
+                    return;
+                }
+                match SeekTarget::parse(&input) {
+                    Some(target) => {
+                        self.feedback.pending_action = Some(AppAction::SeekReplay(target));
+                    }
+                    None => {
+                        self.feedback.status_message =
+                            Some(format!("Can't jump to \"{}\" (try 14:05, 14:05:30 or 50%)", input.trim()));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.seek_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.seek_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn open_glossary(&mut self) {
+        self.overlay_scroll = 0;
+        self.view_mode = ViewMode::Glossary(self.bottom_panel);
+    }
+
+    /// Label of the column the active panel is sorted by, which the glossary
+    /// shows first
+    pub fn glossary_focus(&self) -> Option<&'static str> {
+        match self.bottom_panel {
+            BottomPanel::Queries => Some(self.panels.queries.sort_column.label()),
+            BottomPanel::Indexes => Some(self.panels.indexes.sort_column.label()),
+            BottomPanel::Statements => Some(self.panels.statements.sort_column.label()),
+            BottomPanel::TableStats => Some(self.panels.table_stats.sort_column.label()),
+            BottomPanel::Slots => Some(self.panels.slots.sort_column.label()),
+            _ => None,
+        }
+    }
+
     fn handle_help_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
                 self.overlay_scroll = 0;
                 self.view_mode = ViewMode::Normal;
             }
+            // `?` again from help digs into what the panel's columns mean
+            KeyCode::Char('?') if self.view_mode == ViewMode::Help => {
+                self.open_glossary();
+            }
             _ => {
                 self.handle_overlay_scroll(key);
             }
         }
     }
 
-    fn handle_recordings_key(&mut self, key: KeyEvent) {
+    fn open_connection_switcher(&mut self) {
+        if self.switcher.is_multi() {
+            self.switcher.selected = self.switcher.current;
+            self.view_mode = ViewMode::Connections;
+        } else {
+            self.feedback.status_message =
+                Some("Only one connection (add more with --target)".into());
+        }
+    }
+
+    fn handle_connections_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.view_mode = ViewMode::Normal;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.recordings.selected > 0 {
-                    self.recordings.selected -= 1;
+            KeyCode::Up | KeyCode::Char('k') if self.switcher.selected > 0 => {
+                self.switcher.selected -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.switcher.selected + 1 < self.switcher.entries.len() =>
+            {
+                self.switcher.selected += 1;
+            }
+            KeyCode::Enter => {
+                self.view_mode = ViewMode::Normal;
+                if self.switcher.selected != self.switcher.current {
+                    self.feedback.pending_action =
+                        Some(AppAction::SwitchConnection(self.switcher.selected));
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            _ => {}
+        }
+    }
+
+    fn handle_recordings_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.view_mode = ViewMode::Normal;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.recordings.selected > 0 => {
+                self.recordings.selected -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j')
                 if !self.recordings.list.is_empty()
-                    && self.recordings.selected < self.recordings.list.len() - 1
-                {
-                    self.recordings.selected += 1;
-                }
+                    && self.recordings.selected < self.recordings.list.len() - 1 =>
+            {
+                self.recordings.selected += 1;
             }
             KeyCode::Enter => {
                 if let Some(recording) = self.recordings.current() {
@@ -1057,6 +3186,10 @@ impl App {
         if let KeyCode::Char('y' | 'Y') = key.code {
             if crate::recorder::Recorder::delete_recording(&path).is_ok() {
                 self.feedback.status_message = Some("Recording deleted".into());
+                let name = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+                if self.bookmarks.remove_recording(&first_segment_name(name)) {
+                    self.feedback.pending_action = Some(AppAction::SaveBookmarks);
+                }
                 // Refresh the list
                 self.recordings.list =
                     crate::recorder::Recorder::list_recordings(self.config.recordings_dir.as_deref());
@@ -1078,21 +3211,25 @@ impl App {
     fn handle_filter_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
-                self.filter.clear();
+                self.filter_mut().clear();
                 self.view_mode = ViewMode::Normal;
                 self.reset_panel_selection();
             }
             KeyCode::Enter => {
-                self.filter.active = !self.filter.text.is_empty();
+                self.filter_mut().active = !self.filter().text.is_empty();
                 self.view_mode = ViewMode::Normal;
                 self.reset_panel_selection();
+                self.log_ui_event(UiEventKind::Filter {
+                    panel: self.bottom_panel.label().to_string(),
+                    text: self.filter().text.clone(),
+                });
             }
             KeyCode::Backspace => {
-                self.filter.pop_char();
+                self.filter_mut().pop_char();
                 self.reset_panel_selection();
             }
             KeyCode::Char(c) => {
-                self.filter.push_char(c);
+                self.filter_mut().push_char(c);
                 self.reset_panel_selection();
             }
             _ => {}
@@ -1113,6 +3250,10 @@ impl App {
                 self.running = false;
                 true
             }
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_connection_switcher();
+                true
+            }
             KeyCode::Char('p') if self.replay.is_none() => {
                 self.paused = !self.paused;
                 true
@@ -1126,6 +3267,16 @@ impl App {
                 self.view_mode = ViewMode::Help;
                 true
             }
+            KeyCode::Char(':') if self.replay.is_some() => {
+                self.seek_input.clear();
+                self.view_mode = ViewMode::SeekPrompt;
+                true
+            }
+            KeyCode::Char('#') if self.replay.is_none() => {
+                self.annotation_input.clear();
+                self.view_mode = ViewMode::Annotate;
+                true
+            }
             KeyCode::Char(',') => {
                 self.view_mode = ViewMode::Config;
                 true
@@ -1134,10 +3285,11 @@ impl App {
                 self.yank_selected();
                 true
             }
-            KeyCode::Char('L') if self.replay.is_none() => {
-                // Open recordings browser (live mode only)
+            KeyCode::Char('L') => {
+                // Open recordings browser
                 self.recordings.list =
                     crate::recorder::Recorder::list_recordings(self.config.recordings_dir.as_deref());
+                self.bookmarks = Bookmarks::load();
                 self.recordings.selected = 0;
                 self.view_mode = ViewMode::Recordings;
                 true
@@ -1146,68 +3298,112 @@ impl App {
                 self.graphs_collapsed = !self.graphs_collapsed;
                 true
             }
-            _ => false,
-        }
-    }
-
-    fn handle_panel_switch_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Char('Q') => {
-                self.switch_panel(BottomPanel::Queries);
-                true
-            }
-            KeyCode::Tab => {
-                self.switch_panel(BottomPanel::Blocking);
-                true
-            }
-            KeyCode::Char('w') => {
-                self.switch_panel(BottomPanel::WaitEvents);
+            KeyCode::Char('~') => {
+                self.config.highlight_changes = !self.config.highlight_changes;
+                self.feedback.status_message = Some(
+                    if self.config.highlight_changes {
+                        "Highlighting changes since the last refresh: + new, ↑ jumped"
+                    } else {
+                        "Change highlighting off"
+                    }
+                    .into(),
+                );
                 true
             }
-            KeyCode::Char('t') => {
-                self.switch_panel(BottomPanel::TableStats);
+            KeyCode::Char('|') => {
+                self.toggle_split();
                 true
             }
-            KeyCode::Char('R') => {
-                self.switch_panel(BottomPanel::Replication);
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.switch_split_focus();
                 true
             }
-            KeyCode::Char('v') => {
-                self.switch_panel(BottomPanel::VacuumProgress);
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_export_prompt();
                 true
             }
-            KeyCode::Char('x') => {
-                self.switch_panel(BottomPanel::Wraparound);
+            KeyCode::Char('e') if !self.e_explains() => {
+                self.feedback.pending_action = Some(AppAction::ExportGraphs);
                 true
             }
-            KeyCode::Char('I') => {
-                self.switch_panel(BottomPanel::Indexes);
+            KeyCode::Char('a') if self.replay.is_some() => {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Activity;
                 true
             }
-            KeyCode::Char('S') => {
-                self.switch_panel(BottomPanel::Statements);
+            KeyCode::Char('T') => {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Timeouts;
                 true
             }
-            KeyCode::Char('A') => {
-                self.switch_panel(BottomPanel::WalIo);
+            KeyCode::Char('D') if self.replay.is_none() => {
+                self.overlay_scroll = 0;
+                self.view_mode = ViewMode::Collector;
                 true
             }
-            KeyCode::Char('P') => {
-                self.switch_panel(BottomPanel::Settings);
+            KeyCode::Char(c)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && chords::prefix_title(c).is_some() =>
+            {
+                self.pending_chord = Some(c);
                 true
             }
-            KeyCode::Char('E') => {
-                self.switch_panel(BottomPanel::Extensions);
-                true
+            _ => false,
+        }
+    }
+
+    /// Second key of a chord: run its binding, or drop the chord.
+    fn finish_chord(&mut self, prefix: char, key: KeyEvent) {
+        let binding = match key.code {
+            KeyCode::Char(c) => chords::resolve(prefix, c),
+            _ => None,
+        };
+        match binding {
+            Some(binding) => match binding.action {
+                chords::ChordAction::Panel(panel) => self.go_to_panel(panel),
+            },
+            None if key.code == KeyCode::Esc => {}
+            None => {
+                let pressed = match key.code {
+                    KeyCode::Char(c) => c.to_string(),
+                    other => other.to_string(),
+                };
+                self.feedback.status_message = Some(format!("Nothing bound to {prefix} {pressed}"));
             }
-            KeyCode::Char('/') => {
-                if self.bottom_panel.supports_filter() {
-                    self.view_mode = ViewMode::Filter;
-                }
-                true
+        }
+    }
+
+    fn handle_panel_switch_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(panel) = BottomPanel::from_key(key.code) {
+            self.switch_panel(panel);
+            return true;
+        }
+        if key.code == KeyCode::Char('/') {
+            if self.bottom_panel.supports_filter() {
+                self.view_mode = ViewMode::Filter;
             }
-            _ => false,
+            return true;
         }
+        // Filters stay with their panel until cleared here or with Esc
+        // while typing
+        if key.code == KeyCode::Backspace && self.filter().active {
+            self.filter_mut().clear();
+            self.reset_panel_selection();
+            self.log_ui_event(UiEventKind::Filter {
+                panel: self.bottom_panel.label().to_string(),
+                text: String::new(),
+            });
+            return true;
+        }
+        if key.code == KeyCode::Backspace
+            && self.bottom_panel == BottomPanel::Queries
+            && self.query_scope.take().is_some()
+        {
+            self.reset_panel_selection();
+            self.feedback.status_message = None;
+            return true;
+        }
+        false
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) {
@@ -1251,6 +3447,14 @@ impl App {
                         let path = path.clone();
                         self.handle_confirm_delete_recording_key(key, path);
                     }
+                    ConfirmAction::DropSlot(name) => {
+                        let action = AppAction::DropReplicationSlot(name.clone());
+                        self.handle_yes_no_confirm(key, action, "Drop aborted");
+                    }
+                    ConfirmAction::RunMaintenance(maintenance) => {
+                        let action = AppAction::RunMaintenance(maintenance.clone());
+                        self.handle_yes_no_confirm(key, action, "Maintenance aborted");
+                    }
                     ConfirmAction::ResetStatStatements => {
                         self.handle_yes_no_confirm(
                             key,
@@ -1265,6 +3469,11 @@ impl App {
                 self.handle_inspect_overlay_key(key);
                 return;
             }
+            ViewMode::SqlView(target) => {
+                let target = target.clone();
+                self.handle_sql_view_key(key, target);
+                return;
+            }
             ViewMode::Config => {
                 self.handle_config_key(key);
                 return;
@@ -1285,9 +3494,51 @@ impl App {
                 self.handle_recordings_key(key);
                 return;
             }
+            ViewMode::Connections => {
+                self.handle_connections_key(key);
+                return;
+            }
+            ViewMode::Columns => {
+                self.handle_columns_key(key);
+                return;
+            }
+            ViewMode::Activity
+            | ViewMode::Glossary(_)
+            | ViewMode::Timeouts
+            | ViewMode::SettingsReview
+            | ViewMode::Collector
+            | ViewMode::Explain
+            | ViewMode::IndexAdvice => {
+                // Same controls as the help overlay
+                self.handle_help_key(key);
+                return;
+            }
+            ViewMode::EditNote(queryid) => {
+                let queryid = *queryid;
+                self.handle_edit_note_key(key, queryid);
+                return;
+            }
+            ViewMode::ExportPrompt => {
+                self.handle_export_prompt_key(key);
+                return;
+            }
+            ViewMode::SeekPrompt => {
+                self.handle_seek_prompt_key(key);
+                return;
+            }
+            ViewMode::Annotate => {
+                self.handle_annotate_key(key);
+                return;
+            }
             ViewMode::Normal => {}
         }
 
+        // A chord prefix takes the next key whatever it is
+        if let Some(prefix) = self.pending_chord.take() {
+            self.finish_chord(prefix, key);
+            return;
+        }
+
         // Layer 2: Normal mode global keys
         if self.handle_normal_global_key(key) {
             return;
@@ -1323,12 +3574,19 @@ impl App {
             ConfigItem::ShowEmojis => {
                 self.config.show_emojis = !self.config.show_emojis;
             }
+            ConfigItem::PlainMode => {
+                self.config.plain_mode = !self.config.plain_mode;
+            }
             ConfigItem::RefreshInterval => {
                 let val = self.config.refresh_interval_secs as i64 + i64::from(direction);
                 self.config.refresh_interval_secs = val.clamp(1, 60) as u64;
                 self.refresh_interval_secs = self.config.refresh_interval_secs;
                 self.feedback.pending_action = Some(AppAction::RefreshIntervalChanged);
             }
+            ConfigItem::AdaptiveRefresh => {
+                self.config.adaptive_refresh.enabled = !self.config.adaptive_refresh.enabled;
+                self.adaptive_refresh.reset();
+            }
             ConfigItem::WarnDuration => {
                 let val = f64::from(direction).mul_add(0.5, self.config.warn_duration_secs);
                 self.config.warn_duration_secs = val.clamp(0.1, self.config.danger_duration_secs);
@@ -1356,6 +3614,59 @@ impl App {
                     self.config.recording_retention_secs as i64 + i64::from(direction) * step;
                 self.config.recording_retention_secs = val.clamp(600, 86400) as u64;
             }
+            ConfigItem::LatencyHeatmap => {
+                self.config.latency_heatmap = !self.config.latency_heatmap;
+            }
+            ConfigItem::HighlightChanges => {
+                self.config.highlight_changes = !self.config.highlight_changes;
+            }
+            ConfigItem::RecordUiEvents => {
+                self.config.record_ui_events = !self.config.record_ui_events;
+            }
+            ConfigItem::ReplayInterpolation => {
+                self.config.replay_interpolation = !self.config.replay_interpolation;
+            }
+            ConfigItem::LatencySampler => {
+                self.config.latency_sampler = !self.config.latency_sampler;
+            }
+            ConfigItem::WaitSampler => {
+                self.config.wait_sampler = !self.config.wait_sampler;
+                self.wait_sampler.clear();
+            }
+            ConfigItem::ConfirmFromLog => {
+                self.config.confirm_from_log = !self.config.confirm_from_log;
+            }
+            ConfigItem::ServerLog => {
+                self.config.server_log = !self.config.server_log;
+            }
+            ConfigItem::IdleTxnWatchdog => {
+                // Off, then thresholds from 30s to an hour
+                const STEPS: [u64; 8] = [0, 30, 60, 120, 300, 600, 1800, 3600];
+                let at = STEPS
+                    .iter()
+                    .position(|&s| s >= self.config.idle_txn_watchdog_secs)
+                    .unwrap_or(STEPS.len() - 1);
+                let next = at.saturating_add_signed(isize::from(direction)).min(STEPS.len() - 1);
+                self.config.idle_txn_watchdog_secs = STEPS[next];
+            }
+            ConfigItem::WalDirWarn => {
+                // max_wal_size, then fixed sizes from 1 GB to 64 GB
+                const STEPS: [u64; 8] = [0, 1024, 2048, 4096, 8192, 16384, 32768, 65536];
+                let at = STEPS
+                    .iter()
+                    .position(|&mb| mb >= self.config.wal_dir_warn_mb)
+                    .unwrap_or(STEPS.len() - 1);
+                let next = at.saturating_add_signed(isize::from(direction)).min(STEPS.len() - 1);
+                self.config.wal_dir_warn_mb = STEPS[next];
+            }
+            ConfigItem::LongQueryNotify => {
+                let via = &mut self.config.notifications.long_query;
+                *via = if direction > 0 { via.next() } else { via.prev() };
+            }
+            ConfigItem::BlockingNotify => {
+                let via = &mut self.config.notifications.blocking;
+                *via = if direction > 0 { via.next() } else { via.prev() };
+            }
             ConfigItem::RecordingsDir => {
                 // Path cannot be adjusted with arrows - edit config.toml to change
             }
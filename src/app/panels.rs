@@ -2,47 +2,48 @@
 
 use std::path::PathBuf;
 
+use crate::db::models::{ActiveQuery, ConnectionGroup};
+use crate::maintenance::Maintenance;
+
 /// The active bottom panel.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BottomPanel {
     Queries,
     Blocking,
+    Locks,
+    ConnectionGroups,
+    Sessions,
+    Pooler,
     WaitEvents,
     TableStats,
     Replication,
+    Slots,
+    LogicalReplication,
     VacuumProgress,
+    AutovacuumHealth,
+    Operations,
+    Backends,
     Wraparound,
     Indexes,
+    IndexHealth,
     Statements,
     WalIo,
+    Checkpoints,
+    BufferCache,
     Settings,
     Extensions,
+    Roles,
+    Logs,
+    Alerts,
 }
 
 impl BottomPanel {
-    pub const fn supports_filter(self) -> bool {
-        matches!(
-            self,
-            Self::Queries | Self::Indexes | Self::Statements | Self::TableStats | Self::Settings | Self::Extensions
-        )
+    pub fn supports_filter(self) -> bool {
+        self.descriptor().supports_filter
     }
 
-    #[allow(dead_code)]
-    pub const fn label(self) -> &'static str {
-        match self {
-            Self::Queries => "Queries",
-            Self::Blocking => "Blocking",
-            Self::WaitEvents => "Wait Events",
-            Self::TableStats => "Table Stats",
-            Self::Replication => "Replication",
-            Self::VacuumProgress => "Vacuum Progress",
-            Self::Wraparound => "Wraparound",
-            Self::Indexes => "Indexes",
-            Self::Statements => "Statements",
-            Self::WalIo => "WAL & I/O",
-            Self::Settings => "Settings",
-            Self::Extensions => "Extensions",
-        }
+    pub fn label(self) -> &'static str {
+        self.descriptor().title
     }
 }
 
@@ -53,12 +54,40 @@ pub enum InspectTarget {
     Index(String),        // schema.index_name
     Statement(i64),       // queryid
     Replication(i32),     // PID
+    Slot(String),         // slot_name
     Table(String),        // schema.table_name
     Blocking(i32),        // blocked_pid
     Vacuum(i32),          // PID
     Wraparound(String),   // datname
     Settings(String),     // setting name
     Extensions(String),   // extension name
+    Role(String),         // role name
+    WaitEvent(String, String), // wait_event_type, wait_event
+}
+
+/// What the Queries list is narrowed to after drilling in from another panel.
+#[derive(Debug, Clone)]
+pub enum QueryScope {
+    /// One Connections group
+    Group(ConnectionGroup),
+    /// The backends that were in a wait event when it was opened
+    Pids { label: String, pids: Vec<i32> },
+}
+
+impl QueryScope {
+    pub fn contains(&self, query: &ActiveQuery) -> bool {
+        match self {
+            Self::Group(group) => group.contains(query),
+            Self::Pids { pids, .. } => pids.contains(&query.pid),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Group(group) => group.label(),
+            Self::Pids { label, .. } => label.clone(),
+        }
+    }
 }
 
 /// Confirmation action types
@@ -72,6 +101,9 @@ pub enum ConfirmAction {
     KillBatch(Vec<i32>),
     DeleteRecording(PathBuf),
     ResetStatStatements,
+    DropSlot(String),
+    /// Run this command from an inspect overlay (`--allow-maintenance`)
+    RunMaintenance(Maintenance),
 }
 
 /// Current view/interaction mode.
@@ -85,4 +117,32 @@ pub enum ViewMode {
     ConfigEditRecordingsDir,
     Help,
     Recordings,
+    /// Operator actions recorded in the replayed session
+    Activity,
+    /// Editing the note of a starred statement
+    EditNote(i64),
+    /// Metric glossary for a panel
+    Glossary(BottomPanel),
+    /// Switch between monitored connections
+    Connections,
+    /// Timeout settings checked against long transactions
+    Timeouts,
+    /// Changed settings and risky values among them
+    SettingsReview,
+    /// Snapshot timings and hook results
+    Collector,
+    /// Plan of the selected query or statement
+    Explain,
+    /// Hypothetical index suggestions for the selected statement
+    IndexAdvice,
+    /// Typing the file name for a panel export
+    ExportPrompt,
+    /// Typing a time or percentage to jump the replay to
+    SeekPrompt,
+    /// Typing an annotation for the current time
+    Annotate,
+    /// Choosing which of the panel's columns are shown
+    Columns,
+    /// Full-screen SQL of the inspected query, statement or index
+    SqlView(InspectTarget),
 }
@@ -0,0 +1,297 @@
+//! Plans from repeated EXPLAINs of the same statement, and what changed
+//! between them.
+//!
+//! Every plan the EXPLAIN overlay shows is kept for the session under the
+//! statement's queryid, or its text when it has none. Explaining a
+//! statement again compares the new plan with the one before, node by node:
+//! nodes that appeared or went away, and nodes whose estimated cost moved,
+//! so a plan flip during an incident is there to look at afterwards.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::db::models::{PlanNode, QueryPlan};
+
+/// Smallest relative change in a node's total cost that is listed.
+const COST_CHANGE_RATIO: f64 = 0.1;
+
+/// Plans kept per statement; the oldest go first.
+const MAX_RUNS: usize = 20;
+
+/// Which statement a plan belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlanKey {
+    QueryId(i64),
+    Text(String),
+}
+
+impl PlanKey {
+    /// The queryid when there is one; backends without `compute_query_id`
+    /// report none (or 0) and fall back to the query text.
+    pub fn new(queryid: Option<i64>, query: &str) -> Self {
+        match queryid {
+            Some(id) if id != 0 => Self::QueryId(id),
+            _ => Self::Text(query.trim().to_string()),
+        }
+    }
+
+    /// How the activity trail names the statement.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::QueryId(id) => format!("queryid {id}"),
+            Self::Text(text) => {
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                match text.char_indices().nth(40) {
+                    Some((i, _)) => format!("\"{}…\"", &text[..i]),
+                    None => format!("\"{text}\""),
+                }
+            }
+        }
+    }
+}
+
+/// One EXPLAIN of a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanRun {
+    pub at: DateTime<Utc>,
+    pub plan: QueryPlan,
+}
+
+/// How one plan node differs between two plans.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeChange {
+    Added(String),
+    Removed(String),
+    Cost { label: String, from: f64, to: f64 },
+}
+
+/// What changed from one plan of a statement to the next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanDiff {
+    pub nodes: Vec<NodeChange>,
+    /// Root total cost before and after
+    pub total_cost: (f64, f64),
+}
+
+/// Plan nodes in depth-first order, as `(label, total cost)`.
+fn flatten(root: &PlanNode) -> Vec<(String, f64)> {
+    let mut out = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        out.push((node.label(), node.total_cost));
+        stack.extend(node.children.iter().rev());
+    }
+    out
+}
+
+fn cost_moved(from: f64, to: f64) -> bool {
+    (to - from).abs() >= COST_CHANGE_RATIO * from.abs().max(1.0)
+}
+
+impl PlanDiff {
+    /// Match the nodes of both plans by label, keeping their order (longest
+    /// common subsequence); the rest were added or removed.
+    pub fn between(old: &QueryPlan, new: &QueryPlan) -> Self {
+        let a = flatten(&old.root);
+        let b = flatten(&new.root);
+        // lcs[i][j]: longest match of a[i..] and b[j..]
+        let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i].0 == b[j].0 {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut nodes = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i].0 == b[j].0 {
+                if cost_moved(a[i].1, b[j].1) {
+                    nodes.push(NodeChange::Cost {
+                        label: b[j].0.clone(),
+                        from: a[i].1,
+                        to: b[j].1,
+                    });
+                }
+                i += 1;
+                j += 1;
+            } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                nodes.push(NodeChange::Removed(a[i].0.clone()));
+                i += 1;
+            } else {
+                nodes.push(NodeChange::Added(b[j].0.clone()));
+                j += 1;
+            }
+        }
+
+        Self {
+            nodes,
+            total_cost: (old.root.total_cost, new.root.total_cost),
+        }
+    }
+
+    /// Whether the plan's shape changed, not just its costs.
+    pub fn flipped(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|n| matches!(n, NodeChange::Added(_) | NodeChange::Removed(_)))
+    }
+
+    /// One line for the activity trail: nodes that went away, then the ones
+    /// that replaced them, then the total cost.
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = self
+            .nodes
+            .iter()
+            .filter_map(|n| match n {
+                NodeChange::Removed(label) => Some(format!("-{label}")),
+                NodeChange::Added(label) => Some(format!("+{label}")),
+                NodeChange::Cost { .. } => None,
+            })
+            .collect();
+        parts.push(format!("cost {:.2} → {:.2}", self.total_cost.0, self.total_cost.1));
+        parts.join(", ")
+    }
+}
+
+/// Every plan explained this session, per statement.
+#[derive(Debug, Default)]
+pub struct PlanHistory {
+    runs: HashMap<PlanKey, Vec<PlanRun>>,
+}
+
+impl PlanHistory {
+    /// Keep `plan` as the latest for `key`, and compare it with the one
+    /// before, if any.
+    pub fn push(&mut self, key: PlanKey, plan: QueryPlan, at: DateTime<Utc>) -> Option<PlanDiff> {
+        let runs = self.runs.entry(key).or_default();
+        let diff = runs.last().map(|prev| PlanDiff::between(&prev.plan, &plan));
+        runs.push(PlanRun { at, plan });
+        if runs.len() > MAX_RUNS {
+            runs.remove(0);
+        }
+        diff
+    }
+
+    /// Plans of `key`, oldest first.
+    pub fn runs(&self, key: &PlanKey) -> &[PlanRun] {
+        self.runs.get(key).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(node_type: &str, relation: Option<&str>, cost: f64, children: Vec<PlanNode>) -> PlanNode {
+        PlanNode {
+            node_type: node_type.into(),
+            relation: relation.map(String::from),
+            schema: None,
+            index: None,
+            join_type: None,
+            condition: None,
+            startup_cost: 0.0,
+            total_cost: cost,
+            plan_rows: 1.0,
+            plan_width: 4,
+            children,
+        }
+    }
+
+    fn plan(root: PlanNode) -> QueryPlan {
+        QueryPlan {
+            query: "SELECT * FROM a JOIN b USING (id)".into(),
+            root,
+            generic: false,
+        }
+    }
+
+    fn hash_join(a_cost: f64) -> QueryPlan {
+        plan(node(
+            "Hash Join",
+            None,
+            100.0 + a_cost,
+            vec![
+                node("Seq Scan", Some("a"), a_cost, vec![]),
+                node("Hash", None, 50.0, vec![node("Seq Scan", Some("b"), 40.0, vec![])]),
+            ],
+        ))
+    }
+
+    #[test]
+    fn same_plan_has_no_changes() {
+        let diff = PlanDiff::between(&hash_join(10.0), &hash_join(10.5));
+        assert!(diff.nodes.is_empty());
+        assert!(!diff.flipped());
+    }
+
+    #[test]
+    fn cost_changes_are_listed_per_node() {
+        let diff = PlanDiff::between(&hash_join(10.0), &hash_join(500.0));
+        assert!(!diff.flipped());
+        assert_eq!(
+            diff.nodes,
+            vec![
+                NodeChange::Cost { label: "Hash Join".into(), from: 110.0, to: 600.0 },
+                NodeChange::Cost { label: "Seq Scan on a".into(), from: 10.0, to: 500.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn flip_lists_removed_and_added_nodes() {
+        let nested = plan(node(
+            "Nested Loop",
+            None,
+            30.0,
+            vec![
+                node("Seq Scan", Some("b"), 40.0, vec![]),
+                node("Index Scan", Some("a"), 0.5, vec![]),
+            ],
+        ));
+        let diff = PlanDiff::between(&hash_join(10.0), &nested);
+        assert!(diff.flipped());
+        assert!(diff.nodes.contains(&NodeChange::Removed("Hash Join".into())));
+        assert!(diff.nodes.contains(&NodeChange::Removed("Seq Scan on a".into())));
+        assert!(diff.nodes.contains(&NodeChange::Added("Nested Loop".into())));
+        assert!(diff.nodes.contains(&NodeChange::Added("Index Scan on a".into())));
+        // Seq Scan on b is in both, at the same cost
+        assert!(!diff.nodes.iter().any(|n| matches!(n,
+            NodeChange::Added(l) | NodeChange::Removed(l) if l == "Seq Scan on b")));
+        assert!(diff.summary().ends_with("cost 110.00 → 30.00"));
+    }
+
+    #[test]
+    fn history_diffs_against_the_previous_plan_of_the_same_statement() {
+        let mut history = PlanHistory::default();
+        let at = Utc::now();
+        let key = PlanKey::new(Some(42), "SELECT 1");
+        assert_eq!(history.push(key.clone(), hash_join(10.0), at), None);
+        // Another statement doesn't count
+        assert_eq!(history.push(PlanKey::new(None, "SELECT 2"), hash_join(900.0), at), None);
+        let diff = history.push(key.clone(), hash_join(500.0), at).unwrap();
+        assert_eq!(diff.total_cost, (110.0, 600.0));
+        assert_eq!(history.runs(&key).len(), 2);
+
+        for _ in 0..MAX_RUNS {
+            history.push(key.clone(), hash_join(10.0), at);
+        }
+        assert_eq!(history.runs(&key).len(), MAX_RUNS);
+    }
+
+    #[test]
+    fn key_falls_back_to_query_text() {
+        assert_eq!(PlanKey::new(Some(0), " SELECT 1 "), PlanKey::Text("SELECT 1".into()));
+        assert_eq!(PlanKey::new(Some(7), "SELECT 1").describe(), "queryid 7");
+        assert_eq!(
+            PlanKey::new(None, "SELECT *\n  FROM a").describe(),
+            "\"SELECT * FROM a\""
+        );
+    }
+}
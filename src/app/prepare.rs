@@ -0,0 +1,209 @@
+//! Snapshot post-processing that runs before a snapshot reaches the UI.
+//!
+//! Live sources keep a [`SnapshotPrep`] in their worker task, so carrying
+//! bloat estimates across refreshes, diffing counters into rates, checking
+//! alert rules and notifications, and following operations, autovacuum,
+//! sessions, changed rows and adaptive refresh across snapshots happen off
+//! the UI thread; the app only swaps in the result. Replays and tests go
+//! through [`super::App::update`], which prepares in place.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+
+use crate::adaptive_refresh::{self, AdaptiveRefresh, AdaptiveRefreshConfig};
+use crate::alerts::{Alert, AlertRule, Alerts};
+use crate::autovacuum::{AutovacuumSettings, AutovacuumTracker};
+use crate::config::IgnoreList;
+use crate::db::models::{BloatSource, IndexInfo, PgSnapshot, TableStat};
+use crate::db::queries::{IndexBloat, TableBloat};
+use crate::notifications::{Notification, NotificationConfig, Notifier};
+use crate::session_stats::SessionTracker;
+
+use super::changes::ChangeTracker;
+use super::state::{IntervalRates, ProgressTracker, RateTracker, TempSpill};
+
+/// A bloat estimate as the snapshot rows carry it.
+type Carried = (Option<i64>, Option<f64>, Option<BloatSource>);
+
+/// A snapshot ready to go on screen.
+#[derive(Debug)]
+pub struct PreparedSnapshot {
+    /// Carries the latest bloat estimates
+    pub snapshot: PgSnapshot,
+    /// Temp spill rates over the interval this snapshot ends
+    pub temp_spill: TempSpill,
+    /// Counter rates over the same interval
+    pub rates: IntervalRates,
+    /// Alerts as of this snapshot, newest first
+    pub alerts: VecDeque<Alert>,
+    /// Of `alerts`, those this snapshot fired
+    pub fired_alerts: Vec<Alert>,
+    /// Desktop notifications and bells this snapshot set off
+    pub notifications: Vec<Notification>,
+    pub progress: ProgressTracker,
+    pub autovacuum: AutovacuumTracker,
+    pub sessions: SessionTracker,
+    /// Rows that changed since the snapshot before
+    pub changes: ChangeTracker,
+    /// The interval adaptive refresh settled on after this snapshot
+    pub adaptive_refresh: AdaptiveRefresh,
+}
+
+/// The app settings snapshots are prepared against.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrepWatch {
+    pub rules: Vec<AlertRule>,
+    /// What alerts leave out
+    pub ignore: IgnoreList,
+    pub autovacuum: AutovacuumSettings,
+    /// None while replaying: a recording doesn't notify
+    pub notifications: Option<NotificationConfig>,
+    pub danger_duration_secs: f64,
+    /// None while adaptive refresh is off or replaying
+    pub adaptive_refresh: Option<AdaptiveRefreshConfig>,
+    pub refresh_interval_secs: u64,
+    /// TPS samples averaged into the typical rate adaptive refresh compares
+    /// against, as many as the graphs keep
+    pub tps_history: usize,
+}
+
+/// What preparing needs to remember from one snapshot to the next.
+#[derive(Debug, Default)]
+pub struct SnapshotPrep {
+    /// Latest estimate per `schema.table`
+    table_bloat: HashMap<String, Carried>,
+    /// Latest estimate per `schema.index`
+    index_bloat: HashMap<String, Carried>,
+    temp_spill: TempSpill,
+    /// Table and index stats as last read, for refreshes that skip them
+    relation_stats: Option<(DateTime<Utc>, Vec<TableStat>, Vec<IndexInfo>)>,
+    rates: RateTracker,
+    watch: PrepWatch,
+    alerts: Alerts,
+    notifier: Notifier,
+    progress: ProgressTracker,
+    autovacuum: AutovacuumTracker,
+    sessions: SessionTracker,
+    changes: ChangeTracker,
+    adaptive_refresh: AdaptiveRefresh,
+    /// TPS of recent intervals, oldest first
+    tps: VecDeque<u64>,
+}
+
+impl SnapshotPrep {
+    /// Prepare the snapshots that follow against `watch`.
+    pub fn watch(&mut self, watch: &PrepWatch) {
+        if self.watch != *watch {
+            if self.watch.adaptive_refresh != watch.adaptive_refresh {
+                self.adaptive_refresh.reset();
+            }
+            self.watch.clone_from(watch);
+        }
+    }
+
+    /// Keep a bloat refresh for the snapshots that follow.
+    pub fn remember_bloat(
+        &mut self,
+        tables: &HashMap<String, TableBloat>,
+        indexes: &HashMap<String, IndexBloat>,
+    ) {
+        self.table_bloat.extend(
+            tables
+                .iter()
+                .map(|(key, b)| (key.clone(), (Some(b.bloat_bytes), Some(b.bloat_pct), Some(b.source)))),
+        );
+        self.index_bloat.extend(
+            indexes
+                .iter()
+                .map(|(key, b)| (key.clone(), (Some(b.bloat_bytes), Some(b.bloat_pct), Some(b.source)))),
+        );
+    }
+
+    /// Keep the table and index stats `snapshot` read, or, when it skipped
+    /// them (`read` false), fill in the last ones read.
+    pub fn relation_stats(&mut self, snapshot: &mut PgSnapshot, read: bool) {
+        if read {
+            self.relation_stats = Some((
+                snapshot.timestamp,
+                snapshot.table_stats.clone(),
+                snapshot.indexes.clone(),
+            ));
+        } else if let Some((read_at, tables, indexes)) = &self.relation_stats {
+            snapshot.table_stats.clone_from(tables);
+            snapshot.indexes.clone_from(indexes);
+            snapshot.relation_stats_read_at = Some(*read_at);
+        }
+    }
+
+    /// Fill in bloat from earlier refreshes (unless `carry_bloat` is off,
+    /// as for recorded snapshots that already have theirs), work out rates,
+    /// check the alert rules and notifications, and bring the trackers up
+    /// to date.
+    pub fn prepare(&mut self, mut snapshot: PgSnapshot, carry_bloat: bool) -> PreparedSnapshot {
+        if carry_bloat {
+            for table in &mut snapshot.table_stats {
+                let key = format!("{}.{}", table.schemaname, table.relname);
+                if table.bloat_pct.is_some() {
+                    let carried = (table.bloat_bytes, table.bloat_pct, table.bloat_source);
+                    self.table_bloat.insert(key, carried);
+                } else if let Some(&(bytes, pct, source)) = self.table_bloat.get(&key) {
+                    table.bloat_bytes = bytes;
+                    table.bloat_pct = pct;
+                    table.bloat_source = source;
+                }
+            }
+            for index in &mut snapshot.indexes {
+                let key = format!("{}.{}", index.schemaname, index.index_name);
+                if index.bloat_pct.is_some() {
+                    let carried = (index.bloat_bytes, index.bloat_pct, index.bloat_source);
+                    self.index_bloat.insert(key, carried);
+                } else if let Some(&(bytes, pct, source)) = self.index_bloat.get(&key) {
+                    index.bloat_bytes = bytes;
+                    index.bloat_pct = pct;
+                    index.bloat_source = source;
+                }
+            }
+        }
+        self.temp_spill.update(&snapshot);
+        let rates = self.rates.interval(&snapshot);
+        let fired_alerts = self.alerts.evaluate(&self.watch.rules, &self.watch.ignore, &snapshot);
+        let notifications = self.watch.notifications.as_ref().map_or_else(Vec::new, |config| {
+            self.notifier.check(config, self.watch.danger_duration_secs, &snapshot)
+        });
+        self.progress.observe(snapshot.timestamp, &snapshot.operations_in_progress());
+        self.autovacuum.observe(&snapshot, &self.watch.autovacuum);
+        self.sessions.observe(snapshot.timestamp, &snapshot.active_queries, &snapshot.capped_pids);
+        self.changes.observe(&snapshot);
+        self.observe_load(&snapshot, &rates);
+        PreparedSnapshot {
+            temp_spill: self.temp_spill.rates(),
+            rates,
+            alerts: self.alerts.history.clone(),
+            fired_alerts,
+            notifications,
+            progress: self.progress.clone(),
+            autovacuum: self.autovacuum.clone(),
+            sessions: self.sessions.clone(),
+            changes: self.changes.clone(),
+            adaptive_refresh: self.adaptive_refresh,
+            snapshot,
+        }
+    }
+
+    /// Let adaptive refresh see how busy `snapshot` shows the server to be,
+    /// its TPS compared with the intervals before.
+    fn observe_load(&mut self, snapshot: &PgSnapshot, rates: &IntervalRates) {
+        let typical = (!self.tps.is_empty())
+            .then(|| self.tps.iter().sum::<u64>() as f64 / self.tps.len() as f64);
+        if let Some(tps) = rates.tps {
+            self.tps.push_back(tps as u64);
+            let excess = self.tps.len().saturating_sub(self.watch.tps_history.max(1));
+            self.tps.drain(..excess);
+        }
+        if let Some(config) = &self.watch.adaptive_refresh {
+            let load = adaptive_refresh::load(snapshot, rates.tps, typical);
+            self.adaptive_refresh.observe(config, self.watch.refresh_interval_secs, load);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Rough progress for running queries.
+//!
+//! A backend running a command that has a `pg_stat_progress_*` view
+//! (VACUUM, ANALYZE, CLUSTER, CREATE INDEX, base backups) says how much of
+//! its current phase is done. Other statements report nothing, and a
+//! sequential scan in a SELECT or UPDATE has no progress of its own to
+//! read: the server keeps no per-backend block counts that could be set
+//! against the table's `relpages`. They are measured against two
+//! yardsticks instead: the `statement_timeout` that will cancel them, and
+//! how long the same statement (by queryid) took on average per
+//! pg_stat_statements. A `SET statement_timeout` inside the session itself
+//! can't be seen.
+
+use crate::db::models::{ActiveQuery, OperationProgress, PgSnapshot, ServerInfo};
+
+use super::ProgressTracker;
+
+/// Fewer calls than this make too shaky a mean to compare against.
+const MIN_TYPICAL_CALLS: i64 = 5;
+
+#[derive(Debug, Clone)]
+pub enum QueryProgress {
+    /// The backend's progress view, with the ETA at the rate seen so far
+    Reported {
+        operation: OperationProgress,
+        eta_secs: Option<f64>,
+    },
+    /// Elapsed time against what the statement is allowed or used to take
+    Estimated {
+        elapsed_secs: f64,
+        timeout: Option<StatementTimeout>,
+        typical: Option<TypicalRun>,
+    },
+}
+
+/// The `statement_timeout` that applies to a backend, and where it is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementTimeout {
+    pub secs: f64,
+    /// `server`, or the override's scope, e.g. `role app in shop`
+    pub source: String,
+}
+
+/// The statement's mean execution time from pg_stat_statements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypicalRun {
+    pub mean_secs: f64,
+}
+
+/// Share of `limit` that `elapsed` has used, 1.0 at the limit.
+pub fn fraction(elapsed_secs: f64, limit_secs: f64) -> f64 {
+    if limit_secs > 0.0 {
+        elapsed_secs / limit_secs
+    } else {
+        0.0
+    }
+}
+
+/// What can be told about how far `q` has got: its progress view row when
+/// it has one, otherwise its elapsed time against the yardsticks. `None`
+/// when it isn't running or there is nothing to measure it against.
+pub fn estimate(
+    q: &ActiveQuery,
+    snap: &PgSnapshot,
+    server: &ServerInfo,
+    tracker: &ProgressTracker,
+) -> Option<QueryProgress> {
+    if q.state.as_deref() != Some("active") {
+        return None;
+    }
+    if let Some(operation) = snap.operations_in_progress().into_iter().find(|op| op.pid == q.pid) {
+        let eta_secs = tracker.eta_secs(&operation, snap.timestamp);
+        return Some(QueryProgress::Reported { operation, eta_secs });
+    }
+
+    let timeout = statement_timeout(server, q.usename.as_deref(), q.datname.as_deref());
+    let typical = q
+        .query_id
+        .and_then(|id| snap.stat_statements.iter().find(|s| s.queryid == id))
+        .filter(|s| s.calls >= MIN_TYPICAL_CALLS && s.mean_exec_time > 0.0)
+        .map(|s| TypicalRun {
+            mean_secs: s.mean_exec_time / 1000.0,
+        });
+    (timeout.is_some() || typical.is_some()).then_some(QueryProgress::Estimated {
+        elapsed_secs: q.duration_secs,
+        timeout,
+        typical,
+    })
+}
+
+/// The timeout in effect for `role` in `database`: the most specific
+/// override wins (role in database, then role, then database), then the
+/// server's value. `None` when it is off.
+pub fn statement_timeout(server: &ServerInfo, role: Option<&str>, database: Option<&str>) -> Option<StatementTimeout> {
+    // Some(true) when the override names this role (or database), Some(false)
+    // when it leaves it open, None when it is for someone else
+    let matches = |scope: Option<&String>, actual: Option<&str>| match scope {
+        None => Some(false),
+        Some(s) => (actual == Some(s.as_str())).then_some(true),
+    };
+    let most_specific = server
+        .role_settings
+        .iter()
+        .filter(|o| o.name == "statement_timeout")
+        .filter_map(|o| {
+            let role_match = matches(o.role.as_ref(), role)?;
+            let db_match = matches(o.database.as_ref(), database)?;
+            Some((u8::from(role_match) * 2 + u8::from(db_match), o))
+        })
+        .max_by_key(|(rank, _)| *rank);
+
+    let (raw, source) = match most_specific {
+        Some((_, o)) => (o.value.as_str(), o.scope()),
+        None => {
+            let setting = server.settings.iter().find(|s| s.name == "statement_timeout")?;
+            (setting.setting.as_str(), "server".to_string())
+        }
+    };
+    let secs = parse_timeout_secs(raw)?;
+    (secs > 0.0).then_some(StatementTimeout { secs, source })
+}
+
+/// A time setting as written in `ALTER ROLE ... SET` or read from
+/// `pg_settings`; a bare number is milliseconds.
+fn parse_timeout_secs(raw: &str) -> Option<f64> {
+    let raw = raw.trim().trim_matches('\'');
+    let split = raw.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(raw.len());
+    let value: f64 = raw[..split].parse().ok()?;
+    let scale = match raw[split..].trim() {
+        "" | "ms" => 0.001,
+        "us" => 0.000_001,
+        "s" => 1.0,
+        "min" => 60.0,
+        "h" => 3600.0,
+        "d" => 86_400.0,
+        _ => return None,
+    };
+    Some(value * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::capabilities::Capabilities;
+    use crate::db::models::{DetectedExtensions, PgSetting, RoleSetting};
+
+    fn server(setting: &str, overrides: &[(Option<&str>, Option<&str>, &str)]) -> ServerInfo {
+        ServerInfo {
+            version: "PostgreSQL 16.2".into(),
+            start_time: chrono::Utc::now(),
+            max_connections: 100,
+            extensions: DetectedExtensions::default(),
+            settings: vec![PgSetting {
+                name: "statement_timeout".into(),
+                setting: setting.into(),
+                unit: Some("ms".into()),
+                category: String::new(),
+                short_desc: None,
+                context: "user".into(),
+                source: "configuration file".into(),
+                pending_restart: false,
+                boot_val: None,
+                reset_val: None,
+            }],
+            role_settings: overrides
+                .iter()
+                .map(|(role, database, value)| RoleSetting {
+                    role: role.map(String::from),
+                    database: database.map(String::from),
+                    name: "statement_timeout".into(),
+                    value: (*value).into(),
+                })
+                .collect(),
+            extensions_list: vec![],
+            roles: vec![],
+            capabilities: Capabilities::default(),
+        }
+    }
+
+    #[test]
+    fn parses_time_settings() {
+        assert_eq!(parse_timeout_secs("30000"), Some(30.0));
+        assert_eq!(parse_timeout_secs("30s"), Some(30.0));
+        assert_eq!(parse_timeout_secs("'5min'"), Some(300.0));
+        assert_eq!(parse_timeout_secs("1.5h"), Some(5400.0));
+        assert_eq!(parse_timeout_secs("soon"), None);
+    }
+
+    #[test]
+    fn most_specific_statement_timeout_wins() {
+        let server = server(
+            "60000",
+            &[
+                (Some("app"), None, "30s"),
+                (Some("app"), Some("shop"), "10s"),
+                (None, Some("shop"), "2min"),
+                (Some("batch"), None, "0"),
+            ],
+        );
+        let secs = |role, db| statement_timeout(&server, role, db).map(|t| (t.secs, t.source));
+        assert_eq!(secs(Some("app"), Some("shop")), Some((10.0, "role app in shop".into())));
+        assert_eq!(secs(Some("app"), Some("other")), Some((30.0, "role app".into())));
+        assert_eq!(secs(Some("web"), Some("shop")), Some((120.0, "database shop".into())));
+        assert_eq!(secs(Some("web"), Some("other")), Some((60.0, "server".into())));
+        // An override can turn the timeout off
+        assert_eq!(secs(Some("batch"), Some("other")), None);
+    }
+}
@@ -0,0 +1,597 @@
+//! Panel registry.
+//!
+//! Every bottom panel is described once in [`PANELS`]. Key dispatch, rendering,
+//! the footer and the help overlay all iterate this table, so adding a panel
+//! means adding a `BottomPanel` variant, its state, and one entry here.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+use serde_json::Value;
+
+use super::panels::BottomPanel;
+use super::App;
+use crate::db::models::PgSnapshot;
+use crate::table_export::rows;
+use crate::ui;
+
+/// Export rows from a snapshot list, in the panel's display order.
+fn snapshot_rows<T: serde::Serialize>(
+    app: &App,
+    list: fn(&PgSnapshot) -> &[T],
+    indices: Option<Vec<usize>>,
+) -> Vec<Value> {
+    let Some(snap) = &app.snapshot else {
+        return vec![];
+    };
+    let items = list(snap);
+    rows(items, indices.unwrap_or_else(|| (0..items.len()).collect()))
+}
+
+/// Static description of a bottom panel.
+pub struct PanelDescriptor {
+    pub panel: BottomPanel,
+    /// Stable identifier, safe to use in config files and CLI flags
+    pub id: &'static str,
+    /// Full title, used in help and status text
+    pub title: &'static str,
+    /// Compact name shown in the footer
+    pub short_title: &'static str,
+    /// Key that switches to this panel
+    pub key: KeyCode,
+    /// How the key is written in the help overlay
+    pub key_label: &'static str,
+    /// Letter after `g` that also goes to this panel
+    pub goto: char,
+    /// One-line description for the help overlay
+    pub help: &'static str,
+    /// Key glyph and hint in the footer's panel switch row (None to omit)
+    pub footer_hint: Option<(&'static str, &'static str)>,
+    pub supports_filter: bool,
+    /// Column headers the `c` chooser can hide, in display order (empty
+    /// when the panel has no chooser)
+    pub columns: &'static [&'static str],
+    pub render: fn(&mut Frame, &mut App, Rect),
+    pub handle_key: fn(&mut App, KeyEvent),
+    /// Number of rows currently visible (after filtering)
+    pub row_count: fn(&App) -> usize,
+    /// Visible rows in display order, for CSV/JSON export
+    pub export_rows: fn(&App) -> Vec<Value>,
+}
+
+/// All panels, in `BottomPanel` declaration order.
+pub static PANELS: [PanelDescriptor; 27] = [
+    PanelDescriptor {
+        panel: BottomPanel::Queries,
+        id: "queries",
+        title: "Queries",
+        short_title: "Queries",
+        key: KeyCode::Char('Q'),
+        key_label: "Q",
+        goto: 'q',
+        help: "Queries (active)",
+        footer_hint: None,
+        supports_filter: true,
+        columns: &["PID", "Query", "User", "Database", "Duration", "State", "Wait", "Blocker"],
+        render: |frame, app, area| ui::active_queries::render(frame, app, area),
+        handle_key: App::handle_queries_key,
+        row_count: |app| app.sorted_query_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.active_queries, Some(app.sorted_query_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Blocking,
+        id: "blocking",
+        title: "Blocking",
+        short_title: "Blocking",
+        key: KeyCode::Tab,
+        key_label: "Tab",
+        goto: 'b',
+        help: "Blocking chains",
+        footer_hint: Some(("⇥", "block")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_blocking(frame, app, area),
+        handle_key: App::handle_blocking_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.blocking_info.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.blocking_info, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Locks,
+        id: "locks",
+        title: "Locks",
+        short_title: "Locks",
+        key: KeyCode::Char('O'),
+        key_label: "O",
+        goto: 'l',
+        help: "Locks held and awaited (pg_locks)",
+        footer_hint: Some(("O", "locks")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_locks(frame, app, area),
+        handle_key: App::handle_locks_key,
+        row_count: |app| app.sorted_lock_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.locks, Some(app.sorted_lock_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::ConnectionGroups,
+        id: "connection_groups",
+        title: "Connection Groups",
+        short_title: "Conns",
+        key: KeyCode::Char('U'),
+        key_label: "U",
+        goto: 'c',
+        help: "Connections by user, database and application",
+        footer_hint: Some(("U", "conns")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_connection_groups(frame, app, area),
+        handle_key: App::handle_connection_groups_key,
+        row_count: |app| app.sorted_connection_group_indices().len(),
+        export_rows: |app| {
+            snapshot_rows(app, |s| &s.connection_groups, Some(app.sorted_connection_group_indices()))
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Sessions,
+        id: "sessions",
+        title: "Sessions",
+        short_title: "Sessions",
+        key: KeyCode::Char('$'),
+        key_label: "$",
+        goto: '$',
+        help: "Per-session active time, transactions and queries sampled this session",
+        footer_hint: Some(("$", "sessions")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_sessions(frame, app, area),
+        handle_key: App::handle_sessions_key,
+        row_count: |app| app.session_rows().len(),
+        export_rows: |app| {
+            let sessions = app.session_rows();
+            rows(&sessions, 0..sessions.len())
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Pooler,
+        id: "pooler",
+        title: "Pooler",
+        short_title: "Pooler",
+        key: KeyCode::Char('Z'),
+        key_label: "Z",
+        goto: 'B',
+        help: "PgBouncer pools and client queues (--pgbouncer)",
+        footer_hint: None,
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_pooler(frame, app, area),
+        handle_key: App::handle_pooler_key,
+        row_count: |app| app.snapshot.as_ref().and_then(|s| s.pooler.as_ref()).map_or(0, |p| p.pools.len()),
+        export_rows: |app| snapshot_rows(app, |s| s.pooler.as_ref().map_or(&[], |p| &p.pools), None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::WaitEvents,
+        id: "wait_events",
+        title: "Wait Events",
+        short_title: "Waits",
+        key: KeyCode::Char('w'),
+        key_label: "w",
+        goto: 'w',
+        help: "Wait events",
+        footer_hint: Some(("w", "waits")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_wait_events(frame, app, area),
+        handle_key: App::handle_wait_events_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.wait_events.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.wait_events, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::TableStats,
+        id: "tables",
+        title: "Table Stats",
+        short_title: "Tables",
+        key: KeyCode::Char('t'),
+        key_label: "t",
+        goto: 't',
+        help: "Table stats",
+        footer_hint: Some(("t", "tables")),
+        supports_filter: true,
+        columns: &["Table", "Size", "SeqScan", "IdxScan", "Dead", "Dead%", "Bloat", "Last Vacuum"],
+        render: |frame, app, area| ui::panels::render_table_stats(frame, app, area),
+        handle_key: App::handle_table_stats_key,
+        row_count: |app| app.sorted_table_stat_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.table_stats, Some(app.sorted_table_stat_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Replication,
+        id: "replication",
+        title: "Replication",
+        short_title: "Replication",
+        key: KeyCode::Char('R'),
+        key_label: "R",
+        goto: 'r',
+        help: "Replication (lag, slots, subs)",
+        footer_hint: Some(("R", "repl")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_replication(frame, app, area),
+        handle_key: App::handle_replication_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.replication.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.replication, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Slots,
+        id: "slots",
+        title: "Replication Slots",
+        short_title: "Slots",
+        key: KeyCode::Char('W'),
+        key_label: "W",
+        goto: 's',
+        help: "Replication slots (retained WAL, spill, drop)",
+        footer_hint: Some(("W", "slots")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_slots(frame, app, area),
+        handle_key: App::handle_slots_key,
+        row_count: |app| app.sorted_slot_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.replication_slots, Some(app.sorted_slot_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::LogicalReplication,
+        id: "logical_replication",
+        title: "Logical Replication",
+        short_title: "Logical",
+        key: KeyCode::Char('&'),
+        key_label: "&",
+        goto: 'L',
+        help: "Subscriptions (apply lag, errors, conflicts, worker down)",
+        footer_hint: Some(("&", "logical")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_logical_replication(frame, app, area),
+        handle_key: App::handle_logical_replication_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.subscriptions.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.subscriptions, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::VacuumProgress,
+        id: "vacuum",
+        title: "Vacuum Progress",
+        short_title: "Vacuum",
+        key: KeyCode::Char('v'),
+        key_label: "v",
+        goto: 'v',
+        help: "Vacuum progress",
+        footer_hint: Some(("v", "vacuum")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_vacuum_progress(frame, app, area),
+        handle_key: App::handle_vacuum_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.vacuum_progress.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.vacuum_progress, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::AutovacuumHealth,
+        id: "autovacuum",
+        title: "Autovacuum Health",
+        short_title: "Autovac",
+        key: KeyCode::Char('V'),
+        key_label: "V",
+        goto: 'd',
+        help: "Autovacuum workers and starved tables",
+        footer_hint: Some(("V", "autovac")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_autovacuum(frame, app, area),
+        handle_key: App::handle_autovacuum_key,
+        row_count: |app| app.autovacuum_rows().len(),
+        export_rows: |app| {
+            let tables = app.autovacuum_rows();
+            rows(&tables, 0..tables.len())
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Operations,
+        id: "operations",
+        title: "Operations in Progress",
+        short_title: "Ops",
+        key: KeyCode::Char('M'),
+        key_label: "M",
+        goto: 'o',
+        help: "Vacuum, CREATE INDEX, CLUSTER, ANALYZE and base backup progress",
+        footer_hint: Some(("M", "ops")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_operations(frame, app, area),
+        handle_key: App::handle_operations_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.vacuum_progress.len() + s.operations.len()),
+        export_rows: |app| {
+            let operations = app.snapshot.as_ref().map_or_else(Vec::new, PgSnapshot::operations_in_progress);
+            rows(&operations, 0..operations.len())
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Backends,
+        id: "backends",
+        title: "Background Workers",
+        short_title: "Workers",
+        key: KeyCode::Char('Y'),
+        key_label: "Y",
+        goto: 'y',
+        help: "Parallel, autovacuum and replication workers and other non-client backends",
+        footer_hint: Some(("Y", "workers")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_backends(frame, app, area),
+        handle_key: App::handle_backends_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.background_workers.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.background_workers, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Wraparound,
+        id: "wraparound",
+        title: "Wraparound",
+        short_title: "XID",
+        key: KeyCode::Char('x'),
+        key_label: "x",
+        goto: 'x',
+        help: "Transaction wraparound",
+        footer_hint: Some(("x", "xid")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_wraparound(frame, app, area),
+        handle_key: App::handle_wraparound_key,
+        row_count: |app| app.snapshot.as_ref().map_or(0, |s| s.wraparound.len()),
+        export_rows: |app| snapshot_rows(app, |s| &s.wraparound, None),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Indexes,
+        id: "indexes",
+        title: "Indexes",
+        short_title: "Indexes",
+        key: KeyCode::Char('I'),
+        key_label: "I",
+        goto: 'i',
+        help: "Index stats",
+        footer_hint: Some(("I", "idx")),
+        supports_filter: true,
+        columns: &["Table", "Index", "Size", "Scans", "Tup Read", "Tup Fetch", "Bloat"],
+        render: |frame, app, area| ui::panels::render_indexes(frame, app, area),
+        handle_key: App::handle_indexes_key,
+        row_count: |app| app.sorted_index_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.indexes, Some(app.sorted_index_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::IndexHealth,
+        id: "index_health",
+        title: "Index Health",
+        short_title: "Idx Health",
+        key: KeyCode::Char('F'),
+        key_label: "F",
+        goto: 'h',
+        help: "Unused, duplicate and invalid indexes",
+        footer_hint: Some(("F", "health")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_index_health(frame, app, area),
+        handle_key: App::handle_index_health_key,
+        row_count: |app| app.index_findings().len(),
+        export_rows: |app| {
+            let findings = app.index_findings();
+            rows(&findings, 0..findings.len())
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Statements,
+        id: "statements",
+        title: "Statements",
+        short_title: "Statements",
+        key: KeyCode::Char('S'),
+        key_label: "S",
+        goto: 'S',
+        help: "pg_stat_statements",
+        footer_hint: Some(("S", "stmts")),
+        supports_filter: true,
+        columns: &[
+            "Query", "Calls", "Total", "Mean", "Max", "P95", "Stddev", "Rows", "Hit%", "Reads", "I/O", "Temp", "Errors",
+        ],
+        render: |frame, app, area| ui::panels::render_statements(frame, app, area),
+        handle_key: App::handle_statements_key,
+        row_count: |app| app.sorted_stmt_indices().len(),
+        export_rows: |app| snapshot_rows(app, |s| &s.stat_statements, Some(app.sorted_stmt_indices())),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::WalIo,
+        id: "wal_io",
+        title: "WAL & I/O",
+        short_title: "WAL",
+        key: KeyCode::Char('A'),
+        key_label: "A",
+        goto: 'a',
+        help: "WAL & I/O stats",
+        footer_hint: Some(("A", "wal")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_wal_io(frame, app, area),
+        handle_key: |_, _| {},
+        row_count: |_| 0,
+        export_rows: |_| vec![],
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Checkpoints,
+        id: "checkpoints",
+        title: "Checkpoints",
+        short_title: "Checkpoints",
+        key: KeyCode::Char('B'),
+        key_label: "B",
+        goto: 'k',
+        help: "Checkpoint and bgwriter history",
+        footer_hint: Some(("B", "ckpt")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_checkpoints(frame, app, area),
+        handle_key: |_, _| {},
+        row_count: |app| app.metrics.checkpoint_log.as_vec().len(),
+        export_rows: |_| vec![],
+    },
+    PanelDescriptor {
+        panel: BottomPanel::BufferCache,
+        id: "buffer_cache",
+        title: "Buffer Cache",
+        short_title: "Buffers",
+        key: KeyCode::Char('N'),
+        key_label: "N",
+        goto: 'u',
+        help: "Shared buffer contents (pg_buffercache, b to read)",
+        footer_hint: Some(("N", "bufs")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_buffer_cache(frame, app, area),
+        handle_key: App::handle_buffer_cache_key,
+        row_count: |app| app.buffer_cache.as_ref().map_or(0, |r| r.relations.len()),
+        export_rows: |app| {
+            app.buffer_cache.as_ref().map_or_else(Vec::new, |r| {
+                rows(&r.relations, 0..r.relations.len())
+            })
+        },
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Settings,
+        id: "settings",
+        title: "Settings",
+        short_title: "Settings",
+        key: KeyCode::Char('P'),
+        key_label: "P",
+        goto: 'p',
+        help: "PostgreSQL settings",
+        footer_hint: Some(("P", "cfg")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_settings(frame, app, area),
+        handle_key: App::handle_settings_key,
+        row_count: |app| app.sorted_settings_indices().len(),
+        export_rows: |app| rows(&app.server_info.settings, app.sorted_settings_indices()),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Extensions,
+        id: "extensions",
+        title: "Extensions",
+        short_title: "Extensions",
+        key: KeyCode::Char('E'),
+        key_label: "E",
+        goto: 'e',
+        help: "Extensions",
+        footer_hint: Some(("E", "ext")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_extensions(frame, app, area),
+        handle_key: App::handle_extensions_key,
+        row_count: |app| app.sorted_extensions_indices().len(),
+        export_rows: |app| rows(&app.server_info.extensions_list, app.sorted_extensions_indices()),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Roles,
+        id: "roles",
+        title: "Roles",
+        short_title: "Roles",
+        key: KeyCode::Char('@'),
+        key_label: "@",
+        goto: '@',
+        help: "Roles, memberships and connections",
+        footer_hint: Some(("@", "roles")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_roles(frame, app, area),
+        handle_key: App::handle_roles_key,
+        row_count: |app| app.sorted_role_indices().len(),
+        export_rows: |app| rows(&app.server_info.roles, app.sorted_role_indices()),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Logs,
+        id: "logs",
+        title: "Logs",
+        short_title: "Logs",
+        key: KeyCode::Char('J'),
+        key_label: "J",
+        goto: 'j',
+        help: "Server log lines (Server Log Tail, f cycles levels)",
+        footer_hint: Some(("J", "logs")),
+        supports_filter: true,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_logs(frame, app, area),
+        handle_key: App::handle_logs_key,
+        row_count: |app| app.log_indices().len(),
+        export_rows: |app| rows(&app.server_log, app.log_indices()),
+    },
+    PanelDescriptor {
+        panel: BottomPanel::Alerts,
+        id: "alerts",
+        title: "Alerts",
+        short_title: "Alerts",
+        key: KeyCode::Char('!'),
+        key_label: "!",
+        goto: '!',
+        help: "Alerts (active and resolved)",
+        footer_hint: Some(("!", "alerts")),
+        supports_filter: false,
+        columns: &[],
+        render: |frame, app, area| ui::panels::render_alerts(frame, app, area),
+        handle_key: App::handle_alerts_key,
+        row_count: |app| app.alerts.history.len(),
+        export_rows: |app| {
+            let history: Vec<_> = app.alerts.history.iter().collect();
+            rows(&history, 0..history.len())
+        },
+    },
+];
+
+impl BottomPanel {
+    /// Registry entry for this panel.
+    pub fn descriptor(self) -> &'static PanelDescriptor {
+        &PANELS[self as usize]
+    }
+
+    /// Look up the panel bound to a switch key.
+    pub fn from_key(code: KeyCode) -> Option<Self> {
+        PANELS.iter().find(|d| d.key == code).map(|d| d.panel)
+    }
+
+    /// Look up a panel by its stable id.
+    pub fn from_id(id: &str) -> Option<Self> {
+        PANELS.iter().find(|d| d.id == id).map(|d| d.panel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn registry_is_in_declaration_order() {
+        for (i, desc) in PANELS.iter().enumerate() {
+            assert_eq!(desc.panel as usize, i, "{} is out of order", desc.id);
+            assert_eq!(desc.panel.descriptor().id, desc.id);
+        }
+    }
+
+    #[test]
+    fn keys_and_ids_are_unique() {
+        let keys: HashSet<_> = PANELS.iter().map(|d| d.key).collect();
+        let gotos: HashSet<_> = PANELS.iter().map(|d| d.goto).collect();
+        let ids: HashSet<_> = PANELS.iter().map(|d| d.id).collect();
+        assert_eq!(keys.len(), PANELS.len());
+        assert_eq!(gotos.len(), PANELS.len());
+        assert_eq!(ids.len(), PANELS.len());
+    }
+
+    #[test]
+    fn lookup_by_key_and_id() {
+        assert_eq!(BottomPanel::from_key(KeyCode::Tab), Some(BottomPanel::Blocking));
+        assert_eq!(BottomPanel::from_key(KeyCode::Char('Z')), Some(BottomPanel::Pooler));
+        assert_eq!(BottomPanel::from_key(KeyCode::Char('K')), None);
+        assert_eq!(BottomPanel::from_id("wal_io"), Some(BottomPanel::WalIo));
+        assert_eq!(BottomPanel::from_id("nope"), None);
+    }
+}
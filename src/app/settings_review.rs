@@ -0,0 +1,192 @@
+//! Settings that differ from their defaults, and the risky ones among them.
+//!
+//! The Settings panel can narrow itself to changed settings; the review
+//! overlay goes further and calls out values known to trade safety or memory
+//! for speed, so a misconfigured server stands out before its first crash or
+//! OOM kill rather than after.
+
+use crate::db::models::{PgSetting, ServerInfo};
+
+/// How bad a flagged value is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Risk {
+    /// Data can be lost or corrupted
+    Danger,
+    /// Works, but hurts under load or hides problems
+    Warn,
+}
+
+/// A setting whose value matches one of the [`RULES`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiskyValue {
+    pub name: &'static str,
+    /// Value as shown in the Settings panel, with its unit
+    pub value: String,
+    pub risk: Risk,
+    pub why: &'static str,
+}
+
+/// A changed setting with what it was changed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deviation {
+    pub name: String,
+    pub value: String,
+    pub default: String,
+    pub source: String,
+}
+
+struct Rule {
+    name: &'static str,
+    risk: Risk,
+    why: &'static str,
+    /// Whether the raw `pg_settings.setting` value is risky
+    risky: fn(&str) -> bool,
+}
+
+fn is_off(value: &str) -> bool {
+    value == "off"
+}
+
+fn is_on(value: &str) -> bool {
+    value == "on"
+}
+
+/// Above `limit` in the setting's own unit.
+fn above(value: &str, limit: i64) -> bool {
+    value.parse::<i64>().is_ok_and(|v| v > limit)
+}
+
+const RULES: [Rule; 12] = [
+    Rule {
+        name: "fsync",
+        risk: Risk::Danger,
+        why: "an OS crash or power loss can corrupt the cluster",
+        risky: is_off,
+    },
+    Rule {
+        name: "full_page_writes",
+        risk: Risk::Danger,
+        why: "torn pages after a crash can't be repaired from WAL",
+        risky: is_off,
+    },
+    Rule {
+        name: "zero_damaged_pages",
+        risk: Risk::Danger,
+        why: "damaged pages are silently zeroed, losing their rows",
+        risky: is_on,
+    },
+    Rule {
+        name: "ignore_checksum_failure",
+        risk: Risk::Danger,
+        why: "corrupt pages are read as if they were fine",
+        risky: is_on,
+    },
+    Rule {
+        name: "autovacuum",
+        risk: Risk::Danger,
+        why: "nothing vacuums or prevents wraparound unless done by hand",
+        risky: is_off,
+    },
+    Rule {
+        name: "track_counts",
+        risk: Risk::Danger,
+        why: "autovacuum has no statistics to act on",
+        risky: is_off,
+    },
+    Rule {
+        name: "synchronous_commit",
+        risk: Risk::Warn,
+        why: "the last commits before a crash can be lost",
+        risky: is_off,
+    },
+    Rule {
+        // kB: 256MB
+        name: "work_mem",
+        risk: Risk::Warn,
+        why: "allowed per sort or hash in every query; concurrent queries can exhaust memory",
+        risky: |v| above(v, 256 * 1024),
+    },
+    Rule {
+        // kB: 4GB
+        name: "maintenance_work_mem",
+        risk: Risk::Warn,
+        why: "allowed per vacuum and index build, times autovacuum_max_workers",
+        risky: |v| above(v, 4 * 1024 * 1024),
+    },
+    Rule {
+        name: "max_connections",
+        risk: Risk::Warn,
+        why: "each backend costs memory and snapshot overhead; use a pooler",
+        risky: |v| above(v, 500),
+    },
+    Rule {
+        // 8kB pages: the compiled-in 128MB
+        name: "shared_buffers",
+        risk: Risk::Warn,
+        why: "still the 128MB default; 25% of RAM is the usual start",
+        risky: |v| v == "16384",
+    },
+    Rule {
+        name: "log_min_duration_statement",
+        risk: Risk::Warn,
+        why: "every statement is logged, which costs I/O and log space",
+        risky: |v| v == "0",
+    },
+];
+
+/// Value with its unit, as the Settings panel shows it.
+fn display_value(setting: &PgSetting) -> String {
+    match &setting.unit {
+        Some(unit) if setting.setting.parse::<f64>().is_ok() => format!("{} {unit}", setting.setting),
+        _ => setting.setting.clone(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsReview {
+    pub risky: Vec<RiskyValue>,
+    /// Changed settings, most consequential context first: those needing a
+    /// restart, then reload, then per-session
+    pub changed: Vec<Deviation>,
+}
+
+impl SettingsReview {
+    pub fn build(server: &ServerInfo) -> Self {
+        let mut risky: Vec<RiskyValue> = RULES
+            .iter()
+            .filter_map(|rule| {
+                let setting = server.settings.iter().find(|s| s.name == rule.name)?;
+                (rule.risky)(&setting.setting).then(|| RiskyValue {
+                    name: rule.name,
+                    value: display_value(setting),
+                    risk: rule.risk,
+                    why: rule.why,
+                })
+            })
+            .collect();
+        risky.sort_by_key(|r| r.risk);
+
+        let context_rank = |context: &str| match context {
+            "internal" | "postmaster" => 0,
+            "sighup" | "superuser-backend" | "backend" => 1,
+            _ => 2,
+        };
+        let mut changed: Vec<&PgSetting> = server.settings.iter().filter(|s| s.is_changed()).collect();
+        changed.sort_by_key(|s| context_rank(&s.context));
+        let changed = changed
+            .into_iter()
+            .map(|s| Deviation {
+                name: s.name.clone(),
+                value: display_value(s),
+                default: s.boot_val.clone().unwrap_or_else(|| "?".into()),
+                source: s.source.clone(),
+            })
+            .collect();
+
+        Self { risky, changed }
+    }
+
+    pub fn has_danger(&self) -> bool {
+        self.risky.iter().any(|r| r.risk == Risk::Danger)
+    }
+}
@@ -1,6 +1,9 @@
 //! Sorting and filtering logic for table views.
 
-use crate::db::models::{ActiveQuery, IndexInfo, PgExtension, PgSetting, StatStatement, TableStat};
+use crate::db::models::{
+    ActiveQuery, ConnectionGroup, IndexInfo, LockInfo, LogLine, PgExtension, PgSetting, ReplicationSlot, RoleInfo, RoleSetting,
+    StatStatement, TableStat,
+};
 
 /// Trait for types that can be filtered with fuzzy matching.
 pub trait Filterable {
@@ -42,12 +45,65 @@ impl Filterable for TableStat {
     }
 }
 
+impl Filterable for ConnectionGroup {
+    fn filter_string(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.usename.as_deref().unwrap_or(""),
+            self.datname.as_deref().unwrap_or(""),
+            self.application_name,
+        )
+    }
+}
+
+impl Filterable for LockInfo {
+    fn filter_string(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.pid,
+            self.locktype,
+            self.mode,
+            self.relation.as_deref().unwrap_or(""),
+            self.usename.as_deref().unwrap_or(""),
+            self.query.as_deref().unwrap_or(""),
+        )
+    }
+}
+
+impl Filterable for ReplicationSlot {
+    fn filter_string(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.slot_name,
+            self.slot_type,
+            self.database.as_deref().unwrap_or(""),
+        )
+    }
+}
+
+impl Filterable for RoleSetting {
+    fn filter_string(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.name,
+            self.role.as_deref().unwrap_or(""),
+            self.database.as_deref().unwrap_or("")
+        )
+    }
+}
+
 impl Filterable for PgSetting {
     fn filter_string(&self) -> String {
         format!("{} {} {}", self.name, self.category, self.short_desc.as_deref().unwrap_or(""))
     }
 }
 
+impl Filterable for LogLine {
+    fn filter_string(&self) -> String {
+        format!("{} {} {}", self.prefix, self.level.label(), self.message)
+    }
+}
+
 impl Filterable for PgExtension {
     fn filter_string(&self) -> String {
         format!(
@@ -59,6 +115,12 @@ impl Filterable for PgExtension {
     }
 }
 
+impl Filterable for RoleInfo {
+    fn filter_string(&self) -> String {
+        format!("{} {}", self.name, self.member_of.join(" "))
+    }
+}
+
 /// Trait for sort column enums to enable generic `TableViewState`
 pub trait SortColumnTrait: Copy + PartialEq {
     fn next(self) -> Self;
@@ -122,6 +184,37 @@ define_sort_column!(TableStatSortColumn {
     DeadRatio => "Dead %",
 });
 
+define_sort_column!(LockSortColumn {
+    Status => "Status",
+    Duration => "Duration",
+    Mode => "Mode",
+    Relation => "Relation",
+    Pid => "PID",
+});
+
+define_sort_column!(SlotSortColumn {
+    Retained => "WAL Retained",
+    Spill => "Spill Bytes",
+    Active => "Active",
+    Name => "Slot",
+});
+
+define_sort_column!(ConnectionGroupSortColumn {
+    Total => "Total",
+    Active => "Active",
+    IdleInTransaction => "Idle in Txn",
+    Longest => "Longest",
+    User => "User",
+});
+
+define_sort_column!(SessionSortColumn {
+    ActiveTime => "Active",
+    IdleInTxnTime => "Idle in Txn",
+    Xacts => "Xacts",
+    Queries => "Queries",
+    StateChanges => "Changes",
+});
+
 define_sort_column!(StatementSortColumn {
     TotalTime => "Total Time",
     MeanTime => "Mean Time",
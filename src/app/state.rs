@@ -1,17 +1,33 @@
 //! Application state types.
 
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveTime, Utc};
+use ratatui::layout::{Margin, Rect};
 use ratatui::widgets::TableState;
 
-use crate::db::models::PgSnapshot;
+use crate::alerts::Alert;
+use crate::db::models::{
+    ActiveQuery, BgwriterStats, BloatSource, CheckpointStats, DatabaseStats, IndexAdvice, IoStat, OperationKind,
+    OperationProgress, PgSnapshot, QueryPlan, ReplicationInfo, RoleGrant, StatStatement,
+    StatementSample, TableSchema, WaitEventCount, WaitSampleSource, WalStats, WraparoundTable,
+};
+use crate::db::queries::{IndexBloat, TableBloat};
 use crate::history::RingBuffer;
-use crate::recorder::RecordingInfo;
+use crate::maintenance::Maintenance;
+use crate::hooks::HookOutcome;
+use crate::notifications::Notification;
+use crate::recorder::{RecordingInfo, UiEvent, UiEventKind};
 
 use super::panels::BottomPanel;
 use super::sorting::SortColumnTrait;
-use super::{AppAction, IndexSortColumn, SortColumn, StatementSortColumn, TableStatSortColumn};
+use super::{
+    AppAction, ConnectionGroupSortColumn, IndexSortColumn, LockSortColumn, SessionSortColumn, SlotSortColumn, SortColumn,
+    StatementSortColumn, TableStatSortColumn,
+};
 
 /// Generic table view state with sort column and navigation
 #[derive(Debug)]
@@ -64,6 +80,13 @@ impl<S: SortColumnTrait> TableViewState<S> {
         self.state.select(Some(new_pos));
     }
 
+    /// Keep the selection in range after rows disappear from the list
+    pub fn clamp_selection(&mut self, max: usize) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.min(max.saturating_sub(1))));
+        }
+    }
+
     #[must_use]
     pub const fn selected(&self) -> Option<usize> {
         self.state.selected()
@@ -78,13 +101,30 @@ pub struct PanelStates {
     pub indexes: TableViewState<IndexSortColumn>,
     pub statements: TableViewState<StatementSortColumn>,
     pub table_stats: TableViewState<TableStatSortColumn>,
+    pub locks: TableViewState<LockSortColumn>,
+    pub connection_groups: TableViewState<ConnectionGroupSortColumn>,
+    pub sessions: TableViewState<SessionSortColumn>,
+    pub slots: TableViewState<SlotSortColumn>,
     // Simple panels (no sorting/filtering)
     pub replication: TableState,
     pub blocking: TableState,
     pub vacuum: TableState,
+    pub autovacuum: TableState,
     pub wraparound: TableState,
+    pub index_health: TableState,
+    pub operations: TableState,
+    pub backends: TableState,
+    pub wait_events: TableState,
+    pub buffer_cache: TableState,
     pub settings: TableState,
     pub extensions: TableState,
+    pub roles: TableState,
+    pub logs: TableState,
+    pub alerts: TableState,
+    pub pooler: TableState,
+    pub logical_replication: TableState,
+    /// Filters by panel, kept while switching away and back
+    filters: HashMap<BottomPanel, FilterState>,
 }
 
 impl PanelStates {
@@ -94,15 +134,43 @@ impl PanelStates {
             indexes: TableViewState::new(IndexSortColumn::Scans, true),
             statements: TableViewState::new(StatementSortColumn::TotalTime, false),
             table_stats: TableViewState::new(TableStatSortColumn::DeadTuples, false),
+            locks: TableViewState::new(LockSortColumn::Status, false),
+            connection_groups: TableViewState::new(ConnectionGroupSortColumn::Total, false),
+            sessions: TableViewState::new(SessionSortColumn::ActiveTime, false),
+            slots: TableViewState::new(SlotSortColumn::Retained, false),
             replication: TableState::default(),
             blocking: TableState::default(),
             vacuum: TableState::default(),
+            autovacuum: TableState::default(),
             wraparound: TableState::default(),
+            index_health: TableState::default(),
+            operations: TableState::default(),
+            backends: TableState::default(),
+            wait_events: TableState::default(),
+            buffer_cache: TableState::default(),
             settings: TableState::default(),
             extensions: TableState::default(),
+            roles: TableState::default(),
+            logs: TableState::default(),
+            alerts: TableState::default(),
+            pooler: TableState::default(),
+            logical_replication: TableState::default(),
+            filters: HashMap::new(),
         }
     }
 
+    pub fn filter(&self, panel: BottomPanel) -> &FilterState {
+        static NO_FILTER: FilterState = FilterState {
+            text: String::new(),
+            active: false,
+        };
+        self.filters.get(&panel).unwrap_or(&NO_FILTER)
+    }
+
+    pub fn filter_mut(&mut self, panel: BottomPanel) -> &mut FilterState {
+        self.filters.entry(panel).or_default()
+    }
+
     /// Reset selection to first item for the given panel
     pub fn reset_selection(&mut self, panel: BottomPanel) {
         match panel {
@@ -110,16 +178,63 @@ impl PanelStates {
             BottomPanel::Indexes => self.indexes.select_first(),
             BottomPanel::Statements => self.statements.select_first(),
             BottomPanel::TableStats => self.table_stats.select_first(),
+            BottomPanel::Locks => self.locks.select_first(),
+            BottomPanel::ConnectionGroups => self.connection_groups.select_first(),
+            BottomPanel::Sessions => self.sessions.select_first(),
+            BottomPanel::Slots => self.slots.select_first(),
             BottomPanel::Replication => self.replication.select(Some(0)),
             BottomPanel::Blocking => self.blocking.select(Some(0)),
             BottomPanel::VacuumProgress => self.vacuum.select(Some(0)),
+            BottomPanel::AutovacuumHealth => self.autovacuum.select(Some(0)),
             BottomPanel::Wraparound => self.wraparound.select(Some(0)),
+            BottomPanel::IndexHealth => self.index_health.select(Some(0)),
+            BottomPanel::Operations => self.operations.select(Some(0)),
+            BottomPanel::Backends => self.backends.select(Some(0)),
+            BottomPanel::BufferCache => self.buffer_cache.select(Some(0)),
             BottomPanel::Settings => self.settings.select(Some(0)),
             BottomPanel::Extensions => self.extensions.select(Some(0)),
-            BottomPanel::WaitEvents | BottomPanel::WalIo => {}
+            BottomPanel::Roles => self.roles.select(Some(0)),
+            BottomPanel::Logs => self.logs.select(Some(0)),
+            BottomPanel::Alerts => self.alerts.select(Some(0)),
+            BottomPanel::Pooler => self.pooler.select(Some(0)),
+            BottomPanel::LogicalReplication => self.logical_replication.select(Some(0)),
+            BottomPanel::WaitEvents => self.wait_events.select(Some(0)),
+            BottomPanel::WalIo | BottomPanel::Checkpoints => {}
         }
     }
 
+    /// Selection of the panel's table; None for panels drawn as graphs.
+    pub fn table_state_mut(&mut self, panel: BottomPanel) -> Option<&mut TableState> {
+        Some(match panel {
+            BottomPanel::Queries => &mut self.queries.state,
+            BottomPanel::Indexes => &mut self.indexes.state,
+            BottomPanel::Statements => &mut self.statements.state,
+            BottomPanel::TableStats => &mut self.table_stats.state,
+            BottomPanel::Locks => &mut self.locks.state,
+            BottomPanel::ConnectionGroups => &mut self.connection_groups.state,
+            BottomPanel::Sessions => &mut self.sessions.state,
+            BottomPanel::Slots => &mut self.slots.state,
+            BottomPanel::Replication => &mut self.replication,
+            BottomPanel::Blocking => &mut self.blocking,
+            BottomPanel::VacuumProgress => &mut self.vacuum,
+            BottomPanel::AutovacuumHealth => &mut self.autovacuum,
+            BottomPanel::Operations => &mut self.operations,
+            BottomPanel::Backends => &mut self.backends,
+            BottomPanel::Wraparound => &mut self.wraparound,
+            BottomPanel::IndexHealth => &mut self.index_health,
+            BottomPanel::BufferCache => &mut self.buffer_cache,
+            BottomPanel::Settings => &mut self.settings,
+            BottomPanel::Extensions => &mut self.extensions,
+            BottomPanel::Roles => &mut self.roles,
+            BottomPanel::Logs => &mut self.logs,
+            BottomPanel::Alerts => &mut self.alerts,
+            BottomPanel::Pooler => &mut self.pooler,
+            BottomPanel::LogicalReplication => &mut self.logical_replication,
+            BottomPanel::WaitEvents => &mut self.wait_events,
+            BottomPanel::WalIo | BottomPanel::Checkpoints => return None,
+        })
+    }
+
     /// Handle navigation for simple table panels (no sorting).
     /// Returns `true` if Enter was pressed (caller should open inspect view).
     pub fn simple_nav(
@@ -154,15 +269,11 @@ impl PanelStates {
                 state.select(Some(new_pos));
                 false
             }
-            (KeyCode::Enter, _) => {
-                if len > 0 {
-                    if state.selected().is_none() {
-                        state.select(Some(0));
-                    }
-                    true
-                } else {
-                    false
+            (KeyCode::Enter, _) if len > 0 => {
+                if state.selected().is_none() {
+                    state.select(Some(0));
                 }
+                true
             }
             _ => false,
         }
@@ -204,6 +315,181 @@ impl Default for RecordingsBrowser {
     }
 }
 
+/// Scroll range of the overlay last drawn, recorded by the renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlayExtent {
+    /// Highest scroll offset that still fills the overlay
+    pub max_scroll: u16,
+    /// Visible content lines, one PageDown
+    pub page: u16,
+}
+
+/// Full-screen SQL viewer opened with `f` from an inspect overlay. It
+/// scrolls with `overlay_scroll`; the inspect overlay's offset is kept to
+/// go back to.
+#[derive(Debug)]
+pub struct SqlView {
+    /// Word-wrap long lines instead of scrolling sideways
+    pub wrap: bool,
+    pub line_numbers: bool,
+    /// Columns scrolled to the right while not wrapping
+    pub hscroll: u16,
+    /// Widest line past the visible width, recorded by the renderer
+    pub max_hscroll: Cell<u16>,
+    pub inspect_scroll: u16,
+}
+
+impl Default for SqlView {
+    fn default() -> Self {
+        Self {
+            wrap: true,
+            line_numbers: true,
+            hscroll: 0,
+            max_hscroll: Cell::new(0),
+            inspect_scroll: 0,
+        }
+    }
+}
+
+/// Vim-style `/` search inside an inspect overlay.
+#[derive(Debug, Default)]
+pub struct OverlaySearch {
+    pub query: String,
+    /// The pattern is being typed; `n`/`N` step through matches once it's set
+    pub editing: bool,
+    /// Index into `matches` of the highlighted match
+    pub current: usize,
+    /// Scroll offset of each match's line, recorded by the renderer
+    pub matches: RefCell<Vec<u16>>,
+}
+
+impl OverlaySearch {
+    pub fn start(&mut self) {
+        self.clear();
+        self.editing = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.editing = false;
+        self.current = 0;
+        self.matches.borrow_mut().clear();
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Select the first match at or below `scroll`, wrapping to the top;
+    /// returns its offset.
+    pub fn first_from(&mut self, scroll: u16) -> Option<u16> {
+        let matches = self.matches.borrow();
+        if matches.is_empty() {
+            return None;
+        }
+        self.current = matches.iter().position(|&m| m >= scroll).unwrap_or(0);
+        Some(matches[self.current])
+    }
+
+    /// Move to the next (or previous) match, wrapping around; returns its
+    /// offset.
+    pub fn step(&mut self, forward: bool) -> Option<u16> {
+        let matches = self.matches.borrow();
+        let len = matches.len();
+        if len == 0 {
+            return None;
+        }
+        self.current = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+        Some(matches[self.current])
+    }
+}
+
+/// What the EXPLAIN overlay shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExplainView {
+    /// Waiting for the plan of this query text
+    Loading(String),
+    Plan(QueryPlan),
+    Failed { query: String, error: String },
+}
+
+/// What the index advice overlay shows
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexAdviceView {
+    /// Trying indexes for this query text
+    Loading(String),
+    Advice(IndexAdvice),
+    Failed { query: String, error: String },
+}
+
+/// Catalog definition shown in the table inspect overlay, by `schema.table`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableSchemaView {
+    Loading(String),
+    Loaded { key: String, schema: TableSchema },
+    Failed { key: String, error: String },
+}
+
+impl TableSchemaView {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Loading(key) | Self::Loaded { key, .. } | Self::Failed { key, .. } => key,
+        }
+    }
+}
+
+/// Privileges shown in the role inspect overlay, by role name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoleGrantsView {
+    Loading(String),
+    Loaded { role: String, grants: Vec<RoleGrant> },
+    Failed { role: String, error: String },
+}
+
+impl RoleGrantsView {
+    pub fn role(&self) -> &str {
+        match self {
+            Self::Loading(role) | Self::Loaded { role, .. } | Self::Failed { role, .. } => role,
+        }
+    }
+}
+
+/// Relations driving a database's XID age, shown in its wraparound inspect
+/// overlay, by database name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WraparoundTablesView {
+    Loading(String),
+    Loaded { datname: String, tables: Vec<WraparoundTable> },
+    Failed { datname: String, error: String },
+}
+
+/// One monitored connection, as listed in the connection switcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionEntry {
+    pub label: String,
+    /// Short health line kept current by the runtime
+    pub status: String,
+}
+
+/// State for the connection switcher overlay. Every connection's `App`
+/// carries the full list; `current` is its own position in it.
+#[derive(Debug, Default)]
+pub struct ConnectionSwitcher {
+    pub entries: Vec<ConnectionEntry>,
+    pub current: usize,
+    pub selected: usize,
+}
+
+impl ConnectionSwitcher {
+    pub fn is_multi(&self) -> bool {
+        self.entries.len() > 1
+    }
+}
+
 /// State for config settings overlay
 #[derive(Debug)]
 pub struct ConfigOverlay {
@@ -233,7 +519,36 @@ pub struct UiFeedback {
     pub status_message: Option<String>,
     pub pending_action: Option<AppAction>,
     pub bloat_loading: bool,
+    pub buffer_cache_loading: bool,
     pub spinner_frame: u8,
+    /// Operator actions not yet handed to the recorder
+    pub ui_events: Vec<UiEvent>,
+    /// Set while the connection is lost and being re-established
+    pub reconnecting: Option<Reconnecting>,
+    /// Bell and desktop notifications not yet delivered
+    pub notifications: Vec<Notification>,
+    /// Alerts that fired, not yet handed to the observers
+    pub fired_alerts: Vec<Alert>,
+    /// Maintenance command started from an inspect overlay, until it ends
+    pub maintenance: Option<RunningMaintenance>,
+}
+
+/// A maintenance command running on its own connection
+#[derive(Debug, Clone)]
+pub struct RunningMaintenance {
+    pub command: Maintenance,
+    pub started: Instant,
+    /// Backend running it, once the connection reports it
+    pub pid: Option<i32>,
+    /// Its row in the progress views of the server running it, as last read
+    pub progress: Option<OperationProgress>,
+}
+
+/// Progress of an automatic reconnect after the connection dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reconnecting {
+    pub attempt: u32,
+    pub retry_in_secs: u64,
 }
 
 impl UiFeedback {
@@ -243,7 +558,13 @@ impl UiFeedback {
             status_message: None,
             pending_action: None,
             bloat_loading: false,
+            buffer_cache_loading: false,
             spinner_frame: 0,
+            ui_events: Vec::new(),
+            reconnecting: None,
+            notifications: Vec::new(),
+            fired_alerts: Vec::new(),
+            maintenance: None,
         }
     }
 
@@ -252,6 +573,21 @@ impl UiFeedback {
     pub fn take_action(&mut self) -> Option<AppAction> {
         self.pending_action.take()
     }
+
+    /// Drain operator actions logged since the last call
+    #[must_use]
+    pub fn take_ui_events(&mut self) -> Vec<UiEvent> {
+        std::mem::take(&mut self.ui_events)
+    }
+
+    pub fn take_notifications(&mut self) -> Vec<Notification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    #[must_use]
+    pub fn take_fired_alerts(&mut self) -> Vec<Alert> {
+        std::mem::take(&mut self.fired_alerts)
+    }
 }
 
 impl Default for UiFeedback {
@@ -260,6 +596,29 @@ impl Default for UiFeedback {
     }
 }
 
+/// Note an operator attached to a point in the session with `#`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub at: DateTime<Utc>,
+    pub text: String,
+}
+
+impl Annotation {
+    /// Annotations among recorded events, oldest first.
+    pub fn from_events(events: &[UiEvent]) -> Vec<Self> {
+        events
+            .iter()
+            .filter_map(|e| match &e.kind {
+                UiEventKind::Annotation { text } => Some(Self {
+                    at: e.timestamp,
+                    text: text.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 /// State for replay mode (when reviewing recorded sessions)
 #[derive(Debug)]
 pub struct ReplayState {
@@ -268,6 +627,19 @@ pub struct ReplayState {
     pub total: usize,
     pub speed: f64,
     pub playing: bool,
+    /// Operator actions from the recording, oldest first
+    pub events: Vec<UiEvent>,
+    /// Blended graph values while playing between two snapshots
+    pub interpolation: Option<ReplayInterpolation>,
+    /// Second recording replayed alongside this one (`--compare`)
+    pub compare: Option<CompareState>,
+    /// When each snapshot was taken, for the timeline
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// Snapshot index `[` marked as the start of a bookmark
+    pub mark_start: Option<usize>,
+    /// First and last snapshot index of the interval bookmarked last, which
+    /// `E` exports
+    pub bookmark: Option<(usize, usize)>,
 }
 
 impl ReplayState {
@@ -278,10 +650,170 @@ impl ReplayState {
             total,
             speed: 1.0,
             playing: false,
+            events: Vec::new(),
+            interpolation: None,
+            compare: None,
+            timestamps: Vec::new(),
+            mark_start: None,
+            bookmark: None,
+        }
+    }
+
+    /// Actions that happened at or before `now`
+    pub fn trail(&self, now: DateTime<Utc>) -> &[UiEvent] {
+        let end = self.events.partition_point(|e| e.timestamp <= now);
+        &self.events[..end]
+    }
+}
+
+/// Where the `:` prompt jumps to in a replay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeekTarget {
+    /// Time of day as the header shows it, the first time it comes round
+    /// after the recording starts
+    Time(NaiveTime),
+    /// Share of the recording's time span, 0.0 to 1.0
+    Fraction(f64),
+}
+
+impl SeekTarget {
+    /// Parse `HH:MM`, `HH:MM:SS` or `NN%`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some(pct) = input.strip_suffix('%') {
+            let pct: f64 = pct.trim().parse().ok()?;
+            return (0.0..=100.0)
+                .contains(&pct)
+                .then_some(Self::Fraction(pct / 100.0));
+        }
+        NaiveTime::parse_from_str(input, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(input, "%H:%M"))
+            .ok()
+            .map(Self::Time)
+    }
+
+    /// The instant this target names in a recording running from `first`
+    /// to `last`.
+    pub fn instant(self, first: DateTime<Utc>, last: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::Time(time) => {
+                let same_day = first.date_naive().and_time(time).and_utc();
+                if same_day < first {
+                    same_day + chrono::Duration::days(1)
+                } else {
+                    same_day
+                }
+            }
+            Self::Fraction(f) => {
+                let span_ms = (last - first).num_milliseconds() as f64;
+                first + chrono::Duration::milliseconds((span_ms * f.clamp(0.0, 1.0)) as i64)
+            }
         }
     }
 }
 
+/// A recording compared against the one being replayed, aligned by time
+/// since the start of each recording.
+#[derive(Debug)]
+pub struct CompareState {
+    pub filename: String,
+    /// 1-based position of the aligned snapshot
+    pub position: usize,
+    pub total: usize,
+    pub metrics: MetricsHistory,
+    rates: RateTracker,
+}
+
+impl CompareState {
+    pub fn new(filename: String, total: usize, history_len: usize) -> Self {
+        Self {
+            filename,
+            position: 0,
+            total,
+            metrics: MetricsHistory::new(history_len),
+            rates: RateTracker::default(),
+        }
+    }
+
+    /// Advance to the snapshot aligned with the replay position.
+    pub fn push(&mut self, snap: &PgSnapshot, position: usize) {
+        self.metrics.push_snapshot_metrics(snap);
+        let rates = self.rates.interval(snap);
+        self.metrics.push_rates(&rates);
+        self.position = position;
+    }
+}
+
+/// Graph values blended between the current and next replay snapshot,
+/// so graphs animate smoothly between steps at high replay speeds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayInterpolation {
+    /// Effective time being rendered
+    pub timestamp: DateTime<Utc>,
+    pub connections: u64,
+    pub hit_ratio: u64,
+    pub avg_query_time: u64,
+}
+
+impl ReplayInterpolation {
+    /// Blend two snapshots; `t` is the progress from `current` (0.0) to `next` (1.0).
+    pub fn between(current: &PgSnapshot, next: &PgSnapshot, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| (b - a).mul_add(t, a);
+        let span_ms = (next.timestamp - current.timestamp).num_milliseconds() as f64;
+        Self {
+            timestamp: current.timestamp
+                + chrono::Duration::milliseconds((span_ms * t) as i64),
+            connections: lerp(
+                current.summary.total_backends as f64,
+                next.summary.total_backends as f64,
+            )
+            .round() as u64,
+            hit_ratio: (lerp(current.buffer_cache.hit_ratio, next.buffer_cache.hit_ratio) * 1000.0)
+                as u64,
+            avg_query_time: lerp(
+                avg_active_query_ms(current) as f64,
+                avg_active_query_ms(next) as f64,
+            ) as u64,
+        }
+    }
+}
+
+/// Upper bounds of the latency heatmap's duration buckets, in seconds; a
+/// last bucket takes everything longer.
+pub const LATENCY_BUCKET_BOUNDS: [f64; 5] = [0.01, 0.1, 1.0, 10.0, 60.0];
+
+/// Queries counted in each heatmap bucket, shortest first.
+pub type LatencyBuckets = [u16; LATENCY_BUCKET_BOUNDS.len() + 1];
+
+/// Queries the duration graphs are drawn from: active and idle in transaction.
+fn running_queries(snap: &PgSnapshot) -> impl Iterator<Item = &ActiveQuery> {
+    snap.active_queries
+        .iter()
+        .filter(|q| matches!(q.state.as_deref(), Some("active" | "idle in transaction")))
+}
+
+/// Running queries by duration bucket, for the latency heatmap.
+fn latency_buckets(snap: &PgSnapshot) -> LatencyBuckets {
+    let mut buckets = LatencyBuckets::default();
+    for q in running_queries(snap) {
+        let bucket = LATENCY_BUCKET_BOUNDS.partition_point(|&bound| q.duration_secs >= bound);
+        buckets[bucket] = buckets[bucket].saturating_add(1);
+    }
+    buckets
+}
+
+/// Average duration of active and idle-in-transaction queries, in milliseconds.
+fn avg_active_query_ms(snap: &PgSnapshot) -> u64 {
+    let active: Vec<&_> = running_queries(snap).collect();
+    if active.is_empty() {
+        0
+    } else {
+        let sum: f64 = active.iter().map(|q| q.duration_secs).sum();
+        (sum / active.len() as f64 * 1000.0) as u64
+    }
+}
+
 /// Connection information
 #[derive(Debug)]
 pub struct ConnectionInfo {
@@ -290,6 +822,9 @@ pub struct ConnectionInfo {
     pub dbname: String,
     pub user: String,
     pub ssl_mode: Option<String>,
+    /// `host:port` of the primary that runs maintenance, when monitoring
+    /// through a standby
+    pub actions_via: Option<String>,
 }
 
 impl ConnectionInfo {
@@ -300,12 +835,17 @@ impl ConnectionInfo {
             dbname,
             user,
             ssl_mode: None,
+            actions_via: None,
         }
     }
 
     pub fn set_ssl_mode(&mut self, label: &str) {
         self.ssl_mode = Some(label.to_string());
     }
+
+    pub fn set_actions_via(&mut self, label: &str) {
+        self.actions_via = Some(label.to_string());
+    }
 }
 
 /// Filter state for panel filtering
@@ -330,14 +870,979 @@ impl FilterState {
     }
 }
 
+/// How far back sampled statement latencies are summarised
+pub const LATENCY_WINDOW_SECS: i64 = 300;
+
+/// Executions of one statement between two sampler ticks
+#[derive(Debug, Clone, Copy)]
+struct LatencyInterval {
+    at: DateTime<Utc>,
+    calls: i64,
+    mean_ms: f64,
+}
+
+/// Latency percentiles estimated from sampled intervals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyEstimate {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    /// Sampler intervals that saw at least one call
+    pub intervals: usize,
+    pub calls: i64,
+}
+
+/// Estimates latency percentiles for starred statements.
+///
+/// `pg_stat_statements` only keeps mean/max/stddev, so the sampler diffs
+/// `calls` and `total_exec_time` every tick: each interval yields the mean
+/// latency of the calls that finished in it. Weighting those means by their
+/// call counts approximates the latency distribution; the finer the ticks,
+/// the closer the estimate.
+#[derive(Debug, Default)]
+pub struct LatencySampler {
+    prev: HashMap<i64, (i64, f64)>,
+    intervals: HashMap<i64, VecDeque<LatencyInterval>>,
+}
+
+impl LatencySampler {
+    pub fn push(&mut self, at: DateTime<Utc>, samples: &[StatementSample]) {
+        for s in samples {
+            if let Some((calls, total)) = self.prev.insert(s.queryid, (s.calls, s.total_exec_time)) {
+                let d_calls = s.calls - calls;
+                let d_time = s.total_exec_time - total;
+                // Skip idle intervals and counter resets
+                if d_calls > 0 && d_time >= 0.0 {
+                    self.intervals.entry(s.queryid).or_default().push_back(LatencyInterval {
+                        at,
+                        calls: d_calls,
+                        mean_ms: d_time / d_calls as f64,
+                    });
+                }
+            }
+        }
+
+        let cutoff = at - chrono::Duration::seconds(LATENCY_WINDOW_SECS);
+        for window in self.intervals.values_mut() {
+            while window.front().is_some_and(|i| i.at < cutoff) {
+                window.pop_front();
+            }
+        }
+        self.intervals.retain(|_, w| !w.is_empty());
+    }
+
+    /// Whether the statement has been sampled at least once
+    pub fn is_sampling(&self, queryid: i64) -> bool {
+        self.prev.contains_key(&queryid)
+    }
+
+    pub fn estimate(&self, queryid: i64) -> Option<LatencyEstimate> {
+        let window = self.intervals.get(&queryid)?;
+        let mut sorted: Vec<_> = window.iter().collect();
+        sorted.sort_by(|a, b| a.mean_ms.total_cmp(&b.mean_ms));
+        let calls: i64 = sorted.iter().map(|i| i.calls).sum();
+
+        let percentile = |q: f64| {
+            let target = q * calls as f64;
+            let mut seen = 0;
+            for i in &sorted {
+                seen += i.calls;
+                if seen as f64 >= target {
+                    return i.mean_ms;
+                }
+            }
+            sorted.last().map_or(0.0, |i| i.mean_ms)
+        };
+
+        Some(LatencyEstimate {
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            intervals: sorted.len(),
+            calls,
+        })
+    }
+}
+
+/// Bytes per `pg_stat_statements` temp block (default `BLCKSZ`)
+const TEMP_BLOCK_BYTES: f64 = 8192.0;
+
+/// Attributes temp file spill to the statements running right now.
+///
+/// `pg_stat_activity` has no per-backend temp usage, so this diffs
+/// `pg_stat_statements.temp_blks_written` between snapshots and ties any
+/// growth back to backends through `pg_stat_activity.query_id` (PG14+).
+/// Counters only move when an execution finishes, so a long single run shows
+/// up once it ends; repeated executions show up while they keep spilling.
+#[derive(Debug, Default)]
+pub struct TempSpill {
+    prev: Option<(DateTime<Utc>, HashMap<i64, i64>)>,
+    /// Temp bytes written per second, by queryid, over the last interval
+    rates: HashMap<i64, f64>,
+}
+
+impl TempSpill {
+    pub fn update(&mut self, snap: &PgSnapshot) {
+        let mut written: HashMap<i64, i64> = HashMap::new();
+        for stmt in &snap.stat_statements {
+            *written.entry(stmt.queryid).or_default() += stmt.temp_blks_written;
+        }
+
+        self.rates.clear();
+        if let Some((prev_at, prev)) = &self.prev {
+            let secs = (snap.timestamp - *prev_at).num_milliseconds() as f64 / 1000.0;
+            if secs > 0.0 {
+                for (queryid, blocks) in &written {
+                    let delta = blocks - prev.get(queryid).copied().unwrap_or(*blocks);
+                    if delta > 0 {
+                        self.rates.insert(*queryid, delta as f64 * TEMP_BLOCK_BYTES / secs);
+                    }
+                }
+            }
+        }
+        self.prev = Some((snap.timestamp, written));
+    }
+
+    /// Temp bytes/s for the statement, if it spilled in the last interval
+    pub fn rate(&self, queryid: Option<i64>) -> Option<f64> {
+        queryid.and_then(|id| self.rates.get(&id).copied())
+    }
+
+    /// Rates of the last interval, without the counters behind them
+    pub fn rates(&self) -> Self {
+        Self {
+            prev: None,
+            rates: self.rates.clone(),
+        }
+    }
+}
+
+/// Window the wait sampler aggregates over.
+pub const WAIT_WINDOW_SECS: i64 = 60;
+
+/// Time spent in one wait event type over the window, split by event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitTypeShare {
+    pub wait_event_type: String,
+    pub count: i64,
+    /// Largest first
+    pub events: Vec<(String, i64)>,
+}
+
+/// Wait event samples over the last [`WAIT_WINDOW_SECS`], taken several
+/// times a second on a connection of their own. Each sample counts the
+/// backends in each wait, so a type's share of the total is its share of
+/// the time active backends spent.
+#[derive(Debug, Default)]
+pub struct WaitSampler {
+    polls: VecDeque<(DateTime<Utc>, Vec<WaitEventCount>)>,
+    pub source: Option<WaitSampleSource>,
+    /// Why the last poll failed, cleared by the next that works
+    pub error: Option<String>,
+}
+
+impl WaitSampler {
+    pub fn push(&mut self, at: DateTime<Utc>, source: WaitSampleSource, counts: Vec<WaitEventCount>) {
+        let cutoff = at - chrono::Duration::seconds(WAIT_WINDOW_SECS);
+        while self.polls.front().is_some_and(|(t, _)| *t < cutoff) {
+            self.polls.pop_front();
+        }
+        self.polls.push_back((at, counts));
+        self.source = Some(source);
+        self.error = None;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Polls in the window, including those that found nothing waiting
+    pub fn polls(&self) -> usize {
+        self.polls.len()
+    }
+
+    /// Seconds between the oldest and newest poll
+    pub fn span_secs(&self) -> f64 {
+        match (self.polls.front(), self.polls.back()) {
+            (Some((first, _)), Some((last, _))) => (*last - *first).num_milliseconds() as f64 / 1000.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Samples per wait type and event over the window, largest first.
+    pub fn profile(&self) -> Vec<WaitTypeShare> {
+        let mut by_type: HashMap<&str, HashMap<&str, i64>> = HashMap::new();
+        for (_, counts) in &self.polls {
+            for c in counts {
+                *by_type
+                    .entry(&c.wait_event_type)
+                    .or_default()
+                    .entry(&c.wait_event)
+                    .or_default() += c.count;
+            }
+        }
+        let mut shares: Vec<WaitTypeShare> = by_type
+            .into_iter()
+            .map(|(wait_event_type, events)| {
+                let mut events: Vec<(String, i64)> =
+                    events.into_iter().map(|(e, n)| (e.to_string(), n)).collect();
+                events.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                WaitTypeShare {
+                    wait_event_type: wait_event_type.to_string(),
+                    count: events.iter().map(|(_, n)| n).sum(),
+                    events,
+                }
+            })
+            .collect();
+        shares.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.wait_event_type.cmp(&b.wait_event_type))
+        });
+        shares
+    }
+}
+
+/// Most bloat estimates kept per table or index.
+const BLOAT_SAMPLES: usize = 32;
+
+/// One table or index bloat estimate from a refresh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BloatSample {
+    pub at: DateTime<Utc>,
+    pub pct: f64,
+    pub bytes: i64,
+    pub source: BloatSource,
+}
+
+/// The `|` split view: two bottom panels side by side. The focused pane
+/// shows `App::bottom_panel` and takes the keys; this holds the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitView {
+    /// Panel in the pane without focus
+    pub other: BottomPanel,
+    /// Whether the focused pane is the right one
+    pub focus_right: bool,
+}
+
+/// Where the last frame drew what a mouse click can land on.
+#[derive(Debug, Clone, Default)]
+pub struct MouseTargets {
+    /// Panel switch hints in the footer
+    pub tabs: Vec<(Rect, BottomPanel)>,
+    /// Bottom panel, borders included
+    pub panel: Rect,
+    /// Where the panel table's rows are drawn, below its header; empty
+    /// until a table is drawn
+    pub rows: Rect,
+    /// Height of each table row from the first, when not all are one line
+    pub row_heights: Vec<u16>,
+    /// Replay timeline bar, between its end markers
+    pub timeline: Option<Rect>,
+    /// Split view pane without focus; a click there moves focus to it
+    pub split_pane: Option<Rect>,
+}
+
+impl MouseTargets {
+    /// Targets for a frame with the bottom panel in `panel`. Its table
+    /// notes where it went with [`Self::table_at`].
+    pub fn for_panel(panel: Rect) -> Self {
+        Self {
+            panel,
+            ..Self::default()
+        }
+    }
+
+    /// Note that the panel's selectable table is drawn in `area`, with its
+    /// header row on top, inside its own border when `bordered`.
+    pub fn table_at(&mut self, area: Rect, bordered: bool) {
+        let inner = if bordered {
+            area.inner(Margin::new(1, 1))
+        } else {
+            area
+        };
+        self.rows = Rect {
+            y: inner.y.saturating_add(1),
+            height: inner.height.saturating_sub(1),
+            ..inner
+        };
+    }
+
+    /// Table row under screen row `y`, counting from `offset`, the first
+    /// row scrolled into view.
+    pub fn row_at(&self, y: u16, offset: usize) -> Option<usize> {
+        if y < self.rows.y || y >= self.rows.bottom() {
+            return None;
+        }
+        let mut top = self.rows.y;
+        for row in offset.. {
+            let height = self.row_heights.get(row).copied().unwrap_or(1).max(1);
+            if y < top + height {
+                return Some(row);
+            }
+            top += height;
+        }
+        None
+    }
+}
+
+/// Where each running operation's current phase was first seen, so its
+/// remaining time can be estimated from the rate since then. Progress
+/// counters restart with every phase, so the estimate covers the phase.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker {
+    phases: HashMap<(i32, OperationKind), PhaseStart>,
+}
+
+#[derive(Debug, Clone)]
+struct PhaseStart {
+    phase: String,
+    at: DateTime<Utc>,
+    work_done: i64,
+}
+
+impl ProgressTracker {
+    /// Note the operations running at `at`; finished ones are forgotten.
+    pub fn observe(&mut self, at: DateTime<Utc>, operations: &[OperationProgress]) {
+        self.phases
+            .retain(|key, _| operations.iter().any(|op| (op.pid, op.kind) == *key));
+        for op in operations {
+            // Seeking backwards in a replay also restarts the measurement
+            let restarted = self.phases.get(&(op.pid, op.kind)).map_or(true, |seen| {
+                seen.phase != op.phase || op.work_done < seen.work_done || at < seen.at
+            });
+            if restarted {
+                let start = PhaseStart {
+                    phase: op.phase.clone(),
+                    at,
+                    work_done: op.work_done,
+                };
+                self.phases.insert((op.pid, op.kind), start);
+            }
+        }
+    }
+
+    /// Seconds left in `op`'s current phase at the rate seen so far.
+    pub fn eta_secs(&self, op: &OperationProgress, now: DateTime<Utc>) -> Option<f64> {
+        let seen = self.phases.get(&(op.pid, op.kind)).filter(|s| s.phase == op.phase)?;
+        let secs = (now - seen.at).num_milliseconds() as f64 / 1000.0;
+        let done = op.work_done - seen.work_done;
+        if op.work_total <= 0 || done <= 0 || secs <= 0.0 {
+            return None;
+        }
+        Some((op.work_total - op.work_done).max(0) as f64 * secs / done as f64)
+    }
+}
+
+/// Bloat estimates from every refresh this session, by `schema.name`, so
+/// growing bloat can be told apart from bloat that has been there all along.
+#[derive(Debug, Default)]
+pub struct BloatHistory {
+    tables: HashMap<String, Vec<BloatSample>>,
+    indexes: HashMap<String, Vec<BloatSample>>,
+}
+
+impl BloatHistory {
+    pub fn record(
+        &mut self,
+        at: DateTime<Utc>,
+        table_bloat: &HashMap<String, TableBloat>,
+        index_bloat: &HashMap<String, IndexBloat>,
+    ) {
+        fn push(samples: &mut Vec<BloatSample>, sample: BloatSample) {
+            if samples.len() == BLOAT_SAMPLES {
+                samples.remove(0);
+            }
+            samples.push(sample);
+        }
+        for (key, b) in table_bloat {
+            let sample = BloatSample { at, pct: b.bloat_pct, bytes: b.bloat_bytes, source: b.source };
+            push(self.tables.entry(key.clone()).or_default(), sample);
+        }
+        for (key, b) in index_bloat {
+            let sample = BloatSample { at, pct: b.bloat_pct, bytes: b.bloat_bytes, source: b.source };
+            push(self.indexes.entry(key.clone()).or_default(), sample);
+        }
+    }
+
+    pub fn for_table(&self, key: &str) -> &[BloatSample] {
+        self.tables.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn for_index(&self, key: &str) -> &[BloatSample] {
+        self.indexes.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// First and latest of the estimates made the same way as the latest
+    /// one, since numbers from different methods don't compare. None until
+    /// there are two.
+    pub fn trend(samples: &[BloatSample]) -> Option<(BloatSample, BloatSample)> {
+        let latest = *samples.last()?;
+        let first = *samples.iter().find(|s| s.source == latest.source)?;
+        (first.at < latest.at).then_some((first, latest))
+    }
+}
+
+/// How snapshot collection has been going, for the collector stats overlay.
+#[derive(Debug, Default)]
+pub struct CollectorStats {
+    pub collected: u64,
+    pub failed: u64,
+    pub last_took: Option<Duration>,
+    pub slowest: Duration,
+    total_took: Duration,
+    timed: u32,
+    /// Snapshot hooks from the latest run
+    pub hooks: Vec<HookOutcome>,
+}
+
+impl CollectorStats {
+    /// A snapshot arrived (or failed). `took` is the time since it was
+    /// requested, when known.
+    pub fn record(&mut self, ok: bool, took: Option<Duration>) {
+        if ok {
+            self.collected += 1;
+        } else {
+            self.failed += 1;
+        }
+        if let Some(took) = took {
+            self.last_took = Some(took);
+            self.slowest = self.slowest.max(took);
+            self.total_took += took;
+            self.timed += 1;
+        }
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        (self.timed > 0).then(|| self.total_took / self.timed)
+    }
+
+    /// Replace the hook outcomes. Returns the first error if the previous
+    /// run had none, so a failure is announced once rather than every tick.
+    pub fn set_hooks(&mut self, outcomes: Vec<HookOutcome>) -> Option<String> {
+        let was_failing = self.hooks.iter().any(|h| h.error.is_some());
+        self.hooks = outcomes;
+        let first_error = self.hooks.iter().find_map(|h| h.error.clone());
+        first_error.filter(|_| !was_failing)
+    }
+}
+
 /// Lightweight struct for rate delta calculations (avoids cloning full `PgSnapshot`)
 #[derive(Debug)]
-pub(super) struct PrevMetrics {
+struct PrevMetrics {
     pub timestamp: DateTime<Utc>,
     pub xact_commit: i64,
     pub xact_rollback: i64,
     pub blks_read: i64,
-    pub wal_bytes: Option<i64>,
+    pub wal: Option<WalStats>,
+    pub checkpoint: Option<CheckpointStats>,
+    pub bgwriter: Option<BgwriterStats>,
+    /// (`archived_count`, `failed_count`)
+    pub archiver: Option<(i64, i64)>,
+    pub io: Vec<IoStat>,
+    pub server_start: Option<DateTime<Utc>>,
+    pub stats_reset: Option<DateTime<Utc>>,
+}
+
+/// A discontinuity in the cumulative counters rates are derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterReset {
+    /// `pg_postmaster_start_time()` moved: every counter restarted
+    ServerRestart { at: DateTime<Utc> },
+    /// `pg_stat_database.stats_reset` moved: this database's counters restarted
+    StatsReset { at: DateTime<Utc> },
+}
+
+impl CounterReset {
+    /// Compare the previous sample against the current one. A restart takes
+    /// precedence since it implies a stats reset as well.
+    fn detect(prev: &PrevMetrics, curr: &DatabaseStats) -> Option<Self> {
+        if let (Some(old), Some(new)) = (prev.server_start, curr.server_start) {
+            if old != new {
+                return Some(Self::ServerRestart { at: new });
+            }
+        }
+        match curr.stats_reset {
+            Some(new) if prev.stats_reset != Some(new) => Some(Self::StatsReset { at: new }),
+            _ => None,
+        }
+    }
+
+    pub const fn is_server_restart(&self) -> bool {
+        matches!(self, Self::ServerRestart { .. })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::ServerRestart { at } => format!(
+                "Server restarted at {} \u{2014} rates restarted",
+                at.with_timezone(&chrono::Local).format("%H:%M:%S")
+            ),
+            Self::StatsReset { at } => format!(
+                "Statistics reset at {} \u{2014} rates restarted",
+                at.with_timezone(&chrono::Local).format("%H:%M:%S")
+            ),
+        }
+    }
+}
+
+/// A per-second rate derived from a cumulative counter, with sparkline history
+#[derive(Debug)]
+pub struct RateSeries {
+    pub history: RingBuffer<u64>,
+    pub current: Option<f64>,
+}
+
+impl RateSeries {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            history: RingBuffer::new(capacity),
+            current: None,
+        }
+    }
+
+    /// Record a counter delta over `secs`. Negative deltas (counter reset)
+    /// are skipped.
+    pub fn push_delta(&mut self, delta: f64, secs: f64) {
+        if let Some(rate) = per_sec(delta, secs) {
+            self.push_rate(rate);
+        }
+    }
+
+    fn push_rate(&mut self, rate: f64) {
+        self.current = Some(rate);
+        // Sparklines scale to their own range, so keep milli-units to avoid
+        // flattening fractional rates (e.g. 0.2 checkpoints/s) to zero
+        self.history.push((rate * 1000.0) as u64);
+    }
+}
+
+/// `delta` over `secs` as a per-second rate; none for a negative delta
+/// (counter reset) or an empty interval.
+fn per_sec(delta: f64, secs: f64) -> Option<f64> {
+    (delta >= 0.0 && secs > 0.0).then(|| delta / secs)
+}
+
+/// Write, flush and replay lag of one standby over the session, in ms
+#[derive(Debug)]
+pub struct StandbyLag {
+    pub write: RingBuffer<u64>,
+    pub flush: RingBuffer<u64>,
+    pub replay: RingBuffer<u64>,
+}
+
+impl StandbyLag {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            write: RingBuffer::new(capacity),
+            flush: RingBuffer::new(capacity),
+            replay: RingBuffer::new(capacity),
+        }
+    }
+
+    /// Record one sample. Postgres reports no lag once a standby has caught
+    /// up and gone idle, which counts as zero.
+    fn push(&mut self, r: &ReplicationInfo) {
+        let ms = |secs: Option<f64>| (secs.unwrap_or(0.0).max(0.0) * 1000.0).round() as u64;
+        self.write.push(ms(r.write_lag_secs));
+        self.flush.push(ms(r.flush_lag_secs));
+        self.replay.push(ms(r.replay_lag_secs));
+    }
+}
+
+/// What one pg_stat_statements entry did in each interval of the session
+#[derive(Debug)]
+pub struct StatementTiming {
+    pub calls: RingBuffer<u64>,
+    /// Execution time spent in the interval, in µs
+    pub exec_us: RingBuffer<u64>,
+    /// Time spent reading blocks in the interval, in µs
+    pub blk_read_us: RingBuffer<u64>,
+    prev: (i64, f64, f64),
+}
+
+impl StatementTiming {
+    fn new(capacity: usize, s: &StatStatement) -> Self {
+        Self {
+            calls: RingBuffer::new(capacity),
+            exec_us: RingBuffer::new(capacity),
+            blk_read_us: RingBuffer::new(capacity),
+            prev: (s.calls, s.total_exec_time, s.blk_read_time),
+        }
+    }
+
+    /// Record the deltas since the previous sample. A reset of
+    /// pg_stat_statements makes them negative; that interval is skipped.
+    fn push(&mut self, s: &StatStatement) {
+        let (calls, exec, read) = std::mem::replace(
+            &mut self.prev,
+            (s.calls, s.total_exec_time, s.blk_read_time),
+        );
+        let d_calls = s.calls - calls;
+        let d_exec = s.total_exec_time - exec;
+        let d_read = s.blk_read_time - read;
+        if d_calls < 0 || d_exec < 0.0 || d_read < 0.0 {
+            return;
+        }
+        self.calls.push(d_calls as u64);
+        self.exec_us.push((d_exec * 1000.0).round() as u64);
+        self.blk_read_us.push((d_read * 1000.0).round() as u64);
+    }
+
+    /// Mean execution time per call in each interval, in µs. Intervals
+    /// without calls repeat the previous mean so the line doesn't dip.
+    pub fn mean_us(&self) -> Vec<u64> {
+        let mut last = 0;
+        self.calls
+            .as_vec()
+            .into_iter()
+            .zip(self.exec_us.as_vec())
+            .map(|(calls, exec)| {
+                last = exec.checked_div(calls).unwrap_or(last);
+                last
+            })
+            .collect()
+    }
+}
+
+/// Per-second `pg_stat_io` counts of one backend type in one context, over
+/// the last interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoRate {
+    pub backend_type: String,
+    pub context: String,
+    pub reads: f64,
+    pub writes: f64,
+    pub extends: f64,
+    pub fsyncs: f64,
+}
+
+impl IoRate {
+    /// Rates for the rows in both `prev` and `curr`. A row whose counts went
+    /// backwards (`pg_stat_reset_shared('io')`) is left out.
+    pub fn between(prev: &[IoStat], curr: &[IoStat], secs: f64) -> Vec<Self> {
+        if secs <= 0.0 {
+            return Vec::new();
+        }
+        curr.iter()
+            .filter_map(|c| {
+                let p = prev
+                    .iter()
+                    .find(|p| p.backend_type == c.backend_type && p.context == c.context)?;
+                let deltas = [c.reads - p.reads, c.writes - p.writes, c.extends - p.extends, c.fsyncs - p.fsyncs];
+                if deltas.iter().any(|&d| d < 0) {
+                    return None;
+                }
+                let [reads, writes, extends, fsyncs] = deltas.map(|d| d as f64 / secs);
+                Some(Self {
+                    backend_type: c.backend_type.clone(),
+                    context: c.context.clone(),
+                    reads,
+                    writes,
+                    extends,
+                    fsyncs,
+                })
+            })
+            .collect()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.reads + self.writes + self.extends + self.fsyncs
+    }
+}
+
+/// Per-interval rates for everything shown in the WAL & I/O panel
+#[derive(Debug)]
+pub struct WalIoRates {
+    // pg_stat_wal
+    pub wal_records: RateSeries,
+    pub wal_buffers_full: RateSeries,
+    /// ms of WAL write time per second
+    pub wal_write_time: RateSeries,
+    /// ms of WAL sync time per second
+    pub wal_sync_time: RateSeries,
+
+    // Checkpointer
+    pub checkpoints: RateSeries,
+    pub checkpoints_forced: RateSeries,
+    pub checkpoint_write_time: RateSeries,
+    pub checkpoint_sync_time: RateSeries,
+
+    // Archiver
+    pub archived: RateSeries,
+    pub archive_failed: RateSeries,
+
+    // Buffer I/O
+    pub buffers_checkpoint: RateSeries,
+    pub buffers_backend: RateSeries,
+    pub buffers_clean: RateSeries,
+    pub buffers_throttled: RateSeries,
+    pub buffers_alloc: RateSeries,
+
+    // pg_stat_io, latest interval only
+    pub io: Vec<IoRate>,
+}
+
+impl WalIoRates {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            wal_records: RateSeries::new(capacity),
+            wal_buffers_full: RateSeries::new(capacity),
+            wal_write_time: RateSeries::new(capacity),
+            wal_sync_time: RateSeries::new(capacity),
+            checkpoints: RateSeries::new(capacity),
+            checkpoints_forced: RateSeries::new(capacity),
+            checkpoint_write_time: RateSeries::new(capacity),
+            checkpoint_sync_time: RateSeries::new(capacity),
+            archived: RateSeries::new(capacity),
+            archive_failed: RateSeries::new(capacity),
+            buffers_checkpoint: RateSeries::new(capacity),
+            buffers_backend: RateSeries::new(capacity),
+            buffers_clean: RateSeries::new(capacity),
+            buffers_throttled: RateSeries::new(capacity),
+            buffers_alloc: RateSeries::new(capacity),
+            io: Vec::new(),
+        }
+    }
+
+    /// Every series with its export column name.
+    pub fn named_series(&self) -> [(&'static str, &RateSeries); 15] {
+        [
+            ("wal_records_per_sec", &self.wal_records),
+            ("wal_buffers_full_per_sec", &self.wal_buffers_full),
+            ("wal_write_ms_per_sec", &self.wal_write_time),
+            ("wal_sync_ms_per_sec", &self.wal_sync_time),
+            ("checkpoints_per_sec", &self.checkpoints),
+            ("checkpoints_forced_per_sec", &self.checkpoints_forced),
+            ("checkpoint_write_ms_per_sec", &self.checkpoint_write_time),
+            ("checkpoint_sync_ms_per_sec", &self.checkpoint_sync_time),
+            ("archived_per_sec", &self.archived),
+            ("archive_failed_per_sec", &self.archive_failed),
+            ("buffers_checkpoint_per_sec", &self.buffers_checkpoint),
+            ("buffers_backend_per_sec", &self.buffers_backend),
+            ("buffers_clean_per_sec", &self.buffers_clean),
+            ("buffers_throttled_per_sec", &self.buffers_throttled),
+            ("buffers_alloc_per_sec", &self.buffers_alloc),
+        ]
+    }
+
+    fn series_mut(&mut self) -> [&mut RateSeries; 15] {
+        [
+            &mut self.wal_records,
+            &mut self.wal_buffers_full,
+            &mut self.wal_write_time,
+            &mut self.wal_sync_time,
+            &mut self.checkpoints,
+            &mut self.checkpoints_forced,
+            &mut self.checkpoint_write_time,
+            &mut self.checkpoint_sync_time,
+            &mut self.archived,
+            &mut self.archive_failed,
+            &mut self.buffers_checkpoint,
+            &mut self.buffers_backend,
+            &mut self.buffers_clean,
+            &mut self.buffers_throttled,
+            &mut self.buffers_alloc,
+        ]
+    }
+
+    /// Forget the displayed rates (history is kept) after a server restart
+    fn clear_current(&mut self) {
+        for series in self.series_mut() {
+            series.current = None;
+        }
+        self.io.clear();
+    }
+
+    /// Rates between `prev` and `snap`, in [`Self::series_mut`] order.
+    fn sample(prev: &PrevMetrics, snap: &PgSnapshot, secs: f64) -> WalIoSample {
+        let mut rates = [None; 15];
+        let [
+            wal_records,
+            wal_buffers_full,
+            wal_write_time,
+            wal_sync_time,
+            checkpoints,
+            checkpoints_forced,
+            checkpoint_write_time,
+            checkpoint_sync_time,
+            archived,
+            archive_failed,
+            buffers_checkpoint,
+            buffers_backend,
+            buffers_clean,
+            buffers_throttled,
+            buffers_alloc,
+        ] = &mut rates;
+        if let (Some(p), Some(c)) = (&prev.wal, &snap.wal_stats) {
+            *wal_records = per_sec((c.wal_records - p.wal_records) as f64, secs);
+            *wal_buffers_full = per_sec((c.wal_buffers_full - p.wal_buffers_full) as f64, secs);
+            *wal_write_time = per_sec(c.wal_write_time - p.wal_write_time, secs);
+            *wal_sync_time = per_sec(c.wal_sync_time - p.wal_sync_time, secs);
+        }
+
+        if let (Some(p), Some(c)) = (&prev.checkpoint, &snap.checkpoint_stats) {
+            let total = |s: &CheckpointStats| s.checkpoints_timed + s.checkpoints_req;
+            *checkpoints = per_sec((total(c) - total(p)) as f64, secs);
+            *checkpoints_forced = per_sec((c.checkpoints_req - p.checkpoints_req) as f64, secs);
+            *checkpoint_write_time = per_sec(c.checkpoint_write_time - p.checkpoint_write_time, secs);
+            *checkpoint_sync_time = per_sec(c.checkpoint_sync_time - p.checkpoint_sync_time, secs);
+            *buffers_checkpoint = per_sec((c.buffers_checkpoint - p.buffers_checkpoint) as f64, secs);
+            *buffers_backend = per_sec((c.buffers_backend - p.buffers_backend) as f64, secs);
+        }
+
+        if let (Some((p_archived, p_failed)), Some(c)) = (prev.archiver, &snap.archiver_stats) {
+            *archived = per_sec((c.archived_count - p_archived) as f64, secs);
+            *archive_failed = per_sec((c.failed_count - p_failed) as f64, secs);
+        }
+
+        if let (Some(p), Some(c)) = (&prev.bgwriter, &snap.bgwriter_stats) {
+            *buffers_clean = per_sec((c.buffers_clean - p.buffers_clean) as f64, secs);
+            *buffers_throttled = per_sec((c.maxwritten_clean - p.maxwritten_clean) as f64, secs);
+            *buffers_alloc = per_sec((c.buffers_alloc - p.buffers_alloc) as f64, secs);
+        }
+
+        WalIoSample {
+            rates,
+            io: IoRate::between(&prev.io, &snap.io_stats, secs),
+        }
+    }
+
+    fn push(&mut self, sample: &WalIoSample) {
+        for (series, rate) in self.series_mut().into_iter().zip(sample.rates) {
+            if let Some(rate) = rate {
+                series.push_rate(rate);
+            }
+        }
+        self.io.clone_from(&sample.io);
+    }
+}
+
+/// Checkpoints that completed between two snapshots, for the Checkpoints
+/// panel's log. Usually one; a long refresh interval can cover several.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CheckpointEvent {
+    pub count: i64,
+    /// Of `count`, how many were requested rather than timed
+    pub forced: i64,
+    pub write_ms: f64,
+    pub sync_ms: f64,
+    /// Buffers the checkpointer wrote
+    pub buffers: i64,
+}
+
+impl CheckpointEvent {
+    /// The checkpoints completed between `prev` and `curr`, if any.
+    pub fn between(prev: &CheckpointStats, curr: &CheckpointStats) -> Option<Self> {
+        let count = (curr.checkpoints_timed + curr.checkpoints_req)
+            - (prev.checkpoints_timed + prev.checkpoints_req);
+        (count > 0).then(|| Self {
+            count,
+            forced: (curr.checkpoints_req - prev.checkpoints_req).max(0),
+            write_ms: (curr.checkpoint_write_time - prev.checkpoint_write_time).max(0.0),
+            sync_ms: (curr.checkpoint_sync_time - prev.checkpoint_sync_time).max(0.0),
+            buffers: (curr.buffers_checkpoint - prev.buffers_checkpoint).max(0),
+        })
+    }
+}
+
+/// Per-second rates of the WAL & I/O series over one interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalIoSample {
+    /// In [`WalIoRates::series_mut`] order; none where a counter went back
+    rates: [Option<f64>; 15],
+    io: Vec<IoRate>,
+}
+
+/// Counter rates over one refresh interval, worked out where the snapshot
+/// is prepared so the UI thread only pushes them
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalRates {
+    pub at: DateTime<Utc>,
+    /// Counter reset detected in this interval; the deltas it invalidates
+    /// are left out rather than producing bogus rates
+    pub reset: Option<CounterReset>,
+    pub tps: Option<f64>,
+    /// Blocks read per second (physical I/O)
+    pub blks_read: Option<f64>,
+    /// WAL bytes per second
+    pub wal_bytes: Option<f64>,
+    pub wal_io: Option<WalIoSample>,
+    pub checkpoint: Option<CheckpointEvent>,
+}
+
+/// Keeps the counters of the last snapshot to diff the next one against.
+#[derive(Debug, Default)]
+pub struct RateTracker {
+    prev: Option<PrevMetrics>,
+}
+
+impl RateTracker {
+    /// Rates between the previous snapshot and `snap`.
+    pub fn interval(&mut self, snap: &PgSnapshot) -> IntervalRates {
+        let mut rates = IntervalRates {
+            at: snap.timestamp,
+            reset: None,
+            tps: None,
+            blks_read: None,
+            wal_bytes: None,
+            wal_io: None,
+            checkpoint: None,
+        };
+        if let (Some(prev), Some(curr_db)) = (&self.prev, &snap.db_stats) {
+            rates.reset = CounterReset::detect(prev, curr_db);
+            let secs = snap
+                .timestamp
+                .signed_duration_since(prev.timestamp)
+                .num_milliseconds() as f64
+                / 1000.0;
+
+            // A stats reset only invalidates pg_stat_database deltas; a
+            // restart invalidates the cluster-wide counters too
+            if rates.reset.is_none() {
+                // TPS and blocks read from pg_stat_database
+                let commits = curr_db.xact_commit - prev.xact_commit;
+                let rollbacks = curr_db.xact_rollback - prev.xact_rollback;
+                // Guard against counter reset (server restart)
+                if commits >= 0 && rollbacks >= 0 {
+                    rates.tps = per_sec((commits + rollbacks) as f64, secs);
+                }
+                rates.blks_read = per_sec((curr_db.blks_read - prev.blks_read) as f64, secs);
+            }
+
+            if secs > 0.0 && !rates.reset.is_some_and(|r| r.is_server_restart()) {
+                // WAL rate from pg_stat_wal
+                if let (Some(curr), Some(prev)) = (&snap.wal_stats, &prev.wal) {
+                    rates.wal_bytes = per_sec((curr.wal_bytes - prev.wal_bytes) as f64, secs);
+                }
+                rates.wal_io = Some(WalIoRates::sample(prev, snap, secs));
+                if let (Some(p), Some(c)) = (&prev.checkpoint, &snap.checkpoint_stats) {
+                    rates.checkpoint = CheckpointEvent::between(p, c);
+                }
+            }
+        }
+
+        // Store only the fields needed for next delta calculation
+        if let Some(db) = &snap.db_stats {
+            self.prev = Some(PrevMetrics {
+                timestamp: snap.timestamp,
+                xact_commit: db.xact_commit,
+                xact_rollback: db.xact_rollback,
+                blks_read: db.blks_read,
+                wal: snap.wal_stats,
+                checkpoint: snap.checkpoint_stats,
+                bgwriter: snap.bgwriter_stats,
+                archiver: snap
+                    .archiver_stats
+                    .as_ref()
+                    .map(|a| (a.archived_count, a.failed_count)),
+                io: snap.io_stats.clone(),
+                server_start: db.server_start,
+                stats_reset: db.stats_reset,
+            });
+        }
+        rates
+    }
 }
 
 /// Metrics history for sparklines and rate calculations
@@ -346,9 +1851,16 @@ pub struct MetricsHistory {
     // Sparkline data
     pub connections: RingBuffer<u64>,
     pub avg_query_time: RingBuffer<u64>,
+    /// Running queries by duration bucket, for the latency heatmap
+    pub latency_buckets: RingBuffer<LatencyBuckets>,
     pub hit_ratio: RingBuffer<u64>,
     pub active_queries: RingBuffer<u64>,
     pub lock_count: RingBuffer<u64>,
+    pub autovacuum_workers: RingBuffer<u64>,
+    /// Lag history by standby walsender PID
+    pub replication_lag: HashMap<i32, StandbyLag>,
+    /// Per-interval deltas by pg_stat_statements queryid
+    pub statement_timing: HashMap<i64, StatementTiming>,
 
     // Rate tracking
     pub tps: RingBuffer<u64>,
@@ -360,8 +1872,19 @@ pub struct MetricsHistory {
     pub current_wal_rate: Option<f64>,
     pub current_blks_read_rate: Option<f64>,
 
-    // Previous metrics for delta calculation
-    pub(super) prev_metrics: Option<PrevMetrics>,
+    // WAL & I/O panel rates
+    pub wal_io: WalIoRates,
+
+    // Checkpoints seen completing this session, stamped when noticed
+    pub checkpoint_log: RingBuffer<CheckpointEvent>,
+
+    // One entry per sample, true where counters were reset (graph markers)
+    pub reset_marks: RingBuffer<bool>,
+    pub last_reset: Option<CounterReset>,
+    // One entry per sample, true where an annotation was made since the last
+    pub annotation_marks: RingBuffer<bool>,
+
+    capacity: usize,
 }
 
 impl MetricsHistory {
@@ -369,16 +1892,25 @@ impl MetricsHistory {
         Self {
             connections: RingBuffer::new(capacity),
             avg_query_time: RingBuffer::new(capacity),
+            latency_buckets: RingBuffer::new(capacity),
             hit_ratio: RingBuffer::new(capacity),
             active_queries: RingBuffer::new(capacity),
             lock_count: RingBuffer::new(capacity),
+            autovacuum_workers: RingBuffer::new(capacity),
+            replication_lag: HashMap::new(),
+            statement_timing: HashMap::new(),
             tps: RingBuffer::new(capacity),
             wal_rate: RingBuffer::new(capacity),
             blks_read: RingBuffer::new(capacity),
             current_tps: None,
             current_wal_rate: None,
             current_blks_read_rate: None,
-            prev_metrics: None,
+            wal_io: WalIoRates::new(capacity),
+            checkpoint_log: RingBuffer::new(capacity),
+            reset_marks: RingBuffer::new(capacity),
+            last_reset: None,
+            annotation_marks: RingBuffer::new(capacity),
+            capacity,
         }
     }
 
@@ -386,78 +1918,106 @@ impl MetricsHistory {
     pub fn push_snapshot_metrics(&mut self, snap: &PgSnapshot) {
         self.connections.push(snap.summary.total_backends as u64);
 
-        let active: Vec<&_> = snap
-            .active_queries
-            .iter()
-            .filter(|q| matches!(q.state.as_deref(), Some("active" | "idle in transaction")))
-            .collect();
-        let avg_ms = if active.is_empty() {
-            0u64
-        } else {
-            let sum: f64 = active.iter().map(|q| q.duration_secs).sum();
-            (sum / active.len() as f64 * 1000.0) as u64
-        };
-        self.avg_query_time.push(avg_ms);
+        self.avg_query_time.push(avg_active_query_ms(snap));
+        self.latency_buckets.push(latency_buckets(snap));
 
         self.hit_ratio
             .push((snap.buffer_cache.hit_ratio * 1000.0) as u64);
         self.active_queries
             .push(snap.summary.active_query_count as u64);
         self.lock_count.push(snap.summary.lock_count as u64);
-    }
+        self.autovacuum_workers
+            .push(snap.summary.autovacuum_count as u64);
 
-    /// Calculate and update rate metrics from snapshot delta
-    pub fn calculate_rates(&mut self, snap: &PgSnapshot) {
-        if let (Some(prev), Some(curr_db)) = (&self.prev_metrics, &snap.db_stats) {
-            let secs = snap
-                .timestamp
-                .signed_duration_since(prev.timestamp)
-                .num_milliseconds() as f64
-                / 1000.0;
+        // Standbys that disconnected take their history with them
+        self.replication_lag
+            .retain(|pid, _| snap.replication.iter().any(|r| r.pid == *pid));
+        for r in &snap.replication {
+            let capacity = self.capacity;
+            self.replication_lag
+                .entry(r.pid)
+                .or_insert_with(|| StandbyLag::new(capacity))
+                .push(r);
+        }
 
-            if secs > 0.0 {
-                // TPS and blocks read from pg_stat_database
-                let commits = curr_db.xact_commit - prev.xact_commit;
-                let rollbacks = curr_db.xact_rollback - prev.xact_rollback;
-                // Guard against counter reset (server restart)
-                if commits >= 0 && rollbacks >= 0 {
-                    let tps = (commits + rollbacks) as f64 / secs;
-                    self.current_tps = Some(tps);
-                    self.tps.push(tps as u64);
+        // Statements that fall out of the fetched top list start afresh
+        // if they come back, as their deltas would span the gap. Windowed
+        // counters have no deltas to take.
+        let cumulative = || snap.stat_statements.iter().filter(|s| !s.windowed);
+        self.statement_timing
+            .retain(|id, _| cumulative().any(|s| s.queryid == *id));
+        for s in cumulative() {
+            match self.statement_timing.get_mut(&s.queryid) {
+                Some(timing) => timing.push(s),
+                None => {
+                    let timing = StatementTiming::new(self.capacity, s);
+                    self.statement_timing.insert(s.queryid, timing);
                 }
+            }
+        }
+    }
 
-                // Blocks read rate (physical I/O)
-                let blks = curr_db.blks_read - prev.blks_read;
-                if blks >= 0 {
-                    let rate = blks as f64 / secs;
-                    self.current_blks_read_rate = Some(rate);
-                    self.blks_read.push(rate as u64);
-                }
+    /// Whether a counter reset happened within the retained history
+    pub fn has_recent_reset(&self) -> bool {
+        self.last_reset.is_some() && self.reset_marks.as_vec().contains(&true)
+    }
 
-                // WAL rate from pg_stat_wal
-                if let (Some(curr_wal_bytes), Some(prev_wal_bytes)) =
-                    (snap.wal_stats.as_ref().map(|w| w.wal_bytes), prev.wal_bytes)
-                {
-                    let bytes = curr_wal_bytes - prev_wal_bytes;
-                    if bytes >= 0 {
-                        let rate = bytes as f64 / secs;
-                        self.current_wal_rate = Some(rate);
-                        // Store as KB/s for sparkline (fits in u64 better)
-                        self.wal_rate.push((rate / 1024.0) as u64);
-                    }
-                }
+    /// Push the rates of one interval. A counter reset in it clears the
+    /// current values it invalidates.
+    pub fn push_rates(&mut self, rates: &IntervalRates) {
+        self.reset_marks.push(rates.reset.is_some());
+        if let Some(reset) = rates.reset {
+            self.last_reset = Some(reset);
+            self.current_tps = None;
+            self.current_blks_read_rate = None;
+            if reset.is_server_restart() {
+                self.current_wal_rate = None;
+                self.wal_io.clear_current();
             }
         }
+        if let Some(tps) = rates.tps {
+            self.current_tps = Some(tps);
+            self.tps.push(tps as u64);
+        }
+        if let Some(rate) = rates.blks_read {
+            self.current_blks_read_rate = Some(rate);
+            self.blks_read.push(rate as u64);
+        }
+        if let Some(rate) = rates.wal_bytes {
+            self.current_wal_rate = Some(rate);
+            // Store as KB/s for sparkline (fits in u64 better)
+            self.wal_rate.push((rate / 1024.0) as u64);
+        }
+        if let Some(sample) = &rates.wal_io {
+            self.wal_io.push(sample);
+        }
+        if let Some(event) = rates.checkpoint {
+            self.checkpoint_log.push(event);
+        }
 
-        // Store only the fields needed for next delta calculation
-        if let Some(db) = &snap.db_stats {
-            self.prev_metrics = Some(PrevMetrics {
-                timestamp: snap.timestamp,
-                xact_commit: db.xact_commit,
-                xact_rollback: db.xact_rollback,
-                blks_read: db.blks_read,
-                wal_bytes: snap.wal_stats.as_ref().map(|w| w.wal_bytes),
-            });
+        // Everything pushed for this snapshot, gauges included, gets its time
+        self.stamp(rates.at);
+    }
+
+    fn stamp(&mut self, at: DateTime<Utc>) {
+        for buf in [
+            &mut self.connections,
+            &mut self.avg_query_time,
+            &mut self.hit_ratio,
+            &mut self.active_queries,
+            &mut self.lock_count,
+            &mut self.tps,
+            &mut self.wal_rate,
+            &mut self.blks_read,
+        ] {
+            buf.stamp(at);
+        }
+        self.latency_buckets.stamp(at);
+        self.reset_marks.stamp(at);
+        self.annotation_marks.stamp(at);
+        self.checkpoint_log.stamp(at);
+        for series in self.wal_io.series_mut() {
+            series.history.stamp(at);
         }
     }
 }
@@ -2,8 +2,8 @@
 
 use super::*;
 use crate::db::models::{
-    ActiveQuery, ActivitySummary, BufferCacheStats, DetectedExtensions, PgExtension,
-    PgSnapshot, ServerInfo,
+    ActiveQuery, ActivitySummary, BufferCacheRelation, BufferCacheStats, ConnectionGroup, DetectedExtensions, LockInfo, PgExtension,
+    PgSnapshot, ReplicationSlot, ServerInfo,
 };
 use chrono::Utc;
 
@@ -15,6 +15,9 @@ fn make_server_info() -> ServerInfo {
         extensions: DetectedExtensions::default(),
         settings: vec![],
         extensions_list: vec![],
+        role_settings: vec![],
+        roles: vec![],
+        capabilities: Default::default(),
     }
 }
 
@@ -32,9 +35,15 @@ fn make_snapshot() -> PgSnapshot {
             wait_event: None,
             query_start: None,
             backend_type: None,
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         }],
         wait_events: vec![],
         blocking_info: vec![],
+        locks: vec![],
+        connection_groups: vec![],
         buffer_cache: BufferCacheStats {
             blks_hit: 9900,
             blks_read: 100,
@@ -48,12 +57,19 @@ fn make_snapshot() -> PgSnapshot {
             lock_count: 0,
             oldest_xact_secs: None,
             autovacuum_count: 0,
+            aborted_txn_count: 0,
+            idle_count: 0,
         },
         table_stats: vec![],
         replication: vec![],
         replication_slots: vec![],
         subscriptions: vec![],
         vacuum_progress: vec![],
+        operations: vec![],
+        background_workers: vec![],
+        capped_pids: vec![],
+        wal_dir: None,
+        statement_monitor: None,
         wraparound: vec![],
         indexes: vec![],
         stat_statements: vec![],
@@ -65,6 +81,12 @@ fn make_snapshot() -> PgSnapshot {
         archiver_stats: None,
         bgwriter_stats: None,
         db_stats: None,
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+        io_stats: vec![],
     }
 }
 
@@ -198,11 +220,11 @@ fn bloat_refresh_disabled_in_replay_mode_indexes() {
 }
 
 #[test]
-fn recordings_browser_disabled_in_replay_mode() {
+fn recordings_browser_available_in_replay_mode() {
     let mut app = make_replay_app();
     app.handle_key(key(KeyCode::Char('L')));
-    // Should not open recordings browser since we're already in replay
-    assert_eq!(app.view_mode, ViewMode::Normal);
+    // Replay can switch to another recording without restarting
+    assert_eq!(app.view_mode, ViewMode::Recordings);
 }
 
 #[test]
@@ -212,6 +234,172 @@ fn help_opens() {
     assert_eq!(app.view_mode, ViewMode::Help);
 }
 
+#[test]
+fn second_question_mark_opens_glossary() {
+    let mut app = make_app();
+    app.bottom_panel = BottomPanel::Statements;
+    app.handle_key(key(KeyCode::Char('?')));
+    app.handle_key(key(KeyCode::Char('?')));
+    assert_eq!(app.view_mode, ViewMode::Glossary(BottomPanel::Statements));
+    assert_eq!(app.glossary_focus(), Some("Total Time"));
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn glossary_opens_from_inspect() {
+    let mut app = make_app();
+    app.bottom_panel = BottomPanel::TableStats;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key(KeyCode::Char('?')));
+    assert_eq!(app.view_mode, ViewMode::Glossary(BottomPanel::TableStats));
+}
+
+#[test]
+fn export_graphs_key_requests_action() {
+    let mut app = make_app();
+    app.bottom_panel = BottomPanel::TableStats;
+    app.handle_key(key(KeyCode::Char('e')));
+    assert!(matches!(app.feedback.pending_action, Some(AppAction::ExportGraphs)));
+}
+
+#[test]
+fn ctrl_e_prompts_for_a_panel_export_file() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.handle_key(key_ctrl(KeyCode::Char('e')));
+    assert_eq!(app.view_mode, ViewMode::ExportPrompt);
+    assert!(app.export_input.ends_with(".csv"));
+    assert!(app.export_input.contains("queries_"));
+
+    app.handle_key(key(KeyCode::Tab));
+    assert!(app.export_input.ends_with(".json"));
+    app.handle_key(key(KeyCode::Tab));
+    assert!(app.export_input.ends_with(".svg"));
+
+    app.export_input = "/tmp/out.json".into();
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::ExportPanel(path)) if path == std::path::Path::new("/tmp/out.json")
+    ));
+}
+
+#[test]
+fn colon_prompts_for_a_replay_seek_target() {
+    let mut live = make_app();
+    live.handle_key(key(KeyCode::Char(':')));
+    assert_eq!(live.view_mode, ViewMode::Normal);
+
+    let mut app = make_replay_app();
+    app.handle_key(key(KeyCode::Char(':')));
+    assert_eq!(app.view_mode, ViewMode::SeekPrompt);
+    for c in "14:05".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app.seek_input.is_empty());
+    let time = chrono::NaiveTime::from_hms_opt(14, 5, 0).unwrap();
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::SeekReplay(SeekTarget::Time(t))) if t == time
+    ));
+
+    app.handle_key(key(KeyCode::Char(':')));
+    for c in "soon".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    assert!(app.feedback.take_action().is_none());
+    assert!(app.feedback.status_message.as_deref().unwrap().contains("soon"));
+}
+
+#[test]
+fn seek_targets_parse_times_and_percentages() {
+    use chrono::{NaiveTime, TimeZone};
+    assert_eq!(
+        SeekTarget::parse("12:34:56"),
+        Some(SeekTarget::Time(NaiveTime::from_hms_opt(12, 34, 56).unwrap()))
+    );
+    assert_eq!(
+        SeekTarget::parse(" 9:05 "),
+        Some(SeekTarget::Time(NaiveTime::from_hms_opt(9, 5, 0).unwrap()))
+    );
+    assert_eq!(SeekTarget::parse("25%"), Some(SeekTarget::Fraction(0.25)));
+    assert_eq!(SeekTarget::parse("120%"), None);
+    assert_eq!(SeekTarget::parse("25:00"), None);
+
+    // A recording running from 23:30 to 00:30 the next day
+    let first = Utc.with_ymd_and_hms(2024, 1, 15, 23, 30, 0).unwrap();
+    let last = first + chrono::Duration::hours(1);
+    assert_eq!(SeekTarget::Fraction(0.5).instant(first, last), first + chrono::Duration::minutes(30));
+    assert_eq!(
+        SeekTarget::parse("23:45").unwrap().instant(first, last),
+        first + chrono::Duration::minutes(15)
+    );
+    assert_eq!(
+        SeekTarget::parse("00:15").unwrap().instant(first, last),
+        first + chrono::Duration::minutes(45)
+    );
+}
+
+#[test]
+fn ctrl_e_with_nothing_to_export_stays_closed() {
+    let mut app = make_app();
+    app.handle_key(key_ctrl(KeyCode::Char('e')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Nothing to export in Queries")
+    );
+}
+
+#[test]
+fn ctrl_e_in_the_export_prompt_switches_to_the_metrics_history() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.handle_key(key_ctrl(KeyCode::Char('e')));
+    app.handle_key(key(KeyCode::Tab));
+    assert!(app.export_input.ends_with(".json"));
+
+    app.handle_key(key_ctrl(KeyCode::Char('e')));
+    assert!(app.export_history);
+    assert!(app.export_input.contains("history_") && app.export_input.ends_with(".json"));
+    // No image format for the raw history
+    app.handle_key(key(KeyCode::Tab));
+    assert!(app.export_input.ends_with(".csv"));
+
+    app.export_input = "/tmp/history.csv".into();
+    app.handle_key(key(KeyCode::Enter));
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::ExportHistory(path)) if path == std::path::Path::new("/tmp/history.csv")
+    ));
+}
+
+#[test]
+fn panel_export_rows_follow_filter_and_sort() {
+    let mut app = make_app_with_lock_wait();
+    let export = BottomPanel::Queries.descriptor().export_rows;
+    let pids: Vec<i64> = export(&app).iter().map(|r| r["pid"].as_i64().unwrap()).collect();
+    let expected: Vec<i64> = app
+        .sorted_query_indices()
+        .iter()
+        .map(|&i| i64::from(app.snapshot.as_ref().unwrap().active_queries[i].pid))
+        .collect();
+    assert_eq!(pids, expected);
+    assert_eq!(pids.len(), 2);
+
+    app.filter_mut().text = "12399".into();
+    app.filter_mut().active = true;
+    let rows = export(&app);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["pid"], 12399);
+}
+
 #[test]
 fn config_opens() {
     let mut app = make_app();
@@ -229,6 +417,38 @@ fn zen_mode_toggles() {
     assert!(!app.graphs_collapsed);
 }
 
+#[test]
+fn split_view_moves_focus_between_two_panels() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    // Ctrl+w does nothing without a split
+    app.handle_key(key_ctrl(KeyCode::Char('w')));
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+
+    app.handle_key(key(KeyCode::Char('|')));
+    assert_eq!(app.split, Some(SplitView { other: BottomPanel::Blocking, focus_right: false }));
+
+    app.panels.queries.state.select(Some(0));
+    app.handle_key(key_ctrl(KeyCode::Char('w')));
+    assert_eq!(app.bottom_panel, BottomPanel::Blocking);
+    assert_eq!(app.split, Some(SplitView { other: BottomPanel::Queries, focus_right: true }));
+    // The unfocused pane keeps its selection
+    assert_eq!(app.panels.queries.selected(), Some(0));
+
+    // A panel key changes the focused pane only
+    app.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(app.bottom_panel, BottomPanel::TableStats);
+    assert_eq!(app.split.unwrap().other, BottomPanel::Queries);
+    // Asking for the other pane's panel trades the two
+    app.handle_key(key(KeyCode::Char('q')));
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+    assert_eq!(app.split, Some(SplitView { other: BottomPanel::TableStats, focus_right: true }));
+
+    app.handle_key(key(KeyCode::Char('|')));
+    assert_eq!(app.split, None);
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+}
+
 #[test]
 fn zen_mode_works_in_replay_mode() {
     let mut app = make_replay_app();
@@ -278,13 +498,70 @@ fn panel_switch_keys() {
 }
 
 #[test]
-fn panel_switch_clears_filter() {
+fn go_to_chord_switches_panels_without_toggling() {
     let mut app = make_app();
-    app.filter.text = "test".into();
-    app.filter.active = true;
-    app.handle_key(key(KeyCode::Char('I')));
-    assert!(app.filter.text.is_empty());
-    assert!(!app.filter.active);
+    app.handle_key(key(KeyCode::Char('g')));
+    assert_eq!(app.pending_chord, Some('g'));
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+
+    app.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(app.pending_chord, None);
+    assert_eq!(app.bottom_panel, BottomPanel::TableStats);
+
+    // Unlike `t`, a second `gt` stays on Tables
+    app.handle_key(key(KeyCode::Char('g')));
+    app.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(app.bottom_panel, BottomPanel::TableStats);
+}
+
+#[test]
+fn chord_is_dropped_on_esc_or_an_unbound_key() {
+    let mut app = make_app();
+    app.bottom_panel = BottomPanel::Indexes;
+    app.handle_key(key(KeyCode::Char('g')));
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.pending_chord, None);
+    // Esc only cancelled the chord, it did not go back to Queries
+    assert_eq!(app.bottom_panel, BottomPanel::Indexes);
+    assert!(app.running);
+
+    app.handle_key(key(KeyCode::Char('g')));
+    app.handle_key(key(KeyCode::Char('Z')));
+    assert_eq!(app.pending_chord, None);
+    assert_eq!(app.bottom_panel, BottomPanel::Indexes);
+    assert_eq!(app.feedback.status_message.as_deref(), Some("Nothing bound to g Z"));
+}
+
+#[test]
+fn panel_filters_are_kept_across_switches() {
+    let mut app = make_app();
+    app.filter_mut().text = "test".into();
+    app.filter_mut().active = true;
+    app.handle_key(key(KeyCode::Char('S')));
+    assert!(app.filter().text.is_empty());
+    assert!(!app.filter().active);
+
+    app.filter_mut().text = "orders".into();
+    app.filter_mut().active = true;
+    app.handle_key(key(KeyCode::Char('S'))); // back to Queries
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+    assert_eq!(app.filter().text, "test");
+    assert!(app.filter().active);
+    assert_eq!(app.panels.filter(BottomPanel::Statements).text, "orders");
+}
+
+#[test]
+fn backspace_clears_only_the_current_panels_filter() {
+    let mut app = make_app();
+    app.panels.filter_mut(BottomPanel::Statements).text = "orders".into();
+    app.panels.filter_mut(BottomPanel::Statements).active = true;
+    app.filter_mut().text = "test".into();
+    app.filter_mut().active = true;
+
+    app.handle_key(key(KeyCode::Backspace));
+    assert!(app.filter().text.is_empty());
+    assert!(!app.filter().active);
+    assert!(app.panels.filter(BottomPanel::Statements).active);
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -332,26 +609,26 @@ fn filter_typing() {
     app.handle_key(key(KeyCode::Char('e')));
     app.handle_key(key(KeyCode::Char('s')));
     app.handle_key(key(KeyCode::Char('t')));
-    assert_eq!(app.filter.text, "test");
+    assert_eq!(app.filter().text, "test");
 }
 
 #[test]
 fn filter_backspace() {
     let mut app = make_app();
     app.view_mode = ViewMode::Filter;
-    app.filter.text = "test".into();
+    app.filter_mut().text = "test".into();
     app.handle_key(key(KeyCode::Backspace));
-    assert_eq!(app.filter.text, "tes");
+    assert_eq!(app.filter().text, "tes");
 }
 
 #[test]
 fn filter_enter_activates() {
     let mut app = make_app();
     app.view_mode = ViewMode::Filter;
-    app.filter.text = "query".into();
+    app.filter_mut().text = "query".into();
     app.handle_key(key(KeyCode::Enter));
     assert_eq!(app.view_mode, ViewMode::Normal);
-    assert!(app.filter.active);
+    assert!(app.filter().active);
 }
 
 #[test]
@@ -360,18 +637,18 @@ fn filter_enter_with_empty_text_does_not_activate() {
     app.view_mode = ViewMode::Filter;
     app.handle_key(key(KeyCode::Enter));
     assert_eq!(app.view_mode, ViewMode::Normal);
-    assert!(!app.filter.active);
+    assert!(!app.filter().active);
 }
 
 #[test]
 fn filter_esc_clears_and_exits() {
     let mut app = make_app();
     app.view_mode = ViewMode::Filter;
-    app.filter.text = "test".into();
+    app.filter_mut().text = "test".into();
     app.handle_key(key(KeyCode::Esc));
     assert_eq!(app.view_mode, ViewMode::Normal);
-    assert!(app.filter.text.is_empty());
-    assert!(!app.filter.active);
+    assert!(app.filter().text.is_empty());
+    assert!(!app.filter().active);
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -421,6 +698,37 @@ fn config_adjust_refresh_interval() {
     assert_eq!(app.config.refresh_interval_secs, initial);
 }
 
+#[test]
+fn config_row_color_rule_editor() {
+    use crate::row_colors::{RowColor, RowColumn};
+
+    let mut app = make_app();
+    app.view_mode = ViewMode::Config;
+    app.handle_key(key(KeyCode::Char('a')));
+    assert_eq!(app.config.row_colors.len(), 1);
+    assert_eq!(app.config_overlay.selected, ConfigItem::ALL.len());
+
+    app.handle_key(key(KeyCode::Right));
+    assert_eq!(app.config.row_colors[0].above, Some(130.0));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.config.row_colors[0].column, RowColumn::MaxExecTime);
+    app.handle_key(key(KeyCode::Char(' ')));
+    assert_eq!(app.config.row_colors[0].below, Some(100.0));
+    app.handle_key(key(KeyCode::Char('c')));
+    assert_eq!(app.config.row_colors[0].color, RowColor::Warn);
+    // Settings above the rules are still adjusted as before
+    let before = app.config.danger_duration_secs;
+    app.handle_key(key(KeyCode::Up));
+    app.handle_key(key(KeyCode::Right));
+    assert_eq!(app.config.row_colors[0].below, Some(100.0));
+    assert!(app.config.recordings_dir.is_none() && app.config.danger_duration_secs == before);
+
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char('x')));
+    assert!(app.config.row_colors.is_empty());
+    assert_eq!(app.config_overlay.selected, ConfigItem::ALL.len() - 1);
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Help mode
 // ─────────────────────────────────────────────────────────────────────────────
@@ -472,6 +780,34 @@ fn help_scroll_page() {
     assert_eq!(app.overlay_scroll, 0);
 }
 
+#[test]
+fn overlay_scroll_clamps_to_rendered_content() {
+    let mut app = make_app();
+    app.view_mode = ViewMode::Help;
+    app.overlay_extent.set(Some(crate::app::OverlayExtent {
+        max_scroll: 25,
+        page: 18,
+    }));
+
+    app.handle_key(key(KeyCode::PageDown));
+    assert_eq!(app.overlay_scroll, 18);
+    app.handle_key(key(KeyCode::PageDown));
+    assert_eq!(app.overlay_scroll, 25);
+    app.handle_key(key(KeyCode::Down));
+    assert_eq!(app.overlay_scroll, 25);
+
+    // Up moves off the bottom straight away, not after working off overshoot
+    app.overlay_scroll = 500;
+    app.handle_key(key(KeyCode::Up));
+    assert_eq!(app.overlay_scroll, 24);
+
+    app.handle_key(key(KeyCode::Char('g')));
+    app.handle_key(key(KeyCode::Char('G')));
+    assert_eq!(app.overlay_scroll, 25);
+    app.handle_key(key(KeyCode::PageUp));
+    assert_eq!(app.overlay_scroll, 7);
+}
+
 #[test]
 fn help_exit_keys() {
     for code in [KeyCode::Esc, KeyCode::Char('q'), KeyCode::Enter] {
@@ -568,73 +904,1490 @@ fn confirm_kill_choice_esc() {
 // ─────────────────────────────────────────────────────────────────────────────
 
 #[test]
-fn inspect_scroll_and_exit() {
-    let targets = [
-        InspectTarget::Query(12345),
-        InspectTarget::Index("public.test_idx".to_string()),
-        InspectTarget::Statement(123456789),
-        InspectTarget::Replication(12345),
-        InspectTarget::Table("public.test".to_string()),
-        InspectTarget::Blocking(12345),
-        InspectTarget::Vacuum(12345),
-        InspectTarget::Wraparound("testdb".to_string()),
-    ];
-    for target in targets {
-        let mut app = make_app();
-        app.view_mode = ViewMode::Inspect(target.clone());
-        app.overlay_scroll = 5;
-
-        app.handle_key(key(KeyCode::Down));
-        assert_eq!(app.overlay_scroll, 6, "Down should scroll in {target:?}");
+fn inspecting_a_table_asks_for_its_definition() {
+    use crate::db::models::{TableSchema, TableStat};
 
-        app.handle_key(key(KeyCode::Char('k')));
-        assert_eq!(app.overlay_scroll, 5, "k should scroll up in {target:?}");
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.table_stats = vec![TableStat {
+        schemaname: "public".into(),
+        relname: "users".into(),
+        total_size_bytes: 1_000_000,
+        table_size_bytes: 800_000,
+        indexes_size_bytes: 200_000,
+        seq_scan: 100,
+        seq_tup_read: 5000,
+        idx_scan: 500,
+        idx_tup_fetch: 4500,
+        n_live_tup: 10000,
+        n_dead_tup: 500,
+        dead_ratio: 5.0,
+        n_tup_ins: 100,
+        n_tup_upd: 50,
+        n_tup_del: 10,
+        n_tup_hot_upd: 20,
+        last_vacuum: None,
+        last_autovacuum: None,
+        last_analyze: None,
+        last_autoanalyze: None,
+        vacuum_count: 0,
+        autovacuum_count: 0,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    }];
+    app.update(snap);
+    app.bottom_panel = BottomPanel::TableStats;
 
-        app.handle_key(key(KeyCode::Esc));
-        assert_eq!(app.view_mode, ViewMode::Normal, "Esc should exit {target:?}");
-        assert_eq!(app.overlay_scroll, 0, "Overlay scroll should reset after {target:?}");
-    }
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.users".into())));
+    assert!(matches!(
+        app.feedback.pending_action,
+        Some(AppAction::DescribeTable { ref schema, ref table }) if schema == "public" && table == "users"
+    ));
+    assert_eq!(app.table_schema, Some(TableSchemaView::Loading("public.users".into())));
+
+    // A late answer for another table is dropped
+    app.set_table_schema("public.orders".into(), Ok(TableSchema::default()));
+    assert_eq!(app.table_schema, Some(TableSchemaView::Loading("public.users".into())));
+
+    app.set_table_schema("public.users".into(), Err("permission denied".into()));
+    assert_eq!(
+        app.table_schema,
+        Some(TableSchemaView::Failed {
+            key: "public.users".into(),
+            error: "permission denied".into()
+        })
+    );
 }
 
 #[test]
-fn query_inspect_kill_opens_confirm() {
+fn inspecting_a_role_asks_for_its_privileges() {
+    use crate::db::models::{ConnectionGroup, RoleGrant, RoleInfo};
+
     let mut app = make_app();
-    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+    let role = |name: &str| RoleInfo {
+        name: name.into(),
+        superuser: false,
+        login: true,
+        createdb: false,
+        createrole: false,
+        replication: false,
+        bypassrls: false,
+        conn_limit: -1,
+        valid_until: None,
+        member_of: vec!["readers".into()],
+    };
+    app.server_info.roles = vec![role("app_user"), role("reporting")];
+    let mut snap = make_snapshot();
+    let group = |db: &str, total| ConnectionGroup {
+        usename: Some("reporting".into()),
+        datname: Some(db.into()),
+        application_name: String::new(),
+        total,
+        active: 0,
+        idle: total,
+        idle_in_transaction: 0,
+        waiting: 0,
+        longest_secs: 0.0,
+    };
+    snap.connection_groups = vec![group("shop", 2), group("analytics", 5), group("shop", 4)];
+    app.update(snap);
+    app.switch_panel(BottomPanel::Roles);
 
-    app.handle_key(key(KeyCode::Char('K')));
-    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::Kill(12345)));
+    // Connections add up per database, busiest first
+    assert_eq!(
+        app.role_connections("reporting"),
+        vec![("shop".to_string(), 6), ("analytics".to_string(), 5)]
+    );
+
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Role("reporting".into())));
+    assert!(matches!(
+        app.feedback.pending_action,
+        Some(AppAction::ReadRoleGrants(ref role)) if role == "reporting"
+    ));
+    assert_eq!(app.role_grants, Some(RoleGrantsView::Loading("reporting".into())));
+
+    // A late answer for another role is dropped
+    app.set_role_grants("app_user".into(), Ok(vec![]));
+    assert_eq!(app.role_grants, Some(RoleGrantsView::Loading("reporting".into())));
+
+    let grants = vec![RoleGrant {
+        kind: "table".into(),
+        object: "public.orders".into(),
+        privileges: vec!["SELECT".into()],
+        grantable: false,
+    }];
+    app.set_role_grants("reporting".into(), Ok(grants.clone()));
+    assert_eq!(
+        app.role_grants,
+        Some(RoleGrantsView::Loaded {
+            role: "reporting".into(),
+            grants
+        })
+    );
 }
 
 #[test]
-fn query_inspect_cancel_opens_confirm() {
+fn wraparound_enter_reads_tables_of_the_connected_database_only() {
+    use crate::db::models::{WraparoundInfo, WraparoundTable};
+
     let mut app = make_app();
-    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+    let mut snap = make_snapshot();
+    let wrap = |datname: &str| WraparoundInfo {
+        datname: datname.into(),
+        xid_age: 150_000_000,
+        xids_remaining: 1_997_483_647,
+        pct_towards_wraparound: 7.0,
+    };
+    snap.wraparound = vec![wrap("analytics"), wrap("postgres")];
+    app.update(snap);
+    app.bottom_panel = BottomPanel::Wraparound;
 
-    app.handle_key(key(KeyCode::Char('C')));
-    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::Cancel(12345)));
+    // Another database: its relfrozenxid cannot be read from here
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Wraparound("analytics".into())));
+    assert!(app.feedback.pending_action.is_none());
+    assert!(app.wraparound_tables.is_none());
+
+    app.handle_key(key(KeyCode::Esc));
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert!(matches!(
+        app.feedback.pending_action,
+        Some(AppAction::ReadWraparoundTables(ref datname)) if datname == "postgres"
+    ));
+
+    let tables = vec![WraparoundTable {
+        schemaname: "public".into(),
+        relname: "orders".into(),
+        via_toast: false,
+        xid_age: 150_000_000,
+        size_bytes: 8192,
+    }];
+    app.set_wraparound_tables("postgres".into(), Ok(tables));
+    assert_eq!(
+        app.get_inspect_copy_text().as_deref(),
+        Some("VACUUM (FREEZE, VERBOSE) public.orders;")
+    );
 }
 
 #[test]
-fn query_inspect_kill_disabled_in_replay_mode() {
-    let mut app = make_app();
-    app.replay = Some(crate::app::ReplayState::new("test.jsonl".to_string(), 10));
-    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+fn m_in_table_inspect_measures_exact_bloat_with_pgstattuple() {
+    use crate::db::models::{BloatSource, MeasuredBloat, MeasuredIndex};
 
-    app.handle_key(key(KeyCode::Char('K')));
-    // Should stay in inspect mode, not open confirm
-    assert!(matches!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12345))));
+    let mut app = make_app_with_tables(&["users"]);
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+
+    app.handle_key(key(KeyCode::Char('m')));
+    assert!(app.feedback.take_action().is_none());
+    assert_eq!(app.feedback.status_message.as_deref(), Some("pgstattuple is not installed"));
+
+    app.server_info.extensions.pgstattuple = true;
+    app.handle_key(key(KeyCode::Char('m')));
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::MeasureBloat { ref schema, ref table }) if schema == "public" && table == "users"
+    ));
+    assert!(app.feedback.bloat_loading);
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.users".into())));
+
+    app.set_measured_bloat(
+        "public",
+        "users",
+        Ok(MeasuredBloat {
+            table_len: 1_000_000,
+            tuple_percent: 70.0,
+            dead_tuple_percent: 5.0,
+            free_space: 200_000,
+            free_percent: 20.0,
+            indexes: vec![MeasuredIndex {
+                index_name: "users_pkey".into(),
+                index_size: 8192,
+                avg_leaf_density: 60.0,
+                leaf_fragmentation: 0.0,
+            }],
+        }),
+    );
+    assert!(!app.feedback.bloat_loading);
+    let table = &app.snapshot.as_ref().unwrap().table_stats[0];
+    assert_eq!(table.bloat_pct, Some(25.0));
+    assert_eq!(table.bloat_bytes, Some(250_000));
+    assert_eq!(table.bloat_source, Some(BloatSource::PgstattupleExact));
+    assert_eq!(app.bloat_history.for_index("public.users_pkey")[0].pct, 40.0);
+    assert!(app.measured_bloat.contains_key("public.users"));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel-specific navigation
-// ─────────────────────────────────────────────────────────────────────────────
+#[test]
+fn exact_bloat_is_not_measured_in_replay() {
+    let mut app = make_replay_app();
+    app.server_info.extensions.pgstattuple = true;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key(KeyCode::Char('m')));
+    assert!(app.feedback.take_action().is_none());
+}
 
 #[test]
-fn queries_panel_navigation() {
+fn inspect_search_steps_through_recorded_matches() {
     let mut app = make_app();
-    app.panels.queries.state.select(Some(5));
-    app.handle_key(key(KeyCode::Up));
-    assert_eq!(app.panels.queries.selected(), Some(4));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+    app.overlay_extent.set(Some(OverlayExtent { max_scroll: 40, page: 10 }));
+
+    app.handle_key(key(KeyCode::Char('/')));
+    assert!(app.overlay_search.editing);
+    for c in "ord".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Backspace));
+    assert_eq!(app.overlay_search.query, "or");
+    // Keys are typed into the pattern, not taken as commands
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12345)));
+
+    // What the renderer would record for the text on screen
+    *app.overlay_search.matches.borrow_mut() = vec![3, 12, 30];
+    app.overlay_scroll = 8;
+    app.handle_key(key(KeyCode::Enter));
+    assert!(!app.overlay_search.editing);
+    assert_eq!(app.overlay_search.current, 1, "first match below the view top");
+    assert_eq!(app.overlay_scroll, 10);
+
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.overlay_scroll, 28);
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.overlay_search.current, 0, "n wraps to the top");
+    assert_eq!(app.overlay_scroll, 1);
+    app.handle_key(key(KeyCode::Char('N')));
+    assert_eq!(app.overlay_search.current, 2, "N wraps to the bottom");
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(!app.overlay_search.is_active(), "closing drops the search");
+}
+
+#[test]
+fn inspect_search_esc_while_typing_keeps_the_overlay() {
+    let mut app = make_app();
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.test".into()));
+    app.handle_key(key(KeyCode::Char('/')));
+    app.handle_key(key(KeyCode::Char('x')));
+    app.handle_key(key(KeyCode::Esc));
+    assert!(!app.overlay_search.is_active());
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.test".into())));
+
+    // Without a search n is not taken
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.overlay_scroll, 0);
+}
+
+#[test]
+fn index_inspect_writes_a_rebuild_script_to_a_file() {
+    use crate::db::models::IndexInfo;
+
+    let mut app = make_app_with_tables(&["users"]);
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.indexes = vec![IndexInfo {
+        schemaname: "public".into(),
+        table_name: "orders".into(),
+        index_name: "orders_email_idx".into(),
+        index_size_bytes: 8192,
+        idx_scan: 1,
+        idx_tup_read: 1,
+        idx_tup_fetch: 1,
+        index_definition: "CREATE INDEX orders_email_idx ON public.orders USING btree (email)".into(),
+        is_valid: true,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    }];
+    app.update(snap);
+    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_email_idx".into()));
+
+    app.handle_key(key(KeyCode::Char('C')));
+    let Some(AppAction::WriteScript { path, sql }) = app.feedback.take_action() else {
+        panic!("expected a script to write");
+    };
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with("maintenance_rebuild_public.orders_email_idx_"));
+    assert!(name.ends_with(".sql"));
+    assert!(sql.contains("CREATE INDEX CONCURRENTLY orders_email_idx_new ON public.orders USING btree (email);"));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Index("public.orders_email_idx".into())));
+
+    // Tables get VACUUM, and only from the table overlay
+    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_email_idx".into()));
+    app.handle_key(key(KeyCode::Char('V')));
+    assert!(app.feedback.take_action().is_none());
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key(KeyCode::Char('V')));
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::WriteScript { sql, .. }) if sql == "VACUUM (VERBOSE, ANALYZE) public.users;\n"
+    ));
+}
+
+#[test]
+fn ctrl_v_runs_vacuum_only_with_allow_maintenance_and_after_confirming() {
+    use crate::maintenance::Maintenance;
+
+    let mut app = make_app_with_tables(&["users"]);
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key_ctrl(KeyCode::Char('v')));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.users".into())));
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Start with --allow-maintenance to run VACUUM from here")
+    );
+
+    app.allow_maintenance = true;
+    app.handle_key(key_ctrl(KeyCode::Char('a')));
+    let analyze = Maintenance::Analyze {
+        schema: "public".into(),
+        table: "users".into(),
+    };
+    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::RunMaintenance(analyze)));
+    app.handle_key(key(KeyCode::Esc));
+    assert!(app.feedback.take_action().is_none());
+
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key_ctrl(KeyCode::Char('v')));
+    app.handle_key(key(KeyCode::Char('y')));
+    let Some(AppAction::RunMaintenance(vacuum)) = app.feedback.take_action() else {
+        panic!("expected VACUUM to run");
+    };
+    assert_eq!(vacuum.describe(), "VACUUM public.users");
+
+    // One at a time
+    app.start_maintenance(vacuum);
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key_ctrl(KeyCode::Char('a')));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.users".into())));
+    assert_eq!(app.feedback.status_message.as_deref(), Some("VACUUM public.users is still running"));
+}
+
+#[test]
+fn ctrl_r_runs_reindex_only_where_it_can_be_concurrent() {
+    use crate::db::models::IndexInfo;
+
+    let mut app = make_app_with_tables(&["orders"]);
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.indexes = vec![IndexInfo {
+        schemaname: "public".into(),
+        table_name: "orders".into(),
+        index_name: "orders_pkey".into(),
+        index_size_bytes: 8192,
+        idx_scan: 1,
+        idx_tup_read: 1,
+        idx_tup_fetch: 1,
+        index_definition: "CREATE UNIQUE INDEX orders_pkey ON public.orders USING btree (id)".into(),
+        is_valid: true,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    }];
+    app.update(snap);
+    app.allow_maintenance = true;
+    let target = ViewMode::Inspect(InspectTarget::Index("public.orders_pkey".into()));
+
+    app.server_info.version = "PostgreSQL 11.9".into();
+    app.view_mode = target.clone();
+    app.handle_key(key_ctrl(KeyCode::Char('r')));
+    assert_eq!(app.view_mode, target);
+    assert!(app.feedback.status_message.as_deref().unwrap().starts_with("REINDEX blocks writes before PostgreSQL 12"));
+
+    app.server_info.version = "PostgreSQL 12.4".into();
+    app.handle_key(key_ctrl(KeyCode::Char('r')));
+    assert!(matches!(app.view_mode, ViewMode::Confirm(ConfirmAction::RunMaintenance(_))));
+}
+
+#[test]
+fn inspect_scroll_and_exit() {
+    let targets = [
+        InspectTarget::Query(12345),
+        InspectTarget::Index("public.test_idx".to_string()),
+        InspectTarget::Statement(123456789),
+        InspectTarget::Replication(12345),
+        InspectTarget::Table("public.test".to_string()),
+        InspectTarget::Blocking(12345),
+        InspectTarget::Vacuum(12345),
+        InspectTarget::Wraparound("testdb".to_string()),
+    ];
+    for target in targets {
+        let mut app = make_app();
+        app.view_mode = ViewMode::Inspect(target.clone());
+        app.overlay_scroll = 5;
+
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.overlay_scroll, 6, "Down should scroll in {target:?}");
+
+        app.handle_key(key(KeyCode::Char('k')));
+        assert_eq!(app.overlay_scroll, 5, "k should scroll up in {target:?}");
+
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.view_mode, ViewMode::Normal, "Esc should exit {target:?}");
+        assert_eq!(app.overlay_scroll, 0, "Overlay scroll should reset after {target:?}");
+    }
+}
+
+#[test]
+fn query_inspect_kill_opens_confirm() {
+    let mut app = make_app();
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    app.handle_key(key(KeyCode::Char('K')));
+    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::Kill(12345)));
+}
+
+#[test]
+fn query_inspect_cancel_opens_confirm() {
+    let mut app = make_app();
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    app.handle_key(key(KeyCode::Char('C')));
+    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::Cancel(12345)));
+}
+
+#[test]
+fn query_inspect_kill_disabled_in_replay_mode() {
+    let mut app = make_app();
+    app.replay = Some(crate::app::ReplayState::new("test.jsonl".to_string(), 10));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    app.handle_key(key(KeyCode::Char('K')));
+    // Should stay in inspect mode, not open confirm
+    assert!(matches!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12345))));
+}
+
+/// 12345 waits on a lock held by 12399
+fn make_app_with_lock_wait() -> App {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    let mut blocker = snap.active_queries[0].clone();
+    blocker.pid = 12399;
+    blocker.state = Some("idle in transaction".into());
+    snap.active_queries.push(blocker);
+    snap.blocking_info = vec![crate::db::models::BlockingInfo {
+        blocked_pid: 12345,
+        blocked_user: None,
+        blocked_query: None,
+        blocked_duration_secs: 1.0,
+        blocker_pid: 12399,
+        blocker_user: None,
+        blocker_query: None,
+        blocker_state: Some("idle in transaction".into()),
+    }];
+    app.update(snap);
+    app
+}
+
+#[test]
+fn queries_o_toggles_the_expanded_row() {
+    let mut app = make_app_with_lock_wait();
+    app.panels.queries.state.select(Some(0));
+    let pid = app.selected_query_pid();
+
+    app.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(app.expanded_query, pid);
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(app.expanded_query, app.selected_query_pid());
+    assert_ne!(app.expanded_query, pid);
+    app.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(app.expanded_query, None);
+}
+
+#[test]
+fn shift_arrows_scroll_the_expanded_query() {
+    let mut app = make_app_with_lock_wait();
+    app.panels.queries.state.select(Some(0));
+    let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+
+    // Nothing expanded: nothing to scroll
+    app.handle_key(shift(KeyCode::Down));
+    assert_eq!(app.expanded_scroll, 0);
+
+    app.handle_key(key(KeyCode::Char('o')));
+    app.handle_key(shift(KeyCode::Down));
+    app.handle_key(shift(KeyCode::Down));
+    app.handle_key(shift(KeyCode::Up));
+    assert_eq!(app.expanded_scroll, 1);
+    assert_eq!(app.panels.queries.state.selected(), Some(0));
+
+    // Collapsing and expanding again starts from the top
+    app.handle_key(key(KeyCode::Char('o')));
+    app.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(app.expanded_scroll, 0);
+}
+
+#[test]
+fn query_inspect_b_jumps_to_blocker() {
+    let mut app = make_app_with_lock_wait();
+    assert_eq!(app.blockers_of(12345), vec![12399]);
+    app.panels.queries.state.select(Some(0));
+    app.handle_key(key(KeyCode::Enter));
+    assert!(matches!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12345))));
+
+    app.handle_key(key(KeyCode::Char('b')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(app.selected_query_pid(), Some(12399));
+}
+
+#[test]
+fn query_inspect_b_reports_blocker_outside_list() {
+    let mut app = make_app_with_lock_wait();
+    if let Some(snap) = app.snapshot.as_mut() {
+        snap.active_queries.retain(|q| q.pid != 12399);
+    }
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    app.handle_key(key(KeyCode::Char('b')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Blocker PID 12399 is not in the Queries list")
+    );
+}
+
+fn lock(pid: i32, mode: &str, relation: &str, granted: bool, blocking_pids: Vec<i32>) -> LockInfo {
+    LockInfo {
+        pid,
+        locktype: "relation".into(),
+        mode: mode.into(),
+        granted,
+        relation: Some(relation.into()),
+        relation_oid: None,
+        usename: None,
+        query: None,
+        state: None,
+        duration_secs: if granted { 60.0 } else { 5.0 },
+        blocking_pids,
+    }
+}
+
+fn make_app_with_locks() -> App {
+    let mut app = make_app_with_lock_wait();
+    if let Some(snap) = app.snapshot.as_mut() {
+        snap.locks = vec![
+            lock(12399, "RowExclusiveLock", "public.orders", true, vec![]),
+            lock(12345, "AccessShareLock", "public.users", true, vec![]),
+            lock(12345, "AccessExclusiveLock", "public.orders", false, vec![12399]),
+        ];
+    }
+    app.switch_panel(BottomPanel::Locks);
+    app
+}
+
+#[test]
+fn locks_list_waiting_first_and_find_waiters() {
+    let app = make_app_with_locks();
+    let snap = app.snapshot.as_ref().unwrap();
+    let order: Vec<(i32, bool)> = app
+        .sorted_lock_indices()
+        .iter()
+        .map(|&i| (snap.locks[i].pid, snap.locks[i].granted))
+        .collect();
+    assert_eq!(order, vec![(12345, false), (12399, true), (12345, true)]);
+
+    assert_eq!(app.lock_waiters(0), vec![12345]);
+    assert!(app.lock_waiters(1).is_empty());
+    assert!(app.lock_waiters(2).is_empty());
+}
+
+#[test]
+fn locks_enter_inspects_the_holding_query() {
+    let mut app = make_app_with_locks();
+    assert_eq!(app.selected_lock_holder(), Some(12399));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12399)));
+
+    app.view_mode = ViewMode::Normal;
+    if let Some(snap) = app.snapshot.as_mut() {
+        snap.active_queries.retain(|q| q.pid != 12399);
+    }
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Holder PID 12399 is not in the Queries list")
+    );
+}
+
+#[test]
+fn locks_sort_and_filter() {
+    let mut app = make_app_with_locks();
+    app.handle_key(key(KeyCode::Char('s')));
+    assert_eq!(app.panels.locks.sort_column, LockSortColumn::Duration);
+    let snap = app.snapshot.as_ref().unwrap();
+    assert!(!snap.locks[app.sorted_lock_indices()[2]].granted);
+
+    app.filter_mut().text = "public.users".into();
+    app.filter_mut().active = true;
+    assert_eq!(app.sorted_lock_indices(), vec![1]);
+}
+
+fn group(user: &str, application: &str, total: i64, idle_in_transaction: i64) -> ConnectionGroup {
+    ConnectionGroup {
+        usename: Some(user.into()),
+        datname: Some("testdb".into()),
+        application_name: application.into(),
+        total,
+        active: total - idle_in_transaction,
+        idle: 0,
+        idle_in_transaction,
+        waiting: 0,
+        longest_secs: 1.0,
+    }
+}
+
+fn make_app_with_connection_groups() -> App {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    let mut worker = snap.active_queries[0].clone();
+    worker.pid = 12400;
+    worker.usename = Some("worker".into());
+    worker.application_name = Some("sidekiq".into());
+    snap.active_queries.push(worker);
+    snap.connection_groups = vec![
+        group("postgres", "", 1, 0),
+        group("worker", "sidekiq", 3, 2),
+    ];
+    app.update(snap);
+    app.switch_panel(BottomPanel::ConnectionGroups);
+    app
+}
+
+#[test]
+fn connection_groups_sort_by_size_then_by_idle_transactions() {
+    let mut app = make_app_with_connection_groups();
+    assert_eq!(app.sorted_connection_group_indices(), vec![1, 0]);
+    app.handle_key(key(KeyCode::Char('s')));
+    app.handle_key(key(KeyCode::Char('s')));
+    assert_eq!(
+        app.panels.connection_groups.sort_column,
+        ConnectionGroupSortColumn::IdleInTransaction
+    );
+    assert_eq!(app.sorted_connection_group_indices(), vec![1, 0]);
+
+    app.filter_mut().text = "sidekiq".into();
+    app.filter_mut().active = true;
+    assert_eq!(app.sorted_connection_group_indices(), vec![1]);
+}
+
+#[test]
+fn connection_group_enter_narrows_the_queries_list() {
+    let mut app = make_app_with_connection_groups();
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+    assert_eq!(app.selected_query_pid(), Some(12400));
+    assert_eq!(app.sorted_query_indices().len(), 1);
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Queries from worker@testdb · sidekiq; Backspace shows all (1 of 3 listed, a fetches all)")
+    );
+
+    app.handle_key(key(KeyCode::Backspace));
+    assert!(app.query_scope.is_none());
+    assert_eq!(app.sorted_query_indices().len(), 2);
+}
+
+#[test]
+fn wait_event_enter_lists_its_backends_and_narrows_queries() {
+    use crate::db::models::WaitEventCount;
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.active_queries[0].backend_type = Some("client backend".into());
+    let mut waiting = snap.active_queries[0].clone();
+    waiting.pid = 12400;
+    waiting.duration_secs = 30.0;
+    waiting.wait_event_type = Some("Lock".into());
+    waiting.wait_event = Some("transactionid".into());
+    snap.active_queries.push(waiting.clone());
+    // Idle in a transaction: not counted even while waiting on the client
+    let mut idle = waiting;
+    idle.pid = 12401;
+    idle.state = Some("idle in transaction".into());
+    snap.active_queries.push(idle);
+    snap.wait_events = vec![
+        WaitEventCount {
+            wait_event_type: "CPU/Running".into(),
+            wait_event: "CPU/Running".into(),
+            count: 1,
+        },
+        WaitEventCount {
+            wait_event_type: "Lock".into(),
+            wait_event: "transactionid".into(),
+            count: 1,
+        },
+    ];
+    app.update(snap);
+    app.switch_panel(BottomPanel::WaitEvents);
+
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(
+        app.view_mode,
+        ViewMode::Inspect(InspectTarget::WaitEvent("Lock".into(), "transactionid".into()))
+    );
+    let pids: Vec<i32> = app.wait_event_backends("Lock", "transactionid").iter().map(|q| q.pid).collect();
+    assert_eq!(pids, vec![12400]);
+
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(app.bottom_panel, BottomPanel::Queries);
+    assert_eq!(app.sorted_query_indices().len(), 1);
+    assert_eq!(app.selected_query_pid(), Some(12400));
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Queries waiting on Lock:transactionid; Backspace shows all")
+    );
+
+    app.handle_key(key(KeyCode::Backspace));
+    assert!(app.query_scope.is_none());
+    assert_eq!(app.sorted_query_indices().len(), 3);
+
+    let pids: Vec<i32> = app.wait_event_backends("CPU/Running", "CPU/Running").iter().map(|q| q.pid).collect();
+    assert_eq!(pids, vec![12345]);
+}
+
+fn slot(name: &str, slot_type: &str, active: bool, retained: i64) -> ReplicationSlot {
+    ReplicationSlot {
+        slot_name: name.into(),
+        slot_type: slot_type.into(),
+        database: (slot_type == "logical").then(|| "app".into()),
+        active,
+        restart_lsn: Some("0/4000000".into()),
+        confirmed_flush_lsn: None,
+        wal_retained_bytes: Some(retained),
+        temporary: false,
+        spill_txns: None,
+        spill_count: None,
+        spill_bytes: None,
+    }
+}
+
+fn make_app_with_slots() -> App {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    if let Some(snap) = app.snapshot.as_mut() {
+        snap.replication_slots = vec![
+            slot("replica1", "physical", true, 16 << 20),
+            slot("old_cdc", "logical", false, 40 << 30),
+            slot("debezium", "logical", true, 1 << 20),
+        ];
+    }
+    app.switch_panel(BottomPanel::Slots);
+    app
+}
+
+#[test]
+fn slots_sort_by_retained_wal_and_filter() {
+    let mut app = make_app_with_slots();
+    assert_eq!(app.sorted_slot_indices(), vec![1, 0, 2]);
+    assert_eq!(app.selected_slot().map(|s| s.slot_name.as_str()), Some("old_cdc"));
+
+    // Slot names sort A-Z
+    for _ in 0..3 {
+        app.handle_key(key(KeyCode::Char('s')));
+    }
+    assert_eq!(app.panels.slots.sort_column, SlotSortColumn::Name);
+    assert_eq!(app.sorted_slot_indices(), vec![2, 1, 0]);
+
+    app.filter_mut().text = "logical".into();
+    app.filter_mut().active = true;
+    assert_eq!(app.sorted_slot_indices(), vec![2, 1]);
+}
+
+#[test]
+fn slots_enter_inspects_and_x_drops_only_inactive_slots() {
+    let mut app = make_app_with_slots();
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Slot("old_cdc".into())));
+    app.handle_key(key(KeyCode::Esc));
+
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::DropSlot("old_cdc".into())));
+    app.handle_key(key(KeyCode::Char('y')));
+    assert!(matches!(
+        app.feedback.pending_action,
+        Some(AppAction::DropReplicationSlot(ref name)) if name == "old_cdc"
+    ));
+
+    app.feedback.pending_action = None;
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app.feedback.pending_action.is_none());
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("Slot replica1 is in use; stop its consumer before dropping it")
+    );
+}
+
+#[test]
+fn slots_cannot_be_dropped_in_replay() {
+    let mut app = make_replay_app();
+    app.update(make_snapshot());
+    if let Some(snap) = app.snapshot.as_mut() {
+        snap.replication_slots = vec![slot("old_cdc", "logical", false, 1 << 30)];
+    }
+    app.switch_panel(BottomPanel::Slots);
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn queries_a_toggles_all_backends_and_refreshes() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+
+    app.handle_key(key(KeyCode::Char('a')));
+    assert!(app.show_all_backends);
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::ForceRefresh)));
+
+    app.handle_key(key(KeyCode::Char('a')));
+    assert!(!app.show_all_backends);
+}
+
+#[test]
+fn queries_g_groups_backends_by_fingerprint() {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    for (pid, id, duration_secs) in [(201, 1, 3.0), (202, 2, 40.0)] {
+        let mut query = long_txn(pid, "app", 0, "active");
+        query.query = Some(format!("SELECT * FROM orders WHERE id = {id}"));
+        query.duration_secs = duration_secs;
+        snap.active_queries.push(query);
+    }
+    app.update(snap);
+    assert_eq!(app.sorted_query_indices().len(), 3);
+
+    app.handle_key(key(KeyCode::Char('f')));
+    assert!(app.group_by_fingerprint);
+    assert_eq!(app.sorted_query_indices().len(), 2);
+    let groups = app.fingerprint_groups();
+    let orders = groups.iter().find(|g| g.count == 2).unwrap();
+    assert_eq!(orders.fingerprint, "SELECT * FROM orders WHERE id = ?");
+    // The row stands for the longest-running member
+    let row = app.sorted_query_indices().iter().position(|&i| i == orders.index).unwrap();
+    app.panels.queries.state.select(Some(row));
+    assert_eq!(app.selected_query_pid(), Some(202));
+    // Batch actions still reach every member
+    assert_eq!(app.get_filtered_pids().len(), 3);
+
+    app.handle_key(key(KeyCode::Char('f')));
+    assert!(!app.group_by_fingerprint);
+    assert_eq!(app.sorted_query_indices().len(), 3);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Timeout policy
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn timeout_setting(name: &str, setting: &str) -> crate::db::models::PgSetting {
+    crate::db::models::PgSetting {
+        name: name.into(),
+        setting: setting.into(),
+        unit: Some("ms".into()),
+        category: "Client Connection Defaults / Statement Behavior".into(),
+        short_desc: None,
+        context: "user".into(),
+        source: "default".into(),
+        pending_restart: false,
+        boot_val: None,
+        reset_val: None,
+    }
+}
+
+fn long_txn(pid: i32, role: &str, age_secs: i64, state: &str) -> ActiveQuery {
+    let now = Utc::now();
+    ActiveQuery {
+        pid,
+        usename: Some(role.into()),
+        datname: Some("postgres".into()),
+        state: Some(state.into()),
+        query: Some("SELECT 1".into()),
+        duration_secs: 1.0,
+        wait_event_type: None,
+        wait_event: None,
+        query_start: Some(now),
+        backend_type: None,
+        xact_start: Some(now - chrono::Duration::seconds(age_secs)),
+        query_id: None,
+        application_name: None,
+        state_change: None,
+    }
+}
+
+fn make_timeout_inputs() -> (ServerInfo, PgSnapshot) {
+    let mut server = make_server_info();
+    server.settings = vec![
+        timeout_setting("idle_in_transaction_session_timeout", "0"),
+        timeout_setting("lock_timeout", "0"),
+        timeout_setting("statement_timeout", "0"),
+    ];
+    let mut snap = make_snapshot();
+    snap.active_queries = vec![
+        long_txn(1, "app", 400, "idle in transaction"),
+        long_txn(2, "Reporting", 900, "active"),
+        long_txn(3, "app", 10, "active"),
+    ];
+    (server, snap)
+}
+
+#[test]
+fn timeout_advisory_flags_long_transactions_without_timeouts() {
+    use crate::app::timeouts::TimeoutAdvisory;
+
+    let (server, snap) = make_timeout_inputs();
+    let advisory = TimeoutAdvisory::build(&server, Some(&snap), "postgres");
+
+    assert!(advisory.needs_attention());
+    let pids: Vec<i32> = advisory.long_transactions.iter().map(|t| t.pid).collect();
+    assert_eq!(pids, [2, 1]);
+    assert!(advisory.long_transactions[1].idle);
+    assert_eq!(
+        advisory.suggestions,
+        [
+            "ALTER ROLE \"Reporting\" SET statement_timeout = '30s';",
+            "ALTER ROLE app SET statement_timeout = '30s';",
+            "ALTER DATABASE postgres SET idle_in_transaction_session_timeout = '5min';",
+            "ALTER ROLE \"Reporting\" SET lock_timeout = '10s';",
+            "ALTER ROLE app SET lock_timeout = '10s';",
+        ]
+    );
+}
+
+fn idle_txn(pid: i32, state: &str, duration_secs: f64) -> ActiveQuery {
+    ActiveQuery {
+        duration_secs,
+        ..long_txn(pid, "app", 0, state)
+    }
+}
+
+#[test]
+fn idle_txn_watchdog_lists_offenders_longest_first() {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.active_queries = vec![
+        idle_txn(1, "idle in transaction", 400.0),
+        idle_txn(2, "active", 900.0),
+        idle_txn(3, "idle in transaction (aborted)", 1200.0),
+        idle_txn(4, "idle in transaction", 30.0),
+    ];
+    app.update(snap);
+    assert!(app.idle_txn_offenders().is_empty(), "the watchdog is off by default");
+
+    app.config.idle_txn_watchdog_secs = 300;
+    let pids: Vec<i32> = app.idle_txn_offenders().iter().map(|(q, _)| q.pid).collect();
+    assert_eq!(pids, [3, 1]);
+
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Confirm(ConfirmAction::KillBatch(vec![3, 1])));
+    app.handle_key(key(KeyCode::Char('y')));
+    assert!(matches!(
+        &app.feedback.pending_action,
+        Some(AppAction::TerminateBackends(pids)) if pids == &vec![3, 1]
+    ));
+}
+
+#[test]
+fn idle_txn_watchdog_counts_from_the_last_state_change() {
+    let mut app = make_app();
+    app.config.idle_txn_watchdog_secs = 300;
+    let mut snap = make_snapshot();
+    let idle_since = |secs| Some(snap.timestamp - chrono::Duration::seconds(secs));
+    // The last query started long ago, but the sessions only went idle later
+    snap.active_queries = vec![
+        ActiveQuery { state_change: idle_since(299), ..idle_txn(1, "idle in transaction", 5000.0) },
+        ActiveQuery { state_change: idle_since(300), ..idle_txn(2, "idle in transaction", 5000.0) },
+        ActiveQuery { state_change: idle_since(301), ..idle_txn(3, "idle in transaction (aborted)", 5000.0) },
+    ];
+    app.update(snap);
+    let offenders: Vec<(i32, f64)> = app.idle_txn_offenders().iter().map(|(q, idle)| (q.pid, *idle)).collect();
+    assert_eq!(offenders, [(3, 301.0), (2, 300.0)]);
+}
+
+#[test]
+fn idle_txn_watchdog_key_explains_when_nothing_to_do() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app.feedback.status_message.as_deref().is_some_and(|m| m.contains("watchdog is off")));
+
+    app.config.idle_txn_watchdog_secs = 3600;
+    app.handle_key(key(KeyCode::Char('X')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app.feedback.status_message.as_deref().is_some_and(|m| m.starts_with("No sessions")));
+}
+
+#[test]
+fn config_adjust_idle_txn_watchdog_steps() {
+    let mut app = make_app();
+    app.view_mode = ViewMode::Config;
+    app.config_overlay.selected = ConfigItem::ALL
+        .iter()
+        .position(|&i| i == ConfigItem::IdleTxnWatchdog)
+        .unwrap();
+
+    app.config_adjust(-1);
+    assert_eq!(app.config.idle_txn_watchdog_secs, 0, "Off is the bottom");
+    app.config_adjust(1);
+    assert_eq!(app.config.idle_txn_watchdog_secs, 30);
+    // A hand-edited value joins the ladder at the next step up
+    app.config.idle_txn_watchdog_secs = 400;
+    app.config_adjust(1);
+    assert_eq!(app.config.idle_txn_watchdog_secs, 1800);
+    app.config_adjust(1);
+    app.config_adjust(1);
+    assert_eq!(app.config.idle_txn_watchdog_secs, 3600);
+}
+
+#[test]
+fn config_adjust_notifications_cycle() {
+    use crate::notifications::NotifyVia;
+
+    let mut app = make_app();
+    app.view_mode = ViewMode::Config;
+    app.config_overlay.selected = ConfigItem::ALL
+        .iter()
+        .position(|&i| i == ConfigItem::BlockingNotify)
+        .unwrap();
+
+    app.config_adjust(1);
+    assert_eq!(app.config.notifications.blocking, NotifyVia::Bell);
+    app.config_adjust(-1);
+    app.config_adjust(-1);
+    assert_eq!(app.config.notifications.blocking, NotifyVia::Both);
+    assert_eq!(app.config.notifications.long_query, NotifyVia::Off);
+}
+
+#[test]
+fn long_queries_queue_notifications_only_when_live() {
+    use crate::notifications::{NotifyEvent, NotifyVia};
+
+    let mut snap = make_snapshot();
+    snap.active_queries[0].duration_secs = 120.0;
+
+    let mut app = make_app();
+    app.config.notifications.long_query = NotifyVia::Bell;
+    let mut prep = SnapshotPrep::default();
+    prep.watch(&app.prep_watch());
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+    let queued = app.feedback.take_notifications();
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].event, NotifyEvent::LongQuery);
+    assert!(queued[0].body.starts_with("PID 12345 (postgres@testdb)"));
+
+    let mut app = make_replay_app();
+    app.config.notifications.long_query = NotifyVia::Bell;
+    let mut prep = SnapshotPrep::default();
+    prep.watch(&app.prep_watch());
+    app.apply_prepared(prep.prepare(snap, false));
+    assert!(app.feedback.take_notifications().is_empty());
+}
+
+#[test]
+fn logs_panel_keeps_recent_lines_and_filters_by_level() {
+    use crate::db::models::{LogLevel, LogLine};
+    use crate::server_log::{LevelFilter, PANEL_LINES};
+
+    let line = |level: LogLevel, message: &str| LogLine {
+        prefix: "[7]".into(),
+        level,
+        message: message.into(),
+    };
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.server_log = vec![
+        line(LogLevel::Log, "checkpoint starting"),
+        line(LogLevel::Error, "duplicate key"),
+        line(LogLevel::Statement, "INSERT INTO t VALUES (1)"),
+    ];
+    app.update(snap.clone());
+    app.update(snap.clone());
+    assert_eq!(app.server_log.len(), 6);
+
+    app.bottom_panel = BottomPanel::Logs;
+    assert_eq!(app.log_indices(), vec![4, 5, 3, 1, 2, 0], "newest message first");
+    app.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(app.log_filter, LevelFilter::Warnings);
+    assert_eq!(app.log_indices(), vec![4, 5, 1, 2]);
+    assert_eq!(app.feedback.status_message.as_deref(), Some("Logs: warnings and up"));
+
+    snap.server_log = vec![line(LogLevel::Log, "noise"); PANEL_LINES];
+    app.update(snap);
+    assert_eq!(app.server_log.len(), PANEL_LINES);
+    assert_eq!(app.server_log[0].message, "noise");
+}
+
+#[test]
+fn adaptive_refresh_follows_load_only_when_live_and_enabled() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    assert_eq!(app.effective_refresh_secs(), 2);
+
+    app.view_mode = ViewMode::Config;
+    app.config_overlay.selected = ConfigItem::ALL
+        .iter()
+        .position(|&i| i == ConfigItem::AdaptiveRefresh)
+        .unwrap();
+    app.config_adjust(1);
+    assert!(app.config.adaptive_refresh.enabled);
+
+    // One active query and no TPS yet: quiet
+    app.update(make_snapshot());
+    app.update(make_snapshot());
+    assert_eq!(app.effective_refresh_secs(), 5);
+
+    let mut busy = make_snapshot();
+    busy.summary.active_query_count = 20;
+    app.update(busy);
+    assert_eq!(app.effective_refresh_secs(), 1);
+
+    app.config_adjust(1);
+    assert_eq!(app.effective_refresh_secs(), 2);
+
+    let mut replay = make_replay_app();
+    replay.config.adaptive_refresh.enabled = true;
+    replay.update(make_snapshot());
+    assert_eq!(replay.effective_refresh_secs(), replay.refresh_interval_secs);
+}
+
+#[test]
+fn timeout_advisory_respects_settings_and_overrides() {
+    use crate::app::timeouts::TimeoutAdvisory;
+    use crate::db::models::RoleSetting;
+
+    let (mut server, snap) = make_timeout_inputs();
+    server.role_settings = vec![
+        RoleSetting {
+            role: Some("app".into()),
+            database: None,
+            name: "statement_timeout".into(),
+            value: "15s".into(),
+        },
+        RoleSetting {
+            role: None,
+            database: Some("postgres".into()),
+            name: "idle_in_transaction_session_timeout".into(),
+            value: "10min".into(),
+        },
+    ];
+    let advisory = TimeoutAdvisory::build(&server, Some(&snap), "postgres");
+    assert_eq!(advisory.timeouts[0].overrides.len(), 1);
+    assert!(!advisory
+        .suggestions
+        .iter()
+        .any(|s| s.contains("app SET statement_timeout") || s.contains("DATABASE")));
+
+    // One server-wide timeout is enough to drop the warning
+    server.settings[2].setting = "60000".into();
+    let advisory = TimeoutAdvisory::build(&server, Some(&snap), "postgres");
+    assert!(!advisory.needs_attention());
+    assert_eq!(advisory.timeouts[0].value, "1min");
+    assert!(!advisory.suggestions.iter().any(|s| s.contains("statement_timeout")));
+}
+
+#[test]
+fn shift_t_opens_timeout_policy() {
+    let mut app = make_app();
+    app.handle_key(key(KeyCode::Char('T')));
+    assert_eq!(app.view_mode, ViewMode::Timeouts);
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn shift_d_opens_collector_stats_in_live_mode_only() {
+    let mut app = make_app();
+    app.handle_key(key(KeyCode::Char('D')));
+    assert_eq!(app.view_mode, ViewMode::Collector);
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+
+    let mut app = make_replay_app();
+    app.handle_key(key(KeyCode::Char('D')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Settings review
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn changed_setting(name: &str, setting: &str, boot_val: &str, context: &str) -> crate::db::models::PgSetting {
+    crate::db::models::PgSetting {
+        name: name.into(),
+        setting: setting.into(),
+        unit: None,
+        category: "Test".into(),
+        short_desc: None,
+        context: context.into(),
+        source: if setting == boot_val { "default" } else { "configuration file" }.into(),
+        pending_restart: false,
+        boot_val: Some(boot_val.into()),
+        reset_val: Some(setting.into()),
+    }
+}
+
+#[test]
+fn settings_review_flags_risky_values_and_lists_changes() {
+    use crate::app::settings_review::{Risk, SettingsReview};
+
+    let mut app = make_app();
+    app.server_info.settings = vec![
+        changed_setting("enable_seqscan", "on", "on", "user"),
+        changed_setting("fsync", "off", "on", "sighup"),
+        changed_setting("shared_buffers", "16384", "16384", "postmaster"),
+        changed_setting("work_mem", "1048576", "4096", "user"),
+        changed_setting("wal_level", "logical", "replica", "postmaster"),
+    ];
+    // Set in the config file to the default: not a change
+    app.server_info.settings[0].source = "configuration file".into();
+
+    let review = SettingsReview::build(&app.server_info);
+    let risky: Vec<(&str, Risk)> = review.risky.iter().map(|r| (r.name, r.risk)).collect();
+    assert_eq!(
+        risky,
+        vec![("fsync", Risk::Danger), ("work_mem", Risk::Warn), ("shared_buffers", Risk::Warn)]
+    );
+    let changed: Vec<&str> = review.changed.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(changed, vec!["wal_level", "fsync", "work_mem"]);
+    assert_eq!(review.changed[0].default, "replica");
+    assert!(review.has_danger());
+}
+
+#[test]
+fn settings_d_shows_only_changed_and_o_opens_the_review() {
+    let mut app = make_app();
+    app.server_info.settings = vec![
+        changed_setting("enable_seqscan", "on", "on", "user"),
+        changed_setting("work_mem", "65536", "4096", "user"),
+    ];
+    app.switch_panel(BottomPanel::Settings);
+    assert_eq!(app.sorted_settings_indices(), vec![0, 1]);
+
+    app.handle_key(key(KeyCode::Char('d')));
+    assert!(app.settings_changed_only);
+    assert_eq!(app.sorted_settings_indices(), vec![1]);
+    app.handle_key(key(KeyCode::Char('d')));
+    assert_eq!(app.sorted_settings_indices(), vec![0, 1]);
+
+    app.handle_key(key(KeyCode::Char('o')));
+    assert_eq!(app.view_mode, ViewMode::SettingsReview);
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Alerts
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn alert_fires_on_update_and_is_seen_in_panel() {
+    use crate::alerts::{AlertMetric, AlertRule};
+    use crate::db::models::BlockingInfo;
+
+    let mut app = make_app();
+    app.config.alerts = vec![AlertRule {
+        name: "Blocked".into(),
+        metric: AlertMetric::BlockedQueries,
+        above: Some(0.0),
+        below: None,
+        for_secs: 0,
+    }];
+    let mut snap = make_snapshot();
+    snap.blocking_info = vec![BlockingInfo {
+        blocked_pid: 12345,
+        blocked_user: None,
+        blocked_query: None,
+        blocked_duration_secs: 3.0,
+        blocker_pid: 999,
+        blocker_user: None,
+        blocker_query: None,
+        blocker_state: None,
+    }];
+    app.update(snap);
+    assert_eq!(app.alerts.active_count(), 1);
+    assert_eq!(app.feedback.status_message.as_deref(), Some("Alert: Blocked (1)"));
+    assert!(app.alerts.unseen);
+
+    app.handle_key(key(KeyCode::Char('!')));
+    assert_eq!(app.bottom_panel, BottomPanel::Alerts);
+    assert!(!app.alerts.unseen);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// EXPLAIN
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn e_explains_selected_query() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.panels.queries.state.select(Some(0));
+    app.handle_key(key(KeyCode::Char('e')));
+    assert_eq!(app.view_mode, ViewMode::Explain);
+    assert_eq!(app.explain, Some(ExplainView::Loading("SELECT 1".into())));
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::Explain(q)) if q == "SELECT 1"
+    ));
+}
+
+#[test]
+fn e_explains_selected_statement() {
+    let mut app = make_app_with_statement(42);
+    app.handle_key(key(KeyCode::Char('e')));
+    assert_eq!(app.view_mode, ViewMode::Explain);
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::Explain(q)) if q == "SELECT * FROM report"
+    ));
+}
+
+#[test]
+fn explain_without_query_text_stays_closed() {
+    let mut app = make_app();
+    app.handle_key(key(KeyCode::Char('e')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app.feedback.pending_action.is_none());
+    assert!(app.feedback.status_message.is_some());
+}
+
+#[test]
+fn explain_error_keeps_query_text() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.panels.queries.state.select(Some(0));
+    app.handle_key(key(KeyCode::Char('e')));
+    app.set_explain_result(Err("syntax error".into()));
+    assert_eq!(
+        app.explain,
+        Some(ExplainView::Failed {
+            query: "SELECT 1".into(),
+            error: "syntax error".into()
+        })
+    );
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn h_asks_hypopg_about_the_selected_statement() {
+    let mut app = make_app_with_statement(42);
+    app.handle_key(key(KeyCode::Char('h')));
+    assert_eq!(app.view_mode, ViewMode::Normal, "nothing to ask without hypopg");
+    assert!(app.feedback.status_message.as_deref().is_some_and(|m| m.contains("hypopg")));
+
+    app.server_info.extensions.hypopg = true;
+    app.handle_key(key(KeyCode::Char('h')));
+    assert_eq!(app.view_mode, ViewMode::IndexAdvice);
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::AdviseIndexes(q)) if q == "SELECT * FROM report"
+    ));
+
+    app.set_index_advice_result(Err("permission denied".into()));
+    assert_eq!(
+        app.index_advice,
+        Some(IndexAdviceView::Failed {
+            query: "SELECT * FROM report".into(),
+            error: "permission denied".into()
+        })
+    );
+    // A late answer after the overlay moved on is dropped
+    app.set_index_advice_result(Err("late".into()));
+    assert!(matches!(app.index_advice, Some(IndexAdviceView::Failed { ref error, .. }) if error == "permission denied"));
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Connection switcher
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn explaining_again_keeps_plan_history_and_logs_flips() {
+    use crate::db::models::QueryPlan;
+    let plan = |node: &str| {
+        let json = format!(
+            r#"[{{"Plan": {{"Node Type": "{node}", "Relation Name": "report", "Total Cost": 10.0}}}}]"#
+        );
+        QueryPlan::parse("SELECT * FROM report", &json, false).unwrap()
+    };
+
+    let mut app = make_app_with_statement(42);
+    for node in ["Seq Scan", "Seq Scan", "Index Scan"] {
+        app.handle_key(key(KeyCode::Char('e')));
+        app.set_explain_result(Ok(plan(node)));
+        app.handle_key(key(KeyCode::Esc));
+    }
+    assert_eq!(app.explain_history().len(), 3);
+
+    let flips: Vec<_> = app
+        .feedback
+        .ui_events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            UiEventKind::PlanChanged { statement, summary } => Some((statement.as_str(), summary.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        flips,
+        [("queryid 42", "-Seq Scan on report, +Index Scan on report, cost 10.00 → 10.00")]
+    );
+}
+
+#[test]
+fn ctrl_n_with_single_connection_explains() {
+    let mut app = make_app();
+    app.handle_key(key_ctrl(KeyCode::Char('n')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(app
+        .feedback
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.contains("--target")));
+}
+
+#[test]
+fn connection_switcher_requests_switch() {
+    let mut app = make_app();
+    app.switcher.entries = ["a", "b"]
+        .iter()
+        .map(|label| crate::app::ConnectionEntry {
+            label: (*label).into(),
+            status: String::new(),
+        })
+        .collect();
+
+    app.handle_key(key_ctrl(KeyCode::Char('n')));
+    assert_eq!(app.view_mode, ViewMode::Connections);
+
+    // Picking the current connection just closes
+    app.handle_key(key(KeyCode::Enter));
+    assert!(app.feedback.take_action().is_none());
+
+    app.handle_key(key_ctrl(KeyCode::Char('n')));
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SwitchConnection(1))));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel-specific navigation
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn queries_panel_navigation() {
+    let mut app = make_app();
+    app.panels.queries.state.select(Some(5));
+    app.handle_key(key(KeyCode::Up));
+    assert_eq!(app.panels.queries.selected(), Some(4));
 
     app.handle_key(key(KeyCode::Char('k')));
     assert_eq!(app.panels.queries.selected(), Some(3));
@@ -670,6 +2423,116 @@ fn indexes_panel_bloat_disabled_in_replay() {
     assert!(!app.feedback.bloat_loading);
 }
 
+#[test]
+fn backends_enter_on_parallel_worker_inspects_its_leader() {
+    use crate::db::models::BackgroundWorker;
+
+    let mut app = make_app();
+    let worker = |pid, backend_type: &str, leader_pid| BackgroundWorker {
+        pid,
+        backend_type: backend_type.into(),
+        datname: None,
+        state: Some("active".into()),
+        wait_event_type: None,
+        wait_event: None,
+        duration_secs: 1.0,
+        query: None,
+        leader_pid,
+    };
+    let mut snap = make_snapshot();
+    snap.background_workers = vec![
+        worker(200, "autovacuum worker", None),
+        worker(201, "parallel worker", Some(12345)),
+    ];
+    app.update(snap);
+    app.bottom_panel = BottomPanel::Backends;
+
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("PID 200 is a autovacuum worker with no leader query")
+    );
+
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(12345)));
+}
+
+#[test]
+fn sessions_panel_sorts_and_inspects_sampled_sessions() {
+    let mut app = make_app();
+    let start = Utc::now();
+    let mut idle = make_snapshot().active_queries[0].clone();
+    idle.pid = 222;
+    idle.state = Some("idle in transaction".into());
+    for secs in [0, 2, 4] {
+        let mut snap = make_snapshot();
+        snap.timestamp = start + chrono::Duration::seconds(secs);
+        snap.active_queries.push(idle.clone());
+        app.update(snap);
+    }
+    app.bottom_panel = BottomPanel::Sessions;
+
+    let rows = app.session_rows();
+    assert_eq!(rows.iter().map(|r| r.pid).collect::<Vec<_>>(), vec![12345, 222]);
+    assert_eq!(rows[0].active_secs, 4.0);
+    assert_eq!(rows[1].idle_in_txn_secs, 4.0);
+
+    app.handle_key(key(KeyCode::Char('s')));
+    assert_eq!(app.panels.sessions.sort_column, SessionSortColumn::IdleInTxnTime);
+    assert_eq!(app.session_rows()[0].pid, 222);
+
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(222)));
+}
+
+#[test]
+fn wal_dir_past_max_wal_size_warns_once() {
+    use crate::db::models::{PgSetting, WalDirStats};
+
+    let mut app = make_app();
+    app.server_info.settings.push(PgSetting {
+        name: "max_wal_size".into(),
+        setting: "1024".into(),
+        unit: Some("MB".into()),
+        category: "Write-Ahead Log / Checkpoints".into(),
+        short_desc: None,
+        context: "sighup".into(),
+        source: "default".into(),
+        pending_restart: false,
+        boot_val: None,
+        reset_val: None,
+    });
+    let with_wal_dir = |bytes: i64| {
+        let mut snap = make_snapshot();
+        snap.wal_dir = Some(WalDirStats {
+            segments: bytes / (16 * 1024 * 1024),
+            bytes,
+            archive_ready: Some(0),
+        });
+        snap
+    };
+    const GB: i64 = 1024 * 1024 * 1024;
+
+    app.update(with_wal_dir(GB / 2));
+    assert_eq!(app.wal_dir_fill(), Some(0.5));
+    assert!(app.feedback.status_message.is_none());
+
+    app.update(with_wal_dir(3 * GB / 2));
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("pg_wal is 1.5 GB, past 1.0 GB: check archiving and replication slots")
+    );
+    app.feedback.status_message = None;
+    app.update(with_wal_dir(2 * GB));
+    assert!(app.feedback.status_message.is_none(), "warns on crossing, not every snapshot");
+
+    // A configured size replaces max_wal_size
+    app.config.wal_dir_warn_mb = 4096;
+    assert_eq!(app.wal_dir_fill(), Some(0.5));
+}
+
 #[test]
 fn table_stats_panel_bloat_refresh() {
     let mut app = make_app();
@@ -843,6 +2706,10 @@ fn update_calculates_avg_query_time() {
             wait_event: None,
             query_start: None,
             backend_type: None,
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
         ActiveQuery {
             pid: 2,
@@ -855,6 +2722,10 @@ fn update_calculates_avg_query_time() {
             wait_event: None,
             query_start: None,
             backend_type: None,
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
     ];
 
@@ -881,6 +2752,10 @@ fn update_handles_no_active_queries() {
         wait_event: None,
         query_start: None,
         backend_type: None,
+        xact_start: None,
+        query_id: None,
+        application_name: None,
+        state_change: None,
     }];
 
     app.update(snap);
@@ -890,6 +2765,40 @@ fn update_handles_no_active_queries() {
     assert_eq!(last_avg, 0);
 }
 
+#[test]
+fn update_buckets_running_query_durations() {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    let query = |pid, state: &str, duration_secs| ActiveQuery {
+        pid,
+        usename: None,
+        datname: None,
+        state: Some(state.into()),
+        query: None,
+        duration_secs,
+        wait_event_type: None,
+        wait_event: None,
+        query_start: None,
+        backend_type: None,
+        xact_start: None,
+        query_id: None,
+        application_name: None,
+        state_change: None,
+    };
+    // Two fast, one on a bucket boundary, one stuck, and an idle one left out
+    snap.active_queries = vec![
+        query(1, "active", 0.002),
+        query(2, "active", 0.004),
+        query(3, "active", 1.0),
+        query(4, "idle in transaction", 600.0),
+        query(5, "idle", 900.0),
+    ];
+
+    app.update(snap);
+
+    assert_eq!(app.metrics.latency_buckets.last(), Some([2, 0, 0, 1, 0, 1]));
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // sorted_*_indices edge cases
 // ─────────────────────────────────────────────────────────────────────────────
@@ -960,13 +2869,35 @@ fn get_filtered_pids_no_snapshot() {
 // Filter edge cases
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[test]
+fn settings_filter_applies_to_role_overrides() {
+    use crate::db::models::RoleSetting;
+
+    let mut app = make_app();
+    app.server_info.role_settings = ["work_mem", "statement_timeout"]
+        .iter()
+        .map(|name| RoleSetting {
+            role: Some("reporting".into()),
+            database: None,
+            name: (*name).into(),
+            value: "1".into(),
+        })
+        .collect();
+    app.bottom_panel = BottomPanel::Settings;
+    assert_eq!(app.sorted_role_settings_indices(), vec![0, 1]);
+
+    app.filter_mut().text = "timeout".to_string();
+    app.filter_mut().active = true;
+    assert_eq!(app.sorted_role_settings_indices(), vec![1]);
+}
+
 #[test]
 fn filter_with_no_matches() {
     let mut app = make_app();
     app.update(make_snapshot());
     app.bottom_panel = BottomPanel::Queries;
-    app.filter.text = "xyznonexistent123".to_string();
-    app.filter.active = true;
+    app.filter_mut().text = "xyznonexistent123".to_string();
+    app.filter_mut().active = true;
 
     let indices = app.sorted_query_indices();
     assert!(indices.is_empty());
@@ -980,8 +2911,8 @@ fn filter_with_special_characters() {
     app.update(snap);
 
     app.bottom_panel = BottomPanel::Queries;
-    app.filter.text = "table-with".to_string();
-    app.filter.active = true;
+    app.filter_mut().text = "table-with".to_string();
+    app.filter_mut().active = true;
 
     let indices = app.sorted_query_indices();
     assert!(!indices.is_empty());
@@ -992,8 +2923,8 @@ fn filter_inactive_ignores_filter_text() {
     let mut app = make_app();
     app.update(make_snapshot());
     app.bottom_panel = BottomPanel::Queries;
-    app.filter.text = "xyznonexistent123".to_string();
-    app.filter.active = false;
+    app.filter_mut().text = "xyznonexistent123".to_string();
+    app.filter_mut().active = false;
     app.view_mode = ViewMode::Normal;
 
     // When filter is not active, all queries should be returned
@@ -1143,6 +3074,8 @@ fn rate_calculation_with_counter_reset() {
         xact_commit: 1_000_000,
         xact_rollback: 100,
         blks_read: 50000,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap1);
 
@@ -1153,6 +3086,8 @@ fn rate_calculation_with_counter_reset() {
         xact_commit: 100, // Lower than before - counter reset
         xact_rollback: 0,
         blks_read: 100,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap2);
 
@@ -1160,10 +3095,487 @@ fn rate_calculation_with_counter_reset() {
     // (the rate calculation guards against negative values)
 }
 
+#[test]
+fn rate_calculation_server_restart_is_marked() {
+    use crate::db::models::{DatabaseStats, WalStats};
+
+    let started = chrono::Utc::now() - chrono::Duration::hours(1);
+    let restarted = chrono::Utc::now();
+    let mut app = make_app();
+
+    let mut snap1 = make_snapshot();
+    snap1.db_stats = Some(DatabaseStats {
+        xact_commit: 1_000_000,
+        xact_rollback: 100,
+        blks_read: 50000,
+        stats_reset: None,
+        server_start: Some(started),
+    });
+    snap1.wal_stats = Some(WalStats { wal_bytes: 10_000_000, ..Default::default() });
+    app.update(snap1);
+
+    // Counters restart from zero alongside a new postmaster start time
+    let mut snap2 = make_snapshot();
+    snap2.timestamp = chrono::Utc::now() + chrono::Duration::seconds(2);
+    snap2.db_stats = Some(DatabaseStats {
+        xact_commit: 100,
+        xact_rollback: 0,
+        blks_read: 100,
+        stats_reset: None,
+        server_start: Some(restarted),
+    });
+    snap2.wal_stats = Some(WalStats { wal_bytes: 1000, ..Default::default() });
+    app.update(snap2);
+
+    assert_eq!(app.metrics.last_reset, Some(CounterReset::ServerRestart { at: restarted }));
+    assert_eq!(app.metrics.reset_marks.as_vec(), vec![false, true]);
+    assert!(app.metrics.has_recent_reset());
+    assert!(app.metrics.current_tps.is_none());
+    assert!(app.metrics.current_wal_rate.is_none());
+    assert!(app.metrics.tps.as_vec().is_empty());
+    assert!(app
+        .feedback
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.starts_with("Server restarted")));
+
+    // Rates resume from the new baseline on the next sample
+    let mut snap3 = make_snapshot();
+    snap3.timestamp = chrono::Utc::now() + chrono::Duration::seconds(4);
+    snap3.db_stats = Some(DatabaseStats {
+        xact_commit: 300,
+        xact_rollback: 0,
+        blks_read: 100,
+        stats_reset: None,
+        server_start: Some(restarted),
+    });
+    app.update(snap3);
+
+    assert_eq!(app.metrics.reset_marks.as_vec(), vec![false, true, false]);
+    assert!(app.metrics.current_tps.is_some_and(|tps| tps > 0.0));
+}
+
+#[test]
+fn rate_calculation_stats_reset_keeps_wal_rate() {
+    use crate::db::models::{DatabaseStats, WalStats};
+
+    let mut app = make_app();
+
+    let mut snap1 = make_snapshot();
+    snap1.db_stats = Some(DatabaseStats {
+        xact_commit: 5000,
+        xact_rollback: 0,
+        blks_read: 500,
+        stats_reset: None,
+        server_start: None,
+    });
+    snap1.wal_stats = Some(WalStats { wal_bytes: 1000, ..Default::default() });
+    app.update(snap1);
+
+    // pg_stat_reset() on this database: only pg_stat_database restarts
+    let reset_at = chrono::Utc::now();
+    let mut snap2 = make_snapshot();
+    snap2.timestamp = chrono::Utc::now() + chrono::Duration::seconds(2);
+    snap2.db_stats = Some(DatabaseStats {
+        xact_commit: 10,
+        xact_rollback: 0,
+        blks_read: 1,
+        stats_reset: Some(reset_at),
+        server_start: None,
+    });
+    snap2.wal_stats = Some(WalStats { wal_bytes: 5000, ..Default::default() });
+    app.update(snap2);
+
+    assert_eq!(app.metrics.last_reset, Some(CounterReset::StatsReset { at: reset_at }));
+    assert!(app.metrics.current_tps.is_none());
+    assert!(app.metrics.current_wal_rate.is_some());
+    assert!(app
+        .feedback
+        .status_message
+        .as_deref()
+        .is_some_and(|m| m.starts_with("Statistics reset")));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Latency sampler
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn stmt_sample(calls: i64, total_exec_time: f64) -> crate::db::models::StatementSample {
+    crate::db::models::StatementSample {
+        queryid: 1,
+        calls,
+        total_exec_time,
+    }
+}
+
+#[test]
+fn bloat_trend_compares_estimates_made_the_same_way() {
+    use crate::db::models::BloatSource;
+    use crate::db::queries::IndexBloat;
+
+    let mut history = BloatHistory::default();
+    let t0 = Utc::now();
+    let record = |history: &mut BloatHistory, mins: i64, pct: f64, source: BloatSource| {
+        let bloat = HashMap::from([(
+            "public.orders_pkey".to_string(),
+            IndexBloat { bloat_bytes: 1024, bloat_pct: pct, source },
+        )]);
+        history.record(t0 + chrono::Duration::minutes(mins), &HashMap::new(), &bloat);
+    };
+
+    record(&mut history, 0, 30.0, BloatSource::Naive);
+    assert!(BloatHistory::trend(history.for_index("public.orders_pkey")).is_none());
+    assert!(history.for_table("public.orders_pkey").is_empty());
+
+    // pgstattuple turned up: the naive figure is no baseline for it
+    record(&mut history, 60, 12.0, BloatSource::Pgstattuple);
+    assert!(BloatHistory::trend(history.for_index("public.orders_pkey")).is_none());
+
+    record(&mut history, 180, 15.0, BloatSource::Pgstattuple);
+    let (first, last) = BloatHistory::trend(history.for_index("public.orders_pkey")).unwrap();
+    assert_eq!((first.pct, last.pct), (12.0, 15.0));
+    assert_eq!(last.at - first.at, chrono::Duration::hours(2));
+}
+
+#[test]
+fn latency_sampler_weights_intervals_by_calls() {
+    let mut sampler = LatencySampler::default();
+    let t0 = Utc::now();
+    sampler.push(t0, &[stmt_sample(0, 0.0)]);
+    assert!(sampler.is_sampling(1));
+    assert_eq!(sampler.estimate(1), None);
+
+    // 90 calls at 2ms, then 9 at 20ms, then 1 at 500ms
+    let mut at = t0;
+    for (calls, total) in [(90, 180.0), (99, 360.0), (100, 860.0)] {
+        at += chrono::Duration::seconds(1);
+        sampler.push(at, &[stmt_sample(calls, total)]);
+    }
+
+    let est = sampler.estimate(1).unwrap();
+    assert_eq!(est.calls, 100);
+    assert_eq!(est.intervals, 3);
+    assert!((est.p50 - 2.0).abs() < 1e-9);
+    assert!((est.p95 - 20.0).abs() < 1e-9);
+    assert!((est.p99 - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn latency_sampler_skips_resets_and_expires_old_intervals() {
+    let mut sampler = LatencySampler::default();
+    let t0 = Utc::now();
+    sampler.push(t0, &[stmt_sample(100, 1000.0)]);
+    sampler.push(t0 + chrono::Duration::seconds(1), &[stmt_sample(110, 1100.0)]);
+    // pg_stat_statements_reset(): counters go backwards
+    sampler.push(t0 + chrono::Duration::seconds(2), &[stmt_sample(5, 10.0)]);
+    assert_eq!(sampler.estimate(1).unwrap().calls, 10);
+
+    // Everything ages out of the window
+    let later = t0 + chrono::Duration::seconds(LATENCY_WINDOW_SECS + 10);
+    sampler.push(later, &[stmt_sample(5, 10.0)]);
+    assert_eq!(sampler.estimate(1), None);
+}
+
+#[test]
+fn wait_sampler_profiles_the_window_and_forgets_old_polls() {
+    use crate::db::models::{WaitEventCount, WaitSampleSource};
+    let count = |t: &str, e: &str, n: i64| WaitEventCount {
+        wait_event_type: t.into(),
+        wait_event: e.into(),
+        count: n,
+    };
+
+    let mut sampler = WaitSampler::default();
+    sampler.error = Some("connection refused".into());
+    let t0 = Utc::now();
+    sampler.push(t0, WaitSampleSource::PgWaitSampling, vec![count("IO", "DataFileRead", 5)]);
+    assert_eq!(sampler.error, None);
+    sampler.push(
+        t0 + chrono::Duration::seconds(1),
+        WaitSampleSource::PgWaitSampling,
+        vec![count("Lock", "tuple", 2), count("Lock", "transactionid", 4), count("IO", "WALSync", 1)],
+    );
+
+    let profile = sampler.profile();
+    assert_eq!(profile.len(), 2);
+    assert_eq!((profile[0].wait_event_type.as_str(), profile[0].count), ("IO", 6));
+    assert_eq!(profile[1].events, vec![("transactionid".to_string(), 4), ("tuple".to_string(), 2)]);
+    assert_eq!(sampler.polls(), 2);
+    assert!((sampler.span_secs() - 1.0).abs() < 1e-9);
+
+    // Only the last minute counts, empty polls included
+    sampler.push(t0 + chrono::Duration::seconds(WAIT_WINDOW_SECS + 1), WaitSampleSource::PgWaitSampling, vec![]);
+    assert_eq!(sampler.polls(), 2);
+    assert_eq!(sampler.profile()[0].wait_event_type, "Lock");
+
+    sampler.clear();
+    assert!(sampler.profile().is_empty());
+    assert_eq!(sampler.source, None);
+}
+
+#[test]
+fn progress_eta_follows_the_rate_within_a_phase() {
+    use crate::db::models::{OperationKind, OperationProgress};
+    let op = |phase: &str, done: i64| OperationProgress {
+        pid: 42,
+        kind: OperationKind::CreateIndex,
+        command: "CREATE INDEX".into(),
+        datname: Some("shop".into()),
+        target: "public.orders".into(),
+        relid: None,
+        phase: phase.into(),
+        work_done: done,
+        work_total: 1000,
+        elapsed_secs: Some(30.0),
+    };
+    let t = |secs| Utc::now() + chrono::Duration::seconds(secs);
+    let t0 = t(0);
+    let mut tracker = ProgressTracker::default();
+
+    tracker.observe(t0, &[op("building index: scanning table", 100)]);
+    assert_eq!(tracker.eta_secs(&op("building index: scanning table", 100), t0), None);
+
+    // 200 blocks in 10s leaves 700 blocks, 35s
+    let later = t0 + chrono::Duration::seconds(10);
+    tracker.observe(later, &[op("building index: scanning table", 300)]);
+    let eta = tracker.eta_secs(&op("building index: scanning table", 300), later).unwrap();
+    assert!((eta - 35.0).abs() < 1e-9, "{eta}");
+
+    // A new phase starts the measurement again
+    tracker.observe(later, &[op("building index: sorting live tuples", 0)]);
+    assert_eq!(tracker.eta_secs(&op("building index: sorting live tuples", 0), later), None);
+
+    // Finished operations are forgotten
+    tracker.observe(later, &[]);
+    assert_eq!(tracker.eta_secs(&op("building index: sorting live tuples", 10), t(20)), None);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Temp spill attribution
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn temp_spill_attributes_temp_growth_to_queryid() {
+    let mut app = make_app_with_statement(7);
+    assert_eq!(app.temp_spill.rate(Some(7)), None);
+
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.stat_statements[0].temp_blks_written += 100;
+    app.update(snap.clone());
+    let rate = app.temp_spill.rate(Some(7)).unwrap();
+    assert!((rate - 100.0 * 8192.0 / 2.0).abs() < 1e-6);
+    assert_eq!(app.temp_spill.rate(None), None);
+
+    // No further temp writes: the badge clears
+    snap.timestamp += chrono::Duration::seconds(2);
+    app.update(snap);
+    assert_eq!(app.temp_spill.rate(Some(7)), None);
+}
+
+#[test]
+fn changes_mark_new_queries_and_statements() {
+    let mut app = make_app_with_statement(7);
+    // The first snapshot is only the baseline
+    assert_eq!(app.changes.count(), 0);
+
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.active_queries[0].query_start = Some(snap.timestamp);
+    let mut added = snap.stat_statements[0].clone();
+    added.queryid = 8;
+    snap.stat_statements.push(added);
+    app.update(snap.clone());
+    assert_eq!(app.changes.query(12345), Some(Change::New));
+    assert_eq!(app.changes.statement(8), Some(Change::New));
+    assert_eq!(app.changes.statement(7), None);
+
+    // Marks last one refresh
+    snap.timestamp += chrono::Duration::seconds(2);
+    app.update(snap);
+    assert_eq!(app.changes.count(), 0);
+}
+
+#[test]
+fn changes_mark_counters_that_jump() {
+    let mut app = make_app_with_tables(&["orders"]);
+    let mut snap = app.snapshot.clone().unwrap();
+    let mut step = |app: &mut App, inserts: i64| {
+        snap.timestamp += chrono::Duration::seconds(2);
+        snap.table_stats[0].n_tup_ins += inserts;
+        app.update(snap.clone());
+        app.changes.table(&snap.table_stats[0])
+    };
+    assert_eq!(step(&mut app, 600), None);
+    assert_eq!(step(&mut app, 5000), Some(Change::Jumped));
+    // Steady at the new rate
+    assert_eq!(step(&mut app, 5000), None);
+    // Stats reset: counters went backwards
+    assert_eq!(step(&mut app, -20000), None);
+    // Too few rows to matter, however sudden
+    assert_eq!(step(&mut app, 1), None);
+    assert_eq!(step(&mut app, 900), None);
+}
+
+#[test]
+fn tilde_toggles_change_highlighting() {
+    let mut app = make_app();
+    assert!(app.highlighted_changes().is_none());
+    app.handle_key(key(KeyCode::Char('~')));
+    assert!(app.config.highlight_changes);
+    assert!(app.highlighted_changes().is_some());
+    app.handle_key(key(KeyCode::Char('~')));
+    assert!(app.highlighted_changes().is_none());
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Bloat preservation during update
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[test]
+fn prepared_snapshots_carry_bloat_from_earlier_refreshes() {
+    use crate::db::models::{BloatSource, IndexInfo};
+    use crate::db::queries::IndexBloat;
+
+    let index = IndexInfo {
+        schemaname: "public".into(),
+        table_name: "orders".into(),
+        index_name: "orders_pkey".into(),
+        index_size_bytes: 8192,
+        idx_scan: 1,
+        idx_tup_read: 1,
+        idx_tup_fetch: 1,
+        index_definition: String::new(),
+        is_valid: true,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    };
+    let mut snap = make_snapshot();
+    snap.indexes = vec![index];
+
+    let mut prep = SnapshotPrep::default();
+    assert_eq!(prep.prepare(snap.clone(), true).snapshot.indexes[0].bloat_pct, None);
+
+    prep.remember_bloat(
+        &HashMap::new(),
+        &HashMap::from([(
+            "public.orders_pkey".to_string(),
+            IndexBloat { bloat_bytes: 4096, bloat_pct: 50.0, source: BloatSource::Pgstattuple },
+        )]),
+    );
+    let prepared = prep.prepare(snap.clone(), true);
+    assert_eq!(prepared.snapshot.indexes[0].bloat_pct, Some(50.0));
+    assert_eq!(prepared.snapshot.indexes[0].bloat_source, Some(BloatSource::Pgstattuple));
+
+    // Recorded snapshots keep what they were recorded with
+    assert_eq!(prep.prepare(snap, false).snapshot.indexes[0].bloat_pct, None);
+}
+
+#[test]
+fn prepared_snapshots_show_as_they_are() {
+    use crate::alerts::{AlertMetric, AlertRule};
+    use crate::config::IgnoreList;
+    use crate::db::models::DatabaseStats;
+
+    let mut app = make_app();
+    app.config.alerts.clear();
+    let mut prep = SnapshotPrep::default();
+    prep.watch(&PrepWatch {
+        rules: vec![AlertRule {
+            name: "Busy".into(),
+            metric: AlertMetric::Connections,
+            above: Some(-1.0),
+            below: None,
+            for_secs: 0,
+        }],
+        ignore: IgnoreList::default(),
+        ..PrepWatch::default()
+    });
+    let mut snap = make_snapshot();
+    snap.stat_statements = make_app_with_statement(7).snapshot.unwrap().stat_statements;
+    snap.db_stats = Some(DatabaseStats {
+        xact_commit: 1000,
+        xact_rollback: 0,
+        blks_read: 0,
+        stats_reset: None,
+        server_start: None,
+    });
+    let first = prep.prepare(snap.clone(), true);
+    assert_eq!(first.fired_alerts.len(), 1);
+    app.apply_prepared(first);
+    // The app shows what the source evaluated; it checks no rules itself
+    assert_eq!(app.alerts.active_count(), 1);
+    assert!(app.alerts.unseen);
+
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.stat_statements[0].temp_blks_written += 10;
+    snap.db_stats.as_mut().unwrap().xact_commit += 200;
+    let second = prep.prepare(snap, true);
+    assert_eq!(second.rates.tps, Some(100.0));
+    assert!(second.fired_alerts.is_empty());
+    app.apply_prepared(second);
+    assert!(app.temp_spill.rate(Some(7)).is_some());
+    assert_eq!(app.metrics.current_tps, Some(100.0));
+    assert_eq!(app.metrics.connections.as_vec().len(), 2);
+    assert_eq!(app.alerts.history.len(), 1);
+    assert!(app.feedback.last_error.is_none());
+}
+
+#[test]
+fn prepared_snapshots_carry_the_trackers() {
+    let mut app = make_app();
+    app.config.adaptive_refresh.enabled = true;
+    let mut prep = SnapshotPrep::default();
+    prep.watch(&app.prep_watch());
+    let mut snap = make_snapshot();
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+    snap.timestamp += chrono::Duration::seconds(2);
+    app.apply_prepared(prep.prepare(snap, true));
+
+    // Followed in the source, not on the UI thread
+    assert_eq!(app.session_rows().len(), 1);
+    assert!(app.adaptive_refresh.current().is_some());
+
+    // Switching adaptive refresh off starts it over
+    app.config.adaptive_refresh.enabled = false;
+    prep.watch(&app.prep_watch());
+    let mut snap = make_snapshot();
+    snap.timestamp += chrono::Duration::seconds(4);
+    app.apply_prepared(prep.prepare(snap, true));
+    assert_eq!(app.adaptive_refresh.current(), None);
+}
+
+#[test]
+fn skipped_relation_stats_carry_the_last_read() {
+    let mut app = make_app();
+    let mut prep = SnapshotPrep::default();
+    let mut snap = make_app_with_tables(&["orders"]).snapshot.unwrap();
+    prep.relation_stats(&mut snap, true);
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.table_stats[0].n_tup_ins += 600;
+    let read_at = snap.timestamp;
+    prep.relation_stats(&mut snap, true);
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+
+    // The schedule skipped them: the fetcher left them empty
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.table_stats.clear();
+    prep.relation_stats(&mut snap, false);
+    assert_eq!(snap.table_stats.len(), 1);
+    assert_eq!(snap.relation_stats_read_at, Some(read_at));
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+
+    // The next read is compared with the last one, not the carried copy
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.table_stats[0].n_tup_ins += 5000;
+    snap.relation_stats_read_at = None;
+    prep.relation_stats(&mut snap, true);
+    app.apply_prepared(prep.prepare(snap.clone(), true));
+    assert_eq!(app.changes.table(&snap.table_stats[0]), Some(Change::Jumped));
+}
+
 #[test]
 fn update_preserves_bloat_data() {
     use crate::db::models::TableStat;
@@ -1238,6 +3650,121 @@ fn update_preserves_bloat_data() {
     assert_eq!(snap.table_stats[0].bloat_pct, Some(12.5));
 }
 
+#[test]
+fn update_in_replay_uses_recorded_bloat_as_is() {
+    use crate::db::models::IndexInfo;
+
+    let make_index = |bloat_pct: Option<f64>| IndexInfo {
+        schemaname: "public".into(),
+        table_name: "users".into(),
+        index_name: "users_pkey".into(),
+        index_size_bytes: 8192,
+        idx_scan: 10,
+        idx_tup_read: 10,
+        idx_tup_fetch: 10,
+        index_definition: "CREATE UNIQUE INDEX users_pkey ON users (id)".into(),
+        is_valid: true,
+        bloat_bytes: bloat_pct.map(|_| 1024),
+        bloat_pct,
+        bloat_source: None,
+    };
+
+    let mut app = make_replay_app();
+    let mut snap1 = make_snapshot();
+    snap1.indexes = vec![make_index(Some(30.0))];
+    app.update(snap1);
+
+    // Stepping to a snapshot recorded before bloat was estimated
+    let mut snap2 = make_snapshot();
+    snap2.indexes = vec![make_index(None)];
+    app.update(snap2);
+
+    let snap = app.snapshot.as_ref().unwrap();
+    assert_eq!(snap.indexes[0].bloat_pct, None);
+}
+
+#[test]
+fn autovacuum_panel_flags_tables_left_past_their_trigger() {
+    use crate::autovacuum::AutovacuumStatus;
+    use crate::db::models::TableStat;
+
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.table_stats = vec![TableStat {
+        schemaname: "public".into(),
+        relname: "events".into(),
+        total_size_bytes: 0,
+        table_size_bytes: 0,
+        indexes_size_bytes: 0,
+        seq_scan: 0,
+        seq_tup_read: 0,
+        idx_scan: 0,
+        idx_tup_fetch: 0,
+        n_live_tup: 1_000,
+        n_dead_tup: 900,
+        dead_ratio: 90.0,
+        n_tup_ins: 0,
+        n_tup_upd: 0,
+        n_tup_del: 0,
+        n_tup_hot_upd: 0,
+        last_vacuum: None,
+        last_autovacuum: None,
+        last_analyze: None,
+        last_autoanalyze: None,
+        vacuum_count: 0,
+        autovacuum_count: 4,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    }];
+    let start = snap.timestamp;
+    app.update(snap.clone());
+    assert_eq!(app.autovacuum_rows()[0].status, AutovacuumStatus::Due);
+
+    snap.timestamp = start + chrono::Duration::minutes(5);
+    app.update(snap);
+    assert_eq!(app.autovacuum_rows()[0].status, AutovacuumStatus::Starved);
+    assert_eq!(app.metrics.autovacuum_workers.as_vec().len(), 2);
+
+    app.handle_key(key(KeyCode::Char('V')));
+    assert_eq!(app.bottom_panel, BottomPanel::AutovacuumHealth);
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.events".into())));
+}
+
+#[test]
+fn index_health_enter_inspects_the_flagged_index() {
+    use crate::db::models::IndexInfo;
+
+    let make_index = |name: &str, idx_scan: i64| IndexInfo {
+        schemaname: "public".into(),
+        table_name: "users".into(),
+        index_name: name.into(),
+        index_size_bytes: 8192,
+        idx_scan,
+        idx_tup_read: 0,
+        idx_tup_fetch: 0,
+        index_definition: format!("CREATE INDEX {name} ON public.users USING btree (email)"),
+        is_valid: true,
+        bloat_bytes: None,
+        bloat_pct: None,
+        bloat_source: None,
+    };
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.indexes = vec![make_index("users_email_idx", 40), make_index("users_email_idx1", 2)];
+    app.update(snap);
+
+    app.handle_key(key(KeyCode::Char('F')));
+    assert_eq!(app.bottom_panel, BottomPanel::IndexHealth);
+    assert_eq!((BottomPanel::IndexHealth.descriptor().row_count)(&app), 1);
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(
+        app.view_mode,
+        ViewMode::Inspect(InspectTarget::Index("public.users_email_idx1".to_string()))
+    );
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Navigation with empty data
 // ─────────────────────────────────────────────────────────────────────────────
@@ -1329,6 +3856,8 @@ fn rate_calculation_first_snapshot_no_rate() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap);
 
@@ -1351,6 +3880,8 @@ fn rate_calculation_tps_normal() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap1);
 
@@ -1361,6 +3892,8 @@ fn rate_calculation_tps_normal() {
         xact_commit: 1190, // +190 commits
         xact_rollback: 20, // +10 rollbacks
         blks_read: 600,    // +100 reads
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap2);
 
@@ -1393,6 +3926,8 @@ fn rate_calculation_wal_rate() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     snap1.wal_stats = Some(WalStats {
         wal_records: 10000,
@@ -1413,6 +3948,8 @@ fn rate_calculation_wal_rate() {
         xact_commit: 1100,
         xact_rollback: 10,
         blks_read: 600,
+        stats_reset: None,
+        server_start: None,
     });
     snap2.wal_stats = Some(WalStats {
         wal_records: 12000,
@@ -1426,17 +3963,163 @@ fn rate_calculation_wal_rate() {
     });
     app.update(snap2);
 
-    // WAL rate should be 2MB / 2s = 1MB/s
-    assert!(app.metrics.current_wal_rate.is_some());
-    let wal_rate = app.metrics.current_wal_rate.unwrap();
-    let expected = 1_000_000.0; // 1 MB/s
-    assert!(
-        (wal_rate - expected).abs() < 1000.0,
-        "Expected ~1MB/s WAL rate, got {wal_rate}"
-    );
+    // WAL rate should be 2MB / 2s = 1MB/s
+    assert!(app.metrics.current_wal_rate.is_some());
+    let wal_rate = app.metrics.current_wal_rate.unwrap();
+    let expected = 1_000_000.0; // 1 MB/s
+    assert!(
+        (wal_rate - expected).abs() < 1000.0,
+        "Expected ~1MB/s WAL rate, got {wal_rate}"
+    );
+
+    // History should have one entry (stored as KB/s)
+    assert_eq!(app.metrics.wal_rate.as_vec().len(), 1);
+}
+
+#[test]
+fn rate_calculation_wal_io_panel_rates() {
+    use crate::db::models::{ArchiverStats, BgwriterStats, CheckpointStats, DatabaseStats};
+
+    let mut app = make_app();
+    let base_time = chrono::Utc::now();
+    let db = DatabaseStats {
+        xact_commit: 1000,
+        xact_rollback: 0,
+        blks_read: 0,
+        stats_reset: None,
+        server_start: None,
+    };
+
+    let mut snap1 = make_snapshot();
+    snap1.timestamp = base_time;
+    snap1.db_stats = Some(db);
+    snap1.checkpoint_stats = Some(CheckpointStats {
+        checkpoints_timed: 10,
+        checkpoints_req: 2,
+        checkpoint_write_time: 1000.0,
+        checkpoint_sync_time: 100.0,
+        buffers_checkpoint: 5000,
+        buffers_backend: 100,
+    });
+    snap1.archiver_stats = Some(ArchiverStats {
+        archived_count: 50,
+        failed_count: 1,
+        ..Default::default()
+    });
+    snap1.bgwriter_stats = Some(BgwriterStats {
+        buffers_clean: 300,
+        maxwritten_clean: 0,
+        buffers_alloc: 10_000,
+    });
+    app.update(snap1);
+    assert!(app.metrics.wal_io.checkpoints.current.is_none());
+
+    // 4 seconds later
+    let mut snap2 = make_snapshot();
+    snap2.timestamp = base_time + chrono::Duration::seconds(4);
+    snap2.db_stats = Some(db);
+    snap2.checkpoint_stats = Some(CheckpointStats {
+        checkpoints_timed: 11,
+        checkpoints_req: 3,
+        checkpoint_write_time: 1400.0,
+        checkpoint_sync_time: 108.0,
+        buffers_checkpoint: 5400,
+        buffers_backend: 120,
+    });
+    snap2.archiver_stats = Some(ArchiverStats {
+        archived_count: 54,
+        failed_count: 1,
+        ..Default::default()
+    });
+    snap2.bgwriter_stats = Some(BgwriterStats {
+        buffers_clean: 340,
+        maxwritten_clean: 0,
+        buffers_alloc: 12_000,
+    });
+    app.update(snap2);
+
+    let rates = &app.metrics.wal_io;
+    assert_eq!(rates.checkpoints.current, Some(0.5));
+    assert_eq!(rates.checkpoints_forced.current, Some(0.25));
+    assert_eq!(rates.checkpoint_write_time.current, Some(100.0));
+    assert_eq!(rates.checkpoint_sync_time.current, Some(2.0));
+    assert_eq!(rates.buffers_checkpoint.current, Some(100.0));
+    assert_eq!(rates.buffers_backend.current, Some(5.0));
+    assert_eq!(rates.archived.current, Some(1.0));
+    assert_eq!(rates.archive_failed.current, Some(0.0));
+    assert_eq!(rates.buffers_clean.current, Some(10.0));
+    assert_eq!(rates.buffers_alloc.current, Some(500.0));
+    // Stored in milli-units so fractional rates still draw
+    assert_eq!(rates.checkpoints_forced.history.as_vec(), vec![250]);
+
+    // Both checkpoints completed in the interval land in one log entry
+    let log = app.metrics.checkpoint_log.timed();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].0, base_time + chrono::Duration::seconds(4));
+    assert_eq!(
+        log[0].1,
+        CheckpointEvent { count: 2, forced: 1, write_ms: 400.0, sync_ms: 8.0, buffers: 400 }
+    );
+}
+
+#[test]
+fn rate_calculation_io_by_backend() {
+    use crate::db::models::{DatabaseStats, IoStat};
+
+    let io = |backend_type: &str, context: &str, reads, writes| IoStat {
+        backend_type: backend_type.into(),
+        context: context.into(),
+        reads,
+        writes,
+        extends: 0,
+        fsyncs: 0,
+    };
+    let mut app = make_app();
+    let base_time = chrono::Utc::now();
+    let db = DatabaseStats {
+        xact_commit: 1000,
+        xact_rollback: 0,
+        blks_read: 0,
+        stats_reset: None,
+        server_start: None,
+    };
+
+    let mut snap1 = make_snapshot();
+    snap1.timestamp = base_time;
+    snap1.db_stats = Some(db);
+    snap1.io_stats = vec![io("client backend", "normal", 1000, 40), io("checkpointer", "normal", 0, 9000)];
+    app.update(snap1);
+    assert!(app.metrics.wal_io.io.is_empty());
+
+    let mut snap2 = make_snapshot();
+    snap2.timestamp = base_time + chrono::Duration::seconds(2);
+    snap2.db_stats = Some(db);
+    // The checkpointer row was reset, and autovacuum showed up for the first time
+    snap2.io_stats = vec![
+        io("client backend", "normal", 1600, 50),
+        io("checkpointer", "normal", 0, 10),
+        io("autovacuum worker", "vacuum", 500, 0),
+    ];
+    app.update(snap2);
+
+    let rates = &app.metrics.wal_io.io;
+    assert_eq!(rates.len(), 1);
+    assert_eq!(rates[0].backend_type, "client backend");
+    assert_eq!((rates[0].reads, rates[0].writes), (300.0, 5.0));
+}
 
-    // History should have one entry (stored as KB/s)
-    assert_eq!(app.metrics.wal_rate.as_vec().len(), 1);
+#[test]
+fn rate_series_skips_counter_reset() {
+    use crate::app::RateSeries;
+
+    let mut series = RateSeries::new(10);
+    series.push_delta(20.0, 2.0);
+    assert_eq!(series.current, Some(10.0));
+
+    // Counter went backwards (stats reset): keep the last rate, add no point
+    series.push_delta(-500.0, 2.0);
+    assert_eq!(series.current, Some(10.0));
+    assert_eq!(series.history.as_vec().len(), 1);
 }
 
 #[test]
@@ -1475,6 +4158,8 @@ fn rate_calculation_missing_wal_stats() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     snap1.wal_stats = None;
     app.update(snap1);
@@ -1486,6 +4171,8 @@ fn rate_calculation_missing_wal_stats() {
         xact_commit: 1100,
         xact_rollback: 10,
         blks_read: 600,
+        stats_reset: None,
+        server_start: None,
     });
     snap2.wal_stats = None;
     app.update(snap2);
@@ -1509,6 +4196,8 @@ fn rate_calculation_zero_time_difference() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap1);
 
@@ -1518,6 +4207,8 @@ fn rate_calculation_zero_time_difference() {
         xact_commit: 1100,
         xact_rollback: 20,
         blks_read: 600,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap2);
 
@@ -1540,6 +4231,8 @@ fn rate_calculation_very_small_interval() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 500,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap1);
 
@@ -1550,6 +4243,8 @@ fn rate_calculation_very_small_interval() {
         xact_commit: 1010, // +10 in 100ms
         xact_rollback: 10,
         blks_read: 505,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap2);
 
@@ -1573,6 +4268,8 @@ fn rate_calculation_history_accumulates() {
         xact_commit: 1000,
         xact_rollback: 0,
         blks_read: 100,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap);
 
@@ -1584,6 +4281,8 @@ fn rate_calculation_history_accumulates() {
             xact_commit: 1000 + (i * 100), // +100 per 2 sec = 50 TPS
             xact_rollback: 0,
             blks_read: 100 + (i * 10),
+            stats_reset: None,
+            server_start: None,
         });
         app.update(snap);
     }
@@ -1607,6 +4306,8 @@ fn rate_calculation_counter_reset_blks() {
         xact_commit: 1000,
         xact_rollback: 10,
         blks_read: 1_000_000,
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap1);
 
@@ -1617,6 +4318,8 @@ fn rate_calculation_counter_reset_blks() {
         xact_commit: 1100, // Normal increase
         xact_rollback: 20,
         blks_read: 100, // Counter reset
+        stats_reset: None,
+        server_start: None,
     });
     app.update(snap2);
 
@@ -2006,8 +4709,8 @@ fn sorted_extensions_indices_with_data() {
 fn extensions_filter_matches_name() {
     let mut app = make_app_with_extensions();
     app.bottom_panel = BottomPanel::Extensions;
-    app.filter.text = "plpgsql".into();
-    app.filter.active = true;
+    app.filter_mut().text = "plpgsql".into();
+    app.filter_mut().active = true;
 
     let indices = app.sorted_extensions_indices();
     assert_eq!(indices.len(), 1);
@@ -2019,8 +4722,8 @@ fn extensions_filter_matches_schema() {
     let mut app = make_app_with_extensions();
     app.bottom_panel = BottomPanel::Extensions;
     // pg_catalog is unique to plpgsql in our test data
-    app.filter.text = "pg_catalog".into();
-    app.filter.active = true;
+    app.filter_mut().text = "pg_catalog".into();
+    app.filter_mut().active = true;
 
     let indices = app.sorted_extensions_indices();
     assert_eq!(indices.len(), 1);
@@ -2031,8 +4734,8 @@ fn extensions_filter_matches_schema() {
 fn extensions_filter_no_matches() {
     let mut app = make_app_with_extensions();
     app.bottom_panel = BottomPanel::Extensions;
-    app.filter.text = "nonexistent123".into();
-    app.filter.active = true;
+    app.filter_mut().text = "nonexistent123".into();
+    app.filter_mut().active = true;
 
     let indices = app.sorted_extensions_indices();
     assert!(indices.is_empty());
@@ -2042,8 +4745,8 @@ fn extensions_filter_no_matches() {
 fn extensions_filter_inactive_shows_all() {
     let mut app = make_app_with_extensions();
     app.bottom_panel = BottomPanel::Extensions;
-    app.filter.text = "stat".into();
-    app.filter.active = false;
+    app.filter_mut().text = "stat".into();
+    app.filter_mut().active = false;
     app.view_mode = ViewMode::Normal;
 
     // When filter is inactive, all extensions should be returned
@@ -2169,22 +4872,24 @@ fn extensions_reset_selection_on_filter_confirm() {
     app.bottom_panel = BottomPanel::Extensions;
     app.panels.extensions.select(Some(2));
     app.view_mode = ViewMode::Filter;
-    app.filter.text = "test".into();
+    app.filter_mut().text = "test".into();
 
     app.handle_key(key(KeyCode::Enter));
     assert_eq!(app.panels.extensions.selected(), Some(0));
 }
 
 #[test]
-fn extensions_panel_switch_clears_filter() {
+fn extensions_panel_filter_is_its_own() {
     let mut app = make_app_with_extensions();
     app.bottom_panel = BottomPanel::Extensions;
-    app.filter.text = "test".into();
-    app.filter.active = true;
+    app.filter_mut().text = "test".into();
+    app.filter_mut().active = true;
 
     app.handle_key(key(KeyCode::Char('I'))); // Switch to Indexes
-    assert!(app.filter.text.is_empty());
-    assert!(!app.filter.active);
+    assert!(app.filter().text.is_empty());
+    assert!(!app.filter().active);
+    app.handle_key(key(KeyCode::Char('E')));
+    assert_eq!(app.filter().text, "test");
 }
 
 #[test]
@@ -2235,9 +4940,99 @@ fn recordings_opens_with_l_key() {
 }
 
 #[test]
-fn recordings_l_key_disabled_in_replay_mode() {
+fn recordings_select_in_replay_mode_sets_pending_path() {
+    use crate::recorder::RecordingInfo;
+
     let mut app = make_replay_app();
     app.handle_key(key(KeyCode::Char('L')));
+    app.recordings.list = vec![RecordingInfo {
+        path: std::path::PathBuf::from("/tmp/other.jsonl"),
+        host: "db2".into(),
+        port: 5432,
+        dbname: "postgres".into(),
+        recorded_at: chrono::Utc::now(),
+        file_size: 1024,
+        segments: 1,
+        pg_version: "PostgreSQL 16.1".into(),
+    }];
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(
+        app.recordings.pending_path,
+        Some(std::path::PathBuf::from("/tmp/other.jsonl"))
+    );
+    assert!(!app.running);
+}
+
+#[test]
+fn panel_switch_and_filter_are_logged_in_live_mode() {
+    let mut app = make_app();
+    app.handle_key(key(KeyCode::Char('I')));
+    app.handle_key(key(KeyCode::Char('/')));
+    app.handle_key(key(KeyCode::Char('x')));
+    app.handle_key(key(KeyCode::Enter));
+
+    let kinds: Vec<_> = app.feedback.take_ui_events().into_iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            UiEventKind::PanelSwitch { panel: "Indexes".into() },
+            UiEventKind::Filter { panel: "Indexes".into(), text: "x".into() },
+        ]
+    );
+    assert!(app.feedback.take_ui_events().is_empty());
+}
+
+#[test]
+fn hash_annotates_the_session_and_marks_the_next_sample() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.handle_key(key(KeyCode::Char('#')));
+    assert_eq!(app.view_mode, ViewMode::Annotate);
+    for c in "deployed v2.3".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(app.annotations.len(), 1);
+    assert_eq!(app.annotations[0].text, "deployed v2.3");
+    let kinds: Vec<_> = app.feedback.take_ui_events().into_iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, vec![UiEventKind::Annotation { text: "deployed v2.3".into() }]);
+
+    let mut snap = make_snapshot();
+    snap.timestamp = app.annotations[0].at + chrono::Duration::seconds(2);
+    app.update(snap);
+    assert_eq!(app.metrics.annotation_marks.as_vec(), vec![false, true]);
+
+    // An empty note is dropped
+    app.handle_key(key(KeyCode::Char('#')));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.annotations.len(), 1);
+}
+
+#[test]
+fn hash_does_nothing_in_replay() {
+    let mut app = make_replay_app();
+    app.handle_key(key(KeyCode::Char('#')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn ui_events_not_logged_in_replay_mode() {
+    let mut app = make_replay_app();
+    app.handle_key(key(KeyCode::Char('I')));
+    assert!(app.feedback.ui_events.is_empty());
+}
+
+#[test]
+fn activity_overlay_opens_only_in_replay() {
+    let mut app = make_app();
+    app.handle_key(key(KeyCode::Char('a')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+
+    let mut app = make_replay_app();
+    app.handle_key(key(KeyCode::Char('a')));
+    assert_eq!(app.view_mode, ViewMode::Activity);
+    app.handle_key(key(KeyCode::Esc));
     assert_eq!(app.view_mode, ViewMode::Normal);
 }
 
@@ -2284,6 +5079,7 @@ fn recordings_navigation_down() {
             recorded_at: Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 1000,
+            segments: 1,
         },
         RecordingInfo {
             path: PathBuf::from("/tmp/test2.jsonl"),
@@ -2293,6 +5089,7 @@ fn recordings_navigation_down() {
             recorded_at: Utc::now(),
             pg_version: "PostgreSQL 14.0".into(),
             file_size: 2000,
+            segments: 1,
         },
     ];
 
@@ -2320,6 +5117,7 @@ fn recordings_navigation_up() {
             recorded_at: Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 1000,
+            segments: 1,
         },
         RecordingInfo {
             path: PathBuf::from("/tmp/test2.jsonl"),
@@ -2329,6 +5127,7 @@ fn recordings_navigation_up() {
             recorded_at: Utc::now(),
             pg_version: "PostgreSQL 14.0".into(),
             file_size: 2000,
+            segments: 1,
         },
     ];
     app.recordings.selected = 1;
@@ -2356,6 +5155,7 @@ fn recordings_enter_sets_pending_replay_path() {
         recorded_at: Utc::now(),
         pg_version: "PostgreSQL 15.0".into(),
         file_size: 1000,
+        segments: 1,
     }];
 
     app.handle_key(key(KeyCode::Enter));
@@ -2379,6 +5179,7 @@ fn recordings_d_key_opens_delete_confirm() {
         recorded_at: Utc::now(),
         pg_version: "PostgreSQL 15.0".into(),
         file_size: 1000,
+        segments: 1,
     }];
 
     app.handle_key(key(KeyCode::Char('d')));
@@ -2397,3 +5198,509 @@ fn recordings_delete_confirm_cancel() {
     app.handle_key(key(KeyCode::Esc));
     assert_eq!(app.view_mode, ViewMode::Recordings);
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Starred statements
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn make_app_with_statement(queryid: i64) -> App {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.stat_statements = vec![crate::db::models::StatStatement {
+        queryid,
+        query: "SELECT * FROM report".into(),
+        calls: 10,
+        total_exec_time: 5000.0,
+        min_exec_time: 100.0,
+        mean_exec_time: 500.0,
+        max_exec_time: 900.0,
+        stddev_exec_time: 50.0,
+        rows: 100,
+        shared_blks_hit: 0,
+        shared_blks_read: 0,
+        shared_blks_dirtied: 0,
+        shared_blks_written: 0,
+        local_blks_hit: 0,
+        local_blks_read: 0,
+        local_blks_dirtied: 0,
+        local_blks_written: 0,
+        temp_blks_read: 0,
+        temp_blks_written: 0,
+        blk_read_time: 0.0,
+        blk_write_time: 0.0,
+        hit_ratio: 1.0,
+        windowed: false,
+    }];
+    app.update(snap);
+    app.bottom_panel = BottomPanel::Statements;
+    app
+}
+
+#[test]
+fn star_key_toggles_selected_statement() {
+    let mut app = make_app_with_statement(42);
+
+    app.handle_key(key(KeyCode::Char('*')));
+    assert!(app.starred.is_starred(42));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveStarred)));
+
+    app.handle_key(key(KeyCode::Char('*')));
+    assert!(!app.starred.is_starred(42));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveStarred)));
+}
+
+#[test]
+fn windowed_statements_get_no_interval_timing() {
+    let mut app = make_app_with_statement(42);
+    assert!(app.metrics.statement_timing.contains_key(&42));
+
+    // pg_stat_monitor's oldest bucket expired: the sums dropped
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.stat_statements[0].windowed = true;
+    snap.stat_statements[0].calls = 4;
+    app.update(snap);
+    assert!(!app.metrics.statement_timing.contains_key(&42));
+}
+
+#[test]
+fn note_edit_saves_and_stars() {
+    let mut app = make_app_with_statement(42);
+
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.view_mode, ViewMode::EditNote(42));
+    for c in "slow report".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Backspace));
+    app.handle_key(key(KeyCode::Enter));
+
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(app.starred.note(42), Some("slow repor"));
+    assert!(app.note_input.is_empty());
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveStarred)));
+}
+
+#[test]
+fn note_edit_prefills_and_esc_cancels() {
+    let mut app = make_app_with_statement(42);
+    app.starred.set_note(42, "existing");
+
+    app.handle_key(key(KeyCode::Char('n')));
+    assert_eq!(app.note_input, "existing");
+    // Keys that would normally switch panels are typed into the note
+    app.handle_key(key(KeyCode::Char('Q')));
+    assert_eq!(app.note_input, "existingQ");
+    assert_eq!(app.bottom_panel, BottomPanel::Statements);
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+    assert_eq!(app.starred.note(42), Some("existing"));
+    assert!(app.feedback.take_action().is_none());
+}
+
+#[test]
+fn starring_works_in_replay_mode() {
+    let mut app = make_replay_app();
+    let source = make_app_with_statement(42);
+    app.update(source.snapshot.unwrap());
+    app.bottom_panel = BottomPanel::Statements;
+
+    app.handle_key(key(KeyCode::Char('*')));
+    assert!(app.starred.is_starred(42));
+}
+
+#[test]
+fn b_baselines_statements_and_flags_later_regressions() {
+    let mut app = make_app_with_statement(42);
+
+    app.handle_key(key(KeyCode::Char('b')));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveBaselines)));
+    assert_eq!(app.baselines.get(42).map(|b| b.mean_exec_time), Some(500.0));
+
+    let mut snap = app.snapshot.clone().unwrap();
+    snap.stat_statements[0].mean_exec_time = 1200.0;
+    let stmt = snap.stat_statements[0].clone();
+    app.update(snap);
+    assert_eq!(app.statement_regression(&stmt), Some(2.4));
+
+    // A stricter factor no longer counts it
+    app.config.regression_factor = 3.0;
+    assert_eq!(app.statement_regression(&stmt), None);
+}
+
+#[test]
+fn f_opens_full_screen_sql_and_esc_returns_to_inspect() {
+    let mut app = make_app_with_statement(42);
+    app.view_mode = ViewMode::Inspect(InspectTarget::Statement(42));
+    app.overlay_scroll = 3;
+
+    app.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(app.view_mode, ViewMode::SqlView(InspectTarget::Statement(42)));
+    assert_eq!(app.overlay_scroll, 0);
+
+    app.handle_key(key(KeyCode::Char('w')));
+    assert!(!app.sql_view.wrap);
+    app.sql_view.max_hscroll.set(10);
+    app.handle_key(key(KeyCode::Right));
+    app.handle_key(key(KeyCode::Right));
+    assert_eq!(app.sql_view.hscroll, 10);
+    app.handle_key(key(KeyCode::Char('n')));
+    assert!(!app.sql_view.line_numbers);
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Statement(42)));
+    assert_eq!(app.overlay_scroll, 3);
+
+    // Nothing to show for a table
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.users".into()));
+    app.handle_key(key(KeyCode::Char('f')));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.users".into())));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ignore list
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn make_app_with_tables(names: &[&str]) -> App {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.table_stats = names
+        .iter()
+        .map(|name| crate::db::models::TableStat {
+            schemaname: "public".into(),
+            relname: (*name).into(),
+            total_size_bytes: 0,
+            table_size_bytes: 0,
+            indexes_size_bytes: 0,
+            seq_scan: 0,
+            seq_tup_read: 0,
+            idx_scan: 0,
+            idx_tup_fetch: 0,
+            n_live_tup: 0,
+            n_dead_tup: 0,
+            dead_ratio: 0.0,
+            n_tup_ins: 0,
+            n_tup_upd: 0,
+            n_tup_del: 0,
+            n_tup_hot_upd: 0,
+            last_vacuum: None,
+            last_autovacuum: None,
+            last_analyze: None,
+            last_autoanalyze: None,
+            vacuum_count: 0,
+            autovacuum_count: 0,
+            bloat_bytes: None,
+            bloat_pct: None,
+            bloat_source: None,
+        })
+        .collect();
+    app.update(snap);
+    app.bottom_panel = BottomPanel::TableStats;
+    app
+}
+
+#[test]
+fn ignore_key_hides_statement_and_saves_config() {
+    let mut app = make_app_with_statement(42);
+    assert_eq!(app.sorted_stmt_indices().len(), 1);
+
+    app.handle_key(key(KeyCode::Char('-')));
+    assert!(app.config.ignore.is_statement_ignored(42));
+    assert!(app.sorted_stmt_indices().is_empty());
+    assert_eq!(app.ignored_statement_count(), 1);
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveConfig)));
+}
+
+#[test]
+fn show_ignored_reveals_and_unignores() {
+    let mut app = make_app_with_statement(42);
+    app.config.ignore.toggle_statement(42);
+    assert!(app.sorted_stmt_indices().is_empty());
+
+    app.handle_key(key(KeyCode::Char('H')));
+    assert!(app.show_ignored);
+    assert_eq!(app.sorted_stmt_indices().len(), 1);
+
+    // With ignored rows visible, '-' takes the selected one off the list
+    app.handle_key(key(KeyCode::Char('-')));
+    assert!(!app.config.ignore.is_statement_ignored(42));
+
+    app.handle_key(key(KeyCode::Char('H')));
+    assert!(!app.show_ignored);
+    assert_eq!(app.sorted_stmt_indices().len(), 1);
+}
+
+#[test]
+fn ignore_key_hides_table_and_clamps_selection() {
+    let mut app = make_app_with_tables(&["heartbeat", "users"]);
+    app.config.ignore.tables.push("*_audit".into());
+    app.panels.table_stats.state.select(Some(1));
+    let selected = app.selected_table_key().unwrap();
+
+    app.handle_key(key(KeyCode::Char('-')));
+    assert!(app.config.ignore.tables.contains(&selected));
+    assert_eq!(app.sorted_table_stat_indices().len(), 1);
+    assert_eq!(app.panels.table_stats.selected(), Some(0));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveConfig)));
+}
+
+#[test]
+fn table_patterns_from_config_hide_matching_rows() {
+    let mut app = make_app_with_tables(&["heartbeat", "orders_audit", "orders"]);
+    app.config.ignore.tables = vec!["public.heartbeat".into(), "*_audit".into()];
+
+    let snap = app.snapshot.as_ref().unwrap();
+    let visible: Vec<&str> = app
+        .sorted_table_stat_indices()
+        .into_iter()
+        .map(|i| snap.table_stats[i].relname.as_str())
+        .collect();
+    assert_eq!(visible, vec!["orders"]);
+    assert_eq!(app.ignored_table_count(), 2);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Mouse
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+fn click(column: u16, row: u16) -> MouseEvent {
+    mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+}
+
+/// Three queries in a panel drawn at rows 10-19, table rows from 12.
+fn make_app_for_mouse() -> App {
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    let first = snap.active_queries[0].clone();
+    snap.active_queries = (0..3)
+        .map(|i| ActiveQuery {
+            pid: 100 + i,
+            duration_secs: 30.0 - f64::from(i),
+            ..first.clone()
+        })
+        .collect();
+    app.update(snap);
+    let panel = ratatui::layout::Rect::new(0, 10, 80, 10);
+    app.mouse_targets = MouseTargets::for_panel(panel);
+    app.mouse_targets.table_at(panel, true);
+    app
+}
+
+#[test]
+fn clicking_a_row_selects_it_and_a_double_click_inspects_it() {
+    let mut app = make_app_for_mouse();
+    app.handle_mouse(click(5, 13));
+    assert_eq!(app.panels.queries.selected(), Some(1));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+
+    // Header, bottom border and rows past the end select nothing
+    for row in [11, 16, 19] {
+        app.handle_mouse(click(5, row));
+        assert_eq!(app.panels.queries.selected(), Some(1), "row {row}");
+    }
+
+    app.handle_mouse(click(5, 14));
+    app.handle_mouse(click(5, 14));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Query(102)));
+}
+
+#[test]
+fn clicks_land_on_the_table_where_it_was_drawn() {
+    let mut app = make_app_for_mouse();
+    // A table under a graph, inside the panel's border
+    app.mouse_targets.table_at(ratatui::layout::Rect::new(1, 14, 78, 5), false);
+    app.handle_mouse(click(5, 13));
+    assert_eq!(app.panels.queries.selected(), None, "the graph is not a row");
+    app.handle_mouse(click(5, 16));
+    assert_eq!(app.panels.queries.selected(), Some(1));
+
+    // A panel that drew no table takes no row clicks
+    app.mouse_targets = MouseTargets::for_panel(app.mouse_targets.panel);
+    app.handle_mouse(click(5, 15));
+    assert_eq!(app.panels.queries.selected(), Some(1));
+}
+
+#[test]
+fn taller_rows_shift_the_rows_below_them() {
+    let mut app = make_app_for_mouse();
+    app.mouse_targets.row_heights = vec![3, 1, 1];
+    app.handle_mouse(click(5, 14));
+    assert_eq!(app.panels.queries.selected(), Some(0));
+    app.handle_mouse(click(5, 15));
+    assert_eq!(app.panels.queries.selected(), Some(1));
+}
+
+#[test]
+fn wheel_moves_the_selection_and_tabs_switch_panels() {
+    let mut app = make_app_for_mouse();
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 5, 13));
+    app.handle_mouse(mouse(MouseEventKind::ScrollDown, 5, 13));
+    assert_eq!(app.panels.queries.selected(), Some(2));
+    app.handle_mouse(mouse(MouseEventKind::ScrollUp, 5, 13));
+    assert_eq!(app.panels.queries.selected(), Some(1));
+
+    let tab = ratatui::layout::Rect::new(20, 30, 6, 1);
+    app.mouse_targets.tabs = vec![(tab, BottomPanel::Locks)];
+    app.handle_mouse(click(25, 30));
+    assert_eq!(app.bottom_panel, BottomPanel::Locks);
+    // A second click stays put rather than toggling back like the key
+    app.handle_mouse(click(20, 30));
+    assert_eq!(app.bottom_panel, BottomPanel::Locks);
+}
+
+#[test]
+fn clicking_the_timeline_seeks_the_replay() {
+    let mut app = make_replay_app();
+    app.mouse_targets.timeline = Some(ratatui::layout::Rect::new(11, 40, 101, 1));
+    app.handle_mouse(click(36, 40));
+    assert!(matches!(
+        app.feedback.take_action(),
+        Some(AppAction::SeekReplay(SeekTarget::Fraction(f))) if (f - 0.25).abs() < 1e-9
+    ));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Column chooser
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn column_chooser_hides_columns_and_saves_the_config() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.bottom_panel = BottomPanel::Statements;
+    app.handle_key(key(KeyCode::Char('c')));
+    assert_eq!(app.view_mode, ViewMode::Columns);
+
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Char(' ')));
+    assert!(app.config.hidden_columns.is_hidden("statements", "Calls"));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveConfig)));
+    assert!(!app.visible_columns(BottomPanel::Statements)[1]);
+
+    app.handle_key(key(KeyCode::Enter));
+    assert!(!app.config.hidden_columns.is_hidden("statements", "Calls"));
+
+    app.handle_key(key(KeyCode::Esc));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn column_chooser_only_opens_on_panels_with_columns() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.bottom_panel = BottomPanel::Locks;
+    app.handle_key(key(KeyCode::Char('c')));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Buffer cache
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn buffer_cache_reads_on_b_and_enter_inspects_the_relation() {
+    let mut app = make_app();
+    app.update(make_snapshot());
+    app.bottom_panel = BottomPanel::BufferCache;
+
+    app.handle_key(key(KeyCode::Char('b')));
+    assert!(app.feedback.take_action().is_none());
+    assert_eq!(
+        app.feedback.status_message.as_deref(),
+        Some("pg_buffercache is not installed")
+    );
+
+    app.server_info.extensions.pg_buffercache = true;
+    app.handle_key(key(KeyCode::Char('b')));
+    assert!(matches!(app.feedback.take_action(), Some(AppAction::ReadBufferCache)));
+    assert!(app.feedback.buffer_cache_loading);
+
+    let relation = |relname: &str, relkind: &str| BufferCacheRelation {
+        relid: 0,
+        schemaname: "public".into(),
+        relname: relname.into(),
+        relkind: relkind.into(),
+        buffers: 10,
+        dirty: 0,
+        avg_usage: 1.0,
+        cached_pct: None,
+    };
+    app.set_buffer_cache(Ok(BufferCacheReport {
+        read_at: Utc::now(),
+        block_size: 8192,
+        total_buffers: 100,
+        unused_buffers: 80,
+        dirty_buffers: 0,
+        usage_counts: [0, 20, 0, 0, 0, 0],
+        relations: vec![relation("orders", "r"), relation("orders_pkey", "i"), relation("orders_seq", "S")],
+    }));
+    assert!(!app.feedback.buffer_cache_loading);
+    assert_eq!((BottomPanel::BufferCache.descriptor().row_count)(&app), 3);
+
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Table("public.orders".into())));
+    app.view_mode = ViewMode::Normal;
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Inspect(InspectTarget::Index("public.orders_pkey".into())));
+    app.view_mode = ViewMode::Normal;
+    // Sequences have no inspect overlay
+    app.handle_key(key(KeyCode::Down));
+    app.handle_key(key(KeyCode::Enter));
+    assert_eq!(app.view_mode, ViewMode::Normal);
+}
+
+#[test]
+fn replication_lag_history_is_kept_per_standby() {
+    use crate::db::models::ReplicationInfo;
+
+    let standby = |pid: i32, replay_lag_secs: Option<f64>| ReplicationInfo {
+        pid,
+        usesysid: None,
+        usename: None,
+        application_name: Some(format!("standby{pid}")),
+        client_addr: None,
+        client_hostname: None,
+        client_port: None,
+        backend_start: None,
+        backend_xmin: None,
+        state: Some("streaming".into()),
+        sent_lsn: None,
+        write_lsn: None,
+        flush_lsn: None,
+        replay_lsn: None,
+        write_lag_secs: None,
+        flush_lag_secs: None,
+        replay_lag_secs,
+        sync_priority: None,
+        sync_state: None,
+        reply_time: None,
+    };
+    let mut app = make_app();
+    let mut snap = make_snapshot();
+    snap.replication = vec![standby(1, Some(0.25)), standby(2, None)];
+    app.update(snap.clone());
+    snap.replication = vec![standby(1, Some(1.5)), standby(2, Some(0.01))];
+    app.update(snap.clone());
+
+    let lag = &app.metrics.replication_lag;
+    assert_eq!(lag[&1].replay.as_vec(), vec![250, 1500]);
+    // Caught up and idle reports no lag, graphed as zero
+    assert_eq!(lag[&2].replay.as_vec(), vec![0, 10]);
+    assert_eq!(lag[&2].write.as_vec(), vec![0, 0]);
+
+    // A standby that disconnects is forgotten; a reconnect starts afresh
+    snap.replication = vec![standby(1, Some(0.5))];
+    app.update(snap);
+    assert!(!app.metrics.replication_lag.contains_key(&2));
+    assert_eq!(app.metrics.replication_lag[&1].replay.as_vec(), vec![250, 1500, 500]);
+}
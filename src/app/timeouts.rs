@@ -0,0 +1,244 @@
+//! Timeout policy advisory.
+//!
+//! Cross-checks `statement_timeout`, `idle_in_transaction_session_timeout`
+//! and `lock_timeout` with the transactions actually running, and suggests
+//! concrete settings. Per-role and per-database overrides are taken into
+//! account so roles that already have a timeout aren't flagged again.
+
+use std::collections::BTreeSet;
+
+use crate::db::models::{quote_ident, PgSnapshot, RoleSetting, ServerInfo};
+
+/// Transactions open longer than this count as long-running.
+pub const LONG_TXN_SECS: f64 = 300.0;
+
+/// A timeout setting and the value we suggest for it.
+struct Policy {
+    name: &'static str,
+    recommended: &'static str,
+    why: &'static str,
+    /// Set per role rather than per database, so maintenance roles can run
+    /// longer statements
+    per_role: bool,
+}
+
+const POLICIES: [Policy; 3] = [
+    Policy {
+        name: "statement_timeout",
+        recommended: "30s",
+        why: "caps runaway queries; set per application role",
+        per_role: true,
+    },
+    Policy {
+        name: "idle_in_transaction_session_timeout",
+        recommended: "5min",
+        why: "ends sessions idling with a transaction open",
+        per_role: false,
+    },
+    Policy {
+        name: "lock_timeout",
+        recommended: "10s",
+        why: "fails lock waits instead of queueing behind a blocker",
+        per_role: true,
+    },
+];
+
+/// Server-wide value of one timeout plus the overrides that change it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeoutStatus {
+    pub name: &'static str,
+    /// Human-readable value, `off` when disabled
+    pub value: String,
+    pub source: String,
+    pub disabled: bool,
+    pub recommended: &'static str,
+    pub why: &'static str,
+    pub overrides: Vec<RoleSetting>,
+}
+
+/// A transaction open longer than [`LONG_TXN_SECS`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongTransaction {
+    pub pid: i32,
+    pub role: Option<String>,
+    pub database: Option<String>,
+    pub age_secs: f64,
+    pub idle: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutAdvisory {
+    pub timeouts: Vec<TimeoutStatus>,
+    pub long_transactions: Vec<LongTransaction>,
+    /// `ALTER ROLE/DATABASE` statements that would close the gaps
+    pub suggestions: Vec<String>,
+}
+
+impl TimeoutAdvisory {
+    pub fn build(server: &ServerInfo, snap: Option<&PgSnapshot>, dbname: &str) -> Self {
+        let timeouts: Vec<TimeoutStatus> = POLICIES
+            .iter()
+            .map(|policy| {
+                // Missing when settings couldn't be read (or in old
+                // recordings); don't guess
+                let setting = server.settings.iter().find(|s| s.name == policy.name);
+                TimeoutStatus {
+                    name: policy.name,
+                    value: setting.map_or_else(|| "unknown".into(), |s| format_timeout_ms(&s.setting)),
+                    source: setting.map_or_else(String::new, |s| s.source.clone()),
+                    disabled: setting.is_some_and(|s| is_disabled(&s.setting)),
+                    recommended: policy.recommended,
+                    why: policy.why,
+                    overrides: server
+                        .role_settings
+                        .iter()
+                        .filter(|o| o.name == policy.name)
+                        .cloned()
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let mut long_transactions: Vec<LongTransaction> = snap
+            .map(|snap| {
+                snap.active_queries
+                    .iter()
+                    .filter_map(|q| {
+                        let age_secs = (snap.timestamp - q.xact_start?).num_milliseconds() as f64 / 1000.0;
+                        (age_secs >= LONG_TXN_SECS).then(|| LongTransaction {
+                            pid: q.pid,
+                            role: q.usename.clone(),
+                            database: q.datname.clone(),
+                            age_secs,
+                            idle: q
+                                .state
+                                .as_deref()
+                                .is_some_and(|s| s.starts_with("idle in transaction")),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        long_transactions.sort_by(|a, b| b.age_secs.total_cmp(&a.age_secs));
+
+        let suggestions = suggest(&timeouts, &long_transactions, dbname);
+        Self {
+            timeouts,
+            long_transactions,
+            suggestions,
+        }
+    }
+
+    /// No timeout of any kind is set server-wide.
+    pub fn all_disabled(&self) -> bool {
+        self.timeouts.iter().all(|t| t.disabled)
+    }
+
+    /// Nothing stops the long transactions that are running right now.
+    pub fn needs_attention(&self) -> bool {
+        self.all_disabled() && !self.long_transactions.is_empty()
+    }
+}
+
+fn is_disabled(value: &str) -> bool {
+    matches!(value.trim(), "0" | "0ms" | "0s" | "0min")
+}
+
+/// Format a millisecond setting the way one would write it in SQL.
+fn format_timeout_ms(raw: &str) -> String {
+    match raw.parse::<i64>() {
+        Ok(0) => "off".into(),
+        Ok(ms) if ms % 60_000 == 0 => format!("{}min", ms / 60_000),
+        Ok(ms) if ms % 1000 == 0 => format!("{}s", ms / 1000),
+        Ok(ms) => format!("{ms}ms"),
+        Err(_) => raw.to_string(),
+    }
+}
+
+/// Whether an override turns the timeout on for `role` in `database`.
+fn covered_by_override(overrides: &[RoleSetting], role: Option<&str>, database: &str) -> bool {
+    overrides.iter().any(|o| {
+        !is_disabled(&o.value)
+            && o.role.as_deref().map_or(true, |r| Some(r) == role)
+            && o.database.as_deref().map_or(true, |d| d == database)
+    })
+}
+
+fn suggest(timeouts: &[TimeoutStatus], long: &[LongTransaction], dbname: &str) -> Vec<String> {
+    // Roles seen holding long transactions are the ones to fix first
+    let roles: BTreeSet<&str> = long.iter().filter_map(|t| t.role.as_deref()).collect();
+    let mut out = Vec::new();
+    for (status, policy) in timeouts.iter().zip(&POLICIES) {
+        if !status.disabled {
+            continue;
+        }
+        if policy.per_role {
+            if roles.is_empty() {
+                out.push(format!(
+                    "ALTER ROLE <app_role> SET {} = '{}';",
+                    policy.name, policy.recommended
+                ));
+            }
+            for role in &roles {
+                if !covered_by_override(&status.overrides, Some(role), dbname) {
+                    out.push(format!(
+                        "ALTER ROLE {} SET {} = '{}';",
+                        quote_ident(role),
+                        policy.name,
+                        policy.recommended
+                    ));
+                }
+            }
+        } else if !covered_by_override(&status.overrides, None, dbname) {
+            out.push(format!(
+                "ALTER DATABASE {} SET {} = '{}';",
+                quote_ident(dbname),
+                policy.name,
+                policy.recommended
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role_setting(role: Option<&str>, database: Option<&str>, name: &str, value: &str) -> RoleSetting {
+        RoleSetting {
+            role: role.map(String::from),
+            database: database.map(String::from),
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn formats_millisecond_settings() {
+        assert_eq!(format_timeout_ms("0"), "off");
+        assert_eq!(format_timeout_ms("30000"), "30s");
+        assert_eq!(format_timeout_ms("300000"), "5min");
+        assert_eq!(format_timeout_ms("250"), "250ms");
+    }
+
+    #[test]
+    fn override_coverage_respects_role_and_database() {
+        let overrides = vec![
+            role_setting(Some("app"), None, "statement_timeout", "30s"),
+            role_setting(Some("batch"), None, "statement_timeout", "0"),
+            role_setting(None, Some("shop"), "statement_timeout", "1min"),
+        ];
+        assert!(covered_by_override(&overrides, Some("app"), "other"));
+        assert!(!covered_by_override(&overrides, Some("batch"), "other"));
+        assert!(covered_by_override(&overrides, Some("batch"), "shop"));
+        assert!(!covered_by_override(&overrides, None, "other"));
+    }
+
+    #[test]
+    fn quotes_identifiers_that_need_it() {
+        assert_eq!(quote_ident("app_rw"), "app_rw");
+        assert_eq!(quote_ident("App"), "\"App\"");
+        assert_eq!(quote_ident("my-role"), "\"my-role\"");
+    }
+}
@@ -0,0 +1,383 @@
+//! Autovacuum health: which tables autovacuum should have reached by now.
+//!
+//! A table is due once its dead tuples pass the trigger Postgres computes,
+//! `autovacuum_vacuum_threshold + autovacuum_vacuum_scale_factor * live
+//! tuples`. The live tuple count stands in for `reltuples` and per-table
+//! storage parameters are not read, so the trigger is the server-wide one.
+//!
+//! Tables are watched across snapshots: a table still due after a few
+//! `autovacuum_naptime` rounds, with no autovacuum finishing on it in the
+//! meantime, is reported as starved. That usually means every worker is
+//! busy with something bigger, or a long transaction keeps the dead tuples
+//! from being removed.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::db::models::{quote_ident, PgSetting, PgSnapshot, TableStat, WraparoundTable};
+
+/// Naptime rounds a due table may wait before it counts as starved
+const STARVED_NAPTIMES: f64 = 3.0;
+/// Shortest wait before a due table counts as starved
+const MIN_STARVED_SECS: f64 = 120.0;
+
+/// The server-wide autovacuum settings the trigger is computed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutovacuumSettings {
+    pub enabled: bool,
+    pub threshold: f64,
+    pub scale_factor: f64,
+    pub max_workers: i64,
+    pub naptime_secs: f64,
+    /// XID age at which autovacuum forces an anti-wraparound vacuum
+    pub freeze_max_age: i64,
+}
+
+impl Default for AutovacuumSettings {
+    /// The Postgres defaults, used until the settings have been read
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 50.0,
+            scale_factor: 0.2,
+            max_workers: 3,
+            naptime_secs: 60.0,
+            freeze_max_age: 200_000_000,
+        }
+    }
+}
+
+impl AutovacuumSettings {
+    pub fn from_settings(settings: &[PgSetting]) -> Self {
+        let mut parsed = Self::default();
+        for s in settings {
+            let value = s.setting.parse::<f64>().ok();
+            match (s.name.as_str(), value) {
+                ("autovacuum", _) => parsed.enabled = s.setting == "on",
+                ("autovacuum_vacuum_threshold", Some(v)) => parsed.threshold = v,
+                ("autovacuum_vacuum_scale_factor", Some(v)) => parsed.scale_factor = v,
+                ("autovacuum_max_workers", Some(v)) => parsed.max_workers = v as i64,
+                ("autovacuum_naptime", Some(v)) => parsed.naptime_secs = v,
+                ("autovacuum_freeze_max_age", Some(v)) => parsed.freeze_max_age = v as i64,
+                _ => {}
+            }
+        }
+        parsed
+    }
+
+    /// Dead tuples past which autovacuum picks up a table with `live` rows
+    pub fn trigger(&self, live: i64) -> i64 {
+        (self.threshold + self.scale_factor * live.max(0) as f64).round() as i64
+    }
+
+    /// How long a table may stay due before it counts as starved
+    pub fn starved_after_secs(&self) -> f64 {
+        (self.naptime_secs * STARVED_NAPTIMES).max(MIN_STARVED_SECS)
+    }
+
+    /// Tables worth a manual `VACUUM (FREEZE)`: those past half of
+    /// `autovacuum_freeze_max_age`, oldest first, each table once even when
+    /// both it and its TOAST relation are old.
+    pub fn freeze_candidates<'a>(&self, tables: &'a [WraparoundTable]) -> Vec<&'a WraparoundTable> {
+        let mut candidates: Vec<&WraparoundTable> = tables
+            .iter()
+            .filter(|t| i64::from(t.xid_age) > self.freeze_max_age / 2)
+            .collect();
+        candidates.sort_by_key(|t| Reverse(t.xid_age));
+        let mut seen = HashSet::new();
+        candidates.retain(|t| seen.insert(t.key()));
+        candidates
+    }
+}
+
+/// The statement that freezes `table`.
+pub fn freeze_sql(table: &WraparoundTable) -> String {
+    format!("VACUUM (FREEZE, VERBOSE) {}.{};", quote_ident(&table.schemaname), quote_ident(&table.relname))
+}
+
+/// Where a table stands with autovacuum, most urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutovacuumStatus {
+    Starved,
+    Due,
+    Vacuuming,
+    Ok,
+}
+
+impl AutovacuumStatus {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Starved => "starved",
+            Self::Due => "due",
+            Self::Vacuuming => "vacuuming",
+            Self::Ok => "ok",
+        }
+    }
+}
+
+/// One table in the Autovacuum Health panel.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AutovacuumRow {
+    pub schemaname: String,
+    pub relname: String,
+    pub status: AutovacuumStatus,
+    pub n_dead_tup: i64,
+    /// Dead tuples that trigger autovacuum
+    pub trigger: i64,
+    /// How long the table has been past its trigger, as far as seen
+    pub due_secs: Option<f64>,
+    /// Time since the last vacuum of either kind
+    pub since_vacuum_secs: Option<f64>,
+    /// Autovacuums finished on the table since it was first seen
+    pub autovacuum_runs: i64,
+}
+
+impl AutovacuumRow {
+    /// `schema.table`, the key the table inspect overlay looks up
+    pub fn key(&self) -> String {
+        format!("{}.{}", self.schemaname, self.relname)
+    }
+
+    fn fill(&self) -> f64 {
+        self.n_dead_tup as f64 / self.trigger.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TableWatch {
+    seen_at: DateTime<Utc>,
+    first_count: i64,
+    last_count: i64,
+    due_since: Option<DateTime<Utc>>,
+}
+
+/// Tables watched across snapshots, keyed `schema.table`.
+#[derive(Debug, Clone, Default)]
+pub struct AutovacuumTracker {
+    tables: HashMap<String, TableWatch>,
+}
+
+fn table_key(t: &TableStat) -> String {
+    format!("{}.{}", t.schemaname, t.relname)
+}
+
+impl AutovacuumTracker {
+    /// Note the tables in `snap`; ones no longer listed are forgotten.
+    pub fn observe(&mut self, snap: &PgSnapshot, settings: &AutovacuumSettings) {
+        let at = snap.timestamp;
+        self.tables
+            .retain(|key, _| snap.table_stats.iter().any(|t| table_key(t) == *key));
+        for t in &snap.table_stats {
+            let due = t.n_dead_tup > settings.trigger(t.n_live_tup);
+            let watch = self.tables.entry(table_key(t)).or_insert(TableWatch {
+                seen_at: at,
+                first_count: t.autovacuum_count,
+                last_count: t.autovacuum_count,
+                due_since: None,
+            });
+            // Seeking backwards in a replay or a stats reset starts over
+            if at < watch.seen_at || t.autovacuum_count < watch.last_count {
+                *watch = TableWatch {
+                    seen_at: at,
+                    first_count: t.autovacuum_count,
+                    last_count: t.autovacuum_count,
+                    due_since: None,
+                };
+            }
+            // A finished autovacuum restarts the wait
+            if t.autovacuum_count > watch.last_count {
+                watch.due_since = None;
+            }
+            watch.due_since = if due { watch.due_since.or(Some(at)) } else { None };
+            watch.seen_at = at;
+            watch.last_count = t.autovacuum_count;
+        }
+    }
+
+    /// Every table in `snap`, starved and due ones first, then by how far
+    /// past their trigger they are.
+    pub fn rows(&self, snap: &PgSnapshot, settings: &AutovacuumSettings) -> Vec<AutovacuumRow> {
+        let now = snap.timestamp;
+        let secs_since = |t: DateTime<Utc>| ((now - t).num_milliseconds() as f64 / 1000.0).max(0.0);
+        let mut rows: Vec<AutovacuumRow> = snap
+            .table_stats
+            .iter()
+            .map(|t| {
+                let key = table_key(t);
+                let watch = self.tables.get(&key);
+                let due_secs = watch.and_then(|w| w.due_since).map(secs_since);
+                let vacuuming = snap
+                    .vacuum_progress
+                    .iter()
+                    .any(|v| v.table_name == key || v.table_name == t.relname);
+                let status = match due_secs {
+                    _ if vacuuming => AutovacuumStatus::Vacuuming,
+                    Some(secs) if secs >= settings.starved_after_secs() => AutovacuumStatus::Starved,
+                    Some(_) => AutovacuumStatus::Due,
+                    None => AutovacuumStatus::Ok,
+                };
+                let last_vacuum = t.last_vacuum.max(t.last_autovacuum);
+                AutovacuumRow {
+                    schemaname: t.schemaname.clone(),
+                    relname: t.relname.clone(),
+                    status,
+                    n_dead_tup: t.n_dead_tup,
+                    trigger: settings.trigger(t.n_live_tup),
+                    due_secs,
+                    since_vacuum_secs: last_vacuum.map(secs_since),
+                    autovacuum_runs: watch.map_or(0, |w| t.autovacuum_count - w.first_count),
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then(b.fill().total_cmp(&a.fill()))
+        });
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActivitySummary, BufferCacheStats};
+    use chrono::Duration;
+
+    fn setting(name: &str, value: &str) -> PgSetting {
+        PgSetting {
+            category: "Autovacuum".into(),
+            context: "sighup".into(),
+            ..PgSetting::fixture(name, value)
+        }
+    }
+
+    fn table(relname: &str, n_live_tup: i64, n_dead_tup: i64, autovacuum_count: i64) -> TableStat {
+        TableStat {
+            schemaname: "public".into(),
+            relname: relname.into(),
+            total_size_bytes: 0,
+            table_size_bytes: 0,
+            indexes_size_bytes: 0,
+            seq_scan: 0,
+            seq_tup_read: 0,
+            idx_scan: 0,
+            idx_tup_fetch: 0,
+            n_live_tup,
+            n_dead_tup,
+            dead_ratio: 0.0,
+            n_tup_ins: 0,
+            n_tup_upd: 0,
+            n_tup_del: 0,
+            n_tup_hot_upd: 0,
+            last_vacuum: None,
+            last_autovacuum: None,
+            last_analyze: None,
+            last_autoanalyze: None,
+            vacuum_count: 0,
+            autovacuum_count,
+            bloat_bytes: None,
+            bloat_pct: None,
+            bloat_source: None,
+        }
+    }
+
+    fn snapshot(timestamp: DateTime<Utc>, table_stats: Vec<TableStat>) -> PgSnapshot {
+        PgSnapshot {
+            timestamp,
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 1,
+                ..Default::default()
+            },
+            table_stats,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn freeze_candidates_are_past_half_the_freeze_max_age() {
+        let settings = AutovacuumSettings::from_settings(&[setting("autovacuum_freeze_max_age", "100000000")]);
+        let wrap = |relname: &str, via_toast: bool, xid_age: i32| WraparoundTable {
+            schemaname: "public".into(),
+            relname: relname.into(),
+            via_toast,
+            xid_age,
+            size_bytes: 0,
+        };
+        let tables = vec![
+            wrap("events", true, 90_000_000),
+            wrap("Orders", false, 120_000_000),
+            wrap("events", false, 80_000_000),
+            wrap("users", false, 10_000_000),
+        ];
+        let candidates = settings.freeze_candidates(&tables);
+        let names: Vec<_> = candidates.iter().map(|t| (t.relname.as_str(), t.via_toast)).collect();
+        assert_eq!(names, vec![("Orders", false), ("events", true)]);
+        assert_eq!(freeze_sql(candidates[0]), "VACUUM (FREEZE, VERBOSE) public.\"Orders\";");
+    }
+
+    #[test]
+    fn settings_give_the_trigger_and_the_starvation_wait() {
+        let settings = AutovacuumSettings::from_settings(&[
+            setting("autovacuum", "off"),
+            setting("autovacuum_vacuum_threshold", "100"),
+            setting("autovacuum_vacuum_scale_factor", "0.1"),
+            setting("autovacuum_max_workers", "5"),
+            setting("autovacuum_naptime", "10"),
+        ]);
+        assert!(!settings.enabled);
+        assert_eq!(settings.max_workers, 5);
+        assert_eq!(settings.trigger(10_000), 1_100);
+        // A short naptime still leaves a couple of minutes
+        assert_eq!(settings.starved_after_secs(), MIN_STARVED_SECS);
+
+        let defaults = AutovacuumSettings::from_settings(&[]);
+        assert_eq!(defaults.trigger(1_000), 250);
+        assert_eq!(defaults.starved_after_secs(), 180.0);
+    }
+
+    #[test]
+    fn due_tables_starve_until_an_autovacuum_finishes() {
+        let settings = AutovacuumSettings::default();
+        let start = Utc::now();
+        let mut tracker = AutovacuumTracker::default();
+        let at = |secs: i64| start + Duration::seconds(secs);
+        let status = |tracker: &AutovacuumTracker, snap: &PgSnapshot, name: &str| {
+            tracker.rows(snap, &settings).into_iter().find(|r| r.relname == name).unwrap().status
+        };
+
+        // 1000 live rows trigger at 250 dead
+        let snap = snapshot(at(0), vec![table("orders", 1_000, 400, 2), table("users", 1_000, 10, 0)]);
+        tracker.observe(&snap, &settings);
+        assert_eq!(status(&tracker, &snap, "orders"), AutovacuumStatus::Due);
+        assert_eq!(status(&tracker, &snap, "users"), AutovacuumStatus::Ok);
+
+        let snap = snapshot(at(200), vec![table("orders", 1_000, 600, 2), table("users", 1_000, 10, 0)]);
+        tracker.observe(&snap, &settings);
+        let rows = tracker.rows(&snap, &settings);
+        assert_eq!(rows[0].relname, "orders");
+        assert_eq!(rows[0].status, AutovacuumStatus::Starved);
+        assert_eq!(rows[0].due_secs, Some(200.0));
+
+        // An autovacuum finished but left it due again: the wait restarts
+        let snap = snapshot(at(260), vec![table("orders", 1_000, 300, 3)]);
+        tracker.observe(&snap, &settings);
+        let rows = tracker.rows(&snap, &settings);
+        assert_eq!(rows[0].status, AutovacuumStatus::Due);
+        assert_eq!(rows[0].due_secs, Some(0.0));
+        assert_eq!(rows[0].autovacuum_runs, 1);
+
+        // Seeking back in a replay starts over
+        let snap = snapshot(at(100), vec![table("orders", 1_000, 300, 2)]);
+        tracker.observe(&snap, &settings);
+        assert_eq!(tracker.rows(&snap, &settings)[0].autovacuum_runs, 0);
+    }
+}
@@ -0,0 +1,242 @@
+//! Mean execution time baselines for `pg_stat_statements` entries, persisted
+//! next to the config file so plan regressions show up across sessions.
+//! queryids are only meaningful on the server that computed them, so each
+//! `host:port/dbname` keeps its own file, as recordings are named.
+//!
+//! A statement has regressed when its current mean time is more than
+//! `regression_factor` times the mean recorded in its baseline. Baselines
+//! are taken with `b` in the Statements panel, from every statement in the
+//! current snapshot.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::db::models::StatStatement;
+
+/// Fewer calls than this, on either side, make the means too noisy to compare
+const MIN_CALLS: i64 = 5;
+
+/// The mean time of one statement when it was baselined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub mean_exec_time: f64,
+    pub calls: i64,
+    pub taken_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineEntry {
+    queryid: i64,
+    #[serde(flatten)]
+    baseline: Baseline,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    #[serde(default)]
+    baseline: Vec<BaselineEntry>,
+}
+
+/// Baselines by queryid (empty when none were taken).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StatementBaselines {
+    baselines: BTreeMap<i64, Baseline>,
+    /// Where `save` writes: the file of the server they were loaded for
+    path: Option<PathBuf>,
+}
+
+impl StatementBaselines {
+    /// The baselines file of one server and database.
+    pub fn path(host: &str, port: u16, dbname: &str) -> Option<PathBuf> {
+        let name = format!("{host}_{port}_{dbname}.toml").replace(['/', '\\'], "_");
+        dirs::config_dir().map(|d| d.join("pg_glimpse").join("baselines").join(name))
+    }
+
+    pub fn load(host: &str, port: u16, dbname: &str) -> Self {
+        let path = Self::path(host, port, dbname);
+        Self {
+            path: path.clone(),
+            ..path.map_or_else(Self::default, |p| Self::load_from(&p))
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let file: BaselineFile = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            baselines: file
+                .baseline
+                .into_iter()
+                .map(|e| (e.queryid, e.baseline))
+                .collect(),
+            path: None,
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = &self.path {
+            self.save_to(path);
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let file = BaselineFile {
+            baseline: self
+                .baselines
+                .iter()
+                .map(|(&queryid, &baseline)| BaselineEntry { queryid, baseline })
+                .collect(),
+        };
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            let _ = fs::write(path, contents);
+        }
+    }
+
+    pub fn get(&self, queryid: i64) -> Option<&Baseline> {
+        self.baselines.get(&queryid)
+    }
+
+    /// Replace the baselines of `statements` with their current means.
+    /// Statements with too few calls keep whatever baseline they had, as do
+    /// windowed ones: their mean moves whenever a bucket expires.
+    /// Returns how many were baselined.
+    pub fn rebaseline(&mut self, statements: &[StatStatement], taken_at: DateTime<Utc>) -> usize {
+        let mut count = 0;
+        for stmt in statements.iter().filter(|s| s.calls >= MIN_CALLS && !s.windowed) {
+            self.baselines.insert(
+                stmt.queryid,
+                Baseline {
+                    mean_exec_time: stmt.mean_exec_time,
+                    calls: stmt.calls,
+                    taken_at,
+                },
+            );
+            count += 1;
+        }
+        count
+    }
+
+    /// How many times slower than its baseline `stmt` runs, if that is
+    /// more than `factor`. A windowed mean is not comparable with a
+    /// cumulative one.
+    pub fn regression(&self, stmt: &StatStatement, factor: f64) -> Option<f64> {
+        let baseline = self.get(stmt.queryid)?;
+        if stmt.calls < MIN_CALLS || stmt.windowed || baseline.mean_exec_time <= 0.0 {
+            return None;
+        }
+        let ratio = stmt.mean_exec_time / baseline.mean_exec_time;
+        (ratio > factor).then_some(ratio)
+    }
+
+    pub fn len(&self) -> usize {
+        self.baselines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.baselines.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn stmt(queryid: i64, calls: i64, mean_exec_time: f64) -> StatStatement {
+        StatStatement {
+            queryid,
+            query: format!("SELECT {queryid}"),
+            calls,
+            total_exec_time: mean_exec_time * calls as f64,
+            min_exec_time: 0.0,
+            mean_exec_time,
+            max_exec_time: 0.0,
+            stddev_exec_time: 0.0,
+            rows: 0,
+            shared_blks_hit: 0,
+            shared_blks_read: 0,
+            shared_blks_dirtied: 0,
+            shared_blks_written: 0,
+            local_blks_hit: 0,
+            local_blks_read: 0,
+            local_blks_dirtied: 0,
+            local_blks_written: 0,
+            temp_blks_read: 0,
+            temp_blks_written: 0,
+            blk_read_time: 0.0,
+            blk_write_time: 0.0,
+            hit_ratio: 1.0,
+            windowed: false,
+        }
+    }
+
+    #[test]
+    fn regressions_are_means_past_the_factor_with_enough_calls() {
+        let taken_at = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let mut baselines = StatementBaselines::default();
+        let count = baselines.rebaseline(&[stmt(1, 100, 2.0), stmt(2, 100, 0.0), stmt(3, 2, 5.0)], taken_at);
+        assert_eq!(count, 2);
+        assert!(baselines.get(3).is_none());
+
+        assert_eq!(baselines.regression(&stmt(1, 200, 5.0), 2.0), Some(2.5));
+        assert_eq!(baselines.regression(&stmt(1, 200, 4.0), 2.0), None);
+        // Too few calls since a reset to tell
+        assert_eq!(baselines.regression(&stmt(1, 3, 50.0), 2.0), None);
+        // A zero baseline can't be compared against
+        assert_eq!(baselines.regression(&stmt(2, 200, 5.0), 2.0), None);
+        assert_eq!(baselines.regression(&stmt(4, 200, 5.0), 2.0), None);
+    }
+
+    #[test]
+    fn windowed_means_are_neither_baselined_nor_compared() {
+        let taken_at = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let windowed = |queryid, calls, mean| StatStatement {
+            windowed: true,
+            ..stmt(queryid, calls, mean)
+        };
+        let mut baselines = StatementBaselines::default();
+        assert_eq!(baselines.rebaseline(&[stmt(1, 100, 2.0), windowed(2, 100, 2.0)], taken_at), 1);
+        assert!(baselines.get(2).is_none());
+        assert_eq!(baselines.regression(&windowed(1, 200, 50.0), 2.0), None);
+    }
+
+    #[test]
+    fn roundtrip_through_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("baselines.toml");
+        let taken_at = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        let mut baselines = StatementBaselines::default();
+        baselines.rebaseline(&[stmt(-123_456_789, 10, 1.5), stmt(7, 50, 0.25)], taken_at);
+        baselines.save_to(&path);
+
+        let loaded = StatementBaselines::load_from(&path);
+        assert_eq!(loaded, baselines);
+        assert_eq!(loaded.get(7).map(|b| b.taken_at), Some(taken_at));
+
+        assert!(StatementBaselines::load_from(&dir.path().join("missing.toml")).is_empty());
+    }
+
+    #[test]
+    fn each_server_and_database_has_its_own_file() {
+        let Some(orders) = StatementBaselines::path("db1", 5432, "orders") else {
+            return;
+        };
+        assert_eq!(orders.file_name().unwrap(), "db1_5432_orders.toml");
+        assert_ne!(StatementBaselines::path("db1", 5433, "orders"), Some(orders.clone()));
+        assert_ne!(StatementBaselines::path("db2", 5432, "orders"), Some(orders.clone()));
+        assert_ne!(StatementBaselines::path("db1", 5432, "billing"), Some(orders));
+        // A socket directory is not a path into the config dir
+        let socket = StatementBaselines::path("/var/run/postgresql", 5432, "orders").unwrap();
+        assert_eq!(socket.file_name().unwrap(), "_var_run_postgresql_5432_orders.toml");
+    }
+}
@@ -0,0 +1,146 @@
+//! Replay bookmarks: intervals of a recording marked with `[` and `]`,
+//! persisted next to the config file so the recordings browser can list
+//! them. Recordings are known by file name, so a bookmark follows its
+//! recording when the recordings directory moves.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// File name of the recording (its first segment, if rotated; see
+    /// [`crate::recorder::first_segment_name`])
+    pub recording: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Bookmark {
+    pub fn duration_secs(&self) -> f64 {
+        (self.end - self.start).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    bookmark: Vec<Bookmark>,
+}
+
+/// Every bookmark made in replays, oldest first.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bookmarks {
+    list: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("pg_glimpse").join("bookmarks.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path().map_or_else(Self::default, |p| Self::load_from(&p))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let file: BookmarksFile = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { list: file.bookmark }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = Self::path() {
+            self.save_to(&path);
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let file = BookmarksFile {
+            bookmark: self.list.clone(),
+        };
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            let _ = fs::write(path, contents);
+        }
+    }
+
+    /// Add a bookmark unless the same interval is already marked. Returns
+    /// whether it was added.
+    pub fn add(&mut self, bookmark: Bookmark) -> bool {
+        if self.list.contains(&bookmark) {
+            return false;
+        }
+        self.list.push(bookmark);
+        true
+    }
+
+    /// Bookmarks in `recording`, earliest first.
+    pub fn for_recording(&self, recording: &str) -> Vec<&Bookmark> {
+        let mut marks: Vec<&Bookmark> = self.list.iter().filter(|b| b.recording == recording).collect();
+        marks.sort_by_key(|b| (b.start, b.end));
+        marks
+    }
+
+    /// Forget the bookmarks of a deleted recording. Returns whether there
+    /// were any.
+    pub fn remove_recording(&mut self, recording: &str) -> bool {
+        let before = self.list.len();
+        self.list.retain(|b| b.recording != recording);
+        self.list.len() != before
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn mark(recording: &str, start_min: u32, end_min: u32) -> Bookmark {
+        let at = |min| Utc.with_ymd_and_hms(2024, 1, 15, 12, min, 0).unwrap();
+        Bookmark {
+            recording: recording.into(),
+            start: at(start_min),
+            end: at(end_min),
+        }
+    }
+
+    #[test]
+    fn bookmarks_are_listed_per_recording_in_order() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.add(mark("a.jsonl", 30, 40)));
+        assert!(bookmarks.add(mark("b.jsonl", 0, 5)));
+        assert!(bookmarks.add(mark("a.jsonl", 10, 15)));
+        assert!(!bookmarks.add(mark("a.jsonl", 10, 15)));
+
+        let in_a = bookmarks.for_recording("a.jsonl");
+        assert_eq!(in_a, vec![&mark("a.jsonl", 10, 15), &mark("a.jsonl", 30, 40)]);
+        assert!((in_a[0].duration_secs() - 300.0).abs() < f64::EPSILON);
+
+        assert!(bookmarks.remove_recording("a.jsonl"));
+        assert!(bookmarks.for_recording("a.jsonl").is_empty());
+        assert!(!bookmarks.remove_recording("a.jsonl"));
+    }
+
+    #[test]
+    fn bookmarks_roundtrip_through_the_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bookmarks.toml");
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.add(mark("a.jsonl", 10, 15));
+        bookmarks.save_to(&path);
+
+        assert_eq!(Bookmarks::load_from(&path), bookmarks);
+        assert!(Bookmarks::load_from(&dir.path().join("missing.toml")).is_empty());
+    }
+}
@@ -1,9 +1,17 @@
 use ratatui::style::Color;
 use ratatui::symbols::Marker;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::adaptive_refresh::AdaptiveRefreshConfig;
+use crate::alerts::AlertRule;
+use crate::hooks::SnapshotHooks;
+use crate::notifications::NotificationConfig;
+use crate::row_colors::RowColorRule;
+use crate::schedule::SnapshotSchedule;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GraphMarkerStyle {
     #[default]
@@ -55,6 +63,8 @@ pub enum ColorTheme {
     SolarizedDark,
     SolarizedLight,
     CatppuccinLatte,
+    HighContrast,
+    Monochrome,
 }
 
 impl ColorTheme {
@@ -65,18 +75,22 @@ impl ColorTheme {
             Self::Nord => Self::SolarizedDark,
             Self::SolarizedDark => Self::SolarizedLight,
             Self::SolarizedLight => Self::CatppuccinLatte,
-            Self::CatppuccinLatte => Self::TokyoNight,
+            Self::CatppuccinLatte => Self::HighContrast,
+            Self::HighContrast => Self::Monochrome,
+            Self::Monochrome => Self::TokyoNight,
         }
     }
 
     pub const fn prev(self) -> Self {
         match self {
-            Self::TokyoNight => Self::CatppuccinLatte,
+            Self::TokyoNight => Self::Monochrome,
             Self::Dracula => Self::TokyoNight,
             Self::Nord => Self::Dracula,
             Self::SolarizedDark => Self::Nord,
             Self::SolarizedLight => Self::SolarizedDark,
             Self::CatppuccinLatte => Self::SolarizedLight,
+            Self::HighContrast => Self::CatppuccinLatte,
+            Self::Monochrome => Self::HighContrast,
         }
     }
 
@@ -88,6 +102,8 @@ impl ColorTheme {
             Self::SolarizedDark => "Solarized Dark",
             Self::SolarizedLight => "Solarized Light",
             Self::CatppuccinLatte => "Catppuccin Latte",
+            Self::HighContrast => "High Contrast",
+            Self::Monochrome => "Monochrome",
         }
     }
 
@@ -99,6 +115,8 @@ impl ColorTheme {
             Self::SolarizedDark => ThemeColors::solarized_dark(),
             Self::SolarizedLight => ThemeColors::solarized_light(),
             Self::CatppuccinLatte => ThemeColors::catppuccin_latte(),
+            Self::HighContrast => ThemeColors::high_contrast(),
+            Self::Monochrome => ThemeColors::monochrome(),
         }
     }
 }
@@ -290,6 +308,64 @@ impl ThemeColors {
             sql_comment: Color::Rgb(140, 143, 161),  // overlay0
         }
     }
+
+    /// The 16 bright ANSI colors on black, which every terminal renders
+    /// at full strength
+    pub const fn high_contrast() -> Self {
+        Self {
+            header_bg: Color::Black,
+            fg: Color::White,
+            fg_dim: Color::Gray,
+            border_active: Color::LightCyan,
+            border_warn: Color::LightYellow,
+            border_danger: Color::LightRed,
+            border_ok: Color::LightGreen,
+            border_dim: Color::Gray,
+            graph_connections: Color::LightCyan,
+            graph_cache: Color::LightMagenta,
+            graph_latency: Color::LightGreen,
+            duration_ok: Color::LightGreen,
+            duration_warn: Color::LightYellow,
+            duration_danger: Color::LightRed,
+            state_active: Color::LightGreen,
+            state_idle_txn: Color::LightYellow,
+            overlay_bg: Color::Black,
+            highlight_bg: Color::Blue,
+            sql_keyword: Color::LightMagenta,
+            sql_string: Color::LightGreen,
+            sql_number: Color::LightYellow,
+            sql_comment: Color::Gray,
+        }
+    }
+
+    /// Black, white and grays only, for monochrome terminals and color
+    /// blindness; warnings stand out by their markers, not their color
+    pub const fn monochrome() -> Self {
+        Self {
+            header_bg: Color::Black,
+            fg: Color::White,
+            fg_dim: Color::Gray,
+            border_active: Color::White,
+            border_warn: Color::White,
+            border_danger: Color::White,
+            border_ok: Color::White,
+            border_dim: Color::DarkGray,
+            graph_connections: Color::White,
+            graph_cache: Color::White,
+            graph_latency: Color::White,
+            duration_ok: Color::Gray,
+            duration_warn: Color::White,
+            duration_danger: Color::White,
+            state_active: Color::White,
+            state_idle_txn: Color::White,
+            overlay_bg: Color::Black,
+            highlight_bg: Color::DarkGray,
+            sql_keyword: Color::White,
+            sql_string: Color::Gray,
+            sql_number: Color::Gray,
+            sql_comment: Color::DarkGray,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -298,11 +374,74 @@ pub struct AppConfig {
     pub graph_marker: GraphMarkerStyle,
     pub color_theme: ColorTheme,
     pub show_emojis: bool,
+    /// No emoji or box drawing, and the graphs as numbers, for screen
+    /// readers and limited terminals
+    pub plain_mode: bool,
+    /// Draw the Avg Duration graph as a heatmap of running query durations
+    pub latency_heatmap: bool,
+    /// Mark rows that are new or changed sharply since the previous refresh
+    pub highlight_changes: bool,
     pub refresh_interval_secs: u64,
+    /// Refresh slower while the server is quiet, faster under load
+    pub adaptive_refresh: AdaptiveRefreshConfig,
     pub warn_duration_secs: f64,
     pub danger_duration_secs: f64,
     pub recording_retention_secs: u64,
+    /// Start a new recording file once the current one reaches this many
+    /// MB; 0 never does
+    pub recording_rotate_mb: u64,
+    /// Start a new recording file after this many minutes; 0 never does
+    pub recording_rotate_mins: u64,
     pub recordings_dir: Option<String>,
+    /// Record operator actions (panel switches, filters, cancels) into recordings
+    pub record_ui_events: bool,
+    /// Blend graph values between snapshots while a replay is playing
+    pub replay_interpolation: bool,
+    /// Sample starred statements every second to estimate latency percentiles
+    pub latency_sampler: bool,
+    /// Sample wait events several times a second for the Wait Events panel
+    pub wait_sampler: bool,
+    /// After a cancel or terminate, read the server log for the backend's
+    /// own account of it (needs superuser or EXECUTE on the log functions)
+    pub confirm_from_log: bool,
+    /// Read the lines written to the server log each refresh for the Logs
+    /// panel and recordings (same privileges as `confirm_from_log`)
+    pub server_log: bool,
+    /// Flag sessions idle in transaction for longer than this; 0 turns the
+    /// watchdog off
+    pub idle_txn_watchdog_secs: u64,
+    /// Warn when `pg_wal` grows past this many MB; 0 warns past the
+    /// server's `max_wal_size`
+    pub wal_dir_warn_mb: u64,
+    /// Flag statements whose mean time is more than this many times their
+    /// baseline (taken with `b` in the Statements panel)
+    pub regression_factor: f64,
+    /// Statements and tables hidden from their panels
+    pub ignore: IgnoreList,
+    /// Columns switched off with the column chooser, by panel id
+    pub hidden_columns: HiddenColumns,
+    /// Alert rules checked against every snapshot
+    pub alerts: Vec<AlertRule>,
+    /// Threshold rules coloring Statements, Table Stats and Indexes rows
+    pub row_colors: Vec<RowColorRule>,
+    /// Bell and desktop notifications for long queries and blocking
+    pub notifications: NotificationConfig,
+    /// SQL run before and after every snapshot
+    pub hooks: SnapshotHooks,
+    /// How often table and index stats are read, and when not at all
+    pub schedule: SnapshotSchedule,
+    /// Named connection profiles, picked with `--profile`
+    pub profiles: BTreeMap<String, Profile>,
+    /// The profile picked with `--profile`, shown in the header
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+    /// `--plain` was given: plain mode for this session, whatever the
+    /// setting
+    #[serde(skip)]
+    pub plain_flag: bool,
+    /// The global settings the active profile replaced, put back on save
+    #[serde(skip)]
+    replaced_globals: Profile,
 }
 
 impl Default for AppConfig {
@@ -311,15 +450,57 @@ impl Default for AppConfig {
             graph_marker: GraphMarkerStyle::Braille,
             color_theme: ColorTheme::TokyoNight,
             show_emojis: true,
+            plain_mode: false,
+            latency_heatmap: false,
+            highlight_changes: false,
             refresh_interval_secs: 2,
+            adaptive_refresh: AdaptiveRefreshConfig::default(),
             warn_duration_secs: 1.0,
             danger_duration_secs: 10.0,
             recording_retention_secs: 3600,
+            recording_rotate_mb: 0,
+            recording_rotate_mins: 0,
             recordings_dir: None,
+            record_ui_events: false,
+            replay_interpolation: true,
+            latency_sampler: false,
+            wait_sampler: false,
+            confirm_from_log: false,
+            server_log: false,
+            idle_txn_watchdog_secs: 0,
+            wal_dir_warn_mb: 0,
+            regression_factor: 2.0,
+            ignore: IgnoreList::default(),
+            hidden_columns: HiddenColumns::default(),
+            alerts: AlertRule::defaults(),
+            row_colors: Vec::new(),
+            notifications: NotificationConfig::default(),
+            hooks: SnapshotHooks::default(),
+            schedule: SnapshotSchedule::default(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            plain_flag: false,
+            replaced_globals: Profile::default(),
         }
     }
 }
 
+/// A named set of connection parameters and display settings, e.g.
+/// `[profiles.prod]` in config.toml. Anything left out falls back to the
+/// global settings, and command-line flags win over both.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub dbname: Option<String>,
+    pub user: Option<String>,
+    pub color_theme: Option<ColorTheme>,
+    pub refresh_interval_secs: Option<u64>,
+    pub warn_duration_secs: Option<f64>,
+    pub danger_duration_secs: Option<f64>,
+}
+
 impl AppConfig {
     fn config_path() -> Option<PathBuf> {
         dirs::config_dir().map(|d| d.join("pg_glimpse").join("config.toml"))
@@ -333,6 +514,64 @@ impl AppConfig {
             .map_or_else(|_| Self::default(), |contents| toml::from_str(&contents).unwrap_or_default())
     }
 
+    /// Plain mode, from the setting or `--plain`.
+    pub const fn plain(&self) -> bool {
+        self.plain_mode || self.plain_flag
+    }
+
+    /// Switch to the profile `name`, replacing the global settings it sets.
+    /// Errors with the known names when there is no such profile.
+    pub fn apply_profile(&mut self, name: &str) -> Result<&Profile, String> {
+        let Some(profile) = self.profiles.get(name) else {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            return Err(if known.is_empty() {
+                format!("no profile named '{name}': config.toml has no [profiles.*] sections")
+            } else {
+                format!("no profile named '{name}' (known: {})", known.join(", "))
+            });
+        };
+        let mut replaced = Profile::default();
+        if let Some(theme) = profile.color_theme {
+            replaced.color_theme = Some(std::mem::replace(&mut self.color_theme, theme));
+        }
+        if let Some(secs) = profile.refresh_interval_secs {
+            replaced.refresh_interval_secs = Some(std::mem::replace(&mut self.refresh_interval_secs, secs));
+        }
+        if let Some(secs) = profile.warn_duration_secs {
+            replaced.warn_duration_secs = Some(std::mem::replace(&mut self.warn_duration_secs, secs));
+        }
+        if let Some(secs) = profile.danger_duration_secs {
+            replaced.danger_duration_secs = Some(std::mem::replace(&mut self.danger_duration_secs, secs));
+        }
+        self.replaced_globals = replaced;
+        self.active_profile = Some(name.to_string());
+        Ok(&self.profiles[name])
+    }
+
+    /// The config as written to disk: changes to settings the active
+    /// profile sets go to the profile, and the global values stay as they
+    /// were.
+    fn to_saved(&self) -> Self {
+        let mut saved = self.clone();
+        let Some(profile) = self.active_profile.as_ref().and_then(|name| saved.profiles.get_mut(name)) else {
+            return saved;
+        };
+        let globals = &self.replaced_globals;
+        if let Some(theme) = globals.color_theme {
+            profile.color_theme = Some(std::mem::replace(&mut saved.color_theme, theme));
+        }
+        if let Some(secs) = globals.refresh_interval_secs {
+            profile.refresh_interval_secs = Some(std::mem::replace(&mut saved.refresh_interval_secs, secs));
+        }
+        if let Some(secs) = globals.warn_duration_secs {
+            profile.warn_duration_secs = Some(std::mem::replace(&mut saved.warn_duration_secs, secs));
+        }
+        if let Some(secs) = globals.danger_duration_secs {
+            profile.danger_duration_secs = Some(std::mem::replace(&mut saved.danger_duration_secs, secs));
+        }
+        saved
+    }
+
     pub fn save(&self) {
         let Some(path) = Self::config_path() else {
             return;
@@ -340,33 +579,164 @@ impl AppConfig {
         if let Some(parent) = path.parent() {
             let _ = fs::create_dir_all(parent);
         }
-        if let Ok(contents) = toml::to_string_pretty(self) {
+        if let Ok(contents) = toml::to_string_pretty(&self.to_saved()) {
             let _ = fs::write(&path, contents);
         }
     }
 }
 
+/// Known-noisy entries (monitoring queries, heartbeat tables) to hide.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IgnoreList {
+    /// `pg_stat_statements` queryids
+    pub queryids: Vec<i64>,
+    /// Table patterns, matched against `schema.table` and the bare table
+    /// name. `*` matches any run of characters, e.g. `public.heartbeat_*`.
+    pub tables: Vec<String>,
+}
+
+impl IgnoreList {
+    pub fn is_statement_ignored(&self, queryid: i64) -> bool {
+        self.queryids.contains(&queryid)
+    }
+
+    pub fn is_table_ignored(&self, schema: &str, table: &str) -> bool {
+        let qualified = format!("{schema}.{table}");
+        self.tables
+            .iter()
+            .any(|p| glob_match(p, &qualified) || glob_match(p, table))
+    }
+
+    /// Add or remove a queryid. Returns whether it is now ignored.
+    pub fn toggle_statement(&mut self, queryid: i64) -> bool {
+        if let Some(pos) = self.queryids.iter().position(|&q| q == queryid) {
+            self.queryids.remove(pos);
+            false
+        } else {
+            self.queryids.push(queryid);
+            true
+        }
+    }
+
+    /// Add or remove the exact `schema.table` pattern. Returns whether the
+    /// table is now ignored; it may still match a wildcard pattern.
+    pub fn toggle_table(&mut self, schema: &str, table: &str) -> bool {
+        let qualified = format!("{schema}.{table}");
+        if let Some(pos) = self.tables.iter().position(|p| *p == qualified) {
+            self.tables.remove(pos);
+        } else {
+            self.tables.push(qualified);
+        }
+        self.is_table_ignored(schema, table)
+    }
+}
+
+/// Column headers hidden per panel, keyed by the panel's stable id.
+///
+/// Only hidden columns are stored, so columns added in later versions show
+/// up without touching the config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HiddenColumns(BTreeMap<String, Vec<String>>);
+
+impl HiddenColumns {
+    pub fn is_hidden(&self, panel: &str, column: &str) -> bool {
+        self.0.get(panel).is_some_and(|hidden| hidden.iter().any(|c| c == column))
+    }
+
+    /// Which of `columns` are shown, in order.
+    pub fn visible(&self, panel: &str, columns: &[&str]) -> Vec<bool> {
+        columns.iter().map(|c| !self.is_hidden(panel, c)).collect()
+    }
+
+    /// Hide or show a column. Returns whether it is now shown; the last
+    /// visible column of `columns` stays shown.
+    pub fn toggle(&mut self, panel: &str, column: &str, columns: &[&str]) -> bool {
+        let hidden = self.0.entry(panel.to_string()).or_default();
+        if let Some(pos) = hidden.iter().position(|c| c == column) {
+            hidden.remove(pos);
+        } else if columns.iter().any(|c| *c != column && !hidden.iter().any(|h| h == c)) {
+            hidden.push(column.to_string());
+        }
+        let shown = !hidden.iter().any(|c| c == column);
+        if hidden.is_empty() {
+            self.0.remove(panel);
+        }
+        shown
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: must match exactly
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigItem {
     GraphMarker,
     ColorTheme,
     ShowEmojis,
+    PlainMode,
+    LatencyHeatmap,
+    HighlightChanges,
     RefreshInterval,
+    AdaptiveRefresh,
     WarnDuration,
     DangerDuration,
     RecordingRetention,
+    RecordUiEvents,
+    ReplayInterpolation,
+    LatencySampler,
+    WaitSampler,
+    ConfirmFromLog,
+    ServerLog,
+    IdleTxnWatchdog,
+    WalDirWarn,
+    LongQueryNotify,
+    BlockingNotify,
     RecordingsDir,
 }
 
 impl ConfigItem {
-    pub const ALL: [Self; 8] = [
+    pub const ALL: [Self; 22] = [
         Self::GraphMarker,
         Self::ColorTheme,
         Self::ShowEmojis,
+        Self::PlainMode,
+        Self::LatencyHeatmap,
+        Self::HighlightChanges,
         Self::RefreshInterval,
+        Self::AdaptiveRefresh,
         Self::WarnDuration,
         Self::DangerDuration,
         Self::RecordingRetention,
+        Self::RecordUiEvents,
+        Self::ReplayInterpolation,
+        Self::LatencySampler,
+        Self::WaitSampler,
+        Self::ConfirmFromLog,
+        Self::ServerLog,
+        Self::IdleTxnWatchdog,
+        Self::WalDirWarn,
+        Self::LongQueryNotify,
+        Self::BlockingNotify,
         Self::RecordingsDir,
     ];
 
@@ -375,10 +745,24 @@ impl ConfigItem {
             Self::GraphMarker => "Graph Marker",
             Self::ColorTheme => "Color Theme",
             Self::ShowEmojis => "Show Emojis",
+            Self::PlainMode => "Plain Mode",
+            Self::LatencyHeatmap => "Latency Heatmap",
+            Self::HighlightChanges => "Highlight Changes",
             Self::RefreshInterval => "Refresh Interval",
+            Self::AdaptiveRefresh => "Adaptive Refresh",
             Self::WarnDuration => "Warn Duration",
             Self::DangerDuration => "Danger Duration",
             Self::RecordingRetention => "Recording Retention",
+            Self::RecordUiEvents => "Record Actions",
+            Self::ReplayInterpolation => "Smooth Replay",
+            Self::LatencySampler => "Latency Sampler",
+            Self::WaitSampler => "Wait Sampler",
+            Self::ConfirmFromLog => "Confirm From Log",
+            Self::ServerLog => "Server Log Tail",
+            Self::IdleTxnWatchdog => "Idle Txn Watchdog",
+            Self::WalDirWarn => "WAL Dir Warning",
+            Self::LongQueryNotify => "Long Query Notify",
+            Self::BlockingNotify => "Blocking Notify",
             Self::RecordingsDir => "Recordings Dir",
         }
     }
@@ -454,12 +838,16 @@ mod tests {
         assert_eq!(ColorTheme::Nord.next(), ColorTheme::SolarizedDark);
         assert_eq!(ColorTheme::SolarizedDark.next(), ColorTheme::SolarizedLight);
         assert_eq!(ColorTheme::SolarizedLight.next(), ColorTheme::CatppuccinLatte);
-        assert_eq!(ColorTheme::CatppuccinLatte.next(), ColorTheme::TokyoNight);
+        assert_eq!(ColorTheme::CatppuccinLatte.next(), ColorTheme::HighContrast);
+        assert_eq!(ColorTheme::HighContrast.next(), ColorTheme::Monochrome);
+        assert_eq!(ColorTheme::Monochrome.next(), ColorTheme::TokyoNight);
     }
 
     #[test]
     fn color_theme_prev_cycles() {
-        assert_eq!(ColorTheme::TokyoNight.prev(), ColorTheme::CatppuccinLatte);
+        assert_eq!(ColorTheme::TokyoNight.prev(), ColorTheme::Monochrome);
+        assert_eq!(ColorTheme::Monochrome.prev(), ColorTheme::HighContrast);
+        assert_eq!(ColorTheme::HighContrast.prev(), ColorTheme::CatppuccinLatte);
         assert_eq!(ColorTheme::CatppuccinLatte.prev(), ColorTheme::SolarizedLight);
         assert_eq!(ColorTheme::SolarizedLight.prev(), ColorTheme::SolarizedDark);
         assert_eq!(ColorTheme::SolarizedDark.prev(), ColorTheme::Nord);
@@ -476,6 +864,8 @@ mod tests {
             ColorTheme::SolarizedDark,
             ColorTheme::SolarizedLight,
             ColorTheme::CatppuccinLatte,
+            ColorTheme::HighContrast,
+            ColorTheme::Monochrome,
         ] {
             assert_eq!(theme.next().prev(), theme);
             assert_eq!(theme.prev().next(), theme);
@@ -491,6 +881,8 @@ mod tests {
             ColorTheme::SolarizedDark,
             ColorTheme::SolarizedLight,
             ColorTheme::CatppuccinLatte,
+            ColorTheme::HighContrast,
+            ColorTheme::Monochrome,
         ] {
             assert!(!theme.label().is_empty(), "{theme:?} has empty label");
         }
@@ -506,6 +898,8 @@ mod tests {
             ColorTheme::SolarizedDark,
             ColorTheme::SolarizedLight,
             ColorTheme::CatppuccinLatte,
+            ColorTheme::HighContrast,
+            ColorTheme::Monochrome,
         ] {
             let colors = theme.colors();
             // Verify some colors are set (not default/black)
@@ -513,6 +907,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn plain_flag_is_not_saved() {
+        let mut config = AppConfig::default();
+        assert!(!config.plain());
+        config.plain_flag = true;
+        assert!(config.plain());
+        let saved: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert!(!saved.plain());
+        config.plain_mode = true;
+        let saved: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert!(saved.plain());
+    }
+
     #[test]
     fn color_theme_default() {
         assert_eq!(ColorTheme::default(), ColorTheme::TokyoNight);
@@ -540,11 +947,37 @@ mod tests {
             graph_marker: GraphMarkerStyle::Block,
             color_theme: ColorTheme::Nord,
             show_emojis: true,
+            plain_mode: false,
+            latency_heatmap: false,
+            highlight_changes: false,
             refresh_interval_secs: 5,
+            adaptive_refresh: AdaptiveRefreshConfig::default(),
             warn_duration_secs: 2.5,
             danger_duration_secs: 15.0,
             recording_retention_secs: 7200,
+            recording_rotate_mb: 0,
+            recording_rotate_mins: 0,
             recordings_dir: None,
+            record_ui_events: false,
+            replay_interpolation: true,
+            latency_sampler: false,
+            wait_sampler: false,
+            confirm_from_log: false,
+            server_log: false,
+            idle_txn_watchdog_secs: 300,
+            wal_dir_warn_mb: 0,
+            regression_factor: 2.0,
+            ignore: IgnoreList::default(),
+            hidden_columns: HiddenColumns::default(),
+            alerts: AlertRule::defaults(),
+            row_colors: Vec::new(),
+            notifications: NotificationConfig::default(),
+            hooks: SnapshotHooks::default(),
+            schedule: SnapshotSchedule::default(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            plain_flag: false,
+            replaced_globals: Profile::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -559,6 +992,7 @@ mod tests {
             parsed.recording_retention_secs,
             config.recording_retention_secs
         );
+        assert_eq!(parsed.idle_txn_watchdog_secs, 300);
     }
 
     #[test]
@@ -587,11 +1021,37 @@ mod tests {
             graph_marker: GraphMarkerStyle::HalfBlock,
             color_theme: ColorTheme::Dracula,
             show_emojis: false,
+            plain_mode: false,
+            latency_heatmap: false,
+            highlight_changes: false,
             refresh_interval_secs: 3,
+            adaptive_refresh: AdaptiveRefreshConfig::default(),
             warn_duration_secs: 0.5,
             danger_duration_secs: 5.0,
             recording_retention_secs: 1800,
+            recording_rotate_mb: 0,
+            recording_rotate_mins: 0,
             recordings_dir: None,
+            record_ui_events: false,
+            replay_interpolation: true,
+            latency_sampler: false,
+            wait_sampler: false,
+            confirm_from_log: false,
+            server_log: false,
+            idle_txn_watchdog_secs: 0,
+            wal_dir_warn_mb: 0,
+            regression_factor: 2.0,
+            ignore: IgnoreList::default(),
+            hidden_columns: HiddenColumns::default(),
+            alerts: AlertRule::defaults(),
+            row_colors: Vec::new(),
+            notifications: NotificationConfig::default(),
+            hooks: SnapshotHooks::default(),
+            schedule: SnapshotSchedule::default(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            plain_flag: false,
+            replaced_globals: Profile::default(),
         };
 
         let json_str = serde_json::to_string(&config).unwrap();
@@ -601,6 +1061,187 @@ mod tests {
         assert_eq!(parsed.color_theme, config.color_theme);
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // IgnoreList tests
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("public.heartbeat", "public.heartbeat"));
+        assert!(!glob_match("public.heartbeat", "public.heartbeats"));
+        assert!(glob_match("public.heartbeat_*", "public.heartbeat_2024"));
+        assert!(glob_match("*.heartbeat", "monitor.heartbeat"));
+        assert!(glob_match("*beat*", "public.heartbeat_log"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxcyyb"));
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn ignore_list_tables_match_qualified_or_bare_name() {
+        let ignore = IgnoreList {
+            queryids: vec![],
+            tables: vec!["heartbeat".into(), "audit.*".into()],
+        };
+        assert!(ignore.is_table_ignored("public", "heartbeat"));
+        assert!(ignore.is_table_ignored("audit", "events"));
+        assert!(!ignore.is_table_ignored("public", "events"));
+    }
+
+    #[test]
+    fn ignore_list_toggles() {
+        let mut ignore = IgnoreList::default();
+        assert!(ignore.toggle_statement(42));
+        assert!(ignore.is_statement_ignored(42));
+        assert!(!ignore.toggle_statement(42));
+        assert!(!ignore.is_statement_ignored(42));
+
+        assert!(ignore.toggle_table("public", "heartbeat"));
+        assert_eq!(ignore.tables, vec!["public.heartbeat".to_string()]);
+        assert!(!ignore.toggle_table("public", "heartbeat"));
+        assert!(ignore.tables.is_empty());
+
+        // Still ignored through a wildcard after removing the exact entry
+        ignore.tables.push("public.*".into());
+        assert!(ignore.toggle_table("public", "heartbeat"));
+        assert!(ignore.toggle_table("public", "heartbeat"));
+    }
+
+    #[test]
+    fn ignore_list_toml_roundtrip() {
+        let mut config = AppConfig::default();
+        config.ignore.queryids = vec![-5, 7];
+        config.ignore.tables = vec!["public.heartbeat_*".into()];
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.ignore, config.ignore);
+
+        // Older config files without the section still load
+        let parsed: AppConfig = toml::from_str("show_emojis = false").unwrap();
+        assert_eq!(parsed.ignore, IgnoreList::default());
+    }
+
+    #[test]
+    fn hidden_columns_toggle_and_roundtrip() {
+        let columns = ["Query", "Calls", "Rows"];
+        let mut hidden = HiddenColumns::default();
+        assert!(!hidden.toggle("statements", "Rows", &columns));
+        assert!(!hidden.toggle("statements", "Calls", &columns));
+        // The last visible column cannot be hidden
+        assert!(hidden.toggle("statements", "Query", &columns));
+        assert_eq!(hidden.visible("statements", &columns), vec![true, false, false]);
+        assert!(hidden.visible("indexes", &columns).iter().all(|&v| v));
+
+        let mut config = AppConfig {
+            hidden_columns: hidden,
+            ..AppConfig::default()
+        };
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_str.contains("[hidden_columns]"), "{toml_str}");
+        let parsed: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.hidden_columns, config.hidden_columns);
+
+        // Showing everything again drops the panel's entry
+        config.hidden_columns.toggle("statements", "Rows", &columns);
+        assert!(config.hidden_columns.toggle("statements", "Calls", &columns));
+        assert_eq!(config.hidden_columns, HiddenColumns::default());
+    }
+
+    #[test]
+    fn alert_rules_toml_roundtrip() {
+        use crate::alerts::AlertMetric;
+
+        let parsed: AppConfig = toml::from_str(
+            r#"
+            [[alerts]]
+            name = "Lag"
+            metric = "replication_lag_secs"
+            above = 30
+            for_secs = 60
+            "#,
+        )
+        .unwrap();
+        assert_eq!(parsed.alerts.len(), 1);
+        assert_eq!(parsed.alerts[0].metric, AlertMetric::ReplicationLagSecs);
+        let toml_str = toml::to_string_pretty(&parsed).unwrap();
+        assert_eq!(toml::from_str::<AppConfig>(&toml_str).unwrap().alerts, parsed.alerts);
+
+        // Config files without rules get the defaults; an empty list turns them off
+        let parsed: AppConfig = toml::from_str("show_emojis = false").unwrap();
+        assert_eq!(parsed.alerts, AlertRule::defaults());
+        let parsed: AppConfig = toml::from_str("alerts = []").unwrap();
+        assert!(parsed.alerts.is_empty());
+    }
+
+    #[test]
+    fn row_color_rules_toml_roundtrip() {
+        use crate::row_colors::{RowColor, RowColumn};
+
+        let parsed: AppConfig = toml::from_str(
+            r#"
+            [[row_colors]]
+            column = "mean_exec_time"
+            above = 100
+            color = "danger"
+
+            [[row_colors]]
+            column = "hit_pct"
+            below = 90
+            color = "warn"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(parsed.row_colors.len(), 2);
+        assert_eq!(parsed.row_colors[1].column, RowColumn::HitPct);
+        assert_eq!(parsed.row_colors[1].color, RowColor::Warn);
+        let toml_str = toml::to_string_pretty(&parsed).unwrap();
+        assert_eq!(toml::from_str::<AppConfig>(&toml_str).unwrap().row_colors, parsed.row_colors);
+
+        assert!(AppConfig::default().row_colors.is_empty());
+    }
+
+    #[test]
+    fn profile_replaces_globals_and_saves_back_to_itself() {
+        let mut config: AppConfig = toml::from_str(
+            r#"
+            color_theme = "Nord"
+            refresh_interval_secs = 2
+
+            [profiles.prod]
+            host = "db1.internal"
+            dbname = "shop"
+            color_theme = "Dracula"
+            warn_duration_secs = 0.5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.apply_profile("staging"),
+            Err("no profile named 'staging' (known: prod)".to_string())
+        );
+        assert_eq!(config.apply_profile("prod").unwrap().host.as_deref(), Some("db1.internal"));
+        assert_eq!(config.active_profile.as_deref(), Some("prod"));
+        assert_eq!(config.color_theme, ColorTheme::Dracula);
+        assert_eq!(config.warn_duration_secs, 0.5);
+        // Left to the global setting
+        assert_eq!(config.refresh_interval_secs, 2);
+
+        // A theme change in the Config overlay belongs to the profile
+        config.color_theme = ColorTheme::SolarizedDark;
+        config.refresh_interval_secs = 5;
+        let saved = config.to_saved();
+        assert_eq!(saved.color_theme, ColorTheme::Nord);
+        assert_eq!(saved.warn_duration_secs, 1.0);
+        assert_eq!(saved.refresh_interval_secs, 5);
+        assert_eq!(saved.profiles["prod"].color_theme, Some(ColorTheme::SolarizedDark));
+        assert_eq!(saved.profiles["prod"].warn_duration_secs, Some(0.5));
+        assert_eq!(saved.profiles["prod"].refresh_interval_secs, None);
+        let reloaded: AppConfig = toml::from_str(&toml::to_string_pretty(&saved).unwrap()).unwrap();
+        assert_eq!(reloaded.profiles, saved.profiles);
+        assert_eq!(reloaded.active_profile, None);
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // ConfigItem tests
     // ─────────────────────────────────────────────────────────────────────────────
@@ -608,16 +1249,30 @@ mod tests {
     #[test]
     fn config_item_all_contains_all_variants() {
         // Ensure ALL array has correct count
-        assert_eq!(ConfigItem::ALL.len(), 8);
+        assert_eq!(ConfigItem::ALL.len(), 22);
 
         // Ensure all variants are present
         assert!(ConfigItem::ALL.contains(&ConfigItem::GraphMarker));
         assert!(ConfigItem::ALL.contains(&ConfigItem::ColorTheme));
         assert!(ConfigItem::ALL.contains(&ConfigItem::ShowEmojis));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::PlainMode));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::LatencyHeatmap));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::HighlightChanges));
         assert!(ConfigItem::ALL.contains(&ConfigItem::RefreshInterval));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::AdaptiveRefresh));
         assert!(ConfigItem::ALL.contains(&ConfigItem::WarnDuration));
         assert!(ConfigItem::ALL.contains(&ConfigItem::DangerDuration));
         assert!(ConfigItem::ALL.contains(&ConfigItem::RecordingRetention));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::RecordUiEvents));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::ReplayInterpolation));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::LatencySampler));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::WaitSampler));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::ConfirmFromLog));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::ServerLog));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::IdleTxnWatchdog));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::WalDirWarn));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::LongQueryNotify));
+        assert!(ConfigItem::ALL.contains(&ConfigItem::BlockingNotify));
         assert!(ConfigItem::ALL.contains(&ConfigItem::RecordingsDir));
     }
 
@@ -804,11 +1459,37 @@ mod tests {
             graph_marker: GraphMarkerStyle::HalfBlock,
             color_theme: ColorTheme::Dracula,
             show_emojis: true,
+            plain_mode: false,
+            latency_heatmap: false,
+            highlight_changes: false,
             refresh_interval_secs: 5,
+            adaptive_refresh: AdaptiveRefreshConfig::default(),
             warn_duration_secs: 2.5,
             danger_duration_secs: 15.0,
             recording_retention_secs: 7200,
+            recording_rotate_mb: 0,
+            recording_rotate_mins: 0,
             recordings_dir: None,
+            record_ui_events: false,
+            replay_interpolation: true,
+            latency_sampler: false,
+            wait_sampler: false,
+            confirm_from_log: false,
+            server_log: false,
+            idle_txn_watchdog_secs: 0,
+            wal_dir_warn_mb: 0,
+            regression_factor: 2.0,
+            ignore: IgnoreList::default(),
+            hidden_columns: HiddenColumns::default(),
+            alerts: AlertRule::defaults(),
+            row_colors: Vec::new(),
+            notifications: NotificationConfig::default(),
+            hooks: SnapshotHooks::default(),
+            schedule: SnapshotSchedule::default(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            plain_flag: false,
+            replaced_globals: Profile::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -853,6 +1534,8 @@ mod tests {
             ColorTheme::SolarizedDark,
             ColorTheme::SolarizedLight,
             ColorTheme::CatppuccinLatte,
+            ColorTheme::HighContrast,
+            ColorTheme::Monochrome,
         ] {
             let json = serde_json::to_string(&theme).unwrap();
             let parsed: ColorTheme = serde_json::from_str(&json).unwrap();
@@ -1012,11 +1695,37 @@ mod tests {
                     graph_marker: GraphMarkerStyle::Braille,
                     color_theme: ColorTheme::TokyoNight,
                     show_emojis: true,
+                    plain_mode: false,
+                    latency_heatmap: false,
+            highlight_changes: false,
                     refresh_interval_secs: refresh,
+                    adaptive_refresh: AdaptiveRefreshConfig::default(),
                     warn_duration_secs: warn,
                     danger_duration_secs: danger,
                     recording_retention_secs: retention,
+                    recording_rotate_mb: 0,
+                    recording_rotate_mins: 0,
                     recordings_dir: None,
+                    record_ui_events: false,
+                    replay_interpolation: true,
+                    latency_sampler: false,
+                    wait_sampler: false,
+            confirm_from_log: false,
+            server_log: false,
+            idle_txn_watchdog_secs: 0,
+            wal_dir_warn_mb: 0,
+            regression_factor: 2.0,
+                    ignore: IgnoreList::default(),
+                    hidden_columns: HiddenColumns::default(),
+                    alerts: AlertRule::defaults(),
+                    row_colors: Vec::new(),
+                    notifications: NotificationConfig::default(),
+                    hooks: SnapshotHooks::default(),
+                    schedule: SnapshotSchedule::default(),
+                    profiles: BTreeMap::new(),
+                    active_profile: None,
+                    plain_flag: false,
+                    replaced_globals: Profile::default(),
                 };
 
                 let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -6,7 +6,10 @@ use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
 use rustls::DigitallySignedStruct;
 use std::io;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio_postgres::{AsyncMessage, Notification};
 
 /// Connection error types
 #[derive(Error, Debug)]
@@ -92,15 +95,31 @@ impl SslMode {
     }
 }
 
-/// Spawn the connection handler task
-fn spawn_connection<S, T>(connection: tokio_postgres::Connection<S, T>)
-where
+/// Spawn the connection handler task. With `notifications`, NOTIFY
+/// messages are forwarded there; the sender is dropped when the
+/// connection ends.
+fn spawn_connection<S, T>(
+    mut connection: tokio_postgres::Connection<S, T>,
+    notifications: Option<mpsc::UnboundedSender<Notification>>,
+) where
     S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
     T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
 {
+    // A dropped connection surfaces through `Client::is_closed` and the
+    // snapshot source reconnects; printing here would garble the TUI
     tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            eprintln!("PostgreSQL connection error: {e}");
+        let Some(tx) = notifications else {
+            let _ = connection.await;
+            return;
+        };
+        while let Some(Ok(message)) =
+            std::future::poll_fn(|cx| connection.poll_message(cx)).await
+        {
+            if let AsyncMessage::Notification(n) = message {
+                if tx.send(n).is_err() {
+                    break;
+                }
+            }
         }
     });
 }
@@ -170,30 +189,99 @@ pub async fn try_connect(
     pg_config: &tokio_postgres::Config,
     ssl_mode: SslMode,
     cert_config: &SslCertConfig,
+) -> Result<tokio_postgres::Client, ConnectionError> {
+    connect_with(pg_config, ssl_mode, cert_config, None).await
+}
+
+async fn connect_with(
+    pg_config: &tokio_postgres::Config,
+    ssl_mode: SslMode,
+    cert_config: &SslCertConfig,
+    notifications: Option<mpsc::UnboundedSender<Notification>>,
 ) -> Result<tokio_postgres::Client, ConnectionError> {
     match ssl_mode {
         SslMode::None => {
             let (client, connection) = pg_config.connect(tokio_postgres::NoTls).await?;
-            spawn_connection(connection);
+            spawn_connection(connection, notifications);
             Ok(client)
         }
         SslMode::Verified => {
             let tls_config = build_tls_config(true, cert_config)?;
             let tls = tokio_postgres_rustls::MakeRustlsConnect::new(tls_config);
             let (client, connection) = pg_config.connect(tls).await?;
-            spawn_connection(connection);
+            spawn_connection(connection, notifications);
             Ok(client)
         }
         SslMode::Insecure => {
             let tls_config = build_tls_config(false, cert_config)?;
             let tls = tokio_postgres_rustls::MakeRustlsConnect::new(tls_config);
             let (client, connection) = pg_config.connect(tls).await?;
-            spawn_connection(connection);
+            spawn_connection(connection, notifications);
             Ok(client)
         }
     }
 }
 
+/// Everything needed to open the same connection again after it drops.
+#[derive(Clone)]
+pub struct Reconnect {
+    pub pg_config: tokio_postgres::Config,
+    pub ssl_mode: SslMode,
+    pub cert_config: SslCertConfig,
+}
+
+impl Reconnect {
+    /// Connect with the SSL mode that worked the first time.
+    pub async fn connect(&self) -> Result<tokio_postgres::Client, ConnectionError> {
+        try_connect(&self.pg_config, self.ssl_mode, &self.cert_config).await
+    }
+
+    /// Open a separate connection and `LISTEN` on `channel`. Notifications
+    /// arrive on the receiver, which closes when the connection drops.
+    pub async fn listen(
+        &self,
+        channel: &str,
+    ) -> Result<(tokio_postgres::Client, mpsc::UnboundedReceiver<Notification>), ConnectionError>
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let client = connect_with(&self.pg_config, self.ssl_mode, &self.cert_config, Some(tx)).await?;
+        client.batch_execute(&format!("LISTEN {}", quote_channel(channel))).await?;
+        Ok((client, rx))
+    }
+}
+
+/// Quote a channel name as an identifier, so any name can be listened on.
+fn quote_channel(channel: &str) -> String {
+    format!("\"{}\"", channel.replace('"', "\"\""))
+}
+
+/// Longest wait between reconnect attempts.
+const MAX_BACKOFF_SECS: u64 = 30;
+
+/// Exponential backoff between reconnect attempts: 1s, 2s, 4s, … capped
+/// at 30s.
+#[derive(Debug, Default)]
+pub struct Backoff {
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Attempts made so far.
+    pub const fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Delay before the next attempt; counts the attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let secs = 1u64
+            .checked_shl(self.attempt)
+            .unwrap_or(MAX_BACKOFF_SECS)
+            .min(MAX_BACKOFF_SECS);
+        self.attempt = self.attempt.saturating_add(1);
+        Duration::from_secs(secs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +313,18 @@ mod tests {
         (cert_path, key_path)
     }
 
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        let mut backoff = Backoff::default();
+        let delays: Vec<u64> = (0..8).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30, 30]);
+        assert_eq!(backoff.attempt(), 8);
+
+        let mut backoff = Backoff { attempt: u32::MAX - 1 };
+        assert_eq!(backoff.next_delay().as_secs(), 30);
+        assert_eq!(backoff.next_delay().as_secs(), 30);
+    }
+
     #[test]
     fn test_ssl_mode_labels() {
         assert_eq!(SslMode::None.label(), "No TLS");
@@ -296,4 +396,10 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ConnectionError::Certificate(_)));
     }
+
+    #[test]
+    fn channel_names_are_quoted_verbatim() {
+        assert_eq!(quote_channel("big_batch"), "\"big_batch\"");
+        assert_eq!(quote_channel("Batch \"1\""), "\"Batch \"\"1\"\"\"");
+    }
 }
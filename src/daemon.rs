@@ -0,0 +1,283 @@
+//! Headless recording (`pg_glimpse record`).
+//!
+//! Runs the snapshot pipeline and the recorder without a terminal, for
+//! capturing a server around the clock and replaying the recordings later.
+//! With `--daemon` it behaves like a service: it writes a pidfile, stops
+//! cleanly on SIGTERM and logs timestamped status lines for the journal.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{bail, Context, Result};
+
+use crate::db::models::PgSnapshot;
+use crate::recorder::{Recorder, UiEvent};
+use crate::runtime::{Engine, HeadlessUi, SnapshotSink};
+
+/// Refresh periods without a snapshot before the status line says so.
+const STALE_REFRESHES: i64 = 3;
+
+/// What `record` was asked to do.
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    pub daemon: bool,
+    pub pidfile: Option<PathBuf>,
+    pub status_interval: Duration,
+}
+
+/// What has been recorded so far, shared between the recorder and the
+/// status log.
+#[derive(Debug, Default)]
+pub struct RecordStatus {
+    pub snapshots: u64,
+    pub last_snapshot: Option<DateTime<Utc>>,
+    /// Segment written last
+    pub path: Option<PathBuf>,
+    pub write_errors: u64,
+    pub last_error: Option<String>,
+}
+
+impl RecordStatus {
+    /// One status line: how much is recorded where, or what is wrong.
+    pub fn line(&self, now: DateTime<Utc>, refresh_secs: u64) -> String {
+        let mut line = match self.last_snapshot {
+            None => "waiting for the first snapshot".to_string(),
+            Some(last) => {
+                let ago = (now - last).num_seconds().max(0);
+                let mut line = format!("{} snapshots recorded, last {ago}s ago", self.snapshots);
+                if ago > STALE_REFRESHES * refresh_secs.max(1) as i64 {
+                    line.push_str(" (no snapshot for a while; is the server reachable?)");
+                }
+                line
+            }
+        };
+        if let Some(path) = &self.path {
+            line.push_str(&format!(" → {}", path.display()));
+        }
+        if let Some(error) = &self.last_error {
+            line.push_str(&format!("; {} write errors, last: {error}", self.write_errors));
+        }
+        line
+    }
+}
+
+/// The recorder, keeping `RecordStatus` up to date as it writes.
+pub struct StatusRecorder {
+    recorder: Recorder,
+    status: Arc<Mutex<RecordStatus>>,
+}
+
+impl StatusRecorder {
+    pub fn new(recorder: Recorder, status: Arc<Mutex<RecordStatus>>) -> Self {
+        Self { recorder, status }
+    }
+}
+
+impl SnapshotSink for StatusRecorder {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        let result = self.recorder.record(snapshot);
+        if let Ok(mut status) = self.status.lock() {
+            match &result {
+                Ok(()) => {
+                    status.snapshots += 1;
+                    status.last_snapshot = Some(snapshot.timestamp);
+                }
+                Err(e) => {
+                    status.write_errors += 1;
+                    status.last_error = Some(e.to_string());
+                }
+            }
+            status.path = Some(self.recorder.current_path());
+        }
+        result
+    }
+
+    fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        self.recorder.record_event(event)
+    }
+}
+
+/// Holds the pidfile while recording and removes it when dropped.
+pub struct Pidfile {
+    path: PathBuf,
+}
+
+impl Pidfile {
+    /// Write this process's ID to `path`. Refuses if the file names another
+    /// process that is still running; a stale one is replaced.
+    pub fn create(path: &Path) -> Result<Self> {
+        if let Some(pid) = read_pid(path) {
+            if pid != std::process::id() && process_alive(pid) {
+                bail!("{} belongs to running process {pid}", path.display());
+            }
+        }
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("could not create {}", dir.display()))?;
+        }
+        fs::write(path, format!("{}\n", std::process::id()))
+            .with_context(|| format!("could not write pidfile {}", path.display()))?;
+        Ok(Self { path: path.to_path_buf() })
+    }
+}
+
+impl Drop for Pidfile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Whether `pid` is running, asked with `kill -0` so it works on any unix.
+/// Elsewhere a leftover pidfile is assumed stale.
+fn process_alive(pid: u32) -> bool {
+    // Larger values would wrap to a process group
+    cfg!(unix)
+        && i32::try_from(pid).is_ok()
+        && std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+}
+
+/// SIGTERM, the way service managers stop a process. Registered once, so a
+/// signal arriving while the loop handles something else isn't missed.
+struct Terminate {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Terminate {
+    fn new() -> Self {
+        Self {
+            #[cfg(unix)]
+            signal: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).ok(),
+        }
+    }
+
+    /// Resolves on SIGTERM; never where it can't be received.
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            signal.recv().await;
+            return;
+        }
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Print a progress line, timestamped when running as a daemon.
+fn log(daemon: bool, message: &str) {
+    if daemon {
+        eprintln!("{} {message}", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"));
+    } else {
+        eprintln!("{message}");
+    }
+}
+
+/// Record until SIGTERM, Ctrl-C or the snapshot source going away, logging
+/// status every `status_interval`.
+pub async fn run_record(
+    mut engine: Engine,
+    status: Arc<Mutex<RecordStatus>>,
+    target: &str,
+    options: RecordOptions,
+) -> Result<()> {
+    let _pidfile = match &options.pidfile {
+        Some(path) => Some(Pidfile::create(path)?),
+        None => None,
+    };
+    let refresh_secs = engine.app.refresh_interval_secs;
+    let stop_hint = if options.daemon { "" } else { " (Ctrl-C to stop)" };
+    log(
+        options.daemon,
+        &format!("Recording {target} every {refresh_secs}s, pid {}{stop_hint}", std::process::id()),
+    );
+
+    engine.request_snapshot();
+    let mut ui = HeadlessUi;
+    let run = engine.run(&mut ui, None);
+    tokio::pin!(run);
+    let period = options.status_interval.max(Duration::from_secs(1));
+    let mut status_tick = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    let mut sigterm = Terminate::new();
+    let reason = loop {
+        tokio::select! {
+            res = &mut run => {
+                res?;
+                break "snapshot source closed";
+            }
+            _ = status_tick.tick() => {
+                if let Ok(status) = status.lock() {
+                    log(options.daemon, &status.line(Utc::now(), refresh_secs));
+                }
+            }
+            () = sigterm.recv() => break "SIGTERM",
+            _ = tokio::signal::ctrl_c() => break "interrupted",
+        }
+    };
+
+    if let Ok(status) = status.lock() {
+        log(options.daemon, &format!("Stopped ({reason}): {}", status.line(Utc::now(), refresh_secs)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn status_line_reports_progress_and_staleness() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let mut status = RecordStatus::default();
+        assert_eq!(status.line(now, 2), "waiting for the first snapshot");
+
+        status.snapshots = 42;
+        status.last_snapshot = Some(now - chrono::Duration::seconds(2));
+        status.path = Some(PathBuf::from("/rec/db_5432.jsonl"));
+        assert_eq!(status.line(now, 2), "42 snapshots recorded, last 2s ago → /rec/db_5432.jsonl");
+
+        status.last_snapshot = Some(now - chrono::Duration::seconds(30));
+        status.write_errors = 1;
+        status.last_error = Some("No space left on device".into());
+        let line = status.line(now, 2);
+        assert!(line.contains("no snapshot for a while"));
+        assert!(line.ends_with("; 1 write errors, last: No space left on device"));
+    }
+
+    #[test]
+    fn pidfile_is_written_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run/record.pid");
+        {
+            let _pidfile = Pidfile::create(&path).unwrap();
+            assert_eq!(read_pid(&path), Some(std::process::id()));
+        }
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn running_process_is_alive() {
+        assert!(process_alive(std::process::id()));
+        assert!(!process_alive(u32::MAX - 1));
+    }
+
+    #[test]
+    fn stale_pidfile_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("record.pid");
+        // PIDs never get this high (pid_max is at most 2^22 on Linux)
+        fs::write(&path, "2147483646\n").unwrap();
+        let _pidfile = Pidfile::create(&path).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+    }
+}
@@ -0,0 +1,188 @@
+//! What the monitoring role is allowed to see, probed once at connect.
+//!
+//! Without `pg_monitor` or superuser a lot of what pg_glimpse reads comes
+//! back as NULLs or permission errors. Rather than failing the same query on
+//! every refresh (and filling the server log with the errors), the snapshot
+//! pipeline skips what the role can't run and the UI says what is missing.
+
+use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+
+use super::error::{DbError, Result};
+use super::models::DetectedExtensions;
+
+/// Privileges that change what a snapshot can contain. Superusers have them
+/// all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    pub superuser: bool,
+    /// `pg_read_all_stats`: other roles' queries and wait events in
+    /// `pg_stat_activity`, their statement texts and replication details
+    pub read_all_stats: bool,
+    /// `pg_read_all_settings`: settings such as `data_directory`
+    pub read_all_settings: bool,
+    /// `pg_signal_backend`: cancelling other roles' sessions
+    pub signal_backends: bool,
+    /// `pg_ls_waldir()`, for the `pg_wal` size and archive backlog
+    pub ls_waldir: bool,
+    /// `pg_current_logfile()`, `pg_stat_file(text, boolean)` and
+    /// `pg_read_binary_file(text, bigint, bigint, boolean)`, for the server
+    /// log; only superusers may run them unless granted
+    pub read_server_log: bool,
+    /// `pg_buffercache_pages()`, when the extension is installed
+    pub buffercache: bool,
+    /// `pgstattuple_approx()`, when the extension is installed
+    pub pgstattuple: bool,
+}
+
+/// Everything allowed: the assumption until probed, and for recordings made
+/// before the probe existed.
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            superuser: true,
+            read_all_stats: true,
+            read_all_settings: true,
+            signal_backends: true,
+            ls_waldir: true,
+            read_server_log: true,
+            buffercache: true,
+            pgstattuple: true,
+        }
+    }
+}
+
+/// Something the role can't see: what is affected and how to grant it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingCapability {
+    pub what: &'static str,
+    pub grant: &'static str,
+}
+
+impl Capabilities {
+    /// `extensions` without those whose functions this role can't call, so
+    /// queries fall back as if they weren't installed.
+    pub fn usable_extensions(&self, extensions: &DetectedExtensions) -> DetectedExtensions {
+        let mut usable = extensions.clone();
+        usable.pg_buffercache &= self.buffercache;
+        usable.pgstattuple &= self.pgstattuple;
+        usable
+    }
+
+    /// What is hidden from this role, given the installed extensions.
+    pub fn missing(&self, extensions: &DetectedExtensions) -> Vec<MissingCapability> {
+        let checks = [
+            (
+                self.read_all_stats,
+                "Other roles' queries, wait events, statement texts and replication lag",
+                "GRANT pg_read_all_stats TO <role>",
+            ),
+            (
+                self.read_all_settings,
+                "Superuser-only settings (data_directory, config_file, ...)",
+                "GRANT pg_read_all_settings TO <role>",
+            ),
+            (
+                self.signal_backends,
+                "Cancelling or terminating other roles' sessions",
+                "GRANT pg_signal_backend TO <role>",
+            ),
+            (
+                self.ls_waldir,
+                "pg_wal size and archive backlog",
+                "GRANT pg_monitor TO <role>",
+            ),
+            (
+                self.read_server_log,
+                "Server log (Logs panel, cancel confirmation)",
+                "GRANT EXECUTE ON FUNCTION pg_read_binary_file(text, bigint, bigint, boolean), pg_stat_file(text, boolean), pg_current_logfile() TO <role>",
+            ),
+            (
+                self.buffercache || !extensions.pg_buffercache,
+                "Buffer cache contents (pg_buffercache)",
+                "GRANT pg_monitor TO <role>",
+            ),
+            (
+                self.pgstattuple || !extensions.pgstattuple,
+                "pgstattuple bloat measurements (falls back to estimates)",
+                "GRANT pg_stat_scan_tables TO <role>",
+            ),
+        ];
+        checks
+            .into_iter()
+            .filter(|(allowed, _, _)| !allowed)
+            .map(|(_, what, grant)| MissingCapability { what, grant })
+            .collect()
+    }
+}
+
+/// Superusers pass every check here, so no separate branch is needed for
+/// them. Extension functions are looked up through `pg_proc` so a missing
+/// extension or an unusual schema reads as "no" rather than an error.
+const CAPABILITIES_SQL: &str = "
+SELECT
+    COALESCE((SELECT rolsuper FROM pg_roles WHERE rolname = current_user), false) AS superuser,
+    pg_has_role(current_user, 'pg_read_all_stats', 'USAGE') AS read_all_stats,
+    pg_has_role(current_user, 'pg_read_all_settings', 'USAGE') AS read_all_settings,
+    pg_has_role(current_user, 'pg_signal_backend', 'USAGE') AS signal_backends,
+    has_function_privilege('pg_ls_waldir()', 'EXECUTE') AS ls_waldir,
+    has_function_privilege('pg_current_logfile()', 'EXECUTE')
+        AND has_function_privilege('pg_stat_file(text, boolean)', 'EXECUTE')
+        AND has_function_privilege('pg_read_binary_file(text, bigint, bigint, boolean)', 'EXECUTE')
+        AS read_server_log,
+    EXISTS (
+        SELECT 1 FROM pg_proc
+        WHERE proname = 'pg_buffercache_pages' AND has_function_privilege(oid, 'EXECUTE')
+    ) AS buffercache,
+    EXISTS (
+        SELECT 1 FROM pg_proc
+        WHERE proname = 'pgstattuple_approx' AND has_function_privilege(oid, 'EXECUTE')
+    ) AS pgstattuple
+";
+
+/// Probe what the connected role may read.
+pub async fn probe(client: &Client) -> Result<Capabilities> {
+    let row = client
+        .query_one(CAPABILITIES_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "probe_capabilities",
+            source: e,
+        })?;
+    Ok(Capabilities {
+        superuser: row.get("superuser"),
+        read_all_stats: row.get("read_all_stats"),
+        read_all_settings: row.get("read_all_settings"),
+        signal_backends: row.get("signal_backends"),
+        ls_waldir: row.get("ls_waldir"),
+        read_server_log: row.get("read_server_log"),
+        buffercache: row.get("buffercache"),
+        pgstattuple: row.get("pgstattuple"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_skips_extensions_that_are_not_installed() {
+        let caps = Capabilities {
+            superuser: false,
+            read_all_stats: false,
+            ls_waldir: false,
+            buffercache: false,
+            pgstattuple: false,
+            ..Capabilities::default()
+        };
+        let mut extensions = DetectedExtensions::default();
+        let missing = caps.missing(&extensions);
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing[0].grant, "GRANT pg_read_all_stats TO <role>");
+        assert_eq!(missing[1].what, "pg_wal size and archive backlog");
+
+        extensions.pg_buffercache = true;
+        assert_eq!(caps.missing(&extensions).len(), 3);
+        assert!(Capabilities::default().missing(&extensions).is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+//! Relation and schema names by OID, kept on the client.
+//!
+//! `pg_locks`, the `pg_stat_progress_*` views and `pg_buffercache` only
+//! carry OIDs. Casting them to `regclass` on the server leaves the schema
+//! off for whatever is on the `search_path`, and on large schemas joins
+//! `pg_class` on every refresh. Instead the names are read once at connect
+//! and looked up here, so every panel shows the same `schema.table`.
+//! OIDs the cache doesn't know yet (relations created since) are read on
+//! first sight, and the whole cache is reloaded once it goes stale to pick
+//! up renames.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use tokio_postgres::Client;
+
+use super::error::{DbError, Result};
+use super::models::{BufferCacheReport, LockInfo, OperationProgress, PgSnapshot, VacuumProgress};
+
+/// How long names are trusted before the next lookup reloads them all.
+pub const MAX_AGE: Duration = Duration::from_secs(300);
+
+const NAMESPACES_SQL: &str = "SELECT oid, nspname FROM pg_namespace";
+
+const RELATIONS_SQL: &str = "SELECT oid, relnamespace, relname FROM pg_class";
+
+const RELATIONS_BY_OID_SQL: &str = "
+SELECT c.oid, c.relnamespace, c.relname, n.nspname
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.oid = ANY($1)
+";
+
+/// A relation's schema OID and name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedRelation {
+    namespace: u32,
+    name: String,
+}
+
+/// Names of this database's schemas and relations by OID.
+#[derive(Debug, Default)]
+pub struct CatalogCache {
+    namespaces: HashMap<u32, String>,
+    relations: HashMap<u32, CachedRelation>,
+    /// OIDs looked up and not found (dropped since), not asked for again
+    /// until the next reload
+    unknown: HashSet<u32>,
+    loaded_at: Option<Instant>,
+}
+
+impl CatalogCache {
+    /// Read every schema and relation name of the connected database.
+    pub async fn load(client: &Client) -> Result<Self> {
+        let mut cache = Self::default();
+        cache.reload(client).await?;
+        Ok(cache)
+    }
+
+    /// `schema.relation` for `oid`, if known.
+    pub fn qualified(&self, oid: u32) -> Option<String> {
+        let rel = self.relations.get(&oid)?;
+        let schema = self.namespaces.get(&rel.namespace)?;
+        Some(format!("{schema}.{}", rel.name))
+    }
+
+    /// Schema and relation name for `oid`, if known.
+    pub fn names(&self, oid: u32) -> Option<(&str, &str)> {
+        let rel = self.relations.get(&oid)?;
+        let schema = self.namespaces.get(&rel.namespace)?;
+        Some((schema, &rel.name))
+    }
+
+    fn is_stale(&self) -> bool {
+        self.loaded_at.map_or(true, |at| at.elapsed() >= MAX_AGE)
+    }
+
+    /// Record a relation read from the server.
+    fn insert(&mut self, oid: u32, namespace: u32, name: String) {
+        self.unknown.remove(&oid);
+        self.relations.insert(oid, CachedRelation { namespace, name });
+    }
+
+    async fn reload(&mut self, client: &Client) -> Result<()> {
+        let namespaces = client
+            .query(NAMESPACES_SQL, &[])
+            .await
+            .map_err(|e| DbError::Query {
+                context: "load_catalog_cache namespaces",
+                source: e,
+            })?;
+        let relations = client
+            .query(RELATIONS_SQL, &[])
+            .await
+            .map_err(|e| DbError::Query {
+                context: "load_catalog_cache relations",
+                source: e,
+            })?;
+        self.namespaces = namespaces.iter().map(|row| (row.get("oid"), row.get("nspname"))).collect();
+        self.relations.clear();
+        self.unknown.clear();
+        for row in &relations {
+            self.insert(row.get("oid"), row.get("relnamespace"), row.get("relname"));
+        }
+        self.loaded_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Make sure `oids` are known: reload everything once stale, otherwise
+    /// read just the ones never seen before.
+    pub async fn refresh(&mut self, client: &Client, oids: &[u32]) -> Result<()> {
+        if self.loaded_at.is_some() && self.is_stale() {
+            return self.reload(client).await;
+        }
+        let mut missing: Vec<u32> = oids
+            .iter()
+            .copied()
+            .filter(|oid| !self.relations.contains_key(oid) && !self.unknown.contains(oid))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        missing.sort_unstable();
+        missing.dedup();
+        let rows = client
+            .query(RELATIONS_BY_OID_SQL, &[&missing])
+            .await
+            .map_err(|e| DbError::Query {
+                context: "refresh_catalog_cache",
+                source: e,
+            })?;
+        self.unknown.extend(missing);
+        for row in &rows {
+            let namespace: u32 = row.get("relnamespace");
+            self.namespaces.insert(namespace, row.get("nspname"));
+            self.insert(row.get("oid"), namespace, row.get("relname"));
+        }
+        Ok(())
+    }
+
+    /// Name the relations `snap` only has OIDs for. Lookup failures leave
+    /// the OIDs the queries fell back to in place.
+    pub async fn resolve_snapshot(&mut self, client: &Client, snap: &mut PgSnapshot) {
+        let oids: Vec<u32> = snap
+            .locks
+            .iter()
+            .filter_map(|l| l.relation_oid)
+            .chain(snap.vacuum_progress.iter().filter_map(|v| v.relid))
+            .chain(snap.operations.iter().filter_map(|op| op.relid))
+            .collect();
+        if oids.is_empty() {
+            return;
+        }
+        let _ = self.refresh(client, &oids).await;
+        self.name_rows(&mut snap.locks, &mut snap.vacuum_progress, &mut snap.operations);
+    }
+
+    /// Name the relations of a buffer cache report.
+    pub async fn resolve_buffer_cache(&mut self, client: &Client, report: &mut BufferCacheReport) {
+        let oids: Vec<u32> = report.relations.iter().map(|rel| rel.relid).collect();
+        let _ = self.refresh(client, &oids).await;
+        for rel in &mut report.relations {
+            if let Some((schema, name)) = self.names(rel.relid) {
+                rel.schemaname = schema.to_string();
+                rel.relname = name.to_string();
+            }
+        }
+    }
+
+    fn name_rows(&self, locks: &mut [LockInfo], vacuums: &mut [VacuumProgress], operations: &mut [OperationProgress]) {
+        for lock in locks {
+            if let Some(name) = lock.relation_oid.and_then(|oid| self.qualified(oid)) {
+                lock.relation = Some(name);
+            }
+        }
+        for vacuum in vacuums {
+            if let Some(name) = vacuum.relid.and_then(|oid| self.qualified(oid)) {
+                vacuum.table_name = name;
+            }
+        }
+        for op in operations {
+            if let Some(name) = op.relid.and_then(|oid| self.qualified(oid)) {
+                op.target = name;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::OperationKind;
+
+    fn cache() -> CatalogCache {
+        let mut cache = CatalogCache::default();
+        cache.namespaces.insert(2200, "public".into());
+        cache.namespaces.insert(16390, "billing".into());
+        cache.insert(16400, 2200, "orders".into());
+        cache.insert(16401, 16390, "invoices".into());
+        cache
+    }
+
+    #[test]
+    fn qualified_needs_relation_and_schema() {
+        let mut cache = cache();
+        assert_eq!(cache.qualified(16401).as_deref(), Some("billing.invoices"));
+        assert_eq!(cache.names(16400), Some(("public", "orders")));
+        assert_eq!(cache.qualified(99999), None);
+        cache.insert(16402, 12345, "orphan".into());
+        assert_eq!(cache.qualified(16402), None);
+    }
+
+    #[test]
+    fn name_rows_replaces_oid_fallbacks() {
+        let mut locks = vec![LockInfo {
+            pid: 1,
+            locktype: "relation".into(),
+            mode: "AccessShareLock".into(),
+            granted: true,
+            relation: Some("16400".into()),
+            relation_oid: Some(16400),
+            usename: None,
+            query: None,
+            state: None,
+            duration_secs: 0.0,
+            blocking_pids: vec![],
+        }];
+        // A lock in another database keeps its OID
+        locks.push(LockInfo {
+            relation: Some("16401".into()),
+            relation_oid: None,
+            ..locks[0].clone()
+        });
+        let mut vacuums = vec![VacuumProgress {
+            pid: 2,
+            datname: None,
+            table_name: "16401".into(),
+            relid: Some(16401),
+            phase: "scanning heap".into(),
+            heap_blks_total: 0,
+            heap_blks_vacuumed: 0,
+            progress_pct: 0.0,
+            num_dead_tuples: 0,
+        }];
+        let mut operations = vec![OperationProgress {
+            pid: 3,
+            kind: OperationKind::CreateIndex,
+            command: "CREATE INDEX".into(),
+            datname: None,
+            target: "99999".into(),
+            relid: Some(99999),
+            phase: "building index".into(),
+            work_done: 0,
+            work_total: 0,
+            elapsed_secs: None,
+        }];
+
+        cache().name_rows(&mut locks, &mut vacuums, &mut operations);
+        assert_eq!(locks[0].relation.as_deref(), Some("public.orders"));
+        assert_eq!(locks[1].relation.as_deref(), Some("16401"));
+        assert_eq!(vacuums[0].table_name, "billing.invoices");
+        assert_eq!(operations[0].target, "99999");
+    }
+
+    #[test]
+    fn stale_only_after_load() {
+        let mut cache = CatalogCache::default();
+        assert!(cache.is_stale());
+        cache.loaded_at = Some(Instant::now());
+        assert!(!cache.is_stale());
+        cache.loaded_at = Instant::now().checked_sub(MAX_AGE);
+        assert!(cache.is_stale());
+    }
+}
@@ -1,3 +1,6 @@
+pub mod capabilities;
+pub mod catalog_cache;
 pub mod error;
 pub mod models;
+pub mod pgbouncer;
 pub mod queries;
@@ -1,16 +1,20 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::capabilities::{Capabilities, MissingCapability};
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DetectedExtensions {
     pub pg_stat_statements: bool,
     pub pg_stat_statements_version: Option<String>,
+    pub pg_stat_monitor: bool,
     pub pg_stat_kcache: bool,
     pub pg_wait_sampling: bool,
     pub pg_buffercache: bool,
     pub pgstattuple: bool,
     pub pgstattuple_version: Option<String>,
+    pub hypopg: bool,
 }
 
 /// Source of bloat estimation - indicates accuracy level
@@ -18,6 +22,8 @@ pub struct DetectedExtensions {
 pub enum BloatSource {
     /// Accurate measurement using pgstattuple extension
     Pgstattuple,
+    /// Full `pgstattuple()` scan of one relation, asked for from its inspect overlay
+    PgstattupleExact,
     /// Estimated from pg_stats column widths (ioguix method)
     Statistical,
     /// Simple formula based on assumed row size (legacy, least accurate)
@@ -25,6 +31,17 @@ pub enum BloatSource {
     Naive,
 }
 
+impl BloatSource {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Pgstattuple => "pgstattuple",
+            Self::PgstattupleExact => "pgstattuple, exact",
+            Self::Statistical => "statistical",
+            Self::Naive => "naive",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PgSetting {
     pub name: String,
@@ -35,6 +52,108 @@ pub struct PgSetting {
     pub context: String,        // postmaster, sighup, superuser, user
     pub source: String,         // default, configuration file, etc.
     pub pending_restart: bool,  // PG 9.5+
+    /// Compiled-in default, in the same units as `setting`
+    #[serde(default)]
+    pub boot_val: Option<String>,
+    /// Value a `RESET` would go back to: the configuration file's, if set
+    #[serde(default)]
+    pub reset_val: Option<String>,
+}
+
+#[cfg(test)]
+impl PgSetting {
+    /// A user-context setting at its default, for tests.
+    pub(crate) fn fixture(name: &str, value: &str) -> Self {
+        Self {
+            name: name.into(),
+            setting: value.into(),
+            unit: None,
+            category: "Query Tuning".into(),
+            short_desc: None,
+            context: "user".into(),
+            source: "default".into(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        }
+    }
+}
+
+impl PgSetting {
+    /// Whether the value differs from the compiled-in default. Settings read
+    /// before `boot_val` was collected fall back to their source.
+    pub fn is_changed(&self) -> bool {
+        match &self.boot_val {
+            Some(boot_val) => *boot_val != self.setting,
+            None => self.source != "default",
+        }
+    }
+}
+
+/// A role from `pg_roles` with the roles it is a member of.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleInfo {
+    pub name: String,
+    pub superuser: bool,
+    pub login: bool,
+    pub createdb: bool,
+    pub createrole: bool,
+    pub replication: bool,
+    pub bypassrls: bool,
+    /// -1 for no limit
+    pub conn_limit: i32,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub member_of: Vec<String>,
+}
+
+impl RoleInfo {
+    /// Attributes that are set, in `CREATE ROLE` spelling
+    pub fn attributes(&self) -> Vec<&'static str> {
+        [
+            (self.superuser, "SUPERUSER"),
+            (self.login, "LOGIN"),
+            (self.createdb, "CREATEDB"),
+            (self.createrole, "CREATEROLE"),
+            (self.replication, "REPLICATION"),
+            (self.bypassrls, "BYPASSRLS"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect()
+    }
+}
+
+/// Privileges a role holds on one object, directly or as its owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleGrant {
+    /// database, schema, table, view, sequence, ...
+    pub kind: String,
+    pub object: String,
+    pub privileges: Vec<String>,
+    /// Held WITH GRANT OPTION
+    pub grantable: bool,
+}
+
+/// One `ALTER ROLE/DATABASE ... SET` override from `pg_db_role_setting`.
+/// `None` role or database means it applies to all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleSetting {
+    pub role: Option<String>,
+    pub database: Option<String>,
+    pub name: String,
+    pub value: String,
+}
+
+impl RoleSetting {
+    /// Who the override applies to, e.g. `role app in shop`
+    pub fn scope(&self) -> String {
+        match (&self.role, &self.database) {
+            (Some(role), Some(db)) => format!("role {role} in {db}"),
+            (Some(role), None) => format!("role {role}"),
+            (None, Some(db)) => format!("database {db}"),
+            (None, None) => "everyone".to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +175,15 @@ pub struct ServerInfo {
     pub settings: Vec<PgSetting>,
     #[serde(default)]
     pub extensions_list: Vec<PgExtension>,
+    /// Per-role / per-database setting overrides, read at connect
+    #[serde(default)]
+    pub role_settings: Vec<RoleSetting>,
+    /// Roles from `pg_roles`, read at connect
+    #[serde(default)]
+    pub roles: Vec<RoleInfo>,
+    /// What the monitoring role may read, probed at connect
+    #[serde(default)]
+    pub capabilities: Capabilities,
 }
 
 impl ServerInfo {
@@ -69,6 +197,12 @@ impl ServerInfo {
             .and_then(|v| v.parse().ok())
             .unwrap_or(11)
     }
+
+    /// What the monitoring role can't see on this server.
+    #[must_use]
+    pub fn missing_capabilities(&self) -> Vec<MissingCapability> {
+        self.capabilities.missing(&self.extensions)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -103,6 +237,29 @@ pub struct ArchiverStats {
     pub last_failed_time: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// What `pg_wal` holds: every segment file and the ones waiting for
+/// `archive_command`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalDirStats {
+    pub segments: i64,
+    pub bytes: i64,
+    /// `.ready` files in `archive_status` (PG12+)
+    pub archive_ready: Option<i64>,
+}
+
+/// One `pg_stat_io` row (PG16+), summed over object types: the I/O done
+/// by one kind of backend in one context.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoStat {
+    pub backend_type: String,
+    /// `normal`, `vacuum`, `bulkread` or `bulkwrite`
+    pub context: String,
+    pub reads: i64,
+    pub writes: i64,
+    pub extends: i64,
+    pub fsyncs: i64,
+}
+
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct BgwriterStats {
     pub buffers_clean: i64,
@@ -115,9 +272,15 @@ pub struct DatabaseStats {
     pub xact_commit: i64,
     pub xact_rollback: i64,
     pub blks_read: i64,
+    /// When this database's counters were last reset (NULL until first reset)
+    #[serde(default)]
+    pub stats_reset: Option<DateTime<Utc>>,
+    /// `pg_postmaster_start_time()`, to notice restarts mid-session
+    #[serde(default)]
+    pub server_start: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ActiveQuery {
     pub pid: i32,
     pub usename: Option<String>,
@@ -129,6 +292,53 @@ pub struct ActiveQuery {
     pub duration_secs: f64,
     pub query: Option<String>,
     pub backend_type: Option<String>,
+    /// Start of the backend's current transaction, if one is open
+    #[serde(default)]
+    pub xact_start: Option<DateTime<Utc>>,
+    /// `pg_stat_statements` queryid of the running statement (PG14+)
+    #[serde(default)]
+    pub query_id: Option<i64>,
+    #[serde(default)]
+    pub application_name: Option<String>,
+    /// When `state` last changed: for a session idle in transaction, when
+    /// it went idle
+    #[serde(default)]
+    pub state_change: Option<DateTime<Utc>>,
+}
+
+/// Where a backend stands transaction-wise, independent of what its current
+/// query is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxnState {
+    /// Autocommit statement or no transaction at all
+    None,
+    /// Inside an explicit transaction block
+    Open,
+    /// Transaction failed; everything until ROLLBACK will error
+    Aborted,
+}
+
+impl ActiveQuery {
+    /// Seconds in the current `state` as of `now`. Recordings made before
+    /// `state_change` was read fall back to the time since `query_start`.
+    pub fn state_secs(&self, now: DateTime<Utc>) -> f64 {
+        self.state_change.map_or(self.duration_secs, |at| {
+            (now - at).num_milliseconds().max(0) as f64 / 1000.0
+        })
+    }
+
+    pub fn txn_state(&self) -> TxnState {
+        match self.state.as_deref() {
+            Some("idle in transaction (aborted)") => TxnState::Aborted,
+            Some("idle in transaction") => TxnState::Open,
+            // An autocommit statement's transaction starts with the statement
+            // itself; an earlier start means an explicit BEGIN.
+            _ => match (self.xact_start, self.query_start) {
+                (Some(xact), Some(query)) if xact < query => TxnState::Open,
+                _ => TxnState::None,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +348,35 @@ pub struct WaitEventCount {
     pub count: i64,
 }
 
+impl WaitEventCount {
+    /// Whether `query` is one of the backends counted here, by the same rules
+    /// as the query: active client backends, not waiting reads as CPU/Running.
+    pub fn contains(&self, query: &ActiveQuery) -> bool {
+        query.state.as_deref() == Some("active")
+            && query.backend_type.as_deref() == Some("client backend")
+            && query.wait_event_type.as_deref().unwrap_or("CPU/Running") == self.wait_event_type
+            && query.wait_event.as_deref().unwrap_or("CPU/Running") == self.wait_event
+    }
+}
+
+/// Where the wait sampler's samples come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitSampleSource {
+    /// The extension's own 10ms history, read back since the last poll
+    PgWaitSampling,
+    /// One look at `pg_stat_activity` per poll
+    PgStatActivity,
+}
+
+impl WaitSampleSource {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::PgWaitSampling => "pg_wait_sampling",
+            Self::PgStatActivity => "pg_stat_activity",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockingInfo {
     pub blocked_pid: i32,
@@ -150,14 +389,88 @@ pub struct BlockingInfo {
     pub blocker_state: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Client backends sharing a user, database and application name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionGroup {
+    pub usename: Option<String>,
+    pub datname: Option<String>,
+    /// Empty when the client didn't set one
+    pub application_name: String,
+    pub total: i64,
+    pub active: i64,
+    pub idle: i64,
+    pub idle_in_transaction: i64,
+    /// Backends waiting on a lock
+    pub waiting: i64,
+    /// Longest-running transaction or query among non-idle backends
+    pub longest_secs: f64,
+}
+
+impl ConnectionGroup {
+    /// Whether `query` is one of this group's backends.
+    pub fn contains(&self, query: &ActiveQuery) -> bool {
+        query.usename == self.usename
+            && query.datname == self.datname
+            && query.application_name.as_deref().unwrap_or("") == self.application_name
+    }
+
+    /// `user@database`, followed by the application when it has one.
+    pub fn label(&self) -> String {
+        let mut label = format!(
+            "{}@{}",
+            self.usename.as_deref().unwrap_or("-"),
+            self.datname.as_deref().unwrap_or("-")
+        );
+        if !self.application_name.is_empty() {
+            label.push_str(" · ");
+            label.push_str(&self.application_name);
+        }
+        label
+    }
+}
+
+/// One row of `pg_locks`, with the backend holding or waiting for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub pid: i32,
+    pub locktype: String,
+    pub mode: String,
+    pub granted: bool,
+    /// Locked relation, schema-qualified when it lives in this database;
+    /// otherwise its OID
+    pub relation: Option<String>,
+    /// OID of `relation` when it can be named from this database
+    #[serde(default)]
+    pub relation_oid: Option<u32>,
+    pub usename: Option<String>,
+    pub query: Option<String>,
+    pub state: Option<String>,
+    /// Transaction age for held locks, wait so far for waiting ones
+    pub duration_secs: f64,
+    /// Backends holding the lock a waiting row wants (empty when granted)
+    pub blocking_pids: Vec<i32>,
+}
+
+impl LockInfo {
+    /// Backends holding this lock: the row's own backend once granted,
+    /// otherwise the ones it waits for.
+    pub fn holder_pids(&self) -> Vec<i32> {
+        if self.granted {
+            vec![self.pid]
+        } else {
+            self.blocking_pids.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct BufferCacheStats {
     pub blks_hit: i64,
     pub blks_read: i64,
     pub hit_ratio: f64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct ActivitySummary {
     pub active_query_count: i64,
     pub idle_in_transaction_count: i64,
@@ -166,6 +479,11 @@ pub struct ActivitySummary {
     pub waiting_count: i64,
     pub oldest_xact_secs: Option<f64>,
     pub autovacuum_count: i64,
+    #[serde(default)]
+    pub aborted_txn_count: i64,
+    /// Idle client backends, including any the Queries list left out
+    #[serde(default)]
+    pub idle_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -252,13 +570,38 @@ pub struct Subscription {
     pub latest_end_lsn: Option<String>,
     pub latest_end_time: Option<chrono::DateTime<chrono::Utc>>,
     pub enabled: bool,
+    /// Seconds since the apply worker last reported progress to the publisher
+    #[serde(default)]
+    pub apply_lag_secs: Option<f64>,
+    /// WAL received but not yet reported as applied
+    #[serde(default)]
+    pub apply_lag_bytes: Option<i64>,
+    // PG 15+ stats from pg_stat_subscription_stats
+    #[serde(default)]
+    pub apply_error_count: Option<i64>,
+    #[serde(default)]
+    pub sync_error_count: Option<i64>,
+    /// Conflicts of all kinds met while applying changes (PG 18+)
+    #[serde(default)]
+    pub conflict_count: Option<i64>,
+}
+
+impl Subscription {
+    /// Enabled, but no apply worker is running: changes are not being applied.
+    pub const fn worker_down(&self) -> bool {
+        self.enabled && self.pid.is_none()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VacuumProgress {
     pub pid: i32,
     pub datname: Option<String>,
+    /// Schema-qualified when it lives in this database; otherwise its OID
     pub table_name: String,
+    /// OID of `table_name` when it can be named from this database
+    #[serde(default)]
+    pub relid: Option<u32>,
     pub phase: String,
     pub heap_blks_total: i64,
     pub heap_blks_vacuumed: i64,
@@ -266,6 +609,85 @@ pub struct VacuumProgress {
     pub num_dead_tuples: i64,
 }
 
+/// Which `pg_stat_progress_*` view an operation was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Vacuum,
+    CreateIndex,
+    Cluster,
+    Analyze,
+    BaseBackup,
+}
+
+/// A backend that isn't serving a client: parallel and autovacuum workers,
+/// walsenders, logical replication workers and the server's own processes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundWorker {
+    pub pid: i32,
+    pub backend_type: String,
+    pub datname: Option<String>,
+    pub state: Option<String>,
+    pub wait_event_type: Option<String>,
+    pub wait_event: Option<String>,
+    /// Time in the current query, or since the process started when it has none
+    pub duration_secs: f64,
+    pub query: Option<String>,
+    /// Leader a parallel worker works for (PG13+)
+    pub leader_pid: Option<i32>,
+}
+
+impl BackgroundWorker {
+    pub fn is_parallel_worker(&self) -> bool {
+        self.backend_type == "parallel worker"
+    }
+}
+
+/// A long-running maintenance command, as reported by its progress view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationProgress {
+    pub pid: i32,
+    pub kind: OperationKind,
+    /// Command as the view names it, e.g. `REINDEX CONCURRENTLY` or `VACUUM FULL`
+    pub command: String,
+    pub datname: Option<String>,
+    /// Relation being worked on; the client for a base backup
+    pub target: String,
+    /// OID of the relation when it can be named from this database
+    #[serde(default)]
+    pub relid: Option<u32>,
+    pub phase: String,
+    /// Work done and to do in the current phase, in blocks, tuples or
+    /// bytes depending on the view. A total of 0 means unknown.
+    pub work_done: i64,
+    pub work_total: i64,
+    /// Seconds since the command started, where the view's backend says
+    pub elapsed_secs: Option<f64>,
+}
+
+impl OperationProgress {
+    pub fn progress_pct(&self) -> Option<f64> {
+        (self.work_total > 0).then(|| 100.0 * self.work_done as f64 / self.work_total as f64)
+    }
+}
+
+impl From<&VacuumProgress> for OperationProgress {
+    fn from(v: &VacuumProgress) -> Self {
+        Self {
+            pid: v.pid,
+            kind: OperationKind::Vacuum,
+            command: "VACUUM".into(),
+            datname: v.datname.clone(),
+            target: v.table_name.clone(),
+            relid: v.relid,
+            phase: v.phase.clone(),
+            work_done: v.heap_blks_vacuumed,
+            work_total: v.heap_blks_total,
+            elapsed_secs: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WraparoundInfo {
     pub datname: String,
@@ -274,6 +696,30 @@ pub struct WraparoundInfo {
     pub pct_towards_wraparound: f64,
 }
 
+/// A relation's frozen XID age, read on demand for the wraparound inspect
+/// overlay. Ages of TOAST tables are reported against the table owning
+/// them, since that's what `VACUUM` is run on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WraparoundTable {
+    pub schemaname: String,
+    pub relname: String,
+    /// The age is the table's TOAST relation's
+    pub via_toast: bool,
+    pub xid_age: i32,
+    pub size_bytes: i64,
+}
+
+impl WraparoundTable {
+    pub fn key(&self) -> String {
+        format!("{}.{}", self.schemaname, self.relname)
+    }
+}
+
+/// Older recordings carry no validity flag; their indexes were valid as far as anyone knew.
+const fn default_true() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexInfo {
     pub schemaname: String,
@@ -284,6 +730,9 @@ pub struct IndexInfo {
     pub idx_tup_read: i64,
     pub idx_tup_fetch: i64,
     pub index_definition: String,
+    /// False while a failed `CREATE INDEX CONCURRENTLY` leaves the index unusable
+    #[serde(default = "default_true")]
+    pub is_valid: bool,
     // Bloat estimation (populated on-demand)
     #[serde(default)]
     pub bloat_bytes: Option<i64>,
@@ -293,6 +742,517 @@ pub struct IndexInfo {
     pub bloat_source: Option<BloatSource>,
 }
 
+/// What `pg_buffercache` found in shared buffers. Reading it scans every
+/// buffer header, so it is only read on demand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferCacheReport {
+    pub read_at: DateTime<Utc>,
+    pub block_size: i64,
+    /// Buffers in `shared_buffers`
+    pub total_buffers: i64,
+    /// Buffers holding no page yet
+    pub unused_buffers: i64,
+    pub dirty_buffers: i64,
+    /// Used buffers by usage count, 0 through 5
+    pub usage_counts: [i64; 6],
+    /// Relations of the current database (and shared catalogs) holding the
+    /// most buffers, most first
+    pub relations: Vec<BufferCacheRelation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BufferCacheRelation {
+    pub relid: u32,
+    pub schemaname: String,
+    pub relname: String,
+    /// `pg_class.relkind`: `r` table, `i` index, `t` TOAST, ...
+    pub relkind: String,
+    pub buffers: i64,
+    pub dirty: i64,
+    pub avg_usage: f64,
+    /// Share of the relation's pages that are cached; None when it is empty
+    pub cached_pct: Option<f64>,
+}
+
+/// What `pgstattuple()` and `pgstatindex()` measured for one table and its
+/// btree indexes, read page by page rather than estimated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasuredBloat {
+    pub table_len: i64,
+    pub tuple_percent: f64,
+    pub dead_tuple_percent: f64,
+    pub free_space: i64,
+    pub free_percent: f64,
+    pub indexes: Vec<MeasuredIndex>,
+}
+
+impl MeasuredBloat {
+    /// Dead tuples and free space, the part of the table a rewrite gives back
+    pub fn bloat_pct(&self) -> f64 {
+        self.dead_tuple_percent + self.free_percent
+    }
+
+    pub fn bloat_bytes(&self) -> i64 {
+        (self.table_len as f64 * self.bloat_pct() / 100.0) as i64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasuredIndex {
+    pub index_name: String,
+    pub index_size: i64,
+    pub avg_leaf_density: f64,
+    pub leaf_fragmentation: f64,
+}
+
+impl MeasuredIndex {
+    /// Empty space in the leaf pages; a fresh btree is about 10% (fillfactor 90)
+    pub fn bloat_pct(&self) -> f64 {
+        100.0 - self.avg_leaf_density
+    }
+
+    pub fn bloat_bytes(&self) -> i64 {
+        (self.index_size as f64 * self.bloat_pct() / 100.0) as i64
+    }
+}
+
+/// Definition of a table, read when its inspect overlay opens
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableSchema {
+    pub columns: Vec<ColumnDef>,
+    pub indexes: Vec<IndexDef>,
+    /// Foreign keys of this table and those pointing at it
+    pub foreign_keys: Vec<ForeignKeyDef>,
+    pub triggers: Vec<TriggerDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDef {
+    pub name: String,
+    /// As `format_type` writes it, e.g. `character varying(64)`
+    pub data_type: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexDef {
+    pub name: String,
+    pub definition: String,
+    pub is_primary: bool,
+    pub is_unique: bool,
+    /// False while a concurrent build is unfinished or after it failed
+    pub is_valid: bool,
+    pub size_bytes: i64,
+    pub idx_scan: i64,
+    pub idx_tup_read: i64,
+    pub idx_tup_fetch: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyDef {
+    pub name: String,
+    /// Table on the other end, schema-qualified unless on the search path
+    pub other_table: String,
+    pub definition: String,
+    /// Declared on the other table, referencing this one
+    pub incoming: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerDef {
+    pub name: String,
+    pub definition: String,
+    pub enabled: bool,
+}
+
+/// Cumulative counters of one statement, read by the latency sampler
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatementSample {
+    pub queryid: i64,
+    pub calls: i64,
+    pub total_exec_time: f64,
+}
+
+/// One node of an `EXPLAIN (FORMAT JSON)` plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanNode {
+    pub node_type: String,
+    pub relation: Option<String>,
+    /// Schema of `relation`; only `VERBOSE` plans carry it
+    pub schema: Option<String>,
+    pub index: Option<String>,
+    pub join_type: Option<String>,
+    /// First of Index Cond / Hash Cond / Merge Cond / Join Filter / Filter
+    pub condition: Option<String>,
+    pub startup_cost: f64,
+    pub total_cost: f64,
+    pub plan_rows: f64,
+    pub plan_width: i64,
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    fn from_json(node: &serde_json::Value) -> Option<Self> {
+        let text = |key: &str| node.get(key).and_then(|v| v.as_str()).map(String::from);
+        let number = |key: &str| node.get(key).and_then(serde_json::Value::as_f64).unwrap_or(0.0);
+        let condition = ["Index Cond", "Hash Cond", "Merge Cond", "Join Filter", "Filter"]
+            .into_iter()
+            .find_map(text);
+        let children = node
+            .get("Plans")
+            .and_then(|p| p.as_array())
+            .map(|plans| plans.iter().filter_map(Self::from_json).collect())
+            .unwrap_or_default();
+        Some(Self {
+            node_type: text("Node Type")?,
+            relation: text("Relation Name"),
+            schema: text("Schema"),
+            index: text("Index Name"),
+            join_type: text("Join Type"),
+            condition,
+            startup_cost: number("Startup Cost"),
+            total_cost: number("Total Cost"),
+            plan_rows: number("Plan Rows"),
+            plan_width: node.get("Plan Width").and_then(serde_json::Value::as_i64).unwrap_or(0),
+            children,
+        })
+    }
+
+    /// `Hash Join`, `Index Scan using idx on t` and so on: the node type
+    /// with its join type, index and relation.
+    pub fn label(&self) -> String {
+        let mut label = self.node_type.clone();
+        if let Some(join) = &self.join_type {
+            if !label.contains(join.as_str()) {
+                label = format!("{join} {label}");
+            }
+        }
+        if let Some(index) = &self.index {
+            label.push_str(&format!(" using {index}"));
+        }
+        if let Some(relation) = &self.relation {
+            label.push_str(&format!(" on {relation}"));
+        }
+        label
+    }
+
+    /// Cost of this node alone, without what its children add
+    pub fn self_cost(&self) -> f64 {
+        let children: f64 = self.children.iter().map(|c| c.total_cost).sum();
+        (self.total_cost - children).max(0.0)
+    }
+}
+
+/// Result of explaining a statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    pub query: String,
+    pub root: PlanNode,
+    /// Planned with `GENERIC_PLAN` because the text has `$n` parameters
+    pub generic: bool,
+}
+
+impl QueryPlan {
+    /// Parse the single JSON document `EXPLAIN (FORMAT JSON)` returns.
+    pub fn parse(query: &str, json: &str, generic: bool) -> Option<Self> {
+        let doc: serde_json::Value = serde_json::from_str(json).ok()?;
+        let root = PlanNode::from_json(doc.get(0)?.get("Plan")?)?;
+        Some(Self {
+            query: query.to_string(),
+            root,
+            generic,
+        })
+    }
+}
+
+impl QueryPlan {
+    /// Indexes worth trying for this plan: one per sequentially scanned
+    /// table with a filter, on the filter's columns, equality columns first.
+    pub fn index_candidates(&self) -> Vec<IndexCandidate> {
+        let mut out: Vec<IndexCandidate> = Vec::new();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            stack.extend(node.children.iter().rev());
+            let (Some(relation), Some(condition)) = (&node.relation, &node.condition) else {
+                continue;
+            };
+            if node.node_type != "Seq Scan" {
+                continue;
+            }
+            let columns = filter_columns(condition);
+            if columns.is_empty() || out.iter().any(|c| c.relation == *relation && c.schema == node.schema) {
+                continue;
+            }
+            out.push(IndexCandidate {
+                schema: node.schema.clone(),
+                relation: relation.clone(),
+                columns,
+            });
+        }
+        out
+    }
+
+    /// Every index the plan reads.
+    pub fn index_names(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            stack.extend(node.children.iter());
+            out.extend(node.index.as_deref());
+        }
+        out
+    }
+}
+
+/// Most columns a suggested index gets.
+const MAX_INDEX_COLUMNS: usize = 3;
+
+/// Words in a deparsed filter that are not column names.
+const FILTER_KEYWORDS: [&str; 17] = [
+    "AND", "OR", "NOT", "IS", "NULL", "TRUE", "FALSE", "ANY", "ALL", "IN", "LIKE", "ILIKE",
+    "BETWEEN", "DISTINCT", "FROM", "SubPlan", "hashed",
+];
+
+/// Words that continue a type name after its first ("character varying").
+const TYPE_NAME_WORDS: [&str; 6] = ["varying", "precision", "with", "without", "time", "zone"];
+
+/// Column names in a filter as `EXPLAIN` prints it, e.g.
+/// `((status)::text = 'open'::text) AND (created_at > $1)`. Columns
+/// compared with `=` come first, then the rest in order of appearance.
+fn filter_columns(condition: &str) -> Vec<String> {
+    let chars: Vec<char> = condition.chars().collect();
+    let mut equality = Vec::new();
+    let mut other = Vec::new();
+    let mut i = 0;
+    // Inside the type name of a `::` cast, and whether its first word is read
+    let mut after_cast = false;
+    let mut cast_word = false;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            // String literal; '' is an escaped quote
+            i += 1;
+            while i < chars.len() && !(chars[i] == '\'' && chars.get(i + 1) != Some(&'\'')) {
+                i += if chars[i] == '\'' { 2 } else { 1 };
+            }
+            i += 1;
+            continue;
+        }
+        if c == ':' && chars.get(i + 1) == Some(&':') {
+            after_cast = true;
+            cast_word = false;
+            i += 2;
+            continue;
+        }
+        let quoted = c == '"';
+        if !(quoted || c.is_alphabetic() || c == '_') {
+            if !c.is_whitespace() {
+                after_cast = false;
+            }
+            i += 1;
+            continue;
+        }
+        // An identifier, maybe quoted, maybe qualified: keep the last part
+        let mut name = String::new();
+        loop {
+            name.clear();
+            if chars.get(i) == Some(&'"') {
+                i += 1;
+                while i < chars.len() && !(chars[i] == '"' && chars.get(i + 1) != Some(&'"')) {
+                    name.push(chars[i]);
+                    i += if chars[i] == '"' { 2 } else { 1 };
+                }
+                i += 1;
+            } else {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                    name.push(chars[i]);
+                    i += 1;
+                }
+            }
+            if chars.get(i) == Some(&'.') {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        if after_cast {
+            if !cast_word || TYPE_NAME_WORDS.contains(&name.as_str()) {
+                cast_word = true;
+                continue;
+            }
+            after_cast = false;
+        }
+        if !quoted && FILTER_KEYWORDS.contains(&name.as_str()) {
+            continue;
+        }
+        let rest: String = chars[i..].iter().collect();
+        let rest = rest.trim_start();
+        if rest.starts_with('(') {
+            // A function call
+            continue;
+        }
+        // `(col)::text = ...` compares the column, so look past the cast
+        let rest = rest.trim_start_matches(')');
+        let rest = match rest.strip_prefix("::") {
+            Some(cast) => cast.trim_start_matches(|c: char| c.is_alphanumeric() || c == '_' || c == ' ' || c == ')'),
+            None => rest,
+        };
+        if equality.contains(&name) || other.contains(&name) {
+            continue;
+        }
+        if rest.starts_with("= ") {
+            equality.push(name);
+        } else {
+            other.push(name);
+        }
+    }
+    equality.extend(other);
+    equality.truncate(MAX_INDEX_COLUMNS);
+    equality
+}
+
+/// Quote an identifier unless it is a plain lowercase name.
+pub fn quote_ident(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Quote `text` as a string literal.
+pub fn quote_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// An index the advisor asks hypopg to pretend exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexCandidate {
+    pub schema: Option<String>,
+    pub relation: String,
+    pub columns: Vec<String>,
+}
+
+impl IndexCandidate {
+    pub fn definition(&self) -> String {
+        let table = match &self.schema {
+            Some(schema) => format!("{}.{}", quote_ident(schema), quote_ident(&self.relation)),
+            None => quote_ident(&self.relation),
+        };
+        let columns: Vec<String> = self.columns.iter().map(|c| quote_ident(c)).collect();
+        format!("CREATE INDEX ON {table} ({})", columns.join(", "))
+    }
+}
+
+/// One hypothetical index and whether the planner picked it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexSuggestion {
+    pub definition: String,
+    pub used: bool,
+}
+
+/// What the statement's plan costs with and without the suggested indexes
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexAdvice {
+    pub query: String,
+    /// Planned with `GENERIC_PLAN` because the text has `$n` parameters
+    pub generic: bool,
+    pub cost_before: f64,
+    pub cost_after: f64,
+    pub suggestions: Vec<IndexSuggestion>,
+}
+
+impl IndexAdvice {
+    /// Estimated share of the plan cost the indexes save, 0 to 1.
+    pub fn improvement(&self) -> f64 {
+        if self.cost_before > 0.0 {
+            ((self.cost_before - self.cost_after) / self.cost_before).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// What `pg_stat_monitor` adds to the statements it lists: a latency
+/// histogram, error counts and the time buckets it keeps counters in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatementMonitor {
+    /// Histogram bucket bounds in ms, `(lower, upper)`; the last bucket
+    /// has no upper bound
+    pub ranges: Vec<(f64, Option<f64>)>,
+    pub statements: Vec<MonitorStatement>,
+}
+
+/// One statement's `pg_stat_monitor` detail, summed across users,
+/// databases and time buckets like its `StatStatement`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonitorStatement {
+    pub queryid: i64,
+    /// Calls that ended in an error
+    pub errors: i64,
+    /// Calls per histogram bucket of `StatementMonitor::ranges`
+    pub resp_calls: Vec<i64>,
+    /// Counters per time bucket, oldest first
+    pub history: Vec<MonitorBucket>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonitorBucket {
+    pub start: DateTime<Utc>,
+    pub calls: i64,
+    pub total_exec_time: f64,
+}
+
+impl MonitorBucket {
+    pub fn mean_exec_time(&self) -> f64 {
+        if self.calls > 0 {
+            self.total_exec_time / self.calls as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+impl StatementMonitor {
+    pub fn get(&self, queryid: i64) -> Option<&MonitorStatement> {
+        self.statements.iter().find(|s| s.queryid == queryid)
+    }
+
+    /// Time under which `pct` percent of `stmt`'s calls finished, in ms,
+    /// interpolated within its histogram bucket. In the open last bucket
+    /// this is the bucket's lower bound.
+    pub fn percentile(&self, stmt: &MonitorStatement, pct: f64) -> Option<f64> {
+        let total: i64 = stmt.resp_calls.iter().sum();
+        if total == 0 || stmt.resp_calls.len() > self.ranges.len() {
+            return None;
+        }
+        let target = total as f64 * pct / 100.0;
+        let mut seen = 0.0;
+        for (&calls, &(lower, upper)) in stmt.resp_calls.iter().zip(&self.ranges) {
+            if calls == 0 {
+                continue;
+            }
+            let next = seen + calls as f64;
+            if next >= target {
+                let Some(upper) = upper else {
+                    return Some(lower);
+                };
+                return Some(lower + (upper - lower) * ((target - seen) / calls as f64));
+            }
+            seen = next;
+        }
+        None
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatStatement {
     pub queryid: i64,
@@ -317,14 +1277,118 @@ pub struct StatStatement {
     pub blk_read_time: f64,
     pub blk_write_time: f64,
     pub hit_ratio: f64,
+    /// Summed over `pg_stat_monitor`'s time buckets rather than counted
+    /// since the last reset: the counters drop when the oldest bucket
+    /// expires, so they can't be diffed between snapshots
+    #[serde(default)]
+    pub windowed: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Severity of a server log line, as Postgres writes it after
+/// `log_line_prefix`. `Detail`, `Hint`, `Context` and `Statement` lines
+/// belong to the message before them; `Other` is anything without a
+/// severity (e.g. stderr of a child process).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Log,
+    Warning,
+    Error,
+    Fatal,
+    Panic,
+    Detail,
+    Hint,
+    Context,
+    Statement,
+    Other,
+}
+
+impl LogLevel {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Notice => "NOTICE",
+            Self::Log => "LOG",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+            Self::Panic => "PANIC",
+            Self::Detail => "DETAIL",
+            Self::Hint => "HINT",
+            Self::Context => "CONTEXT",
+            Self::Statement => "STATEMENT",
+            Self::Other => "",
+        }
+    }
+
+    /// Lines that add to the message before them rather than start one.
+    pub const fn is_followup(self) -> bool {
+        matches!(self, Self::Detail | Self::Hint | Self::Context | Self::Statement)
+    }
+}
+
+/// One PgBouncer pool from `SHOW POOLS`, with the averages `SHOW STATS`
+/// keeps for its database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolerPool {
+    pub database: String,
+    pub user: String,
+    pub pool_mode: String,
+    /// Clients paired with a server connection
+    pub cl_active: i64,
+    /// Clients queued for a server connection
+    pub cl_waiting: i64,
+    /// Server connections in use by a client
+    pub sv_active: i64,
+    /// Server connections ready for a client
+    pub sv_idle: i64,
+    /// Server connections idle past `server_check_delay`, to be checked first
+    pub sv_used: i64,
+    /// How long the oldest queued client has waited, in seconds
+    pub maxwait_secs: f64,
+    /// Averages over the last stats period, in microseconds
+    pub avg_query_us: i64,
+    pub avg_xact_us: i64,
+    pub avg_wait_us: i64,
+}
+
+/// What PgBouncer's admin console reported at a refresh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolerStats {
+    /// As `SHOW VERSION` gives it, e.g. `PgBouncer 1.22.1`
+    pub version: String,
+    pub pools: Vec<PoolerPool>,
+}
+
+impl PoolerStats {
+    pub fn clients_waiting(&self) -> i64 {
+        self.pools.iter().map(|p| p.cl_waiting).sum()
+    }
+}
+
+/// A server log line read during a refresh.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLine {
+    /// What `log_line_prefix` put before the severity (timestamp, PID, ...)
+    pub prefix: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PgSnapshot {
     pub timestamp: DateTime<Utc>,
     pub active_queries: Vec<ActiveQuery>,
     pub wait_events: Vec<WaitEventCount>,
     pub blocking_info: Vec<BlockingInfo>,
+    #[serde(default)]
+    pub locks: Vec<LockInfo>,
+    #[serde(default)]
+    pub connection_groups: Vec<ConnectionGroup>,
     pub buffer_cache: BufferCacheStats,
     pub summary: ActivitySummary,
     pub table_stats: Vec<TableStat>,
@@ -332,6 +1396,16 @@ pub struct PgSnapshot {
     pub replication_slots: Vec<ReplicationSlot>,
     pub subscriptions: Vec<Subscription>,
     pub vacuum_progress: Vec<VacuumProgress>,
+    /// CREATE INDEX, CLUSTER, ANALYZE and base backup progress
+    #[serde(default)]
+    pub operations: Vec<OperationProgress>,
+    /// Non-client backends, from `pg_stat_activity`
+    #[serde(default)]
+    pub background_workers: Vec<BackgroundWorker>,
+    /// Idle client backends left out of `active_queries` by the idle cap:
+    /// still connected, just not listed
+    #[serde(default)]
+    pub capped_pids: Vec<i32>,
     pub wraparound: Vec<WraparoundInfo>,
     pub indexes: Vec<IndexInfo>,
     pub stat_statements: Vec<StatStatement>,
@@ -341,8 +1415,59 @@ pub struct PgSnapshot {
     pub checkpoint_stats: Option<CheckpointStats>,
     pub wal_stats: Option<WalStats>,
     pub archiver_stats: Option<ArchiverStats>,
+    /// Size of `pg_wal` and the archive backlog (needs `pg_monitor`)
+    #[serde(default)]
+    pub wal_dir: Option<WalDirStats>,
+    /// I/O by backend type and context from `pg_stat_io` (PG16+)
+    #[serde(default)]
+    pub io_stats: Vec<IoStat>,
+    /// Set when `pg_stat_monitor` is the statements source
+    #[serde(default)]
+    pub statement_monitor: Option<StatementMonitor>,
     pub bgwriter_stats: Option<BgwriterStats>,
     pub db_stats: Option<DatabaseStats>,
+    /// Server log lines written since the previous snapshot, when tailing
+    /// the log is on
+    #[serde(default)]
+    pub server_log: Vec<LogLine>,
+    #[serde(default)]
+    pub server_log_error: Option<String>,
+    /// PgBouncer pools, when started with `--pgbouncer`
+    #[serde(default)]
+    pub pooler: Option<PoolerStats>,
+    #[serde(default)]
+    pub pooler_error: Option<String>,
+    /// When the table and index stats were read, if an earlier refresh
+    /// than this one (see [`crate::schedule`])
+    #[serde(default)]
+    pub relation_stats_read_at: Option<DateTime<Utc>>,
+}
+
+impl PgSnapshot {
+    /// Parallel workers serving the leader backend `pid`.
+    pub fn parallel_workers_of(&self, pid: i32) -> Vec<&BackgroundWorker> {
+        self.background_workers
+            .iter()
+            .filter(|w| w.is_parallel_worker() && w.leader_pid == Some(pid))
+            .collect()
+    }
+
+    /// Vacuums and every other command with a progress view, in one list.
+    pub fn operations_in_progress(&self) -> Vec<OperationProgress> {
+        self.vacuum_progress
+            .iter()
+            .map(|v| OperationProgress {
+                // The vacuum query does not read the start time; the backend's entry has it
+                elapsed_secs: self
+                    .active_queries
+                    .iter()
+                    .find(|q| q.pid == v.pid)
+                    .map(|q| q.duration_secs),
+                ..OperationProgress::from(v)
+            })
+            .chain(self.operations.iter().cloned())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -361,6 +1486,9 @@ mod tests {
             extensions: DetectedExtensions::default(),
             settings: vec![],
             extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
         }
     }
 
@@ -455,11 +1583,13 @@ mod tests {
             extensions: DetectedExtensions {
                 pg_stat_statements: true,
                 pg_stat_statements_version: Some("1.10".to_string()),
+                pg_stat_monitor: false,
                 pg_stat_kcache: false,
                 pg_wait_sampling: true,
                 pg_buffercache: false,
                 pgstattuple: false,
                 pgstattuple_version: None,
+                hypopg: false,
             },
             settings: vec![PgSetting {
                 name: "max_connections".to_string(),
@@ -470,8 +1600,13 @@ mod tests {
                 context: "postmaster".to_string(),
                 source: "configuration file".to_string(),
                 pending_restart: false,
+                boot_val: None,
+                reset_val: None,
             }],
             extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -497,6 +1632,8 @@ mod tests {
             waiting_count: 1,
             oldest_xact_secs: Some(120.5),
             autovacuum_count: 0,
+            aborted_txn_count: 0,
+            idle_count: 0,
         };
 
         let json = serde_json::to_string(&summary).unwrap();
@@ -506,6 +1643,39 @@ mod tests {
         assert_eq!(parsed.oldest_xact_secs, Some(120.5));
     }
 
+    #[test]
+    fn txn_state_separates_transaction_from_query_state() {
+        let at = |m| Some(chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, 15, 12, m, 0).unwrap());
+        let mut q = ActiveQuery {
+            pid: 1,
+            usename: None,
+            datname: None,
+            state: Some("active".to_string()),
+            wait_event_type: None,
+            wait_event: None,
+            query_start: at(30),
+            duration_secs: 0.0,
+            query: None,
+            backend_type: None,
+            xact_start: at(30),
+            query_id: None,
+            application_name: None,
+            state_change: None,
+        };
+        // Autocommit: the transaction is the statement
+        assert_eq!(q.txn_state(), TxnState::None);
+
+        q.xact_start = at(20);
+        assert_eq!(q.txn_state(), TxnState::Open);
+
+        q.state = Some("idle in transaction (aborted)".to_string());
+        assert_eq!(q.txn_state(), TxnState::Aborted);
+
+        q.state = Some("idle".to_string());
+        q.xact_start = None;
+        assert_eq!(q.txn_state(), TxnState::None);
+    }
+
     #[test]
     fn active_query_with_nulls() {
         let query = ActiveQuery {
@@ -519,6 +1689,10 @@ mod tests {
             duration_secs: 5.5,
             query: None,
             backend_type: None,
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         };
 
         let json = serde_json::to_string(&query).unwrap();
@@ -641,7 +1815,12 @@ mod tests {
 
     #[test]
     fn bloat_source_serde_roundtrip() {
-        for source in [BloatSource::Pgstattuple, BloatSource::Statistical, BloatSource::Naive] {
+        for source in [
+            BloatSource::Pgstattuple,
+            BloatSource::PgstattupleExact,
+            BloatSource::Statistical,
+            BloatSource::Naive,
+        ] {
             let json = serde_json::to_string(&source).unwrap();
             let parsed: BloatSource = serde_json::from_str(&json).unwrap();
             assert_eq!(parsed, source);
@@ -747,4 +1926,110 @@ mod tests {
         assert!(parsed.pgstattuple);
         assert_eq!(parsed.pgstattuple_version, Some("1.5".to_string()));
     }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // QueryPlan parsing tests
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    const PLAN_JSON: &str = r#"[{"Plan": {
+        "Node Type": "Hash Join", "Join Type": "Inner",
+        "Startup Cost": 10.5, "Total Cost": 120.0, "Plan Rows": 500, "Plan Width": 16,
+        "Hash Cond": "(o.user_id = u.id)",
+        "Plans": [
+            {"Node Type": "Seq Scan", "Relation Name": "orders", "Alias": "o",
+             "Startup Cost": 0.0, "Total Cost": 80.0, "Plan Rows": 5000, "Plan Width": 8,
+             "Filter": "(status = 'open'::text)"},
+            {"Node Type": "Hash", "Startup Cost": 9.0, "Total Cost": 9.0, "Plan Rows": 100, "Plan Width": 8,
+             "Plans": [
+                {"Node Type": "Index Scan", "Relation Name": "users", "Index Name": "users_pkey",
+                 "Startup Cost": 0.3, "Total Cost": 9.0, "Plan Rows": 100, "Plan Width": 8,
+                 "Index Cond": "(id < 100)"}
+             ]}
+        ]}}]"#;
+
+    #[test]
+    fn query_plan_parses_tree() {
+        let plan = QueryPlan::parse("SELECT 1", PLAN_JSON, false).unwrap();
+        let root = &plan.root;
+        assert_eq!(root.node_type, "Hash Join");
+        assert_eq!(root.join_type.as_deref(), Some("Inner"));
+        assert_eq!(root.condition.as_deref(), Some("(o.user_id = u.id)"));
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].relation.as_deref(), Some("orders"));
+        assert_eq!(root.children[0].condition.as_deref(), Some("(status = 'open'::text)"));
+        let index_scan = &root.children[1].children[0];
+        assert_eq!(index_scan.index.as_deref(), Some("users_pkey"));
+        assert!((index_scan.plan_rows - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn plan_node_self_cost_excludes_children() {
+        let plan = QueryPlan::parse("SELECT 1", PLAN_JSON, false).unwrap();
+        assert!((plan.root.self_cost() - 31.0).abs() < 1e-9);
+        assert!((plan.root.children[1].self_cost()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn filter_columns_skips_literals_casts_and_functions() {
+        assert_eq!(filter_columns("(status = 'open'::text)"), ["status"]);
+        assert_eq!(
+            filter_columns("((created_at > $1) AND ((o.status)::text = 'it''s AND x'::character varying))"),
+            ["status", "created_at"]
+        );
+        assert_eq!(filter_columns("(lower(\"Email\") = $1)"), ["Email"]);
+        assert_eq!(filter_columns("((a = 1) AND (b = 2) AND (c = 3) AND (d = 4))"), ["a", "b", "c"]);
+        assert_eq!(filter_columns("(('a'::text <> region) OR (NOT (hashed SubPlan 1)))"), ["region"]);
+        assert!(filter_columns("(ts > '2024-01-01'::timestamp with time zone)").eq(&["ts"]));
+    }
+
+    #[test]
+    fn index_candidates_come_from_filtered_seq_scans() {
+        let plan = QueryPlan::parse("SELECT 1", PLAN_JSON, false).unwrap();
+        let candidates = plan.index_candidates();
+        assert_eq!(
+            candidates,
+            [IndexCandidate {
+                schema: None,
+                relation: "orders".into(),
+                columns: vec!["status".into()],
+            }]
+        );
+        assert_eq!(candidates[0].definition(), "CREATE INDEX ON orders (status)");
+        assert_eq!(plan.index_names(), ["users_pkey"]);
+
+        let qualified = IndexCandidate {
+            schema: Some("Sales".into()),
+            relation: "orders".into(),
+            columns: vec!["customer_id".into(), "placed at".into()],
+        };
+        assert_eq!(
+            qualified.definition(),
+            "CREATE INDEX ON \"Sales\".orders (customer_id, \"placed at\")"
+        );
+    }
+
+    #[test]
+    fn query_plan_rejects_unexpected_json() {
+        assert!(QueryPlan::parse("SELECT 1", "[]", false).is_none());
+        assert!(QueryPlan::parse("SELECT 1", "not json", false).is_none());
+        assert!(QueryPlan::parse("SELECT 1", r#"[{"Plan": {}}]"#, false).is_none());
+    }
+
+    #[test]
+    fn monitor_percentiles_interpolate_within_buckets() {
+        let monitor = StatementMonitor {
+            ranges: vec![(0.0, Some(1.0)), (1.0, Some(10.0)), (10.0, None)],
+            statements: vec![MonitorStatement {
+                queryid: 7,
+                resp_calls: vec![50, 40, 10],
+                ..MonitorStatement::default()
+            }],
+        };
+        let stmt = monitor.get(7).unwrap();
+        assert_eq!(monitor.percentile(stmt, 50.0), Some(1.0));
+        assert_eq!(monitor.percentile(stmt, 70.0), Some(5.5));
+        // The open last bucket only has a lower bound
+        assert_eq!(monitor.percentile(stmt, 99.0), Some(10.0));
+        assert_eq!(monitor.percentile(&MonitorStatement::default(), 50.0), None);
+    }
 }
@@ -0,0 +1,137 @@
+//! PgBouncer's admin console.
+//!
+//! Started with `--pgbouncer`, pg_glimpse keeps a second connection to the
+//! pooler's `pgbouncer` database and reads `SHOW POOLS` and `SHOW STATS`
+//! with each snapshot. The console only speaks the simple query protocol
+//! and sends every value as text, so rows are read by column name.
+
+use std::collections::HashMap;
+
+use tokio_postgres::{Client, SimpleQueryMessage};
+
+use super::models::{PoolerPool, PoolerStats};
+
+type ConsoleRow = HashMap<String, String>;
+
+async fn show(client: &Client, what: &str) -> Result<Vec<ConsoleRow>, String> {
+    let messages = client
+        .simple_query(&format!("SHOW {what}"))
+        .await
+        .map_err(|e| e.as_db_error().map_or_else(|| e.to_string(), |db| db.message().to_string()))?;
+    Ok(messages
+        .into_iter()
+        .filter_map(|message| match message {
+            SimpleQueryMessage::Row(row) => Some(
+                row.columns()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| (column.name().to_string(), row.get(i).unwrap_or("").to_string()))
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .collect())
+}
+
+/// `SHOW VERSION`, which also tells a PgBouncer console apart from a server.
+pub async fn version(client: &Client) -> Result<String, String> {
+    let rows = show(client, "VERSION")
+        .await
+        .map_err(|e| format!("not a PgBouncer admin console ({e})"))?;
+    let version = rows
+        .first()
+        .and_then(|row| row.get("version"))
+        .cloned()
+        .unwrap_or_default();
+    if version.starts_with("PgBouncer") {
+        Ok(version)
+    } else {
+        Err(format!("not a PgBouncer admin console (SHOW VERSION gave \"{version}\")"))
+    }
+}
+
+pub async fn fetch_stats(client: &Client) -> Result<PoolerStats, String> {
+    let version = version(client).await?;
+    let pools = show(client, "POOLS").await?;
+    let stats = show(client, "STATS").await?;
+    Ok(PoolerStats {
+        version,
+        pools: pools_with_stats(&pools, &stats),
+    })
+}
+
+fn int(row: &ConsoleRow, column: &str) -> i64 {
+    row.get(column).and_then(|v| v.parse().ok()).unwrap_or(0)
+}
+
+/// Pools joined to their database's averages. The console's own
+/// `pgbouncer` pool is left out.
+fn pools_with_stats(pools: &[ConsoleRow], stats: &[ConsoleRow]) -> Vec<PoolerPool> {
+    let text = |row: &ConsoleRow, column: &str| row.get(column).cloned().unwrap_or_default();
+    pools
+        .iter()
+        .filter(|pool| pool.get("database").map(String::as_str) != Some("pgbouncer"))
+        .map(|pool| {
+            let database = text(pool, "database");
+            let db_stats = stats.iter().find(|s| s.get("database") == Some(&database));
+            let avg = |column| db_stats.map_or(0, |s| int(s, column));
+            PoolerPool {
+                user: text(pool, "user"),
+                pool_mode: text(pool, "pool_mode"),
+                cl_active: int(pool, "cl_active"),
+                cl_waiting: int(pool, "cl_waiting"),
+                sv_active: int(pool, "sv_active"),
+                sv_idle: int(pool, "sv_idle"),
+                sv_used: int(pool, "sv_used"),
+                // maxwait_us holds the part under a second (1.8+)
+                maxwait_secs: int(pool, "maxwait") as f64 + int(pool, "maxwait_us") as f64 / 1_000_000.0,
+                avg_query_us: avg("avg_query_time"),
+                avg_xact_us: avg("avg_xact_time"),
+                avg_wait_us: avg("avg_wait_time"),
+                database,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> ConsoleRow {
+        pairs.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect()
+    }
+
+    #[test]
+    fn pools_take_their_database_averages() {
+        let pools = [
+            row(&[("database", "pgbouncer"), ("user", "pgbouncer"), ("pool_mode", "statement")]),
+            row(&[
+                ("database", "app"),
+                ("user", "app_rw"),
+                ("cl_active", "40"),
+                ("cl_waiting", "3"),
+                ("sv_active", "20"),
+                ("sv_idle", "0"),
+                ("sv_used", "2"),
+                ("maxwait", "1"),
+                ("maxwait_us", "500000"),
+                ("pool_mode", "transaction"),
+            ]),
+        ];
+        let stats = [row(&[
+            ("database", "app"),
+            ("avg_query_time", "1800"),
+            ("avg_xact_time", "4200"),
+            ("avg_wait_time", "350"),
+        ])];
+
+        let joined = pools_with_stats(&pools, &stats);
+        assert_eq!(joined.len(), 1);
+        let app = &joined[0];
+        assert_eq!((app.database.as_str(), app.user.as_str(), app.pool_mode.as_str()), ("app", "app_rw", "transaction"));
+        assert_eq!((app.cl_active, app.cl_waiting, app.sv_active, app.sv_idle, app.sv_used), (40, 3, 20, 0, 2));
+        assert!((app.maxwait_secs - 1.5).abs() < f64::EPSILON);
+        assert_eq!((app.avg_query_us, app.avg_xact_us, app.avg_wait_us), (1800, 4200, 350));
+    }
+}
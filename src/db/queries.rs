@@ -2,42 +2,106 @@ use chrono::{DateTime, Utc};
 use color_eyre::Result;
 use tokio_postgres::Client;
 
+use super::capabilities::Capabilities;
 use super::error::{DbError, Result as DbResult};
 use super::models::{
-    ActiveQuery, ActivitySummary, ArchiverStats, BgwriterStats, BlockingInfo, BloatSource,
-    BufferCacheStats, CheckpointStats, DatabaseStats, DetectedExtensions, IndexInfo,
-    PgExtension, PgSetting, PgSnapshot, ReplicationInfo, ReplicationSlot, ServerInfo,
-    StatStatement, Subscription, TableStat, VacuumProgress, WaitEventCount, WalStats,
-    WraparoundInfo,
+    ActiveQuery, ActivitySummary, ArchiverStats, BackgroundWorker, BgwriterStats, BlockingInfo, BloatSource, IoStat,
+    BufferCacheRelation, BufferCacheReport, BufferCacheStats, CheckpointStats, ColumnDef, ConnectionGroup, ForeignKeyDef, IndexAdvice, IndexDef, IndexSuggestion, MeasuredBloat, MeasuredIndex, TableSchema, TriggerDef, DatabaseStats, DetectedExtensions, IndexInfo, LockInfo,
+    PgExtension, PgSetting, PgSnapshot, QueryPlan, ReplicationInfo, ReplicationSlot, RoleGrant, RoleInfo, RoleSetting, ServerInfo,
+    MonitorBucket, MonitorStatement, StatStatement, StatementMonitor, StatementSample, Subscription, TableStat, OperationKind, OperationProgress, VacuumProgress, WaitEventCount,
+    WalDirStats, WalStats, WraparoundInfo, WraparoundTable,
 };
 
 /// Limit: 100 active queries
-const ACTIVE_QUERIES_SQL: &str = "
-SELECT
+/// Idle client backends listed when not fetching everything. Active and
+/// in-transaction backends are always listed; on servers with thousands of
+/// pooled connections the idle ones would otherwise swamp the panel.
+pub const IDLE_BACKENDS_CAP: i64 = 50;
+
+/// Client backends by state priority (active, idle in transaction, aborted,
+/// then the rest). `$1` caps the low-priority rows; NULL lists everything.
+/// `pg_stat_activity.query_id` exists from PG14 (populated when
+/// `compute_query_id` is on); older servers get NULL.
+fn build_active_queries_sql(version: u32) -> String {
+    let query_id = if version >= 14 { "query_id" } else { "NULL::bigint AS query_id" };
+    format!(
+        "WITH activity AS (
+    SELECT
+        pid,
+        usename,
+        datname,
+        state,
+        wait_event_type,
+        wait_event,
+        query_start,
+        COALESCE(EXTRACT(EPOCH FROM (clock_timestamp() - query_start))::float8, 0) AS duration_secs,
+        query,
+        backend_type,
+        xact_start,
+        {query_id},
+        application_name,
+        state_change,
+        CASE state
+            WHEN 'active' THEN 0
+            WHEN 'idle in transaction' THEN 1
+            WHEN 'idle in transaction (aborted)' THEN 2
+            ELSE 3
+        END AS state_rank
+    FROM pg_stat_activity
+    WHERE pid <> pg_backend_pid()
+      AND state IS NOT NULL
+      AND backend_type = 'client backend'
+), ranked AS (
+    SELECT *, ROW_NUMBER() OVER (PARTITION BY state_rank ORDER BY duration_secs DESC) AS rank_in_state
+    FROM activity
+)
+SELECT pid, usename, datname, state, wait_event_type, wait_event, query_start,
+       duration_secs, query, backend_type, xact_start, query_id, application_name, state_change
+FROM ranked
+WHERE state_rank < 3 OR $1::bigint IS NULL OR rank_in_state <= $1::bigint
+ORDER BY state_rank, duration_secs DESC"
+    )
+}
+
+const CLIENT_PIDS_SQL: &str = "SELECT pid FROM pg_stat_activity
+WHERE pid <> pg_backend_pid()
+  AND state IS NOT NULL
+  AND backend_type = 'client backend'";
+
+/// PIDs of every client backend, as `build_active_queries_sql` would list
+/// them without a cap.
+pub async fn fetch_client_pids(client: &Client) -> DbResult<Vec<i32>> {
+    let rows = client
+        .query(CLIENT_PIDS_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_client_pids",
+            source: e,
+        })?;
+    Ok(rows.iter().map(|row| row.get("pid")).collect())
+}
+
+/// Every backend that isn't a client's. `leader_pid` came in PG13; before
+/// that parallel workers can't be tied to their leader.
+fn background_workers_sql(version: u32) -> String {
+    let leader_pid = if version >= 13 { "leader_pid" } else { "NULL::int AS leader_pid" };
+    format!(
+        "SELECT
     pid,
-    usename,
+    COALESCE(backend_type, 'unknown') AS backend_type,
     datname,
     state,
     wait_event_type,
     wait_event,
-    query_start,
-    COALESCE(EXTRACT(EPOCH FROM (clock_timestamp() - query_start))::float8, 0) AS duration_secs,
-    query,
-    backend_type
+    COALESCE(EXTRACT(EPOCH FROM (clock_timestamp() - COALESCE(query_start, backend_start)))::float8, 0) AS duration_secs,
+    NULLIF(query, '') AS query,
+    {leader_pid}
 FROM pg_stat_activity
 WHERE pid <> pg_backend_pid()
-  AND state IS NOT NULL
-  AND backend_type = 'client backend'
-ORDER BY
-    CASE state
-        WHEN 'active' THEN 0
-        WHEN 'idle in transaction' THEN 1
-        WHEN 'idle in transaction (aborted)' THEN 2
-        ELSE 3
-    END,
-    duration_secs DESC
-LIMIT 100
-";
+  AND backend_type IS DISTINCT FROM 'client backend'
+ORDER BY backend_type, pid"
+    )
+}
 
 const WAIT_EVENTS_SQL: &str = "
 SELECT
@@ -52,6 +116,23 @@ GROUP BY wait_event_type, wait_event
 ORDER BY count DESC
 ";
 
+/// Samples the extension took since `$1` (the last second on the first
+/// poll), minus idle backends and background processes waiting for work
+const WAIT_HISTORY_SQL: &str = "
+SELECT
+    COALESCE(event_type, 'CPU/Running') AS wait_event_type,
+    COALESCE(event, 'CPU/Running') AS wait_event,
+    COUNT(*) AS count,
+    MAX(ts) AS latest
+FROM pg_wait_sampling_history
+WHERE ts > COALESCE($1, now() - interval '1 second')
+  AND pid <> pg_backend_pid()
+  AND event_type IS DISTINCT FROM 'Activity'
+  AND event IS DISTINCT FROM 'ClientRead'
+GROUP BY 1, 2
+ORDER BY count DESC
+";
+
 /// See `limits::MAX_BLOCKING_CHAINS`
 const BLOCKING_SQL: &str = "
 SELECT
@@ -72,6 +153,57 @@ ORDER BY blocked_duration_secs DESC
 LIMIT 50
 ";
 
+/// Every lock except the virtual transaction ID each transaction holds on
+/// itself, waiting ones first. Relations are left as OIDs for the catalog
+/// cache to name; those in other databases can't be named from here.
+const LOCKS_SQL: &str = "
+SELECT
+    l.pid,
+    l.locktype,
+    l.mode,
+    l.granted,
+    l.relation::text AS relation,
+    CASE
+        WHEN l.database IN (0, (SELECT oid FROM pg_database WHERE datname = current_database()))
+            THEN l.relation
+    END AS relation_oid,
+    a.usename,
+    a.query,
+    a.state,
+    COALESCE(EXTRACT(EPOCH FROM (clock_timestamp() -
+        CASE WHEN l.granted THEN a.xact_start ELSE a.query_start END))::float8, 0) AS duration_secs,
+    CASE WHEN l.granted THEN '{}'::int[] ELSE pg_blocking_pids(l.pid) END AS blocking_pids
+FROM pg_locks AS l
+JOIN pg_stat_activity AS a ON a.pid = l.pid
+WHERE l.pid <> pg_backend_pid()
+  AND l.locktype <> 'virtualxid'
+ORDER BY l.granted, duration_secs DESC
+LIMIT 200
+";
+
+/// Client backends grouped by user, database and application, largest
+/// groups first. The longest duration only counts backends doing something,
+/// so a pool of long-idle connections doesn't look stuck.
+const CONNECTION_GROUPS_SQL: &str = "
+SELECT
+    usename,
+    datname,
+    COALESCE(application_name, '') AS application_name,
+    COUNT(*) AS total,
+    COUNT(*) FILTER (WHERE state = 'active') AS active,
+    COUNT(*) FILTER (WHERE state = 'idle') AS idle,
+    COUNT(*) FILTER (WHERE state LIKE 'idle in transaction%') AS idle_in_transaction,
+    COUNT(*) FILTER (WHERE wait_event_type = 'Lock') AS waiting,
+    COALESCE(MAX(EXTRACT(EPOCH FROM (clock_timestamp() - COALESCE(xact_start, query_start))))
+        FILTER (WHERE state <> 'idle'), 0)::float8 AS longest_secs
+FROM pg_stat_activity
+WHERE pid <> pg_backend_pid()
+  AND backend_type = 'client backend'
+GROUP BY usename, datname, COALESCE(application_name, '')
+ORDER BY total DESC, usename, datname
+LIMIT 200
+";
+
 const BUFFER_CACHE_SQL: &str = "
 SELECT
     COALESCE(blks_hit, 0) AS blks_hit,
@@ -194,8 +326,30 @@ ORDER BY s.slot_name
 ";
 
 /// Subscriptions query for PG 10+ (logical replication subscriber side)
-const SUBSCRIPTIONS_SQL: &str = "
-SELECT
+/// One row per subscription, from its apply worker. PG15 added the error
+/// counters in `pg_stat_subscription_stats` and PG18 the conflict counters;
+/// PG16 parallel apply workers are skipped so each subscription appears once.
+fn subscriptions_sql(version: u32) -> String {
+    let errors = if version >= 15 {
+        "ss.apply_error_count, ss.sync_error_count"
+    } else {
+        "NULL::bigint AS apply_error_count, NULL::bigint AS sync_error_count"
+    };
+    let conflicts = if version >= 18 {
+        "(ss.confl_insert_exists + ss.confl_update_origin_differs + ss.confl_update_exists
+        + ss.confl_update_missing + ss.confl_delete_origin_differs + ss.confl_delete_missing
+        + ss.confl_multiple_unique_conflicts) AS conflict_count"
+    } else {
+        "NULL::bigint AS conflict_count"
+    };
+    let stats_join = if version >= 15 {
+        "\nLEFT JOIN pg_stat_subscription_stats ss ON ss.subid = sub.oid"
+    } else {
+        ""
+    };
+    let leader = if version >= 16 { "\n  AND stat.leader_pid IS NULL" } else { "" };
+    format!(
+        "SELECT
     sub.subname,
     stat.pid,
     (SELECT COUNT(*) FROM pg_subscription_rel WHERE srsubid = sub.oid) AS relcount,
@@ -204,29 +358,99 @@ SELECT
     stat.last_msg_receipt_time,
     stat.latest_end_lsn::text AS latest_end_lsn,
     stat.latest_end_time,
-    sub.subenabled AS enabled
+    sub.subenabled AS enabled,
+    EXTRACT(EPOCH FROM now() - stat.latest_end_time)::float8 AS apply_lag_secs,
+    pg_wal_lsn_diff(stat.received_lsn, stat.latest_end_lsn)::bigint AS apply_lag_bytes,
+    {errors},
+    {conflicts}
 FROM pg_subscription sub
-LEFT JOIN pg_stat_subscription stat ON sub.oid = stat.subid
-WHERE stat.relid IS NULL
-ORDER BY sub.subname
-";
+LEFT JOIN pg_stat_subscription stat ON sub.oid = stat.subid{stats_join}
+WHERE stat.relid IS NULL{leader}
+ORDER BY sub.subname"
+    )
+}
 
 /// Vacuum progress query - uses 0 for `num_dead_tuples` for compatibility
-/// (column name varies across PG versions and cloud providers)
+/// (column name varies across PG versions and cloud providers). Tables are
+/// left as OIDs for the catalog cache to name.
 const VACUUM_PROGRESS_SQL: &str = "
 SELECT p.pid, a.datname,
-    COALESCE(n.nspname || '.' || c.relname, p.relid::text) AS table_name,
+    p.relid::text AS table_name,
+    CASE WHEN p.datid = (SELECT oid FROM pg_database WHERE datname = current_database())
+        THEN p.relid END AS relid,
     p.phase,
     p.heap_blks_total, p.heap_blks_vacuumed,
     (CASE WHEN p.heap_blks_total > 0 THEN (100.0 * p.heap_blks_vacuumed / p.heap_blks_total) ELSE 0 END)::float8 AS progress_pct,
     0::bigint AS num_dead_tuples
 FROM pg_stat_progress_vacuum p
 JOIN pg_stat_activity a ON a.pid = p.pid
-LEFT JOIN pg_class c ON c.oid = p.relid
-LEFT JOIN pg_namespace n ON n.oid = c.relnamespace
 ORDER BY p.pid
 ";
 
+/// One SELECT per `pg_stat_progress_*` view, with the lowest server
+/// version that has it. Each yields the columns of `OperationProgress`;
+/// relations are left as OIDs for the catalog cache to name.
+const OPERATION_PROGRESS_SQL: [(u32, &str); 4] = [
+    (
+        12,
+        "SELECT 'create_index' AS kind, p.pid, p.datname, p.command,
+    COALESCE(p.relid::text, '') AS target,
+    CASE WHEN p.datid = (SELECT oid FROM pg_database WHERE datname = current_database()) THEN p.relid END AS relid,
+    p.phase,
+    (CASE WHEN p.blocks_total > 0 THEN p.blocks_done ELSE p.tuples_done END)::bigint AS work_done,
+    (CASE WHEN p.blocks_total > 0 THEN p.blocks_total ELSE p.tuples_total END)::bigint AS work_total,
+    a.query_start
+FROM pg_stat_progress_create_index p",
+    ),
+    (
+        12,
+        "SELECT 'cluster', p.pid, p.datname, p.command,
+    COALESCE(p.relid::text, ''),
+    CASE WHEN p.datid = (SELECT oid FROM pg_database WHERE datname = current_database()) THEN p.relid END,
+    p.phase,
+    p.heap_blks_scanned::bigint, p.heap_blks_total::bigint,
+    a.query_start
+FROM pg_stat_progress_cluster p",
+    ),
+    (
+        13,
+        "SELECT 'analyze', p.pid, p.datname, 'ANALYZE',
+    COALESCE(p.relid::text, ''),
+    CASE WHEN p.datid = (SELECT oid FROM pg_database WHERE datname = current_database()) THEN p.relid END,
+    p.phase,
+    p.sample_blks_scanned::bigint, p.sample_blks_total::bigint,
+    a.query_start
+FROM pg_stat_progress_analyze p",
+    ),
+    (
+        13,
+        "SELECT 'base_backup', p.pid, NULL::name, 'BASE BACKUP',
+    COALESCE(a.application_name || ' ', '') || COALESCE(host(a.client_addr), 'local'), NULL::oid, p.phase,
+    p.backup_streamed::bigint, COALESCE(p.backup_total, 0)::bigint,
+    a.backend_start
+FROM pg_stat_progress_basebackup p",
+    ),
+];
+
+/// The progress views `version` has, as one query; None before PG12.
+fn operations_sql(version: u32) -> Option<String> {
+    let selects: Vec<String> = OPERATION_PROGRESS_SQL
+        .iter()
+        .filter(|(since, _)| version >= *since)
+        .map(|(_, select)| format!("{select}\nJOIN pg_stat_activity a ON a.pid = p.pid"))
+        .collect();
+    if selects.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "SELECT kind, pid, datname, command, target, relid, phase, work_done, work_total,
+    EXTRACT(EPOCH FROM now() - query_start)::float8 AS elapsed_secs
+FROM (\n{}\n) ops
+ORDER BY pid",
+        selects.join("\nUNION ALL\n")
+    ))
+}
+
 const WRAPAROUND_SQL: &str = "
 SELECT datname,
     age(datfrozenxid) AS xid_age,
@@ -245,8 +469,10 @@ SELECT
     COALESCE(s.idx_scan, 0)::bigint AS idx_scan,
     COALESCE(s.idx_tup_read, 0)::bigint AS idx_tup_read,
     COALESCE(s.idx_tup_fetch, 0)::bigint AS idx_tup_fetch,
-    pg_get_indexdef(s.indexrelid) AS index_definition
+    pg_get_indexdef(s.indexrelid) AS index_definition,
+    i.indisvalid AS is_valid
 FROM pg_stat_user_indexes s
+JOIN pg_index i ON i.indexrelid = s.indexrelid
 ORDER BY pg_relation_size(s.indexrelid) DESC NULLS LAST
 ";
 
@@ -327,6 +553,95 @@ LIMIT 100",
     )
 }
 
+/// Cumulative calls and execution time for a handful of statements, summed
+/// across users and databases. Cheap enough to run every second.
+fn build_statement_samples_sql(cols: StatStatementsColumns) -> String {
+    format!(
+        "SELECT
+    queryid,
+    SUM(calls)::bigint AS calls,
+    SUM(total_{tp}time)::float8 AS total_exec_time
+FROM pg_stat_statements
+WHERE queryid = ANY($1)
+GROUP BY queryid",
+        tp = cols.time_prefix,
+    )
+}
+
+/// `pg_stat_monitor` (2.0+) summed over its time buckets into the same
+/// columns as `build_stat_statements_sql`, plus the calls that errored.
+/// Block I/O times were renamed like `pg_stat_statements`' on PG17.
+fn build_stat_monitor_sql(cols: StatStatementsColumns) -> String {
+    format!(
+        "SELECT
+    queryid,
+    MIN(query) AS query,
+    SUM(calls)::bigint AS calls,
+    SUM(total_exec_time)::float8 AS total_exec_time,
+    MIN(min_exec_time)::float8 AS min_exec_time,
+    CASE WHEN SUM(calls) > 0 THEN SUM(total_exec_time) / SUM(calls) ELSE 0 END::float8 AS mean_exec_time,
+    MAX(max_exec_time)::float8 AS max_exec_time,
+    MAX(stddev_exec_time)::float8 AS stddev_exec_time,
+    SUM(rows)::bigint AS rows,
+    SUM(shared_blks_hit)::bigint AS shared_blks_hit,
+    SUM(shared_blks_read)::bigint AS shared_blks_read,
+    SUM(shared_blks_dirtied)::bigint AS shared_blks_dirtied,
+    SUM(shared_blks_written)::bigint AS shared_blks_written,
+    SUM(local_blks_hit)::bigint AS local_blks_hit,
+    SUM(local_blks_read)::bigint AS local_blks_read,
+    SUM(local_blks_dirtied)::bigint AS local_blks_dirtied,
+    SUM(local_blks_written)::bigint AS local_blks_written,
+    SUM(temp_blks_read)::bigint AS temp_blks_read,
+    SUM(temp_blks_written)::bigint AS temp_blks_written,
+    SUM({blk_read})::float8 AS blk_read_time,
+    SUM({blk_write})::float8 AS blk_write_time,
+    CASE
+        WHEN SUM(shared_blks_hit) + SUM(shared_blks_read) = 0 THEN 1.0
+        ELSE SUM(shared_blks_hit)::float / (SUM(shared_blks_hit) + SUM(shared_blks_read))
+    END AS hit_ratio,
+    COALESCE(SUM(calls) FILTER (WHERE elevel > 0), 0)::bigint AS errors
+FROM pg_stat_monitor
+WHERE queryid IS NOT NULL
+GROUP BY queryid
+ORDER BY total_exec_time DESC
+LIMIT 100",
+        blk_read = cols.blk_read_time,
+        blk_write = cols.blk_write_time,
+    )
+}
+
+/// `resp_calls` histograms summed element-wise across time buckets.
+const STAT_MONITOR_HISTOGRAM_SQL: &str = "
+SELECT queryid, r.i::int AS bucket, SUM(r.c::bigint)::bigint AS calls
+FROM pg_stat_monitor, unnest(resp_calls) WITH ORDINALITY AS r(c, i)
+WHERE queryid = ANY($1)
+GROUP BY queryid, r.i
+";
+
+const STAT_MONITOR_HISTORY_SQL: &str = "
+SELECT
+    queryid,
+    bucket_start_time,
+    SUM(calls)::bigint AS calls,
+    SUM(total_exec_time)::float8 AS total_exec_time
+FROM pg_stat_monitor
+WHERE queryid = ANY($1)
+GROUP BY queryid, bucket_start_time
+ORDER BY bucket_start_time
+";
+
+/// Parse one `range()` element such as `{0.000 - 1.000}` or
+/// `(100000.000 - ...)` into histogram bounds in ms.
+fn parse_monitor_range(range: &str) -> Option<(f64, Option<f64>)> {
+    let mut numbers = range
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|part| part.chars().any(|c| c.is_ascii_digit()))
+        .map(str::parse::<f64>);
+    let lower = numbers.next()?.ok()?;
+    let upper = numbers.next().and_then(Result::ok);
+    Some((lower, upper))
+}
+
 /// Parse extension version like "1.8" or "1.10" and return (major, minor)
 pub(crate) fn parse_ext_version(v: &str) -> Option<(u32, u32)> {
     let parts: Vec<&str> = v.split('.').collect();
@@ -408,6 +723,90 @@ mod tests {
         assert_eq!(parse_ext_version("99.99"), Some((99, 99)));
     }
 
+    #[test]
+    fn explain_sql_uses_generic_plan_for_parameters() {
+        assert_eq!(
+            build_explain_sql("SELECT * FROM t WHERE id = 1;", 14, false),
+            Ok(("EXPLAIN (FORMAT JSON) SELECT * FROM t WHERE id = 1".to_string(), false))
+        );
+        assert_eq!(
+            build_explain_sql("SELECT * FROM t WHERE id = $1", 16, false),
+            Ok(("EXPLAIN (GENERIC_PLAN, FORMAT JSON) SELECT * FROM t WHERE id = $1".to_string(), true))
+        );
+        assert!(build_explain_sql("SELECT * FROM t WHERE id = $1", 15, false)
+            .unwrap_err()
+            .contains("PostgreSQL 16"));
+        // Dollar quoting is not a parameter
+        assert_eq!(build_explain_sql("SELECT $$a$$", 14, false).map(|(_, g)| g), Ok(false));
+        assert!(build_explain_sql("  ", 16, false).is_err());
+        assert_eq!(
+            build_explain_sql("SELECT 1", 16, true).map(|(sql, _)| sql),
+            Ok("EXPLAIN (VERBOSE, FORMAT JSON) SELECT 1".to_string())
+        );
+    }
+
+    #[test]
+    fn operations_sql_reads_the_progress_views_the_server_has() {
+        assert_eq!(operations_sql(11), None);
+        let pg12 = operations_sql(12).unwrap();
+        assert!(pg12.contains("pg_stat_progress_create_index") && pg12.contains("pg_stat_progress_cluster"));
+        assert!(!pg12.contains("pg_stat_progress_analyze"));
+        let pg16 = operations_sql(16).unwrap();
+        assert_eq!(pg16.matches("UNION ALL").count(), 3);
+        assert_eq!(pg16.matches("JOIN pg_stat_activity a ON a.pid = p.pid").count(), 4);
+        assert!(pg16.contains("pg_stat_progress_basebackup"));
+    }
+
+    #[test]
+    fn wal_dir_sql_counts_ready_files_from_pg12() {
+        assert!(wal_dir_sql(11).contains("NULL::bigint AS archive_ready"));
+        assert!(wal_dir_sql(12).contains("pg_ls_archive_statusdir()"));
+        assert!(wal_dir_sql(12).contains("FROM pg_ls_waldir()"));
+    }
+
+    #[test]
+    fn subscriptions_sql_reads_stats_the_server_has() {
+        let pg14 = subscriptions_sql(14);
+        assert!(pg14.contains("NULL::bigint AS apply_error_count"));
+        assert!(!pg14.contains("pg_stat_subscription_stats"));
+        let pg16 = subscriptions_sql(16);
+        assert!(pg16.contains("LEFT JOIN pg_stat_subscription_stats ss"));
+        assert!(pg16.contains("NULL::bigint AS conflict_count"));
+        assert!(pg16.contains("stat.leader_pid IS NULL"));
+        assert!(subscriptions_sql(18).contains("ss.confl_delete_missing"));
+    }
+
+    #[test]
+    fn background_workers_sql_reads_leader_pid_from_pg13() {
+        assert!(background_workers_sql(12).contains("NULL::int AS leader_pid"));
+        assert!(background_workers_sql(13).contains("    leader_pid\n"));
+    }
+
+    #[test]
+    fn active_queries_sql_selects_query_id_from_pg14() {
+        assert!(build_active_queries_sql(13).contains("NULL::bigint AS query_id"));
+        let v14 = build_active_queries_sql(14);
+        assert!(v14.contains("        query_id,\n"));
+        assert!(!v14.contains("NULL::bigint AS query_id"));
+    }
+
+    #[test]
+    fn active_queries_sql_caps_only_low_priority_states() {
+        let sql = build_active_queries_sql(16);
+        assert!(sql.contains("state_rank < 3 OR $1::bigint IS NULL OR rank_in_state <= $1::bigint"));
+        assert!(sql.contains("ORDER BY state_rank, duration_secs DESC"));
+        assert!(!sql.contains("LIMIT"));
+    }
+
+    #[test]
+    fn statement_samples_sql_follows_version_columns() {
+        let v11 = build_statement_samples_sql(STAT_STATEMENTS_V11);
+        assert!(v11.contains("SUM(total_time)"));
+        let v13 = build_statement_samples_sql(STAT_STATEMENTS_V13);
+        assert!(v13.contains("SUM(total_exec_time)"));
+        assert!(v13.contains("queryid = ANY($1)"));
+    }
+
     #[test]
     fn stat_statements_sql_v11_uses_total_time() {
         let sql = build_stat_statements_sql(STAT_STATEMENTS_V11);
@@ -446,6 +845,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stat_monitor_sql_sums_buckets_into_stat_statements_columns() {
+        let sql = build_stat_monitor_sql(STAT_STATEMENTS_V17);
+        assert!(sql.contains("SUM(shared_blk_read_time)::float8 AS blk_read_time"));
+        assert!(sql.contains("AS errors"));
+        assert!(sql.contains("GROUP BY queryid"));
+        assert!(build_stat_monitor_sql(STAT_STATEMENTS_V13).contains("SUM(blk_read_time)"));
+    }
+
+    #[test]
+    fn monitor_ranges_parse_with_an_open_last_bucket() {
+        assert_eq!(parse_monitor_range("{0.000 - 1.000}"), Some((0.0, Some(1.0))));
+        assert_eq!(parse_monitor_range("(3.162 - 10.000)"), Some((3.162, Some(10.0))));
+        assert_eq!(parse_monitor_range("{100000.000 - ...}"), Some((100_000.0, None)));
+        assert_eq!(parse_monitor_range("{}"), None);
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Version selection logic tests
     // ─────────────────────────────────────────────────────────────────────────────
@@ -560,6 +976,8 @@ const ACTIVITY_SUMMARY_SQL: &str = "
 SELECT
     COUNT(*) FILTER (WHERE state = 'active' AND pid <> pg_backend_pid()) AS active_query_count,
     COUNT(*) FILTER (WHERE state = 'idle in transaction') AS idle_in_transaction_count,
+    COUNT(*) FILTER (WHERE state = 'idle in transaction (aborted)') AS aborted_txn_count,
+    COUNT(*) FILTER (WHERE state = 'idle') AS idle_count,
     COUNT(*) AS total_backends,
     (SELECT COUNT(*) FROM pg_locks WHERE NOT granted) AS lock_count,
     COUNT(*) FILTER (WHERE wait_event_type = 'Lock') AS waiting_count,
@@ -571,7 +989,7 @@ WHERE backend_type = 'client backend'
 
 const EXTENSIONS_SQL: &str = "
 SELECT extname, extversion FROM pg_extension
-WHERE extname IN ('pg_stat_statements', 'pg_stat_kcache', 'pg_wait_sampling', 'pg_buffercache', 'pgstattuple')
+WHERE extname IN ('pg_stat_statements', 'pg_stat_monitor', 'pg_stat_kcache', 'pg_wait_sampling', 'pg_buffercache', 'pgstattuple', 'hypopg')
 ";
 
 const SERVER_INFO_SQL: &str = "
@@ -590,11 +1008,113 @@ SELECT
     short_desc,
     context,
     source,
-    COALESCE(pending_restart, false) AS pending_restart
+    COALESCE(pending_restart, false) AS pending_restart,
+    boot_val,
+    reset_val
 FROM pg_settings
 ORDER BY category, name
 ";
 
+/// Per-role and per-database overrides (`ALTER ROLE/DATABASE ... SET`).
+/// A zero `setrole`/`setdatabase` means the override applies to all.
+const ROLE_SETTINGS_SQL: &str = "
+SELECT
+    r.rolname AS role,
+    d.datname AS database,
+    split_part(cfg, '=', 1) AS name,
+    substr(cfg, strpos(cfg, '=') + 1) AS value
+FROM pg_db_role_setting s
+LEFT JOIN pg_roles r ON r.oid = s.setrole
+LEFT JOIN pg_database d ON d.oid = s.setdatabase
+CROSS JOIN LATERAL unnest(s.setconfig) AS cfg
+ORDER BY name, role NULLS FIRST, database NULLS FIRST
+";
+
+/// Roles with their attributes and the roles they are members of.
+const ROLES_SQL: &str = "
+SELECT
+    r.rolname AS name,
+    r.rolsuper AS superuser,
+    r.rolcanlogin AS login,
+    r.rolcreatedb AS createdb,
+    r.rolcreaterole AS createrole,
+    r.rolreplication AS replication,
+    r.rolbypassrls AS bypassrls,
+    r.rolconnlimit AS conn_limit,
+    NULLIF(r.rolvaliduntil, 'infinity') AS valid_until,
+    ARRAY(
+        SELECT b.rolname::text
+        FROM pg_auth_members m
+        JOIN pg_roles b ON b.oid = m.roleid
+        WHERE m.member = r.oid
+        ORDER BY 1
+    ) AS member_of
+FROM pg_roles r
+WHERE r.rolname !~ '^pg_'
+ORDER BY r.rolname
+";
+
+/// Relations of the current database holding back its frozen XID, oldest
+/// first. `relfrozenxid` is per database, so other databases need their own
+/// connection.
+const WRAPAROUND_TABLES_SQL: &str = "
+SELECT
+    COALESCE(owner_ns.nspname, n.nspname) AS schemaname,
+    COALESCE(owner.relname, c.relname) AS relname,
+    owner.oid IS NOT NULL AS via_toast,
+    age(c.relfrozenxid) AS xid_age,
+    pg_total_relation_size(COALESCE(owner.oid, c.oid)) AS size_bytes
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+LEFT JOIN pg_class owner ON owner.reltoastrelid = c.oid
+LEFT JOIN pg_namespace owner_ns ON owner_ns.oid = owner.relnamespace
+WHERE c.relkind IN ('r', 'm', 't')
+ORDER BY age(c.relfrozenxid) DESC
+LIMIT 25
+";
+
+/// Privileges one role holds on databases, schemas and relations, from
+/// their ACLs (or the owner defaults when the ACL is empty).
+const ROLE_GRANTS_SQL: &str = "
+WITH target AS (
+    SELECT oid FROM pg_roles WHERE rolname = $1
+), acl AS (
+    SELECT 'database' AS kind, d.datname::text AS object,
+           (aclexplode(COALESCE(d.datacl, acldefault('d', d.datdba)))).*
+    FROM pg_database d
+    UNION ALL
+    SELECT 'schema', n.nspname::text,
+           (aclexplode(COALESCE(n.nspacl, acldefault('n', n.nspowner)))).*
+    FROM pg_namespace n
+    WHERE n.nspname !~ '^pg_' AND n.nspname <> 'information_schema'
+    UNION ALL
+    SELECT CASE c.relkind
+               WHEN 'v' THEN 'view'
+               WHEN 'm' THEN 'materialized view'
+               WHEN 'f' THEN 'foreign table'
+               WHEN 'S' THEN 'sequence'
+               ELSE 'table'
+           END,
+           n.nspname || '.' || c.relname,
+           (aclexplode(COALESCE(c.relacl,
+               acldefault(CASE WHEN c.relkind = 'S' THEN 's' ELSE 'r' END::\"char\", c.relowner)))).*
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f', 'S')
+      AND n.nspname !~ '^pg_' AND n.nspname <> 'information_schema'
+)
+SELECT
+    kind,
+    object,
+    array_agg(DISTINCT privilege_type ORDER BY privilege_type) AS privileges,
+    bool_or(is_grantable) AS grantable
+FROM acl
+WHERE grantee = (SELECT oid FROM target)
+GROUP BY kind, object
+ORDER BY kind, object
+LIMIT 500
+";
+
 const PG_EXTENSIONS_LIST_SQL: &str = "
 SELECT
     e.extname AS name,
@@ -608,6 +1128,141 @@ LEFT JOIN pg_available_extensions a ON a.name = e.extname
 ORDER BY e.extname
 ";
 
+const TABLE_OID_SQL: &str = "
+SELECT c.oid
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1 AND c.relname = $2
+";
+
+const TABLE_COLUMNS_SQL: &str = "
+SELECT
+    a.attname AS name,
+    format_type(a.atttypid, a.atttypmod) AS data_type,
+    a.attnotnull AS not_null,
+    pg_get_expr(d.adbin, d.adrelid) AS default_expr
+FROM pg_attribute a
+LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+WHERE a.attrelid = $1 AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY a.attnum
+";
+
+const TABLE_INDEXES_SQL: &str = "
+SELECT
+    c.relname AS name,
+    pg_get_indexdef(i.indexrelid) AS definition,
+    i.indisprimary AS is_primary,
+    i.indisunique AS is_unique,
+    i.indisvalid AS is_valid,
+    pg_relation_size(i.indexrelid)::bigint AS size_bytes,
+    COALESCE(s.idx_scan, 0)::bigint AS idx_scan,
+    COALESCE(s.idx_tup_read, 0)::bigint AS idx_tup_read,
+    COALESCE(s.idx_tup_fetch, 0)::bigint AS idx_tup_fetch
+FROM pg_index i
+JOIN pg_class c ON c.oid = i.indexrelid
+LEFT JOIN pg_stat_all_indexes s ON s.indexrelid = i.indexrelid
+WHERE i.indrelid = $1
+ORDER BY i.indisprimary DESC, c.relname
+";
+
+const TABLE_FOREIGN_KEYS_SQL: &str = "
+SELECT
+    con.conname AS name,
+    CASE WHEN con.conrelid = $1 THEN con.confrelid ELSE con.conrelid END::regclass::text AS other_table,
+    pg_get_constraintdef(con.oid) AS definition,
+    con.conrelid <> $1 AS incoming
+FROM pg_constraint con
+WHERE con.contype = 'f' AND (con.conrelid = $1 OR con.confrelid = $1)
+ORDER BY incoming, con.conname
+";
+
+const TABLE_TRIGGERS_SQL: &str = "
+SELECT
+    t.tgname AS name,
+    pg_get_triggerdef(t.oid) AS definition,
+    t.tgenabled <> 'D' AS enabled
+FROM pg_trigger t
+WHERE t.tgrelid = $1 AND NOT t.tgisinternal
+ORDER BY t.tgname
+";
+
+const LOG_TAIL_SQL: &str = "
+SELECT pg_read_binary_file(f, GREATEST((pg_stat_file(f, false)).size - $1, 0), $1, false)
+FROM pg_current_logfile() AS f
+";
+
+/// Carry on from `$2` in file `$1`, or start `$3` bytes from the end when
+/// the file changed (rotation) or shrank; never read more than `$3` bytes
+const LOG_READ_SQL: &str = "
+SELECT f, o.start, pg_read_binary_file(f, o.start, $3, false)
+FROM pg_current_logfile() AS f,
+     pg_stat_file(f, false) AS s,
+     LATERAL (
+         SELECT CASE WHEN f = $1 AND $2 <= s.size
+                     THEN GREATEST($2, s.size - $3)
+                     ELSE GREATEST(s.size - $3, 0)
+                END AS start
+     ) o
+";
+
+/// Relations listed in the Buffer Cache panel
+pub const BUFFER_CACHE_TOP: i64 = 50;
+
+/// Buffers by usage count; unused buffers have a NULL usage count
+const BUFFER_USAGE_SQL: &str = "
+SELECT
+    b.usagecount::int4 AS usagecount,
+    count(*)::int8 AS buffers,
+    count(*) FILTER (WHERE b.isdirty)::int8 AS dirty,
+    current_setting('block_size')::int8 AS block_size
+FROM pg_buffercache b
+GROUP BY b.usagecount
+";
+
+/// Shared catalogs have reldatabase 0. Schemas are left for the catalog
+/// cache to name.
+const BUFFER_RELATIONS_SQL: &str = "
+SELECT
+    c.oid AS relid,
+    c.relnamespace::text AS schemaname,
+    c.relname,
+    c.relkind::text AS relkind,
+    count(*)::int8 AS buffers,
+    count(*) FILTER (WHERE b.isdirty)::int8 AS dirty,
+    avg(b.usagecount)::float8 AS avg_usage,
+    (100.0 * count(*) * current_setting('block_size')::int8
+        / NULLIF(pg_relation_size(c.oid), 0))::float8 AS cached_pct
+FROM pg_buffercache b
+JOIN pg_class c ON b.relfilenode = pg_relation_filenode(c.oid)
+WHERE b.reldatabase IN (0, (SELECT oid FROM pg_database WHERE datname = current_database()))
+GROUP BY c.oid, c.relnamespace, c.relname, c.relkind
+ORDER BY buffers DESC
+LIMIT $1
+";
+
+/// Reads every page of the table, so only run on request
+const TABLE_PGSTATTUPLE_EXACT_SQL: &str = "
+SELECT table_len, tuple_percent, dead_tuple_percent, free_space, free_percent
+FROM pgstattuple($1::oid::regclass)
+";
+
+/// Valid btree indexes of a table; empty ones have no leaf density to report
+const TABLE_PGSTATINDEX_EXACT_SQL: &str = "
+SELECT
+    c.relname AS index_name,
+    pg_relation_size(i.indexrelid) AS index_size,
+    s.avg_leaf_density,
+    s.leaf_fragmentation
+FROM pg_index i
+JOIN pg_class c ON c.oid = i.indexrelid,
+LATERAL pgstatindex(i.indexrelid::regclass) s
+WHERE i.indrelid = $1
+  AND i.indisvalid
+  AND c.relam = (SELECT oid FROM pg_am WHERE amname = 'btree')
+  AND s.leaf_pages > 0
+ORDER BY c.relname
+";
+
 const DB_SIZE_SQL: &str = "
 SELECT pg_database_size(current_database()) AS db_size
 ";
@@ -658,6 +1313,21 @@ SELECT
 FROM pg_stat_wal
 ";
 
+/// I/O by backend type and context (PG16+). Counts a backend can't do are
+/// NULL rather than zero, and object types are summed together.
+const IO_STATS_SQL: &str = "
+SELECT
+    backend_type,
+    context,
+    COALESCE(sum(reads), 0)::bigint AS reads,
+    COALESCE(sum(writes), 0)::bigint AS writes,
+    COALESCE(sum(extends), 0)::bigint AS extends,
+    COALESCE(sum(fsyncs), 0)::bigint AS fsyncs
+FROM pg_stat_io
+GROUP BY backend_type, context
+ORDER BY backend_type, context
+";
+
 /// WAL stats query for PG18+ (`wal_write`, `wal_sync`, `wal_write_time`, `wal_sync_time` removed)
 const WAL_STATS_SQL_V18: &str = "
 SELECT
@@ -684,6 +1354,24 @@ SELECT
 FROM pg_stat_archiver
 ";
 
+/// Size of `pg_wal` and, from PG12 where `pg_ls_archive_statusdir()`
+/// appeared, segments still waiting to be archived. Both functions need
+/// superuser or `pg_monitor`.
+fn wal_dir_sql(version: u32) -> String {
+    let archive_ready = if version >= 12 {
+        "(SELECT count(*) FROM pg_ls_archive_statusdir() WHERE name LIKE '%.ready')"
+    } else {
+        "NULL::bigint"
+    };
+    format!(
+        "SELECT
+    count(*) AS segments,
+    COALESCE(sum(size), 0)::bigint AS bytes,
+    {archive_ready} AS archive_ready
+FROM pg_ls_waldir()"
+    )
+}
+
 /// Background writer stats query (all versions)
 const BGWRITER_STATS_SQL: &str = "
 SELECT
@@ -698,7 +1386,9 @@ const DATABASE_STATS_SQL: &str = "
 SELECT
     COALESCE(xact_commit, 0) AS xact_commit,
     COALESCE(xact_rollback, 0) AS xact_rollback,
-    COALESCE(blks_read, 0) AS blks_read
+    COALESCE(blks_read, 0) AS blks_read,
+    stats_reset,
+    pg_postmaster_start_time() AS server_start
 FROM pg_stat_database
 WHERE datname = current_database()
 ";
@@ -927,6 +1617,7 @@ pub async fn detect_extensions(client: &Client) -> DetectedExtensions {
                 ext.pg_stat_statements = true;
                 ext.pg_stat_statements_version = Some(version);
             }
+            "pg_stat_monitor" => ext.pg_stat_monitor = true,
             "pg_stat_kcache" => ext.pg_stat_kcache = true,
             "pg_wait_sampling" => ext.pg_wait_sampling = true,
             "pg_buffercache" => ext.pg_buffercache = true,
@@ -934,6 +1625,7 @@ pub async fn detect_extensions(client: &Client) -> DetectedExtensions {
                 ext.pgstattuple = true;
                 ext.pgstattuple_version = Some(version);
             }
+            "hypopg" => ext.hypopg = true,
             _ => {}
         }
     }
@@ -959,11 +1651,96 @@ pub async fn fetch_pg_settings(client: &Client) -> DbResult<Vec<PgSetting>> {
             context: row.get("context"),
             source: row.get("source"),
             pending_restart: row.get("pending_restart"),
+            boot_val: row.get("boot_val"),
+            reset_val: row.get("reset_val"),
         });
     }
     Ok(results)
 }
 
+pub async fn fetch_role_settings(client: &Client) -> DbResult<Vec<RoleSetting>> {
+    let rows = client
+        .query(ROLE_SETTINGS_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_role_settings",
+            source: e,
+        })?;
+    Ok(rows
+        .iter()
+        .map(|row| RoleSetting {
+            role: row.get("role"),
+            database: row.get("database"),
+            name: row.get("name"),
+            value: row.get("value"),
+        })
+        .collect())
+}
+
+pub async fn fetch_roles(client: &Client) -> DbResult<Vec<RoleInfo>> {
+    let rows = client
+        .query(ROLES_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_roles",
+            source: e,
+        })?;
+    Ok(rows
+        .iter()
+        .map(|row| RoleInfo {
+            name: row.get("name"),
+            superuser: row.get("superuser"),
+            login: row.get("login"),
+            createdb: row.get("createdb"),
+            createrole: row.get("createrole"),
+            replication: row.get("replication"),
+            bypassrls: row.get("bypassrls"),
+            conn_limit: row.get("conn_limit"),
+            valid_until: row.get("valid_until"),
+            member_of: row.get("member_of"),
+        })
+        .collect())
+}
+
+pub async fn fetch_role_grants(client: &Client, role: &str) -> DbResult<Vec<RoleGrant>> {
+    let rows = client
+        .query(ROLE_GRANTS_SQL, &[&role])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_role_grants",
+            source: e,
+        })?;
+    Ok(rows
+        .iter()
+        .map(|row| RoleGrant {
+            kind: row.get("kind"),
+            object: row.get("object"),
+            privileges: row.get("privileges"),
+            grantable: row.get("grantable"),
+        })
+        .collect())
+}
+
+pub async fn fetch_wraparound_tables(client: &Client) -> DbResult<Vec<WraparoundTable>> {
+    let rows = client
+        .query(WRAPAROUND_TABLES_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_wraparound_tables",
+            source: e,
+        })?;
+    Ok(rows
+        .iter()
+        .map(|row| WraparoundTable {
+            schemaname: row.get("schemaname"),
+            relname: row.get("relname"),
+            via_toast: row.get("via_toast"),
+            xid_age: row.get("xid_age"),
+            size_bytes: row.get("size_bytes"),
+        })
+        .collect())
+}
+
 pub async fn fetch_extensions_list(client: &Client) -> DbResult<Vec<PgExtension>> {
     let rows = client
         .query(PG_EXTENSIONS_LIST_SQL, &[])
@@ -989,6 +1766,10 @@ pub async fn fetch_server_info(client: &Client) -> DbResult<ServerInfo> {
     let extensions = detect_extensions(client).await;
     let settings = fetch_pg_settings(client).await.unwrap_or_default();
     let extensions_list = fetch_extensions_list(client).await.unwrap_or_default();
+    let role_settings = fetch_role_settings(client).await.unwrap_or_default();
+    let roles = fetch_roles(client).await.unwrap_or_default();
+    // A failed probe leaves every query enabled, as before the probe existed
+    let capabilities = super::capabilities::probe(client).await.unwrap_or_default();
     let row = client
         .query_one(SERVER_INFO_SQL, &[])
         .await
@@ -1006,6 +1787,9 @@ pub async fn fetch_server_info(client: &Client) -> DbResult<ServerInfo> {
         extensions,
         settings,
         extensions_list,
+        role_settings,
+        roles,
+        capabilities,
     })
 }
 
@@ -1064,6 +1848,27 @@ pub async fn fetch_wal_stats(client: &Client, version: u32) -> DbResult<WalStats
     })
 }
 
+pub async fn fetch_io_stats(client: &Client) -> DbResult<Vec<IoStat>> {
+    let rows = client
+        .query(IO_STATS_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_io_stats",
+            source: e,
+        })?;
+    Ok(rows
+        .iter()
+        .map(|row| IoStat {
+            backend_type: row.get("backend_type"),
+            context: row.get("context"),
+            reads: row.get("reads"),
+            writes: row.get("writes"),
+            extends: row.get("extends"),
+            fsyncs: row.get("fsyncs"),
+        })
+        .collect())
+}
+
 pub async fn fetch_archiver_stats(client: &Client) -> DbResult<ArchiverStats> {
     let row = client
         .query_one(ARCHIVER_STATS_SQL, &[])
@@ -1082,6 +1887,21 @@ pub async fn fetch_archiver_stats(client: &Client) -> DbResult<ArchiverStats> {
     })
 }
 
+pub async fn fetch_wal_dir_stats(client: &Client, version: u32) -> DbResult<WalDirStats> {
+    let row = client
+        .query_one(&wal_dir_sql(version), &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_wal_dir_stats",
+            source: e,
+        })?;
+    Ok(WalDirStats {
+        segments: row.get("segments"),
+        bytes: row.get("bytes"),
+        archive_ready: row.get("archive_ready"),
+    })
+}
+
 pub async fn fetch_bgwriter_stats(client: &Client) -> DbResult<BgwriterStats> {
     let row = client
         .query_one(BGWRITER_STATS_SQL, &[])
@@ -1109,12 +1929,19 @@ pub async fn fetch_database_stats(client: &Client) -> DbResult<DatabaseStats> {
         xact_commit: row.get("xact_commit"),
         xact_rollback: row.get("xact_rollback"),
         blks_read: row.get("blks_read"),
+        stats_reset: row.get("stats_reset"),
+        server_start: row.get("server_start"),
     })
 }
 
-pub async fn fetch_active_queries(client: &Client) -> DbResult<Vec<ActiveQuery>> {
+/// `idle_cap` limits idle backends (see `IDLE_BACKENDS_CAP`); `None` fetches all.
+pub async fn fetch_active_queries(
+    client: &Client,
+    version: u32,
+    idle_cap: Option<i64>,
+) -> DbResult<Vec<ActiveQuery>> {
     let rows = client
-        .query(ACTIVE_QUERIES_SQL, &[])
+        .query(&build_active_queries_sql(version), &[&idle_cap])
         .await
         .map_err(|e| DbError::Query {
             context: "fetch_active_queries",
@@ -1133,6 +1960,35 @@ pub async fn fetch_active_queries(client: &Client) -> DbResult<Vec<ActiveQuery>>
             duration_secs: row.get("duration_secs"),
             query: row.get("query"),
             backend_type: row.get("backend_type"),
+            xact_start: row.get("xact_start"),
+            query_id: row.get("query_id"),
+            application_name: row.get("application_name"),
+            state_change: row.get("state_change"),
+        });
+    }
+    Ok(results)
+}
+
+pub async fn fetch_background_workers(client: &Client, version: u32) -> DbResult<Vec<BackgroundWorker>> {
+    let rows = client
+        .query(&background_workers_sql(version), &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_background_workers",
+            source: e,
+        })?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        results.push(BackgroundWorker {
+            pid: row.get("pid"),
+            backend_type: row.get("backend_type"),
+            datname: row.get("datname"),
+            state: row.get("state"),
+            wait_event_type: row.get("wait_event_type"),
+            wait_event: row.get("wait_event"),
+            duration_secs: row.get("duration_secs"),
+            query: row.get("query"),
+            leader_pid: row.get("leader_pid"),
         });
     }
     Ok(results)
@@ -1157,18 +2013,43 @@ pub async fn fetch_wait_events(client: &Client) -> DbResult<Vec<WaitEventCount>>
     Ok(results)
 }
 
-pub async fn fetch_blocking_info(client: &Client) -> DbResult<Vec<BlockingInfo>> {
+/// Wait samples `pg_wait_sampling` took after `since`, and the time of the
+/// newest one to pass as `since` next time.
+pub async fn fetch_wait_history(
+    client: &Client,
+    since: Option<DateTime<Utc>>,
+) -> DbResult<(Vec<WaitEventCount>, Option<DateTime<Utc>>)> {
     let rows = client
-        .query(BLOCKING_SQL, &[])
+        .query(WAIT_HISTORY_SQL, &[&since])
         .await
         .map_err(|e| DbError::Query {
-            context: "fetch_blocking_info",
+            context: "fetch_wait_history",
             source: e,
         })?;
-    let mut results = Vec::with_capacity(rows.len());
-    for row in rows {
-        results.push(BlockingInfo {
-            blocked_pid: row.get("blocked_pid"),
+    let latest = rows.iter().filter_map(|row| row.get::<_, Option<DateTime<Utc>>>("latest")).max();
+    let counts = rows
+        .iter()
+        .map(|row| WaitEventCount {
+            wait_event_type: row.get("wait_event_type"),
+            wait_event: row.get("wait_event"),
+            count: row.get("count"),
+        })
+        .collect();
+    Ok((counts, latest))
+}
+
+pub async fn fetch_blocking_info(client: &Client) -> DbResult<Vec<BlockingInfo>> {
+    let rows = client
+        .query(BLOCKING_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_blocking_info",
+            source: e,
+        })?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        results.push(BlockingInfo {
+            blocked_pid: row.get("blocked_pid"),
             blocked_user: row.get("blocked_user"),
             blocked_query: row.get("blocked_query"),
             blocked_duration_secs: row.get("blocked_duration_secs"),
@@ -1181,6 +2062,58 @@ pub async fn fetch_blocking_info(client: &Client) -> DbResult<Vec<BlockingInfo>>
     Ok(results)
 }
 
+pub async fn fetch_locks(client: &Client) -> DbResult<Vec<LockInfo>> {
+    let rows = client
+        .query(LOCKS_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_locks",
+            source: e,
+        })?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        results.push(LockInfo {
+            pid: row.get("pid"),
+            locktype: row.get("locktype"),
+            mode: row.get("mode"),
+            granted: row.get("granted"),
+            relation: row.get("relation"),
+            relation_oid: row.get("relation_oid"),
+            usename: row.get("usename"),
+            query: row.get("query"),
+            state: row.get("state"),
+            duration_secs: row.get("duration_secs"),
+            blocking_pids: row.get("blocking_pids"),
+        });
+    }
+    Ok(results)
+}
+
+pub async fn fetch_connection_groups(client: &Client) -> DbResult<Vec<ConnectionGroup>> {
+    let rows = client
+        .query(CONNECTION_GROUPS_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_connection_groups",
+            source: e,
+        })?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        results.push(ConnectionGroup {
+            usename: row.get("usename"),
+            datname: row.get("datname"),
+            application_name: row.get("application_name"),
+            total: row.get("total"),
+            active: row.get("active"),
+            idle: row.get("idle"),
+            idle_in_transaction: row.get("idle_in_transaction"),
+            waiting: row.get("waiting"),
+            longest_secs: row.get("longest_secs"),
+        });
+    }
+    Ok(results)
+}
+
 pub async fn fetch_buffer_cache(client: &Client) -> DbResult<BufferCacheStats> {
     let row = client
         .query_one(BUFFER_CACHE_SQL, &[])
@@ -1212,6 +2145,8 @@ pub async fn fetch_activity_summary(client: &Client) -> DbResult<ActivitySummary
         waiting_count: row.get("waiting_count"),
         oldest_xact_secs: row.get("oldest_xact_secs"),
         autovacuum_count: row.get("autovacuum_count"),
+        aborted_txn_count: row.get("aborted_txn_count"),
+        idle_count: row.get("idle_count"),
     })
 }
 
@@ -1330,7 +2265,7 @@ pub async fn fetch_subscriptions(client: &Client, version: u32) -> DbResult<Vec<
     if version < 10 {
         return Ok(vec![]);
     }
-    let Ok(rows) = client.query(SUBSCRIPTIONS_SQL, &[]).await else {
+    let Ok(rows) = client.query(&subscriptions_sql(version), &[]).await else {
         return Ok(vec![]); // Graceful fallback if query fails
     };
     let mut results = Vec::with_capacity(rows.len());
@@ -1345,6 +2280,11 @@ pub async fn fetch_subscriptions(client: &Client, version: u32) -> DbResult<Vec<
             latest_end_lsn: row.get("latest_end_lsn"),
             latest_end_time: row.get("latest_end_time"),
             enabled: row.get("enabled"),
+            apply_lag_secs: row.get("apply_lag_secs"),
+            apply_lag_bytes: row.get("apply_lag_bytes"),
+            apply_error_count: row.get("apply_error_count"),
+            sync_error_count: row.get("sync_error_count"),
+            conflict_count: row.get("conflict_count"),
         });
     }
     Ok(results)
@@ -1364,6 +2304,7 @@ pub async fn fetch_vacuum_progress(client: &Client, _version: u32) -> DbResult<V
             pid: row.get("pid"),
             datname: row.get("datname"),
             table_name: row.get("table_name"),
+            relid: row.get("relid"),
             phase: row.get("phase"),
             heap_blks_total: row.get("heap_blks_total"),
             heap_blks_vacuumed: row.get("heap_blks_vacuumed"),
@@ -1374,6 +2315,51 @@ pub async fn fetch_vacuum_progress(client: &Client, _version: u32) -> DbResult<V
     Ok(results)
 }
 
+pub async fn fetch_operations(client: &Client, version: u32) -> DbResult<Vec<OperationProgress>> {
+    let Some(sql) = operations_sql(version) else {
+        return Ok(Vec::new());
+    };
+    let rows = client
+        .query(sql.as_str(), &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_operations",
+            source: e,
+        })?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let kind = match row.get::<_, &str>("kind") {
+            "create_index" => OperationKind::CreateIndex,
+            "cluster" => OperationKind::Cluster,
+            "analyze" => OperationKind::Analyze,
+            _ => OperationKind::BaseBackup,
+        };
+        results.push(OperationProgress {
+            pid: row.get("pid"),
+            kind,
+            command: row.get("command"),
+            datname: row.get("datname"),
+            target: row.get("target"),
+            relid: row.get("relid"),
+            phase: row.get("phase"),
+            work_done: row.get::<_, Option<i64>>("work_done").unwrap_or(0),
+            work_total: row.get::<_, Option<i64>>("work_total").unwrap_or(0),
+            elapsed_secs: row.get("elapsed_secs"),
+        });
+    }
+    Ok(results)
+}
+
+/// The progress view row of backend `pid`, if it is in one. Vacuums carry
+/// no elapsed time here; the snapshot takes it from the backend's entry.
+pub async fn fetch_progress_of(client: &Client, version: u32, pid: i32) -> DbResult<Option<OperationProgress>> {
+    let vacuum = fetch_vacuum_progress(client, version).await?;
+    if let Some(v) = vacuum.iter().find(|v| v.pid == pid) {
+        return Ok(Some(OperationProgress::from(v)));
+    }
+    Ok(fetch_operations(client, version).await?.into_iter().find(|op| op.pid == pid))
+}
+
 pub async fn fetch_wraparound(client: &Client) -> DbResult<Vec<WraparoundInfo>> {
     let rows = client
         .query(WRAPAROUND_SQL, &[])
@@ -1413,6 +2399,7 @@ pub async fn fetch_indexes(client: &Client) -> DbResult<Vec<IndexInfo>> {
             idx_tup_read: row.get("idx_tup_read"),
             idx_tup_fetch: row.get("idx_tup_fetch"),
             index_definition: row.get("index_definition"),
+            is_valid: row.get("is_valid"),
             bloat_bytes: None,
             bloat_pct: None,
             bloat_source: None,
@@ -1422,6 +2409,163 @@ pub async fn fetch_indexes(client: &Client) -> DbResult<Vec<IndexInfo>> {
 }
 
 /// Returns (statements, `error_message`)
+/// Read the counters the latency sampler diffs between ticks.
+pub async fn fetch_statement_samples(
+    client: &Client,
+    extensions: &DetectedExtensions,
+    pg_major_version: u32,
+    queryids: &[i64],
+) -> DbResult<Vec<StatementSample>> {
+    let ext_version = extensions.pg_stat_statements_version.as_deref();
+    let mut columns_to_try = select_stat_statements_columns(pg_major_version, ext_version)
+        .into_iter()
+        .peekable();
+    while let Some(cols) = columns_to_try.next() {
+        match client.query(&build_statement_samples_sql(cols), &[&queryids]).await {
+            Ok(rows) => {
+                return Ok(rows
+                    .iter()
+                    .map(|row| StatementSample {
+                        queryid: row.get("queryid"),
+                        calls: row.get("calls"),
+                        total_exec_time: row.get("total_exec_time"),
+                    })
+                    .collect());
+            }
+            // Older column names: try the next variant
+            Err(e) if columns_to_try.peek().is_some() && e.to_string().contains("does not exist") => {}
+            Err(e) => {
+                return Err(DbError::Query {
+                    context: "fetch_statement_samples",
+                    source: e,
+                });
+            }
+        }
+    }
+    // No column variant to read
+    Ok(Vec::new())
+}
+
+fn stat_statement_from_row(row: &tokio_postgres::Row) -> StatStatement {
+    StatStatement {
+        queryid: row.get("queryid"),
+        query: row.get("query"),
+        calls: row.get("calls"),
+        total_exec_time: row.get("total_exec_time"),
+        min_exec_time: row.get("min_exec_time"),
+        mean_exec_time: row.get("mean_exec_time"),
+        max_exec_time: row.get("max_exec_time"),
+        stddev_exec_time: row.get("stddev_exec_time"),
+        rows: row.get("rows"),
+        shared_blks_hit: row.get("shared_blks_hit"),
+        shared_blks_read: row.get("shared_blks_read"),
+        shared_blks_dirtied: row.get("shared_blks_dirtied"),
+        shared_blks_written: row.get("shared_blks_written"),
+        local_blks_hit: row.get("local_blks_hit"),
+        local_blks_read: row.get("local_blks_read"),
+        local_blks_dirtied: row.get("local_blks_dirtied"),
+        local_blks_written: row.get("local_blks_written"),
+        temp_blks_read: row.get("temp_blks_read"),
+        temp_blks_written: row.get("temp_blks_written"),
+        blk_read_time: row.get("blk_read_time"),
+        blk_write_time: row.get("blk_write_time"),
+        hit_ratio: row.get("hit_ratio"),
+        windowed: false,
+    }
+}
+
+/// Statements from `pg_stat_monitor` when it is installed and readable,
+/// with its histograms, otherwise from `pg_stat_statements`.
+pub async fn fetch_statements(
+    client: &Client,
+    extensions: &DetectedExtensions,
+    pg_major_version: u32,
+) -> (Vec<StatStatement>, Option<String>, Option<StatementMonitor>) {
+    if extensions.pg_stat_monitor {
+        match fetch_stat_monitor(client, pg_major_version).await {
+            Ok((statements, monitor)) => return (statements, None, Some(monitor)),
+            Err(e) if !extensions.pg_stat_statements => {
+                return (vec![], Some(format!("{e} (pg_stat_monitor 2.0+ is needed)")), None);
+            }
+            // Fall back to pg_stat_statements
+            Err(_) => {}
+        }
+    }
+    let (statements, error) = fetch_stat_statements(client, extensions, pg_major_version).await;
+    (statements, error, None)
+}
+
+async fn fetch_stat_monitor(
+    client: &Client,
+    pg_major_version: u32,
+) -> DbResult<(Vec<StatStatement>, StatementMonitor)> {
+    let query_err = |e| DbError::Query {
+        context: "fetch_stat_monitor",
+        source: e,
+    };
+    let columns_to_try = if pg_major_version >= 17 {
+        vec![STAT_STATEMENTS_V17, STAT_STATEMENTS_V13]
+    } else {
+        vec![STAT_STATEMENTS_V13]
+    };
+    let last = columns_to_try.len() - 1;
+    let mut rows = Vec::new();
+    for (i, cols) in columns_to_try.into_iter().enumerate() {
+        match client.query(&build_stat_monitor_sql(cols), &[]).await {
+            Ok(found) => {
+                rows = found;
+                break;
+            }
+            Err(e) if i < last && e.to_string().contains("does not exist") => {}
+            Err(e) => return Err(query_err(e)),
+        }
+    }
+    let statements: Vec<StatStatement> = rows
+        .iter()
+        .map(|row| StatStatement {
+            windowed: true,
+            ..stat_statement_from_row(row)
+        })
+        .collect();
+    let queryids: Vec<i64> = statements.iter().map(|s| s.queryid).collect();
+    let mut monitored: Vec<MonitorStatement> = rows
+        .iter()
+        .map(|row| MonitorStatement {
+            queryid: row.get("queryid"),
+            errors: row.get("errors"),
+            ..MonitorStatement::default()
+        })
+        .collect();
+
+    let ranges_row = client.query_one("SELECT range() AS ranges", &[]).await.map_err(query_err)?;
+    let ranges: Vec<String> = ranges_row.get("ranges");
+    let ranges = ranges.iter().filter_map(|r| parse_monitor_range(r)).collect();
+
+    let histogram = client.query(STAT_MONITOR_HISTOGRAM_SQL, &[&queryids]).await.map_err(query_err)?;
+    let history = client.query(STAT_MONITOR_HISTORY_SQL, &[&queryids]).await.map_err(query_err)?;
+    let mut by_id: HashMap<i64, &mut MonitorStatement> = monitored.iter_mut().map(|m| (m.queryid, m)).collect();
+    for row in &histogram {
+        let Some(m) = by_id.get_mut(&row.get::<_, i64>("queryid")) else {
+            continue;
+        };
+        let bucket = usize::try_from(row.get::<_, i32>("bucket") - 1).unwrap_or(0);
+        if m.resp_calls.len() <= bucket {
+            m.resp_calls.resize(bucket + 1, 0);
+        }
+        m.resp_calls[bucket] = row.get("calls");
+    }
+    for row in &history {
+        if let Some(m) = by_id.get_mut(&row.get::<_, i64>("queryid")) {
+            m.history.push(MonitorBucket {
+                start: row.get("bucket_start_time"),
+                calls: row.get("calls"),
+                total_exec_time: row.get("total_exec_time"),
+            });
+        }
+    }
+    Ok((statements, StatementMonitor { ranges, statements: monitored }))
+}
+
 pub async fn fetch_stat_statements(
     client: &Client,
     extensions: &DetectedExtensions,
@@ -1478,36 +2622,7 @@ pub async fn fetch_stat_statements(
     for cols in columns_to_try {
         let sql = build_stat_statements_sql(cols);
         match client.query(&sql, &[]).await {
-            Ok(rows) => {
-                let mut results = Vec::with_capacity(rows.len());
-                for row in rows {
-                    results.push(StatStatement {
-                        queryid: row.get("queryid"),
-                        query: row.get("query"),
-                        calls: row.get("calls"),
-                        total_exec_time: row.get("total_exec_time"),
-                        min_exec_time: row.get("min_exec_time"),
-                        mean_exec_time: row.get("mean_exec_time"),
-                        max_exec_time: row.get("max_exec_time"),
-                        stddev_exec_time: row.get("stddev_exec_time"),
-                        rows: row.get("rows"),
-                        shared_blks_hit: row.get("shared_blks_hit"),
-                        shared_blks_read: row.get("shared_blks_read"),
-                        shared_blks_dirtied: row.get("shared_blks_dirtied"),
-                        shared_blks_written: row.get("shared_blks_written"),
-                        local_blks_hit: row.get("local_blks_hit"),
-                        local_blks_read: row.get("local_blks_read"),
-                        local_blks_dirtied: row.get("local_blks_dirtied"),
-                        local_blks_written: row.get("local_blks_written"),
-                        temp_blks_read: row.get("temp_blks_read"),
-                        temp_blks_written: row.get("temp_blks_written"),
-                        blk_read_time: row.get("blk_read_time"),
-                        blk_write_time: row.get("blk_write_time"),
-                        hit_ratio: row.get("hit_ratio"),
-                    });
-                }
-                return (results, None);
-            }
+            Ok(rows) => return (rows.iter().map(stat_statement_from_row).collect(), None),
             Err(e) => {
                 // If it's a column error, try next query variant
                 let msg = e.to_string();
@@ -1560,6 +2675,36 @@ pub struct IndexBloat {
     pub source: BloatSource,
 }
 
+/// A table measured with [`measure_bloat`] as single-entry estimate maps,
+/// so it goes through the same path as a bloat refresh.
+pub fn measured_bloat_estimates(
+    schema: &str,
+    table: &str,
+    measured: &MeasuredBloat,
+) -> (HashMap<String, TableBloat>, HashMap<String, IndexBloat>) {
+    let tables = HashMap::from([(
+        format!("{schema}.{table}"),
+        TableBloat {
+            bloat_bytes: measured.bloat_bytes(),
+            bloat_pct: measured.bloat_pct(),
+            source: BloatSource::PgstattupleExact,
+        },
+    )]);
+    let indexes = measured
+        .indexes
+        .iter()
+        .map(|index| {
+            let bloat = IndexBloat {
+                bloat_bytes: index.bloat_bytes(),
+                bloat_pct: index.bloat_pct(),
+                source: BloatSource::PgstattupleExact,
+            };
+            (format!("{schema}.{}", index.index_name), bloat)
+        })
+        .collect();
+    (tables, indexes)
+}
+
 /// Try pgstattuple-based table bloat query
 async fn try_pgstattuple_table_bloat(client: &Client) -> Option<HashMap<String, TableBloat>> {
     let rows = client.query(TABLE_BLOAT_PGSTATTUPLE_SQL, &[]).await.ok()?;
@@ -1744,17 +2889,281 @@ pub async fn fetch_index_bloat(
     naive_index_bloat(client).await
 }
 
-pub async fn reset_stat_statements(client: &Client) -> DbResult<()> {
+/// Drop a replication slot. Fails if a consumer is attached to it.
+pub async fn drop_replication_slot(client: &Client, slot_name: &str) -> DbResult<()> {
     client
-        .execute("SELECT pg_stat_statements_reset()", &[])
+        .execute("SELECT pg_drop_replication_slot($1)", &[&slot_name])
         .await
         .map_err(|e| DbError::Query {
+            context: "drop_replication_slot",
+            source: e,
+        })?;
+    Ok(())
+}
+
+/// Reset `pg_stat_statements` and, when installed, `pg_stat_monitor`.
+pub async fn reset_stat_statements(client: &Client, extensions: &DetectedExtensions) -> DbResult<()> {
+    let resets = [
+        (extensions.pg_stat_statements, "SELECT pg_stat_statements_reset()"),
+        (extensions.pg_stat_monitor, "SELECT pg_stat_monitor_reset()"),
+    ];
+    for (_, sql) in resets.into_iter().filter(|(installed, _)| *installed) {
+        client.execute(sql, &[]).await.map_err(|e| DbError::Query {
             context: "reset_stat_statements",
             source: e,
         })?;
+    }
     Ok(())
 }
 
+/// End of the current server log file, `max_bytes` at most, or `None` when
+/// the server isn't writing one (`logging_collector` off). Needs superuser
+/// or EXECUTE on the three log functions. Read as bytes since the cut may
+/// fall inside a multi-byte character.
+pub async fn fetch_log_tail(client: &Client, max_bytes: i64) -> DbResult<Option<String>> {
+    let row = client
+        .query_one(LOG_TAIL_SQL, &[&max_bytes])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_log_tail",
+            source: e,
+        })?;
+    let bytes: Option<Vec<u8>> = row.get(0);
+    Ok(bytes.map(|b| String::from_utf8_lossy(&b).into_owned()))
+}
+
+/// Bytes of the current server log from where the last read stopped
+/// (`file` and `offset`), as `(file, start, bytes)`; `None` when the server
+/// isn't writing a log file. Same privileges as [`fetch_log_tail`].
+pub async fn fetch_log_from(
+    client: &Client,
+    file: Option<&str>,
+    offset: i64,
+    max_bytes: i64,
+) -> DbResult<Option<(String, i64, Vec<u8>)>> {
+    let row = client
+        .query_opt(LOG_READ_SQL, &[&file, &offset, &max_bytes])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_log_from",
+            source: e,
+        })?;
+    Ok(row.and_then(|row| {
+        let file: Option<String> = row.get(0);
+        let start: Option<i64> = row.get(1);
+        let bytes: Option<Vec<u8>> = row.get(2);
+        Some((file?, start?, bytes?))
+    }))
+}
+
+/// Contents of shared buffers from `pg_buffercache`: the usage count
+/// spread and the relations holding the most buffers.
+pub async fn fetch_buffer_contents(client: &Client) -> DbResult<BufferCacheReport> {
+    let rows = client
+        .query(BUFFER_USAGE_SQL, &[])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_buffer_contents",
+            source: e,
+        })?;
+    let mut report = BufferCacheReport {
+        read_at: Utc::now(),
+        block_size: 8192,
+        total_buffers: 0,
+        unused_buffers: 0,
+        dirty_buffers: 0,
+        usage_counts: [0; 6],
+        relations: Vec::new(),
+    };
+    for row in &rows {
+        let buffers: i64 = row.get("buffers");
+        report.block_size = row.get("block_size");
+        report.total_buffers += buffers;
+        report.dirty_buffers += row.get::<_, i64>("dirty");
+        match row.get::<_, Option<i32>>("usagecount") {
+            None => report.unused_buffers += buffers,
+            Some(count) => report.usage_counts[count.clamp(0, 5) as usize] += buffers,
+        }
+    }
+
+    let rows = client
+        .query(BUFFER_RELATIONS_SQL, &[&BUFFER_CACHE_TOP])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "fetch_buffer_contents relations",
+            source: e,
+        })?;
+    report.relations = rows
+        .iter()
+        .map(|row| BufferCacheRelation {
+            relid: row.get("relid"),
+            schemaname: row.get("schemaname"),
+            relname: row.get("relname"),
+            relkind: row.get("relkind"),
+            buffers: row.get("buffers"),
+            dirty: row.get("dirty"),
+            avg_usage: row.get::<_, Option<f64>>("avg_usage").unwrap_or(0.0),
+            cached_pct: row.get("cached_pct"),
+        })
+        .collect();
+    Ok(report)
+}
+
+/// Exact bloat of `schema.table` and its btree indexes from `pgstattuple()`
+/// and `pgstatindex()`, or None if the table is gone. Both read the whole
+/// relation, unlike the `pgstattuple_approx` sampling of the bloat refresh.
+pub async fn measure_bloat(
+    client: &Client,
+    schema: &str,
+    table: &str,
+) -> DbResult<Option<MeasuredBloat>> {
+    let Some(row) = client
+        .query_opt(TABLE_OID_SQL, &[&schema, &table])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "measure_bloat",
+            source: e,
+        })?
+    else {
+        return Ok(None);
+    };
+    let oid: u32 = row.get(0);
+
+    let row = client
+        .query_one(TABLE_PGSTATTUPLE_EXACT_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "measure_bloat pgstattuple",
+            source: e,
+        })?;
+    let mut measured = MeasuredBloat {
+        table_len: row.get("table_len"),
+        tuple_percent: row.get("tuple_percent"),
+        dead_tuple_percent: row.get("dead_tuple_percent"),
+        free_space: row.get("free_space"),
+        free_percent: row.get("free_percent"),
+        indexes: Vec::new(),
+    };
+
+    let rows = client
+        .query(TABLE_PGSTATINDEX_EXACT_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "measure_bloat pgstatindex",
+            source: e,
+        })?;
+    measured.indexes = rows
+        .iter()
+        .map(|row| MeasuredIndex {
+            index_name: row.get("index_name"),
+            index_size: row.get("index_size"),
+            avg_leaf_density: row.get("avg_leaf_density"),
+            leaf_fragmentation: row.get("leaf_fragmentation"),
+        })
+        .collect();
+    Ok(Some(measured))
+}
+
+/// Columns, indexes with their usage, foreign keys both ways and triggers
+/// of `schema.table`, or None if it is gone. Read from the catalogs, so it
+/// works for any table the role can see.
+pub async fn describe_table(
+    client: &Client,
+    schema: &str,
+    table: &str,
+) -> DbResult<Option<TableSchema>> {
+    let Some(row) = client
+        .query_opt(TABLE_OID_SQL, &[&schema, &table])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "describe_table",
+            source: e,
+        })?
+    else {
+        return Ok(None);
+    };
+    let oid: u32 = row.get(0);
+
+    let rows = client
+        .query(TABLE_COLUMNS_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "describe_table columns",
+            source: e,
+        })?;
+    let columns = rows
+        .iter()
+        .map(|row| ColumnDef {
+            name: row.get("name"),
+            data_type: row.get("data_type"),
+            not_null: row.get("not_null"),
+            default: row.get("default_expr"),
+        })
+        .collect();
+
+    let rows = client
+        .query(TABLE_INDEXES_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "describe_table indexes",
+            source: e,
+        })?;
+    let indexes = rows
+        .iter()
+        .map(|row| IndexDef {
+            name: row.get("name"),
+            definition: row.get("definition"),
+            is_primary: row.get("is_primary"),
+            is_unique: row.get("is_unique"),
+            is_valid: row.get("is_valid"),
+            size_bytes: row.get("size_bytes"),
+            idx_scan: row.get("idx_scan"),
+            idx_tup_read: row.get("idx_tup_read"),
+            idx_tup_fetch: row.get("idx_tup_fetch"),
+        })
+        .collect();
+
+    let rows = client
+        .query(TABLE_FOREIGN_KEYS_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "describe_table foreign keys",
+            source: e,
+        })?;
+    let foreign_keys = rows
+        .iter()
+        .map(|row| ForeignKeyDef {
+            name: row.get("name"),
+            other_table: row.get("other_table"),
+            definition: row.get("definition"),
+            incoming: row.get("incoming"),
+        })
+        .collect();
+
+    let rows = client
+        .query(TABLE_TRIGGERS_SQL, &[&oid])
+        .await
+        .map_err(|e| DbError::Query {
+            context: "describe_table triggers",
+            source: e,
+        })?;
+    let triggers = rows
+        .iter()
+        .map(|row| TriggerDef {
+            name: row.get("name"),
+            definition: row.get("definition"),
+            enabled: row.get("enabled"),
+        })
+        .collect();
+
+    Ok(Some(TableSchema {
+        columns,
+        indexes,
+        foreign_keys,
+        triggers,
+    }))
+}
+
 pub async fn cancel_backend(client: &Client, pid: i32) -> DbResult<bool> {
     let row = client
         .query_one("SELECT pg_cancel_backend($1)", &[&pid])
@@ -1797,29 +3206,60 @@ pub async fn terminate_backends(client: &Client, pids: &[i32]) -> Vec<(i32, bool
     results
 }
 
+/// Read everything a refresh shows. Without `relation_stats` the table and
+/// index stats are left empty, for the caller to carry over an earlier read.
 pub async fn fetch_snapshot(
     client: &Client,
     extensions: &DetectedExtensions,
+    capabilities: &Capabilities,
     version: u32,
+    all_backends: bool,
+    relation_stats: bool,
 ) -> Result<PgSnapshot> {
+    let idle_cap = (!all_backends).then_some(IDLE_BACKENDS_CAP);
     let ext = extensions.clone();
-    let (active, waits, blocks, cache, summary, tables, repl, repl_slots, subs, vacuum, wrap, indexes, ss, db_size, chkpt, wal, archiver, bgwriter, db_stats) =
+    let (active, client_pids, waits, blocks, locks, groups, cache, summary, tables, repl, repl_slots, subs, vacuum, operations, workers, wrap, indexes, ss, db_size, chkpt, wal, archiver, wal_dir, io_stats, bgwriter, db_stats) =
         tokio::try_join!(
-            async { fetch_active_queries(client).await.map_err(color_eyre::Report::from) },
+            async { fetch_active_queries(client, version, idle_cap).await.map_err(color_eyre::Report::from) },
+            // Uncapped, the list above has every backend already
+            async {
+                if idle_cap.is_some() {
+                    Ok::<_, color_eyre::Report>(fetch_client_pids(client).await.unwrap_or_default())
+                } else {
+                    Ok(Vec::new())
+                }
+            },
             async { fetch_wait_events(client).await.map_err(color_eyre::Report::from) },
             async { fetch_blocking_info(client).await.map_err(color_eyre::Report::from) },
+            async { fetch_locks(client).await.map_err(color_eyre::Report::from) },
+            async { fetch_connection_groups(client).await.map_err(color_eyre::Report::from) },
             async { fetch_buffer_cache(client).await.map_err(color_eyre::Report::from) },
             async { fetch_activity_summary(client).await.map_err(color_eyre::Report::from) },
             // Table stats can fail if tables are dropped during query - return empty on error
-            async { Ok::<_, color_eyre::Report>(fetch_table_stats(client).await.unwrap_or_default()) },
+            async {
+                if relation_stats {
+                    Ok::<_, color_eyre::Report>(fetch_table_stats(client).await.unwrap_or_default())
+                } else {
+                    Ok(Vec::new())
+                }
+            },
             async { fetch_replication(client, version).await.map_err(color_eyre::Report::from) },
             async { fetch_replication_slots(client, version).await.map_err(color_eyre::Report::from) },
             async { fetch_subscriptions(client, version).await.map_err(color_eyre::Report::from) },
             async { fetch_vacuum_progress(client, version).await.map_err(color_eyre::Report::from) },
+            // Progress views came in PG12/13; losing them is not worth losing the snapshot
+            async { Ok::<_, color_eyre::Report>(fetch_operations(client, version).await.unwrap_or_default()) },
+            async { Ok::<_, color_eyre::Report>(fetch_background_workers(client, version).await.unwrap_or_default()) },
             async { fetch_wraparound(client).await.map_err(color_eyre::Report::from) },
             // Index stats can fail if tables are dropped during query - return empty on error
-            async { Ok::<_, color_eyre::Report>(fetch_indexes(client).await.unwrap_or_default()) },
-            async { Ok(fetch_stat_statements(client, &ext, version).await) },
+            async {
+                if relation_stats {
+                    Ok::<_, color_eyre::Report>(fetch_indexes(client).await.unwrap_or_default())
+                } else {
+                    Ok(Vec::new())
+                }
+            },
+            async { Ok(fetch_statements(client, &ext, version).await) },
             async { fetch_db_size(client).await.map_err(color_eyre::Report::from) },
             async { Ok(fetch_checkpoint_stats(client, version).await.ok()) },
             async {
@@ -1831,15 +3271,37 @@ pub async fn fetch_snapshot(
                 }
             },
             async { Ok(fetch_archiver_stats(client).await.ok()) },
+            // Without pg_monitor the directory listing is refused
+            async {
+                if capabilities.ls_waldir {
+                    Ok(fetch_wal_dir_stats(client, version).await.ok())
+                } else {
+                    Ok(None)
+                }
+            },
+            // pg_stat_io only available in PG16+
+            async {
+                if version >= 16 {
+                    Ok(fetch_io_stats(client).await.unwrap_or_default())
+                } else {
+                    Ok(Vec::new())
+                }
+            },
             async { Ok(fetch_bgwriter_stats(client).await.ok()) },
             async { Ok(fetch_database_stats(client).await.ok()) },
         )?;
-    let (stat_statements, stat_statements_error) = ss;
+    let (stat_statements, stat_statements_error, statement_monitor) = ss;
+    let capped_pids = client_pids
+        .into_iter()
+        .filter(|pid| !active.iter().any(|q| q.pid == *pid))
+        .collect();
     Ok(PgSnapshot {
         timestamp: chrono::Utc::now(),
         active_queries: active,
         wait_events: waits,
         blocking_info: blocks,
+        locks,
+        connection_groups: groups,
         buffer_cache: cache,
         summary,
         table_stats: tables,
@@ -1847,6 +3309,9 @@ pub async fn fetch_snapshot(
         replication_slots: repl_slots,
         subscriptions: subs,
         vacuum_progress: vacuum,
+        operations,
+        background_workers: workers,
+        capped_pids,
         wraparound: wrap,
         indexes,
         stat_statements,
@@ -1856,7 +3321,148 @@ pub async fn fetch_snapshot(
         checkpoint_stats: chkpt,
         wal_stats: wal,
         archiver_stats: archiver,
+        wal_dir,
+        io_stats,
+        statement_monitor,
         bgwriter_stats: bgwriter,
         db_stats,
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+    })
+}
+
+/// `EXPLAIN` statement for `query`, and whether it needs a generic plan.
+/// Statement texts from `pg_stat_statements` are normalized to `$n`
+/// parameters, which only `GENERIC_PLAN` (PG16+) can plan. `verbose`
+/// adds schema names to the plan.
+fn build_explain_sql(query: &str, pg_major_version: u32, verbose: bool) -> Result<(String, bool), String> {
+    let query = query.trim().trim_end_matches(';').trim_end();
+    if query.is_empty() {
+        return Err("No query text to explain".into());
+    }
+    let verbose = if verbose { "VERBOSE, " } else { "" };
+    let bytes = query.as_bytes();
+    let has_params = bytes
+        .windows(2)
+        .any(|w| w[0] == b'$' && w[1].is_ascii_digit());
+    if !has_params {
+        return Ok((format!("EXPLAIN ({verbose}FORMAT JSON) {query}"), false));
+    }
+    if pg_major_version < 16 {
+        return Err(
+            "Statement has $n parameters; planning it needs EXPLAIN (GENERIC_PLAN), PostgreSQL 16+"
+                .into(),
+        );
+    }
+    Ok((format!("EXPLAIN (GENERIC_PLAN, {verbose}FORMAT JSON) {query}"), true))
+}
+
+/// Raw text of a `json` column. Saves pulling in serde support for
+/// tokio-postgres just to read one document.
+struct JsonText(String);
+
+impl<'a> tokio_postgres::types::FromSql<'a> for JsonText {
+    fn from_sql(
+        _ty: &tokio_postgres::types::Type,
+        raw: &'a [u8],
+    ) -> std::result::Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Self(std::str::from_utf8(raw)?.to_string()))
+    }
+
+    fn accepts(ty: &tokio_postgres::types::Type) -> bool {
+        *ty == tokio_postgres::types::Type::JSON
+    }
+}
+
+/// Plan `query` without running it. The server's message is kept on
+/// failure since it is usually what the user needs (syntax error,
+/// truncated query text, missing permissions).
+pub async fn explain(
+    client: &Client,
+    query: &str,
+    pg_major_version: u32,
+) -> std::result::Result<QueryPlan, String> {
+    let (sql, generic) = build_explain_sql(query, pg_major_version, false)?;
+    run_explain(client, &sql, query, generic).await
+}
+
+async fn run_explain(
+    client: &Client,
+    sql: &str,
+    query: &str,
+    generic: bool,
+) -> std::result::Result<QueryPlan, String> {
+    let row = client.query_one(sql, &[]).await.map_err(|e| server_message(&e))?;
+    let JsonText(json) = row.try_get(0).map_err(|e| e.to_string())?;
+    QueryPlan::parse(query, &json, generic).ok_or_else(|| "Could not read the plan".to_string())
+}
+
+/// PID of the backend serving this connection.
+pub async fn backend_pid(client: &Client) -> std::result::Result<i32, String> {
+    let row = client
+        .query_one("SELECT pg_backend_pid()", &[])
+        .await
+        .map_err(|e| server_message(&e))?;
+    Ok(row.get(0))
+}
+
+/// Run maintenance SQL to completion. It may hold several statements, and
+/// `CONCURRENTLY` ones must not be wrapped in a transaction.
+pub async fn run_maintenance(client: &Client, sql: &str) -> std::result::Result<(), String> {
+    client.batch_execute(sql).await.map_err(|e| server_message(&e))
+}
+
+fn server_message(e: &tokio_postgres::Error) -> String {
+    e.as_db_error()
+        .map_or_else(|| e.to_string(), |db| db.message().to_string())
+}
+
+/// Plan `query`, then plan it again with hypopg pretending the indexes
+/// its filtered sequential scans could use exist. Hypothetical indexes
+/// live in the session only; they are dropped again before returning.
+pub async fn advise_indexes(
+    client: &Client,
+    query: &str,
+    pg_major_version: u32,
+) -> std::result::Result<IndexAdvice, String> {
+    let (sql, generic) = build_explain_sql(query, pg_major_version, true)?;
+    let before = run_explain(client, &sql, query, generic).await?;
+    let mut created = Vec::new();
+    for candidate in before.index_candidates() {
+        let definition = candidate.definition();
+        // A filter word taken for a column fails here; skip that one
+        if let Ok(row) = client
+            .query_one("SELECT indexname FROM hypopg_create_index($1)", &[&definition])
+            .await
+        {
+            created.push((definition, row.get::<_, String>(0)));
+        }
+    }
+    let after = if created.is_empty() {
+        Ok(before.clone())
+    } else {
+        run_explain(client, &sql, query, generic).await
+    };
+    client
+        .batch_execute("SELECT hypopg_reset()")
+        .await
+        .map_err(|e| server_message(&e))?;
+    let after = after?;
+    let used = after.index_names();
+    Ok(IndexAdvice {
+        query: query.to_string(),
+        generic,
+        cost_before: before.root.total_cost,
+        cost_after: after.root.total_cost,
+        suggestions: created
+            .into_iter()
+            .map(|(definition, name)| IndexSuggestion {
+                used: used.contains(&name.as_str()),
+                definition,
+            })
+            .collect(),
     })
 }
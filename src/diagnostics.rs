@@ -0,0 +1,515 @@
+//! Diagnostic bundle for bug reports and vendor tickets (`--diagnostics`).
+//!
+//! A gzipped tarball holding the config with hook SQL, ignored table names
+//! and profile connection details redacted, the server's version and
+//! settings, how long a few snapshots took to collect, the tail of the
+//! app's error log and, when asked for, the end of the newest recording
+//! with its query texts and connection details redacted.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use tokio_postgres::Client;
+
+use crate::config::AppConfig;
+use crate::db::models::ServerInfo;
+use crate::db::queries::{fetch_server_info, fetch_snapshot};
+use crate::error_log;
+use crate::recorder::{session_segments, Recorder};
+
+/// Snapshots collected to time the collector.
+pub const COLLECTOR_SAMPLES: usize = 5;
+/// Lines kept from the end of the error log.
+pub const ERROR_LOG_LINES: usize = 200;
+/// Snapshot lines kept from the end of the newest recording.
+pub const RECORDING_TAIL_LINES: usize = 100;
+
+/// Stands in for anything the bundle leaves out.
+const REDACTED: &str = "<redacted>";
+
+/// Settings whose values can carry credentials or hostnames.
+const SENSITIVE_SETTING_PARTS: [&str; 4] = ["conninfo", "command", "passphrase", "password"];
+
+/// Recording fields holding SQL text or literals, at any depth.
+const RECORDING_TEXT_FIELDS: [&str; 4] = ["query", "blocked_query", "blocker_query", "message"];
+
+/// Header fields of a recording naming the connection.
+const RECORDING_CONNECTION_FIELDS: [&str; 3] = ["host", "dbname", "user"];
+
+/// How long one snapshot took to collect.
+#[derive(Debug, Serialize)]
+pub struct CollectorTiming {
+    pub took_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Files gathered for the tarball, in the order they were added.
+#[derive(Debug)]
+pub struct Bundle {
+    created_at: DateTime<Local>,
+    files: Vec<(String, Vec<u8>)>,
+    /// One line per file for the README
+    notes: Vec<String>,
+}
+
+impl Bundle {
+    pub fn new(created_at: DateTime<Local>) -> Self {
+        Self {
+            created_at,
+            files: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// `pg_glimpse-diagnostics-<time>.tar.gz`
+    pub fn default_file_name(&self) -> String {
+        format!("{}.tar.gz", self.dir_name())
+    }
+
+    fn dir_name(&self) -> String {
+        format!("pg_glimpse-diagnostics-{}", self.created_at.format("%Y%m%d_%H%M%S"))
+    }
+
+    pub fn add(&mut self, name: &str, note: &str, contents: impl Into<Vec<u8>>) {
+        self.files.push((name.to_string(), contents.into()));
+        self.notes.push(format!("{name:<20} {note}"));
+    }
+
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn add_config(&mut self, config: &AppConfig) {
+        let contents = toml::to_string_pretty(&redacted_config(config))
+            .unwrap_or_else(|e| format!("# could not serialize the config: {e}\n"));
+        self.add(
+            "config.toml",
+            "settings, with hook SQL, ignored tables and profile connections redacted",
+            contents,
+        );
+    }
+
+    /// Server version and settings, then [`COLLECTOR_SAMPLES`] timed snapshots.
+    pub async fn add_server(&mut self, client: &Client, ssl_label: &str) {
+        let mut info = match fetch_server_info(client).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.add("server_error.txt", "why the server could not be described", describe(&e));
+                return;
+            }
+        };
+        redact_server_info(&mut info);
+        let server = serde_json::json!({
+            "ssl": ssl_label,
+            "server_info": info,
+        });
+        self.add(
+            "server.json",
+            "version, extensions and pg_settings, with credentials redacted",
+            serde_json::to_vec_pretty(&server).unwrap_or_default(),
+        );
+
+        let mut timings = Vec::with_capacity(COLLECTOR_SAMPLES);
+        for _ in 0..COLLECTOR_SAMPLES {
+            let started = Instant::now();
+            let result = fetch_snapshot(client, &info.extensions, &info.capabilities, info.major_version(), false, true).await;
+            timings.push(CollectorTiming {
+                took_ms: started.elapsed().as_secs_f64() * 1000.0,
+                error: result.err().map(|e| format!("{e:#}")),
+            });
+        }
+        self.add(
+            "collector.json",
+            "time taken by back-to-back snapshots",
+            serde_json::to_vec_pretty(&timings).unwrap_or_default(),
+        );
+    }
+
+    pub fn add_connection_error(&mut self, error: &str) {
+        self.add("connection_error.txt", "why pg_glimpse could not connect", format!("{error}\n"));
+    }
+
+    pub fn add_error_log(&mut self, path: &Path) {
+        let contents = match error_log::tail(path, ERROR_LOG_LINES) {
+            Ok(lines) if lines.is_empty() => "(no errors logged)\n".to_string(),
+            Ok(lines) => lines.join("\n") + "\n",
+            Err(e) => format!("(could not read {}: {e})\n", path.display()),
+        };
+        self.add("error.log", "latest errors from live sessions", contents);
+    }
+
+    /// End of the newest recording under `recordings_dir`, if there is one.
+    pub fn add_latest_recording(&mut self, recordings_dir: Option<&str>) {
+        let Some(latest) = Recorder::list_recordings(recordings_dir).into_iter().next() else {
+            self.add("recording.txt", "no recording was found", "(no recordings)\n");
+            return;
+        };
+        // The segment being written, when the session was rotated
+        let path = session_segments(&latest.path).pop().unwrap_or(latest.path);
+        match recording_tail(&path, RECORDING_TAIL_LINES) {
+            Ok(contents) => self.add(
+                "recording.jsonl",
+                "header and latest snapshots of the newest recording, query texts redacted (replay with --replay)",
+                contents,
+            ),
+            Err(e) => self.add(
+                "recording.txt",
+                "the newest recording could not be read",
+                format!("(could not read {}: {e})\n", path.display()),
+            ),
+        }
+    }
+
+    /// Gzipped tarball with every file under one directory, plus a README.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let dir = self.dir_name();
+        let mut tar = Vec::new();
+        append_tar_entry(&mut tar, &format!("{dir}/README.txt"), self.readme().as_bytes(), &self.created_at);
+        for (name, contents) in &self.files {
+            append_tar_entry(&mut tar, &format!("{dir}/{name}"), contents, &self.created_at);
+        }
+        // End of archive: two empty blocks
+        tar.extend_from_slice(&[0; 1024]);
+
+        let mut encoder = GzEncoder::new(File::create(path)?, Compression::default());
+        encoder.write_all(&tar)?;
+        encoder.finish()?.flush()
+    }
+
+    fn readme(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "pg_glimpse {} diagnostic bundle", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(out, "Created:  {}", self.created_at.format("%Y-%m-%d %H:%M:%S %z"));
+        let _ = writeln!(out, "Platform: {} {}", std::env::consts::OS, std::env::consts::ARCH);
+        let _ = writeln!(
+            out,
+            "Terminal: {}",
+            std::env::var("TERM").unwrap_or_else(|_| "unknown".into())
+        );
+        let _ = writeln!(out);
+        for note in &self.notes {
+            let _ = writeln!(out, "{note}");
+        }
+        out
+    }
+}
+
+/// `config` with the parts that can hold SQL literals or object names
+/// replaced.
+pub fn redacted_config(config: &AppConfig) -> AppConfig {
+    let mut config = config.clone();
+    for sql in config.hooks.before.iter_mut().chain(config.hooks.after.iter_mut()) {
+        *sql = REDACTED.to_string();
+    }
+    for table in &mut config.ignore.tables {
+        *table = REDACTED.to_string();
+    }
+    for profile in config.profiles.values_mut() {
+        for value in [&mut profile.host, &mut profile.dbname, &mut profile.user].into_iter().flatten() {
+            *value = REDACTED.to_string();
+        }
+    }
+    config
+}
+
+/// Blank out setting values (server-wide and per-role) that can carry
+/// credentials, such as `primary_conninfo` or `archive_command`.
+pub fn redact_server_info(info: &mut ServerInfo) {
+    for setting in &mut info.settings {
+        if is_sensitive_setting(&setting.name) {
+            setting.setting = REDACTED.to_string();
+        }
+    }
+    for setting in &mut info.role_settings {
+        if is_sensitive_setting(&setting.name) {
+            setting.value = REDACTED.to_string();
+        }
+    }
+}
+
+fn is_sensitive_setting(name: &str) -> bool {
+    SENSITIVE_SETTING_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// The header line of a recording and its last `count` lines after it,
+/// each passed through [`redact_recording_line`].
+fn recording_tail(path: &Path, count: usize) -> io::Result<Vec<u8>> {
+    let mut lines = BufReader::new(File::open(path)?).lines();
+    let header = lines.next().transpose()?.unwrap_or_default();
+    let rest: Vec<String> = lines.collect::<io::Result<_>>()?;
+    let mut out = String::new();
+    for line in std::iter::once(&header).chain(&rest[rest.len().saturating_sub(count)..]) {
+        if let Some(line) = redact_recording_line(line) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    Ok(out.into_bytes())
+}
+
+/// A recording line with query texts and log messages replaced, and, in
+/// the header, the connection and sensitive settings. Lines that don't
+/// parse are left out rather than copied unread.
+fn redact_recording_line(line: &str) -> Option<String> {
+    let mut value: serde_json::Value = serde_json::from_str(line).ok()?;
+    redact_text_fields(&mut value);
+    if value["type"] == "header" {
+        for field in RECORDING_CONNECTION_FIELDS {
+            if value[field].is_string() {
+                value[field] = REDACTED.into();
+            }
+        }
+        let mut info: ServerInfo = serde_json::from_value(value["server_info"].take()).ok()?;
+        redact_server_info(&mut info);
+        value["server_info"] = serde_json::to_value(info).ok()?;
+    }
+    serde_json::to_string(&value).ok()
+}
+
+fn redact_text_fields(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map {
+                if field.is_string() && RECORDING_TEXT_FIELDS.contains(&key.as_str()) {
+                    *field = REDACTED.into();
+                } else {
+                    redact_text_fields(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_text_fields),
+        _ => {}
+    }
+}
+
+/// An error and the chain of causes behind it.
+fn describe(error: &dyn std::error::Error) -> String {
+    let mut out = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let _ = write!(out, ": {cause}");
+        source = cause.source();
+    }
+    out
+}
+
+/// One regular file in ustar format: a 512-byte header, then the contents
+/// padded to a whole block.
+fn append_tar_entry(tar: &mut Vec<u8>, name: &str, contents: &[u8], mtime: &DateTime<Local>) {
+    let mut header = [0u8; 512];
+    let name = name.as_bytes();
+    header[..name.len().min(100)].copy_from_slice(&name[..name.len().min(100)]);
+    header[100..108].copy_from_slice(b"0000644\0");
+    header[108..116].copy_from_slice(b"0000000\0");
+    header[116..124].copy_from_slice(b"0000000\0");
+    header[124..136].copy_from_slice(format!("{:011o}\0", contents.len()).as_bytes());
+    header[136..148].copy_from_slice(format!("{:011o}\0", mtime.timestamp().max(0)).as_bytes());
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    // The checksum is taken with its own field as spaces
+    header[148..156].copy_from_slice(b"        ");
+    let checksum: u32 = header.iter().map(|&b| u32::from(b)).sum();
+    header[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+
+    tar.extend_from_slice(&header);
+    tar.extend_from_slice(contents);
+    let padding = (512 - contents.len() % 512) % 512;
+    tar.extend(std::iter::repeat(0).take(padding));
+}
+
+/// Where `--diagnostics` writes when given no file name.
+pub fn output_path(requested: Option<&Path>, bundle: &Bundle) -> PathBuf {
+    requested.map_or_else(|| PathBuf::from(bundle.default_file_name()), Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{PgSetting, RoleSetting};
+    use chrono::TimeZone;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn bundle() -> Bundle {
+        Bundle::new(Local.with_ymd_and_hms(2024, 1, 15, 12, 30, 0).unwrap())
+    }
+
+    /// `(name, contents)` of each file in an uncompressed tarball.
+    fn read_tar(tar: &[u8]) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        let mut offset = 0;
+        while tar[offset] != 0 {
+            let header = &tar[offset..offset + 512];
+            let name_end = header[..100].iter().position(|&b| b == 0).unwrap_or(100);
+            let name = String::from_utf8(header[..name_end].to_vec()).unwrap();
+            let size_field = std::str::from_utf8(&header[124..135]).unwrap();
+            let size = usize::from_str_radix(size_field, 8).unwrap();
+
+            let stored = u32::from_str_radix(std::str::from_utf8(&header[148..154]).unwrap(), 8).unwrap();
+            let mut blank = header.to_vec();
+            blank[148..156].copy_from_slice(b"        ");
+            assert_eq!(stored, blank.iter().map(|&b| u32::from(b)).sum::<u32>());
+
+            let body = &tar[offset + 512..offset + 512 + size];
+            files.push((name, String::from_utf8(body.to_vec()).unwrap()));
+            offset += 512 + size.div_ceil(512) * 512;
+        }
+        assert!(tar[offset..].iter().all(|&b| b == 0));
+        files
+    }
+
+    #[test]
+    fn bundle_is_a_gzipped_tarball_under_one_directory() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("bundle.tar.gz");
+        let mut b = bundle();
+        b.add_config(&AppConfig::default());
+        b.add_connection_error("could not connect to PostgreSQL");
+        b.add_error_log(&tmp.path().join("missing.log"));
+        b.write(&path).unwrap();
+
+        let mut tar = Vec::new();
+        GzDecoder::new(File::open(&path).unwrap()).read_to_end(&mut tar).unwrap();
+        let files = read_tar(&tar);
+        let names: Vec<&str> = files.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "pg_glimpse-diagnostics-20240115_123000/README.txt",
+                "pg_glimpse-diagnostics-20240115_123000/config.toml",
+                "pg_glimpse-diagnostics-20240115_123000/connection_error.txt",
+                "pg_glimpse-diagnostics-20240115_123000/error.log",
+            ]
+        );
+        assert!(files[0].1.contains("connection_error.txt"));
+        assert_eq!(files[2].1, "could not connect to PostgreSQL\n");
+        assert_eq!(files[3].1, "(no errors logged)\n");
+        assert_eq!(b.default_file_name(), "pg_glimpse-diagnostics-20240115_123000.tar.gz");
+    }
+
+    #[test]
+    fn config_loses_hook_sql_and_ignored_tables() {
+        let mut config = AppConfig::default();
+        config.hooks.before.push("SET app.token = 's3cret'".into());
+        config.ignore.tables.push("billing.cards".into());
+        config.ignore.queryids.push(42);
+        config.profiles.insert(
+            "prod".into(),
+            crate::config::Profile {
+                host: Some("db1.internal".into()),
+                port: Some(6432),
+                dbname: Some("payroll".into()),
+                ..Default::default()
+            },
+        );
+
+        let redacted = redacted_config(&config);
+        assert_eq!(redacted.hooks.before, [REDACTED]);
+        assert_eq!(redacted.ignore.tables, [REDACTED]);
+        assert_eq!(redacted.ignore.queryids, [42]);
+        assert_eq!(redacted.profiles["prod"].host.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.profiles["prod"].dbname.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.profiles["prod"].port, Some(6432));
+        assert_eq!(redacted.refresh_interval_secs, config.refresh_interval_secs);
+    }
+
+    #[test]
+    fn settings_that_can_hold_credentials_are_blanked() {
+        let setting = |name: &str, value: &str| PgSetting {
+            name: name.into(),
+            setting: value.into(),
+            unit: None,
+            category: String::new(),
+            short_desc: None,
+            context: "sighup".into(),
+            source: "configuration file".into(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        };
+        let mut info = ServerInfo {
+            version: "PostgreSQL 16.1".into(),
+            start_time: chrono::Utc::now(),
+            max_connections: 100,
+            extensions: Default::default(),
+            settings: vec![
+                setting("primary_conninfo", "host=db1 password=hunter2"),
+                setting("archive_command", "aws s3 cp %p s3://k:s@bucket"),
+                setting("work_mem", "4096"),
+            ],
+            extensions_list: vec![],
+            role_settings: vec![RoleSetting {
+                role: Some("app".into()),
+                database: None,
+                name: "restore_command".into(),
+                value: "cp /secret/%f %p".into(),
+            }],
+            roles: vec![],
+            capabilities: Default::default(),
+        };
+        redact_server_info(&mut info);
+        let values: Vec<&str> = info.settings.iter().map(|s| s.setting.as_str()).collect();
+        assert_eq!(values, [REDACTED, REDACTED, "4096"]);
+        assert_eq!(info.role_settings[0].value, REDACTED);
+    }
+
+    #[test]
+    fn recording_tail_keeps_the_header() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("rec.jsonl");
+        let lines: Vec<String> = std::iter::once(r#"{"type":"other"}"#.to_string())
+            .chain((0..10).map(|i| format!(r#"{{"type":"snapshot","n":{i}}}"#)))
+            .collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let tail = String::from_utf8(recording_tail(&path, 3).unwrap()).unwrap();
+        assert_eq!(tail, format!("{}\n{}\n", lines[0], lines[8..].join("\n")));
+    }
+
+    #[test]
+    fn recording_tail_loses_query_texts_and_the_connection() {
+        let tmp = TempDir::new().unwrap();
+        let info = ServerInfo {
+            version: "PostgreSQL 16.1".into(),
+            start_time: chrono::Utc::now(),
+            max_connections: 100,
+            extensions: Default::default(),
+            settings: vec![],
+            extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
+        };
+        let mut recorder =
+            Recorder::new_with_path(tmp.path().join("rec.jsonl"), "db1.internal", 5432, "payroll", "admin", &info)
+                .unwrap();
+        let snap = crate::db::models::PgSnapshot {
+            active_queries: vec![crate::db::models::ActiveQuery {
+                pid: 7,
+                query: Some("SELECT * FROM cards WHERE number = '4111'".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        recorder.record(&snap).unwrap();
+
+        drop(recorder);
+        let path = std::fs::read_dir(tmp.path()).unwrap().next().unwrap().unwrap().path();
+        let tail = String::from_utf8(recording_tail(&path, 10).unwrap()).unwrap();
+        assert!(!tail.contains("4111"));
+        assert!(!tail.contains("db1.internal"));
+        assert!(!tail.contains("payroll"));
+        assert!(tail.contains(r#""pid":7"#));
+        assert_eq!(tail.lines().count(), 2);
+    }
+}
@@ -0,0 +1,123 @@
+//! Log of errors the live session ran into, kept so a diagnostic bundle
+//! can show what went wrong before the user went looking.
+//!
+//! One timestamped line per error; a repeat of the previous error (a
+//! server that stays down fails every refresh) is written once.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+/// Past this size the older half of the log is dropped.
+const MAX_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug)]
+pub struct ErrorLog {
+    path: PathBuf,
+    last: Option<String>,
+}
+
+impl ErrorLog {
+    /// `<data dir>/pg_glimpse/error.log`
+    pub fn default_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pg_glimpse")
+            .join("error.log")
+    }
+
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, last: None }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `message` unless it repeats the previous one.
+    pub fn append(&mut self, message: &str) -> io::Result<()> {
+        if self.last.as_deref() == Some(message) {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(&self.path).is_ok_and(|m| m.len() > MAX_BYTES) {
+            self.drop_older_half()?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        // Multi-line errors stay on one line so the log reads line by line
+        let message_line = message.replace('\n', " | ");
+        writeln!(file, "{} {message_line}", Utc::now().format("%Y-%m-%dT%H:%M:%SZ"))?;
+        self.last = Some(message.to_string());
+        Ok(())
+    }
+
+    fn drop_older_half(&self) -> io::Result<()> {
+        let lines = read_lines(&self.path)?;
+        let kept = &lines[lines.len() / 2..];
+        let mut contents = kept.join("\n");
+        contents.push('\n');
+        fs::write(&self.path, contents)
+    }
+}
+
+/// The last `count` lines of the log at `path`; empty if there is none.
+pub fn tail(path: &Path, count: usize) -> io::Result<Vec<String>> {
+    match read_lines(path) {
+        Ok(lines) => Ok(lines[lines.len().saturating_sub(count)..].to_vec()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(path)?).lines().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn repeats_are_written_once() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("logs").join("error.log");
+        let mut log = ErrorLog::new(path.clone());
+        log.append("connection refused").unwrap();
+        log.append("connection refused").unwrap();
+        log.append("timeout\nafter 5s").unwrap();
+        log.append("connection refused").unwrap();
+
+        let lines = tail(&path, 10).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with(" connection refused"));
+        assert!(lines[1].ends_with(" timeout | after 5s"));
+        assert_eq!(tail(&path, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn missing_log_has_an_empty_tail() {
+        let tmp = TempDir::new().unwrap();
+        assert!(tail(&tmp.path().join("error.log"), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn oversized_log_keeps_its_newer_half() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("error.log");
+        let old: String = (0..20_000).map(|i| format!("old {i:060}\n")).collect();
+        fs::write(&path, old).unwrap();
+
+        let mut log = ErrorLog::new(path.clone());
+        log.append("fresh").unwrap();
+        let lines = tail(&path, usize::MAX).unwrap();
+        assert_eq!(lines.len(), 10_001);
+        assert!(lines[0].starts_with("old 0000"));
+        assert!(lines[0].ends_with("10000"));
+        assert!(lines[10_000].ends_with(" fresh"));
+    }
+}
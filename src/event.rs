@@ -1,9 +1,13 @@
-use crossterm::event::{self, Event as CEvent, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyEvent, KeyEventKind, MouseEvent,
+    MouseEventKind,
+};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 pub enum AppEvent {
     Key(KeyEvent),
+    Mouse(MouseEvent),
 }
 
 pub struct EventHandler {
@@ -11,16 +15,32 @@ pub struct EventHandler {
 }
 
 impl EventHandler {
+    /// Read terminal events on a thread of their own. Mouse capture is on
+    /// while the handler lives; most terminals still select text with Shift held.
     pub fn new(poll_rate: Duration) -> Self {
+        let _ = crossterm::execute!(std::io::stdout(), EnableMouseCapture);
         let (tx, rx) = mpsc::unbounded_channel();
         std::thread::spawn(move || loop {
             if event::poll(poll_rate).unwrap_or(false) {
-                if let Ok(CEvent::Key(key)) = event::read() {
-                    if (key.kind == KeyEventKind::Press || key.kind == KeyEventKind::Repeat)
-                        && tx.send(AppEvent::Key(key)).is_err()
+                let event = match event::read() {
+                    Ok(CEvent::Key(key))
+                        if key.kind == KeyEventKind::Press || key.kind == KeyEventKind::Repeat =>
                     {
-                        break;
+                        AppEvent::Key(key)
                     }
+                    // Movement and drags would only cost redraws
+                    Ok(CEvent::Mouse(mouse))
+                        if matches!(
+                            mouse.kind,
+                            MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                        ) =>
+                    {
+                        AppEvent::Mouse(mouse)
+                    }
+                    _ => continue,
+                };
+                if tx.send(event).is_err() {
+                    break;
                 }
             }
         });
@@ -30,4 +50,15 @@ impl EventHandler {
     pub async fn next(&mut self) -> Option<AppEvent> {
         self.rx.recv().await
     }
+
+    /// Take a pending event without waiting.
+    pub fn try_next(&mut self) -> Option<AppEvent> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+    }
 }
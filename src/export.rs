@@ -0,0 +1,243 @@
+//! Export the top graphs as PNG/SVG images for incident docs.
+//!
+//! Plots the same `MetricsHistory` buffers that feed the terminal charts, so
+//! an export matches what was on screen (live or at the current replay
+//! position).
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use color_eyre::eyre::{eyre, Result};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::app::MetricsHistory;
+
+const IMAGE_SIZE: (u32, u32) = (1600, 1000);
+
+/// Where exported images go unless a directory is given.
+pub fn default_exports_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pg_glimpse")
+        .join("exports")
+}
+
+/// One metric's history, ready to plot.
+pub struct GraphSeries {
+    pub title: &'static str,
+    /// Y axis description
+    pub unit: &'static str,
+    pub values: Vec<f64>,
+    pub color: RGBColor,
+}
+
+/// The four top-of-screen metrics, in the terminal's reading order.
+pub fn graph_series(metrics: &MetricsHistory) -> Vec<GraphSeries> {
+    let scaled = |values: Vec<u64>, div: f64| values.into_iter().map(|v| v as f64 / div).collect();
+    vec![
+        GraphSeries {
+            title: "Connections",
+            unit: "backends",
+            values: scaled(metrics.connections.as_vec(), 1.0),
+            color: RGBColor(97, 175, 239),
+        },
+        GraphSeries {
+            title: "Transactions / s",
+            unit: "tps",
+            values: scaled(metrics.tps.as_vec(), 1.0),
+            color: RGBColor(198, 120, 221),
+        },
+        GraphSeries {
+            title: "Cache Hit",
+            unit: "%",
+            values: scaled(metrics.hit_ratio.as_vec(), 10.0),
+            color: RGBColor(152, 195, 121),
+        },
+        GraphSeries {
+            title: "Avg Duration",
+            unit: "ms",
+            values: scaled(metrics.avg_query_time.as_vec(), 1.0),
+            color: RGBColor(229, 192, 123),
+        },
+    ]
+}
+
+/// Render `series` as a grid into `<dir>/<stem>.svg` and `<dir>/<stem>.png`.
+/// Samples are `interval_secs` apart; the x axis counts seconds back from
+/// the newest one (or samples, when the interval is unknown).
+pub fn export_graphs(
+    series: &[GraphSeries],
+    interval_secs: u64,
+    title: &str,
+    dir: &Path,
+    stem: &str,
+) -> Result<Vec<PathBuf>> {
+    if series.iter().all(|s| s.values.is_empty()) {
+        return Err(eyre!("no graph data to export yet"));
+    }
+    register_font()?;
+    std::fs::create_dir_all(dir)?;
+
+    let stem = stem.replace(['/', '\\'], "_");
+    let svg_path = dir.join(format!("{stem}.svg"));
+    let png_path = dir.join(format!("{stem}.png"));
+
+    {
+        let root = SVGBackend::new(&svg_path, IMAGE_SIZE).into_drawing_area();
+        draw_grid(&root, series, interval_secs, title)?;
+    }
+    {
+        let root = BitMapBackend::new(&png_path, IMAGE_SIZE).into_drawing_area();
+        draw_grid(&root, series, interval_secs, title)?;
+    }
+
+    Ok(vec![svg_path, png_path])
+}
+
+fn draw_grid<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    series: &[GraphSeries],
+    interval_secs: u64,
+    title: &str,
+) -> Result<()> {
+    root.fill(&WHITE).map_err(plot_error)?;
+    let root = root
+        .titled(title, ("sans-serif", 28))
+        .map_err(plot_error)?;
+
+    let cols = if series.len() > 1 { 2 } else { 1 };
+    let rows = series.len().div_ceil(cols);
+    for (area, s) in root.split_evenly((rows, cols)).iter().zip(series) {
+        draw_chart(area, s, interval_secs)?;
+    }
+    root.present().map_err(plot_error)
+}
+
+fn draw_chart<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    series: &GraphSeries,
+    interval_secs: u64,
+) -> Result<()> {
+    let n = series.values.len();
+    let step = interval_secs.max(1) as f64;
+    let x_desc = if interval_secs == 0 { "samples" } else { "seconds" };
+    let x_min = -((n.saturating_sub(1)) as f64 * step);
+    let y_max = series.values.iter().copied().fold(0.0, f64::max).max(1.0) * 1.1;
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(series.title, ("sans-serif", 22))
+        .margin(16)
+        .x_label_area_size(36)
+        .y_label_area_size(64)
+        .build_cartesian_2d(x_min.min(-step)..0.0, 0.0..y_max)
+        .map_err(plot_error)?;
+
+    chart
+        .configure_mesh()
+        .x_desc(x_desc)
+        .y_desc(series.unit)
+        .light_line_style(RGBColor(235, 235, 235))
+        .draw()
+        .map_err(plot_error)?;
+
+    let points = series
+        .values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (x_min + i as f64 * step, v));
+    chart
+        .draw_series(LineSeries::new(points, series.color.stroke_width(2)))
+        .map_err(plot_error)?;
+    Ok(())
+}
+
+fn plot_error<E: std::error::Error + Send + Sync>(err: DrawingAreaErrorKind<E>) -> color_eyre::Report {
+    eyre!("rendering graphs failed: {err}")
+}
+
+/// Common locations of a sans-serif TrueType font on Linux and macOS
+const FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/usr/share/fonts/liberation/LiberationSans-Regular.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+];
+
+/// Plotters draws text with a font it is handed, so load one from disk once
+/// per process.
+fn register_font() -> Result<()> {
+    static REGISTERED: OnceLock<bool> = OnceLock::new();
+    let ok = *REGISTERED.get_or_init(|| {
+        FONT_CANDIDATES.iter().any(|path| {
+            std::fs::read(path).is_ok_and(|bytes| {
+                let bytes: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+                plotters::style::register_font("sans-serif", FontStyle::Normal, bytes).is_ok()
+            })
+        })
+    });
+    if ok {
+        Ok(())
+    } else {
+        Err(eyre!("no TrueType font found for graph labels (install DejaVu Sans)"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: Vec<f64>) -> GraphSeries {
+        GraphSeries {
+            title: "Connections",
+            unit: "backends",
+            values,
+            color: RGBColor(0, 0, 255),
+        }
+    }
+
+    #[test]
+    fn graph_series_scales_ring_buffers() {
+        let mut metrics = MetricsHistory::new(10);
+        metrics.hit_ratio.push(995);
+        metrics.connections.push(12);
+        let series = graph_series(&metrics);
+        assert_eq!(series.len(), 4);
+        assert_eq!(series[0].values, vec![12.0]);
+        assert_eq!(series[2].values, vec![99.5]);
+    }
+
+    #[test]
+    fn export_refuses_empty_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = export_graphs(&[series(vec![])], 2, "t", dir.path(), "empty").unwrap_err();
+        assert!(err.to_string().contains("no graph data"));
+    }
+
+    #[test]
+    fn export_writes_svg_and_png() {
+        if register_font().is_err() {
+            // No system font to draw labels with; nothing to check here
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let paths = export_graphs(
+            &[series(vec![1.0, 5.0, 3.0]), series(vec![2.0])],
+            2,
+            "localhost:5432",
+            dir.path(),
+            "host/5432",
+        )
+        .unwrap();
+
+        assert_eq!(paths[0], dir.path().join("host_5432.svg"));
+        let svg = std::fs::read_to_string(&paths[0]).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("Connections"));
+        let png = std::fs::read(&paths[1]).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
@@ -0,0 +1,277 @@
+//! Query fingerprints: SQL text with its literals taken out, so backends
+//! running the same statement with different values can be counted as one.
+//!
+//! Strings (including `E''` escape strings and dollar quoting), numbers and
+//! `$n` parameters become `?`, and a list of them collapses to `?, ...` so
+//! `IN (1, 2)` and `IN (3, 4, 5)` match. Comments are dropped and whitespace
+//! is folded to single spaces, with none after `(` or before `)` and `,`.
+//! Identifiers, quoted ones included, and keywords are kept as written.
+
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::db::models::ActiveQuery;
+
+/// Skip the rest of a quoted string whose opening quote was already read.
+/// A doubled quote is an escaped one; with `backslashes` so is `\'`.
+fn skip_string(chars: &mut Peekable<Chars<'_>>, backslashes: bool) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if backslashes => {
+                chars.next();
+            }
+            '\'' if chars.peek() == Some(&'\'') => {
+                chars.next();
+            }
+            '\'' => return,
+            _ => {}
+        }
+    }
+}
+
+/// After a `$`: a `$tag$...$tag$` string (the tag may be empty) is skipped
+/// whole. Returns false, consuming nothing, when it is not one.
+fn skip_dollar_quoted(chars: &mut Peekable<Chars<'_>>) -> bool {
+    let mut lookahead = chars.clone();
+    let mut tag = String::from("$");
+    loop {
+        match lookahead.next() {
+            Some('$') => break,
+            Some(c) if c.is_alphanumeric() || c == '_' => tag.push(c),
+            _ => return false,
+        }
+    }
+    tag.push('$');
+    let body: String = lookahead.collect();
+    let Some(end) = body.find(&tag) else {
+        return false;
+    };
+    // Tag and body (`tag` still lacks the opening `$`, already read)
+    for _ in 0..tag.chars().count() - 1 + body[..end].chars().count() + tag.chars().count() {
+        chars.next();
+    }
+    true
+}
+
+/// Skip a `/* */` comment whose `/` was already read; they nest in Postgres.
+fn skip_block_comment(chars: &mut Peekable<Chars<'_>>) {
+    chars.next();
+    let mut depth = 1;
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('*', Some('/')) => {
+                chars.next();
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                depth += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// The fingerprint of `sql`; see the module docs for what is normalized.
+pub fn fingerprint(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut space = false;
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                space = true;
+                continue;
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&c| c == '\n');
+                space = true;
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                skip_block_comment(&mut chars);
+                space = true;
+                continue;
+            }
+            _ => {}
+        }
+        if space && !out.is_empty() && !out.ends_with('(') && !matches!(c, ')' | ',') {
+            out.push(' ');
+        }
+        space = false;
+
+        match c {
+            '\'' => {
+                skip_string(&mut chars, false);
+                out.push('?');
+            }
+            '"' => {
+                out.push('"');
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                while chars.next_if(char::is_ascii_digit).is_some() {}
+                out.push('?');
+            }
+            '$' if skip_dollar_quoted(&mut chars) => out.push('?'),
+            c if c.is_ascii_digit() || (c == '.' && chars.peek().is_some_and(char::is_ascii_digit)) => {
+                while chars
+                    .next_if(|&c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+                    .is_some()
+                {}
+                out.push('?');
+            }
+            c if is_word_char(c) => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|&c| is_word_char(c)) {
+                    word.push(c);
+                }
+                // E'', B'' and X'' literals
+                if chars.peek() == Some(&'\'') && matches!(word.as_str(), "E" | "e" | "B" | "b" | "X" | "x") {
+                    chars.next();
+                    skip_string(&mut chars, word.eq_ignore_ascii_case("e"));
+                    out.push('?');
+                } else {
+                    out.push_str(&word);
+                }
+            }
+            ',' => {
+                out.push_str(", ");
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            c => out.push(c),
+        }
+    }
+    loop {
+        if let Some(pos) = out.find("..., ?") {
+            out.replace_range(pos..pos + 6, "...");
+        } else if let Some(pos) = out.find("?, ?") {
+            out.replace_range(pos..pos + 4, "?, ...");
+        } else {
+            break;
+        }
+    }
+    out.trim_end_matches([' ', ';']).to_string()
+}
+
+/// Backends running one fingerprint, shown as a single Queries row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FingerprintGroup {
+    pub fingerprint: String,
+    /// The longest-running member, which the row stands for
+    pub index: usize,
+    pub count: usize,
+}
+
+/// Group `queries[indices]` by fingerprint, in the order each fingerprint
+/// first appears in `indices`.
+pub fn group(queries: &[ActiveQuery], indices: &[usize]) -> Vec<FingerprintGroup> {
+    let mut groups: Vec<FingerprintGroup> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for &i in indices {
+        let fingerprint = fingerprint(queries[i].query.as_deref().unwrap_or(""));
+        match positions.get(&fingerprint) {
+            Some(&pos) => {
+                let group = &mut groups[pos];
+                group.count += 1;
+                if queries[i].duration_secs > queries[group.index].duration_secs {
+                    group.index = i;
+                }
+            }
+            None => {
+                positions.insert(fingerprint.clone(), groups.len());
+                groups.push(FingerprintGroup {
+                    fingerprint,
+                    index: i,
+                    count: 1,
+                });
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals_become_placeholders() {
+        assert_eq!(
+            fingerprint("SELECT * FROM orders WHERE id = 42 AND note = 'it''s' AND total > 1.5e3"),
+            "SELECT * FROM orders WHERE id = ? AND note = ? AND total > ?"
+        );
+        assert_eq!(
+            fingerprint(r"SELECT E'a\'b', x'1F', $1, $tag$body $ here$tag$, $$x$$"),
+            "SELECT ?, ...",
+        );
+        // Digits inside identifiers stay
+        assert_eq!(fingerprint(r#"SELECT t1.c2 FROM "Table 3" t1"#), r#"SELECT t1.c2 FROM "Table 3" t1"#);
+    }
+
+    #[test]
+    fn whitespace_comments_and_lists_are_folded() {
+        let a = fingerprint("SELECT *\n  FROM orders  -- by id\n WHERE id IN (1,2,3);");
+        let b = fingerprint("/* app: web */ SELECT * FROM orders WHERE id IN ( 7, 8 )");
+        assert_eq!(a, "SELECT * FROM orders WHERE id IN (?, ...)");
+        assert_eq!(a, b);
+        assert_eq!(fingerprint("INSERT INTO t VALUES (1)"), "INSERT INTO t VALUES (?)");
+    }
+
+    #[test]
+    fn different_statements_keep_different_fingerprints() {
+        assert_ne!(
+            fingerprint("SELECT * FROM orders WHERE id = 1"),
+            fingerprint("SELECT * FROM users WHERE id = 1")
+        );
+        assert_eq!(fingerprint(""), "");
+        // An unterminated string still yields something
+        assert_eq!(fingerprint("SELECT 'oops"), "SELECT ?");
+    }
+
+    #[test]
+    fn groups_keep_first_appearance_order_and_the_longest_member() {
+        let query = |pid: i32, text: &str, duration_secs: f64| ActiveQuery {
+            pid,
+            state: Some("active".into()),
+            duration_secs,
+            query: Some(text.into()),
+            ..Default::default()
+        };
+        let queries = vec![
+            query(1, "SELECT * FROM orders WHERE id = 1", 2.0),
+            query(2, "UPDATE users SET seen = now() WHERE id = 9", 1.0),
+            query(3, "SELECT * FROM orders WHERE id = 2", 30.0),
+            query(4, "select 1", 0.5),
+        ];
+        let groups = group(&queries, &[0, 1, 2]);
+        assert_eq!(
+            groups,
+            vec![
+                FingerprintGroup {
+                    fingerprint: "SELECT * FROM orders WHERE id = ?".into(),
+                    index: 2,
+                    count: 2,
+                },
+                FingerprintGroup {
+                    fingerprint: "UPDATE users SET seen = now() WHERE id = ?".into(),
+                    index: 1,
+                    count: 1,
+                },
+            ]
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::VecDeque;
 
+use chrono::{DateTime, Utc};
+
 #[derive(Debug, Clone)]
 pub struct RingBuffer<T> {
     data: VecDeque<T>,
+    /// When each value was sampled, once stamped
+    times: VecDeque<Option<DateTime<Utc>>>,
     capacity: usize,
 }
 
@@ -10,15 +14,41 @@ impl<T: Copy + Default> RingBuffer<T> {
     pub fn new(capacity: usize) -> Self {
         Self {
             data: VecDeque::with_capacity(capacity),
+            times: VecDeque::with_capacity(capacity),
             capacity,
         }
     }
 
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn push(&mut self, value: T) {
         if self.data.len() >= self.capacity {
             self.data.pop_front();
+            self.times.pop_front();
         }
         self.data.push_back(value);
+        self.times.push_back(None);
+    }
+
+    /// Give every value pushed since the last stamp the time `at`.
+    pub fn stamp(&mut self, at: DateTime<Utc>) {
+        for time in self.times.iter_mut().rev() {
+            if time.is_some() {
+                break;
+            }
+            *time = Some(at);
+        }
+    }
+
+    /// Stamped values with their times, oldest first.
+    pub fn timed(&self) -> Vec<(DateTime<Utc>, T)> {
+        self.times
+            .iter()
+            .zip(&self.data)
+            .filter_map(|(time, value)| time.map(|t| (t, *value)))
+            .collect()
     }
 
     pub fn as_vec(&self) -> Vec<T> {
@@ -115,4 +145,19 @@ mod tests {
         }
         assert_eq!(buf.as_vec(), vec![8, 9, 10]);
     }
+
+    #[test]
+    fn stamp_marks_only_values_pushed_since_the_last_stamp() {
+        let t = |secs| DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::seconds(secs);
+        let mut buf = RingBuffer::new(3);
+        buf.push(1);
+        buf.stamp(t(10));
+        buf.push(2);
+        buf.push(3);
+        buf.stamp(t(20));
+        buf.push(4);
+        assert_eq!(buf.timed(), vec![(t(20), 2), (t(20), 3)]);
+        buf.stamp(t(30));
+        assert_eq!(buf.timed(), vec![(t(20), 2), (t(20), 3), (t(30), 4)]);
+    }
 }
@@ -0,0 +1,128 @@
+//! User-defined SQL run before and after every snapshot.
+//!
+//! Hooks run on the snapshot connection, so session settings they make
+//! (`SET application_name`, `SET search_path`) stick. Each statement gets
+//! its own `statement_timeout`; a hook that fails or times out is reported
+//! and the snapshot goes ahead anyway.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// The `[hooks]` section of the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotHooks {
+    /// Run before each snapshot, in order
+    pub before: Vec<String>,
+    /// Run after each snapshot, in order
+    pub after: Vec<String>,
+    /// Longest a single hook may run
+    pub timeout_ms: u64,
+}
+
+impl Default for SnapshotHooks {
+    fn default() -> Self {
+        Self {
+            before: Vec::new(),
+            after: Vec::new(),
+            timeout_ms: 1000,
+        }
+    }
+}
+
+impl SnapshotHooks {
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    fn statements(&self, phase: HookPhase) -> &[String] {
+        match phase {
+            HookPhase::Before => &self.before,
+            HookPhase::After => &self.after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookPhase {
+    Before,
+    After,
+}
+
+impl HookPhase {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Before => "before",
+            Self::After => "after",
+        }
+    }
+}
+
+/// How one hook went on its last run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOutcome {
+    pub phase: HookPhase,
+    pub sql: String,
+    pub took: Duration,
+    pub error: Option<String>,
+}
+
+/// Run the hooks for `phase`, each under the configured timeout.
+pub async fn run_hooks(
+    client: &tokio_postgres::Client,
+    phase: HookPhase,
+    hooks: &SnapshotHooks,
+) -> Vec<HookOutcome> {
+    let mut outcomes = Vec::new();
+    for sql in hooks.statements(phase) {
+        let started = Instant::now();
+        let error = run_with_timeout(client, sql, hooks.timeout_ms).await.err();
+        outcomes.push(HookOutcome {
+            phase,
+            sql: sql.clone(),
+            took: started.elapsed(),
+            error,
+        });
+    }
+    outcomes
+}
+
+async fn run_with_timeout(
+    client: &tokio_postgres::Client,
+    sql: &str,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    client
+        .batch_execute(&format!("SET statement_timeout = {timeout_ms}"))
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = client.batch_execute(sql).await.map_err(|e| e.to_string());
+    // Back to the session default so snapshot queries aren't cut short
+    let reset = client
+        .batch_execute("RESET statement_timeout")
+        .await
+        .map_err(|e| e.to_string());
+    result.and(reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_parse_from_toml_with_default_timeout() {
+        let hooks: SnapshotHooks = toml::from_str(
+            r#"
+            before = ["SET application_name = 'pg_glimpse'"]
+            after = ["REFRESH MATERIALIZED VIEW CONCURRENTLY ops.load_summary"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(hooks.before.len(), 1);
+        assert_eq!(hooks.statements(HookPhase::After)[0], hooks.after[0]);
+        assert_eq!(hooks.timeout_ms, 1000);
+        assert!(!hooks.is_empty());
+        assert!(SnapshotHooks::default().is_empty());
+    }
+}
@@ -0,0 +1,289 @@
+//! Index health findings derived from the indexes in a snapshot.
+//!
+//! Flags the indexes that cost space and write overhead for nothing:
+//! invalid ones left behind by a failed `CREATE INDEX CONCURRENTLY`,
+//! duplicates of another index on the same table, ones whose columns are a
+//! leading prefix of another btree index, and ones not scanned since
+//! statistics were last reset. Definitions are compared as Postgres prints
+//! them with `pg_get_indexdef`, so no extra queries are needed and the same
+//! findings come out of a replayed recording.
+//!
+//! Unique indexes enforce a constraint whether or not anything scans them,
+//! so they are never reported as unused or overlapping.
+
+use std::cmp::Reverse;
+
+use serde::Serialize;
+
+use crate::db::models::IndexInfo;
+
+/// Why an index is reported, most urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexIssue {
+    Invalid,
+    Duplicate,
+    Overlapping,
+    Unused,
+}
+
+impl IndexIssue {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Invalid => "invalid",
+            Self::Duplicate => "duplicate",
+            Self::Overlapping => "overlapping",
+            Self::Unused => "unused",
+        }
+    }
+}
+
+/// One index that could be dropped (or rebuilt, when invalid).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexFinding {
+    pub schemaname: String,
+    pub table_name: String,
+    pub index_name: String,
+    pub issue: IndexIssue,
+    /// The index that makes this one redundant
+    pub redundant_with: Option<String>,
+    pub index_size_bytes: i64,
+    pub idx_scan: i64,
+}
+
+impl IndexFinding {
+    /// `schema.index`, the key the index inspect overlay looks up
+    pub fn key(&self) -> String {
+        format!("{}.{}", self.schemaname, self.index_name)
+    }
+}
+
+/// The parts of an index definition two indexes are compared on.
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedIndex<'a> {
+    unique: bool,
+    method: &'a str,
+    /// Key columns or expressions, with any ordering and opclass
+    columns: Vec<&'a str>,
+    include: Vec<&'a str>,
+    predicate: Option<&'a str>,
+}
+
+impl ParsedIndex<'_> {
+    fn same_as(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.columns == other.columns
+            && self.include == other.include
+            && self.predicate == other.predicate
+    }
+
+    /// Every lookup this index serves, `other` serves too.
+    fn covered_by(&self, other: &Self) -> bool {
+        !self.unique
+            && self.method == "btree"
+            && other.method == "btree"
+            && self.include.is_empty()
+            && self.predicate == other.predicate
+            && self.columns.len() < other.columns.len()
+            && other.columns.starts_with(&self.columns)
+    }
+}
+
+/// Split the text inside the parenthesised group `text` starts with,
+/// returning its top-level comma-separated items and the rest of `text`.
+fn paren_group(text: &str) -> Option<(Vec<&str>, &str)> {
+    let text = text.strip_prefix('(')?;
+    let mut depth = 0usize;
+    let mut in_quote = None;
+    let mut items = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match (in_quote, c) {
+            (Some(q), c) if c == q => in_quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => in_quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => {
+                items.push(text[start..i].trim());
+                return Some((items, &text[i + 1..]));
+            }
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                items.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse `CREATE [UNIQUE] INDEX name ON table USING method (cols) [INCLUDE (cols)] ... [WHERE pred]`.
+fn parse_definition(definition: &str) -> Option<ParsedIndex<'_>> {
+    let unique = definition.starts_with("CREATE UNIQUE INDEX");
+    let (_, using) = definition.split_once(" USING ")?;
+    let (method, rest) = using.split_once(' ')?;
+    let (columns, rest) = paren_group(rest)?;
+    let (rest, predicate) = match rest.split_once(" WHERE ") {
+        Some((rest, predicate)) => (rest, Some(predicate.trim())),
+        None => (rest, None),
+    };
+    let include = match rest.split_once("INCLUDE ") {
+        Some((_, group)) => paren_group(group)?.0,
+        None => Vec::new(),
+    };
+    Some(ParsedIndex {
+        unique,
+        method,
+        columns,
+        include,
+        predicate,
+    })
+}
+
+/// Findings for `indexes`, most urgent first and largest first within an issue.
+pub fn findings(indexes: &[IndexInfo]) -> Vec<IndexFinding> {
+    let parsed: Vec<_> = indexes
+        .iter()
+        .map(|i| parse_definition(&i.index_definition).filter(|_| i.is_valid))
+        .collect();
+    let same_table = |a: usize, b: usize| {
+        indexes[a].schemaname == indexes[b].schemaname
+            && indexes[a].table_name == indexes[b].table_name
+    };
+
+    let mut found = Vec::new();
+    for (i, index) in indexes.iter().enumerate() {
+        let issue = if index.is_valid {
+            parsed[i].as_ref().and_then(|this| {
+                let others = || {
+                    parsed
+                        .iter()
+                        .enumerate()
+                        .filter(move |&(j, _)| same_table(i, j))
+                        .filter_map(|(j, p)| Some((j, p.as_ref()?)))
+                };
+                // Of identical indexes the one kept is the unique, most used, first listed
+                let kept = others()
+                    .filter(|(_, p)| p.same_as(this))
+                    .max_by_key(|&(j, p)| (p.unique, indexes[j].idx_scan, Reverse(j)))
+                    .map(|(j, _)| j)
+                    .filter(|&j| j != i);
+                if let Some(j) = kept {
+                    return Some((IndexIssue::Duplicate, Some(j)));
+                }
+                if let Some((j, _)) = others().find(|(_, p)| this.covered_by(p)) {
+                    return Some((IndexIssue::Overlapping, Some(j)));
+                }
+                (index.idx_scan == 0 && !this.unique).then_some((IndexIssue::Unused, None))
+            })
+        } else {
+            Some((IndexIssue::Invalid, None))
+        };
+        if let Some((issue, other)) = issue {
+            found.push(IndexFinding {
+                schemaname: index.schemaname.clone(),
+                table_name: index.table_name.clone(),
+                index_name: index.index_name.clone(),
+                issue,
+                redundant_with: other.map(|j| indexes[j].index_name.clone()),
+                index_size_bytes: index.index_size_bytes,
+                idx_scan: index.idx_scan,
+            });
+        }
+    }
+    found.sort_by_key(|f| (f.issue, Reverse(f.index_size_bytes)));
+    found
+}
+
+/// Space freed by dropping every index in `findings`.
+pub fn reclaimable_bytes(findings: &[IndexFinding]) -> i64 {
+    findings.iter().map(|f| f.index_size_bytes).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(name: &str, definition: &str, idx_scan: i64) -> IndexInfo {
+        IndexInfo {
+            schemaname: "public".into(),
+            table_name: "orders".into(),
+            index_name: name.into(),
+            index_size_bytes: 1024,
+            idx_scan,
+            idx_tup_read: 0,
+            idx_tup_fetch: 0,
+            index_definition: definition.into(),
+            is_valid: true,
+            bloat_bytes: None,
+            bloat_pct: None,
+            bloat_source: None,
+        }
+    }
+
+    #[test]
+    fn definitions_split_into_columns_include_and_predicate() {
+        let parsed = parse_definition(
+            "CREATE UNIQUE INDEX o_idx ON public.orders USING btree (lower((email)::text), id DESC) \
+             INCLUDE (total) WHERE (status = 'a, b')",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            ParsedIndex {
+                unique: true,
+                method: "btree",
+                columns: vec!["lower((email)::text)", "id DESC"],
+                include: vec!["total"],
+                predicate: Some("(status = 'a, b')"),
+            }
+        );
+        assert!(parse_definition("not an index").is_none());
+    }
+
+    #[test]
+    fn flags_invalid_duplicate_overlapping_and_unused_indexes() {
+        let mut invalid = index("orders_note_idx", "CREATE INDEX orders_note_idx ON public.orders USING btree (note)", 0);
+        invalid.is_valid = false;
+        let indexes = vec![
+            index("orders_pkey", "CREATE UNIQUE INDEX orders_pkey ON public.orders USING btree (id)", 0),
+            index("orders_id_idx", "CREATE INDEX orders_id_idx ON public.orders USING btree (id)", 50),
+            index("orders_user_idx", "CREATE INDEX orders_user_idx ON public.orders USING btree (user_id)", 9),
+            index(
+                "orders_user_created_idx",
+                "CREATE INDEX orders_user_created_idx ON public.orders USING btree (user_id, created_at)",
+                3,
+            ),
+            index("orders_open_idx", "CREATE INDEX orders_open_idx ON public.orders USING btree (user_id) WHERE (open)", 0),
+            invalid,
+        ];
+        let found: Vec<_> = findings(&indexes)
+            .into_iter()
+            .map(|f| (f.index_name, f.issue, f.redundant_with))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("orders_note_idx".into(), IndexIssue::Invalid, None),
+                // The unique index is kept even though the copy is the one being scanned
+                ("orders_id_idx".into(), IndexIssue::Duplicate, Some("orders_pkey".into())),
+                ("orders_user_idx".into(), IndexIssue::Overlapping, Some("orders_user_created_idx".into())),
+                // A partial index is not covered by a full one; the unused pkey is not reported
+                ("orders_open_idx".into(), IndexIssue::Unused, None),
+            ]
+        );
+        assert_eq!(reclaimable_bytes(&findings(&indexes)), 4 * 1024);
+    }
+
+    #[test]
+    fn indexes_on_other_tables_are_not_duplicates() {
+        let mut other = index("items_id_idx", "CREATE INDEX items_id_idx ON public.items USING btree (id)", 1);
+        other.table_name = "items".into();
+        let indexes = vec![
+            index("orders_id_idx", "CREATE INDEX orders_id_idx ON public.orders USING btree (id)", 1),
+            other,
+        ];
+        assert!(findings(&indexes).is_empty());
+    }
+}
@@ -1,19 +1,44 @@
 //! pg_glimpse - A TUI for monitoring PostgreSQL databases.
 
+pub mod adaptive_refresh;
+pub mod alerts;
 pub mod app;
+pub mod autovacuum;
+pub mod baseline;
+pub mod bookmarks;
 pub mod cli;
 pub mod config;
 pub mod connection;
+pub mod daemon;
 pub mod db;
+pub mod diagnostics;
+pub mod error_log;
 pub mod event;
+pub mod export;
+pub mod fingerprint;
 pub mod history;
+pub mod hooks;
+pub mod index_health;
+pub mod maintenance;
+pub mod notifications;
+pub mod observe;
+pub mod once;
 pub mod recorder;
 pub mod replay;
+pub mod report;
+pub mod row_colors;
 pub mod runtime;
+pub mod schedule;
+pub mod server_log;
+pub mod session_report;
+pub mod session_stats;
 pub mod ssl;
+pub mod starred;
+pub mod table_export;
 pub mod ui;
+pub mod usage_stats;
+pub mod web;
 
-use clap::Parser;
 use cli::Cli;
 use color_eyre::eyre::Result;
 
@@ -24,7 +49,7 @@ use color_eyre::eyre::Result;
 /// to either live mode or replay mode based on the arguments.
 pub fn run_cli() -> Result<()> {
     color_eyre::install()?;
-    let cli = Cli::parse();
+    let cli = Cli::parse_args();
 
     let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
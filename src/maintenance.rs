@@ -0,0 +1,216 @@
+//! Maintenance SQL for a table or index.
+//!
+//! By default pg_glimpse only writes these out, to the clipboard or a `.sql`
+//! file, for the operator to read and run themselves. Started with
+//! `--allow-maintenance`, VACUUM, ANALYZE and REINDEX can also be run from
+//! the inspect overlays, after a confirmation, on a connection of their own.
+
+use crate::db::models::{quote_ident, quote_literal, IndexInfo, TableStat};
+
+/// A maintenance command on one relation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Maintenance {
+    /// `VACUUM (VERBOSE, ANALYZE)` on a table
+    VacuumAnalyze { schema: String, table: String },
+    /// `ANALYZE (VERBOSE)` on a table
+    Analyze { schema: String, table: String },
+    /// `REINDEX INDEX CONCURRENTLY`, or a locking `REINDEX` before PG12
+    Reindex { schema: String, index: String },
+    /// Build a copy of the index concurrently, then swap it in
+    Rebuild { schema: String, index: String, definition: String },
+}
+
+impl Maintenance {
+    pub fn vacuum(table: &TableStat) -> Self {
+        Self::VacuumAnalyze {
+            schema: table.schemaname.clone(),
+            table: table.relname.clone(),
+        }
+    }
+
+    pub fn analyze(table: &TableStat) -> Self {
+        Self::Analyze {
+            schema: table.schemaname.clone(),
+            table: table.relname.clone(),
+        }
+    }
+
+    pub fn reindex(index: &IndexInfo) -> Self {
+        Self::Reindex {
+            schema: index.schemaname.clone(),
+            index: index.index_name.clone(),
+        }
+    }
+
+    pub fn rebuild(index: &IndexInfo) -> Self {
+        Self::Rebuild {
+            schema: index.schemaname.clone(),
+            index: index.index_name.clone(),
+            definition: index.index_definition.clone(),
+        }
+    }
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::VacuumAnalyze { .. } => "VACUUM",
+            Self::Analyze { .. } => "ANALYZE",
+            Self::Reindex { .. } => "REINDEX",
+            Self::Rebuild { .. } => "Rebuild",
+        }
+    }
+
+    /// Whether it can be run from the TUI against a server of this major
+    /// version. The rebuild script has a check to make by hand halfway
+    /// through, and before PostgreSQL 12 REINDEX locks out writes for as
+    /// long as it runs, so those are only ever generated.
+    pub const fn runnable(&self, server_major: u32) -> bool {
+        match self {
+            Self::Rebuild { .. } => false,
+            Self::Reindex { .. } => server_major >= 12,
+            Self::VacuumAnalyze { .. } | Self::Analyze { .. } => true,
+        }
+    }
+
+    /// `VACUUM public.orders`, for the status line and confirmations.
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.label(), self.relation())
+    }
+
+    /// The relation the command is about, unquoted, for messages and file
+    /// names.
+    pub fn relation(&self) -> String {
+        match self {
+            Self::VacuumAnalyze { schema, table } | Self::Analyze { schema, table } => format!("{schema}.{table}"),
+            Self::Reindex { schema, index } | Self::Rebuild { schema, index, .. } => format!("{schema}.{index}"),
+        }
+    }
+
+    /// `maintenance_<kind>_<relation>`, the start of the file name it is
+    /// written to.
+    pub fn file_stem(&self) -> String {
+        let kind = match self {
+            Self::VacuumAnalyze { .. } => "vacuum",
+            Self::Analyze { .. } => "analyze",
+            Self::Reindex { .. } => "reindex",
+            Self::Rebuild { .. } => "rebuild",
+        };
+        let relation: String = self
+            .relation()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '.' { c } else { '_' })
+            .collect();
+        format!("maintenance_{kind}_{relation}")
+    }
+
+    /// The SQL to run against a server of this major version. `None` when an
+    /// index definition can't be turned into a concurrent build.
+    pub fn sql(&self, server_major: u32) -> Option<String> {
+        match self {
+            Self::VacuumAnalyze { schema, table } => Some(format!(
+                "VACUUM (VERBOSE, ANALYZE) {}.{};",
+                quote_ident(schema),
+                quote_ident(table)
+            )),
+            Self::Analyze { schema, table } => Some(format!(
+                "ANALYZE (VERBOSE) {}.{};",
+                quote_ident(schema),
+                quote_ident(table)
+            )),
+            Self::Reindex { schema, index } => {
+                let qualified = format!("{}.{}", quote_ident(schema), quote_ident(index));
+                Some(if server_major >= 12 {
+                    format!("REINDEX INDEX CONCURRENTLY {qualified};")
+                } else {
+                    format!(
+                        "-- REINDEX CONCURRENTLY needs PostgreSQL 12; this blocks writes to the table\n\
+                         REINDEX INDEX {qualified};"
+                    )
+                })
+            }
+            Self::Rebuild { schema, index, definition } => {
+                let new_name = format!("{index}_new");
+                let create = concurrent_copy(definition, &quote_ident(&new_name))?;
+                let old = format!("{}.{}", quote_ident(schema), quote_ident(index));
+                let new = format!("{}.{}", quote_ident(schema), quote_ident(&new_name));
+                Some(format!(
+                    "-- Build a copy without blocking writes; run outside a transaction block\n\
+                     {create};\n\
+                     \n\
+                     -- A failed build leaves an invalid index behind: check before swapping\n\
+                     SELECT indisvalid FROM pg_index WHERE indexrelid = {}::regclass;\n\
+                     \n\
+                     -- Swap it in. Indexes backing a constraint need ALTER TABLE instead\n\
+                     DROP INDEX CONCURRENTLY {old};\n\
+                     ALTER INDEX {new} RENAME TO {};",
+                    quote_literal(&new),
+                    quote_ident(index)
+                ))
+            }
+        }
+    }
+}
+
+/// `definition` (as `pg_get_indexdef` returns it) as a `CREATE INDEX
+/// CONCURRENTLY` under `new_name`, which must already be quoted.
+fn concurrent_copy(definition: &str, new_name: &str) -> Option<String> {
+    let (create, rest) = if let Some(rest) = definition.strip_prefix("CREATE UNIQUE INDEX ") {
+        ("CREATE UNIQUE INDEX", rest)
+    } else {
+        ("CREATE INDEX", definition.strip_prefix("CREATE INDEX ")?)
+    };
+    // The name runs up to the first " ON "; a quoted name could contain
+    // one, so skip past the closing quote first
+    let name_end = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.find('"')? + 2,
+        None => 0,
+    };
+    let on = rest[name_end..].find(" ON ")? + name_end;
+    Some(format!("{create} CONCURRENTLY {new_name}{}", &rest[on..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuild_copies_the_definition_concurrently() {
+        let rebuild = Maintenance::Rebuild {
+            schema: "public".into(),
+            index: "Orders_Email".into(),
+            definition: "CREATE UNIQUE INDEX \"Orders_Email\" ON public.orders USING btree (lower(email))".into(),
+        };
+        let sql = rebuild.sql(16).unwrap();
+        assert!(sql.contains(
+            "CREATE UNIQUE INDEX CONCURRENTLY \"Orders_Email_new\" ON public.orders USING btree (lower(email));"
+        ));
+        assert!(sql.contains("DROP INDEX CONCURRENTLY public.\"Orders_Email\";"));
+        assert!(sql.ends_with("ALTER INDEX public.\"Orders_Email_new\" RENAME TO \"Orders_Email\";"));
+        assert_eq!(rebuild.file_stem(), "maintenance_rebuild_public.Orders_Email");
+
+        let quoted = Maintenance::Rebuild {
+            schema: "public".into(),
+            index: "it's".into(),
+            definition: "CREATE INDEX \"it's\" ON public.orders USING btree (id)".into(),
+        };
+        assert!(quoted.sql(16).unwrap().contains("indexrelid = 'public.\"it''s_new\"'::regclass;"));
+
+        let odd = Maintenance::Rebuild {
+            schema: "public".into(),
+            index: "x".into(),
+            definition: "ALTER TABLE x".into(),
+        };
+        assert_eq!(odd.sql(16), None);
+    }
+
+    #[test]
+    fn reindex_falls_back_before_pg12() {
+        let reindex = Maintenance::Reindex {
+            schema: "public".into(),
+            index: "orders_pkey".into(),
+        };
+        assert_eq!(reindex.sql(16).unwrap(), "REINDEX INDEX CONCURRENTLY public.orders_pkey;");
+        assert!(reindex.sql(11).unwrap().ends_with("\nREINDEX INDEX public.orders_pkey;"));
+        assert!(reindex.runnable(12));
+        assert!(!reindex.runnable(11));
+    }
+}
@@ -0,0 +1,325 @@
+//! Terminal bell and desktop notifications for queries running past the
+//! danger duration threshold and for new blocking chains.
+//!
+//! Each event type is configured on its own in `config.toml`:
+//!
+//! ```toml
+//! [notifications]
+//! long_query = "bell"      # off, bell, desktop or both
+//! blocking = "desktop"
+//! min_interval_secs = 60
+//! ```
+//!
+//! An event type notifies at most once per `min_interval_secs`; whatever
+//! crosses the line in between is left to the panels. Only live snapshots
+//! are checked, never replayed ones.
+
+use std::collections::HashSet;
+use std::io::{IsTerminal, Write};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::PgSnapshot;
+use crate::ui::util::truncate;
+
+/// How an event is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyVia {
+    #[default]
+    Off,
+    Bell,
+    Desktop,
+    Both,
+}
+
+impl NotifyVia {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Bell,
+            Self::Bell => Self::Desktop,
+            Self::Desktop => Self::Both,
+            Self::Both => Self::Off,
+        }
+    }
+
+    pub const fn prev(self) -> Self {
+        match self {
+            Self::Off => Self::Both,
+            Self::Bell => Self::Off,
+            Self::Desktop => Self::Bell,
+            Self::Both => Self::Desktop,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Bell => "Bell",
+            Self::Desktop => "Desktop",
+            Self::Both => "Bell + Desktop",
+        }
+    }
+
+    const fn bell(self) -> bool {
+        matches!(self, Self::Bell | Self::Both)
+    }
+
+    const fn desktop(self) -> bool {
+        matches!(self, Self::Desktop | Self::Both)
+    }
+}
+
+/// The `[notifications]` table of the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    /// An active query ran past the danger duration threshold
+    pub long_query: NotifyVia,
+    /// A backend started waiting on a lock held by another
+    pub blocking: NotifyVia,
+    /// Least time between two notifications of the same event type
+    pub min_interval_secs: u64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            long_query: NotifyVia::Off,
+            blocking: NotifyVia::Off,
+            min_interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyEvent {
+    LongQuery,
+    Blocking,
+}
+
+/// One notification waiting to be delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub event: NotifyEvent,
+    pub via: NotifyVia,
+    pub title: String,
+    pub body: String,
+}
+
+/// Query text kept in a notification body
+const BODY_QUERY_CHARS: usize = 80;
+
+/// What has already been announced, so each query or blocked backend is
+/// only announced when it crosses the line.
+#[derive(Debug, Default)]
+pub struct Notifier {
+    long_running: HashSet<i32>,
+    blocked: HashSet<i32>,
+    last_long_query: Option<DateTime<Utc>>,
+    last_blocking: Option<DateTime<Utc>>,
+}
+
+impl Notifier {
+    /// Notifications for what crossed the line since the previous snapshot.
+    pub fn check(
+        &mut self,
+        config: &NotificationConfig,
+        danger_secs: f64,
+        snap: &PgSnapshot,
+    ) -> Vec<Notification> {
+        let now = snap.timestamp;
+        let min_gap = chrono::Duration::seconds(config.min_interval_secs as i64);
+        let allowed = |last: &mut Option<DateTime<Utc>>| {
+            if last.is_some_and(|at| now - at < min_gap && now >= at) {
+                return false;
+            }
+            *last = Some(now);
+            true
+        };
+        let mut found = Vec::new();
+
+        let long: Vec<_> = snap
+            .active_queries
+            .iter()
+            .filter(|q| q.state.as_deref() == Some("active") && q.duration_secs >= danger_secs)
+            .collect();
+        let mut new: Vec<_> = long.iter().filter(|q| !self.long_running.contains(&q.pid)).collect();
+        self.long_running = long.iter().map(|q| q.pid).collect();
+        new.sort_by(|a, b| b.duration_secs.total_cmp(&a.duration_secs));
+        if let Some(q) = new.first() {
+            if config.long_query != NotifyVia::Off && allowed(&mut self.last_long_query) {
+                let mut body = format!(
+                    "PID {} ({}@{}): {}",
+                    q.pid,
+                    q.usename.as_deref().unwrap_or("-"),
+                    q.datname.as_deref().unwrap_or("-"),
+                    truncate(q.query.as_deref().unwrap_or(""), BODY_QUERY_CHARS)
+                );
+                if new.len() > 1 {
+                    body.push_str(&format!(" (+{} more)", new.len() - 1));
+                }
+                found.push(Notification {
+                    event: NotifyEvent::LongQuery,
+                    via: config.long_query,
+                    title: format!("Query running past {danger_secs:.0}s"),
+                    body,
+                });
+            }
+        }
+
+        let blocked: HashSet<i32> = snap.blocking_info.iter().map(|b| b.blocked_pid).collect();
+        let mut new: Vec<_> = snap
+            .blocking_info
+            .iter()
+            .filter(|b| !self.blocked.contains(&b.blocked_pid))
+            .collect();
+        new.sort_by_key(|b| b.blocked_pid);
+        new.dedup_by_key(|b| b.blocked_pid);
+        self.blocked = blocked;
+        if let Some(b) = new.first() {
+            if config.blocking != NotifyVia::Off && allowed(&mut self.last_blocking) {
+                let mut body = format!("PID {} waits on PID {}", b.blocked_pid, b.blocker_pid);
+                if new.len() > 1 {
+                    body.push_str(&format!(" (+{} more blocked)", new.len() - 1));
+                }
+                found.push(Notification {
+                    event: NotifyEvent::Blocking,
+                    via: config.blocking,
+                    title: "Blocking chain".into(),
+                    body,
+                });
+            }
+        }
+        found
+    }
+}
+
+/// Ring the bell and/or show a desktop notification. The desktop side runs
+/// on a thread of its own since it can block on the notification daemon;
+/// failures (no daemon, no session bus) are ignored.
+pub fn deliver(notification: &Notification) {
+    if notification.via.bell() {
+        let mut stdout = std::io::stdout();
+        if stdout.is_terminal() {
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+    }
+    if notification.via.desktop() {
+        let title = format!("pg_glimpse: {}", notification.title);
+        let body = notification.body.clone();
+        std::thread::spawn(move || {
+            let _ = notify_rust::Notification::new()
+                .appname("pg_glimpse")
+                .summary(&title)
+                .body(&body)
+                .show();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActiveQuery, ActivitySummary, BlockingInfo, BufferCacheStats};
+    use chrono::TimeZone;
+
+    fn query(pid: i32, duration_secs: f64) -> ActiveQuery {
+        ActiveQuery {
+            pid,
+            usename: Some("app".into()),
+            datname: Some("shop".into()),
+            state: Some("active".into()),
+            duration_secs,
+            query: Some("SELECT pg_sleep(60)".into()),
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(secs: i64, queries: Vec<ActiveQuery>, blocking: Vec<(i32, i32)>) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap() + chrono::Duration::seconds(secs),
+            active_queries: queries,
+            blocking_info: blocking
+                .into_iter()
+                .map(|(blocked_pid, blocker_pid)| BlockingInfo {
+                    blocked_pid,
+                    blocked_user: None,
+                    blocked_query: None,
+                    blocked_duration_secs: 1.0,
+                    blocker_pid,
+                    blocker_user: None,
+                    blocker_query: None,
+                    blocker_state: None,
+                })
+                .collect(),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn long_queries_notify_once_when_they_cross_the_threshold() {
+        let config = NotificationConfig {
+            long_query: NotifyVia::Bell,
+            ..NotificationConfig::default()
+        };
+        let mut notifier = Notifier::default();
+        assert!(notifier.check(&config, 10.0, &snapshot(0, vec![query(1, 5.0)], vec![])).is_empty());
+
+        let fired = notifier.check(&config, 10.0, &snapshot(2, vec![query(1, 12.0), query(2, 30.0)], vec![]));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].via, NotifyVia::Bell);
+        assert_eq!(fired[0].title, "Query running past 10s");
+        assert_eq!(fired[0].body, "PID 2 (app@shop): SELECT pg_sleep(60) (+1 more)");
+
+        // Still running: nothing new
+        assert!(notifier.check(&config, 10.0, &snapshot(4, vec![query(1, 14.0)], vec![])).is_empty());
+    }
+
+    #[test]
+    fn notifications_are_rate_limited_per_event_type() {
+        let config = NotificationConfig {
+            long_query: NotifyVia::Desktop,
+            blocking: NotifyVia::Both,
+            min_interval_secs: 60,
+        };
+        let mut notifier = Notifier::default();
+        let fired = notifier.check(&config, 10.0, &snapshot(0, vec![query(1, 20.0)], vec![(5, 6)]));
+        assert_eq!(
+            fired.iter().map(|n| n.event).collect::<Vec<_>>(),
+            vec![NotifyEvent::LongQuery, NotifyEvent::Blocking]
+        );
+        assert_eq!(fired[1].body, "PID 5 waits on PID 6");
+
+        // A new long query within the minute is held back; blocking has not changed
+        assert!(notifier.check(&config, 10.0, &snapshot(30, vec![query(2, 20.0)], vec![(5, 6)])).is_empty());
+        let fired = notifier.check(&config, 10.0, &snapshot(90, vec![query(3, 20.0)], vec![(5, 6), (7, 6)]));
+        assert_eq!(
+            fired.iter().map(|n| n.event).collect::<Vec<_>>(),
+            vec![NotifyEvent::LongQuery, NotifyEvent::Blocking]
+        );
+    }
+
+    #[test]
+    fn off_events_and_config_roundtrip() {
+        let mut notifier = Notifier::default();
+        let config = NotificationConfig::default();
+        assert!(notifier.check(&config, 10.0, &snapshot(0, vec![query(1, 20.0)], vec![(5, 6)])).is_empty());
+
+        let parsed: NotificationConfig = toml::from_str("long_query = \"both\"").unwrap();
+        assert_eq!(parsed.long_query, NotifyVia::Both);
+        assert_eq!(parsed.blocking, NotifyVia::Off);
+        assert_eq!(parsed.min_interval_secs, 60);
+        assert_eq!(NotifyVia::Off.prev().next(), NotifyVia::Off);
+    }
+}
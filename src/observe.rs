@@ -0,0 +1,500 @@
+//! Load-test observation mode.
+//!
+//! Watches a database for a fixed window without the UI, then prints (and
+//! optionally exports) a summary: TPS percentiles, peak connections, lock
+//! waits and the statements that added the most execution time.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+
+use crate::app::ConnectionInfo;
+use crate::db::models::{PgSnapshot, StatStatement};
+use crate::runtime::{Engine, HeadlessUi, SnapshotSink};
+
+/// Number of statements listed in the summary.
+const TOP_STATEMENTS: usize = 10;
+
+/// Running aggregates over the snapshots seen during the window.
+#[derive(Debug, Default)]
+pub struct Observation {
+    samples: usize,
+    started_at: Option<DateTime<Utc>>,
+    ended_at: Option<DateTime<Utc>>,
+    tps: Vec<f64>,
+    /// Timestamp and commit+rollback counter of the previous sample
+    prev_xacts: Option<(DateTime<Utc>, i64)>,
+    peak_connections: i64,
+    peak_waiting: i64,
+    /// Backends waiting on locks, integrated over time (session-seconds)
+    lock_wait_secs: f64,
+    longest_block_secs: f64,
+    /// `(calls, total_exec_time)` per queryid when statements were first seen
+    statements_baseline: Option<HashMap<i64, (i64, f64)>>,
+    latest_statements: Vec<StatStatement>,
+}
+
+impl Observation {
+    pub fn push(&mut self, snap: &PgSnapshot) {
+        if let Some(prev) = self.ended_at {
+            let secs = (snap.timestamp - prev).num_milliseconds() as f64 / 1000.0;
+            if secs > 0.0 {
+                self.lock_wait_secs += snap.summary.waiting_count as f64 * secs;
+            }
+        }
+        self.samples += 1;
+        self.started_at.get_or_insert(snap.timestamp);
+        self.ended_at = Some(snap.timestamp);
+
+        if let Some(db) = &snap.db_stats {
+            let xacts = db.xact_commit + db.xact_rollback;
+            if let Some((prev_ts, prev_xacts)) = self.prev_xacts {
+                let secs = (snap.timestamp - prev_ts).num_milliseconds() as f64 / 1000.0;
+                // Guard against counter reset (server restart)
+                if secs > 0.0 && xacts >= prev_xacts {
+                    self.tps.push((xacts - prev_xacts) as f64 / secs);
+                }
+            }
+            self.prev_xacts = Some((snap.timestamp, xacts));
+        }
+
+        self.peak_connections = self.peak_connections.max(snap.summary.total_backends);
+        self.peak_waiting = self.peak_waiting.max(snap.summary.waiting_count);
+        for b in &snap.blocking_info {
+            self.longest_block_secs = self.longest_block_secs.max(b.blocked_duration_secs);
+        }
+
+        // pg_stat_monitor's windowed sums can't be diffed over the run
+        let cumulative: Vec<&StatStatement> = snap.stat_statements.iter().filter(|s| !s.windowed).collect();
+        if !cumulative.is_empty() {
+            self.statements_baseline.get_or_insert_with(|| {
+                cumulative
+                    .iter()
+                    .map(|s| (s.queryid, (s.calls, s.total_exec_time)))
+                    .collect()
+            });
+            self.latest_statements = cumulative.into_iter().cloned().collect();
+        }
+    }
+
+    pub fn summary(&self, target: &str) -> ObserveSummary {
+        let mut tps = self.tps.clone();
+        tps.sort_by(f64::total_cmp);
+
+        let baseline = self.statements_baseline.as_ref();
+        let mut top_statements: Vec<StatementDelta> = self
+            .latest_statements
+            .iter()
+            .map(|s| {
+                let (calls, exec) = baseline
+                    .and_then(|b| b.get(&s.queryid))
+                    .copied()
+                    .unwrap_or((0, 0.0));
+                // A reset during the window restarts the counters from zero
+                if s.calls < calls {
+                    StatementDelta::new(s, s.calls, s.total_exec_time)
+                } else {
+                    StatementDelta::new(s, s.calls - calls, s.total_exec_time - exec)
+                }
+            })
+            .filter(|d| d.exec_time_ms > 0.0)
+            .collect();
+        top_statements.sort_by(|a, b| b.exec_time_ms.total_cmp(&a.exec_time_ms));
+        top_statements.truncate(TOP_STATEMENTS);
+
+        let duration_secs = match (self.started_at, self.ended_at) {
+            (Some(start), Some(end)) => (end - start).num_milliseconds() as f64 / 1000.0,
+            _ => 0.0,
+        };
+
+        ObserveSummary {
+            target: target.to_string(),
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            duration_secs,
+            samples: self.samples,
+            tps_p50: percentile(&tps, 50.0),
+            tps_p95: percentile(&tps, 95.0),
+            tps_max: tps.last().copied(),
+            peak_connections: self.peak_connections,
+            peak_waiting: self.peak_waiting,
+            lock_wait_secs: self.lock_wait_secs,
+            longest_block_secs: self.longest_block_secs,
+            top_statements,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Execution time a statement added during the window.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementDelta {
+    pub queryid: i64,
+    pub query: String,
+    pub calls: i64,
+    pub exec_time_ms: f64,
+}
+
+impl StatementDelta {
+    fn new(stmt: &StatStatement, calls: i64, exec_time_ms: f64) -> Self {
+        Self {
+            queryid: stmt.queryid,
+            query: stmt.query.clone(),
+            calls,
+            exec_time_ms,
+        }
+    }
+}
+
+/// Result of an observation window.
+#[derive(Debug, Clone, Serialize)]
+pub struct ObserveSummary {
+    pub target: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_secs: f64,
+    pub samples: usize,
+    pub tps_p50: Option<f64>,
+    pub tps_p95: Option<f64>,
+    pub tps_max: Option<f64>,
+    pub peak_connections: i64,
+    pub peak_waiting: i64,
+    /// Time backends spent waiting on locks, summed across backends
+    pub lock_wait_secs: f64,
+    pub longest_block_secs: f64,
+    pub top_statements: Vec<StatementDelta>,
+}
+
+impl ObserveSummary {
+    /// Human-readable report for the terminal.
+    pub fn render_text(&self) -> String {
+        format!("{}\n{}", self.render_header("Observation of"), self.render_sections())
+    }
+
+    /// `<title> <target>` followed by the time range covered.
+    pub fn render_header(&self, title: &str) -> String {
+        let fmt_ts = |ts: Option<DateTime<Utc>>| {
+            ts.map_or_else(|| "-".to_string(), |t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{title} {}", self.target);
+        let _ = writeln!(
+            out,
+            "  {} → {} ({:.0}s, {} samples)",
+            fmt_ts(self.started_at),
+            fmt_ts(self.ended_at),
+            self.duration_secs,
+            self.samples
+        );
+        out
+    }
+
+    /// Throughput, connection, lock and top-statement sections.
+    pub fn render_sections(&self) -> String {
+        let fmt_opt = |v: Option<f64>| v.map_or_else(|| "n/a".to_string(), |v| format!("{v:.1}"));
+
+        let mut out = String::new();
+        let _ = writeln!(out, "Throughput");
+        let _ = writeln!(out, "  TPS p50          {}", fmt_opt(self.tps_p50));
+        let _ = writeln!(out, "  TPS p95          {}", fmt_opt(self.tps_p95));
+        let _ = writeln!(out, "  TPS max          {}", fmt_opt(self.tps_max));
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Connections");
+        let _ = writeln!(out, "  Peak backends    {}", self.peak_connections);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Locks");
+        let _ = writeln!(out, "  Peak waiting     {}", self.peak_waiting);
+        let _ = writeln!(out, "  Total lock wait  {:.1}s", self.lock_wait_secs);
+        let _ = writeln!(out, "  Longest block    {:.1}s", self.longest_block_secs);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Top statements by added execution time");
+        if self.top_statements.is_empty() {
+            let _ = writeln!(out, "  (none; is pg_stat_statements installed?)");
+        }
+        for (i, s) in self.top_statements.iter().enumerate() {
+            let query: String = s.query.split_whitespace().collect::<Vec<_>>().join(" ");
+            let query: String = query.chars().take(60).collect();
+            let _ = writeln!(
+                out,
+                "  {:>2}. {:>12.1} ms {:>9} calls  {}",
+                i + 1,
+                s.exec_time_ms,
+                s.calls,
+                query
+            );
+        }
+        out
+    }
+}
+
+/// Feeds engine snapshots into a shared [`Observation`].
+pub struct ObserveSink(Arc<Mutex<Observation>>);
+
+impl ObserveSink {
+    pub fn new(observation: Arc<Mutex<Observation>>) -> Self {
+        Self(observation)
+    }
+}
+
+impl SnapshotSink for ObserveSink {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        if let Ok(mut obs) = self.0.lock() {
+            obs.push(snapshot);
+        }
+        Ok(())
+    }
+}
+
+/// `user@host:port/db` label used in summaries.
+pub fn connection_target(conn: &ConnectionInfo) -> String {
+    format!("{}@{}:{}/{}", conn.user, conn.host, conn.port, conn.dbname)
+}
+
+/// Drive the engine headless for `window` (or until Ctrl-C), then print the
+/// summary and optionally write it as JSON.
+pub async fn run_observe(
+    mut engine: Engine,
+    observation: Arc<Mutex<Observation>>,
+    window: Duration,
+    output: Option<&Path>,
+) -> Result<()> {
+    let target = connection_target(&engine.app.connection);
+    eprintln!(
+        "Observing {target} for {}s (Ctrl-C to stop early)...",
+        window.as_secs()
+    );
+
+    engine.request_snapshot();
+    let mut ui = HeadlessUi;
+    tokio::select! {
+        res = engine.run(&mut ui, None) => res?,
+        () = tokio::time::sleep(window) => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    let summary = observation
+        .lock()
+        .map_err(|_| color_eyre::eyre::eyre!("observation state poisoned"))?
+        .summary(&target);
+    print!("{}", summary.render_text());
+
+    if let Some(path) = output {
+        let json = serde_json::to_string_pretty(&summary)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("could not write summary to {}", path.display()))?;
+        eprintln!("Summary written to {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActivitySummary, BlockingInfo, BufferCacheStats, DatabaseStats};
+    use chrono::TimeZone;
+
+    fn make_snapshot(secs: i64, xacts: i64) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+                + chrono::Duration::seconds(secs),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            db_stats: Some(DatabaseStats {
+                xact_commit: xacts,
+                xact_rollback: 0,
+                blks_read: 0,
+                stats_reset: None,
+                server_start: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn make_statement(queryid: i64, calls: i64, total_exec_time: f64) -> StatStatement {
+        StatStatement {
+            queryid,
+            query: format!("SELECT {queryid}"),
+            calls,
+            total_exec_time,
+            min_exec_time: 0.0,
+            mean_exec_time: 0.0,
+            max_exec_time: 0.0,
+            stddev_exec_time: 0.0,
+            rows: 0,
+            shared_blks_hit: 0,
+            shared_blks_read: 0,
+            shared_blks_dirtied: 0,
+            shared_blks_written: 0,
+            local_blks_hit: 0,
+            local_blks_read: 0,
+            local_blks_dirtied: 0,
+            local_blks_written: 0,
+            temp_blks_read: 0,
+            temp_blks_written: 0,
+            blk_read_time: 0.0,
+            blk_write_time: 0.0,
+            hit_ratio: 1.0,
+            windowed: false,
+        }
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&values, 50.0), Some(10.0));
+        assert_eq!(percentile(&values, 95.0), Some(19.0));
+        assert_eq!(percentile(&values, 100.0), Some(20.0));
+        assert_eq!(percentile(&[7.0], 95.0), Some(7.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn tps_from_transaction_deltas() {
+        let mut obs = Observation::default();
+        // 100, 200, 300 tx/s over 2s steps
+        for (secs, xacts) in [(0, 0), (2, 200), (4, 600), (6, 1200)] {
+            obs.push(&make_snapshot(secs, xacts));
+        }
+        let summary = obs.summary("db");
+        assert_eq!(summary.samples, 4);
+        assert!((summary.duration_secs - 6.0).abs() < f64::EPSILON);
+        assert_eq!(summary.tps_p50, Some(200.0));
+        assert_eq!(summary.tps_p95, Some(300.0));
+        assert_eq!(summary.tps_max, Some(300.0));
+    }
+
+    #[test]
+    fn tps_skips_counter_reset() {
+        let mut obs = Observation::default();
+        obs.push(&make_snapshot(0, 1000));
+        obs.push(&make_snapshot(2, 10));
+        obs.push(&make_snapshot(4, 30));
+        assert_eq!(obs.summary("db").tps_max, Some(10.0));
+    }
+
+    #[test]
+    fn peaks_and_lock_waits() {
+        let mut obs = Observation::default();
+        let mut a = make_snapshot(0, 0);
+        a.summary.total_backends = 10;
+        let mut b = make_snapshot(5, 0);
+        b.summary.total_backends = 40;
+        b.summary.waiting_count = 2;
+        b.blocking_info.push(BlockingInfo {
+            blocked_pid: 1,
+            blocked_user: None,
+            blocked_query: None,
+            blocked_duration_secs: 4.5,
+            blocker_pid: 2,
+            blocker_user: None,
+            blocker_query: None,
+            blocker_state: None,
+        });
+        let c = make_snapshot(10, 0);
+        for snap in [&a, &b, &c] {
+            obs.push(snap);
+        }
+
+        let summary = obs.summary("db");
+        assert_eq!(summary.peak_connections, 40);
+        assert_eq!(summary.peak_waiting, 2);
+        // Two waiters over the 5s leading up to the second sample
+        assert!((summary.lock_wait_secs - 10.0).abs() < f64::EPSILON);
+        assert!((summary.longest_block_secs - 4.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn top_statements_by_added_exec_time() {
+        let mut obs = Observation::default();
+        let mut first = make_snapshot(0, 0);
+        first.stat_statements = vec![make_statement(1, 10, 1000.0), make_statement(2, 5, 50.0)];
+        let mut last = make_snapshot(60, 0);
+        last.stat_statements = vec![
+            make_statement(1, 12, 1100.0), // +100ms
+            make_statement(2, 50, 950.0),  // +900ms
+            make_statement(3, 4, 400.0),   // new, +400ms
+            make_statement(4, 0, 0.0),     // idle
+        ];
+        obs.push(&first);
+        obs.push(&last);
+
+        let top = obs.summary("db").top_statements;
+        let ids: Vec<i64> = top.iter().map(|s| s.queryid).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+        assert_eq!(top[0].calls, 45);
+        assert!((top[0].exec_time_ms - 900.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn top_statements_after_reset_use_current_counters() {
+        let mut obs = Observation::default();
+        let mut first = make_snapshot(0, 0);
+        first.stat_statements = vec![make_statement(1, 100, 5000.0)];
+        let mut last = make_snapshot(60, 0);
+        last.stat_statements = vec![make_statement(1, 3, 30.0)];
+        obs.push(&first);
+        obs.push(&last);
+
+        let top = obs.summary("db").top_statements;
+        assert_eq!(top[0].calls, 3);
+        assert!((top[0].exec_time_ms - 30.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn windowed_statements_are_left_out() {
+        let mut obs = Observation::default();
+        let mut first = make_snapshot(0, 0);
+        first.stat_statements = vec![make_statement(1, 100, 5000.0)];
+        let mut last = make_snapshot(60, 0);
+        last.stat_statements = vec![make_statement(1, 40, 2000.0)];
+        for s in first.stat_statements.iter_mut().chain(&mut last.stat_statements) {
+            s.windowed = true;
+        }
+        obs.push(&first);
+        obs.push(&last);
+        assert!(obs.summary("db").top_statements.is_empty());
+    }
+
+    #[test]
+    fn render_text_lists_sections() {
+        let mut obs = Observation::default();
+        obs.push(&make_snapshot(0, 0));
+        obs.push(&make_snapshot(2, 100));
+        let text = obs.summary("postgres@localhost:5432/app").render_text();
+        assert!(text.contains("Observation of postgres@localhost:5432/app"));
+        assert!(text.contains("TPS p50          50.0"));
+        assert!(text.contains("Peak backends    3"));
+        assert!(text.contains("pg_stat_statements installed"));
+    }
+
+    #[test]
+    fn summary_serializes_to_json() {
+        let mut obs = Observation::default();
+        obs.push(&make_snapshot(0, 0));
+        let json = serde_json::to_value(obs.summary("db")).unwrap();
+        assert_eq!(json["samples"], 1);
+        assert!(json["tps_p50"].is_null());
+        assert!(json["top_statements"].as_array().unwrap().is_empty());
+    }
+}
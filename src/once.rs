@@ -0,0 +1,208 @@
+//! One-shot snapshot mode (`--once`).
+//!
+//! Takes a single snapshot without the UI and prints it, as JSON for
+//! scripts or as a short summary for a person or a cron mail.
+
+use std::fmt::Write as _;
+
+use color_eyre::eyre::{Context, Result};
+use tokio_postgres::Client;
+
+use crate::cli::OnceFormat;
+use crate::db::catalog_cache::CatalogCache;
+use crate::db::models::{PgSnapshot, ServerInfo};
+use crate::db::queries::fetch_snapshot;
+use crate::ui::util::format_bytes;
+
+/// Longest-running queries listed in the summary.
+const TOP_QUERIES: usize = 5;
+
+/// Take one snapshot and print it to stdout in `format`.
+pub async fn run_once(
+    client: &Client,
+    server_info: &ServerInfo,
+    target: &str,
+    format: OnceFormat,
+) -> Result<()> {
+    let mut snapshot = fetch_snapshot(
+        client,
+        &server_info.extensions,
+        &server_info.capabilities,
+        server_info.major_version(),
+        false,
+        true,
+    )
+    .await
+    .context("could not take a snapshot")?;
+    CatalogCache::default().resolve_snapshot(client, &mut snapshot).await;
+    match format {
+        OnceFormat::Json => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+        OnceFormat::Text => print!("{}", render_text(target, server_info, &snapshot)),
+    }
+    Ok(())
+}
+
+/// Connections, cache, locks and the longest-running queries.
+pub fn render_text(target: &str, server_info: &ServerInfo, snap: &PgSnapshot) -> String {
+    let summary = &snap.summary;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Snapshot of {target} at {}",
+        snap.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    // "PostgreSQL 16.1 on x86_64-pc-linux-gnu, compiled by ..." → "PostgreSQL 16.1"
+    let version: String = server_info
+        .version
+        .split_whitespace()
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = writeln!(out, "  {version}, database size {}", format_bytes(snap.db_size));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Connections");
+    let _ = writeln!(
+        out,
+        "  Backends         {} / {}",
+        summary.total_backends, server_info.max_connections
+    );
+    let _ = writeln!(out, "  Active           {}", summary.active_query_count);
+    let _ = writeln!(out, "  Idle in txn      {}", summary.idle_in_transaction_count);
+    let _ = writeln!(out, "  Waiting on locks {}", summary.waiting_count);
+    let oldest = summary
+        .oldest_xact_secs
+        .map_or_else(|| "-".to_string(), |s| format!("{s:.1}s"));
+    let _ = writeln!(out, "  Oldest xact      {oldest}");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Cache");
+    let _ = writeln!(out, "  Hit ratio        {:.1}%", snap.buffer_cache.hit_ratio * 100.0);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Locks");
+    let _ = writeln!(out, "  Held             {}", summary.lock_count);
+    let _ = writeln!(out, "  Blocked backends {}", snap.blocking_info.len());
+    if !snap.replication.is_empty() {
+        let max_lag = snap
+            .replication
+            .iter()
+            .filter_map(|r| r.replay_lag_secs)
+            .fold(None, |max: Option<f64>, lag| Some(max.map_or(lag, |m| m.max(lag))));
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Replication");
+        let _ = writeln!(out, "  Replicas         {}", snap.replication.len());
+        let lag = max_lag.map_or_else(|| "-".to_string(), |s| format!("{s:.3}s"));
+        let _ = writeln!(out, "  Max replay lag   {lag}");
+    }
+    let _ = writeln!(out);
+
+    let mut running: Vec<_> = snap
+        .active_queries
+        .iter()
+        .filter(|q| q.state.as_deref() != Some("idle"))
+        .collect();
+    running.sort_by(|a, b| b.duration_secs.total_cmp(&a.duration_secs));
+    let _ = writeln!(out, "Longest-running queries");
+    if running.is_empty() {
+        let _ = writeln!(out, "  (none)");
+    }
+    for q in running.iter().take(TOP_QUERIES) {
+        let query: String = q
+            .query
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let query: String = query.chars().take(60).collect();
+        let _ = writeln!(
+            out,
+            "  {:>9.1}s  pid {:<7} {:<19} {query}",
+            q.duration_secs,
+            q.pid,
+            q.state.as_deref().unwrap_or("-"),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{
+        ActiveQuery, ActivitySummary, BufferCacheStats, DetectedExtensions,
+    };
+    use chrono::{TimeZone, Utc};
+
+    fn make_query(pid: i32, state: &str, duration_secs: f64, query: &str) -> ActiveQuery {
+        ActiveQuery {
+            pid,
+            usename: Some("app".into()),
+            datname: Some("shop".into()),
+            state: Some(state.into()),
+            duration_secs,
+            query: Some(query.into()),
+            backend_type: Some("client backend".into()),
+            ..Default::default()
+        }
+    }
+
+    fn make_snapshot() -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 30, 0).unwrap(),
+            active_queries: vec![
+                make_query(101, "active", 2.5, "SELECT * FROM orders\n  WHERE id = $1"),
+                make_query(102, "idle", 300.0, "COMMIT"),
+                make_query(103, "idle in transaction", 45.0, "UPDATE stock SET n = n - 1"),
+            ],
+            buffer_cache: BufferCacheStats {
+                blks_hit: 990,
+                blks_read: 10,
+                hit_ratio: 0.99,
+            },
+            summary: ActivitySummary {
+                active_query_count: 1,
+                idle_in_transaction_count: 1,
+                total_backends: 12,
+                lock_count: 7,
+                oldest_xact_secs: Some(45.0),
+                idle_count: 10,
+                ..Default::default()
+            },
+            db_size: 3 * 1024 * 1024 * 1024,
+            ..Default::default()
+        }
+    }
+
+    fn make_server_info() -> ServerInfo {
+        ServerInfo {
+            version: "PostgreSQL 16.1 on x86_64-pc-linux-gnu, compiled by gcc".into(),
+            start_time: Utc::now(),
+            max_connections: 100,
+            extensions: DetectedExtensions::default(),
+            settings: vec![],
+            extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
+        }
+    }
+
+    #[test]
+    fn text_summary_lists_the_longest_running_queries_first() {
+        let text = render_text("app@db1:5432/shop", &make_server_info(), &make_snapshot());
+        assert!(text.starts_with("Snapshot of app@db1:5432/shop at 2024-01-15 12:30:00 UTC\n"));
+        assert!(text.contains("  PostgreSQL 16.1, database size 3.0 GB\n"));
+        assert!(text.contains("  Backends         12 / 100\n"));
+        assert!(text.contains("  Hit ratio        99.0%\n"));
+        assert!(!text.contains("Replication"));
+
+        let queries: Vec<&str> = text
+            .lines()
+            .skip_while(|l| *l != "Longest-running queries")
+            .skip(1)
+            .collect();
+        assert_eq!(queries.len(), 2, "idle backends are left out");
+        assert!(queries[0].contains("pid 103") && queries[0].contains("UPDATE stock"));
+        assert!(queries[1].ends_with("SELECT * FROM orders WHERE id = $1"));
+    }
+
+}
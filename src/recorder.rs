@@ -1,12 +1,14 @@
 use chrono::{DateTime, Utc};
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::db::models::{PgSnapshot, ServerInfo};
+use crate::config::AppConfig;
+use crate::db::models::{PgSetting, PgSnapshot, ServerInfo};
 
 /// Metadata about a recorded session, parsed from the header line.
 #[derive(Debug, Clone)]
@@ -17,7 +19,10 @@ pub struct RecordingInfo {
     pub dbname: String,
     pub recorded_at: DateTime<Utc>,
     pub pg_version: String,
+    /// Total size of every segment
     pub file_size: u64,
+    /// Files the session was rotated into; `path` is the first
+    pub segments: usize,
 }
 
 impl RecordingInfo {
@@ -50,6 +55,124 @@ impl RecordingInfo {
     }
 }
 
+/// Operator action (or server setting change) captured during a live session
+/// and stored in recordings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiEvent {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: UiEventKind,
+    /// Connection that carried out the action, when not the monitored one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+}
+
+impl UiEvent {
+    /// Description for the activity trail, naming the connection used.
+    pub fn describe(&self) -> String {
+        match &self.via {
+            Some(via) => format!("{} (via {via})", self.kind.describe()),
+            None => self.kind.describe(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UiEventKind {
+    PanelSwitch { panel: String },
+    Filter { panel: String, text: String },
+    CancelQuery { pids: Vec<i32> },
+    TerminateBackend { pids: Vec<i32> },
+    ResetStatStatements,
+    DropReplicationSlot { slot: String },
+    /// VACUUM, ANALYZE or REINDEX started from pg_glimpse
+    Maintenance { command: String },
+    /// A `pg_settings` value changed while the session was running
+    SettingChanged { name: String, from: String, to: String },
+    /// What the server logged after a cancel or terminate
+    ServerLog { pid: i32, lines: Vec<String> },
+    /// Note typed with `#` to mark what happened at this time
+    Annotation { text: String },
+    /// Explaining a statement again gave a different plan
+    PlanChanged { statement: String, summary: String },
+}
+
+impl UiEventKind {
+    /// Short human-readable description for the activity trail.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::PanelSwitch { panel } => format!("Switched to {panel}"),
+            Self::Filter { panel, text } if text.is_empty() => format!("Cleared filter on {panel}"),
+            Self::Filter { panel, text } => format!("Filtered {panel} by \"{text}\""),
+            Self::CancelQuery { pids } => format!("Cancelled {}", format_pids(pids)),
+            Self::TerminateBackend { pids } => format!("Terminated {}", format_pids(pids)),
+            Self::ResetStatStatements => "Reset pg_stat_statements".to_string(),
+            Self::DropReplicationSlot { slot } => format!("Dropped replication slot {slot}"),
+            Self::Maintenance { command } => format!("Ran {command}"),
+            Self::SettingChanged { name, from, to } => format!("Setting {name}: {from} → {to}"),
+            Self::ServerLog { pid, lines } => format!("PID {pid} logged: {}", lines.join(" · ")),
+            Self::Annotation { text } => format!("# {text}"),
+            Self::PlanChanged { statement, summary } => format!("Plan changed for {statement}: {summary}"),
+        }
+    }
+
+    /// One `SettingChanged` per setting whose value differs between two
+    /// `pg_settings` reads. Settings present in only one of them are skipped.
+    pub fn setting_changes(old: &[PgSetting], new: &[PgSetting]) -> Vec<Self> {
+        let before: HashMap<&str, &str> = old
+            .iter()
+            .map(|s| (s.name.as_str(), s.setting.as_str()))
+            .collect();
+        new.iter()
+            .filter_map(|s| {
+                let from = *before.get(s.name.as_str())?;
+                (from != s.setting).then(|| Self::SettingChanged {
+                    name: s.name.clone(),
+                    from: from.to_string(),
+                    to: s.setting.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Net setting changes across a list of events: `(name, first value, last
+/// value)` for settings that ended up different from where they started.
+pub fn net_setting_changes(events: &[UiEvent]) -> Vec<(String, String, String)> {
+    let mut net: Vec<(String, String, String)> = Vec::new();
+    for event in events {
+        if let UiEventKind::SettingChanged { name, from, to } = &event.kind {
+            match net.iter_mut().find(|(n, _, _)| n == name) {
+                Some(entry) => entry.2.clone_from(to),
+                None => net.push((name.clone(), from.clone(), to.clone())),
+            }
+        }
+    }
+    net.retain(|(_, from, to)| from != to);
+    net
+}
+
+/// Apply the setting changes in `events` on top of `base`.
+pub fn apply_setting_changes(base: &[PgSetting], events: &[UiEvent]) -> Vec<PgSetting> {
+    let mut settings = base.to_vec();
+    for event in events {
+        if let UiEventKind::SettingChanged { name, to, .. } = &event.kind {
+            if let Some(s) = settings.iter_mut().find(|s| &s.name == name) {
+                s.setting.clone_from(to);
+            }
+        }
+    }
+    settings
+}
+
+fn format_pids(pids: &[i32]) -> String {
+    match pids {
+        [pid] => format!("PID {pid}"),
+        _ => format!("{} backends", pids.len()),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[allow(clippy::large_enum_variant)]
@@ -62,13 +185,126 @@ enum RecordLine {
         user: String,
         server_info: ServerInfo,
         recorded_at: chrono::DateTime<chrono::Utc>,
+        /// Name shared by the segments of a rotated session
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session: Option<String>,
+        /// Position of this file within its session, from 1
+        #[serde(default)]
+        segment: u32,
     },
     #[serde(rename = "snapshot")]
     Snapshot { data: PgSnapshot },
+    #[serde(rename = "event")]
+    Event { data: UiEvent },
+}
+
+/// When a recording rolls over to a new segment file. Zero turns a limit
+/// off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rotation {
+    pub max_bytes: u64,
+    pub max_age: Duration,
+}
+
+impl Rotation {
+    /// From the `recording_rotate_mb` and `recording_rotate_mins` settings.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            max_bytes: config.recording_rotate_mb * 1_048_576,
+            max_age: Duration::from_secs(config.recording_rotate_mins * 60),
+        }
+    }
+
+    fn due(&self, written: u64, age: Duration) -> bool {
+        (self.max_bytes > 0 && written >= self.max_bytes) || (!self.max_age.is_zero() && age >= self.max_age)
+    }
+}
+
+/// How often recordings past the retention age are looked for.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(300);
+
+/// File name of segment `segment` of `session`. The first keeps the plain
+/// name so unrotated recordings look as they always did.
+fn segment_filename(session: &str, segment: u32) -> String {
+    if segment <= 1 {
+        format!("{session}.jsonl")
+    } else {
+        format!("{session}_part{segment}.jsonl")
+    }
+}
+
+/// File name of the first segment of the session that `file_name` is a
+/// segment of, undoing `segment_filename`. Bookmarks know a recording by
+/// it, whichever of its segments was opened.
+pub fn first_segment_name(file_name: &str) -> String {
+    let session = file_name
+        .strip_suffix(".jsonl")
+        .and_then(|stem| stem.rsplit_once("_part"))
+        .filter(|(_, n)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        .filter(|(_, n)| n.parse::<u32>().is_ok_and(|n| n > 1))
+        .map(|(session, _)| session);
+    session.map_or_else(|| file_name.to_string(), |session| segment_filename(session, 1))
+}
+
+/// The header of a recording file: connection, server, session and segment.
+fn read_header(path: &Path) -> Option<RecordLine> {
+    let file = File::open(path).ok()?;
+    let first_line = BufReader::new(file).lines().next()?.ok()?;
+    let header: RecordLine = serde_json::from_str(&first_line).ok()?;
+    matches!(header, RecordLine::Header { .. }).then_some(header)
+}
+
+fn header_session(header: &RecordLine) -> Option<(&str, u32)> {
+    match header {
+        RecordLine::Header { session: Some(session), segment, .. } => Some((session.as_str(), *segment)),
+        _ => None,
+    }
+}
+
+/// Every segment of the session `path` belongs to, in order. A recording
+/// made without rotation is its own only segment.
+pub fn session_segments(path: &Path) -> Vec<PathBuf> {
+    let header = read_header(path);
+    let Some((session, _)) = header.as_ref().and_then(header_session) else {
+        return vec![path.to_path_buf()];
+    };
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![path.to_path_buf()];
+    };
+    let mut segments: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+        .filter_map(|p| {
+            let header = read_header(&p)?;
+            let (s, segment) = header_session(&header)?;
+            (s == session).then_some((segment, p))
+        })
+        .collect();
+    if segments.is_empty() {
+        return vec![path.to_path_buf()];
+    }
+    segments.sort();
+    segments.into_iter().map(|(_, p)| p).collect()
 }
 
 pub struct Recorder {
     writer: BufWriter<File>,
+    dir: PathBuf,
+    session: String,
+    segment: u32,
+    host: String,
+    port: u16,
+    dbname: String,
+    user: String,
+    server_info: ServerInfo,
+    rotation: Rotation,
+    /// Bytes in the current segment
+    written: u64,
+    opened_at: Instant,
+    /// Whether the current segment holds a snapshot yet
+    has_snapshot: bool,
 }
 
 impl Recorder {
@@ -79,44 +315,106 @@ impl Recorder {
         user: &str,
         server_info: &ServerInfo,
         custom_dir: Option<&str>,
+        rotation: Rotation,
     ) -> Result<Self> {
         let dir = Self::recordings_dir(custom_dir);
-        fs::create_dir_all(&dir)?;
-
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("{host}_{port}_{timestamp}.jsonl");
         // Sanitize filename: replace any path-unfriendly chars
-        let filename = filename.replace(['/', '\\'], "_");
-        let path = dir.join(filename);
-
-        let file = File::create(&path)?;
-        let mut writer = BufWriter::new(file);
+        let session = format!("{host}_{port}_{timestamp}").replace(['/', '\\'], "_");
+        Self::open(dir, session, host, port, dbname, user, server_info, rotation)
+    }
 
-        let header = RecordLine::Header {
+    #[allow(clippy::too_many_arguments)]
+    fn open(
+        dir: PathBuf,
+        session: String,
+        host: &str,
+        port: u16,
+        dbname: &str,
+        user: &str,
+        server_info: &ServerInfo,
+        rotation: Rotation,
+    ) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = File::create(dir.join(segment_filename(&session, 1)))?;
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            dir,
+            session,
+            segment: 1,
             host: host.to_string(),
             port,
             dbname: dbname.to_string(),
             user: user.to_string(),
             server_info: server_info.clone(),
+            rotation,
+            written: 0,
+            opened_at: Instant::now(),
+            has_snapshot: false,
+        };
+        recorder.write_header()?;
+        Ok(recorder)
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let header = RecordLine::Header {
+            host: self.host.clone(),
+            port: self.port,
+            dbname: self.dbname.clone(),
+            user: self.user.clone(),
+            server_info: self.server_info.clone(),
             recorded_at: chrono::Utc::now(),
+            session: Some(self.session.clone()),
+            segment: self.segment,
         };
-        serde_json::to_writer(&mut writer, &header)?;
-        writer.write_all(b"\n")?;
-        writer.flush()?;
+        self.write_line(&header)
+    }
+
+    fn write_line(&mut self, line: &RecordLine) -> Result<()> {
+        let mut bytes = serde_json::to_vec(line)?;
+        bytes.push(b'\n');
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
 
-        Ok(Self { writer })
+    /// Close the current segment and continue in the next one.
+    fn rotate(&mut self) -> Result<()> {
+        self.segment += 1;
+        let file = File::create(self.dir.join(segment_filename(&self.session, self.segment)))?;
+        self.writer = BufWriter::new(file);
+        self.written = 0;
+        self.opened_at = Instant::now();
+        self.has_snapshot = false;
+        self.write_header()
+    }
+
+    /// Path of the segment being written.
+    pub fn current_path(&self) -> PathBuf {
+        self.dir.join(segment_filename(&self.session, self.segment))
     }
 
     pub fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        // Rolling over only ahead of a snapshot keeps one in every segment
+        if self.has_snapshot && self.rotation.due(self.written, self.opened_at.elapsed()) {
+            self.rotate()?;
+        }
         let line = RecordLine::Snapshot {
             data: snapshot.clone(),
         };
-        serde_json::to_writer(&mut self.writer, &line)?;
-        self.writer.write_all(b"\n")?;
-        self.writer.flush()?;
+        self.write_line(&line)?;
+        self.has_snapshot = true;
         Ok(())
     }
 
+    pub fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        let line = RecordLine::Event {
+            data: event.clone(),
+        };
+        self.write_line(&line)
+    }
+
     /// Returns the default recordings directory.
     pub fn default_recordings_dir() -> PathBuf {
         dirs::data_local_dir()
@@ -132,30 +430,65 @@ impl Recorder {
             .unwrap_or_else(Self::default_recordings_dir)
     }
 
+    /// Delete recordings past `max_age_secs` now and every few minutes after,
+    /// so sessions that ended while this one runs age out too.
+    pub fn spawn_cleanup(max_age_secs: u64, custom_dir: Option<String>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+            loop {
+                interval.tick().await;
+                let dir = custom_dir.clone();
+                let _ = tokio::task::spawn_blocking(move || Self::cleanup_old(max_age_secs, dir.as_deref())).await;
+            }
+        });
+    }
+
+    /// Delete sessions whose newest segment was last written more than
+    /// `max_age_secs` ago, all their segments together. A session still
+    /// being written is never touched, and its first segment, which
+    /// bookmarks key on, goes only with the rest.
     pub fn cleanup_old(max_age_secs: u64, custom_dir: Option<&str>) {
         let dir = Self::recordings_dir(custom_dir);
         let Ok(entries) = fs::read_dir(&dir) else {
             return;
         };
-        let now = SystemTime::now();
+        // Newest write and files per session; files from before rotation
+        // (or without a readable header) are sessions of their own
+        let mut sessions: HashMap<String, (Option<SystemTime>, Vec<PathBuf>)> = HashMap::new();
         for entry in entries.flatten() {
             let path = entry.path();
             if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
                 continue;
             }
-            if let Ok(meta) = path.metadata() {
-                if let Ok(modified) = meta.modified() {
-                    if let Ok(age) = now.duration_since(modified) {
-                        if age.as_secs() > max_age_secs {
-                            let _ = fs::remove_file(&path);
-                        }
-                    }
+            let key = read_header(&path)
+                .as_ref()
+                .and_then(header_session)
+                .map_or_else(|| path.display().to_string(), |(session, _)| session.to_string());
+            let modified = path.metadata().and_then(|m| m.modified()).ok();
+            let (newest, files) = sessions.entry(key).or_default();
+            // A segment whose age can't be read keeps its session
+            *newest = match (*newest, modified) {
+                (Some(newest), Some(modified)) => Some(newest.max(modified)),
+                _ if files.is_empty() => modified,
+                _ => None,
+            };
+            files.push(path);
+        }
+        let now = SystemTime::now();
+        for (newest, files) in sessions.into_values() {
+            let expired = newest
+                .and_then(|at| now.duration_since(at).ok())
+                .is_some_and(|age| age.as_secs() > max_age_secs);
+            if expired {
+                for path in files {
+                    let _ = fs::remove_file(path);
                 }
             }
         }
     }
 
-    /// List all recordings, sorted by date (newest first).
+    /// List all recordings, sorted by date (newest first). The segments of
+    /// a rotated session are listed once, under the earliest one left.
     /// Parses only the header line of each file for efficiency.
     pub fn list_recordings(custom_dir: Option<&str>) -> Vec<RecordingInfo> {
         let dir = Self::recordings_dir(custom_dir);
@@ -163,7 +496,7 @@ impl Recorder {
             return vec![];
         };
 
-        let mut recordings: Vec<RecordingInfo> = entries
+        let mut files: Vec<(Option<String>, u32, RecordingInfo)> = entries
             .flatten()
             .filter_map(|entry| {
                 let path = entry.path();
@@ -174,21 +507,17 @@ impl Recorder {
                 let meta = path.metadata().ok()?;
                 let file_size = meta.len();
 
-                // Read and parse only the first line (header)
-                let file = File::open(&path).ok()?;
-                let reader = BufReader::new(file);
-                let first_line = reader.lines().next()?.ok()?;
-
-                let header: RecordLine = serde_json::from_str(&first_line).ok()?;
-                match header {
+                match read_header(&path)? {
                     RecordLine::Header {
                         host,
                         port,
                         dbname,
                         recorded_at,
                         server_info,
+                        session,
+                        segment,
                         ..
-                    } => Some(RecordingInfo {
+                    } => Some((session, segment, RecordingInfo {
                         path,
                         host,
                         port,
@@ -196,51 +525,114 @@ impl Recorder {
                         recorded_at,
                         pg_version: server_info.version,
                         file_size,
-                    }),
-                    RecordLine::Snapshot { .. } => None,
+                        segments: 1,
+                    })),
+                    RecordLine::Snapshot { .. } | RecordLine::Event { .. } => None,
                 }
             })
             .collect();
 
+        // Earliest segment first, so it is the one each session keeps
+        files.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+        let mut recordings: Vec<RecordingInfo> = Vec::with_capacity(files.len());
+        let mut sessions: HashMap<String, usize> = HashMap::new();
+        for (session, _, info) in files {
+            let Some(session) = session else {
+                recordings.push(info);
+                continue;
+            };
+            match sessions.get(&session) {
+                Some(&i) => {
+                    recordings[i].file_size += info.file_size;
+                    recordings[i].segments += 1;
+                }
+                None => {
+                    sessions.insert(session, recordings.len());
+                    recordings.push(info);
+                }
+            }
+        }
+
         // Sort by date, newest first
-        recordings.sort_by(|a, b| b.recorded_at.cmp(&a.recorded_at));
+        recordings.sort_by_key(|r| std::cmp::Reverse(r.recorded_at));
         recordings
     }
 
-    /// Delete a recording file.
-    pub fn delete_recording(path: &PathBuf) -> Result<()> {
-        fs::remove_file(path)?;
+    /// Delete a recording, with every segment of its session.
+    pub fn delete_recording(path: &Path) -> Result<()> {
+        for segment in session_segments(path) {
+            fs::remove_file(segment)?;
+        }
         Ok(())
     }
 
-    #[cfg(test)]
-    pub fn new_with_path(
-        path: PathBuf,
+    /// Write `snapshots`, and the `events` recorded among them, to a new
+    /// standalone recording at `path`. `server_info` should carry the
+    /// settings as they stood at the first snapshot.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_excerpt(
+        path: &Path,
         host: &str,
         port: u16,
         dbname: &str,
         user: &str,
         server_info: &ServerInfo,
-    ) -> Result<Self> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let file = File::create(&path)?;
-        let mut writer = BufWriter::new(file);
-
-        let header = RecordLine::Header {
+        snapshots: &[PgSnapshot],
+        events: &[UiEvent],
+    ) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut write = |line: &RecordLine| -> Result<()> {
+            serde_json::to_writer(&mut writer, line)?;
+            writer.write_all(b"\n")?;
+            Ok(())
+        };
+        write(&RecordLine::Header {
             host: host.to_string(),
             port,
             dbname: dbname.to_string(),
             user: user.to_string(),
             server_info: server_info.clone(),
-            recorded_at: chrono::Utc::now(),
-        };
-        serde_json::to_writer(&mut writer, &header)?;
-        writer.write_all(b"\n")?;
+            recorded_at: snapshots.first().map_or_else(chrono::Utc::now, |s| s.timestamp),
+            session: None,
+            segment: 1,
+        })?;
+        // Interleave events by time, as they were recorded
+        let mut events = events.iter().peekable();
+        for snapshot in snapshots {
+            while let Some(event) = events.next_if(|e| e.timestamp <= snapshot.timestamp) {
+                write(&RecordLine::Event { data: event.clone() })?;
+            }
+            write(&RecordLine::Snapshot { data: snapshot.clone() })?;
+        }
+        for event in events {
+            write(&RecordLine::Event { data: event.clone() })?;
+        }
         writer.flush()?;
+        Ok(())
+    }
 
-        Ok(Self { writer })
+    #[cfg(test)]
+    pub fn new_with_path(
+        path: PathBuf,
+        host: &str,
+        port: u16,
+        dbname: &str,
+        user: &str,
+        server_info: &ServerInfo,
+    ) -> Result<Self> {
+        let dir = path.parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        let session = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("recording")
+            .to_string();
+        Self::open(dir, session, host, port, dbname, user, server_info, Rotation::default())
+    }
+
+    /// Set when to roll over to a new segment.
+    #[cfg(test)]
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
     }
 }
 
@@ -265,6 +657,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 1000,
+            segments: 1,
         };
         assert_eq!(info.connection_display(), "localhost:5432/mydb");
     }
@@ -279,6 +672,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 500,
+            segments: 1,
         };
         assert_eq!(info.size_display(), "500B");
     }
@@ -293,6 +687,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 2048,
+            segments: 1,
         };
         assert_eq!(info.size_display(), "2KB");
     }
@@ -307,6 +702,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "PostgreSQL 15.0".into(),
             file_size: 2_097_152,
+            segments: 1,
         };
         assert_eq!(info.size_display(), "2.0MB");
     }
@@ -321,6 +717,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "PostgreSQL 15.3 on x86_64-pc-linux-gnu".into(),
             file_size: 1000,
+            segments: 1,
         };
         assert_eq!(info.pg_version_short(), "PG 15");
     }
@@ -335,6 +732,7 @@ mod tests {
             recorded_at: chrono::Utc::now(),
             pg_version: "Unknown Version".into(),
             file_size: 1000,
+            segments: 1,
         };
         // Should take first 10 chars
         assert_eq!(info.pg_version_short(), "Unknown Ve");
@@ -352,6 +750,9 @@ mod tests {
             extensions: DetectedExtensions::default(),
             settings: vec![],
             extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
         }
     }
 
@@ -361,6 +762,8 @@ mod tests {
             active_queries: vec![],
             wait_events: vec![],
             blocking_info: vec![],
+            locks: vec![],
+            connection_groups: vec![],
             buffer_cache: BufferCacheStats {
                 blks_hit: 9900,
                 blks_read: 100,
@@ -374,12 +777,19 @@ mod tests {
                 lock_count: 0,
                 oldest_xact_secs: None,
                 autovacuum_count: 0,
+                aborted_txn_count: 0,
+                idle_count: 0,
             },
             table_stats: vec![],
             replication: vec![],
             replication_slots: vec![],
             subscriptions: vec![],
             vacuum_progress: vec![],
+            operations: vec![],
+            background_workers: vec![],
+            capped_pids: vec![],
+            wal_dir: None,
+            statement_monitor: None,
             wraparound: vec![],
             indexes: vec![],
             stat_statements: vec![],
@@ -391,6 +801,12 @@ mod tests {
             archiver_stats: None,
             bgwriter_stats: None,
             db_stats: None,
+            server_log: vec![],
+            server_log_error: None,
+            pooler: None,
+            pooler_error: None,
+            relation_stats_read_at: None,
+            io_stats: vec![],
         }
     }
 
@@ -616,6 +1032,17 @@ mod tests {
         assert_eq!(dir.to_string_lossy(), custom);
     }
 
+    #[test]
+    fn first_segment_name_undoes_segment_filename() {
+        assert_eq!(first_segment_name(&segment_filename("db1_5432_x", 12)), "db1_5432_x.jsonl");
+        assert_eq!(first_segment_name("db1_5432_x.jsonl"), "db1_5432_x.jsonl");
+        // Not a segment suffix
+        assert_eq!(first_segment_name("spare_parts.jsonl"), "spare_parts.jsonl");
+        assert_eq!(first_segment_name("db_part1.jsonl"), "db_part1.jsonl");
+        assert_eq!(first_segment_name("db_part+2.jsonl"), "db_part+2.jsonl");
+        assert_eq!(first_segment_name("db_part2.json"), "db_part2.json");
+    }
+
     #[test]
     fn filename_sanitization() {
         // Test that slashes in hostname are replaced
@@ -649,14 +1076,19 @@ mod tests {
             extensions: DetectedExtensions {
                 pg_stat_statements: true,
                 pg_stat_statements_version: Some("1.10".to_string()),
+                pg_stat_monitor: false,
                 pg_stat_kcache: false,
                 pg_wait_sampling: true,
                 pg_buffercache: true,
                 pgstattuple: false,
                 pgstattuple_version: None,
+                hypopg: false,
             },
             settings: vec![],
             extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
         };
 
         // Create a complex snapshot with data in all fields
@@ -674,6 +1106,10 @@ mod tests {
                     duration_secs: 5.5,
                     query: Some("SELECT * FROM large_table".to_string()),
                     backend_type: Some("client backend".to_string()),
+                    xact_start: None,
+                    query_id: None,
+                    application_name: None,
+                    state_change: None,
                 },
                 ActiveQuery {
                     pid: 12346,
@@ -686,6 +1122,10 @@ mod tests {
                     duration_secs: 120.0,
                     query: Some("BEGIN; UPDATE users SET x = 1".to_string()),
                     backend_type: Some("client backend".to_string()),
+                    xact_start: None,
+                    query_id: None,
+                    application_name: None,
+                    state_change: None,
                 },
             ],
             wait_events: vec![WaitEventCount {
@@ -703,6 +1143,8 @@ mod tests {
                 blocker_query: Some("SELECT * FROM t FOR UPDATE".to_string()),
                 blocker_state: Some("idle in transaction".to_string()),
             }],
+            locks: vec![],
+            connection_groups: vec![],
             buffer_cache: BufferCacheStats {
                 blks_hit: 99000,
                 blks_read: 1000,
@@ -716,6 +1158,8 @@ mod tests {
                 lock_count: 10,
                 oldest_xact_secs: Some(300.5),
                 autovacuum_count: 1,
+                aborted_txn_count: 0,
+                idle_count: 0,
             },
             table_stats: vec![TableStat {
                 schemaname: "public".to_string(),
@@ -789,17 +1233,28 @@ mod tests {
                 latest_end_lsn: Some("0/5555550".to_string()),
                 latest_end_time: Some(chrono::Utc::now()),
                 enabled: true,
+                apply_lag_secs: Some(0.4),
+                apply_lag_bytes: Some(5),
+                apply_error_count: Some(0),
+                sync_error_count: Some(0),
+                conflict_count: None,
             }],
             vacuum_progress: vec![VacuumProgress {
                 pid: 7777,
                 datname: Some("testdb".to_string()),
                 table_name: "public.large_table".to_string(),
+                relid: None,
                 phase: "scanning heap".to_string(),
                 heap_blks_total: 10000,
                 heap_blks_vacuumed: 2500,
                 progress_pct: 25.0,
                 num_dead_tuples: 5000,
             }],
+            operations: vec![],
+            background_workers: vec![],
+            capped_pids: vec![],
+            wal_dir: None,
+            statement_monitor: None,
             wraparound: vec![WraparoundInfo {
                 datname: "testdb".to_string(),
                 xid_age: 500_000_000,
@@ -816,6 +1271,7 @@ mod tests {
                 idx_tup_fetch: 48000,
                 index_definition: "CREATE UNIQUE INDEX users_pkey ON public.users USING btree (id)"
                     .to_string(),
+                is_valid: true,
                 bloat_bytes: Some(25000),
                 bloat_pct: Some(5.0),
                 bloat_source: None,
@@ -843,16 +1299,19 @@ mod tests {
                 blk_read_time: 10.5,
                 blk_write_time: 5.2,
                 hit_ratio: 0.99,
+                windowed: false,
             }],
             stat_statements_error: None,
             extensions: DetectedExtensions {
                 pg_stat_statements: true,
                 pg_stat_statements_version: Some("1.10".to_string()),
+                pg_stat_monitor: false,
                 pg_stat_kcache: false,
                 pg_wait_sampling: true,
                 pg_buffercache: true,
                 pgstattuple: false,
                 pgstattuple_version: None,
+                hypopg: false,
             },
             db_size: 5_000_000_000,
             checkpoint_stats: Some(CheckpointStats {
@@ -890,7 +1349,15 @@ mod tests {
                 xact_commit: 500_000,
                 xact_rollback: 100,
                 blks_read: 10000,
+                stats_reset: None,
+                server_start: None,
             }),
+            server_log: vec![],
+            server_log_error: None,
+            pooler: None,
+            pooler_error: None,
+            relation_stats_read_at: None,
+            io_stats: vec![],
         };
 
         // Record the session
@@ -1069,6 +1536,128 @@ mod tests {
         assert!(diff < 1000); // Allow 1ms tolerance for serialization rounding
     }
 
+    #[test]
+    fn roundtrip_ui_events() {
+        use crate::replay::ReplaySession;
+
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("events.jsonl");
+
+        let server_info = make_server_info();
+        let mut recorder =
+            Recorder::new_with_path(path.clone(), "host", 5432, "db", "user", &server_info)
+                .unwrap();
+
+        recorder.record(&make_snapshot()).unwrap();
+        let event = UiEvent {
+            timestamp: chrono::Utc::now(),
+            kind: UiEventKind::CancelQuery { pids: vec![42] },
+            via: None,
+        };
+        recorder.record_event(&event).unwrap();
+        recorder.record(&make_snapshot()).unwrap();
+        drop(recorder);
+
+        let session = ReplaySession::load(&path).unwrap();
+        assert_eq!(session.len(), 2);
+        assert_eq!(session.events, vec![event]);
+    }
+
+    #[test]
+    fn ui_event_kind_describe() {
+        assert_eq!(
+            UiEventKind::PanelSwitch { panel: "Indexes".into() }.describe(),
+            "Switched to Indexes"
+        );
+        assert_eq!(
+            UiEventKind::Filter { panel: "Queries".into(), text: "vacuum".into() }.describe(),
+            "Filtered Queries by \"vacuum\""
+        );
+        assert_eq!(
+            UiEventKind::TerminateBackend { pids: vec![1, 2, 3] }.describe(),
+            "Terminated 3 backends"
+        );
+        assert_eq!(
+            UiEventKind::SettingChanged {
+                name: "enable_seqscan".into(),
+                from: "on".into(),
+                to: "off".into(),
+            }
+            .describe(),
+            "Setting enable_seqscan: on → off"
+        );
+    }
+
+    fn changed(secs: i64, name: &str, from: &str, to: &str) -> UiEvent {
+        UiEvent {
+            timestamp: chrono::DateTime::UNIX_EPOCH + chrono::Duration::seconds(secs),
+            kind: UiEventKind::SettingChanged {
+                name: name.into(),
+                from: from.into(),
+                to: to.into(),
+            },
+            via: None,
+        }
+    }
+
+    #[test]
+    fn setting_changes_reports_changed_values_only() {
+        let old = vec![
+            PgSetting::fixture("enable_seqscan", "on"),
+            PgSetting::fixture("work_mem", "4096"),
+        ];
+        let new = vec![
+            PgSetting::fixture("enable_seqscan", "off"),
+            PgSetting::fixture("work_mem", "4096"),
+            PgSetting::fixture("new_guc", "1"),
+        ];
+        assert_eq!(
+            UiEventKind::setting_changes(&old, &new),
+            vec![UiEventKind::SettingChanged {
+                name: "enable_seqscan".into(),
+                from: "on".into(),
+                to: "off".into(),
+            }]
+        );
+        assert!(UiEventKind::setting_changes(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn net_setting_changes_drops_settings_flipped_back() {
+        let events = vec![
+            changed(1, "enable_seqscan", "on", "off"),
+            UiEvent {
+                timestamp: chrono::DateTime::UNIX_EPOCH,
+                kind: UiEventKind::ResetStatStatements,
+                via: None,
+            },
+            changed(2, "work_mem", "4096", "8192"),
+            changed(3, "enable_seqscan", "off", "on"),
+            changed(4, "work_mem", "8192", "16384"),
+        ];
+        assert_eq!(
+            net_setting_changes(&events),
+            vec![("work_mem".to_string(), "4096".to_string(), "16384".to_string())]
+        );
+    }
+
+    #[test]
+    fn apply_setting_changes_replays_values_in_order() {
+        let base = vec![
+            PgSetting::fixture("enable_seqscan", "on"),
+            PgSetting::fixture("work_mem", "4096"),
+        ];
+        let events = vec![
+            changed(1, "enable_seqscan", "on", "off"),
+            changed(2, "work_mem", "4096", "8192"),
+            changed(3, "work_mem", "8192", "1024"),
+        ];
+        let settings = apply_setting_changes(&base, &events[..2]);
+        assert_eq!(settings[0].setting, "off");
+        assert_eq!(settings[1].setting, "8192");
+        assert_eq!(apply_setting_changes(&base, &events)[1].setting, "1024");
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // list_recordings tests
     // ─────────────────────────────────────────────────────────────────────────────
@@ -1221,6 +1810,67 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Rotation
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn rotation_is_due_at_either_limit() {
+        let off = Rotation::default();
+        assert!(!off.due(u64::MAX, Duration::from_secs(86_400)));
+        let by_size = Rotation { max_bytes: 1000, max_age: Duration::ZERO };
+        assert!(!by_size.due(999, Duration::from_secs(86_400)));
+        assert!(by_size.due(1000, Duration::ZERO));
+        let by_age = Rotation { max_bytes: 0, max_age: Duration::from_secs(60) };
+        assert!(by_age.due(0, Duration::from_secs(60)));
+        assert!(!by_age.due(u64::MAX, Duration::from_secs(59)));
+    }
+
+    #[test]
+    fn rotated_segments_list_and_replay_as_one_session() {
+        use crate::replay::ReplaySession;
+
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("db1_5432_20240115_120000.jsonl");
+        let mut recorder =
+            Recorder::new_with_path(path.clone(), "db1", 5432, "shop", "postgres", &make_server_info()).unwrap();
+        // Every snapshot is far larger than this, so each one starts a segment
+        recorder.set_rotation(Rotation { max_bytes: 1, max_age: Duration::ZERO });
+        for _ in 0..3 {
+            recorder.record(&make_snapshot()).unwrap();
+        }
+        recorder
+            .record_event(&UiEvent {
+                timestamp: chrono::Utc::now(),
+                kind: UiEventKind::Annotation { text: "failover".into() },
+                via: None,
+            })
+            .unwrap();
+        assert_eq!(
+            recorder.current_path(),
+            tmp.path().join("db1_5432_20240115_120000_part3.jsonl")
+        );
+
+        let recordings = Recorder::list_recordings(Some(tmp.path().to_str().unwrap()));
+        assert_eq!(recordings.len(), 1);
+        assert_eq!(recordings[0].path, path);
+        assert_eq!(recordings[0].segments, 3);
+        let on_disk: u64 = session_segments(&path).iter().map(|p| p.metadata().unwrap().len()).sum();
+        assert_eq!(recordings[0].file_size, on_disk);
+
+        // Opening any segment replays the whole session in order
+        let last = tmp.path().join("db1_5432_20240115_120000_part3.jsonl");
+        let session = ReplaySession::load(&last).unwrap();
+        assert_eq!(session.snapshots.len(), 3);
+        assert_eq!(session.events.len(), 1);
+        assert_eq!(session.host, "db1");
+        // ...and its bookmarks are the first segment's
+        assert_eq!(first_segment_name("db1_5432_20240115_120000_part3.jsonl"), "db1_5432_20240115_120000.jsonl");
+
+        Recorder::delete_recording(&path).unwrap();
+        assert!(Recorder::list_recordings(Some(tmp.path().to_str().unwrap())).is_empty());
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // cleanup_old tests with actual function
     // ─────────────────────────────────────────────────────────────────────────────
@@ -1260,4 +1910,30 @@ mod tests {
         Recorder::cleanup_old(3600, Some(tmp.path().to_str().unwrap()));
         assert!(!path.exists());
     }
+
+    #[test]
+    fn cleanup_old_keeps_a_session_while_its_newest_segment_is_recent() {
+        let tmp = TempDir::new().unwrap();
+        let mut recorder =
+            Recorder::new_with_path(tmp.path().join("long.jsonl"), "host", 5432, "db", "user", &make_server_info())
+                .unwrap();
+        recorder.set_rotation(Rotation { max_bytes: 1, max_age: Duration::ZERO });
+        recorder.record(&make_snapshot()).unwrap();
+        recorder.record(&make_snapshot()).unwrap();
+        let first = tmp.path().join("long.jsonl");
+        let second = tmp.path().join("long_part2.jsonl");
+        assert!(second.exists());
+
+        let old_time = std::time::SystemTime::now() - std::time::Duration::from_secs(7200);
+        filetime::set_file_mtime(&first, filetime::FileTime::from_system_time(old_time)).unwrap();
+        let dir = tmp.path().to_str().unwrap();
+        Recorder::cleanup_old(3600, Some(dir));
+        assert!(first.exists(), "the first segment goes only with the session");
+
+        filetime::set_file_mtime(&second, filetime::FileTime::from_system_time(old_time)).unwrap();
+        drop(recorder);
+        Recorder::cleanup_old(3600, Some(dir));
+        assert!(!first.exists());
+        assert!(!second.exists());
+    }
 }
@@ -5,13 +5,19 @@ use crossterm::event::KeyCode;
 use serde::Deserialize;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use ratatui::DefaultTerminal;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use crate::app::{App, AppAction, ViewMode};
+use crate::app::{Annotation, App, AppAction, CompareState, ReplayInterpolation, SeekTarget, ViewMode};
 use crate::config::AppConfig;
 use crate::db::models::{PgSnapshot, ServerInfo};
-use crate::{event, ui};
+use crate::recorder::{apply_setting_changes, first_segment_name, session_segments, Recorder, UiEvent, UiEventKind};
+use crate::baseline::StatementBaselines;
+use crate::bookmarks::{Bookmark, Bookmarks};
+use crate::starred::StarredStatements;
+use crate::{event, server_log, ui};
 
 #[derive(Deserialize)]
 #[serde(tag = "type")]
@@ -27,6 +33,8 @@ enum RecordLine {
     },
     #[serde(rename = "snapshot")]
     Snapshot { data: PgSnapshot },
+    #[serde(rename = "event")]
+    Event { data: UiEvent },
 }
 
 #[derive(Debug)]
@@ -37,6 +45,8 @@ pub struct ReplaySession {
     pub dbname: String,
     pub user: String,
     pub snapshots: Vec<PgSnapshot>,
+    /// Operator actions recorded alongside the snapshots
+    pub events: Vec<UiEvent>,
     pub position: usize,
 }
 
@@ -60,22 +70,26 @@ fn parse_header(
     }
 }
 
-/// Load snapshots from recording file with optional progress callback.
+/// Load snapshots and UI events from one recording file into `snapshots`
+/// and `events`, with optional progress callback.
 fn load_snapshots<F>(
     lines: std::io::Lines<BufReader<File>>,
-    mut progress_callback: Option<F>,
-) -> Result<Vec<PgSnapshot>>
+    snapshots: &mut Vec<PgSnapshot>,
+    events: &mut Vec<UiEvent>,
+    progress_callback: &mut Option<F>,
+) -> Result<()>
 where
     F: FnMut(usize) -> bool,
 {
-    let mut snapshots = Vec::new();
     for line in lines {
         let line = line?;
         if line.trim().is_empty() {
             continue;
         }
         let record: RecordLine = serde_json::from_str(&line)?;
-        if let RecordLine::Snapshot { data } = record {
+        if let RecordLine::Event { data } = record {
+            events.push(data);
+        } else if let RecordLine::Snapshot { data } = record {
             snapshots.push(data);
 
             // Call progress callback if provided
@@ -88,17 +102,7 @@ where
             }
         }
     }
-
-    if snapshots.is_empty() {
-        return Err(eyre!("Recording contains no snapshots"));
-    }
-
-    // Final callback with total count
-    if let Some(mut cb) = progress_callback {
-        cb(snapshots.len());
-    }
-
-    Ok(snapshots)
+    Ok(())
 }
 
 impl ReplaySession {
@@ -109,6 +113,8 @@ impl ReplaySession {
 
     /// Load recording with progress callback (for UI feedback during loading).
     /// Returns header info immediately, then calls callback for each snapshot loaded.
+    /// A rotated session is loaded whole, its segments one after another,
+    /// with the connection and server taken from the first.
     pub fn load_with_progress<F>(
         path: &Path,
         progress_callback: F,
@@ -116,15 +122,34 @@ impl ReplaySession {
     where
         F: FnMut(usize) -> bool, // Returns false to cancel
     {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
+        let mut progress_callback = Some(progress_callback);
+        let mut header = None;
+        let mut snapshots = Vec::new();
+        let mut events = Vec::new();
+        for segment in session_segments(path) {
+            let file = File::open(&segment)?;
+            let reader = BufReader::new(file);
+            let mut lines = reader.lines();
+
+            // Parse header from first line
+            let segment_header = parse_header(&mut lines)?;
+            header.get_or_insert(segment_header);
+
+            // Load snapshots with progress feedback
+            load_snapshots(lines, &mut snapshots, &mut events, &mut progress_callback)?;
+        }
+        let Some((host, port, dbname, user, server_info)) = header else {
+            return Err(eyre!("Recording file is empty"));
+        };
 
-        // Parse header from first line
-        let (host, port, dbname, user, server_info) = parse_header(&mut lines)?;
+        if snapshots.is_empty() {
+            return Err(eyre!("Recording contains no snapshots"));
+        }
 
-        // Load snapshots with progress feedback
-        let snapshots = load_snapshots(lines, Some(progress_callback))?;
+        // Final callback with total count
+        if let Some(mut cb) = progress_callback {
+            cb(snapshots.len());
+        }
 
         Ok(Self {
             server_info,
@@ -133,10 +158,36 @@ impl ReplaySession {
             dbname,
             user,
             snapshots,
+            events,
             position: 0,
         })
     }
 
+    /// Write snapshots `start..=end` to a standalone recording at `path`,
+    /// with the settings as they stood at `start` and the `events` (the
+    /// recording's, oldest first) that fall between.
+    pub fn write_excerpt(&self, events: &[UiEvent], start: usize, end: usize, path: &Path) -> Result<()> {
+        let slice = self
+            .snapshots
+            .get(start..=end)
+            .ok_or_else(|| eyre!("Bookmark is outside the recording"))?;
+        let (from, to) = (slice[0].timestamp, slice[slice.len() - 1].timestamp);
+        let before = events.partition_point(|e| e.timestamp < from);
+        let upto = events.partition_point(|e| e.timestamp <= to);
+        let mut server_info = self.server_info.clone();
+        server_info.settings = apply_setting_changes(&self.server_info.settings, &events[..before]);
+        Recorder::write_excerpt(
+            path,
+            &self.host,
+            self.port,
+            &self.dbname,
+            &self.user,
+            &server_info,
+            slice,
+            &events[before..upto],
+        )
+    }
+
     pub fn current(&self) -> Option<&PgSnapshot> {
         self.snapshots.get(self.position)
     }
@@ -177,9 +228,43 @@ impl ReplaySession {
         }
     }
 
+    /// Move to the first snapshot taken at or after `target`, or the last
+    /// one if the recording ends before it.
+    pub fn seek(&mut self, target: SeekTarget) {
+        let (Some(first), Some(last)) = (self.snapshots.first(), self.snapshots.last()) else {
+            return;
+        };
+        let at = target.instant(first.timestamp, last.timestamp);
+        self.position = self
+            .snapshots
+            .partition_point(|s| s.timestamp < at)
+            .min(self.snapshots.len() - 1);
+    }
+
+    /// Snapshot after the current position, if any.
+    pub fn peek_next(&self) -> Option<&PgSnapshot> {
+        self.snapshots.get(self.position + 1)
+    }
+
     pub fn at_end(&self) -> bool {
         self.position + 1 >= self.snapshots.len()
     }
+
+    /// Index of the snapshot taken at the same offset from the start of this
+    /// recording as `other`'s current snapshot is from the start of its own.
+    /// Clamps to the first and last snapshots outside the overlapping range.
+    pub fn aligned_index(&self, other: &Self) -> usize {
+        let (Some(first), Some(other_first), Some(other_current)) =
+            (self.snapshots.first(), other.snapshots.first(), other.current())
+        else {
+            return 0;
+        };
+        let offset = other_current.timestamp - other_first.timestamp;
+        let target = first.timestamp + offset;
+        self.snapshots
+            .partition_point(|s| s.timestamp <= target)
+            .saturating_sub(1)
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -239,18 +324,52 @@ fn render_loading_screen(
     frame.render_widget(paragraph, chunks[1]);
 }
 
-/// Run the application in replay mode.
-pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
-    use crossterm::event::{poll, read, Event, KeyCode, KeyEventKind};
+/// Run the application in replay mode, optionally comparing against a
+/// second recording.
+pub async fn run_replay(path: &Path, compare: Option<&Path>, config: AppConfig) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let mut events = event::EventHandler::new(Duration::from_millis(10));
+    let result = replay_in_terminal(&mut terminal, &mut events, path, compare, config).await;
+    ratatui::restore();
+    result.map(|_| ())
+}
 
-    let filename = path
-        .file_name()
-        .and_then(|f| f.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+/// Replay a recording on an already initialized terminal.
+///
+/// Used by live mode to switch into replay in-process. Picking another
+/// recording from the recordings browser swaps sessions without leaving
+/// replay mode. Returns the config, including any changes made while
+/// replaying. The `compare` recording, if any, stays loaded across sessions.
+pub async fn replay_in_terminal(
+    terminal: &mut DefaultTerminal,
+    events: &mut event::EventHandler,
+    path: &Path,
+    compare: Option<&Path>,
+    config: AppConfig,
+) -> Result<AppConfig> {
+    let mut path = path.to_path_buf();
+    let mut config = config;
+    loop {
+        let (next_config, next_path) =
+            run_replay_session(terminal, events, &path, compare, config).await?;
+        config = next_config;
+        match next_path {
+            Some(next) => path = next,
+            None => return Ok(config),
+        }
+    }
+}
 
-    // Initialize terminal FIRST so we can show loading progress
-    let mut terminal = ratatui::init();
+/// Replay a single recording. Returns the config and the next recording to
+/// open, if the user picked one from the recordings browser.
+async fn run_replay_session(
+    terminal: &mut DefaultTerminal,
+    events: &mut event::EventHandler,
+    path: &Path,
+    compare: Option<&Path>,
+    config: AppConfig,
+) -> Result<(AppConfig, Option<PathBuf>)> {
+    let filename = file_label(path);
 
     // Loading state
     let mut loading_snapshots = 0usize;
@@ -273,12 +392,10 @@ pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
                 });
 
                 // Check for ESC key to cancel
-                if poll(Duration::from_millis(0)).unwrap_or(false) {
-                    if let Ok(Event::Key(key)) = read() {
-                        if key.kind == KeyEventKind::Press && matches!(key.code, KeyCode::Esc) {
-                            cancelled = true;
-                            return false; // Cancel loading
-                        }
+                while let Some(event::AppEvent::Key(key)) = events.try_next() {
+                    if key.code == KeyCode::Esc {
+                        cancelled = true;
+                        return false; // Cancel loading
                     }
                 }
             }
@@ -288,12 +405,22 @@ pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
     };
 
     if cancelled {
-        ratatui::restore();
-        return Ok(()); // User cancelled, exit gracefully
+        return Ok((config, None)); // User cancelled, exit gracefully
     }
 
     let mut session = session_result?;
 
+    let compare_session = match compare {
+        Some(compare_path) => {
+            let compare_name = file_label(compare_path);
+            terminal.draw(|frame| {
+                render_loading_screen(frame, &compare_name, None);
+            })?;
+            Some((compare_name, ReplaySession::load(compare_path)?))
+        }
+        None => None,
+    };
+
     let mut app = App::new_replay(
         session.host.clone(),
         session.port,
@@ -306,20 +433,30 @@ pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
         session.len(),
     );
 
-    // Feed first snapshot
-    if let Some(snap) = session.current() {
-        app.update(snap.clone());
-        if let Some(ref mut replay) = app.replay {
-            replay.position = 1;
-            replay.playing = true; // Auto-play on open
+    app.starred = StarredStatements::load();
+    app.baselines = StatementBaselines::load(&app.connection.host, app.connection.port, &app.connection.dbname);
+    app.bookmarks = Bookmarks::load();
+
+    if let Some(ref mut replay) = app.replay {
+        replay.events = std::mem::take(&mut session.events);
+        app.annotations = Annotation::from_events(&replay.events);
+        replay.timestamps = session.snapshots.iter().map(|s| s.timestamp).collect();
+        if let Some((ref name, ref other)) = compare_session {
+            replay.compare = Some(CompareState::new(name.clone(), other.len(), 120));
         }
     }
+    let compare_session = compare_session.map(|(_, other)| other);
 
-    let mut events = event::EventHandler::new(Duration::from_millis(10));
+    // Feed first snapshot
+    sync_replay_position(&mut app, &session, compare_session.as_ref());
+    if let Some(ref mut replay) = app.replay {
+        replay.playing = true; // Auto-play on open
+    }
 
     let mut last_advance = Instant::now();
 
     while app.running {
+        update_interpolation(&mut app, &session, last_advance.elapsed());
         terminal.draw(|frame| ui::render(frame, &mut app))?;
 
         // Auto-advance when playing
@@ -329,7 +466,7 @@ pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
             let interval = compute_replay_interval(&session, speed);
             if last_advance.elapsed() >= interval {
                 if session.step_forward() {
-                    sync_replay_position(&mut app, &session);
+                    sync_replay_position(&mut app, &session, compare_session.as_ref());
                 }
                 last_advance = Instant::now();
                 if session.at_end() {
@@ -345,40 +482,165 @@ pub async fn run_replay(path: &Path, config: AppConfig) -> Result<()> {
             biased;
 
             event = events.next() => {
-                if let Some(event::AppEvent::Key(key)) = event {
-                    // Replay-specific keys first
-                    let handled = handle_replay_key(&mut app, &mut session, key.code, &mut last_advance);
-                    if !handled {
-                        app.handle_key(key);
+                match event {
+                    Some(event::AppEvent::Key(key)) => {
+                        // Replay-specific keys first
+                        let handled = handle_replay_key(
+                            &mut app,
+                            &mut session,
+                            compare_session.as_ref(),
+                            key.code,
+                            &mut last_advance,
+                        );
+                        if !handled {
+                            app.handle_key(key);
+                        }
                     }
+                    Some(event::AppEvent::Mouse(mouse)) => app.handle_mouse(mouse),
+                    None => {}
                 }
             }
             () = tokio::time::sleep(Duration::from_millis(10)) => {}
         }
 
-        // Process pending actions (only SaveConfig matters in replay)
-        if matches!(app.feedback.take_action(), Some(AppAction::SaveConfig)) {
-            app.config.save();
+        // Process pending actions (only saving state matters in replay)
+        match app.feedback.take_action() {
+            Some(AppAction::SaveConfig) => app.config.save(),
+            Some(AppAction::SaveStarred) => app.starred.save(),
+            Some(AppAction::SaveBaselines) => app.baselines.save(),
+            Some(AppAction::SaveBookmarks) => app.bookmarks.save(),
+            Some(AppAction::ExportExcerpt) => export_excerpt(&mut app, &session, path),
+            Some(AppAction::SeekReplay(target)) => {
+                session.seek(target);
+                sync_replay_position(&mut app, &session, compare_session.as_ref());
+                last_advance = Instant::now();
+                if session.at_end() {
+                    if let Some(ref mut replay) = app.replay {
+                        replay.playing = false;
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
-    ratatui::restore();
-    Ok(())
+    let next = app.recordings.pending_path.take();
+    Ok((app.config, next))
+}
+
+/// Where the excerpt of `source` from `from` to `to` is written: next to
+/// it, named after the interval, so it shows up in the recordings browser.
+fn excerpt_path(source: &Path, from: DateTime<Utc>, to: DateTime<Utc>) -> PathBuf {
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("recording");
+    let name = format!("{stem}_excerpt_{}-{}.jsonl", from.format("%H%M%S"), to.format("%H%M%S"));
+    source.with_file_name(name)
+}
+
+/// Write the bookmarked interval to a recording of its own.
+fn export_excerpt(app: &mut App, session: &ReplaySession, source: &Path) {
+    let Some(ref replay) = app.replay else {
+        return;
+    };
+    let Some((start, end)) = replay.bookmark else {
+        return;
+    };
+    let (Some(first), Some(last)) = (session.snapshots.get(start), session.snapshots.get(end)) else {
+        return;
+    };
+    let path = excerpt_path(source, first.timestamp, last.timestamp);
+    app.feedback.status_message = Some(match session.write_excerpt(&replay.events, start, end, &path) {
+        Ok(()) => format!("Excerpt of {} snapshots written to {}", end - start + 1, path.display()),
+        Err(e) => format!("Excerpt failed: {e}"),
+    });
+}
+
+/// File name shown in the header and loading screen.
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("unknown")
+        .to_string()
 }
 
-/// Sync app state with current replay session position.
-fn sync_replay_position(app: &mut App, session: &ReplaySession) {
+/// Sync app state with current replay session position, moving the compared
+/// recording to the snapshot at the same relative time.
+fn sync_replay_position(app: &mut App, session: &ReplaySession, compare: Option<&ReplaySession>) {
     if let Some(snap) = session.current() {
         app.update(snap.clone());
+        sync_replay_settings(app, session, snap);
+        sync_replay_log(app, session);
         if let Some(ref mut replay) = app.replay {
             replay.position = session.position + 1;
+            if let (Some(state), Some(other)) = (replay.compare.as_mut(), compare) {
+                let idx = other.aligned_index(session);
+                if let Some(other_snap) = other.snapshots.get(idx) {
+                    state.push(other_snap, idx + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Show settings as they were at `snap`, and annotate changes that happened
+/// since the previous snapshot.
+fn sync_replay_settings(app: &mut App, session: &ReplaySession, snap: &PgSnapshot) {
+    let upto = session.events.partition_point(|e| e.timestamp <= snap.timestamp);
+    let seen = &session.events[..upto];
+    app.server_info.settings = apply_setting_changes(&session.server_info.settings, seen);
+
+    let since = session
+        .position
+        .checked_sub(1)
+        .and_then(|i| session.snapshots.get(i))
+        .map(|prev| prev.timestamp);
+    if let Some(since) = since {
+        let latest = seen
+            .iter()
+            .rev()
+            .take_while(|e| e.timestamp > since)
+            .find(|e| matches!(e.kind, UiEventKind::SettingChanged { .. }));
+        if let Some(event) = latest {
+            app.feedback.status_message = Some(event.describe());
         }
     }
 }
 
+/// Fill the Logs panel with the last lines recorded up to the current
+/// snapshot, so it reads the same after a seek as after stepping there.
+fn sync_replay_log(app: &mut App, session: &ReplaySession) {
+    let upto = (session.position + 1).min(session.snapshots.len());
+    let mut lines: Vec<_> = session.snapshots[..upto]
+        .iter()
+        .rev()
+        .flat_map(|snap| snap.server_log.iter().rev())
+        .take(server_log::PANEL_LINES)
+        .cloned()
+        .collect();
+    lines.reverse();
+    app.server_log = lines;
+}
+
+/// Refresh the blended graph values for the time elapsed since the last step.
+/// Cleared when paused, at the end, or when interpolation is disabled.
+fn update_interpolation(app: &mut App, session: &ReplaySession, elapsed: Duration) {
+    let enabled = app.config.replay_interpolation;
+    let Some(ref mut replay) = app.replay else {
+        return;
+    };
+    replay.interpolation = match (session.current(), session.peek_next()) {
+        (Some(current), Some(next)) if enabled && replay.playing => {
+            let interval = compute_replay_interval(session, replay.speed);
+            let t = elapsed.as_secs_f64() / interval.as_secs_f64();
+            Some(ReplayInterpolation::between(current, next, t))
+        }
+        _ => None,
+    };
+}
+
 fn handle_replay_key(
     app: &mut App,
     session: &mut ReplaySession,
+    compare: Option<&ReplaySession>,
     code: KeyCode,
     last_advance: &mut Instant,
 ) -> bool {
@@ -396,7 +658,7 @@ fn handle_replay_key(
             if app.view_mode == ViewMode::Normal =>
         {
             if session.step_forward() {
-                sync_replay_position(app, session);
+                sync_replay_position(app, session, compare);
             }
             true
         }
@@ -404,7 +666,7 @@ fn handle_replay_key(
             if app.view_mode == ViewMode::Normal =>
         {
             if session.step_back() {
-                sync_replay_position(app, session);
+                sync_replay_position(app, session, compare);
             }
             true
         }
@@ -418,12 +680,52 @@ fn handle_replay_key(
         }
         KeyCode::Char('g') if app.view_mode == ViewMode::Normal => {
             session.jump_start();
-            sync_replay_position(app, session);
+            sync_replay_position(app, session, compare);
+            true
+        }
+        KeyCode::Char('[') if app.view_mode == ViewMode::Normal => {
+            replay.mark_start = Some(session.position);
+            if let Some(snap) = session.current() {
+                app.feedback.status_message = Some(format!(
+                    "Bookmark starts at {} · ] to end it",
+                    snap.timestamp.format("%H:%M:%S")
+                ));
+            }
+            true
+        }
+        KeyCode::Char(']') if app.view_mode == ViewMode::Normal => {
+            let Some(mark) = replay.mark_start.take() else {
+                app.feedback.status_message = Some("Press [ to mark where the bookmark starts".into());
+                return true;
+            };
+            let (start, end) = (mark.min(session.position), mark.max(session.position));
+            let (Some(first), Some(last)) = (session.snapshots.get(start), session.snapshots.get(end)) else {
+                return true;
+            };
+            replay.bookmark = Some((start, end));
+            let bookmark = Bookmark {
+                recording: first_segment_name(&replay.filename),
+                start: first.timestamp,
+                end: last.timestamp,
+            };
+            app.feedback.status_message = Some(format!(
+                "Bookmarked {}–{} · E exports it",
+                first.timestamp.format("%H:%M:%S"),
+                last.timestamp.format("%H:%M:%S")
+            ));
+            if app.bookmarks.add(bookmark) {
+                app.feedback.pending_action = Some(AppAction::SaveBookmarks);
+            }
+            true
+        }
+        // Without a bookmark, E opens the Extensions panel as usual
+        KeyCode::Char('E') if app.view_mode == ViewMode::Normal && replay.bookmark.is_some() => {
+            app.feedback.pending_action = Some(AppAction::ExportExcerpt);
             true
         }
         KeyCode::Char('G') if app.view_mode == ViewMode::Normal => {
             session.jump_end();
-            sync_replay_position(app, session);
+            sync_replay_position(app, session, compare);
             if let Some(ref mut replay) = app.replay {
                 replay.playing = false;
             }
@@ -879,6 +1181,357 @@ mod tests {
         assert!((prev_speed(0.25) - 0.25).abs() < 0.01); // Min stays at min
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Interpolation tests
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Two snapshots 10s apart going from 10 to 30 backends
+    fn make_interpolation_session() -> ReplaySession {
+        let header = make_header_json("localhost", 5432, "testdb", "testuser");
+        let snap1 = make_snapshot_json(10);
+        let snap2 = make_snapshot_json(30);
+        let file = create_recording_file(&[&header, &snap1, &snap2]);
+        let mut session = ReplaySession::load(file.path()).unwrap();
+        session.snapshots[1].timestamp += chrono::Duration::seconds(10);
+        session
+    }
+
+    fn make_replay_app(session: &ReplaySession) -> App {
+        let mut app = App::new_replay(
+            session.host.clone(),
+            session.port,
+            session.dbname.clone(),
+            session.user.clone(),
+            120,
+            AppConfig::default(),
+            session.server_info.clone(),
+            "test.jsonl".into(),
+            session.len(),
+        );
+        app.update(session.current().unwrap().clone());
+        app
+    }
+
+    #[test]
+    fn interpolation_blends_toward_next_snapshot() {
+        let session = make_interpolation_session();
+        let current = session.current().unwrap();
+        let next = session.peek_next().unwrap();
+
+        let start = ReplayInterpolation::between(current, next, 0.0);
+        assert_eq!(start.connections, 10);
+        assert_eq!(start.timestamp, current.timestamp);
+
+        let half = ReplayInterpolation::between(current, next, 0.5);
+        assert_eq!(half.connections, 20);
+        assert_eq!(half.hit_ratio, 990);
+        assert_eq!(half.timestamp, current.timestamp + chrono::Duration::seconds(5));
+
+        // Overshoot is clamped to the next snapshot
+        let past = ReplayInterpolation::between(current, next, 3.0);
+        assert_eq!(past.connections, 30);
+        assert_eq!(past.timestamp, next.timestamp);
+    }
+
+    #[test]
+    fn update_interpolation_only_while_playing() {
+        let session = make_interpolation_session();
+        let mut app = make_replay_app(&session);
+
+        update_interpolation(&mut app, &session, Duration::from_secs(5));
+        assert!(app.replay.as_ref().unwrap().interpolation.is_none());
+
+        app.replay.as_mut().unwrap().playing = true;
+        update_interpolation(&mut app, &session, Duration::from_secs(5));
+        let interp = app.replay.as_ref().unwrap().interpolation.unwrap();
+        assert_eq!(interp.connections, 20);
+    }
+
+    #[test]
+    fn update_interpolation_respects_speed_and_config() {
+        let session = make_interpolation_session();
+        let mut app = make_replay_app(&session);
+        {
+            let replay = app.replay.as_mut().unwrap();
+            replay.playing = true;
+            replay.speed = 4.0;
+        }
+
+        // 10s gap at 4x is a 2.5s step, so 1.25s is halfway
+        update_interpolation(&mut app, &session, Duration::from_millis(1250));
+        assert_eq!(app.replay.as_ref().unwrap().interpolation.unwrap().connections, 20);
+
+        app.config.replay_interpolation = false;
+        update_interpolation(&mut app, &session, Duration::from_millis(1250));
+        assert!(app.replay.as_ref().unwrap().interpolation.is_none());
+    }
+
+    #[test]
+    fn update_interpolation_clears_at_end() {
+        let mut session = make_interpolation_session();
+        let mut app = make_replay_app(&session);
+        app.replay.as_mut().unwrap().playing = true;
+        update_interpolation(&mut app, &session, Duration::from_secs(1));
+        assert!(app.replay.as_ref().unwrap().interpolation.is_some());
+
+        session.step_forward();
+        update_interpolation(&mut app, &session, Duration::from_secs(1));
+        assert!(app.replay.as_ref().unwrap().interpolation.is_none());
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Compare tests
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Session whose snapshots are `interval_secs` apart, starting at
+    /// an arbitrary wall-clock time.
+    fn make_timed_session(count: i64, interval_secs: i64, start_hour: u32) -> ReplaySession {
+        use chrono::TimeZone;
+        let header = make_header_json("localhost", 5432, "testdb", "testuser");
+        let snaps: Vec<String> = (0..count).map(make_snapshot_json).collect();
+        let mut lines: Vec<&str> = vec![&header];
+        lines.extend(snaps.iter().map(String::as_str));
+        let file = create_recording_file(&lines);
+        let mut session = ReplaySession::load(file.path()).unwrap();
+        let start = chrono::Utc.with_ymd_and_hms(2024, 1, 1, start_hour, 0, 0).unwrap();
+        for (i, snap) in session.snapshots.iter_mut().enumerate() {
+            snap.timestamp = start + chrono::Duration::seconds(i as i64 * interval_secs);
+        }
+        session
+    }
+
+    #[test]
+    fn aligned_index_uses_relative_time() {
+        // Primary samples every 2s, compared recording every 5s, hours apart
+        let mut primary = make_timed_session(20, 2, 9);
+        let other = make_timed_session(10, 5, 15);
+
+        assert_eq!(other.aligned_index(&primary), 0);
+        primary.position = 5; // 10s in
+        assert_eq!(other.aligned_index(&primary), 2);
+        primary.position = 7; // 14s in
+        assert_eq!(other.aligned_index(&primary), 2);
+        primary.position = 19; // 38s in
+        assert_eq!(other.aligned_index(&primary), 7);
+    }
+
+    #[test]
+    fn seek_lands_on_the_first_snapshot_at_or_after_the_target() {
+        // 09:00:00 to 09:00:38, every 2s
+        let mut session = make_timed_session(20, 2, 9);
+        session.seek(SeekTarget::parse("09:00:07").unwrap());
+        assert_eq!(session.position, 4);
+        session.seek(SeekTarget::parse("50%").unwrap());
+        assert_eq!(session.position, 10);
+        session.seek(SeekTarget::parse("0%").unwrap());
+        assert_eq!(session.position, 0);
+        // Past the end of the recording
+        session.seek(SeekTarget::parse("09:30").unwrap());
+        assert_eq!(session.position, 19);
+        // Earlier in the day means tomorrow, which is also past the end
+        session.jump_start();
+        session.seek(SeekTarget::parse("08:59").unwrap());
+        assert!(session.at_end());
+    }
+
+    #[test]
+    fn aligned_index_clamps_to_last_snapshot() {
+        let mut primary = make_timed_session(20, 10, 9);
+        let other = make_timed_session(3, 10, 15);
+        primary.position = 15;
+        assert_eq!(other.aligned_index(&primary), 2);
+    }
+
+    #[test]
+    fn sync_pushes_aligned_compare_snapshot() {
+        let mut primary = make_timed_session(5, 2, 9);
+        let other = make_timed_session(5, 4, 15);
+        let mut app = make_replay_app(&primary);
+        app.replay.as_mut().unwrap().compare =
+            Some(CompareState::new("other.jsonl".into(), other.len(), 120));
+
+        primary.position = 4; // 8s in
+        sync_replay_position(&mut app, &primary, Some(&other));
+
+        let compare = app.replay.as_ref().unwrap().compare.as_ref().unwrap();
+        assert_eq!(compare.position, 3);
+        // make_snapshot_json uses the index as the backend count
+        assert_eq!(compare.metrics.connections.last(), Some(2));
+        assert_eq!(app.replay.as_ref().unwrap().position, 5);
+    }
+
+    #[test]
+    fn sync_applies_and_annotates_setting_changes() {
+        use crate::db::models::PgSetting;
+
+        let mut session = make_timed_session(3, 10, 9);
+        session.server_info.settings = vec![PgSetting {
+            name: "enable_seqscan".into(),
+            setting: "on".into(),
+            unit: None,
+            category: "Query Tuning".into(),
+            short_desc: None,
+            context: "user".into(),
+            source: "default".into(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        }];
+        // Flipped between the first and second snapshot
+        session.events = vec![UiEvent {
+            timestamp: session.snapshots[0].timestamp + chrono::Duration::seconds(5),
+            kind: UiEventKind::SettingChanged {
+                name: "enable_seqscan".into(),
+                from: "on".into(),
+                to: "off".into(),
+            },
+            via: None,
+        }];
+        let mut app = make_replay_app(&session);
+
+        sync_replay_position(&mut app, &session, None);
+        assert_eq!(app.server_info.settings[0].setting, "on");
+        assert!(app.feedback.status_message.is_none());
+
+        session.position = 1;
+        sync_replay_position(&mut app, &session, None);
+        assert_eq!(app.server_info.settings[0].setting, "off");
+        assert_eq!(
+            app.feedback.status_message.as_deref(),
+            Some("Setting enable_seqscan: on → off")
+        );
+
+        // Stepping back restores the value from before the change
+        app.feedback.status_message = None;
+        session.position = 0;
+        sync_replay_position(&mut app, &session, None);
+        assert_eq!(app.server_info.settings[0].setting, "on");
+        session.position = 2;
+        sync_replay_position(&mut app, &session, None);
+        assert!(app.feedback.status_message.is_none());
+    }
+
+    #[test]
+    fn sync_rebuilds_the_log_panel_up_to_the_position() {
+        use crate::db::models::{LogLevel, LogLine};
+
+        let mut session = make_timed_session(4, 10, 9);
+        for (i, snap) in session.snapshots.iter_mut().enumerate() {
+            snap.server_log = vec![LogLine {
+                prefix: format!("[{i}]"),
+                level: LogLevel::Log,
+                message: format!("line {i}"),
+            }];
+        }
+        let mut app = make_replay_app(&session);
+
+        session.position = 2;
+        sync_replay_position(&mut app, &session, None);
+        let messages: Vec<_> = app.server_log.iter().map(|l| l.message.as_str()).collect();
+        assert_eq!(messages, vec!["line 0", "line 1", "line 2"]);
+
+        // Seeking back drops what was logged later
+        session.position = 0;
+        sync_replay_position(&mut app, &session, None);
+        assert_eq!(app.server_log.len(), 1);
+    }
+
+    #[test]
+    fn brackets_bookmark_an_interval_and_e_exports_it() {
+        let mut session = make_timed_session(10, 10, 9);
+        let mut app = make_replay_app(&session);
+        let mut last_advance = Instant::now();
+        let mut press = |app: &mut App, session: &mut ReplaySession, c| {
+            handle_replay_key(app, session, None, KeyCode::Char(c), &mut last_advance)
+        };
+
+        // Without a bookmark E is left to the Extensions panel
+        assert!(!press(&mut app, &mut session, 'E'));
+
+        // Marking backwards still bookmarks the earlier snapshot first
+        session.position = 6;
+        assert!(press(&mut app, &mut session, '['));
+        session.position = 2;
+        assert!(press(&mut app, &mut session, ']'));
+        assert_eq!(app.replay.as_ref().unwrap().bookmark, Some((2, 6)));
+        assert_eq!(app.feedback.status_message.as_deref(), Some("Bookmarked 09:00:20–09:01:00 · E exports it"));
+        assert!(matches!(app.feedback.take_action(), Some(AppAction::SaveBookmarks)));
+        let marks = app.bookmarks.for_recording("test.jsonl");
+        assert_eq!(marks.len(), 1);
+        assert!((marks[0].duration_secs() - 40.0).abs() < f64::EPSILON);
+
+        assert!(press(&mut app, &mut session, 'E'));
+        assert!(matches!(app.feedback.take_action(), Some(AppAction::ExportExcerpt)));
+
+        // Replaying from a later segment still bookmarks the session
+        app.replay.as_mut().unwrap().filename = "test_part3.jsonl".into();
+        assert!(press(&mut app, &mut session, '['));
+        session.position = 8;
+        assert!(press(&mut app, &mut session, ']'));
+        assert_eq!(app.bookmarks.for_recording("test.jsonl").len(), 2);
+    }
+
+    #[test]
+    fn fingerprint_grouping_is_not_shadowed_by_jump_to_end() {
+        let mut session = make_timed_session(10, 10, 9);
+        let mut app = make_replay_app(&session);
+        app.bottom_panel = crate::app::BottomPanel::Queries;
+        let mut last_advance = Instant::now();
+
+        // Replay leaves f to the Queries panel
+        assert!(!handle_replay_key(&mut app, &mut session, None, KeyCode::Char('f'), &mut last_advance));
+        app.handle_key(crossterm::event::KeyEvent::new(KeyCode::Char('f'), crossterm::event::KeyModifiers::NONE));
+        assert!(app.group_by_fingerprint);
+
+        assert!(handle_replay_key(&mut app, &mut session, None, KeyCode::Char('G'), &mut last_advance));
+        assert_eq!(session.position, 9);
+        assert!(app.group_by_fingerprint);
+    }
+
+    #[test]
+    fn excerpt_holds_the_interval_with_settings_as_of_its_start() {
+        use crate::db::models::PgSetting;
+
+        let mut session = make_timed_session(10, 10, 9);
+        session.server_info.settings = vec![PgSetting {
+            name: "work_mem".into(),
+            setting: "4096".into(),
+            unit: Some("kB".into()),
+            category: "Resource Usage".into(),
+            short_desc: None,
+            context: "user".into(),
+            source: "default".into(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        }];
+        let change = |secs, to: &str| UiEvent {
+            timestamp: session.snapshots[0].timestamp + chrono::Duration::seconds(secs),
+            kind: UiEventKind::SettingChanged {
+                name: "work_mem".into(),
+                from: "4096".into(),
+                to: to.into(),
+            },
+            via: None,
+        };
+        // One change before the excerpt, one inside it, one after
+        let events = vec![change(15, "8192"), change(35, "16384"), change(85, "4096")];
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("incident.jsonl");
+        let path = excerpt_path(&source, session.snapshots[2].timestamp, session.snapshots[5].timestamp);
+        assert_eq!(path.file_name().unwrap(), "incident_excerpt_090020-090050.jsonl");
+        session.write_excerpt(&events, 2, 5, &path).unwrap();
+
+        let excerpt = ReplaySession::load(&path).unwrap();
+        assert_eq!(excerpt.len(), 4);
+        assert_eq!(excerpt.snapshots[0].timestamp, session.snapshots[2].timestamp);
+        assert_eq!(excerpt.host, "localhost");
+        assert_eq!(excerpt.server_info.settings[0].setting, "8192");
+        assert_eq!(excerpt.events.len(), 1);
+        assert_eq!(excerpt.events[0].timestamp, events[1].timestamp);
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Fuzz tests for JSONL parsing robustness
     // ─────────────────────────────────────────────────────────────────────────────
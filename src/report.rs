@@ -0,0 +1,505 @@
+//! Static HTML report of a recording (`pg_glimpse report`).
+//!
+//! Replays the recording through the same metrics history and observation
+//! summary the UI and `--observe` use, then writes one self-contained page:
+//! inline SVG charts of connections, TPS, cache hit, average duration and
+//! replication lag, the statements that added the most execution time,
+//! blocking incidents and the operator's annotations and actions.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{bail, Context, Result};
+
+use crate::app::{MetricsHistory, RateTracker};
+use crate::db::models::PgSnapshot;
+use crate::observe::{Observation, ObserveSummary};
+use crate::recorder::{UiEvent, UiEventKind};
+use crate::replay::ReplaySession;
+
+const CHART_WIDTH: f64 = 560.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+/// One metric over the recording, by snapshot time.
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub title: &'static str,
+    pub unit: &'static str,
+    /// CSS color of the line
+    pub color: &'static str,
+    pub points: Vec<(DateTime<Utc>, f64)>,
+}
+
+/// A run of consecutive snapshots with at least one blocked backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockingIncident {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Most backends blocked at once
+    pub peak_blocked: usize,
+    pub longest_wait_secs: f64,
+    /// Backends holding the locks, in order of first appearance
+    pub blockers: Vec<i32>,
+}
+
+impl BlockingIncident {
+    /// Group `snapshots` (oldest first) into incidents.
+    pub fn collect(snapshots: &[PgSnapshot]) -> Vec<Self> {
+        let mut incidents: Vec<Self> = Vec::new();
+        let mut open = false;
+        for snap in snapshots {
+            if snap.blocking_info.is_empty() {
+                open = false;
+                continue;
+            }
+            if !open {
+                incidents.push(Self {
+                    started_at: snap.timestamp,
+                    ended_at: snap.timestamp,
+                    peak_blocked: 0,
+                    longest_wait_secs: 0.0,
+                    blockers: Vec::new(),
+                });
+                open = true;
+            }
+            let Some(incident) = incidents.last_mut() else {
+                continue;
+            };
+            incident.ended_at = snap.timestamp;
+            incident.peak_blocked = incident.peak_blocked.max(snap.blocking_info.len());
+            for b in &snap.blocking_info {
+                incident.longest_wait_secs = incident.longest_wait_secs.max(b.blocked_duration_secs);
+                if !incident.blockers.contains(&b.blocker_pid) {
+                    incident.blockers.push(b.blocker_pid);
+                }
+            }
+        }
+        incidents
+    }
+}
+
+/// Everything the page shows, worked out from a recording.
+#[derive(Debug)]
+pub struct Report {
+    pub pg_version: String,
+    pub summary: ObserveSummary,
+    pub charts: Vec<Chart>,
+    pub incidents: Vec<BlockingIncident>,
+    /// Annotations and actions that changed the server
+    pub events: Vec<UiEvent>,
+}
+
+impl Report {
+    pub fn build(session: &ReplaySession) -> Self {
+        let target = format!("{}@{}:{}/{}", session.user, session.host, session.port, session.dbname);
+        let mut observation = Observation::default();
+        let mut metrics = MetricsHistory::new(session.snapshots.len().max(1));
+        let mut rates = RateTracker::default();
+        let mut lag = Vec::new();
+        for snap in &session.snapshots {
+            observation.push(snap);
+            metrics.push_snapshot_metrics(snap);
+            metrics.push_rates(&rates.interval(snap));
+            let worst = snap
+                .replication
+                .iter()
+                .filter_map(|r| r.replay_lag_secs)
+                .fold(None, |acc: Option<f64>, l| Some(acc.map_or(l, |a| a.max(l))));
+            if let Some(worst) = worst {
+                lag.push((snap.timestamp, worst));
+            }
+        }
+
+        let scaled = |points: Vec<(DateTime<Utc>, u64)>, div: f64| {
+            points.into_iter().map(|(t, v)| (t, v as f64 / div)).collect()
+        };
+        let mut charts = vec![
+            Chart {
+                title: "Connections",
+                unit: "backends",
+                color: "#61afef",
+                points: scaled(metrics.connections.timed(), 1.0),
+            },
+            Chart {
+                title: "Transactions / s",
+                unit: "tps",
+                color: "#c678dd",
+                points: scaled(metrics.tps.timed(), 1.0),
+            },
+            Chart {
+                title: "Cache Hit",
+                unit: "%",
+                color: "#98c379",
+                points: scaled(metrics.hit_ratio.timed(), 10.0),
+            },
+            Chart {
+                title: "Avg Duration",
+                unit: "ms",
+                color: "#e5c07b",
+                points: scaled(metrics.avg_query_time.timed(), 1.0),
+            },
+        ];
+        if !lag.is_empty() {
+            charts.push(Chart {
+                title: "Replication Lag",
+                unit: "s",
+                color: "#e06c75",
+                points: lag,
+            });
+        }
+
+        let events = session
+            .events
+            .iter()
+            .filter(|e| {
+                !matches!(
+                    e.kind,
+                    UiEventKind::PanelSwitch { .. } | UiEventKind::Filter { .. }
+                )
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            pg_version: session.server_info.version.clone(),
+            summary: observation.summary(&target),
+            charts,
+            incidents: BlockingIncident::collect(&session.snapshots),
+            events,
+        }
+    }
+
+    pub fn render_html(&self) -> String {
+        let s = &self.summary;
+        let fmt_ts = |ts: Option<DateTime<Utc>>| {
+            ts.map_or_else(|| "-".to_string(), |t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        };
+        let fmt_opt = |v: Option<f64>| v.map_or_else(|| "n/a".to_string(), |v| format!("{v:.1}"));
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>pg_glimpse report · {target}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n",
+            target = escape(&s.target)
+        );
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&s.target));
+        let _ = writeln!(
+            out,
+            "<p class=\"dim\">{} → {} · {:.0}s · {} snapshots · {}</p>",
+            fmt_ts(s.started_at),
+            fmt_ts(s.ended_at),
+            s.duration_secs,
+            s.samples,
+            escape(&self.pg_version)
+        );
+
+        out.push_str("<section class=\"cards\">\n");
+        for (label, value) in [
+            ("TPS p50", fmt_opt(s.tps_p50)),
+            ("TPS p95", fmt_opt(s.tps_p95)),
+            ("TPS max", fmt_opt(s.tps_max)),
+            ("Peak backends", s.peak_connections.to_string()),
+            ("Peak lock waits", s.peak_waiting.to_string()),
+            ("Total lock wait", format!("{:.1}s", s.lock_wait_secs)),
+            ("Longest block", format!("{:.1}s", s.longest_block_secs)),
+        ] {
+            let _ = writeln!(out, "<div><span>{label}</span><b>{value}</b></div>");
+        }
+        out.push_str("</section>\n");
+
+        out.push_str("<section class=\"charts\">\n");
+        for chart in &self.charts {
+            out.push_str(&svg_chart(chart, s.started_at, s.ended_at));
+        }
+        out.push_str("</section>\n");
+
+        out.push_str("<h2>Top statements by added execution time</h2>\n");
+        if s.top_statements.is_empty() {
+            out.push_str("<p class=\"dim\">None recorded; is pg_stat_statements installed?</p>\n");
+        } else {
+            out.push_str("<table>\n<tr><th>#</th><th>Exec time</th><th>Calls</th><th>Query</th></tr>\n");
+            for (i, st) in s.top_statements.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td class=\"num\">{:.1} ms</td><td class=\"num\">{}</td><td><code>{}</code></td></tr>",
+                    i + 1,
+                    st.exec_time_ms,
+                    st.calls,
+                    escape(&st.query)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("<h2>Blocking incidents</h2>\n");
+        if self.incidents.is_empty() {
+            out.push_str("<p class=\"dim\">No blocking seen.</p>\n");
+        } else {
+            out.push_str(
+                "<table>\n<tr><th>From</th><th>To</th><th>Peak blocked</th><th>Longest wait</th><th>Blockers</th></tr>\n",
+            );
+            for incident in &self.incidents {
+                let blockers: Vec<String> = incident.blockers.iter().map(ToString::to_string).collect();
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}s</td><td>{}</td></tr>",
+                    incident.started_at.format("%H:%M:%S"),
+                    incident.ended_at.format("%H:%M:%S"),
+                    incident.peak_blocked,
+                    incident.longest_wait_secs,
+                    blockers.join(", ")
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        if !self.events.is_empty() {
+            out.push_str("<h2>Annotations and actions</h2>\n<table>\n");
+            for event in &self.events {
+                let class = if matches!(event.kind, UiEventKind::Annotation { .. }) {
+                    " class=\"note\""
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "<tr{class}><td>{}</td><td>{}</td></tr>",
+                    event.timestamp.format("%H:%M:%S"),
+                    escape(&event.describe())
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222;max-width:1200px}\
+h1{margin-bottom:.2rem}.dim{color:#777}\
+.cards{display:flex;flex-wrap:wrap;gap:.8rem;margin:1rem 0}\
+.cards div{border:1px solid #ddd;border-radius:6px;padding:.5rem .8rem;min-width:8rem}\
+.cards span{display:block;font-size:.8rem;color:#777}.cards b{font-size:1.3rem}\
+.charts{display:flex;flex-wrap:wrap;gap:1rem}\
+figure{margin:0;border:1px solid #ddd;border-radius:6px;padding:.5rem}\
+figcaption{font-weight:600;margin-bottom:.3rem}\
+table{border-collapse:collapse;width:100%}th,td{text-align:left;padding:.3rem .5rem;border-bottom:1px solid #eee;vertical-align:top}\
+td.num{text-align:right;white-space:nowrap}code{white-space:pre-wrap;font-size:.85rem}tr.note td{color:#2a6fb0}";
+
+/// An inline SVG line chart of `chart` across the recording's time span.
+fn svg_chart(chart: &Chart, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> String {
+    let mut out = String::new();
+    let latest = chart.points.last().map_or_else(|| "-".to_string(), |(_, v)| format!("{v:.1}"));
+    let _ = write!(
+        out,
+        "<figure><figcaption>{} <span class=\"dim\">({}, last {latest})</span></figcaption>",
+        chart.title, chart.unit
+    );
+    let _ = write!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{CHART_HEIGHT}\" viewBox=\"0 0 {CHART_WIDTH} {CHART_HEIGHT}\">"
+    );
+    let (Some(start), Some(end)) = (start, end) else {
+        out.push_str("</svg></figure>\n");
+        return out;
+    };
+    let span = (end - start).num_milliseconds().max(1) as f64;
+    let peak = chart.points.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+    let y_max = if peak > 0.0 { peak * 1.1 } else { 1.0 };
+    let _ = write!(
+        out,
+        "<line x1=\"0\" y1=\"{CHART_HEIGHT}\" x2=\"{CHART_WIDTH}\" y2=\"{CHART_HEIGHT}\" stroke=\"#ccc\"/>\
+         <text x=\"4\" y=\"12\" font-size=\"11\" fill=\"#777\">{peak:.1}</text>"
+    );
+    let points: Vec<String> = chart
+        .points
+        .iter()
+        .map(|(t, v)| {
+            let x = (*t - start).num_milliseconds() as f64 / span * CHART_WIDTH;
+            let y = CHART_HEIGHT - v / y_max * (CHART_HEIGHT - 4.0);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect();
+    let _ = write!(
+        out,
+        "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" points=\"{}\"/>",
+        chart.color,
+        points.join(" ")
+    );
+    out.push_str("</svg></figure>\n");
+    out
+}
+
+/// Escape text for HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `pg_glimpse report`: write the report of `recording` to `output`, or
+/// next to the recording with an `.html` extension.
+pub fn run_report(recording: &Path, output: Option<&Path>) -> Result<()> {
+    let session = ReplaySession::load(recording)
+        .with_context(|| format!("could not read recording {}", recording.display()))?;
+    if session.is_empty() {
+        bail!("{} has no snapshots to report on", recording.display());
+    }
+    let output: PathBuf = output.map_or_else(|| recording.with_extension("html"), Path::to_path_buf);
+    std::fs::write(&output, Report::build(&session).render_html())
+        .with_context(|| format!("could not write report to {}", output.display()))?;
+    eprintln!("Report written to {}", output.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{
+        ActivitySummary, BlockingInfo, BufferCacheStats, DatabaseStats, DetectedExtensions, ServerInfo,
+    };
+    use crate::recorder::Recorder;
+    use chrono::TimeZone;
+
+    fn snapshot(secs: i64, xacts: i64, blocked: &[(i32, f64)]) -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap() + chrono::Duration::seconds(secs),
+            blocking_info: blocked
+                .iter()
+                .map(|&(blocker_pid, secs)| BlockingInfo {
+                    blocked_pid: blocker_pid + 100,
+                    blocked_user: None,
+                    blocked_query: None,
+                    blocked_duration_secs: secs,
+                    blocker_pid,
+                    blocker_user: None,
+                    blocker_query: None,
+                    blocker_state: None,
+                })
+                .collect(),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 0.99,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 7,
+                waiting_count: blocked.len() as i64,
+                ..Default::default()
+            },
+            db_stats: Some(DatabaseStats {
+                xact_commit: xacts,
+                ..DatabaseStats::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn server_info() -> ServerInfo {
+        ServerInfo {
+            version: "PostgreSQL 16.2".into(),
+            start_time: Utc::now(),
+            max_connections: 100,
+            extensions: DetectedExtensions::default(),
+            settings: vec![],
+            extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
+        }
+    }
+
+    fn session(snapshots: Vec<PgSnapshot>, events: Vec<UiEvent>) -> ReplaySession {
+        ReplaySession {
+            server_info: server_info(),
+            host: "db1".into(),
+            port: 5432,
+            dbname: "shop".into(),
+            user: "postgres".into(),
+            snapshots,
+            events,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn blocking_incidents_group_consecutive_snapshots() {
+        let snapshots = vec![
+            snapshot(0, 0, &[]),
+            snapshot(2, 0, &[(10, 1.0)]),
+            snapshot(4, 0, &[(10, 3.0), (11, 0.5)]),
+            snapshot(6, 0, &[]),
+            snapshot(8, 0, &[(12, 2.0)]),
+        ];
+        let incidents = BlockingIncident::collect(&snapshots);
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].peak_blocked, 2);
+        assert_eq!(incidents[0].longest_wait_secs, 3.0);
+        assert_eq!(incidents[0].blockers, vec![10, 11]);
+        assert_eq!(incidents[0].ended_at, snapshots[2].timestamp);
+        assert_eq!(incidents[1].blockers, vec![12]);
+    }
+
+    #[test]
+    fn report_is_self_contained_html_with_escaped_text() {
+        let events = vec![
+            UiEvent {
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 3).unwrap(),
+                kind: UiEventKind::Annotation { text: "deployed <v2.3>".into() },
+                via: None,
+            },
+            UiEvent {
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 4).unwrap(),
+                kind: UiEventKind::PanelSwitch { panel: "Locks".into() },
+                via: None,
+            },
+        ];
+        let session = session(
+            vec![snapshot(0, 100, &[]), snapshot(2, 200, &[(10, 1.5)]), snapshot(4, 300, &[])],
+            events,
+        );
+        let report = Report::build(&session);
+        assert_eq!(report.summary.tps_max, Some(50.0));
+        assert_eq!(report.events.len(), 1);
+        // No standbys, so no lag chart
+        assert_eq!(report.charts.len(), 4);
+
+        let html = report.render_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("postgres@db1:5432/shop"));
+        assert!(html.contains("# deployed &lt;v2.3&gt;"));
+        assert!(html.contains("<polyline"));
+        // Nothing loaded from elsewhere
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+    }
+
+    #[test]
+    fn run_report_writes_next_to_the_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db1.jsonl");
+        let mut recorder =
+            Recorder::new_with_path(path.clone(), "db1", 5432, "shop", "postgres", &server_info()).unwrap();
+        assert!(run_report(&path, None).is_err(), "no snapshots yet");
+
+        recorder.record(&snapshot(0, 100, &[])).unwrap();
+        recorder.record(&snapshot(2, 150, &[])).unwrap();
+        drop(recorder);
+        run_report(&path, None).unwrap();
+        let html = std::fs::read_to_string(dir.path().join("db1.html")).unwrap();
+        assert!(html.contains("Transactions / s"));
+
+        assert!(run_report(&dir.path().join("missing.jsonl"), None).is_err());
+    }
+}
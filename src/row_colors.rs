@@ -0,0 +1,355 @@
+//! Threshold rules that color panel rows at render time.
+//!
+//! Rules are read from `config.toml` as `[[row_colors]]` tables:
+//!
+//! ```toml
+//! [[row_colors]]
+//! column = "mean_exec_time"
+//! above = 100
+//! color = "danger"
+//!
+//! [[row_colors]]
+//! column = "dead_ratio"
+//! above = 10
+//! color = "warn"
+//! ```
+//!
+//! Each column belongs to one panel (Statements, Table Stats or Indexes).
+//! Rules are checked in order and the first match colors the row, so put
+//! the stricter rule first when two watch the same column.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::models::{IndexInfo, StatStatement, TableStat};
+
+/// A numeric column a rule can watch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowColumn {
+    /// Statements: mean execution time in milliseconds
+    MeanExecTime,
+    /// Statements: max execution time in milliseconds
+    MaxExecTime,
+    /// Statements: total execution time in milliseconds
+    TotalExecTime,
+    Calls,
+    /// Statements: buffer cache hit percentage
+    HitPct,
+    /// Statements: temp blocks read plus written
+    TempBlks,
+    /// Table Stats: dead tuples as a percentage of live ones
+    DeadRatio,
+    DeadTuples,
+    SeqScan,
+    /// Table Stats: total size in MB, indexes and TOAST included
+    TableSizeMb,
+    IdxScan,
+    /// Indexes: index size in MB
+    IndexSizeMb,
+}
+
+impl RowColumn {
+    /// In panel order, then in column order, for cycling in the editor.
+    pub const ALL: [Self; 12] = [
+        Self::MeanExecTime,
+        Self::MaxExecTime,
+        Self::TotalExecTime,
+        Self::Calls,
+        Self::HitPct,
+        Self::TempBlks,
+        Self::DeadRatio,
+        Self::DeadTuples,
+        Self::SeqScan,
+        Self::TableSizeMb,
+        Self::IdxScan,
+        Self::IndexSizeMb,
+    ];
+
+    pub const fn panel_label(self) -> &'static str {
+        match self {
+            Self::MeanExecTime
+            | Self::MaxExecTime
+            | Self::TotalExecTime
+            | Self::Calls
+            | Self::HitPct
+            | Self::TempBlks => "Statements",
+            Self::DeadRatio | Self::DeadTuples | Self::SeqScan | Self::TableSizeMb => "Table Stats",
+            Self::IdxScan | Self::IndexSizeMb => "Indexes",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::MeanExecTime => "mean time",
+            Self::MaxExecTime => "max time",
+            Self::TotalExecTime => "total time",
+            Self::Calls => "calls",
+            Self::HitPct => "hit ratio",
+            Self::TempBlks => "temp blocks",
+            Self::DeadRatio => "dead ratio",
+            Self::DeadTuples => "dead tuples",
+            Self::SeqScan => "seq scans",
+            Self::TableSizeMb => "size",
+            Self::IdxScan => "index scans",
+            Self::IndexSizeMb => "size",
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Self::MeanExecTime | Self::MaxExecTime | Self::TotalExecTime => format!("{value}ms"),
+            Self::HitPct | Self::DeadRatio => format!("{value}%"),
+            Self::TableSizeMb | Self::IndexSizeMb => format!("{value} MB"),
+            Self::Calls | Self::TempBlks | Self::DeadTuples | Self::SeqScan | Self::IdxScan => {
+                format!("{value}")
+            }
+        }
+    }
+
+    /// Threshold a new rule on this column starts from.
+    const fn default_threshold(self) -> f64 {
+        match self {
+            Self::MeanExecTime | Self::MaxExecTime => 100.0,
+            Self::TotalExecTime => 60_000.0,
+            Self::Calls => 100_000.0,
+            Self::HitPct => 90.0,
+            Self::TempBlks | Self::IdxScan => 1.0,
+            Self::DeadRatio => 10.0,
+            Self::DeadTuples | Self::SeqScan => 10_000.0,
+            Self::TableSizeMb | Self::IndexSizeMb => 1000.0,
+        }
+    }
+}
+
+/// Theme color a matching row is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowColor {
+    Danger,
+    Warn,
+    Ok,
+    Accent,
+}
+
+impl RowColor {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Danger => "danger",
+            Self::Warn => "warn",
+            Self::Ok => "ok",
+            Self::Accent => "accent",
+        }
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Danger => Self::Warn,
+            Self::Warn => Self::Ok,
+            Self::Ok => Self::Accent,
+            Self::Accent => Self::Danger,
+        }
+    }
+}
+
+/// One `[[row_colors]]` entry from the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowColorRule {
+    pub column: RowColumn,
+    /// Color rows whose value is above this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub above: Option<f64>,
+    /// Color rows whose value is below this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub below: Option<f64>,
+    pub color: RowColor,
+}
+
+impl RowColorRule {
+    /// A rule on `column` at its usual threshold, used by the editor's add key.
+    pub const fn new(column: RowColumn) -> Self {
+        let (above, below) = match column {
+            RowColumn::HitPct => (None, Some(column.default_threshold())),
+            _ => (Some(column.default_threshold()), None),
+        };
+        Self {
+            column,
+            above,
+            below,
+            color: RowColor::Danger,
+        }
+    }
+
+    pub fn matches(&self, value: f64) -> bool {
+        self.above.is_some_and(|limit| value > limit) || self.below.is_some_and(|limit| value < limit)
+    }
+
+    /// The rule in words, e.g. `Statements mean time > 100ms`.
+    pub fn condition(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(limit) = self.above {
+            parts.push(format!("> {}", self.column.format(limit)));
+        }
+        if let Some(limit) = self.below {
+            parts.push(format!("< {}", self.column.format(limit)));
+        }
+        format!("{} {} {}", self.column.panel_label(), self.column.label(), parts.join(" or "))
+    }
+
+    /// Move to the next column, keeping the direction but starting from
+    /// that column's usual threshold.
+    pub fn cycle_column(&mut self) {
+        let i = RowColumn::ALL.iter().position(|&c| c == self.column).unwrap_or(0);
+        self.column = RowColumn::ALL[(i + 1) % RowColumn::ALL.len()];
+        let threshold = Some(self.column.default_threshold());
+        if self.below.is_some() && self.above.is_none() {
+            self.below = threshold;
+        } else {
+            self.above = threshold;
+            self.below = None;
+        }
+    }
+
+    /// Swap between coloring above and below the threshold.
+    pub fn flip(&mut self) {
+        std::mem::swap(&mut self.above, &mut self.below);
+    }
+
+    /// Nudge the threshold by a quarter in `direction`, rounded to two
+    /// significant digits so repeated steps stay readable.
+    pub fn step(&mut self, direction: i8) {
+        let Some(limit) = self.above.as_mut().or(self.below.as_mut()) else {
+            return;
+        };
+        let next = if direction > 0 { *limit * 1.25 } else { *limit / 1.25 };
+        *limit = if next < 1.0 {
+            if direction > 0 { 1.0 } else { 0.0 }
+        } else {
+            round_significant(next)
+        };
+    }
+}
+
+fn round_significant(value: f64) -> f64 {
+    let scale = 10f64.powi(value.log10().floor() as i32 - 1);
+    (value / scale).round() * scale
+}
+
+/// A panel row that rules can be checked against.
+pub trait RuleRow {
+    /// Value of `column`, or None when the column belongs to another panel.
+    fn rule_value(&self, column: RowColumn) -> Option<f64>;
+}
+
+impl RuleRow for StatStatement {
+    fn rule_value(&self, column: RowColumn) -> Option<f64> {
+        match column {
+            RowColumn::MeanExecTime => Some(self.mean_exec_time),
+            RowColumn::MaxExecTime => Some(self.max_exec_time),
+            RowColumn::TotalExecTime => Some(self.total_exec_time),
+            RowColumn::Calls => Some(self.calls as f64),
+            RowColumn::HitPct => Some(self.hit_ratio * 100.0),
+            RowColumn::TempBlks => Some((self.temp_blks_read + self.temp_blks_written) as f64),
+            _ => None,
+        }
+    }
+}
+
+impl RuleRow for TableStat {
+    fn rule_value(&self, column: RowColumn) -> Option<f64> {
+        match column {
+            RowColumn::DeadRatio => Some(self.dead_ratio),
+            RowColumn::DeadTuples => Some(self.n_dead_tup as f64),
+            RowColumn::SeqScan => Some(self.seq_scan as f64),
+            RowColumn::TableSizeMb => Some(self.total_size_bytes as f64 / 1_048_576.0),
+            _ => None,
+        }
+    }
+}
+
+impl RuleRow for IndexInfo {
+    fn rule_value(&self, column: RowColumn) -> Option<f64> {
+        match column {
+            RowColumn::IdxScan => Some(self.idx_scan as f64),
+            RowColumn::IndexSizeMb => Some(self.index_size_bytes as f64 / 1_048_576.0),
+            _ => None,
+        }
+    }
+}
+
+/// Color of the first rule `row` matches.
+pub fn row_color(rules: &[RowColorRule], row: &impl RuleRow) -> Option<RowColor> {
+    rules
+        .iter()
+        .find(|rule| row.rule_value(rule.column).is_some_and(|v| rule.matches(v)))
+        .map(|rule| rule.color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Stmt {
+        mean: f64,
+    }
+
+    impl RuleRow for Stmt {
+        fn rule_value(&self, column: RowColumn) -> Option<f64> {
+            (column == RowColumn::MeanExecTime).then_some(self.mean)
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_colors_the_row() {
+        let rules = vec![
+            RowColorRule {
+                column: RowColumn::DeadRatio,
+                above: Some(0.0),
+                below: None,
+                color: RowColor::Accent,
+            },
+            RowColorRule { color: RowColor::Danger, ..RowColorRule::new(RowColumn::MeanExecTime) },
+            RowColorRule {
+                column: RowColumn::MeanExecTime,
+                above: Some(10.0),
+                below: None,
+                color: RowColor::Warn,
+            },
+        ];
+        // The dead ratio rule belongs to another panel and is skipped
+        assert_eq!(row_color(&rules, &Stmt { mean: 250.0 }), Some(RowColor::Danger));
+        assert_eq!(row_color(&rules, &Stmt { mean: 50.0 }), Some(RowColor::Warn));
+        assert_eq!(row_color(&rules, &Stmt { mean: 5.0 }), None);
+    }
+
+    #[test]
+    fn editing_a_rule() {
+        let mut rule = RowColorRule::new(RowColumn::MeanExecTime);
+        assert_eq!(rule.condition(), "Statements mean time > 100ms");
+        rule.step(1);
+        assert_eq!(rule.above, Some(130.0));
+        rule.step(-1);
+        rule.step(-1);
+        assert_eq!(rule.above, Some(80.0));
+        rule.flip();
+        assert_eq!((rule.above, rule.below), (None, Some(80.0)));
+        rule.cycle_column();
+        assert_eq!(rule.column, RowColumn::MaxExecTime);
+        assert_eq!(rule.condition(), "Statements max time < 100ms");
+
+        let mut tiny = RowColorRule::new(RowColumn::IdxScan);
+        tiny.step(-1);
+        assert_eq!(tiny.above, Some(0.0));
+        tiny.step(1);
+        assert_eq!(tiny.above, Some(1.0));
+    }
+
+    #[test]
+    fn parses_from_toml() {
+        let rule: RowColorRule =
+            toml::from_str("column = \"dead_ratio\"\nabove = 10\ncolor = \"warn\"").unwrap();
+        assert_eq!(rule.column, RowColumn::DeadRatio);
+        assert_eq!(rule.above, Some(10.0));
+        assert_eq!(rule.color, RowColor::Warn);
+        assert_eq!(rule.condition(), "Table Stats dead ratio > 10%");
+    }
+}
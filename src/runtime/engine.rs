@@ -0,0 +1,2593 @@
+//! Reusable event-loop engine.
+//!
+//! The engine owns the `App` and drives it from three inputs: key events,
+//! results coming back from the snapshot source, and timers. Rendering and
+//! recording are injected so the same loop can run with a terminal, without
+//! one (headless modes), or inside tests.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use tokio::sync::{mpsc, oneshot, watch};
+
+use crate::alerts::Alert;
+use crate::app::{App, AppAction, ConnectionEntry, PreparedSnapshot, PrepWatch, SnapshotPrep, ViewMode};
+use crate::connection::{Backoff, Reconnect};
+use crate::db::capabilities::Capabilities;
+use crate::db::catalog_cache::CatalogCache;
+use crate::db::models::{
+    BufferCacheReport, DetectedExtensions, IndexAdvice, MeasuredBloat, OperationProgress, PgSetting, PgSnapshot, QueryPlan, RoleGrant, RoleInfo, RoleSetting, StatementSample,
+    TableSchema, WaitEventCount, WaitSampleSource, WraparoundTable,
+};
+use crate::db::queries::{IndexBloat, TableBloat};
+use crate::error_log::ErrorLog;
+use crate::hooks::{self, HookOutcome, HookPhase, SnapshotHooks};
+use crate::maintenance::Maintenance;
+use crate::recorder::{Recorder, UiEvent, UiEventKind};
+use crate::schedule::{RelationStatsTimer, SnapshotSchedule};
+use crate::web::WebMirror;
+use crate::{db, event, export, notifications, server_log, table_export, ui};
+
+/// The refresh `app` asks for.
+fn fetch_snapshot(app: &App) -> DbCommand {
+    DbCommand::FetchSnapshot {
+        all_backends: app.show_all_backends,
+        server_log: app.config.server_log && app.replay.is_none(),
+    }
+}
+
+/// Commands sent from the engine to the snapshot source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DbCommand {
+    /// `all_backends` lifts the cap on idle backends in the Queries list;
+    /// `server_log` tails the server log into the snapshot
+    FetchSnapshot { all_backends: bool, server_log: bool },
+    CancelQuery(i32),
+    TerminateBackend(i32),
+    CancelQueries(Vec<i32>),
+    TerminateBackends(Vec<i32>),
+    RefreshBloat,
+    ResetStatStatements,
+    DropReplicationSlot(String),
+    FetchSettings,
+    /// `ALTER ROLE/DATABASE ... SET` overrides, refreshed with the settings
+    FetchRoleSettings,
+    /// Roles and memberships, refreshed with the settings
+    FetchRoles,
+    /// Privileges held by one role for its inspect overlay
+    FetchRoleGrants(String),
+    /// Oldest relations by frozen XID, for the named database's inspect overlay
+    FetchWraparoundTables(String),
+    /// Counters of these statements for the latency sampler
+    SampleStatements(Vec<i64>),
+    /// Plan this query text (without running it)
+    Explain(String),
+    /// Plan this query text with and without hypothetical indexes
+    AdviseIndexes(String),
+    /// What the server logged about these just-cancelled or terminated
+    /// backends
+    FetchActionLog(Vec<i32>),
+    /// Take a wait event sample on the sampler's own connection
+    SampleWaits,
+    /// Catalog definition of a table for its inspect overlay
+    DescribeTable { schema: String, table: String },
+    /// What `pg_buffercache` shows in shared buffers
+    ReadBufferCache,
+    /// Exact `pgstattuple()` bloat of one table and its indexes, on a
+    /// connection of its own
+    MeasureBloat { schema: String, table: String },
+    /// VACUUM, ANALYZE or REINDEX on a connection of its own
+    RunMaintenance(Maintenance),
+}
+
+/// The server `cmd` runs on. Only maintenance goes to the primary: a
+/// standby can't VACUUM, and the relations are the same on both. PIDs, slots
+/// and statement stats belong to the monitored server, so signals, slot drops
+/// and resets stay on it.
+fn server_for<'a>(cmd: &DbCommand, monitored: &'a Reconnect, primary: Option<&'a Reconnect>) -> &'a Reconnect {
+    match (cmd, primary) {
+        (DbCommand::RunMaintenance(_), Some(primary)) => primary,
+        _ => monitored,
+    }
+}
+
+/// How often `pg_settings` is re-read to catch changes mid-session.
+const SETTINGS_REFRESH_SECS: u64 = 60;
+
+/// Latency sampler tick. Finer ticks give better percentile estimates.
+const LATENCY_SAMPLE_MILLIS: u64 = 1000;
+
+/// Wait sampler tick. With `pg_wait_sampling` each tick collects the
+/// extension's own finer samples instead.
+const WAIT_SAMPLE_MILLIS: u64 = 250;
+
+pub type BloatResult = (HashMap<String, TableBloat>, HashMap<String, IndexBloat>);
+
+/// Results sent back from the snapshot source to the engine.
+#[derive(Debug)]
+pub enum DbResult {
+    Snapshot(Box<Result<PreparedSnapshot, String>>),
+    CancelQuery(i32, Result<bool, String>),
+    TerminateBackend(i32, Result<bool, String>),
+    CancelQueries(Vec<(i32, bool)>),
+    TerminateBackends(Vec<(i32, bool)>),
+    BloatData(Result<BloatResult, String>),
+    ResetStatStatements(Result<(), String>),
+    DropReplicationSlot(String, Result<(), String>),
+    Settings(Result<Vec<PgSetting>, String>),
+    RoleSettings(Result<Vec<RoleSetting>, String>),
+    Roles(Result<Vec<RoleInfo>, String>),
+    /// Privileges held by the named role
+    RoleGrants(String, Result<Vec<RoleGrant>, String>),
+    /// Oldest relations of the named database
+    WraparoundTables(String, Result<Vec<WraparoundTable>, String>),
+    StatementSamples(Result<Vec<StatementSample>, String>),
+    /// The connection dropped; the next attempt is `retry_in` away
+    Reconnecting {
+        attempt: u32,
+        retry_in: Duration,
+        error: String,
+    },
+    /// The connection is back after a drop
+    Reconnected,
+    Explain(Result<QueryPlan, String>),
+    IndexAdvice(Result<IndexAdvice, String>),
+    /// A NOTIFY arrived on the listened channel; time for a fresh snapshot
+    Notified { channel: String, payload: String },
+    /// The LISTEN connection could not be opened; it keeps retrying
+    ListenFailed(String),
+    /// How the snapshot hooks went, sent after each snapshot that ran any
+    Hooks(Vec<HookOutcome>),
+    /// Server log lines per PID, from the last cancel or terminate on
+    ActionLog(Result<Vec<(i32, Vec<String>)>, String>),
+    /// Wait events seen since the previous sample
+    WaitSamples(Result<(WaitSampleSource, Vec<WaitEventCount>), String>),
+    /// Definition of the table inspected as `schema.table`
+    TableSchema(String, Result<TableSchema, String>),
+    BufferCache(Result<BufferCacheReport, String>),
+    MeasuredBloat {
+        schema: String,
+        table: String,
+        result: Result<MeasuredBloat, String>,
+    },
+    /// The maintenance command's connection is up and runs as this PID
+    MaintenanceStarted(i32),
+    /// Progress view row of the maintenance backend, read on the server
+    /// running it
+    MaintenanceProgress(i32, Option<OperationProgress>),
+    /// The maintenance command ended
+    Maintenance(Maintenance, Result<(), String>),
+}
+
+impl DbResult {
+    /// Results of the commands that [`server_for`] sends to the primary.
+    const fn ran_on_primary(&self) -> bool {
+        matches!(self, Self::Maintenance(..))
+    }
+}
+
+/// Refresh as soon as a NOTIFY arrives on `channel`, but no more often
+/// than `min_interval`.
+#[derive(Debug, Clone)]
+pub struct NotifyTrigger {
+    pub channel: String,
+    pub min_interval: Duration,
+}
+
+/// Optional extras for a live snapshot source.
+#[derive(Clone, Default)]
+pub struct SourceOptions {
+    pub notify: Option<NotifyTrigger>,
+    pub hooks: SnapshotHooks,
+    /// Run maintenance here rather than on the monitored server, e.g. when
+    /// snapshots come from a read-only standby
+    pub primary: Option<Reconnect>,
+    /// PgBouncer admin console to read pools from with each snapshot
+    pub pgbouncer: Option<Reconnect>,
+    /// When to read table and index stats
+    pub schedule: SnapshotSchedule,
+}
+
+/// Channels connecting the engine to a snapshot source.
+pub struct SnapshotSource {
+    pub commands: mpsc::Sender<DbCommand>,
+    pub results: mpsc::UnboundedReceiver<DbResult>,
+    /// Settings the source prepares its snapshots against
+    pub watch: watch::Sender<PrepWatch>,
+}
+
+impl SnapshotSource {
+    /// Hand `app`'s alert rules and other preparation settings to the
+    /// source, when they changed since the last time.
+    pub fn send_watch(&self, app: &App) {
+        let fresh = app.prep_watch();
+        self.watch.send_if_modified(|watch| {
+            let changed = *watch != fresh;
+            if changed {
+                *watch = fresh;
+            }
+            changed
+        });
+    }
+
+    /// Spawn a background task that executes commands against a live connection.
+    /// When the connection drops it is re-established with `reconnect`,
+    /// backing off between attempts.
+    pub fn spawn_postgres(
+        client: tokio_postgres::Client,
+        extensions: DetectedExtensions,
+        capabilities: Capabilities,
+        pg_major_version: u32,
+        reconnect: Reconnect,
+        options: SourceOptions,
+    ) -> Self {
+        let (cmd_tx, mut cmd_rx) = mpsc::channel::<DbCommand>(16);
+        let (result_tx, result_rx) = mpsc::unbounded_channel::<DbResult>();
+        let (watch_tx, watch_rx) = watch::channel(PrepWatch::default());
+        let (measured_tx, mut measured_rx) = mpsc::unbounded_channel::<BloatResult>();
+
+        if let Some(trigger) = options.notify {
+            spawn_listener(reconnect.clone(), trigger, result_tx.clone());
+        }
+        let snapshot_hooks = options.hooks;
+        let primary = options.primary;
+        let pgbouncer = options.pgbouncer;
+        let schedule = options.schedule;
+        let wait_sampler =
+            spawn_wait_sampler(reconnect.clone(), extensions.pg_wait_sampling, result_tx.clone());
+
+        tokio::spawn(async move {
+            let mut client = client;
+            let mut catalog = CatalogCache::load(&client).await.unwrap_or_default();
+            let mut prep = SnapshotPrep::default();
+            let mut log_tail = server_log::LogTail::default();
+            let mut pooler_client = None;
+            let mut relation_stats_timer = RelationStatsTimer::default();
+            while let Some(cmd) = cmd_rx.recv().await {
+                if let DbCommand::Explain(_) | DbCommand::AdviseIndexes(_) = cmd {
+                    spawn_explain(&reconnect, &extensions, &capabilities, pg_major_version, cmd, &result_tx);
+                    continue;
+                }
+                if let DbCommand::MeasureBloat { schema, table } = cmd {
+                    spawn_measure_bloat(&reconnect, schema, table, &result_tx, &measured_tx);
+                    continue;
+                }
+                if let DbCommand::SampleWaits = cmd {
+                    // Dropped while the previous sample is still running
+                    let _ = wait_sampler.try_send(());
+                    continue;
+                }
+                let server = server_for(&cmd, &reconnect, primary.as_ref());
+                if let DbCommand::FetchActionLog(_) = cmd {
+                    spawn_action_log(server, &extensions, &capabilities, pg_major_version, cmd, &result_tx);
+                    continue;
+                }
+                if let DbCommand::RunMaintenance(maintenance) = cmd {
+                    spawn_maintenance(server, pg_major_version, maintenance, &result_tx);
+                    continue;
+                }
+                let is_snapshot = matches!(cmd, DbCommand::FetchSnapshot { .. });
+                let mut hook_outcomes = Vec::new();
+                if is_snapshot {
+                    hook_outcomes = hooks::run_hooks(&client, HookPhase::Before, &snapshot_hooks).await;
+                }
+                let result = match cmd {
+                    // Prepared here so the UI thread only swaps it in
+                    DbCommand::FetchSnapshot { all_backends, server_log } => {
+                        let relation_stats =
+                            relation_stats_timer.due(&schedule, chrono::Local::now().time());
+                        let fetched = db::queries::fetch_snapshot(
+                            &client,
+                            &extensions,
+                            &capabilities,
+                            pg_major_version,
+                            all_backends,
+                            relation_stats,
+                        )
+                        .await;
+                        DbResult::Snapshot(Box::new(match fetched {
+                            Ok(mut snap) => {
+                                prep.relation_stats(&mut snap, relation_stats);
+                                catalog.resolve_snapshot(&client, &mut snap).await;
+                                if server_log {
+                                    tail_server_log(&client, &capabilities, &mut log_tail, &mut snap).await;
+                                }
+                                if let Some(admin) = &pgbouncer {
+                                    read_pooler(admin, &mut pooler_client, &mut snap).await;
+                                }
+                                while let Ok((tables, indexes)) = measured_rx.try_recv() {
+                                    prep.remember_bloat(&tables, &indexes);
+                                }
+                                prep.watch(&watch_rx.borrow());
+                                Ok(prep.prepare(snap, true))
+                            }
+                            Err(e) => Err(e.to_string()),
+                        }))
+                    }
+                    DbCommand::ReadBufferCache if capabilities.buffercache => {
+                        DbResult::BufferCache(read_buffer_cache(&client, &mut catalog).await)
+                    }
+                    cmd => execute_command(&client, &extensions, &capabilities, pg_major_version, cmd).await,
+                };
+                if let DbResult::BloatData(Ok((tables, indexes))) = &result {
+                    prep.remember_bloat(tables, indexes);
+                }
+                if is_snapshot {
+                    hook_outcomes.extend(hooks::run_hooks(&client, HookPhase::After, &snapshot_hooks).await);
+                }
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+                if !hook_outcomes.is_empty() && result_tx.send(DbResult::Hooks(hook_outcomes)).is_err() {
+                    break;
+                }
+                if client.is_closed() {
+                    let Some(fresh) = reconnect_with_backoff(&reconnect, &result_tx).await else {
+                        break;
+                    };
+                    client = fresh;
+                    catalog = CatalogCache::load(&client).await.unwrap_or_default();
+                    // Commands queued while down refer to the old session
+                    while cmd_rx.try_recv().is_ok() {}
+                    if result_tx.send(DbResult::Reconnected).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            commands: cmd_tx,
+            results: result_rx,
+            watch: watch_tx,
+        }
+    }
+}
+
+/// Longest time the planner gets for an EXPLAIN.
+const EXPLAIN_TIMEOUT_SQL: &str = "SET statement_timeout = '10s'";
+
+/// Run an EXPLAIN on its own connection so planning a heavy query never
+/// holds up refreshes, and a session setting can bound it. Index advice
+/// goes the same way, which also keeps its hypothetical indexes out of
+/// the snapshot session.
+fn spawn_explain(
+    reconnect: &Reconnect,
+    extensions: &DetectedExtensions,
+    capabilities: &Capabilities,
+    pg_major_version: u32,
+    cmd: DbCommand,
+    result_tx: &mpsc::UnboundedSender<DbResult>,
+) {
+    let reconnect = reconnect.clone();
+    let extensions = extensions.clone();
+    let capabilities = capabilities.clone();
+    let result_tx = result_tx.clone();
+    tokio::spawn(async move {
+        let result = match reconnect.connect().await {
+            Ok(client) => match client.batch_execute(EXPLAIN_TIMEOUT_SQL).await {
+                Ok(()) => execute_command(&client, &extensions, &capabilities, pg_major_version, cmd).await,
+                Err(e) => planning_failed(&cmd, e.to_string()),
+            },
+            Err(e) => planning_failed(&cmd, format!("Could not open a connection for EXPLAIN: {e}")),
+        };
+        let _ = result_tx.send(result);
+    });
+}
+
+fn planning_failed(cmd: &DbCommand, error: String) -> DbResult {
+    match cmd {
+        DbCommand::AdviseIndexes(_) => DbResult::IndexAdvice(Err(error)),
+        _ => DbResult::Explain(Err(error)),
+    }
+}
+
+/// Measure exact bloat on a connection of its own: `pgstattuple()` reads
+/// the whole table and would hold up refreshes. What it measured also goes
+/// to `measured_tx`, for the worker to carry into the snapshots that follow.
+fn spawn_measure_bloat(
+    reconnect: &Reconnect,
+    schema: String,
+    table: String,
+    result_tx: &mpsc::UnboundedSender<DbResult>,
+    measured_tx: &mpsc::UnboundedSender<BloatResult>,
+) {
+    let reconnect = reconnect.clone();
+    let result_tx = result_tx.clone();
+    let measured_tx = measured_tx.clone();
+    tokio::spawn(async move {
+        let result = match reconnect.connect().await {
+            Ok(client) => measure_bloat(&client, &schema, &table).await,
+            Err(e) => Err(format!("could not open a connection: {e}")),
+        };
+        if let Ok(measured) = &result {
+            let _ = measured_tx.send(db::queries::measured_bloat_estimates(&schema, &table, measured));
+        }
+        let _ = result_tx.send(DbResult::MeasuredBloat { schema, table, result });
+    });
+}
+
+async fn measure_bloat(client: &tokio_postgres::Client, schema: &str, table: &str) -> Result<MeasuredBloat, String> {
+    match db::queries::measure_bloat(client, schema, table).await {
+        Ok(Some(measured)) => Ok(measured),
+        Ok(None) => Err("Table no longer exists".into()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Run a maintenance command on a connection of its own, so a long VACUUM
+/// never holds up refreshes. Its backend's PID is sent first, then its
+/// progress as a second connection to the same server reads it: with
+/// `--primary` that is not the server the snapshots come from.
+fn spawn_maintenance(
+    server: &Reconnect,
+    pg_major_version: u32,
+    maintenance: Maintenance,
+    result_tx: &mpsc::UnboundedSender<DbResult>,
+) {
+    let server = server.clone();
+    let result_tx = result_tx.clone();
+    tokio::spawn(async move {
+        let result = async {
+            let sql = maintenance
+                .sql(pg_major_version)
+                .ok_or_else(|| "nothing to run".to_string())?;
+            let client = server
+                .connect()
+                .await
+                .map_err(|e| format!("could not open a connection: {e}"))?;
+            let pid = db::queries::backend_pid(&client).await?;
+            let _ = result_tx.send(DbResult::MaintenanceStarted(pid));
+            let (_done, done_rx) = oneshot::channel::<()>();
+            spawn_progress_watch(server.clone(), pg_major_version, pid, result_tx.clone(), done_rx);
+            db::queries::run_maintenance(&client, &sql).await
+        }
+        .await;
+        let _ = result_tx.send(DbResult::Maintenance(maintenance, result));
+    });
+}
+
+/// Poll the progress views for backend `pid` until `done` is dropped.
+fn spawn_progress_watch(
+    server: Reconnect,
+    pg_major_version: u32,
+    pid: i32,
+    result_tx: mpsc::UnboundedSender<DbResult>,
+    mut done: oneshot::Receiver<()>,
+) {
+    tokio::spawn(async move {
+        let Ok(client) = server.connect().await else {
+            return;
+        };
+        let mut ticks = tokio::time::interval(MAINTENANCE_PROGRESS_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut done => break,
+                _ = ticks.tick() => {
+                    if let Ok(progress) = db::queries::fetch_progress_of(&client, pg_major_version, pid).await {
+                        if result_tx.send(DbResult::MaintenanceProgress(pid, progress)).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Take wait samples on a connection of their own whenever asked, so a
+/// slow snapshot never delays them. The connection is opened on the first
+/// request and reopened after it drops.
+fn spawn_wait_sampler(
+    reconnect: Reconnect,
+    use_extension: bool,
+    result_tx: mpsc::UnboundedSender<DbResult>,
+) -> mpsc::Sender<()> {
+    let (tx, mut rx) = mpsc::channel::<()>(1);
+    tokio::spawn(async move {
+        let mut client: Option<tokio_postgres::Client> = None;
+        let mut since = None;
+        while rx.recv().await.is_some() {
+            if client.as_ref().map_or(true, tokio_postgres::Client::is_closed) {
+                match reconnect.connect().await {
+                    Ok(fresh) => client = Some(fresh),
+                    Err(e) => {
+                        let _ = result_tx.send(DbResult::WaitSamples(Err(e.to_string())));
+                        continue;
+                    }
+                }
+            }
+            let Some(client) = client.as_ref() else {
+                continue;
+            };
+            let result = if use_extension {
+                db::queries::fetch_wait_history(client, since).await.map(|(counts, latest)| {
+                    since = latest.or(since);
+                    (WaitSampleSource::PgWaitSampling, counts)
+                })
+            } else {
+                db::queries::fetch_wait_events(client)
+                    .await
+                    .map(|counts| (WaitSampleSource::PgStatActivity, counts))
+            };
+            if result_tx.send(DbResult::WaitSamples(result.map_err(|e| e.to_string()))).is_err() {
+                return;
+            }
+        }
+    });
+    tx
+}
+
+/// How long the backend gets to act on a cancel or terminate and log it.
+const ACTION_LOG_DELAY: Duration = Duration::from_millis(500);
+
+/// How often a running maintenance command's progress is read.
+const MAINTENANCE_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Read the server log on its own connection, a moment after the action,
+/// so the refresh loop never waits for it.
+fn spawn_action_log(
+    server: &Reconnect,
+    extensions: &DetectedExtensions,
+    capabilities: &Capabilities,
+    pg_major_version: u32,
+    cmd: DbCommand,
+    result_tx: &mpsc::UnboundedSender<DbResult>,
+) {
+    let server = server.clone();
+    let extensions = extensions.clone();
+    let capabilities = capabilities.clone();
+    let result_tx = result_tx.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(ACTION_LOG_DELAY).await;
+        let result = match server.connect().await {
+            Ok(client) => execute_command(&client, &extensions, &capabilities, pg_major_version, cmd).await,
+            Err(e) => DbResult::ActionLog(Err(e.to_string())),
+        };
+        let _ = result_tx.send(result);
+    });
+}
+
+/// Leading-edge throttle for NOTIFY refreshes: the first notification fires
+/// at once, and any that follow within `min_interval` collapse into a single
+/// fire when the interval is up.
+#[derive(Debug)]
+struct NotifyThrottle {
+    min_interval: Duration,
+    last_fired: Option<Instant>,
+    pending: bool,
+}
+
+impl NotifyThrottle {
+    const fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_fired: None,
+            pending: false,
+        }
+    }
+
+    /// A notification arrived. Returns true if it should fire now.
+    fn notify(&mut self, now: Instant) -> bool {
+        if self
+            .last_fired
+            .map_or(true, |t| now.duration_since(t) >= self.min_interval)
+        {
+            self.last_fired = Some(now);
+            self.pending = false;
+            true
+        } else {
+            self.pending = true;
+            false
+        }
+    }
+
+    /// When a held-back notification is due, if any.
+    fn deadline(&self) -> Option<Instant> {
+        self.last_fired
+            .filter(|_| self.pending)
+            .map(|t| t + self.min_interval)
+    }
+
+    fn fire_pending(&mut self, now: Instant) {
+        self.last_fired = Some(now);
+        self.pending = false;
+    }
+}
+
+/// Keep a dedicated connection LISTENing on the trigger's channel and pass
+/// notifications on, throttled. Reconnects with backoff when it drops.
+fn spawn_listener(
+    reconnect: Reconnect,
+    trigger: NotifyTrigger,
+    result_tx: mpsc::UnboundedSender<DbResult>,
+) {
+    tokio::spawn(async move {
+        let mut throttle = NotifyThrottle::new(trigger.min_interval);
+        let mut backoff = Backoff::default();
+        loop {
+            match reconnect.listen(&trigger.channel).await {
+                Ok((_client, mut notifications)) => {
+                    backoff = Backoff::default();
+                    let mut held = None;
+                    loop {
+                        let deadline = throttle.deadline();
+                        tokio::select! {
+                            n = notifications.recv() => {
+                                let Some(n) = n else { break };
+                                if throttle.notify(Instant::now()) {
+                                    held = None;
+                                    let _ = result_tx.send(notified(&n));
+                                } else {
+                                    held = Some(n);
+                                }
+                            }
+                            () = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now).into()), if deadline.is_some() => {
+                                throttle.fire_pending(Instant::now());
+                                if let Some(n) = held.take() {
+                                    let _ = result_tx.send(notified(&n));
+                                }
+                            }
+                        }
+                        if result_tx.is_closed() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => {
+                    // Report the first failure only; the main connection
+                    // shows its own reconnect progress
+                    if backoff.attempt() == 0 {
+                        let _ = result_tx.send(DbResult::ListenFailed(e.to_string()));
+                    }
+                }
+            }
+            if result_tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(backoff.next_delay()).await;
+        }
+    });
+}
+
+fn notified(n: &tokio_postgres::Notification) -> DbResult {
+    DbResult::Notified {
+        channel: n.channel().to_string(),
+        payload: n.payload().to_string(),
+    }
+}
+
+/// Retry until the connection is back. Returns None once the engine is gone.
+async fn reconnect_with_backoff(
+    reconnect: &Reconnect,
+    result_tx: &mpsc::UnboundedSender<DbResult>,
+) -> Option<tokio_postgres::Client> {
+    let mut backoff = Backoff::default();
+    let mut error = "connection to server lost".to_string();
+    loop {
+        let retry_in = backoff.next_delay();
+        let status = DbResult::Reconnecting {
+            attempt: backoff.attempt(),
+            retry_in,
+            error: error.clone(),
+        };
+        if result_tx.send(status).is_err() {
+            return None;
+        }
+        tokio::time::sleep(retry_in).await;
+        match reconnect.connect().await {
+            Ok(client) => return Some(client),
+            Err(e) => error = e.to_string(),
+        }
+    }
+}
+
+async fn execute_command(
+    client: &tokio_postgres::Client,
+    extensions: &DetectedExtensions,
+    capabilities: &Capabilities,
+    pg_major_version: u32,
+    cmd: DbCommand,
+) -> DbResult {
+    match cmd {
+        // Without a worker's history to prepare from, as a first snapshot
+        DbCommand::FetchSnapshot { all_backends, server_log } => {
+            match db::queries::fetch_snapshot(client, extensions, capabilities, pg_major_version, all_backends, true).await {
+                Ok(mut snap) => {
+                    CatalogCache::default().resolve_snapshot(client, &mut snap).await;
+                    if server_log {
+                        tail_server_log(client, capabilities, &mut server_log::LogTail::default(), &mut snap).await;
+                    }
+                    DbResult::Snapshot(Box::new(Ok(SnapshotPrep::default().prepare(snap, true))))
+                }
+                Err(e) => DbResult::Snapshot(Box::new(Err(e.to_string()))),
+            }
+        }
+        DbCommand::CancelQuery(pid) => DbResult::CancelQuery(
+            pid,
+            db::queries::cancel_backend(client, pid)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::TerminateBackend(pid) => DbResult::TerminateBackend(
+            pid,
+            db::queries::terminate_backend(client, pid)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::CancelQueries(pids) => {
+            DbResult::CancelQueries(db::queries::cancel_backends(client, &pids).await)
+        }
+        DbCommand::TerminateBackends(pids) => {
+            DbResult::TerminateBackends(db::queries::terminate_backends(client, &pids).await)
+        }
+        DbCommand::RefreshBloat => {
+            let usable = capabilities.usable_extensions(extensions);
+            let table_bloat = db::queries::fetch_table_bloat(client, &usable).await;
+            let index_bloat = db::queries::fetch_index_bloat(client, &usable).await;
+            match (table_bloat, index_bloat) {
+                (Ok(tb), Ok(ib)) => DbResult::BloatData(Ok((tb, ib))),
+                (Err(e), Ok(_)) => {
+                    DbResult::BloatData(Err(format!("Table bloat query failed: {e}")))
+                }
+                (Ok(_), Err(e)) => {
+                    DbResult::BloatData(Err(format!("Index bloat query failed: {e}")))
+                }
+                (Err(e1), Err(_)) => {
+                    DbResult::BloatData(Err(format!("Bloat queries failed: {e1}")))
+                }
+            }
+        }
+        DbCommand::ResetStatStatements => DbResult::ResetStatStatements(
+            db::queries::reset_stat_statements(client, extensions)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::DropReplicationSlot(name) => {
+            let result = db::queries::drop_replication_slot(client, &name)
+                .await
+                .map_err(|e| e.to_string());
+            DbResult::DropReplicationSlot(name, result)
+        }
+        DbCommand::FetchSettings => DbResult::Settings(
+            db::queries::fetch_pg_settings(client)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::FetchRoleSettings => DbResult::RoleSettings(
+            db::queries::fetch_role_settings(client)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::FetchRoles => DbResult::Roles(
+            db::queries::fetch_roles(client)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::FetchRoleGrants(role) => {
+            let result = db::queries::fetch_role_grants(client, &role)
+                .await
+                .map_err(|e| e.to_string());
+            DbResult::RoleGrants(role, result)
+        }
+        DbCommand::FetchWraparoundTables(datname) => {
+            let result = db::queries::fetch_wraparound_tables(client)
+                .await
+                .map_err(|e| e.to_string());
+            DbResult::WraparoundTables(datname, result)
+        }
+        DbCommand::SampleStatements(queryids) => DbResult::StatementSamples(
+            db::queries::fetch_statement_samples(client, extensions, pg_major_version, &queryids)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::Explain(query) => {
+            DbResult::Explain(db::queries::explain(client, &query, pg_major_version).await)
+        }
+        DbCommand::AdviseIndexes(query) => {
+            DbResult::IndexAdvice(db::queries::advise_indexes(client, &query, pg_major_version).await)
+        }
+        // The live source gives these a connection of their own
+        DbCommand::RunMaintenance(maintenance) => {
+            let result = match maintenance.sql(pg_major_version) {
+                Some(sql) => db::queries::run_maintenance(client, &sql).await,
+                None => Err("nothing to run".into()),
+            };
+            DbResult::Maintenance(maintenance, result)
+        }
+        // The live source hands these to its wait sampler; anywhere else a
+        // one-off look at pg_stat_activity will do
+        DbCommand::SampleWaits => DbResult::WaitSamples(
+            db::queries::fetch_wait_events(client)
+                .await
+                .map(|counts| (WaitSampleSource::PgStatActivity, counts))
+                .map_err(|e| e.to_string()),
+        ),
+        DbCommand::DescribeTable { schema, table } => {
+            let key = format!("{schema}.{table}");
+            let result = match db::queries::describe_table(client, &schema, &table).await {
+                Ok(Some(definition)) => Ok(definition),
+                Ok(None) => Err("Table no longer exists".into()),
+                Err(e) => Err(e.to_string()),
+            };
+            DbResult::TableSchema(key, result)
+        }
+        DbCommand::MeasureBloat { schema, table } => {
+            let result = measure_bloat(client, &schema, &table).await;
+            DbResult::MeasuredBloat { schema, table, result }
+        }
+        DbCommand::ReadBufferCache if !capabilities.buffercache => {
+            DbResult::BufferCache(Err(BUFFERCACHE_DENIED.into()))
+        }
+        DbCommand::ReadBufferCache => {
+            DbResult::BufferCache(read_buffer_cache(client, &mut CatalogCache::default()).await)
+        }
+        DbCommand::FetchActionLog(_) if !capabilities.read_server_log => {
+            DbResult::ActionLog(Err(SERVER_LOG_DENIED.into()))
+        }
+        DbCommand::FetchActionLog(pids) => DbResult::ActionLog(
+            match db::queries::fetch_log_tail(client, server_log::TAIL_BYTES).await {
+                Ok(Some(log)) => Ok(pids
+                    .into_iter()
+                    .map(|pid| (pid, server_log::action_lines(&log, pid)))
+                    .collect()),
+                Ok(None) => Err("the server writes no log file (logging_collector is off)".into()),
+                Err(e) => Err(e.to_string()),
+            },
+        ),
+    }
+}
+
+/// `pg_buffercache` contents, relations named from `catalog`.
+async fn read_buffer_cache(
+    client: &tokio_postgres::Client,
+    catalog: &mut CatalogCache,
+) -> Result<BufferCacheReport, String> {
+    let mut report = db::queries::fetch_buffer_contents(client)
+        .await
+        .map_err(|e| e.to_string())?;
+    catalog.resolve_buffer_cache(client, &mut report).await;
+    Ok(report)
+}
+
+/// Why reads of the server log are skipped for this role.
+const SERVER_LOG_DENIED: &str = "reading the server log needs superuser or GRANT EXECUTE ON FUNCTION pg_read_binary_file(text, bigint, bigint, boolean), pg_stat_file(text, boolean), pg_current_logfile()";
+
+/// Why `pg_buffercache` is skipped for this role.
+const BUFFERCACHE_DENIED: &str = "reading pg_buffercache needs superuser or pg_monitor";
+
+/// Put the server log lines written since the last read into `snap`, or
+/// why they could not be read.
+/// Read PgBouncer's pools into the snapshot, connecting to its admin
+/// console again after a drop. Failures show in the Pooler panel rather
+/// than failing the snapshot.
+async fn read_pooler(admin: &Reconnect, client: &mut Option<tokio_postgres::Client>, snap: &mut PgSnapshot) {
+    if !matches!(client, Some(c) if !c.is_closed()) {
+        match admin.connect().await {
+            Ok(fresh) => *client = Some(fresh),
+            Err(e) => {
+                *client = None;
+                snap.pooler_error = Some(format!("Could not connect to PgBouncer: {e}"));
+                return;
+            }
+        }
+    }
+    let Some(console) = client.as_ref() else {
+        return;
+    };
+    match db::pgbouncer::fetch_stats(console).await {
+        Ok(stats) => snap.pooler = Some(stats),
+        Err(e) => snap.pooler_error = Some(e),
+    }
+}
+
+async fn tail_server_log(
+    client: &tokio_postgres::Client,
+    capabilities: &Capabilities,
+    tail: &mut server_log::LogTail,
+    snap: &mut PgSnapshot,
+) {
+    if !capabilities.read_server_log {
+        snap.server_log_error = Some(SERVER_LOG_DENIED.into());
+        return;
+    }
+    match db::queries::fetch_log_from(client, tail.file(), tail.offset(), server_log::TAIL_BYTES).await {
+        Ok(Some((file, start, bytes))) => snap.server_log = tail.advance(file, start, &bytes),
+        Ok(None) => {
+            snap.server_log_error = Some("the server writes no log file (logging_collector is off)".into());
+        }
+        Err(e) => snap.server_log_error = Some(e.to_string()),
+    }
+}
+
+/// Destination for snapshots as they arrive (e.g. the JSONL recorder).
+pub trait SnapshotSink {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()>;
+
+    /// Store an operator action. Sinks that only care about snapshots can ignore it.
+    fn record_event(&mut self, _event: &UiEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// How long a snapshot took from request to arrival.
+    fn record_collection(&mut self, _took: Duration) {}
+
+    /// An alert rule that just fired.
+    fn record_alert(&mut self, _alert: &Alert) {}
+}
+
+impl SnapshotSink for Recorder {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        Self::record(self, snapshot)
+    }
+
+    fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        Self::record_event(self, event)
+    }
+}
+
+/// Renders the app after every loop iteration.
+pub trait UiDriver {
+    fn draw(&mut self, app: &mut App) -> Result<()>;
+}
+
+/// Draws to the real terminal.
+pub struct TerminalUi {
+    terminal: ratatui::DefaultTerminal,
+}
+
+impl TerminalUi {
+    pub fn new(terminal: ratatui::DefaultTerminal) -> Self {
+        Self { terminal }
+    }
+
+    /// Borrow the terminal, e.g. to hand it to replay mode.
+    pub fn terminal_mut(&mut self) -> &mut ratatui::DefaultTerminal {
+        &mut self.terminal
+    }
+}
+
+impl UiDriver for TerminalUi {
+    fn draw(&mut self, app: &mut App) -> Result<()> {
+        self.terminal.draw(|frame| ui::render(frame, app))?;
+        Ok(())
+    }
+}
+
+/// Renders nothing. Used when no terminal is attached.
+pub struct HeadlessUi;
+
+impl UiDriver for HeadlessUi {
+    fn draw(&mut self, _app: &mut App) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A monitored connection: its app state, snapshot pipeline and recorder.
+/// Connections not on screen keep collecting in the background.
+pub struct Connection {
+    pub app: App,
+    pub source: SnapshotSource,
+    pub recorder: Option<Box<dyn SnapshotSink + Send>>,
+}
+
+/// Status line for the connection switcher
+fn connection_status(app: &App) -> String {
+    if let Some(r) = app.feedback.reconnecting {
+        format!("reconnecting (attempt {})", r.attempt)
+    } else if app.feedback.last_error.is_some() {
+        "error".into()
+    } else if let Some(snap) = &app.snapshot {
+        format!(
+            "{} conns · {} active",
+            snap.summary.total_backends, snap.summary.active_query_count
+        )
+    } else {
+        "connecting…".into()
+    }
+}
+
+/// The live-mode event loop.
+pub struct Engine {
+    pub app: App,
+    source: SnapshotSource,
+    recorder: Option<Box<dyn SnapshotSink + Send>>,
+    /// Extra sinks that see every snapshot and operator action, regardless
+    /// of whether actions are being recorded
+    observers: Vec<Box<dyn SnapshotSink + Send>>,
+    refresh_interval_secs: u64,
+    /// Every monitored connection by switcher position; the one on screen
+    /// lives in `app`/`source`/`recorder` and its slot is empty
+    connections: Vec<Option<Connection>>,
+    /// When the outstanding snapshot request was sent
+    snapshot_requested_at: Option<Instant>,
+    /// Browser mirror of whatever is on screen
+    web: Option<WebMirror>,
+    /// Where errors on the on-screen connection are kept for `--diagnostics`
+    error_log: Option<ErrorLog>,
+}
+
+impl Engine {
+    pub fn new(
+        app: App,
+        source: SnapshotSource,
+        recorder: Option<Box<dyn SnapshotSink + Send>>,
+    ) -> Self {
+        let refresh_interval_secs = app.refresh_interval_secs;
+        Self {
+            app,
+            source,
+            recorder,
+            observers: Vec::new(),
+            refresh_interval_secs,
+            connections: vec![None],
+            snapshot_requested_at: None,
+            web: None,
+            error_log: None,
+        }
+    }
+
+    /// Keep snapshot, connection and recording errors in `log`.
+    pub fn set_error_log(&mut self, log: ErrorLog) {
+        self.error_log = Some(log);
+    }
+
+    /// Publish the on-screen dashboard to the web mirror after every
+    /// snapshot and connection switch.
+    pub fn set_web_mirror(&mut self, mirror: WebMirror) {
+        mirror.publish(&self.app);
+        self.web = Some(mirror);
+    }
+
+    /// Monitor another connection in the background, switchable with Ctrl-n.
+    pub fn add_connection(&mut self, connection: Connection) {
+        connection.source.send_watch(&connection.app);
+        self.connections.push(Some(connection));
+        self.sync_switcher();
+        for (i, slot) in self.connections.iter_mut().enumerate() {
+            slot.as_mut().map_or(&mut self.app, |c| &mut c.app).switcher.current = i;
+        }
+    }
+
+    /// Refresh every connection's label and status in the on-screen app's
+    /// switcher, and hand the list to the background apps.
+    fn sync_switcher(&mut self) {
+        let entries: Vec<ConnectionEntry> = self
+            .connections
+            .iter()
+            .map(|slot| {
+                let app = slot.as_ref().map_or(&self.app, |c| &c.app);
+                ConnectionEntry {
+                    label: crate::observe::connection_target(&app.connection),
+                    status: connection_status(app),
+                }
+            })
+            .collect();
+        for slot in &mut self.connections {
+            slot.as_mut().map_or(&mut self.app, |c| &mut c.app).switcher.entries.clone_from(&entries);
+        }
+    }
+
+    /// Put connection `index` on screen; the current one keeps collecting in
+    /// the background.
+    fn switch_connection(&mut self, index: usize) {
+        let Some(mut next) = self.connections.get_mut(index).and_then(Option::take) else {
+            return;
+        };
+        let current = self.app.switcher.current;
+        std::mem::swap(&mut self.app, &mut next.app);
+        std::mem::swap(&mut self.source, &mut next.source);
+        std::mem::swap(&mut self.recorder, &mut next.recorder);
+
+        // Config is global: carry over anything changed on the other screen
+        self.app.config = next.app.config.clone();
+        self.app.view_mode = ViewMode::Normal;
+        self.connections[current] = Some(next);
+        self.sync_switcher();
+        self.app.feedback.status_message = Some(format!(
+            "Switched to {}",
+            crate::observe::connection_target(&self.app.connection)
+        ));
+        if let Some(web) = &self.web {
+            web.publish(&self.app);
+        }
+    }
+
+    /// Apply whatever the background connections have delivered.
+    fn drain_background(&mut self) {
+        for conn in self.connections.iter_mut().flatten() {
+            while let Ok(res) = conn.source.results.try_recv() {
+                match res {
+                    DbResult::Snapshot(result) => match *result {
+                        Ok(prepared) => {
+                            conn.app.collector.record(true, None);
+                            conn.app.apply_prepared(prepared);
+                            for notification in conn.app.feedback.take_notifications() {
+                                notifications::deliver(&notification);
+                            }
+                            if let (Some(rec), Some(snap)) = (conn.recorder.as_mut(), conn.app.snapshot.as_ref()) {
+                                if let Err(e) = rec.record(snap) {
+                                    conn.app.feedback.status_message =
+                                        Some(format!("Recording failed: {e}"));
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            conn.app.collector.record(false, None);
+                            conn.app.update_error(e);
+                        }
+                    },
+                    DbResult::Settings(Ok(settings)) => conn.app.server_info.settings = settings,
+                    DbResult::RoleSettings(Ok(overrides)) => {
+                        conn.app.server_info.role_settings = overrides;
+                    }
+                    DbResult::Roles(Ok(roles)) => conn.app.server_info.roles = roles,
+                    DbResult::Reconnecting {
+                        attempt,
+                        retry_in,
+                        error,
+                    } => conn.app.set_reconnecting(attempt, retry_in.as_secs(), error),
+                    DbResult::Reconnected => {
+                        conn.app.set_reconnected();
+                        let _ = conn.source.commands.try_send(fetch_snapshot(&conn.app));
+                    }
+                    DbResult::Hooks(outcomes) => {
+                        let _ = conn.app.collector.set_hooks(outcomes);
+                    }
+                    DbResult::Notified { .. } if !conn.app.paused => {
+                        let _ = conn.source.commands.try_send(fetch_snapshot(&conn.app));
+                    }
+                    // Actions only go to the connection on screen; a result
+                    // still in flight when switching away is dropped
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Send `cmd` to every background connection.
+    fn send_background(&self, cmd: impl Fn(&App) -> DbCommand) {
+        for conn in self.connections.iter().flatten() {
+            let _ = conn.source.commands.try_send(cmd(&conn.app));
+        }
+    }
+
+    /// Attach a sink that follows the whole session (e.g. the exit report).
+    pub fn add_observer(&mut self, sink: Box<dyn SnapshotSink + Send>) {
+        self.observers.push(sink);
+    }
+
+    /// Ask the snapshot source for a fresh snapshot.
+    pub fn request_snapshot(&mut self) {
+        self.snapshot_requested_at.get_or_insert_with(Instant::now);
+        self.source.send_watch(&self.app);
+        self.send(fetch_snapshot(&self.app));
+    }
+
+    fn send(&self, cmd: DbCommand) {
+        let _ = self.source.commands.try_send(cmd);
+    }
+
+    /// Look up what the server logged about a cancel or terminate, when
+    /// that's switched on.
+    fn confirm_from_log(&self, pids: Vec<i32>) {
+        if self.app.config.confirm_from_log && !pids.is_empty() {
+            self.send(DbCommand::FetchActionLog(pids));
+        }
+    }
+
+    /// Run until the app stops. Key events are optional so the loop can be
+    /// driven without a terminal.
+    pub async fn run(
+        &mut self,
+        ui: &mut dyn UiDriver,
+        mut events: Option<&mut event::EventHandler>,
+    ) -> Result<()> {
+        let mut tick_interval =
+            tokio::time::interval(Duration::from_secs(self.refresh_interval_secs));
+        let mut spinner_interval = tokio::time::interval(Duration::from_millis(80));
+        let mut sampler_interval =
+            tokio::time::interval(Duration::from_millis(LATENCY_SAMPLE_MILLIS));
+        let mut wait_interval = tokio::time::interval(Duration::from_millis(WAIT_SAMPLE_MILLIS));
+        // Settings were read at startup, so skip the immediate first tick
+        let mut settings_interval = tokio::time::interval_at(
+            tokio::time::Instant::now() + Duration::from_secs(SETTINGS_REFRESH_SECS),
+            Duration::from_secs(SETTINGS_REFRESH_SECS),
+        );
+
+        while self.app.running {
+            ui.draw(&mut self.app)?;
+
+            tokio::select! {
+                biased;
+
+                event = async {
+                    match events.as_deref_mut() {
+                        Some(handler) => handler.next().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    match event {
+                        Some(event::AppEvent::Key(key)) => self.app.handle_key(key),
+                        Some(event::AppEvent::Mouse(mouse)) => self.app.handle_mouse(mouse),
+                        None => {}
+                    }
+                }
+                result = self.source.results.recv() => {
+                    match result {
+                        Some(res) => self.handle_result(res),
+                        // Source went away; nothing more will arrive
+                        None => self.app.running = false,
+                    }
+                }
+                _ = tick_interval.tick() => {
+                    if !self.app.paused && self.app.feedback.reconnecting.is_none() {
+                        self.request_snapshot();
+                    }
+                    self.send_background(fetch_snapshot);
+                }
+                _ = settings_interval.tick() => {
+                    self.send(DbCommand::FetchSettings);
+                    self.send(DbCommand::FetchRoleSettings);
+                    self.send_background(|_| DbCommand::FetchSettings);
+                    self.send_background(|_| DbCommand::FetchRoleSettings);
+                    self.send(DbCommand::FetchRoles);
+                    self.send_background(|_| DbCommand::FetchRoles);
+                }
+                _ = sampler_interval.tick() => self.sample_statements(),
+                _ = wait_interval.tick() => self.sample_waits(),
+                _ = spinner_interval.tick() => {
+                    if self.app.feedback.bloat_loading
+                        || self.app.feedback.buffer_cache_loading
+                        || self.app.alerts.unseen
+                    {
+                        self.app.feedback.spinner_frame = self.app.feedback.spinner_frame.wrapping_add(1);
+                    }
+                }
+            }
+
+            self.flush_ui_events();
+            if self.app.switcher.is_multi() {
+                self.drain_background();
+                self.sync_switcher();
+            }
+
+            if let Some(action) = self.app.feedback.take_action() {
+                if self.handle_action(action) {
+                    tick_interval =
+                        tokio::time::interval(Duration::from_secs(self.refresh_interval_secs));
+                }
+            }
+            if self.sync_adaptive_interval() {
+                // A refresh just happened; the next one is a full period away
+                let period = Duration::from_secs(self.refresh_interval_secs);
+                tick_interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            }
+        }
+        Ok(())
+    }
+
+    /// Follow the interval adaptive refresh chose, or the configured one once
+    /// it is switched off. True when the timer needs rebuilding.
+    fn sync_adaptive_interval(&mut self) -> bool {
+        let secs = self.app.effective_refresh_secs();
+        if secs == self.refresh_interval_secs {
+            return false;
+        }
+        self.refresh_interval_secs = secs;
+        true
+    }
+
+    /// Apply a result from the snapshot source to the app.
+    pub fn handle_result(&mut self, res: DbResult) {
+        if let DbResult::Snapshot(result) = &res {
+            let took = self.snapshot_requested_at.take().map(|sent| sent.elapsed());
+            if let Some(took) = took {
+                for observer in &mut self.observers {
+                    observer.record_collection(took);
+                }
+            }
+            self.app.collector.record(result.is_ok(), took);
+        }
+        let ran_on_primary = res.ran_on_primary();
+        let app = &mut self.app;
+        match res {
+            DbResult::Snapshot(result) => match *result {
+                Ok(prepared) => {
+                    app.apply_prepared(prepared);
+                    for notification in app.feedback.take_notifications() {
+                        notifications::deliver(&notification);
+                    }
+                    // Record the merged snapshot so bloat estimates carried
+                    // over from earlier refreshes end up in the recording
+                    if let (Some(rec), Some(snap)) = (self.recorder.as_mut(), app.snapshot.as_ref())
+                    {
+                        if let Err(e) = rec.record(snap) {
+                            let message = format!("Recording failed: {e}");
+                            if let Some(log) = self.error_log.as_mut() {
+                                let _ = log.append(&message);
+                            }
+                            app.feedback.status_message = Some(message);
+                        }
+                    }
+                    if let Some(snap) = app.snapshot.as_ref() {
+                        for observer in &mut self.observers {
+                            let _ = observer.record(snap);
+                        }
+                    }
+                    for alert in app.feedback.take_fired_alerts() {
+                        for observer in &mut self.observers {
+                            observer.record_alert(&alert);
+                        }
+                    }
+                    if let Some(web) = &self.web {
+                        web.publish(app);
+                    }
+                }
+                Err(e) => {
+                    if let Some(log) = self.error_log.as_mut() {
+                        let _ = log.append(&format!("Snapshot failed: {e}"));
+                    }
+                    app.update_error(e);
+                }
+            },
+            DbResult::CancelQuery(pid, Ok(true)) => {
+                app.feedback.status_message = Some(format!("Cancelled query on PID {pid}"));
+                self.request_snapshot();
+                self.confirm_from_log(vec![pid]);
+            }
+            DbResult::CancelQuery(pid, Ok(false)) | DbResult::TerminateBackend(pid, Ok(false)) => {
+                app.feedback.status_message =
+                    Some(format!("PID {pid} not found or already finished"));
+            }
+            DbResult::CancelQuery(_, Err(e)) => {
+                app.feedback.status_message = Some(format!("Cancel failed: {e}"));
+            }
+            DbResult::TerminateBackend(pid, Ok(true)) => {
+                app.feedback.status_message = Some(format!("Terminated backend PID {pid}"));
+                self.request_snapshot();
+                self.confirm_from_log(vec![pid]);
+            }
+            DbResult::TerminateBackend(_, Err(e)) => {
+                app.feedback.status_message = Some(format!("Terminate failed: {e}"));
+            }
+            DbResult::CancelQueries(results) => {
+                let total = results.len();
+                let succeeded = results.iter().filter(|(_, ok)| *ok).count();
+                if succeeded == total {
+                    app.feedback.status_message =
+                        Some(format!("Cancelled {succeeded}/{total} queries"));
+                } else {
+                    app.feedback.status_message = Some(format!(
+                        "Cancelled {}/{} queries ({} already finished)",
+                        succeeded,
+                        total,
+                        total - succeeded
+                    ));
+                }
+                self.request_snapshot();
+                self.confirm_from_log(results.iter().filter(|(_, ok)| *ok).map(|(pid, _)| *pid).collect());
+            }
+            DbResult::TerminateBackends(results) => {
+                let total = results.len();
+                let succeeded = results.iter().filter(|(_, ok)| *ok).count();
+                if succeeded == total {
+                    app.feedback.status_message =
+                        Some(format!("Terminated {succeeded}/{total} backends"));
+                } else {
+                    app.feedback.status_message = Some(format!(
+                        "Terminated {}/{} backends ({} already finished)",
+                        succeeded,
+                        total,
+                        total - succeeded
+                    ));
+                }
+                self.request_snapshot();
+                self.confirm_from_log(results.iter().filter(|(_, ok)| *ok).map(|(pid, _)| *pid).collect());
+            }
+            DbResult::BloatData(Ok((table_bloat, index_bloat))) => {
+                app.feedback.bloat_loading = false;
+                app.apply_bloat_data(&table_bloat, &index_bloat);
+                let table_count = table_bloat.len();
+                let index_count = index_bloat.len();
+                app.feedback.status_message = Some(format!(
+                    "Bloat estimates refreshed ({table_count} tables, {index_count} indexes)"
+                ));
+            }
+            DbResult::BloatData(Err(e)) => {
+                app.feedback.bloat_loading = false;
+                app.feedback.status_message = Some(format!("Bloat estimation failed: {e}"));
+            }
+            DbResult::ResetStatStatements(Ok(())) => {
+                app.feedback.status_message = Some("Statement statistics reset".into());
+                self.request_snapshot();
+            }
+            DbResult::ResetStatStatements(Err(e)) => {
+                app.feedback.status_message = Some(format!("Reset failed: {e}"));
+            }
+            DbResult::DropReplicationSlot(name, Ok(())) => {
+                app.feedback.status_message = Some(format!("Dropped replication slot {name}"));
+                self.request_snapshot();
+            }
+            DbResult::DropReplicationSlot(name, Err(e)) => {
+                app.feedback.status_message = Some(format!("Could not drop slot {name}: {e}"));
+            }
+            DbResult::ActionLog(Ok(found)) => {
+                let found: Vec<_> = found.into_iter().filter(|(_, lines)| !lines.is_empty()).collect();
+                if found.is_empty() {
+                    app.feedback.status_message =
+                        Some("Nothing in the server log about it yet".into());
+                }
+                for (pid, lines) in found {
+                    let kind = UiEventKind::ServerLog { pid, lines };
+                    app.feedback.status_message = Some(kind.describe());
+                    app.log_action_event(kind);
+                }
+            }
+            DbResult::ActionLog(Err(e)) => {
+                app.feedback.status_message = Some(format!("Could not read the server log: {e}"));
+            }
+            DbResult::Settings(Ok(settings)) => self.apply_settings(settings),
+            // Best effort: keep the last known settings
+            DbResult::Settings(Err(_)) => {}
+            DbResult::RoleSettings(Ok(overrides)) => app.server_info.role_settings = overrides,
+            // Best effort: reading pg_db_role_setting may not be allowed
+            DbResult::RoleSettings(Err(_)) => {}
+            DbResult::Roles(Ok(roles)) => app.server_info.roles = roles,
+            // Best effort: keep the roles read at connect
+            DbResult::Roles(Err(_)) => {}
+            DbResult::RoleGrants(role, result) => app.set_role_grants(role, result),
+            DbResult::WraparoundTables(datname, result) => app.set_wraparound_tables(datname, result),
+            DbResult::StatementSamples(Ok(samples)) => {
+                app.latency.push(chrono::Utc::now(), &samples);
+            }
+            // Best effort: the next tick tries again
+            DbResult::StatementSamples(Err(_)) => {}
+            DbResult::WaitSamples(Ok((source, counts))) => {
+                app.wait_sampler.push(chrono::Utc::now(), source, counts);
+            }
+            DbResult::WaitSamples(Err(e)) => app.wait_sampler.error = Some(e),
+            DbResult::Reconnecting {
+                attempt,
+                retry_in,
+                error,
+            } => {
+                if let Some(log) = self.error_log.as_mut() {
+                    let _ = log.append(&format!("Connection lost: {error}"));
+                }
+                app.set_reconnecting(attempt, retry_in.as_secs(), error);
+                // Time spent reconnecting is not collection time
+                self.snapshot_requested_at = None;
+            }
+            DbResult::Reconnected => {
+                app.set_reconnected();
+                self.request_snapshot();
+                self.send(DbCommand::FetchSettings);
+                self.send(DbCommand::FetchRoleSettings);
+                self.send(DbCommand::FetchRoles);
+            }
+            DbResult::Explain(result) => app.set_explain_result(result),
+            DbResult::IndexAdvice(result) => app.set_index_advice_result(result),
+            DbResult::TableSchema(key, result) => app.set_table_schema(key, result),
+            DbResult::BufferCache(result) => app.set_buffer_cache(result),
+            DbResult::MeasuredBloat { schema, table, result } => {
+                app.set_measured_bloat(&schema, &table, result);
+            }
+            DbResult::Notified { channel, payload } => {
+                if app.paused || app.feedback.reconnecting.is_some() {
+                    return;
+                }
+                app.feedback.status_message = Some(if payload.is_empty() {
+                    format!("NOTIFY {channel}: refreshing")
+                } else {
+                    format!("NOTIFY {channel} ({payload}): refreshing")
+                });
+                self.request_snapshot();
+            }
+            DbResult::ListenFailed(e) => {
+                app.feedback.status_message = Some(format!("LISTEN failed, retrying: {e}"));
+            }
+            DbResult::Hooks(outcomes) => {
+                if let Some(e) = app.collector.set_hooks(outcomes) {
+                    app.feedback.status_message =
+                        Some(format!("Snapshot hook failed (D for details): {e}"));
+                }
+            }
+            DbResult::MaintenanceStarted(pid) => {
+                if let Some(running) = app.feedback.maintenance.as_mut() {
+                    running.pid = Some(pid);
+                }
+            }
+            DbResult::MaintenanceProgress(pid, progress) => {
+                // A late read after the command ended, or of an earlier one
+                if let Some(running) = app.feedback.maintenance.as_mut().filter(|r| r.pid == Some(pid)) {
+                    running.progress = progress;
+                }
+            }
+            DbResult::Maintenance(command, result) => {
+                app.finish_maintenance(&command, result);
+                self.request_snapshot();
+            }
+        }
+        // Say where maintenance ran when it wasn't the server on screen
+        if ran_on_primary {
+            let conn = &self.app.connection;
+            if let (Some(via), Some(msg)) = (&conn.actions_via, self.app.feedback.status_message.as_mut()) {
+                msg.push_str(&format!(" (via {via})"));
+            }
+        }
+    }
+
+    /// Sample starred statements when the latency sampler is on. Samples
+    /// are diffed, so they come from `pg_stat_statements`' cumulative
+    /// counters; without it there is nothing to sample, as
+    /// `pg_stat_monitor`'s sums drop when a bucket expires.
+    fn sample_statements(&self) {
+        if !self.app.config.latency_sampler
+            || self.app.paused
+            || !self.app.server_info.extensions.pg_stat_statements
+        {
+            return;
+        }
+        let queryids = self.app.starred.queryids();
+        if !queryids.is_empty() {
+            self.send(DbCommand::SampleStatements(queryids));
+        }
+    }
+
+    /// Sample wait events when the wait sampler is on.
+    fn sample_waits(&self) {
+        if self.app.config.wait_sampler && !self.app.paused {
+            self.send(DbCommand::SampleWaits);
+        }
+    }
+
+    /// Replace the known settings and record any values that changed, so
+    /// replays can show when a setting was flipped mid-incident. Changes are
+    /// recorded even when operator actions are not.
+    fn apply_settings(&mut self, settings: Vec<PgSetting>) {
+        let changes = UiEventKind::setting_changes(&self.app.server_info.settings, &settings);
+        self.app.server_info.settings = settings;
+
+        let now = chrono::Utc::now();
+        for kind in changes {
+            self.app.feedback.status_message = Some(kind.describe());
+            let event = UiEvent {
+                timestamp: now,
+                kind,
+                via: None,
+            };
+            if let Some(rec) = self.recorder.as_mut() {
+                if let Err(e) = rec.record_event(&event) {
+                    self.app.feedback.status_message = Some(format!("Recording failed: {e}"));
+                }
+            }
+            for observer in &mut self.observers {
+                let _ = observer.record_event(&event);
+            }
+        }
+    }
+
+    /// Hand logged operator actions to the observers, and to the recorder
+    /// when enabled. Annotations are always recorded.
+    fn flush_ui_events(&mut self) {
+        let mut events = self.app.feedback.take_ui_events();
+        for observer in &mut self.observers {
+            for event in &events {
+                let _ = observer.record_event(event);
+            }
+        }
+        if !self.app.config.record_ui_events {
+            events.retain(|e| matches!(e.kind, UiEventKind::Annotation { .. }));
+        }
+        let Some(rec) = self.recorder.as_mut() else {
+            return;
+        };
+        for event in &events {
+            if let Err(e) = rec.record_event(event) {
+                self.app.feedback.status_message = Some(format!("Recording failed: {e}"));
+                break;
+            }
+        }
+    }
+
+    /// Perform a side effect requested by the app.
+    /// Returns true if the refresh interval changed and the tick timer must be rebuilt.
+    pub fn handle_action(&mut self, action: AppAction) -> bool {
+        let event = match &action {
+            AppAction::CancelQuery(pid) => Some(UiEventKind::CancelQuery { pids: vec![*pid] }),
+            AppAction::CancelQueries(pids) => Some(UiEventKind::CancelQuery { pids: pids.clone() }),
+            AppAction::TerminateBackend(pid) => {
+                Some(UiEventKind::TerminateBackend { pids: vec![*pid] })
+            }
+            AppAction::TerminateBackends(pids) => {
+                Some(UiEventKind::TerminateBackend { pids: pids.clone() })
+            }
+            AppAction::ResetStatStatements => Some(UiEventKind::ResetStatStatements),
+            AppAction::DropReplicationSlot(slot) => {
+                Some(UiEventKind::DropReplicationSlot { slot: slot.clone() })
+            }
+            AppAction::RunMaintenance(command) => Some(UiEventKind::Maintenance {
+                command: command.describe(),
+            }),
+            _ => None,
+        };
+        if let Some(kind) = event {
+            self.app.log_action_event(kind);
+            self.flush_ui_events();
+        }
+
+        match action {
+            AppAction::ForceRefresh => self.request_snapshot(),
+            AppAction::CancelQuery(pid) => self.send(DbCommand::CancelQuery(pid)),
+            AppAction::TerminateBackend(pid) => self.send(DbCommand::TerminateBackend(pid)),
+            AppAction::CancelQueries(pids) => self.send(DbCommand::CancelQueries(pids)),
+            AppAction::TerminateBackends(pids) => self.send(DbCommand::TerminateBackends(pids)),
+            AppAction::RefreshBloat => self.send(DbCommand::RefreshBloat),
+            AppAction::SaveConfig => self.app.config.save(),
+            AppAction::SaveStarred => self.app.starred.save(),
+            AppAction::SaveBaselines => self.app.baselines.save(),
+            AppAction::SaveBookmarks => self.app.bookmarks.save(),
+            AppAction::RefreshIntervalChanged => {
+                if self.app.config.refresh_interval_secs != self.refresh_interval_secs {
+                    self.refresh_interval_secs = self.app.config.refresh_interval_secs;
+                    return true;
+                }
+            }
+            AppAction::ResetStatStatements => self.send(DbCommand::ResetStatStatements),
+            AppAction::DropReplicationSlot(slot) => self.send(DbCommand::DropReplicationSlot(slot)),
+            AppAction::RunMaintenance(command) => {
+                self.app.start_maintenance(command.clone());
+                self.send(DbCommand::RunMaintenance(command));
+            }
+            AppAction::ExportGraphs => {
+                let conn = &self.app.connection;
+                let now = chrono::Local::now();
+                let stem = format!("{}_{}_{}", conn.host, conn.port, now.format("%Y%m%d_%H%M%S"));
+                self.export_graphs(&export::default_exports_dir(), &stem);
+            }
+            AppAction::ExportPanel(path) => self.export_panel(&path),
+            AppAction::WriteScript { path, sql } => {
+                let written = path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|()| std::fs::write(&path, sql));
+                self.app.feedback.status_message = Some(match written {
+                    Ok(()) => format!("Wrote {}", path.display()),
+                    Err(e) => format!("Writing {} failed: {e}", path.display()),
+                });
+            }
+            AppAction::ExportHistory(path) => {
+                let rows = table_export::history_rows(&self.app.metrics);
+                self.app.feedback.status_message = Some(match table_export::write_rows(&rows, &path) {
+                    Ok(()) => format!("Exported {} samples to {}", rows.len(), path.display()),
+                    Err(e) => format!("Export failed: {e}"),
+                });
+            }
+            AppAction::SwitchConnection(index) => self.switch_connection(index),
+            AppAction::Explain(query) => self.send(DbCommand::Explain(query)),
+            AppAction::AdviseIndexes(query) => self.send(DbCommand::AdviseIndexes(query)),
+            AppAction::DescribeTable { schema, table } => {
+                self.send(DbCommand::DescribeTable { schema, table });
+            }
+            AppAction::ReadBufferCache => self.send(DbCommand::ReadBufferCache),
+            AppAction::ReadRoleGrants(role) => self.send(DbCommand::FetchRoleGrants(role)),
+            AppAction::ReadWraparoundTables(datname) => self.send(DbCommand::FetchWraparoundTables(datname)),
+            AppAction::MeasureBloat { schema, table } => {
+                self.send(DbCommand::MeasureBloat { schema, table });
+            }
+            // Only the replay loop moves through a recording
+            AppAction::SeekReplay(_) | AppAction::ExportExcerpt => {}
+        }
+        false
+    }
+
+    fn export_graphs(&mut self, dir: &Path, stem: &str) {
+        let conn = &self.app.connection;
+        let now = chrono::Local::now();
+        let title = format!(
+            "{} \u{2014} {}",
+            crate::observe::connection_target(conn),
+            now.format("%Y-%m-%d %H:%M:%S")
+        );
+        let series = export::graph_series(&self.app.metrics);
+        let result = export::export_graphs(&series, self.app.refresh_interval_secs, &title, dir, stem);
+        self.app.feedback.status_message = Some(match result {
+            Ok(paths) => format!("Graphs exported to {}", paths[0].with_extension("{svg,png}").display()),
+            Err(e) => format!("Graph export failed: {e}"),
+        });
+    }
+
+    /// Write the visible rows of the current panel, or the graphs when the
+    /// file name asks for an image.
+    fn export_panel(&mut self, path: &Path) {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("png") {
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("graphs");
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            self.export_graphs(dir, stem);
+            return;
+        }
+        let rows = (self.app.bottom_panel.descriptor().export_rows)(&self.app);
+        self.app.feedback.status_message = Some(match table_export::write_rows(&rows, path) {
+            Ok(()) => format!("Exported {} rows to {}", rows.len(), path.display()),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::models::{ActivitySummary, BufferCacheStats, ServerInfo};
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn make_server_info() -> ServerInfo {
+        ServerInfo {
+            version: "PostgreSQL 16.1".into(),
+            start_time: Utc::now(),
+            max_connections: 100,
+            extensions: DetectedExtensions::default(),
+            settings: vec![],
+            extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
+        }
+    }
+
+    fn make_prepared() -> PreparedSnapshot {
+        SnapshotPrep::default().prepare(make_snapshot(), true)
+    }
+
+    fn make_snapshot() -> PgSnapshot {
+        PgSnapshot {
+            timestamp: Utc::now(),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn make_app() -> App {
+        App::new(
+            "localhost".into(),
+            5432,
+            "postgres".into(),
+            "postgres".into(),
+            2,
+            120,
+            AppConfig::default(),
+            make_server_info(),
+        )
+    }
+
+    /// Engine wired to in-memory channels. Returns the engine plus the far ends.
+    fn make_engine(
+        recorder: Option<Box<dyn SnapshotSink + Send>>,
+    ) -> (
+        Engine,
+        mpsc::Receiver<DbCommand>,
+        mpsc::UnboundedSender<DbResult>,
+    ) {
+        let (cmd_tx, cmd_rx) = mpsc::channel(16);
+        let (result_tx, result_rx) = mpsc::unbounded_channel();
+        let source = SnapshotSource {
+            commands: cmd_tx,
+            results: result_rx,
+            watch: watch::channel(PrepWatch::default()).0,
+        };
+        (Engine::new(make_app(), source, recorder), cmd_rx, result_tx)
+    }
+
+    struct CountingSink(Arc<AtomicUsize>);
+
+    impl SnapshotSink for CountingSink {
+        fn record(&mut self, _snapshot: &PgSnapshot) -> Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn snapshot_result_updates_app_and_records() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) =
+            make_engine(Some(Box::new(CountingSink(Arc::clone(&count)))));
+
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(make_prepared()))));
+
+        assert!(engine.app.snapshot.is_some());
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn collector_stats_count_snapshots_and_announce_hook_failures_once() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.request_snapshot();
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(make_prepared()))));
+        engine.handle_result(DbResult::Snapshot(Box::new(Err("boom".into()))));
+        assert_eq!(engine.app.collector.collected, 1);
+        assert_eq!(engine.app.collector.failed, 1);
+        assert!(engine.app.collector.last_took.is_some());
+
+        let failed = || {
+            vec![HookOutcome {
+                phase: HookPhase::Before,
+                sql: "REFRESH MATERIALIZED VIEW ops.summary".into(),
+                took: Duration::from_millis(1000),
+                error: Some("canceling statement due to statement timeout".into()),
+            }]
+        };
+        engine.app.feedback.status_message = None;
+        engine.handle_result(DbResult::Hooks(failed()));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Snapshot hook failed (D for details): canceling statement due to statement timeout")
+        );
+
+        // Still failing: no new announcement
+        engine.app.feedback.status_message = None;
+        engine.handle_result(DbResult::Hooks(failed()));
+        assert!(engine.app.feedback.status_message.is_none());
+        assert_eq!(engine.app.collector.hooks.len(), 1);
+    }
+
+    #[test]
+    fn background_connection_collects_and_switches_in() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        let (bg_cmd_tx, mut bg_cmd_rx) = mpsc::channel(16);
+        let (bg_result_tx, bg_result_rx) = mpsc::unbounded_channel();
+        let mut app = make_app();
+        app.connection.host = "replica1".into();
+        engine.add_connection(Connection {
+            app,
+            source: SnapshotSource {
+                commands: bg_cmd_tx,
+                results: bg_result_rx,
+                watch: watch::channel(PrepWatch::default()).0,
+            },
+            recorder: None,
+        });
+        assert_eq!(engine.app.switcher.entries.len(), 2);
+        assert_eq!(engine.app.switcher.entries[1].label, "postgres@replica1:5432/postgres");
+        assert_eq!(engine.app.switcher.entries[1].status, "connecting…");
+
+        engine.send_background(|_| DbCommand::FetchSettings);
+        assert_eq!(bg_cmd_rx.try_recv().ok(), Some(DbCommand::FetchSettings));
+
+        bg_result_tx
+            .send(DbResult::Snapshot(Box::new(Ok(make_prepared()))))
+            .unwrap();
+        engine.drain_background();
+        engine.sync_switcher();
+        assert!(engine.app.snapshot.is_none());
+        assert_eq!(engine.app.switcher.entries[1].status, "3 conns · 0 active");
+
+        engine.handle_action(AppAction::SwitchConnection(1));
+        assert_eq!(engine.app.connection.host, "replica1");
+        assert_eq!(engine.app.switcher.current, 1);
+        assert!(engine.app.snapshot.is_some());
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Switched to postgres@replica1:5432/postgres")
+        );
+
+        // Commands now go to the replica's pipeline
+        engine.request_snapshot();
+        assert_eq!(
+            bg_cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false })
+        );
+    }
+
+    #[test]
+    fn snapshots_tail_the_server_log_when_enabled() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.config.server_log = true;
+        engine.request_snapshot();
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchSnapshot { all_backends: false, server_log: true })
+        );
+    }
+
+    #[test]
+    fn snapshot_error_sets_last_error() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_result(DbResult::Snapshot(Box::new(Err("boom".into()))));
+        assert_eq!(engine.app.feedback.last_error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn successful_cancel_requests_refresh() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_result(DbResult::CancelQuery(42, Ok(true)));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Cancelled query on PID 42")
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false }));
+    }
+
+    #[test]
+    fn only_maintenance_runs_on_the_primary() {
+        let server = |host: &str| {
+            let mut pg_config = tokio_postgres::Config::new();
+            pg_config.host(host);
+            Reconnect {
+                pg_config,
+                ssl_mode: crate::connection::SslMode::None,
+                cert_config: crate::ssl::SslCertConfig::default(),
+            }
+        };
+        let (standby, primary) = (server("standby1"), server("primary1"));
+        let vacuum = DbCommand::RunMaintenance(Maintenance::VacuumAnalyze {
+            schema: "public".into(),
+            table: "orders".into(),
+        });
+        assert!(std::ptr::eq(server_for(&vacuum, &standby, Some(&primary)), &primary));
+        assert!(std::ptr::eq(server_for(&vacuum, &standby, None), &standby));
+        // The PIDs, slots and stats on screen are the standby's own
+        for cmd in [
+            DbCommand::TerminateBackend(42),
+            DbCommand::TerminateBackends(vec![42, 43]),
+            DbCommand::CancelQuery(42),
+            DbCommand::DropReplicationSlot("replica1".into()),
+            DbCommand::ResetStatStatements,
+            DbCommand::FetchActionLog(vec![42]),
+        ] {
+            assert!(std::ptr::eq(server_for(&cmd, &standby, Some(&primary)), &standby), "{cmd:?}");
+        }
+
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.connection.set_actions_via("primary1:5432");
+        engine.handle_action(AppAction::TerminateBackend(42));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::TerminateBackend(42)));
+        engine.app.log_action_event(UiEventKind::TerminateBackend { pids: vec![42] });
+        let logged = engine.app.feedback.take_ui_events();
+        assert_eq!(logged[0].describe(), "Terminated PID 42");
+        engine.handle_result(DbResult::TerminateBackend(42, Ok(true)));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Terminated backend PID 42")
+        );
+
+        let vacuum = Maintenance::VacuumAnalyze {
+            schema: "public".into(),
+            table: "orders".into(),
+        };
+        engine.app.log_action_event(UiEventKind::Maintenance { command: vacuum.describe() });
+        let logged = engine.app.feedback.take_ui_events();
+        assert!(logged[0].describe().ends_with("(via primary1:5432)"), "{}", logged[0].describe());
+        engine.app.start_maintenance(vacuum.clone());
+        engine.handle_result(DbResult::Maintenance(vacuum, Err("canceled".into())));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("VACUUM public.orders failed: canceled (via primary1:5432)")
+        );
+    }
+
+    #[test]
+    fn notify_requests_a_snapshot_unless_paused() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_result(DbResult::Notified {
+            channel: "batch_jobs".into(),
+            payload: "nightly".into(),
+        });
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("NOTIFY batch_jobs (nightly): refreshing")
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false }));
+
+        engine.app.paused = true;
+        engine.handle_result(DbResult::Notified {
+            channel: "batch_jobs".into(),
+            payload: String::new(),
+        });
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn notify_throttle_fires_first_and_coalesces_the_rest() {
+        let start = Instant::now();
+        let secs = |n: u64| start + Duration::from_secs(n);
+        let mut throttle = NotifyThrottle::new(Duration::from_secs(5));
+
+        assert!(throttle.notify(start));
+        assert_eq!(throttle.deadline(), None);
+
+        // A burst inside the interval is held back as a single fire
+        assert!(!throttle.notify(secs(1)));
+        assert!(!throttle.notify(secs(2)));
+        assert_eq!(throttle.deadline(), Some(secs(5)));
+        throttle.fire_pending(secs(5));
+        assert_eq!(throttle.deadline(), None);
+
+        assert!(!throttle.notify(secs(7)));
+        assert!(throttle.notify(secs(10)));
+        assert_eq!(throttle.deadline(), None);
+    }
+
+    #[test]
+    fn dropping_a_slot_is_recorded_and_refreshes() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_action(AppAction::DropReplicationSlot("old_cdc".into()));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::DropReplicationSlot("old_cdc".into())));
+
+        engine.handle_result(DbResult::DropReplicationSlot("old_cdc".into(), Ok(())));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Dropped replication slot old_cdc")
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false }));
+
+        engine.handle_result(DbResult::DropReplicationSlot(
+            "replica1".into(),
+            Err("replication slot \"replica1\" is active".into()),
+        ));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Could not drop slot replica1: replication slot \"replica1\" is active")
+        );
+    }
+
+    #[test]
+    fn maintenance_is_recorded_tracked_by_pid_and_reported() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        let vacuum = Maintenance::VacuumAnalyze {
+            schema: "public".into(),
+            table: "orders".into(),
+        };
+        engine.handle_action(AppAction::RunMaintenance(vacuum.clone()));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::RunMaintenance(vacuum.clone())));
+        assert!(engine.app.feedback.maintenance.is_some());
+
+        engine.handle_result(DbResult::MaintenanceStarted(4242));
+        assert_eq!(engine.app.feedback.maintenance.as_ref().and_then(|m| m.pid), Some(4242));
+
+        // Progress comes from the server running it, not the snapshot
+        let progress = |pid| OperationProgress {
+            pid,
+            kind: crate::db::models::OperationKind::Vacuum,
+            command: "VACUUM".into(),
+            datname: Some("shop".into()),
+            target: "public.orders".into(),
+            relid: None,
+            phase: "scanning heap".into(),
+            work_done: 30,
+            work_total: 100,
+            elapsed_secs: None,
+        };
+        assert!(engine.app.maintenance_progress().is_none());
+        engine.handle_result(DbResult::MaintenanceProgress(4242, Some(progress(4242))));
+        assert_eq!(engine.app.maintenance_progress().map(|op| op.phase.as_str()), Some("scanning heap"));
+        // A late read of another command's backend
+        engine.handle_result(DbResult::MaintenanceProgress(99, None));
+        assert!(engine.app.maintenance_progress().is_some());
+
+        engine.handle_result(DbResult::Maintenance(vacuum.clone(), Err("canceling statement due to user request".into())));
+        assert!(engine.app.feedback.maintenance.is_none());
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("VACUUM public.orders failed: canceling statement due to user request")
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false }));
+
+        engine.app.start_maintenance(vacuum.clone());
+        engine.handle_result(DbResult::Maintenance(vacuum, Ok(())));
+        let status = engine.app.feedback.status_message.clone().unwrap();
+        assert!(status.starts_with("VACUUM public.orders finished in "), "{status}");
+    }
+
+    #[test]
+    fn cancels_are_confirmed_from_the_server_log_when_enabled() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_result(DbResult::CancelQuery(7, Ok(true)));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false }));
+        assert!(cmd_rx.try_recv().is_err());
+
+        engine.app.config.confirm_from_log = true;
+        engine.handle_result(DbResult::TerminateBackends(vec![(7, true), (8, false)]));
+        cmd_rx.try_recv().unwrap();
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchActionLog(vec![7])));
+
+        let lines = vec![
+            "FATAL: terminating connection due to administrator command".to_string(),
+            "LOG: could not send data to client: Broken pipe".to_string(),
+        ];
+        engine.handle_result(DbResult::ActionLog(Ok(vec![(7, lines.clone())])));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("PID 7 logged: FATAL: terminating connection due to administrator command · LOG: could not send data to client: Broken pipe")
+        );
+        let events = engine.app.feedback.take_ui_events();
+        assert_eq!(events[0].kind, UiEventKind::ServerLog { pid: 7, lines });
+
+        engine.handle_result(DbResult::ActionLog(Ok(vec![(9, vec![])])));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Nothing in the server log about it yet")
+        );
+    }
+
+    #[test]
+    fn partial_batch_terminate_reports_finished() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_result(DbResult::TerminateBackends(vec![(1, true), (2, false)]));
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Terminated 1/2 backends (1 already finished)")
+        );
+    }
+
+    #[test]
+    fn latency_sampler_samples_starred_statements_when_enabled() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.starred.toggle(7);
+        engine.app.server_info.extensions.pg_stat_statements = true;
+
+        engine.sample_statements();
+        assert!(cmd_rx.try_recv().is_err(), "sampler is off by default");
+
+        engine.app.config.latency_sampler = true;
+        engine.sample_statements();
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::SampleStatements(vec![7])));
+
+        // pg_stat_monitor alone has no cumulative counters to diff
+        engine.app.server_info.extensions.pg_stat_statements = false;
+        engine.app.server_info.extensions.pg_stat_monitor = true;
+        engine.sample_statements();
+        assert!(cmd_rx.try_recv().is_err());
+
+        engine.handle_result(DbResult::StatementSamples(Ok(vec![StatementSample {
+            queryid: 7,
+            calls: 10,
+            total_exec_time: 50.0,
+        }])));
+        assert!(engine.app.latency.is_sampling(7));
+    }
+
+    #[test]
+    fn snapshot_request_follows_all_backends_toggle() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.show_all_backends = true;
+        engine.handle_action(AppAction::ForceRefresh);
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchSnapshot { all_backends: true, server_log: false })
+        );
+    }
+
+    #[test]
+    fn actions_are_forwarded_as_commands() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_action(AppAction::CancelQueries(vec![1, 2]));
+        engine.handle_action(AppAction::RefreshBloat);
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::CancelQueries(vec![1, 2]))
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::RefreshBloat));
+    }
+
+    #[test]
+    fn adaptive_interval_rebuilds_the_timer_when_it_moves() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        assert!(!engine.sync_adaptive_interval());
+        engine.app.config.adaptive_refresh.enabled = true;
+        // An idle server: the interval lengthens
+        let mut prep = SnapshotPrep::default();
+        prep.watch(&engine.app.prep_watch());
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(prep.prepare(make_snapshot(), true)))));
+        assert!(engine.sync_adaptive_interval());
+        assert_eq!(engine.refresh_interval_secs, engine.app.effective_refresh_secs());
+        assert!(!engine.sync_adaptive_interval());
+    }
+
+    #[test]
+    fn refresh_interval_change_rebuilds_timer_only_when_different() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        assert!(!engine.handle_action(AppAction::RefreshIntervalChanged));
+        engine.app.config.refresh_interval_secs = 5;
+        assert!(engine.handle_action(AppAction::RefreshIntervalChanged));
+        assert!(!engine.handle_action(AppAction::RefreshIntervalChanged));
+    }
+
+    struct EventSink(Arc<AtomicUsize>);
+
+    impl SnapshotSink for EventSink {
+        fn record(&mut self, _snapshot: &PgSnapshot) -> Result<()> {
+            Ok(())
+        }
+
+        fn record_event(&mut self, _event: &UiEvent) -> Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ui_events_recorded_only_when_enabled() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) =
+            make_engine(Some(Box::new(EventSink(Arc::clone(&count)))));
+
+        engine.handle_action(AppAction::CancelQuery(7));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        engine.app.config.record_ui_events = true;
+        engine.handle_action(AppAction::TerminateBackends(vec![1, 2]));
+        engine.handle_action(AppAction::RefreshBloat);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn annotations_recorded_even_with_action_recording_off() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) =
+            make_engine(Some(Box::new(EventSink(Arc::clone(&count)))));
+
+        engine.app.log_ui_event(UiEventKind::PanelSwitch { panel: "Locks".into() });
+        engine.app.add_annotation("deployed v2.3".into(), chrono::Utc::now());
+        engine.flush_ui_events();
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn observers_see_snapshots_and_all_actions() {
+        let snapshots = Arc::new(AtomicUsize::new(0));
+        let events = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.add_observer(Box::new(CountingSink(Arc::clone(&snapshots))));
+        engine.add_observer(Box::new(EventSink(Arc::clone(&events))));
+
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(make_prepared()))));
+        // Action recording is off, but observers still get the action
+        engine.handle_action(AppAction::CancelQuery(7));
+
+        assert_eq!(snapshots.load(Ordering::SeqCst), 1);
+        assert_eq!(events.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn fired_alerts_reach_the_session_report() {
+        use crate::alerts::{AlertMetric, AlertRule};
+        use crate::session_report::{SessionLog, SessionSink};
+
+        let log = Arc::new(std::sync::Mutex::new(SessionLog::default()));
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.add_observer(Box::new(SessionSink::new(Arc::clone(&log))));
+        engine.app.config.alerts = vec![AlertRule {
+            name: "Busy".into(),
+            metric: AlertMetric::Connections,
+            above: Some(-1.0),
+            below: None,
+            for_secs: 0,
+        }];
+
+        // The rules reach the source with the next request
+        engine.request_snapshot();
+        let watch = engine.source.watch.borrow().clone();
+        assert_eq!(watch, engine.app.prep_watch());
+
+        let mut prep = SnapshotPrep::default();
+        prep.watch(&watch);
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(prep.prepare(make_snapshot(), true)))));
+        // Still firing: not a new alert
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(prep.prepare(make_snapshot(), true)))));
+
+        let report = log.lock().unwrap().report("x");
+        assert_eq!(report.alerts.len(), 1);
+        assert!(report.render_text().contains("Busy: connections > -1"));
+    }
+
+    struct TimingSink(Arc<AtomicUsize>);
+
+    impl SnapshotSink for TimingSink {
+        fn record(&mut self, _snapshot: &PgSnapshot) -> Result<()> {
+            Ok(())
+        }
+
+        fn record_collection(&mut self, _took: Duration) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn observers_get_one_timing_per_requested_snapshot() {
+        let timings = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        engine.add_observer(Box::new(TimingSink(Arc::clone(&timings))));
+
+        // Unrequested results carry no timing
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(make_prepared()))));
+        assert_eq!(timings.load(Ordering::SeqCst), 0);
+
+        // A second request while one is outstanding keeps the first send time
+        engine.request_snapshot();
+        engine.request_snapshot();
+        engine.handle_result(DbResult::Snapshot(Box::new(Err("timeout".into()))));
+        engine.handle_result(DbResult::Snapshot(Box::new(Ok(make_prepared()))));
+        assert_eq!(timings.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn explain_goes_to_the_database_and_fills_the_overlay() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.explain = Some(crate::app::ExplainView::Loading("SELECT 1".into()));
+        engine.handle_action(AppAction::Explain("SELECT 1".into()));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::Explain("SELECT 1".into())));
+
+        engine.handle_result(DbResult::Explain(Err("permission denied".into())));
+        assert!(matches!(
+            engine.app.explain,
+            Some(crate::app::ExplainView::Failed { ref error, .. }) if error == "permission denied"
+        ));
+    }
+
+    #[test]
+    fn index_advice_goes_to_the_database_and_fills_the_overlay() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.index_advice = Some(crate::app::IndexAdviceView::Loading("SELECT 1".into()));
+        engine.handle_action(AppAction::AdviseIndexes("SELECT 1".into()));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::AdviseIndexes("SELECT 1".into())));
+
+        engine.handle_result(DbResult::IndexAdvice(Err("function hypopg_reset() does not exist".into())));
+        assert!(matches!(
+            engine.app.index_advice,
+            Some(crate::app::IndexAdviceView::Failed { ref error, .. }) if error.contains("hypopg")
+        ));
+    }
+
+    #[test]
+    fn table_definitions_are_read_on_request() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.table_schema = Some(crate::app::TableSchemaView::Loading("public.orders".into()));
+        engine.handle_action(AppAction::DescribeTable {
+            schema: "public".into(),
+            table: "orders".into(),
+        });
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::DescribeTable {
+                schema: "public".into(),
+                table: "orders".into()
+            })
+        );
+
+        engine.handle_result(DbResult::TableSchema(
+            "public.orders".into(),
+            Ok(TableSchema::default()),
+        ));
+        assert!(matches!(
+            engine.app.table_schema,
+            Some(crate::app::TableSchemaView::Loaded { ref key, .. }) if key == "public.orders"
+        ));
+    }
+
+    #[test]
+    fn role_privileges_are_read_on_request() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.role_grants = Some(crate::app::RoleGrantsView::Loading("app_user".into()));
+        engine.handle_action(AppAction::ReadRoleGrants("app_user".into()));
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchRoleGrants("app_user".into()))
+        );
+
+        engine.handle_result(DbResult::RoleGrants(
+            "app_user".into(),
+            Err("permission denied for table pg_authid".into()),
+        ));
+        assert!(matches!(
+            engine.app.role_grants,
+            Some(crate::app::RoleGrantsView::Failed { ref role, .. }) if role == "app_user"
+        ));
+    }
+
+    #[test]
+    fn wraparound_tables_are_read_on_request() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.wraparound_tables = Some(crate::app::WraparoundTablesView::Loading("postgres".into()));
+        engine.handle_action(AppAction::ReadWraparoundTables("postgres".into()));
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchWraparoundTables("postgres".into()))
+        );
+
+        engine.handle_result(DbResult::WraparoundTables("postgres".into(), Ok(vec![])));
+        assert!(matches!(
+            engine.app.wraparound_tables,
+            Some(crate::app::WraparoundTablesView::Loaded { ref datname, .. }) if datname == "postgres"
+        ));
+    }
+
+    #[test]
+    fn exact_bloat_is_measured_on_request() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.handle_action(AppAction::MeasureBloat {
+            schema: "public".into(),
+            table: "orders".into(),
+        });
+        let cmd = cmd_rx.try_recv().ok();
+        assert_eq!(
+            cmd,
+            Some(DbCommand::MeasureBloat {
+                schema: "public".into(),
+                table: "orders".into()
+            })
+        );
+
+        engine.app.feedback.bloat_loading = true;
+        engine.handle_result(DbResult::MeasuredBloat {
+            schema: "public".into(),
+            table: "orders".into(),
+            result: Err("permission denied for function pgstattuple".into()),
+        });
+        assert!(!engine.app.feedback.bloat_loading);
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("pgstattuple on public.orders failed: permission denied for function pgstattuple")
+        );
+    }
+
+    #[test]
+    fn buffer_cache_is_read_on_request() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.app.feedback.buffer_cache_loading = true;
+        engine.handle_action(AppAction::ReadBufferCache);
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::ReadBufferCache));
+
+        engine.handle_result(DbResult::BufferCache(Err("permission denied".into())));
+        assert!(!engine.app.feedback.buffer_cache_loading);
+        assert!(engine.app.buffer_cache.is_none());
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Reading pg_buffercache failed: permission denied")
+        );
+    }
+
+    #[test]
+    fn panel_export_writes_the_visible_rows() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        let mut snap = make_snapshot();
+        snap.wait_events = vec![crate::db::models::WaitEventCount {
+            wait_event_type: "Lock".into(),
+            wait_event: "relation".into(),
+            count: 3,
+        }];
+        engine.app.update(snap);
+        engine.app.bottom_panel = crate::app::BottomPanel::WaitEvents;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("waits.json");
+
+        engine.handle_action(AppAction::ExportPanel(path.clone()));
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let rows = engine.app.snapshot.as_ref().unwrap().wait_events.len();
+        assert_eq!(written.as_array().unwrap().len(), rows);
+        assert_eq!(
+            engine.app.feedback.status_message,
+            Some(format!("Exported {rows} rows to {}", path.display()))
+        );
+    }
+
+    #[test]
+    fn history_export_writes_a_row_per_snapshot() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        let first = make_snapshot();
+        let mut second = make_snapshot();
+        second.timestamp = first.timestamp + chrono::Duration::seconds(2);
+        engine.app.update(first);
+        engine.app.update(second);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.csv");
+
+        engine.handle_action(AppAction::ExportHistory(path.clone()));
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.starts_with("timestamp,connections,"));
+        assert_eq!(
+            engine.app.feedback.status_message,
+            Some(format!("Exported 2 samples to {}", path.display()))
+        );
+    }
+
+    #[test]
+    fn reconnect_shows_progress_then_resumes_snapshots() {
+        let (mut engine, mut cmd_rx, _result_tx) = make_engine(None);
+        engine.request_snapshot();
+        let _ = cmd_rx.try_recv();
+
+        engine.handle_result(DbResult::Reconnecting {
+            attempt: 2,
+            retry_in: Duration::from_secs(2),
+            error: "connection refused".into(),
+        });
+        assert_eq!(
+            engine.app.feedback.reconnecting,
+            Some(crate::app::Reconnecting {
+                attempt: 2,
+                retry_in_secs: 2,
+            })
+        );
+        assert_eq!(engine.app.feedback.last_error.as_deref(), Some("connection refused"));
+        assert!(engine.snapshot_requested_at.is_none());
+
+        engine.handle_result(DbResult::Reconnected);
+        assert!(engine.app.feedback.reconnecting.is_none());
+        assert!(engine.app.feedback.last_error.is_none());
+        assert_eq!(engine.app.feedback.status_message.as_deref(), Some("Reconnected"));
+        assert_eq!(
+            cmd_rx.try_recv().ok(),
+            Some(DbCommand::FetchSnapshot { all_backends: false, server_log: false })
+        );
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchSettings));
+        assert_eq!(cmd_rx.try_recv().ok(), Some(DbCommand::FetchRoleSettings));
+    }
+
+    #[test]
+    fn role_settings_result_replaces_overrides() {
+        let (mut engine, _cmd_rx, _result_tx) = make_engine(None);
+        let overrides = vec![RoleSetting {
+            role: Some("app".into()),
+            database: None,
+            name: "statement_timeout".into(),
+            value: "30s".into(),
+        }];
+        engine.handle_result(DbResult::RoleSettings(Ok(overrides.clone())));
+        assert_eq!(engine.app.server_info.role_settings, overrides);
+
+        // A failed refresh keeps what we had
+        engine.handle_result(DbResult::RoleSettings(Err("permission denied".into())));
+        assert_eq!(engine.app.server_info.role_settings, overrides);
+    }
+
+    #[test]
+    fn settings_refresh_records_changes_even_without_action_recording() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let (mut engine, _cmd_rx, _result_tx) =
+            make_engine(Some(Box::new(EventSink(Arc::clone(&count)))));
+        let mut settings = vec![PgSetting::fixture("enable_seqscan", "on")];
+        engine.app.server_info.settings.clone_from(&settings);
+
+        engine.handle_result(DbResult::Settings(Ok(settings.clone())));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        settings[0].setting = "off".into();
+        engine.handle_result(DbResult::Settings(Ok(settings)));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(engine.app.server_info.settings[0].setting, "off");
+        assert_eq!(
+            engine.app.feedback.status_message.as_deref(),
+            Some("Setting enable_seqscan: on → off")
+        );
+    }
+
+    #[tokio::test]
+    async fn headless_run_stops_when_source_closes() {
+        let (mut engine, _cmd_rx, result_tx) = make_engine(None);
+        result_tx
+            .send(DbResult::Snapshot(Box::new(Ok(make_prepared()))))
+            .unwrap();
+        drop(result_tx);
+
+        engine.run(&mut HeadlessUi, None).await.unwrap();
+
+        assert!(!engine.app.running);
+        assert!(engine.app.snapshot.is_some());
+    }
+}
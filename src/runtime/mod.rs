@@ -0,0 +1,455 @@
+//! Main application runtime - live mode event loop.
+
+use crate::cli::{Cli, Command, ConnectionInfo};
+use crate::config::AppConfig;
+use crate::connection::{try_connect, Reconnect, SslMode};
+use crate::daemon::{run_record, RecordOptions, RecordStatus, StatusRecorder};
+use crate::diagnostics::Bundle;
+use crate::error_log::ErrorLog;
+use crate::app::AppAction;
+use crate::observe::{connection_target, run_observe, ObserveSink, Observation};
+use crate::once::run_once;
+use crate::replay::{replay_in_terminal, run_replay};
+use crate::session_report::{SessionLog, SessionSink};
+use crate::baseline::StatementBaselines;
+use crate::starred::StarredStatements;
+use crate::ui::theme;
+use crate::usage_stats::{UsageContext, UsageSink, UsageStats};
+use crate::web::WebMirror;
+use crate::{app, db, event, recorder};
+use color_eyre::eyre::{bail, eyre, Context, Result};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod engine;
+
+pub use engine::{Connection, DbCommand, DbResult, Engine, HeadlessUi, NotifyTrigger, SnapshotSink, SourceOptions, SnapshotSource, TerminalUi, UiDriver};
+
+/// Establish a PostgreSQL connection with SSL mode handling.
+///
+/// If `--ssl`, `--ssl-insecure` or the URI's `sslmode` picks a mode, uses
+/// that mode directly. Otherwise, auto-detects by trying: No TLS, SSL
+/// (verified), SSL (insecure).
+async fn establish_connection(
+    cli: &Cli,
+    pg_config: &tokio_postgres::Config,
+    conn_info: &ConnectionInfo,
+) -> Result<(tokio_postgres::Client, SslMode)> {
+    let cert_config = cli.ssl_cert_config();
+
+    if let Some(mode) = cli.ssl_mode() {
+        // User explicitly specified SSL mode - use it directly
+        let client = try_connect(pg_config, mode, &cert_config).await.with_context(|| {
+            format!(
+                "could not connect to PostgreSQL ({})\n\nConnection: {}:{}/{}\n\nTry: pg_glimpse -H localhost -p 5432 -d mydb -U postgres -W mypassword\nSee: pg_glimpse --help",
+                mode.label(),
+                conn_info.host,
+                conn_info.port,
+                conn_info.dbname
+            )
+        })?;
+        Ok((client, mode))
+    } else {
+        // Auto-detect: try connection modes in order
+        let modes = [SslMode::None, SslMode::Verified, SslMode::Insecure];
+        let mut last_error = None;
+
+        for mode in modes {
+            match try_connect(pg_config, mode, &cert_config).await {
+                Ok(client) => {
+                    return Ok((client, mode));
+                }
+                Err(e) => {
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        bail!(
+            "could not connect to PostgreSQL with any SSL mode: {:?}\n\nConnection: {}:{}/{}\n\nTried: No TLS, SSL (verified), SSL (insecure)\nTry: pg_glimpse -H localhost -p 5432 -d mydb -U postgres -W mypassword\nSee: pg_glimpse --help",
+            last_error.unwrap(),
+            conn_info.host,
+            conn_info.port,
+            conn_info.dbname
+        )
+    }
+}
+
+/// Run the main application in live mode.
+pub async fn run(mut cli: Cli) -> Result<()> {
+    if let Some(Command::Report { ref recording, ref output }) = cli.command {
+        return crate::report::run_report(recording, output.as_deref());
+    }
+    let mut config = AppConfig::load();
+    config.plain_flag = cli.plain;
+    if let Some(ref name) = cli.profile {
+        let profile = config.apply_profile(name).map_err(|e| eyre!(e))?.clone();
+        cli.apply_profile(&profile);
+    }
+    if let Some(ref replay_path) = cli.replay {
+        theme::set_theme(config.color_theme.colors());
+        theme::set_duration_thresholds(config.warn_duration_secs, config.danger_duration_secs);
+        return run_replay(replay_path, cli.compare.as_deref(), config).await;
+    }
+    if let Some(ref output) = cli.diagnostics {
+        return write_diagnostics(&cli, &config, output.as_deref()).await;
+    }
+    let record = match cli.command {
+        Some(Command::Record { daemon, ref pidfile, status_interval }) => {
+            if cli.observe.is_some() || cli.once {
+                bail!("record can't be combined with --observe or --once");
+            }
+            Some(RecordOptions { daemon, pidfile: pidfile.clone(), status_interval })
+        }
+        _ => None,
+    };
+
+    let pg_config = cli
+        .pg_config()
+        .context("invalid connection config\n\nTry: pg_glimpse -H localhost -p 5432 -d mydb -U postgres -W mypassword\nSee: pg_glimpse --help")?;
+    theme::set_theme(config.color_theme.colors());
+    theme::set_duration_thresholds(config.warn_duration_secs, config.danger_duration_secs);
+
+    let conn_info = cli.connection_info();
+    let (client, ssl_mode) = match establish_connection(&cli, &pg_config, &conn_info).await {
+        Ok(connected) => connected,
+        Err(e) => {
+            // First line only; the rest is usage hints
+            let message = e.to_string();
+            let first = message.lines().next().unwrap_or_default();
+            let _ = ErrorLog::new(ErrorLog::default_path()).append(first);
+            return Err(e);
+        }
+    };
+    let server_info = db::queries::fetch_server_info(&client).await?;
+    if cli.once {
+        let target = format!("{}@{}:{}/{}", conn_info.user, conn_info.host, conn_info.port, conn_info.dbname);
+        return run_once(&client, &server_info, &target, cli.format).await;
+    }
+
+    // Clean up old recordings now and while running. Observation runs don't record.
+    recorder::Recorder::spawn_cleanup(config.recording_retention_secs, config.recordings_dir.clone());
+    let rotation = recorder::Rotation::from_config(&config);
+    let mut recorder = if cli.observe.is_some() {
+        None
+    } else {
+        let recording = recorder::Recorder::new(&conn_info.host, conn_info.port, &conn_info.dbname, &conn_info.user, &server_info, config.recordings_dir.as_deref(), rotation);
+        if record.is_some() {
+            // Recording is all `record` does
+            Some(recording.context("could not start a recording")?)
+        } else {
+            recording.ok()
+        }
+    };
+
+    let refresh = cli.refresh.unwrap_or(config.refresh_interval_secs);
+    let extra_targets = cli
+        .extra_targets()
+        .context("could not read --targets-file")?;
+    let mut app = build_app(&cli, refresh, config, conn_info, server_info, ssl_mode);
+    let reconnect = Reconnect {
+        pg_config,
+        ssl_mode,
+        cert_config: cli.ssl_cert_config(),
+    };
+    let mut options = source_options(&cli, &app);
+    if let Some(ref target) = cli.primary {
+        let (primary, label) = connect_primary(&cli, target).await?;
+        app.connection.set_actions_via(&label);
+        options.primary = Some(primary);
+    }
+    if let Some(ref target) = cli.pgbouncer {
+        options.pgbouncer = Some(connect_pgbouncer(&cli, target).await?);
+    }
+    let source = spawn_source(client, &app, reconnect, options);
+
+    if let Some(window) = cli.observe {
+        let observation = Arc::new(Mutex::new(Observation::default()));
+        let sink = Box::new(ObserveSink::new(Arc::clone(&observation)));
+        let engine = Engine::new(app, source, Some(sink));
+        return run_observe(engine, observation, window, cli.observe_output.as_deref()).await;
+    }
+
+    if let (Some(options), Some(recorder)) = (record, recorder.take()) {
+        let target = connection_target(&app.connection);
+        let status = Arc::new(Mutex::new(RecordStatus::default()));
+        let sink = Box::new(StatusRecorder::new(recorder, Arc::clone(&status)));
+        let mut engine = Engine::new(app, source, Some(sink));
+        engine.set_error_log(ErrorLog::new(ErrorLog::default_path()));
+        return run_record(engine, status, &target, options).await;
+    }
+
+    let recorder = recorder.map(|r| Box::new(r) as Box<dyn SnapshotSink + Send>);
+    let config = app.config.clone();
+    let mut engine = Engine::new(app, source, recorder);
+    engine.set_error_log(ErrorLog::new(ErrorLog::default_path()));
+    for (i, target) in extra_targets.iter().enumerate() {
+        match connect_extra_target(&cli, target, refresh, &config, rotation).await {
+            Ok(connection) => engine.add_connection(connection),
+            Err(e) => {
+                // The target may carry a password, so name it by position
+                // only; first line only, the rest is usage hints
+                let message = e.to_string();
+                let first = message.lines().next().unwrap_or_default();
+                let message = format!("extra target #{} not monitored: {first}", i + 1);
+                let _ = ErrorLog::new(ErrorLog::default_path()).append(&message);
+                engine.app.feedback.status_message = Some(message);
+            }
+        }
+    }
+
+    let wants_report = cli.exit_report.is_some() || cli.exit_report_mail.is_some();
+    let session_log = Arc::new(Mutex::new(SessionLog::default()));
+    if wants_report {
+        engine.add_observer(Box::new(SessionSink::new(Arc::clone(&session_log))));
+    }
+    let usage_stats = Arc::new(Mutex::new(UsageStats::new(chrono::Utc::now())));
+    if cli.usage_stats.is_some() {
+        engine.add_observer(Box::new(UsageSink::new(Arc::clone(&usage_stats))));
+    }
+
+    if let Some(addr) = cli.web_listen {
+        let (mirror, bound) = WebMirror::bind(addr, cli.web_public).await?;
+        engine.set_web_mirror(mirror);
+        engine.app.feedback.status_message = Some(format!("Web mirror on http://{bound}/"));
+    }
+
+    // Initial fetch
+    engine.request_snapshot();
+
+    let mut ui = TerminalUi::new(ratatui::init());
+    let mut events = event::EventHandler::new(Duration::from_millis(10));
+
+    loop {
+        engine.run(&mut ui, Some(&mut events)).await?;
+
+        // Check if user selected a recording to replay
+        if let Some(replay_path) = engine.app.recordings.pending_path.take() {
+            // Live collection is suspended while the replay owns the terminal
+            let result = replay_in_terminal(
+                ui.terminal_mut(),
+                &mut events,
+                &replay_path,
+                None,
+                engine.app.config.clone(),
+            )
+            .await;
+
+            // Back to live mode, keeping config changes made during replay
+            match result {
+                Ok(config) => engine.app.config = config,
+                Err(e) => {
+                    engine.app.feedback.status_message = Some(format!("Replay failed: {e}"));
+                }
+            }
+            engine.app.running = true;
+            engine.app.view_mode = app::ViewMode::Normal;
+            engine.handle_action(AppAction::RefreshIntervalChanged);
+
+            // Trigger immediate refresh
+            engine.request_snapshot();
+
+            // Continue outer loop to resume live mode
+            continue;
+        }
+
+        // No replay requested, exit
+        break;
+    }
+
+    ratatui::restore();
+
+    if wants_report {
+        write_exit_report(&cli, &engine, &session_log);
+    }
+    if let Some(path) = &cli.usage_stats {
+        write_usage_stats(path, &engine, &usage_stats, extra_targets.len() + 1);
+    }
+    Ok(())
+}
+
+/// Gather a diagnostic bundle and write it to `output`, or a timestamped
+/// file in the working directory. A failed connection goes into the bundle
+/// rather than stopping it.
+async fn write_diagnostics(cli: &Cli, config: &AppConfig, output: Option<&Path>) -> Result<()> {
+    let mut bundle = Bundle::new(chrono::Local::now());
+    bundle.add_config(config);
+
+    let connected = match cli.pg_config() {
+        Ok(pg_config) => establish_connection(cli, &pg_config, &cli.connection_info()).await,
+        Err(e) => Err(e).context("invalid connection config"),
+    };
+    match connected {
+        Ok((client, ssl_mode)) => bundle.add_server(&client, ssl_mode.label()).await,
+        Err(e) => bundle.add_connection_error(&format!("{e:#}")),
+    }
+
+    bundle.add_error_log(&ErrorLog::default_path());
+    if cli.diagnostics_recording {
+        bundle.add_latest_recording(config.recordings_dir.as_deref());
+    }
+
+    let path = crate::diagnostics::output_path(output, &bundle);
+    bundle
+        .write(&path)
+        .with_context(|| format!("could not write {}", path.display()))?;
+    println!("Wrote diagnostic bundle {}", path.display());
+    for name in bundle.file_names() {
+        println!("  {name}");
+    }
+    Ok(())
+}
+
+/// App state for one live connection.
+fn build_app(
+    cli: &Cli,
+    refresh: u64,
+    config: AppConfig,
+    conn_info: ConnectionInfo,
+    server_info: db::models::ServerInfo,
+    ssl_mode: SslMode,
+) -> app::App {
+    let mut app = app::App::new(
+        conn_info.host,
+        conn_info.port,
+        conn_info.dbname,
+        conn_info.user,
+        refresh,
+        cli.history_length,
+        config,
+        server_info,
+    );
+    app.set_ssl_mode_label(ssl_mode.label());
+    app.allow_maintenance = cli.allow_maintenance;
+    app.starred = StarredStatements::load();
+    app.baselines = StatementBaselines::load(&app.connection.host, app.connection.port, &app.connection.dbname);
+    app
+}
+
+/// `--listen`, the configured snapshot hooks and schedule.
+fn source_options(cli: &Cli, app: &app::App) -> SourceOptions {
+    SourceOptions {
+        notify: cli.listen.clone().map(|channel| NotifyTrigger {
+            channel,
+            min_interval: cli.listen_min_interval,
+        }),
+        hooks: app.config.hooks.clone(),
+        primary: None,
+        pgbouncer: None,
+        schedule: app.config.schedule.clone(),
+    }
+}
+
+/// Check that `--primary` is reachable now rather than at the first
+/// cancel, and return how to reach it again with its `host:port`.
+async fn connect_primary(cli: &Cli, target: &str) -> Result<(Reconnect, String)> {
+    let (pg_config, conn_info) = cli
+        .target_config(target)
+        .context("invalid --primary connection")?;
+    let (_client, ssl_mode) = establish_connection(cli, &pg_config, &conn_info)
+        .await
+        .context("could not connect to the --primary server")?;
+    let reconnect = Reconnect {
+        pg_config,
+        ssl_mode,
+        cert_config: cli.ssl_cert_config(),
+    };
+    Ok((reconnect, format!("{}:{}", conn_info.host, conn_info.port)))
+}
+
+/// Check that `--pgbouncer` reaches a PgBouncer admin console now rather
+/// than at the first refresh, and return how to reach it again.
+async fn connect_pgbouncer(cli: &Cli, target: &str) -> Result<Reconnect> {
+    let (pg_config, conn_info) = cli
+        .pgbouncer_config(target)
+        .context("invalid --pgbouncer connection")?;
+    let (client, ssl_mode) = establish_connection(cli, &pg_config, &conn_info)
+        .await
+        .context("could not connect to the --pgbouncer admin console")?;
+    db::pgbouncer::version(&client)
+        .await
+        .map_err(|e| eyre!("--pgbouncer: {e}"))?;
+    Ok(Reconnect {
+        pg_config,
+        ssl_mode,
+        cert_config: cli.ssl_cert_config(),
+    })
+}
+
+/// Connect to one `--targets-file` entry and start monitoring it.
+async fn connect_extra_target(
+    cli: &Cli,
+    target: &str,
+    refresh: u64,
+    config: &AppConfig,
+    rotation: recorder::Rotation,
+) -> Result<Connection> {
+    let (pg_config, conn_info) = cli
+        .target_config(target)
+        .context("invalid connection")?;
+    let (client, ssl_mode) = establish_connection(cli, &pg_config, &conn_info).await?;
+    let server_info = db::queries::fetch_server_info(&client).await?;
+    let recorder = recorder::Recorder::new(&conn_info.host, conn_info.port, &conn_info.dbname, &conn_info.user, &server_info, config.recordings_dir.as_deref(), rotation)
+        .ok()
+        .map(|r| Box::new(r) as Box<dyn SnapshotSink + Send>);
+    let app = build_app(cli, refresh, config.clone(), conn_info, server_info, ssl_mode);
+    let reconnect = Reconnect {
+        pg_config,
+        ssl_mode,
+        cert_config: cli.ssl_cert_config(),
+    };
+    let source = spawn_source(client, &app, reconnect, source_options(cli, &app));
+    Ok(Connection { app, source, recorder })
+}
+
+/// Start the snapshot pipeline for a live connection.
+fn spawn_source(
+    client: tokio_postgres::Client,
+    app: &app::App,
+    reconnect: Reconnect,
+    options: SourceOptions,
+) -> SnapshotSource {
+    let extensions = app.server_info.extensions.clone();
+    let capabilities = app.server_info.capabilities.clone();
+    let pg_major_version = app.server_info.major_version();
+    SnapshotSource::spawn_postgres(client, extensions, capabilities, pg_major_version, reconnect, options)
+}
+
+/// Exit hook: write and/or mail the session summary. Failures are reported
+/// but don't turn a clean exit into an error.
+fn write_exit_report(cli: &Cli, engine: &Engine, log: &Mutex<SessionLog>) {
+    let Ok(log) = log.lock() else {
+        return;
+    };
+    let report = log.report(&connection_target(&engine.app.connection));
+
+    if let Some(path) = &cli.exit_report {
+        match report.write_to(path) {
+            Ok(()) => eprintln!("Session summary written to {}", path.display()),
+            Err(e) => eprintln!("{e:#}"),
+        }
+    }
+    if let Some(to) = &cli.exit_report_mail {
+        match report.send_mail(to) {
+            Ok(()) => eprintln!("Session summary mailed to {to}"),
+            Err(e) => eprintln!("Could not mail session summary: {e:#}"),
+        }
+    }
+}
+
+/// Exit hook for `--usage-stats`.
+fn write_usage_stats(path: &Path, engine: &Engine, stats: &Mutex<UsageStats>, connections: usize) {
+    let Ok(stats) = stats.lock() else {
+        return;
+    };
+    let context = UsageContext {
+        pg_major_version: engine.app.server_info.major_version(),
+        refresh_interval_secs: engine.app.refresh_interval_secs,
+        connections,
+    };
+    match stats.report(context, chrono::Utc::now()).write_to(path) {
+        Ok(()) => eprintln!("Usage stats written to {}", path.display()),
+        Err(e) => eprintln!("{e:#}"),
+    }
+}
@@ -0,0 +1,183 @@
+//! Snapshot scheduling: reading table and index stats less often.
+//!
+//! ```toml
+//! [schedule]
+//! relation_stats_every = 5
+//! quiet_hours = ["01:00-05:00"]
+//! ```
+//!
+//! Table and index stats join `pg_class` and size every relation, which
+//! dominates a refresh on databases with many tables. With
+//! `relation_stats_every` above 1 they are read on every Nth refresh only,
+//! and not at all during quiet hours (local time; ranges may wrap past
+//! midnight). Refreshes in between carry the last read over, so the panels
+//! keep their rows and title them with when they were read. The first
+//! refresh always reads them.
+
+use std::fmt;
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// The `[schedule]` section of the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotSchedule {
+    /// Read table and index stats on every this many refreshes
+    pub relation_stats_every: u32,
+    /// Local times during which table and index stats are not read at all
+    pub quiet_hours: Vec<QuietHours>,
+}
+
+impl Default for SnapshotSchedule {
+    fn default() -> Self {
+        Self {
+            relation_stats_every: 1,
+            quiet_hours: Vec::new(),
+        }
+    }
+}
+
+impl SnapshotSchedule {
+    pub fn is_quiet(&self, now: NaiveTime) -> bool {
+        self.quiet_hours.iter().any(|q| q.contains(now))
+    }
+}
+
+/// A daily time range such as `22:00-06:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `time` falls in the range, start included, end not.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            // Past midnight
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl TryFrom<String> for QuietHours {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let parse = |t: &str| NaiveTime::parse_from_str(t.trim(), "%H:%M");
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("quiet hours {s:?}: expected HH:MM-HH:MM"))?;
+        match (parse(start), parse(end)) {
+            (Ok(start), Ok(end)) => Ok(Self { start, end }),
+            _ => Err(format!("quiet hours {s:?}: expected HH:MM-HH:MM")),
+        }
+    }
+}
+
+impl From<QuietHours> for String {
+    fn from(q: QuietHours) -> Self {
+        q.to_string()
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
+
+/// Counts refreshes since table and index stats were last read.
+#[derive(Debug, Default)]
+pub struct RelationStatsTimer {
+    /// Refreshes skipped since the last read; none before the first
+    skipped: Option<u32>,
+}
+
+impl RelationStatsTimer {
+    /// Whether the refresh about to run at local time `now` reads table and
+    /// index stats.
+    pub fn due(&mut self, schedule: &SnapshotSchedule, now: NaiveTime) -> bool {
+        let due = match self.skipped {
+            None => true,
+            Some(_) if schedule.is_quiet(now) => false,
+            Some(skipped) => skipped + 1 >= schedule.relation_stats_every.max(1),
+        };
+        self.skipped = Some(match self.skipped {
+            Some(skipped) if !due => skipped + 1,
+            _ => 0,
+        });
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn quiet(s: &str) -> QuietHours {
+        QuietHours::try_from(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_parse_and_wrap_midnight() {
+        let night = quiet("22:00-06:30");
+        assert_eq!(night.to_string(), "22:00-06:30");
+        assert!(night.contains(at(23, 0)));
+        assert!(night.contains(at(3, 0)));
+        assert!(!night.contains(at(6, 30)));
+        assert!(!night.contains(at(12, 0)));
+
+        let lunch = quiet("12:00 - 13:00");
+        assert!(lunch.contains(at(12, 0)));
+        assert!(!lunch.contains(at(13, 0)));
+
+        assert!(QuietHours::try_from("22:00".to_string()).is_err());
+        assert!(QuietHours::try_from("25:00-06:00".to_string()).is_err());
+    }
+
+    #[test]
+    fn reads_every_nth_refresh() {
+        let schedule = SnapshotSchedule {
+            relation_stats_every: 3,
+            quiet_hours: vec![],
+        };
+        let mut timer = RelationStatsTimer::default();
+        let reads: Vec<bool> = (0..7).map(|_| timer.due(&schedule, at(12, 0))).collect();
+        assert_eq!(reads, [true, false, false, true, false, false, true]);
+
+        let mut timer = RelationStatsTimer::default();
+        assert!((0..3).all(|_| timer.due(&SnapshotSchedule::default(), at(12, 0))));
+    }
+
+    #[test]
+    fn quiet_hours_skip_all_but_the_first_read() {
+        let schedule = SnapshotSchedule {
+            relation_stats_every: 1,
+            quiet_hours: vec![quiet("01:00-05:00")],
+        };
+        let mut timer = RelationStatsTimer::default();
+        assert!(timer.due(&schedule, at(2, 0)));
+        assert!(!timer.due(&schedule, at(2, 0)));
+        assert!(!timer.due(&schedule, at(4, 59)));
+        // Read straight away once quiet hours end
+        assert!(timer.due(&schedule, at(5, 0)));
+    }
+
+    #[test]
+    fn schedule_reads_from_toml() {
+        let schedule: SnapshotSchedule =
+            toml::from_str("relation_stats_every = 10\nquiet_hours = [\"23:00-01:00\"]").unwrap();
+        assert_eq!(schedule.relation_stats_every, 10);
+        assert!(schedule.is_quiet(at(0, 30)));
+        assert!(toml::from_str::<SnapshotSchedule>("quiet_hours = [\"late\"]").is_err());
+    }
+}
@@ -0,0 +1,340 @@
+//! Server log lines read back through `pg_read_binary_file`.
+//!
+//! With tailing on, each refresh reads what was written to the log since
+//! the previous one ([`LogTail`]) and the snapshot carries the parsed lines
+//! to the Logs panel and into recordings.
+//!
+//! After a cancel or terminate the backend writes its own account of it to
+//! the log ("canceling statement due to user request", "terminating
+//! connection due to administrator command"), usually followed by the
+//! statement it was running and whatever went wrong with the client. Finding
+//! those lines confirms the action landed. Lines are matched on the PID in
+//! `log_line_prefix` (`%p`, part of the default) and on the English message
+//! text, so a server logging in another language finds nothing.
+
+use crate::db::models::{LogLevel, LogLine};
+
+/// Bytes read from the end of the current log file.
+pub const TAIL_BYTES: i64 = 64 * 1024;
+
+/// Lines the Logs panel keeps.
+pub const PANEL_LINES: usize = 500;
+
+/// Most lines kept per backend.
+const MAX_LINES: usize = 6;
+
+/// Severity markers that end `log_line_prefix`.
+const LEVELS: [&str; 16] = [
+    "STATEMENT:",
+    "ERROR:",
+    "FATAL:",
+    "LOG:",
+    "WARNING:",
+    "DETAIL:",
+    "HINT:",
+    "CONTEXT:",
+    "PANIC:",
+    "NOTICE:",
+    "INFO:",
+    "DEBUG1:",
+    "DEBUG2:",
+    "DEBUG3:",
+    "DEBUG4:",
+    "DEBUG5:",
+];
+
+/// How the backend reports being cancelled or terminated.
+const ACTION_MESSAGES: [&str; 2] = [
+    "canceling statement due to user request",
+    "terminating connection due to administrator command",
+];
+
+/// Prefix, severity (with its colon) and message of a line.
+fn split(line: &str) -> Option<(&str, &'static str, &str)> {
+    let (at, level) = LEVELS
+        .iter()
+        .filter_map(|level| line.find(level).map(|at| (at, *level)))
+        .min_by_key(|(at, _)| *at)?;
+    Some((&line[..at], level, line[at + level.len()..].trim()))
+}
+
+/// The PID from the line's prefix and the message from its severity on,
+/// with the padding Postgres puts after the severity squeezed out.
+fn split_line(line: &str) -> Option<(&str, String)> {
+    let (prefix, level, message) = split(line)?;
+    Some((prefix, format!("{level} {message}")))
+}
+
+/// Whether the prefix carries `pid` as a field of its own (`[4242]`,
+/// `pid=4242`), not as part of the timestamp.
+fn prefix_has_pid(prefix: &str, pid: &str) -> bool {
+    prefix
+        .split(|c: char| c.is_whitespace() || matches!(c, '=' | ',' | ';'))
+        .any(|field| field.trim_matches(|c: char| !c.is_ascii_digit()) == pid)
+}
+
+/// What backend `pid` logged from its last cancel or terminate onwards.
+/// Empty when the log holds no such line for it (yet).
+pub fn action_lines(log: &str, pid: i32) -> Vec<String> {
+    let pid = pid.to_string();
+    let lines: Vec<String> = log
+        .lines()
+        .filter_map(split_line)
+        .filter(|(prefix, _)| prefix_has_pid(prefix, &pid))
+        .map(|(_, message)| message)
+        .collect();
+    let Some(start) = lines
+        .iter()
+        .rposition(|m| ACTION_MESSAGES.iter().any(|a| m.contains(a)))
+    else {
+        return Vec::new();
+    };
+    lines.into_iter().skip(start).take(MAX_LINES).collect()
+}
+
+fn parse_level(level: &str) -> LogLevel {
+    match level.trim_end_matches(':') {
+        "STATEMENT" => LogLevel::Statement,
+        "ERROR" => LogLevel::Error,
+        "FATAL" => LogLevel::Fatal,
+        "LOG" => LogLevel::Log,
+        "WARNING" => LogLevel::Warning,
+        "DETAIL" => LogLevel::Detail,
+        "HINT" => LogLevel::Hint,
+        "CONTEXT" => LogLevel::Context,
+        "PANIC" => LogLevel::Panic,
+        "NOTICE" => LogLevel::Notice,
+        "INFO" => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+/// Log text as lines. The tab-indented lines that carry on a multi-line
+/// message or statement are joined to the line they belong to.
+pub fn parse_lines(text: &str) -> Vec<LogLine> {
+    let mut lines: Vec<LogLine> = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            if let Some(last) = lines.last_mut() {
+                last.message.push(' ');
+                last.message.push_str(line.trim());
+                continue;
+            }
+        }
+        lines.push(match split(line) {
+            Some((prefix, level, message)) => LogLine {
+                prefix: prefix.trim_end().to_string(),
+                level: parse_level(level),
+                message: message.to_string(),
+            },
+            None => LogLine {
+                prefix: String::new(),
+                level: LogLevel::Other,
+                message: line.trim_end().to_string(),
+            },
+        });
+    }
+    lines
+}
+
+/// Where reading the current log file left off, so each refresh only
+/// takes what was written since the one before.
+#[derive(Debug, Default)]
+pub struct LogTail {
+    file: Option<String>,
+    offset: i64,
+}
+
+impl LogTail {
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub const fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Take `bytes` read from `start` in `file`. Only whole lines are
+    /// returned: a partial last line is read again next time, and a partial
+    /// first line is dropped when the read did not carry on from the last
+    /// one (first read, rotation, or more written than one read takes).
+    pub fn advance(&mut self, file: String, start: i64, bytes: &[u8]) -> Vec<LogLine> {
+        let continued = self.file.as_deref() == Some(file.as_str()) && start == self.offset;
+        self.file = Some(file);
+        let from = if continued || start == 0 {
+            0
+        } else {
+            match bytes.iter().position(|&b| b == b'\n') {
+                Some(at) => at + 1,
+                None => {
+                    self.offset = start + bytes.len() as i64;
+                    return Vec::new();
+                }
+            }
+        };
+        let Some(end) = bytes[from..].iter().rposition(|&b| b == b'\n').map(|at| from + at + 1) else {
+            self.offset = start + from as i64;
+            return Vec::new();
+        };
+        self.offset = start + end as i64;
+        parse_lines(&String::from_utf8_lossy(&bytes[from..end]))
+    }
+}
+
+/// Severities the Logs panel shows, cycled with `f`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelFilter {
+    #[default]
+    All,
+    Warnings,
+    Errors,
+}
+
+impl LevelFilter {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::All => Self::Warnings,
+            Self::Warnings => Self::Errors,
+            Self::Errors => Self::All,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::All => "all levels",
+            Self::Warnings => "warnings and up",
+            Self::Errors => "errors and up",
+        }
+    }
+
+    const fn admits(self, level: LogLevel) -> bool {
+        match self {
+            Self::All => true,
+            Self::Warnings => matches!(
+                level,
+                LogLevel::Warning | LogLevel::Error | LogLevel::Fatal | LogLevel::Panic
+            ),
+            Self::Errors => matches!(level, LogLevel::Error | LogLevel::Fatal | LogLevel::Panic),
+        }
+    }
+}
+
+/// Indices of the lines `filter` lets through, newest message first.
+/// DETAIL, HINT, CONTEXT and STATEMENT lines stay under the message they
+/// belong to.
+pub fn visible(lines: &[LogLine], filter: LevelFilter) -> Vec<usize> {
+    let mut entries: Vec<Vec<usize>> = Vec::new();
+    let mut keep = true;
+    for (i, line) in lines.iter().enumerate() {
+        match entries.last_mut() {
+            Some(entry) if line.level.is_followup() => {
+                if keep {
+                    entry.push(i);
+                }
+            }
+            _ => {
+                keep = filter.admits(line.level);
+                entries.push(if keep { vec![i] } else { Vec::new() });
+            }
+        }
+    }
+    entries.into_iter().rev().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+2026-10-18 09:00:01.120 UTC [4242] ERROR:  canceling statement due to user request
+2026-10-18 09:00:01.120 UTC [4242] STATEMENT:  SELECT pg_sleep(60)
+2026-10-18 09:00:02.500 UTC [31337] LOG:  checkpoint starting: time
+2026-10-18 09:00:03.001 UTC [4242] FATAL:  terminating connection due to administrator command
+2026-10-18 09:00:03.001 UTC [4242] STATEMENT:  UPDATE orders SET status = 'x'
+2026-10-18 09:00:03.002 UTC [4242] LOG:  could not send data to client: Broken pipe
+2026-10-18 09:00:04.000 UTC [42] ERROR:  canceling statement due to user request
+";
+
+    #[test]
+    fn lines_start_at_the_latest_action_for_that_pid() {
+        assert_eq!(
+            action_lines(LOG, 4242),
+            vec![
+                "FATAL: terminating connection due to administrator command",
+                "STATEMENT: UPDATE orders SET status = 'x'",
+                "LOG: could not send data to client: Broken pipe",
+            ]
+        );
+        // Neither part of another PID nor of the timestamp counts
+        assert_eq!(action_lines(LOG, 42), vec!["ERROR: canceling statement due to user request"]);
+        assert!(action_lines(LOG, 2026).is_empty());
+        assert_eq!(
+            action_lines("pid=77,user=app ERROR:  canceling statement due to user request", 77).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn lines_parse_into_levels_with_continuations_joined() {
+        let lines = parse_lines(
+            "2026-10-18 09:00:01 UTC [7] LOG:  statement: SELECT 1\n\
+             \tFROM t\n\
+             \n\
+             2026-10-18 09:00:02 UTC [7] NOTICE:  relation exists\n\
+             stray output\n",
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].prefix, "2026-10-18 09:00:01 UTC [7]");
+        assert_eq!(lines[0].level, LogLevel::Log);
+        assert_eq!(lines[0].message, "statement: SELECT 1 FROM t");
+        assert_eq!(lines[1].level, LogLevel::Notice);
+        assert_eq!(lines[2].level, LogLevel::Other);
+        assert_eq!(lines[2].message, "stray output");
+    }
+
+    #[test]
+    fn tail_carries_on_from_the_last_whole_line() {
+        let mut tail = LogTail::default();
+        // First read starts mid-file: the cut line is dropped, the unfinished one kept for later
+        let lines = tail.advance("log/a".into(), 100, b"ut line\n[1] LOG:  one\n[1] LOG:  tw");
+        assert_eq!(lines.iter().map(|l| l.message.as_str()).collect::<Vec<_>>(), vec!["one"]);
+        assert_eq!((tail.file(), tail.offset()), (Some("log/a"), 100 + 22));
+
+        let lines = tail.advance("log/a".into(), 122, b"[1] LOG:  two\n");
+        assert_eq!(lines[0].message, "two");
+        assert_eq!(tail.offset(), 136);
+
+        // A new file after rotation is read from its start
+        let lines = tail.advance("log/b".into(), 0, b"[2] ERROR:  three\n");
+        assert_eq!(lines[0].level, LogLevel::Error);
+        assert!(tail.advance("log/b".into(), 18, b"").is_empty());
+        assert_eq!(tail.offset(), 18);
+    }
+
+    #[test]
+    fn newest_first_with_followups_kept_under_their_message() {
+        let lines = parse_lines(
+            "[1] LOG:  checkpoint starting\n\
+             [1] ERROR:  duplicate key\n\
+             [1] DETAIL:  Key (id)=(1) already exists.\n\
+             [1] STATEMENT:  INSERT INTO t VALUES (1)\n\
+             [1] WARNING:  there is no transaction in progress\n\
+             [1] LOG:  checkpoint complete\n\
+             [1] DETAIL:  wrote 3 buffers\n",
+        );
+        assert_eq!(visible(&lines, LevelFilter::All), vec![5, 6, 4, 1, 2, 3, 0]);
+        assert_eq!(visible(&lines, LevelFilter::Warnings), vec![4, 1, 2, 3]);
+        assert_eq!(visible(&lines, LevelFilter::Errors), vec![1, 2, 3]);
+        assert_eq!(LevelFilter::Errors.next(), LevelFilter::All);
+    }
+
+    #[test]
+    fn nothing_found_without_an_action_line() {
+        assert!(action_lines(LOG, 31337).is_empty());
+        assert!(action_lines("", 4242).is_empty());
+        assert!(action_lines("partial line without a level", 4242).is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+//! Session summary written (or mailed) when the live UI exits.
+//!
+//! Meant as a shift-handoff note: how long the session ran, which alerts
+//! fired, which actions the operator took and which statements added the
+//! most execution time.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use color_eyre::eyre::{bail, Context, Result};
+
+use crate::alerts::Alert;
+use crate::db::models::PgSnapshot;
+use crate::observe::{ObserveSummary, Observation};
+use crate::recorder::{UiEvent, UiEventKind};
+use crate::runtime::SnapshotSink;
+
+/// Snapshots and operator actions collected over a live session.
+#[derive(Debug, Default)]
+pub struct SessionLog {
+    observation: Observation,
+    actions: Vec<UiEvent>,
+    alerts: Vec<Alert>,
+}
+
+impl SessionLog {
+    pub fn push_snapshot(&mut self, snap: &PgSnapshot) {
+        self.observation.push(snap);
+    }
+
+    /// Keep actions that changed the server; navigation and setting changes
+    /// made outside the tool are left out.
+    pub fn push_event(&mut self, event: &UiEvent) {
+        if !matches!(
+            event.kind,
+            UiEventKind::PanelSwitch { .. }
+                | UiEventKind::Filter { .. }
+                | UiEventKind::SettingChanged { .. }
+        ) {
+            self.actions.push(event.clone());
+        }
+    }
+
+    pub fn push_alert(&mut self, alert: &Alert) {
+        self.alerts.push(alert.clone());
+    }
+
+    pub fn report(&self, target: &str) -> SessionReport {
+        SessionReport {
+            summary: self.observation.summary(target),
+            actions: self.actions.clone(),
+            alerts: self.alerts.clone(),
+        }
+    }
+}
+
+/// Feeds engine snapshots and actions into a shared [`SessionLog`].
+pub struct SessionSink(Arc<Mutex<SessionLog>>);
+
+impl SessionSink {
+    pub fn new(log: Arc<Mutex<SessionLog>>) -> Self {
+        Self(log)
+    }
+}
+
+impl SnapshotSink for SessionSink {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        if let Ok(mut log) = self.0.lock() {
+            log.push_snapshot(snapshot);
+        }
+        Ok(())
+    }
+
+    fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        if let Ok(mut log) = self.0.lock() {
+            log.push_event(event);
+        }
+        Ok(())
+    }
+
+    fn record_alert(&mut self, alert: &Alert) {
+        if let Ok(mut log) = self.0.lock() {
+            log.push_alert(alert);
+        }
+    }
+}
+
+/// The finished summary for a session.
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    pub summary: ObserveSummary,
+    pub actions: Vec<UiEvent>,
+    /// Alerts as they fired, oldest first
+    pub alerts: Vec<Alert>,
+}
+
+impl SessionReport {
+    pub fn subject(&self) -> String {
+        format!("pg_glimpse session summary: {}", self.summary.target)
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut out = self.summary.render_header("Session on");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Alerts fired");
+        if self.alerts.is_empty() {
+            let _ = writeln!(out, "  (none)");
+        }
+        for alert in &self.alerts {
+            let _ = writeln!(
+                out,
+                "  {}  {}: {} ({})",
+                alert.fired_at.format("%H:%M:%S"),
+                alert.name,
+                alert.condition,
+                alert.peak
+            );
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Actions taken");
+        if self.actions.is_empty() {
+            let _ = writeln!(out, "  (none)");
+        }
+        for event in &self.actions {
+            let _ = writeln!(
+                out,
+                "  {}  {}",
+                event.timestamp.format("%H:%M:%S"),
+                event.describe()
+            );
+        }
+        let _ = writeln!(out);
+        out.push_str(&self.summary.render_sections());
+        out
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.render_text())
+            .with_context(|| format!("could not write session report to {}", path.display()))
+    }
+
+    /// Message for `sendmail -t`, which reads recipients from the headers.
+    pub fn mail_message(&self, to: &str) -> String {
+        format!(
+            "To: {to}\nSubject: {}\nContent-Type: text/plain; charset=utf-8\n\n{}",
+            self.subject(),
+            self.render_text()
+        )
+    }
+
+    pub fn send_mail(&self, to: &str) -> Result<()> {
+        let mut child = Command::new("sendmail")
+            .arg("-t")
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("could not run sendmail")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(self.mail_message(to).as_bytes())?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("sendmail exited with {status}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use tempfile::tempdir;
+
+    fn event(secs: u32, kind: UiEventKind) -> UiEvent {
+        UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, secs).unwrap(),
+            kind,
+            via: None,
+        }
+    }
+
+    fn make_log() -> SessionLog {
+        let mut log = SessionLog::default();
+        log.push_event(&event(1, UiEventKind::PanelSwitch { panel: "Locks".into() }));
+        log.push_event(&event(2, UiEventKind::CancelQuery { pids: vec![4242] }));
+        log.push_event(&event(
+            3,
+            UiEventKind::Filter {
+                panel: "Queries".into(),
+                text: "report".into(),
+            },
+        ));
+        log.push_event(&event(4, UiEventKind::ResetStatStatements));
+        log
+    }
+
+    #[test]
+    fn navigation_is_not_an_action() {
+        let report = make_log().report("postgres@localhost:5432/app");
+        assert_eq!(report.actions.len(), 2);
+        assert_eq!(report.actions[0].kind, UiEventKind::CancelQuery { pids: vec![4242] });
+    }
+
+    #[test]
+    fn text_lists_actions_and_sections() {
+        let text = make_log().report("postgres@localhost:5432/app").render_text();
+        assert!(text.starts_with("Session on postgres@localhost:5432/app"));
+        assert!(text.contains("09:00:02  Cancelled"));
+        assert!(text.contains("Reset pg_stat_statements"));
+        assert!(text.contains("Top statements by added execution time"));
+
+        let empty = SessionLog::default().report("x").render_text();
+        assert!(empty.contains("Alerts fired\n  (none)"));
+        assert!(empty.contains("Actions taken\n  (none)"));
+    }
+
+    #[test]
+    fn mail_message_has_headers_then_body() {
+        let report = make_log().report("postgres@db:5432/app");
+        let msg = report.mail_message("oncall@example.com");
+        let (headers, body) = msg.split_once("\n\n").unwrap();
+        assert!(headers.starts_with("To: oncall@example.com\n"));
+        assert!(headers.contains("Subject: pg_glimpse session summary: postgres@db:5432/app"));
+        assert_eq!(body, report.render_text());
+    }
+
+    #[test]
+    fn write_to_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("handoff.txt");
+        let report = make_log().report("x");
+        report.write_to(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), report.render_text());
+    }
+}
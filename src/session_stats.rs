@@ -0,0 +1,270 @@
+//! Per-session statistics sampled from `pg_stat_activity`.
+//!
+//! Postgres keeps session time counters per database (PG14's
+//! `pg_stat_database.active_time` and friends) but nothing per backend, and
+//! without pg_stat_statements there is no record of who did the work. Each
+//! snapshot's client backends are compared with the previous one instead:
+//! the time between two snapshots is charged to the state a backend is in
+//! at the second, and a new `xact_start` or `query_start` counts as a
+//! transaction or query started.
+//!
+//! Anything that begins and ends between two snapshots goes unseen, so the
+//! counts are lower bounds and the times get coarser as the refresh interval
+//! grows. Sessions are forgotten once they leave `pg_stat_activity`; idle
+//! ones the snapshot left out under the idle cap are kept as they were.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::db::models::ActiveQuery;
+
+/// One session in the Sessions panel.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionRow {
+    pub pid: i32,
+    pub usename: Option<String>,
+    pub datname: Option<String>,
+    pub application_name: Option<String>,
+    pub state: Option<String>,
+    /// How long the session has been watched
+    pub observed_secs: f64,
+    pub active_secs: f64,
+    pub idle_in_txn_secs: f64,
+    /// Transactions seen starting
+    pub xacts: i64,
+    /// Queries seen starting
+    pub queries: i64,
+    pub state_changes: i64,
+}
+
+impl SessionRow {
+    /// Share of the watched time spent running queries.
+    pub fn active_pct(&self) -> f64 {
+        if self.observed_secs > 0.0 {
+            (self.active_secs / self.observed_secs * 100.0).min(100.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SessionWatch {
+    usename: Option<String>,
+    datname: Option<String>,
+    application_name: Option<String>,
+    first_seen: DateTime<Utc>,
+    seen_at: DateTime<Utc>,
+    state: Option<String>,
+    query_start: Option<DateTime<Utc>>,
+    xact_start: Option<DateTime<Utc>>,
+    active_secs: f64,
+    idle_in_txn_secs: f64,
+    xacts: i64,
+    queries: i64,
+    state_changes: i64,
+}
+
+impl SessionWatch {
+    fn new(q: &ActiveQuery, at: DateTime<Utc>) -> Self {
+        Self {
+            usename: q.usename.clone(),
+            datname: q.datname.clone(),
+            application_name: q.application_name.clone(),
+            first_seen: at,
+            seen_at: at,
+            state: q.state.clone(),
+            query_start: q.query_start,
+            xact_start: q.xact_start,
+            active_secs: 0.0,
+            idle_in_txn_secs: 0.0,
+            xacts: 0,
+            queries: 0,
+            state_changes: 0,
+        }
+    }
+
+    /// Whether `q` is still the backend first seen under this PID.
+    fn same_backend(&self, q: &ActiveQuery) -> bool {
+        self.usename == q.usename
+            && self.datname == q.datname
+            && !matches!((q.query_start, self.query_start), (Some(now), Some(before)) if now < before)
+    }
+}
+
+fn secs_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    ((to - from).num_milliseconds() as f64 / 1000.0).max(0.0)
+}
+
+/// Client backends watched across snapshots, keyed by PID.
+#[derive(Debug, Clone, Default)]
+pub struct SessionTracker {
+    sessions: HashMap<i32, SessionWatch>,
+}
+
+impl SessionTracker {
+    /// Charge the time since the last snapshot to each backend in `queries`.
+    /// `capped` backends are connected but idle and not listed: they keep
+    /// their counts, and being idle, are charged nothing.
+    pub fn observe(&mut self, at: DateTime<Utc>, queries: &[ActiveQuery], capped: &[i32]) {
+        // Seeking backwards in a replay starts over
+        if self.sessions.values().any(|w| at < w.seen_at) {
+            self.sessions.clear();
+        }
+        self.sessions
+            .retain(|pid, _| queries.iter().any(|q| q.pid == *pid) || capped.contains(pid));
+        for pid in capped {
+            if let Some(watch) = self.sessions.get_mut(pid) {
+                if watch.state.as_deref() != Some("idle") {
+                    watch.state_changes += 1;
+                    watch.state = Some("idle".into());
+                }
+                watch.seen_at = at;
+            }
+        }
+        for q in queries {
+            let watch = self.sessions.entry(q.pid).or_insert_with(|| SessionWatch::new(q, at));
+            // The PID was reused by a new backend
+            if !watch.same_backend(q) {
+                *watch = SessionWatch::new(q, at);
+            }
+            let interval = secs_between(watch.seen_at, at);
+            match q.state.as_deref() {
+                Some("active") => {
+                    // A query that started within the interval only ran for part of it
+                    let running = q.query_start.map_or(interval, |start| secs_between(start, at));
+                    watch.active_secs += interval.min(running);
+                }
+                Some(state) if state.starts_with("idle in transaction") => watch.idle_in_txn_secs += interval,
+                _ => {}
+            }
+            if q.state != watch.state {
+                watch.state_changes += 1;
+            }
+            if q.query_start.is_some() && q.query_start != watch.query_start {
+                watch.queries += 1;
+            }
+            if q.xact_start.is_some() && q.xact_start != watch.xact_start {
+                watch.xacts += 1;
+            }
+            watch.state = q.state.clone();
+            watch.query_start = q.query_start;
+            watch.xact_start = q.xact_start;
+            watch.application_name = q.application_name.clone();
+            watch.seen_at = at;
+        }
+    }
+
+    /// Every watched session, most active time first.
+    pub fn rows(&self) -> Vec<SessionRow> {
+        let mut rows: Vec<SessionRow> = self
+            .sessions
+            .iter()
+            .map(|(&pid, w)| SessionRow {
+                pid,
+                usename: w.usename.clone(),
+                datname: w.datname.clone(),
+                application_name: w.application_name.clone(),
+                state: w.state.clone(),
+                observed_secs: secs_between(w.first_seen, w.seen_at),
+                active_secs: w.active_secs,
+                idle_in_txn_secs: w.idle_in_txn_secs,
+                xacts: w.xacts,
+                queries: w.queries,
+                state_changes: w.state_changes,
+            })
+            .collect();
+        rows.sort_by(|a, b| b.active_secs.total_cmp(&a.active_secs).then(a.pid.cmp(&b.pid)));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn backend(pid: i32, state: &str, query_start: DateTime<Utc>, xact_start: Option<DateTime<Utc>>) -> ActiveQuery {
+        ActiveQuery {
+            pid,
+            usename: Some("app".into()),
+            datname: Some("shop".into()),
+            state: Some(state.into()),
+            query_start: Some(query_start),
+            backend_type: Some("client backend".into()),
+            xact_start,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn samples_add_up_per_session() {
+        let start = Utc::now();
+        let at = |secs: i64| start + Duration::seconds(secs);
+        let mut tracker = SessionTracker::default();
+
+        tracker.observe(at(0), &[backend(1, "active", at(-5), Some(at(-5))), backend(2, "idle", at(-60), None)], &[]);
+        // pid 1 runs a new statement that started 1s ago; pid 2 opens a transaction
+        tracker.observe(
+            at(2),
+            &[
+                backend(1, "active", at(1), Some(at(1))),
+                backend(2, "idle in transaction", at(1), Some(at(1))),
+            ],
+            &[],
+        );
+        tracker.observe(
+            at(4),
+            &[backend(1, "idle", at(1), None), backend(2, "idle in transaction", at(1), Some(at(1)))],
+            &[],
+        );
+
+        let rows = tracker.rows();
+        assert_eq!(rows[0].pid, 1);
+        assert_eq!(rows[0].observed_secs, 4.0);
+        assert_eq!(rows[0].active_secs, 1.0);
+        assert_eq!((rows[0].xacts, rows[0].queries, rows[0].state_changes), (1, 1, 1));
+        assert_eq!(rows[0].active_pct(), 25.0);
+        assert_eq!(rows[1].pid, 2);
+        assert_eq!(rows[1].idle_in_txn_secs, 4.0);
+        assert_eq!((rows[1].xacts, rows[1].queries, rows[1].state_changes), (1, 1, 1));
+
+        // pid 2 disconnects and its PID goes to another user's backend
+        let mut reused = backend(1, "active", at(5), None);
+        reused.usename = Some("report".into());
+        tracker.observe(at(6), &[reused], &[]);
+        let rows = tracker.rows();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].usename.as_deref(), Some("report"));
+        assert_eq!(rows[0].observed_secs, 0.0);
+
+        // Seeking back in a replay starts over
+        tracker.observe(at(3), &[backend(1, "active", at(1), None)], &[]);
+        assert_eq!(tracker.rows()[0].observed_secs, 0.0);
+    }
+
+    #[test]
+    fn idle_sessions_left_out_by_the_cap_are_kept() {
+        let start = Utc::now();
+        let at = |secs: i64| start + Duration::seconds(secs);
+        let mut tracker = SessionTracker::default();
+
+        tracker.observe(at(0), &[backend(1, "active", at(-5), Some(at(-5))), backend(2, "idle", at(-60), None)], &[]);
+        tracker.observe(at(2), &[backend(1, "active", at(-5), Some(at(-5)))], &[2]);
+        // Back in the list after two capped snapshots, with its counts
+        tracker.observe(at(4), &[backend(1, "active", at(-5), Some(at(-5)))], &[2]);
+        tracker.observe(at(6), &[backend(1, "idle", at(-5), None), backend(2, "active", at(5), Some(at(5)))], &[]);
+        let rows = tracker.rows();
+        let idle = rows.iter().find(|r| r.pid == 2).unwrap();
+        assert_eq!(idle.observed_secs, 6.0);
+        // Idle while capped, so only the second since its query started counts
+        assert_eq!(idle.active_secs, 1.0);
+        assert_eq!((idle.queries, idle.state_changes), (1, 1));
+
+        // Gone from both lists: disconnected
+        tracker.observe(at(8), &[backend(1, "idle", at(-5), None)], &[]);
+        assert_eq!(tracker.rows().len(), 1);
+    }
+}
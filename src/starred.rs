@@ -0,0 +1,175 @@
+//! Starred statements with notes, persisted next to the config file so they
+//! survive across sessions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StarredEntry {
+    queryid: i64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    note: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StarredFile {
+    #[serde(default)]
+    starred: Vec<StarredEntry>,
+}
+
+/// Starred `pg_stat_statements` queryids and their notes (empty when none).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StarredStatements {
+    notes: BTreeMap<i64, String>,
+}
+
+impl StarredStatements {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("pg_glimpse").join("starred.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path().map_or_else(Self::default, |p| Self::load_from(&p))
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let file: StarredFile = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            notes: file
+                .starred
+                .into_iter()
+                .map(|e| (e.queryid, e.note))
+                .collect(),
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = Self::path() {
+            self.save_to(&path);
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let file = StarredFile {
+            starred: self
+                .notes
+                .iter()
+                .map(|(&queryid, note)| StarredEntry {
+                    queryid,
+                    note: note.clone(),
+                })
+                .collect(),
+        };
+        if let Ok(contents) = toml::to_string_pretty(&file) {
+            let _ = fs::write(path, contents);
+        }
+    }
+
+    pub fn is_starred(&self, queryid: i64) -> bool {
+        self.notes.contains_key(&queryid)
+    }
+
+    /// Note for a starred statement, if it has a non-empty one.
+    pub fn note(&self, queryid: i64) -> Option<&str> {
+        self.notes
+            .get(&queryid)
+            .map(String::as_str)
+            .filter(|n| !n.is_empty())
+    }
+
+    /// Star or unstar a statement. Unstarring drops its note. Returns
+    /// whether the statement is now starred.
+    pub fn toggle(&mut self, queryid: i64) -> bool {
+        if self.notes.remove(&queryid).is_some() {
+            false
+        } else {
+            self.notes.insert(queryid, String::new());
+            true
+        }
+    }
+
+    /// Set a statement's note, starring it if needed.
+    pub fn set_note(&mut self, queryid: i64, note: &str) {
+        self.notes.insert(queryid, note.trim().to_string());
+    }
+
+    /// Starred queryids, in ascending order.
+    pub fn queryids(&self) -> Vec<i64> {
+        self.notes.keys().copied().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn toggle_stars_and_unstars() {
+        let mut starred = StarredStatements::default();
+        assert!(starred.toggle(42));
+        assert!(starred.is_starred(42));
+        assert_eq!(starred.note(42), None);
+        assert!(!starred.toggle(42));
+        assert!(!starred.is_starred(42));
+    }
+
+    #[test]
+    fn set_note_stars_and_unstar_drops_note() {
+        let mut starred = StarredStatements::default();
+        starred.set_note(7, "  known-bad report query  ");
+        assert!(starred.is_starred(7));
+        assert_eq!(starred.note(7), Some("known-bad report query"));
+
+        starred.set_note(7, "");
+        assert!(starred.is_starred(7));
+        assert_eq!(starred.note(7), None);
+
+        starred.set_note(7, "again");
+        starred.toggle(7);
+        assert!(!starred.is_starred(7));
+        assert_eq!(starred.len(), 0);
+    }
+
+    #[test]
+    fn roundtrip_through_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("starred.toml");
+
+        let mut starred = StarredStatements::default();
+        starred.set_note(-123_456_789, "ticket DB-123");
+        starred.toggle(99);
+        starred.save_to(&path);
+
+        let loaded = StarredStatements::load_from(&path);
+        assert_eq!(loaded, starred);
+        assert_eq!(loaded.note(-123_456_789), Some("ticket DB-123"));
+        assert!(loaded.is_starred(99));
+    }
+
+    #[test]
+    fn load_missing_or_invalid_file_is_empty() {
+        let dir = tempdir().unwrap();
+        assert!(StarredStatements::load_from(&dir.path().join("missing.toml")).is_empty());
+
+        let bad = dir.path().join("bad.toml");
+        fs::write(&bad, "not [valid toml").unwrap();
+        assert!(StarredStatements::load_from(&bad).is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+//! Export a panel's rows to CSV or JSON.
+//!
+//! Rows are the snapshot records themselves, serialized as JSON objects, so
+//! every field is exported with its raw value (bytes, seconds) rather than
+//! the rounded text the table shows. CSV columns are the union of the
+//! objects' keys; nested values are written as JSON text.
+//!
+//! The metrics history behind the graphs exports the same way, one row per
+//! snapshot.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::app::MetricsHistory;
+use crate::history::RingBuffer;
+
+/// File format, picked from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// `.json` files get JSON; anything else is CSV.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Csv,
+        }
+    }
+}
+
+/// Serialize `items[i]` for each index, in the given order.
+pub fn rows<T: Serialize>(items: &[T], indices: impl IntoIterator<Item = usize>) -> Vec<Value> {
+    indices
+        .into_iter()
+        .filter_map(|i| items.get(i))
+        .filter_map(|item| serde_json::to_value(item).ok())
+        .collect()
+}
+
+/// One row per sampled snapshot with every metrics history series, in the
+/// units the graphs show. Rates are empty for snapshots that didn't yield
+/// one (the first sample, or right after a counter reset).
+pub fn history_rows(metrics: &MetricsHistory) -> Vec<Value> {
+    let count = |buf: &RingBuffer<u64>| {
+        buf.timed().into_iter().map(|(t, v)| (t, Value::from(v))).collect::<Vec<_>>()
+    };
+    let scaled = |buf: &RingBuffer<u64>, div: f64| {
+        buf.timed()
+            .into_iter()
+            .map(|(t, v)| (t, Value::from(v as f64 / div)))
+            .collect::<Vec<_>>()
+    };
+
+    let mut columns = vec![
+        ("connections", count(&metrics.connections)),
+        ("active_queries", count(&metrics.active_queries)),
+        ("locks", count(&metrics.lock_count)),
+        ("avg_query_ms", count(&metrics.avg_query_time)),
+        ("cache_hit_pct", scaled(&metrics.hit_ratio, 10.0)),
+        ("tps", count(&metrics.tps)),
+        ("wal_kb_per_sec", count(&metrics.wal_rate)),
+        ("blks_read_per_sec", count(&metrics.blks_read)),
+    ];
+    // Rate series keep milli-units for the sparklines
+    columns.extend(
+        metrics
+            .wal_io
+            .named_series()
+            .map(|(name, series)| (name, scaled(&series.history, 1000.0))),
+    );
+    let resets: Vec<_> = metrics
+        .reset_marks
+        .timed()
+        .into_iter()
+        .map(|(t, reset)| (t, Value::from(reset)))
+        .collect();
+    columns.push(("counter_reset", resets));
+
+    let mut by_time: BTreeMap<DateTime<Utc>, Map<String, Value>> = BTreeMap::new();
+    for (name, values) in &columns {
+        for (at, value) in values {
+            by_time.entry(*at).or_default().insert((*name).to_string(), value.clone());
+        }
+    }
+    by_time
+        .into_iter()
+        .map(|(at, mut values)| {
+            let mut row = Map::new();
+            row.insert("timestamp".into(), Value::from(at.to_rfc3339()));
+            for (name, _) in &columns {
+                row.insert((*name).to_string(), values.remove(*name).unwrap_or(Value::Null));
+            }
+            Value::Object(row)
+        })
+        .collect()
+}
+
+/// Column names in first-seen order.
+fn columns(rows: &[Value]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for key in rows.iter().filter_map(Value::as_object).flat_map(Map::keys) {
+        if !columns.contains(key) {
+            columns.push(key.clone());
+        }
+    }
+    columns
+}
+
+fn csv_field(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => return String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+pub fn to_csv(rows: &[Value]) -> String {
+    let columns = columns(rows);
+    let mut out = columns
+        .iter()
+        .map(|c| csv_field(Some(&Value::String(c.clone()))))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = columns.iter().map(|c| csv_field(row.get(c))).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write `rows` to `path`, creating its directory if needed.
+pub fn write_rows(rows: &[Value], path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("could not create {}", dir.display()))?;
+    }
+    let text = match ExportFormat::from_path(path) {
+        ExportFormat::Csv => to_csv(rows),
+        ExportFormat::Json => serde_json::to_string_pretty(rows)?,
+    };
+    std::fs::write(path, text).with_context(|| format!("could not write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn csv_quotes_fields_and_keeps_missing_cells_empty() {
+        let rows = vec![
+            json!({"pid": 1, "query": "SELECT 'a, b'", "tags": [1, 2]}),
+            json!({"pid": 2, "query": "say \"hi\"\nbye", "tags": null}),
+        ];
+        assert_eq!(
+            to_csv(&rows),
+            "pid,query,tags\n1,\"SELECT 'a, b'\",\"[1,2]\"\n2,\"say \"\"hi\"\"\nbye\",\n"
+        );
+    }
+
+    #[test]
+    fn rows_follow_the_given_order() {
+        #[derive(Serialize)]
+        struct Item {
+            n: i32,
+        }
+        let items = [Item { n: 10 }, Item { n: 20 }, Item { n: 30 }];
+        assert_eq!(rows(&items, [2, 0]), vec![json!({"n": 30}), json!({"n": 10})]);
+    }
+
+    #[test]
+    fn history_rows_line_up_series_by_snapshot_time() {
+        let t = |secs| DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::seconds(secs);
+        let mut metrics = MetricsHistory::new(10);
+        metrics.connections.push(10);
+        metrics.hit_ratio.push(995);
+        metrics.reset_marks.push(false);
+        metrics.connections.stamp(t(0));
+        metrics.hit_ratio.stamp(t(0));
+        metrics.reset_marks.stamp(t(0));
+        metrics.connections.push(12);
+        metrics.tps.push(250);
+        metrics.wal_io.checkpoints.push_delta(1.0, 2.0);
+        metrics.connections.stamp(t(2));
+        metrics.tps.stamp(t(2));
+        metrics.wal_io.checkpoints.history.stamp(t(2));
+
+        let rows = history_rows(&metrics);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["timestamp"], json!("1970-01-01T00:00:00+00:00"));
+        assert_eq!(rows[0]["connections"], json!(10));
+        assert_eq!(rows[0]["cache_hit_pct"], json!(99.5));
+        assert_eq!(rows[0]["tps"], Value::Null);
+        assert_eq!(rows[1]["tps"], json!(250));
+        assert_eq!(rows[1]["checkpoints_per_sec"], json!(0.5));
+        assert!(to_csv(&rows).starts_with("timestamp,connections,active_queries,"));
+    }
+
+    #[test]
+    fn format_follows_the_extension() {
+        let dir = tempdir().unwrap();
+        let rows = vec![json!({"name": "work_mem", "setting": "4MB"})];
+
+        let json_path = dir.path().join("out/settings.JSON");
+        write_rows(&rows, &json_path).unwrap();
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(written, json!(rows));
+
+        let csv_path = dir.path().join("settings.csv");
+        write_rows(&rows, &csv_path).unwrap();
+        assert_eq!(std::fs::read_to_string(&csv_path).unwrap(), "name,setting\nwork_mem,4MB\n");
+    }
+}
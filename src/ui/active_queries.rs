@@ -1,13 +1,23 @@
 use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Line;
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, BorderType, Borders, Cell, Row};
 use ratatui::Frame;
 
+use std::collections::HashMap;
+
 use crate::app::{App, BottomPanel, SortColumn};
+use crate::db::models::TxnState;
+use crate::fingerprint::FingerprintGroup;
+use crate::ui::layout::{shown_columns, LayoutTier};
 use super::overlay::highlight_sql_inline;
 use super::theme::Theme;
-use super::util::{compute_match_indices, format_duration, highlight_matches, styled_table};
+use super::util::{
+    change_badge, compute_match_indices, format_duration, highlight_matches, shown_only, styled_table, truncate,
+    wrap_words,
+};
+
+const SPILL_BADGE: &str = " spill ";
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let total_count = app
@@ -30,12 +40,20 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    let emoji = if app.config.show_emojis { "🔍 " } else { "" };
-    let title = if app.bottom_panel == BottomPanel::Queries && (app.filter.active || (!app.filter.text.is_empty() && app.view_mode == crate::app::ViewMode::Filter)) {
+    let emoji = if app.show_emojis() { "🔍 " } else { "" };
+    let group = app
+        .query_scope
+        .as_ref()
+        .map(|g| format!(" ({})", g.label()))
+        .unwrap_or_default();
+    let grouping = if app.group_by_fingerprint { " grouped" } else { "" };
+    let title = if app.bottom_panel == BottomPanel::Queries && (app.filter().active || (!app.filter().text.is_empty() && app.view_mode == crate::app::ViewMode::Filter)) {
         format!(
-            " {emoji}Queries [{}/{}] (filter: {}) ",
-            filtered_count, total_count, app.filter.text
+            " {emoji}Queries [{}/{}]{group}{grouping} (filter: {}) ",
+            filtered_count, total_count, app.filter().text
         )
+    } else if app.query_scope.is_some() || app.group_by_fingerprint {
+        format!(" {emoji}Queries [{filtered_count}/{total_count}]{group}{grouping} ")
     } else {
         format!(" {emoji}Queries [{total_count}] ")
     };
@@ -45,8 +63,19 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Theme::border_style(Theme::border_active()));
+    let block = match hidden_idle_count(app) {
+        0 => block,
+        hidden => {
+            let hint = if app.is_replay_mode() { "" } else { " · a show all" };
+            block.title_bottom(Line::styled(
+                format!(" {hidden} idle connections hidden{hint} "),
+                Style::default().fg(Theme::fg_dim()),
+            ))
+        }
+    };
 
-    let header = Row::new(vec![
+    let shown = shown_columns(app, BottomPanel::Queries, area.width);
+    let header = Row::new(shown_only([
         Cell::from(format!("PID{}", sort_indicator(SortColumn::Pid))),
         Cell::from("Query"),
         Cell::from(format!("User{}", sort_indicator(SortColumn::User))),
@@ -54,7 +83,8 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         Cell::from(format!("Duration{}", sort_indicator(SortColumn::Duration))),
         Cell::from(format!("State{}", sort_indicator(SortColumn::State))),
         Cell::from("Wait"),
-    ])
+        Cell::from("Blocker"),
+    ], &shown))
     .style(
         Style::default()
             .fg(Theme::fg())
@@ -62,15 +92,50 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     )
     .bottom_margin(0);
 
-    // Calculate query column width: Fill(6) out of total Fill(16), minus borders/highlight
-    let query_width = ((area.width.saturating_sub(4)) as usize * 6 / 16).max(20);
+    // Narrow panels keep PIDs and durations whole instead of sharing the squeeze
+    let narrow = LayoutTier::for_width(area.width) == LayoutTier::Narrow;
+    let whole = |len| if narrow { Constraint::Length(len) } else { Constraint::Fill(1) };
+    let widths = shown_only([
+        whole(7), // PID
+        Constraint::Fill(5), // Query (gets most space)
+        Constraint::Fill(2), // User
+        Constraint::Fill(2), // Database
+        whole(8), // Duration
+        Constraint::Fill(2), // State
+        Constraint::Fill(2), // Wait
+        whole(7), // Blocker (PID this one waits on)
+    ], &shown);
+
+    // Calculate query column width: Fill(5) out of the shown columns' fills, minus borders/highlight
+    // and fixed columns
+    let (fill_total, fixed) = widths.iter().fold((0u16, 0u16), |(fill, fixed), c| match c {
+        Constraint::Fill(n) => (fill + n, fixed),
+        Constraint::Length(n) => (fill, fixed + n),
+        _ => (fill, fixed),
+    });
+    let query_width =
+        ((area.width.saturating_sub(4 + fixed)) as usize * 5 / usize::from(fill_total.max(1))).max(20);
 
     // Check if filtering is active
     let is_filtering = app.bottom_panel == BottomPanel::Queries
-        && !app.filter.text.is_empty()
-        && (app.filter.active || app.view_mode == crate::app::ViewMode::Filter);
-    let filter_text = &app.filter.text;
+        && !app.filter().text.is_empty()
+        && (app.filter().active || app.view_mode == crate::app::ViewMode::Filter);
+    let filter_text = &app.filter().text;
+
+    let offenders: Vec<i32> = app.idle_txn_offenders().iter().map(|(q, _)| q.pid).collect();
+
+    // Grouped rows show the fingerprint and how many backends run it
+    let groups: HashMap<usize, FingerprintGroup> = if app.group_by_fingerprint {
+        app.fingerprint_groups().into_iter().map(|g| (g.index, g)).collect()
+    } else {
+        HashMap::new()
+    };
 
+    // An expanded query makes its row taller; clicks need to know
+    let mut row_heights = Vec::with_capacity(indices.len());
+    // Lines an expanded row can take: the panel less borders and header
+    let expanded_fits = usize::from(area.height.saturating_sub(3)).max(1);
+    let mut expanded_max_scroll = 0;
     let rows: Vec<Row> = app.snapshot.as_ref().map_or_else(Vec::new, |snap| {
         indices
             .iter()
@@ -78,7 +143,34 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
                 let q = &snap.active_queries[i];
                 let dur_color = Theme::duration_color(q.duration_secs);
                 let state_color = Theme::state_color(q.state.as_deref());
-                let query_text = q.query.as_deref().unwrap_or("");
+                let mut state_spans: Vec<Span> = txn_chip(q.txn_state())
+                    .map(|chip| vec![chip, Span::raw(" ")])
+                    .unwrap_or_default();
+                state_spans.push(Span::styled(
+                    query_state(q.state.as_deref()),
+                    Style::default().fg(state_color),
+                ));
+                // Past the idle transaction watchdog's threshold
+                let duration_cell = if offenders.contains(&q.pid) {
+                    Cell::from(format!(" {} ", format_duration(q.duration_secs))).style(
+                        Style::default()
+                            .fg(Theme::overlay_bg())
+                            .bg(Theme::border_danger())
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Cell::from(format_duration(q.duration_secs)).style(Style::default().fg(dur_color))
+                };
+                let blockers = app.blockers_of(q.pid);
+                let waiting_on = match blockers.as_slice() {
+                    [] => Cell::from("-").style(Style::default().fg(Theme::fg_dim())),
+                    [pid] => Cell::from(pid.to_string())
+                        .style(Style::default().fg(Theme::border_danger())),
+                    [pid, rest @ ..] => Cell::from(format!("{pid} +{}", rest.len()))
+                        .style(Style::default().fg(Theme::border_danger())),
+                };
+                let group = groups.get(&i);
+                let query_text = group.map_or_else(|| q.query.as_deref().unwrap_or(""), |g| g.fingerprint.as_str());
                 let usename = q.usename.clone().unwrap_or_else(|| "-".into());
                 let datname = q.datname.clone().unwrap_or_else(|| "-".into());
 
@@ -89,66 +181,180 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
                     None
                 };
 
+                // Statements writing temp files get a badge ahead of the SQL
+                let spilling = q.state.as_deref() == Some("active")
+                    && app.temp_spill.rate(q.query_id).is_some();
+                let change = app.highlighted_changes().and_then(|c| c.query(q.pid));
+                let mut query_spans = Vec::new();
+                let mut text_width = query_width;
+                if let Some(change) = change {
+                    query_spans.push(change_badge(change));
+                    text_width = text_width.saturating_sub(2);
+                }
+                if let Some(g) = group.filter(|g| g.count > 1) {
+                    let badge = format!(" {}× ", g.count);
+                    text_width = text_width.saturating_sub(badge.chars().count() + 1);
+                    query_spans.push(Span::styled(
+                        badge,
+                        Style::default()
+                            .fg(Theme::overlay_bg())
+                            .bg(Theme::border_active())
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    query_spans.push(Span::raw(" "));
+                }
+                if spilling {
+                    query_spans.push(Span::styled(
+                        SPILL_BADGE,
+                        Style::default()
+                            .fg(Theme::overlay_bg())
+                            .bg(Theme::border_warn()),
+                    ));
+                    query_spans.push(Span::raw(" "));
+                    text_width = text_width.saturating_sub(SPILL_BADGE.len() + 1);
+                }
+
                 // Build query cell with optional highlighting
-                let query_cell = match_indices.map_or_else(
-                    || Cell::from(Line::from(highlight_sql_inline(query_text, query_width))),
-                    |indices| {
+                let mut expanded_lines = Vec::new();
+                match match_indices {
+                    _ if app.expanded_query == Some(q.pid) => {
+                        let wrapped = wrap_words(query_text, text_width);
+                        let (mut window, max_scroll) =
+                            expanded_window(&wrapped, app.expanded_scroll, expanded_fits, text_width);
+                        expanded_max_scroll = max_scroll;
+                        let first = window.remove(0);
+                        query_spans.extend(first.spans);
+                        expanded_lines.extend(window);
+                    }
+                    None => query_spans.extend(highlight_sql_inline(query_text, text_width)),
+                    Some(indices) => {
                         // Truncate query_text for display
-                        let display_text = if query_text.len() > query_width {
-                            format!("{}…", &query_text[..query_width.saturating_sub(1)])
+                        let display_text = if query_text.len() > text_width {
+                            format!("{}…", &query_text[..text_width.saturating_sub(1)])
                         } else {
                             query_text.to_string()
                         };
 
-                        let spans = highlight_matches(
+                        query_spans.extend(highlight_matches(
                             &display_text,
                             &indices,
                             Style::default().fg(Theme::fg()),
-                        );
-                        Cell::from(Line::from(spans))
-                    },
-                );
+                        ));
+                    }
+                }
+                let height = 1 + expanded_lines.len() as u16;
+                row_heights.push(height);
+                expanded_lines.insert(0, Line::from(query_spans));
+                let query_cell = Cell::from(Text::from(expanded_lines));
 
-                Row::new(vec![
+                Row::new(shown_only([
                     Cell::from(q.pid.to_string()),
                     query_cell,
                     Cell::from(usename),
                     Cell::from(datname).style(Style::default().fg(Theme::fg_dim())),
-                    Cell::from(format_duration(q.duration_secs))
-                        .style(Style::default().fg(dur_color)),
-                    Cell::from(short_state(q.state.as_deref()))
-                        .style(Style::default().fg(state_color)),
+                    duration_cell,
+                    Cell::from(Line::from(state_spans)),
                     Cell::from(q.wait_event.clone().unwrap_or_else(|| "-".into()))
                         .style(Style::default().fg(if q.wait_event.is_some() {
                             Color::Yellow
                         } else {
                             Theme::fg_dim()
                         })),
-                ])
+                    waiting_on,
+                ], &shown))
+                .height(height)
+                .style(if change.is_some() {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                })
             })
             .collect()
     });
 
-    let widths = [
-        Constraint::Fill(1), // PID
-        Constraint::Fill(6), // Query (gets most space)
-        Constraint::Fill(2), // User
-        Constraint::Fill(2), // Database
-        Constraint::Fill(1), // Duration
-        Constraint::Fill(2), // State
-        Constraint::Fill(2), // Wait
-    ];
-
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.queries.state);
+    app.mouse_targets.table_at(area, true);
+    app.mouse_targets.row_heights = row_heights;
+    app.expanded_scroll = app.expanded_scroll.min(expanded_max_scroll);
 }
 
+/// The lines of an expanded query that fit in `fits` rows, from line
+/// `scroll` on, with a marker for what is above or below. Also returns how
+/// far the text can scroll.
+fn expanded_window(
+    wrapped: &[String],
+    scroll: usize,
+    fits: usize,
+    width: usize,
+) -> (Vec<Line<'static>>, usize) {
+    let sql = |line: &String| Line::from(highlight_sql_inline(line, width));
+    if wrapped.len() <= fits {
+        let lines = if wrapped.is_empty() {
+            vec![Line::from("")]
+        } else {
+            wrapped.iter().map(sql).collect()
+        };
+        return (lines, 0);
+    }
+    let marker = |text: String| {
+        Line::from(Span::styled(truncate(&text, width), Style::default().fg(Theme::fg_dim())))
+    };
+    // Room for a marker on either side
+    let body = fits.saturating_sub(2).max(1);
+    let max_scroll = wrapped.len() - body;
+    let scroll = scroll.min(max_scroll);
+    let mut lines = Vec::with_capacity(body + 2);
+    if scroll > 0 {
+        lines.push(marker(format!("↑ {scroll} more lines (Shift+↑)")));
+    }
+    lines.extend(wrapped[scroll..scroll + body].iter().map(sql));
+    let below = max_scroll - scroll;
+    if below > 0 {
+        lines.push(marker(format!("↓ {below} more lines (Shift+↓)")));
+    }
+    (lines, max_scroll)
+}
+
+/// Idle backends the server-side cap left out of the list
+fn hidden_idle_count(app: &App) -> i64 {
+    if app.show_all_backends {
+        return 0;
+    }
+    app.snapshot.as_ref().map_or(0, |snap| {
+        let listed = snap
+            .active_queries
+            .iter()
+            .filter(|q| q.state.as_deref() == Some("idle"))
+            .count() as i64;
+        (snap.summary.idle_count - listed).max(0)
+    })
+}
+
+/// Marker for the backend's transaction, shown ahead of its query state so an
+/// open or failed transaction stands out even while a statement is running.
+pub(crate) fn txn_chip(txn: TxnState) -> Option<Span<'static>> {
+    match txn {
+        TxnState::None => None,
+        TxnState::Open => Some(Span::styled(
+            "txn",
+            Style::default().fg(Theme::state_idle_txn()),
+        )),
+        TxnState::Aborted => Some(Span::styled(
+            " ABORT ",
+            Style::default()
+                .fg(Theme::overlay_bg())
+                .bg(Theme::border_danger())
+                .add_modifier(Modifier::BOLD),
+        )),
+    }
+}
 
-fn short_state(state: Option<&str>) -> String {
+/// What the current query is doing; transaction state is shown by `txn_chip`.
+fn query_state(state: Option<&str>) -> String {
     match state {
         Some("active") => "active".into(),
-        Some("idle in transaction") => "idle-txn".into(),
-        Some("idle in transaction (aborted)") => "idle-abort".into(),
+        Some("idle in transaction" | "idle in transaction (aborted)") => "idle".into(),
         Some("idle") => "idle".into(),
         Some(s) => s.to_string(),
         None => "-".into(),
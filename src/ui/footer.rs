@@ -4,7 +4,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::app::{App, BottomPanel, ViewMode};
+use crate::app::{App, BottomPanel, ViewMode, PANELS};
 use super::theme::Theme;
 
 struct FooterStyles {
@@ -63,9 +63,33 @@ impl FooterStyles {
 }
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
-    if app.view_mode == ViewMode::Filter {
-        render_filter(frame, app, area);
-        return;
+    match app.view_mode {
+        ViewMode::Filter => {
+            render_input(frame, " Filter ", &app.filter().text, area, None);
+            return;
+        }
+        ViewMode::EditNote(_) => {
+            render_input(frame, " ★ Note ", &app.note_input, area, None);
+            return;
+        }
+        ViewMode::ExportPrompt => {
+            let (label, hint) = if app.export_history {
+                (" Export history to ", " csv / json · ^e panel rows")
+            } else {
+                (" Export to ", " csv / json / svg · ^e graph history")
+            };
+            render_input(frame, label, &app.export_input, area, Some(("Tab", hint)));
+            return;
+        }
+        ViewMode::SeekPrompt => {
+            render_input(frame, " Jump to ", &app.seek_input, area, Some(("", "HH:MM[:SS] or NN%")));
+            return;
+        }
+        ViewMode::Annotate => {
+            render_input(frame, " # Annotate ", &app.annotation_input, area, None);
+            return;
+        }
+        _ => {}
     }
 
     if app.is_replay_mode() {
@@ -75,7 +99,8 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     }
 }
 
-fn render_filter(frame: &mut Frame, app: &App, area: Rect) {
+/// Single-line text prompt; `extra` adds a key hint after confirm/cancel.
+fn render_input(frame: &mut Frame, label: &str, text: &str, area: Rect, extra: Option<(&str, &str)>) {
     let key_style = Style::default()
         .fg(Theme::border_active())
         .add_modifier(Modifier::BOLD);
@@ -87,13 +112,13 @@ fn render_filter(frame: &mut Frame, app: &App, area: Rect) {
         .add_modifier(Modifier::BOLD);
 
     let line1 = vec![
-        Span::styled(" Filter ", label_style),
+        Span::styled(label, label_style),
         Span::styled("  ", Style::default()),
-        Span::styled(&app.filter.text, input_style),
+        Span::styled(text, input_style),
         Span::styled("▌", Style::default().fg(Theme::border_active())),
     ];
 
-    let line2 = vec![
+    let mut line2 = vec![
         Span::styled(" ", Style::default()),
         Span::styled("⏎", key_style),
         Span::styled(" confirm", desc_style),
@@ -101,6 +126,11 @@ fn render_filter(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled("Esc", key_style),
         Span::styled(" cancel", desc_style),
     ];
+    if let Some((key, desc)) = extra {
+        line2.push(Span::styled(" · ", Style::default().fg(Theme::border_dim())));
+        line2.push(Span::styled(key, key_style));
+        line2.push(Span::styled(desc, desc_style));
+    }
 
     let paragraph = Paragraph::new(vec![Line::from(line1), Line::from(line2)])
         .style(Style::default().bg(Theme::header_bg()));
@@ -110,13 +140,17 @@ fn render_filter(frame: &mut Frame, app: &App, area: Rect) {
 fn render_live(frame: &mut Frame, app: &App, area: Rect) {
     let styles = FooterStyles::live();
 
-    let panel_name = panel_name(app.bottom_panel);
+    let panel_name = app.bottom_panel.descriptor().short_title;
 
     // Line 1: Panel name + contextual actions
     let mut line1: Vec<Span> = vec![
         Span::styled(format!(" {panel_name} "), styles.section_style),
         styles.space(),
     ];
+    if app.config.adaptive_refresh.enabled {
+        line1.push(styles.key(&format!("⟳ {}s", app.effective_refresh_secs())));
+        line1.push(styles.desc(" auto"));
+    }
     render_panel_keys(&mut line1, app, &styles);
 
     // Line 2: Panel switches + global keys
@@ -150,6 +184,9 @@ fn render_replay(frame: &mut Frame, app: &App, area: Rect) {
         styles.desc("/"),
         styles.key("G"),
         styles.desc(" jump"),
+        styles.dot(),
+        styles.key(":"),
+        styles.desc(" go to time"),
     ];
     render_panel_keys(&mut line1, app, &styles);
 
@@ -164,23 +201,6 @@ fn render_replay(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
-fn panel_name(panel: BottomPanel) -> &'static str {
-    match panel {
-        BottomPanel::Queries => "Queries",
-        BottomPanel::Blocking => "Locks",
-        BottomPanel::WaitEvents => "Waits",
-        BottomPanel::TableStats => "Tables",
-        BottomPanel::Replication => "Replication",
-        BottomPanel::VacuumProgress => "Vacuum",
-        BottomPanel::Wraparound => "XID",
-        BottomPanel::Indexes => "Indexes",
-        BottomPanel::Statements => "Statements",
-        BottomPanel::WalIo => "WAL",
-        BottomPanel::Settings => "Settings",
-        BottomPanel::Extensions => "Extensions",
-    }
-}
-
 fn render_global_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterStyles, is_live: bool) {
     spans.push(styles.sep());
     spans.push(styles.pipe());
@@ -208,6 +228,20 @@ fn render_global_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &Footer
 }
 
 fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterStyles) {
+    render_panel_specific_keys(spans, app, styles);
+    if app.split.is_some() {
+        spans.push(styles.dot());
+        spans.push(styles.key("^w"));
+        spans.push(styles.desc(" pane"));
+    }
+    if app.filter().active {
+        spans.push(styles.dot());
+        spans.push(styles.key("⌫"));
+        spans.push(styles.desc(" clear filter"));
+    }
+}
+
+fn render_panel_specific_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterStyles) {
     match app.bottom_panel {
         BottomPanel::Queries => {
             spans.push(styles.sep());
@@ -222,6 +256,9 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
             spans.push(styles.dot());
             spans.push(styles.key("/"));
             spans.push(styles.desc(" filter"));
+            spans.push(styles.dot());
+            spans.push(styles.key("f"));
+            spans.push(styles.desc(" group"));
             if !app.is_replay_mode() {
                 spans.push(styles.dot());
                 spans.push(styles.key("C"));
@@ -230,7 +267,7 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
                 spans.push(styles.desc(" cancel/kill"));
             }
         }
-        BottomPanel::TableStats | BottomPanel::Indexes => {
+        BottomPanel::TableStats | BottomPanel::Indexes | BottomPanel::Locks => {
             spans.push(styles.sep());
             spans.push(styles.key("↑↓"));
             spans.push(styles.desc(" nav"));
@@ -247,6 +284,34 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
             spans.push(styles.key("Esc"));
             spans.push(styles.desc(" back"));
         }
+        BottomPanel::ConnectionGroups => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" queries"));
+            spans.push(styles.dot());
+            spans.push(styles.key("s"));
+            spans.push(styles.desc(" sort"));
+            spans.push(styles.dot());
+            spans.push(styles.key("/"));
+            spans.push(styles.desc(" filter"));
+        }
+        BottomPanel::Sessions => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" inspect"));
+            spans.push(styles.dot());
+            spans.push(styles.key("s"));
+            spans.push(styles.desc(" sort"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
         BottomPanel::Statements => {
             spans.push(styles.sep());
             spans.push(styles.key("↑↓"));
@@ -264,12 +329,24 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
                 spans.push(styles.dot());
                 spans.push(styles.key("X"));
                 spans.push(styles.desc(" reset"));
+                if app.server_info.extensions.hypopg {
+                    spans.push(styles.dot());
+                    spans.push(styles.key("h"));
+                    spans.push(styles.desc(" advise"));
+                }
             }
             spans.push(styles.dot());
             spans.push(styles.key("Esc"));
             spans.push(styles.desc(" back"));
         }
-        BottomPanel::Blocking | BottomPanel::VacuumProgress | BottomPanel::Wraparound | BottomPanel::Replication => {
+        BottomPanel::Blocking
+        | BottomPanel::VacuumProgress
+        | BottomPanel::AutovacuumHealth
+        | BottomPanel::Operations
+        | BottomPanel::Backends
+        | BottomPanel::Wraparound
+        | BottomPanel::IndexHealth
+        | BottomPanel::Replication => {
             spans.push(styles.sep());
             spans.push(styles.key("↑↓"));
             spans.push(styles.desc(" nav"));
@@ -280,12 +357,23 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
             spans.push(styles.key("Esc"));
             spans.push(styles.desc(" back"));
         }
-        BottomPanel::WaitEvents | BottomPanel::WalIo => {
+        BottomPanel::BufferCache => {
             spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" inspect"));
+            if !app.is_replay_mode() {
+                spans.push(styles.dot());
+                spans.push(styles.key("b"));
+                spans.push(styles.desc(" read"));
+            }
+            spans.push(styles.dot());
             spans.push(styles.key("Esc"));
             spans.push(styles.desc(" back"));
         }
-        BottomPanel::Settings | BottomPanel::Extensions => {
+        BottomPanel::Slots => {
             spans.push(styles.sep());
             spans.push(styles.key("↑↓"));
             spans.push(styles.desc(" nav"));
@@ -293,46 +381,133 @@ fn render_panel_keys(spans: &mut Vec<Span<'static>>, app: &App, styles: &FooterS
             spans.push(styles.key("⏎"));
             spans.push(styles.desc(" inspect"));
             spans.push(styles.dot());
+            spans.push(styles.key("s"));
+            spans.push(styles.desc(" sort"));
+            spans.push(styles.dot());
             spans.push(styles.key("/"));
             spans.push(styles.desc(" filter"));
+            if !app.is_replay_mode() {
+                spans.push(styles.dot());
+                spans.push(styles.key("X"));
+                spans.push(styles.desc(" drop"));
+            }
             spans.push(styles.dot());
             spans.push(styles.key("Esc"));
             spans.push(styles.desc(" back"));
         }
+        BottomPanel::Logs => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("f"));
+            spans.push(styles.desc(" level"));
+            spans.push(styles.dot());
+            spans.push(styles.key("/"));
+            spans.push(styles.desc(" filter"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+        BottomPanel::Alerts | BottomPanel::Pooler | BottomPanel::LogicalReplication => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+        BottomPanel::WaitEvents if !(app.config.wait_sampler && app.replay.is_none()) => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" backends"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+        BottomPanel::WaitEvents | BottomPanel::WalIo | BottomPanel::Checkpoints => {
+            spans.push(styles.sep());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+        BottomPanel::Settings => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" inspect"));
+            spans.push(styles.dot());
+            spans.push(styles.key("d"));
+            spans.push(styles.desc(if app.settings_changed_only { " all" } else { " changed" }));
+            spans.push(styles.dot());
+            spans.push(styles.key("o"));
+            spans.push(styles.desc(" review"));
+            spans.push(styles.dot());
+            spans.push(styles.key("/"));
+            spans.push(styles.desc(" filter"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+        BottomPanel::Extensions | BottomPanel::Roles => {
+            spans.push(styles.sep());
+            spans.push(styles.key("↑↓"));
+            spans.push(styles.desc(" nav"));
+            spans.push(styles.dot());
+            spans.push(styles.key("⏎"));
+            spans.push(styles.desc(" inspect"));
+            spans.push(styles.dot());
+            spans.push(styles.key("/"));
+            spans.push(styles.desc(" filter"));
+            spans.push(styles.dot());
+            spans.push(styles.key("Esc"));
+            spans.push(styles.desc(" back"));
+        }
+    }
+}
+
+/// Where each panel switch hint sits on the footer's second line, for
+/// clicks. Empty while the footer shows a text prompt instead.
+pub fn tab_targets(app: &App, area: Rect) -> Vec<(Rect, BottomPanel)> {
+    if matches!(
+        app.view_mode,
+        ViewMode::Filter
+            | ViewMode::EditNote(_)
+            | ViewMode::ExportPrompt
+            | ViewMode::SeekPrompt
+            | ViewMode::Annotate
+    ) || area.height < 2
+    {
+        return Vec::new();
     }
+    let styles = FooterStyles::live();
+    let dot = styles.dot().width() as u16;
+    // After the leading space, as render_panel_switch_keys lays them out
+    let mut x = area.x + styles.space().width() as u16;
+    let mut targets = Vec::new();
+    let hints = PANELS.iter().filter_map(|d| Some((d.panel, d.footer_hint?)));
+    for (panel, (key, hint)) in hints {
+        if x >= area.right() {
+            break;
+        }
+        let width = (styles.key(key).width() + styles.desc(&format!(" {hint}")).width()) as u16;
+        targets.push((Rect::new(x, area.y + 1, width.min(area.right() - x), 1), panel));
+        x = x.saturating_add(width + dot);
+    }
+    targets
 }
 
 fn render_panel_switch_keys(spans: &mut Vec<Span<'static>>, styles: &FooterStyles) {
-    spans.push(styles.key("⇥"));
-    spans.push(styles.desc(" locks"));
-    spans.push(styles.dot());
-    spans.push(styles.key("w"));
-    spans.push(styles.desc(" waits"));
-    spans.push(styles.dot());
-    spans.push(styles.key("t"));
-    spans.push(styles.desc(" tables"));
-    spans.push(styles.dot());
-    spans.push(styles.key("R"));
-    spans.push(styles.desc(" repl"));
-    spans.push(styles.dot());
-    spans.push(styles.key("v"));
-    spans.push(styles.desc(" vacuum"));
-    spans.push(styles.dot());
-    spans.push(styles.key("x"));
-    spans.push(styles.desc(" xid"));
-    spans.push(styles.dot());
-    spans.push(styles.key("I"));
-    spans.push(styles.desc(" idx"));
-    spans.push(styles.dot());
-    spans.push(styles.key("S"));
-    spans.push(styles.desc(" stmts"));
-    spans.push(styles.dot());
-    spans.push(styles.key("A"));
-    spans.push(styles.desc(" wal"));
-    spans.push(styles.dot());
-    spans.push(styles.key("P"));
-    spans.push(styles.desc(" cfg"));
-    spans.push(styles.dot());
-    spans.push(styles.key("E"));
-    spans.push(styles.desc(" ext"));
+    let hints = PANELS.iter().filter_map(|d| d.footer_hint);
+    for (i, (key, hint)) in hints.enumerate() {
+        if i > 0 {
+            spans.push(styles.dot());
+        }
+        spans.push(styles.key(key));
+        spans.push(styles.desc(&format!(" {hint}")));
+    }
 }
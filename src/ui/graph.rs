@@ -173,6 +173,271 @@ pub fn render_ratio_chart(
     frame.render_widget(canvas, area);
 }
 
+/// Two series on shared axes, used when comparing recordings. The compared
+/// series is drawn first so the primary line stays on top.
+#[allow(clippy::too_many_arguments)]
+pub fn render_compare_chart(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    current_label: &str,
+    data: &[u64],
+    other: &[u64],
+    color: Color,
+    other_color: Color,
+    marker: Marker,
+    max_y: Option<u64>,
+) {
+    let block = make_block(title, current_label, color, color);
+
+    if (data.is_empty() && other.is_empty()) || area.width < 4 || area.height < 4 {
+        frame.render_widget(block, area);
+        return;
+    }
+
+    let max_val = data.iter().chain(other).copied().max().unwrap_or(1).max(1) as f64;
+    let y_ceil = max_y.map(|m| m as f64).unwrap_or_else(|| nice_ceil(max_val));
+    let n = data.len().max(other.len());
+    let x_max = (n - 1).max(1) as f64;
+
+    let series = [(other.to_vec(), other_color), (data.to_vec(), color)];
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(marker)
+        .x_bounds([0.0, x_max])
+        .y_bounds([0.0, y_ceil])
+        .paint(move |ctx| {
+            for (points, line_color) in &series {
+                for i in 0..points.len().saturating_sub(1) {
+                    ctx.draw(&CanvasLine {
+                        x1: i as f64,
+                        y1: points[i] as f64,
+                        x2: (i + 1) as f64,
+                        y2: points[i + 1] as f64,
+                        color: *line_color,
+                    });
+                }
+            }
+        });
+
+    frame.render_widget(canvas, area);
+}
+
+/// One line of a [`render_multi_chart`], named in the title in its color.
+pub struct ChartSeries {
+    pub label: String,
+    pub data: Vec<u64>,
+    pub color: Color,
+}
+
+/// Several series on shared axes, each labelled in the title. Later series
+/// are drawn on top.
+pub fn render_multi_chart(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    series: Vec<ChartSeries>,
+    marker: Marker,
+) {
+    let mut title_spans = vec![Span::styled(
+        format!(" {title} "),
+        Style::default()
+            .fg(Theme::fg())
+            .add_modifier(Modifier::BOLD),
+    )];
+    title_spans.push(Span::styled("── ", Style::default().fg(Theme::border_dim())));
+    for s in &series {
+        title_spans.push(Span::styled(
+            format!("{} ", s.label),
+            Style::default().fg(s.color).add_modifier(Modifier::BOLD),
+        ));
+    }
+    let block = Block::default()
+        .title(Line::from(title_spans))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Theme::border_dim()));
+
+    let n = series.iter().map(|s| s.data.len()).max().unwrap_or(0);
+    if n == 0 || area.width < 4 || area.height < 4 {
+        frame.render_widget(block, area);
+        return;
+    }
+
+    let max_val = series
+        .iter()
+        .flat_map(|s| s.data.iter().copied())
+        .max()
+        .unwrap_or(1)
+        .max(1) as f64;
+    let y_ceil = nice_ceil(max_val);
+    let x_max = (n - 1).max(1) as f64;
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(marker)
+        .x_bounds([0.0, x_max])
+        .y_bounds([0.0, y_ceil])
+        .paint(move |ctx| {
+            for s in &series {
+                // Right-align series that started later so they share the time axis
+                let offset = (n - s.data.len()) as f64;
+                for i in 0..s.data.len().saturating_sub(1) {
+                    ctx.draw(&CanvasLine {
+                        x1: offset + i as f64,
+                        y1: s.data[i] as f64,
+                        x2: offset + (i + 1) as f64,
+                        y2: s.data[i + 1] as f64,
+                        color: s.color,
+                    });
+                }
+            }
+        });
+
+    frame.render_widget(canvas, area);
+}
+
+/// Overlay a dotted vertical rule at each sample where the counters behind
+/// the graphs were reset (server restart or `pg_stat_reset`), so a drop in
+/// the line reads as a discontinuity rather than a real change. `points` is
+/// the number of x positions the chart was drawn with, of which `trailing`
+/// come after the newest mark (e.g. an interpolated replay point).
+/// Row labels of the latency heatmap, shortest bucket first.
+const HEATMAP_LABELS: [&str; 6] = ["<10ms", "<100ms", "<1s", "<10s", "<1m", "1m+"];
+
+/// Columns left of the heatmap for its row labels.
+const HEATMAP_GUTTER: u16 = 7;
+
+/// Shades from an empty cell to the busiest one.
+const HEATMAP_SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+
+/// Where the heatmap's cells go within `area`, its block and gutter
+/// aside; pass it as the area for the reset and annotation marks.
+pub fn heatmap_plot_area(area: Rect) -> Rect {
+    let gutter = HEATMAP_GUTTER.min(area.width);
+    Rect::new(area.x + gutter, area.y, area.width - gutter, area.height)
+}
+
+/// Time across, duration bucket up (longest on top), shade for how many
+/// queries were in the bucket. Samples stretch over the width like the line
+/// charts, so the marks line up; with fewer rows than buckets, neighbouring
+/// buckets share a row.
+pub fn render_heatmap(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    current_label: &str,
+    data: &[[u16; 6]],
+    color: Color,
+) {
+    let block = make_block(title, current_label, color, color);
+    frame.render_widget(block, area);
+    let plot = heatmap_plot_area(area);
+    if data.is_empty() || plot.width < 3 || area.height < 3 {
+        return;
+    }
+    let inner = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+    let cells = Rect::new(plot.x + 1, inner.y, plot.width - 2, inner.height);
+    let (rows, cols, n) = (usize::from(cells.height), usize::from(cells.width), data.len());
+    let buckets = HEATMAP_LABELS.len();
+
+    // Buckets each row covers, counted down from the longest
+    let row_span = |r: usize| {
+        let lo = r * buckets / rows;
+        (lo, ((r + 1) * buckets / rows).max(lo + 1))
+    };
+    // Mean count of the samples falling in a column, per row
+    let cell = |c: usize, r: usize| {
+        let (lo, hi) = (c * n / cols, ((c + 1) * n / cols).max(c * n / cols + 1));
+        let (top, bottom) = row_span(r);
+        let sum: u32 = data[lo..hi.min(n)]
+            .iter()
+            .map(|counts| (top..bottom).map(|t| u32::from(counts[buckets - 1 - t])).sum::<u32>())
+            .sum();
+        sum as f64 / (hi.min(n) - lo) as f64
+    };
+    let max = (0..cols)
+        .flat_map(|c| (0..rows).map(move |r| (c, r)))
+        .map(|(c, r)| cell(c, r))
+        .fold(0.0, f64::max);
+
+    let label_style = Style::default().fg(Theme::fg_dim());
+    let cell_style = Style::default().fg(color);
+    let buf = frame.buffer_mut();
+    for r in 0..rows {
+        let y = cells.y + r as u16;
+        let (top, _) = row_span(r);
+        if r == 0 || row_span(r - 1).0 != top {
+            let label = format!("{:>6}", HEATMAP_LABELS[buckets - 1 - top]);
+            buf.set_stringn(inner.x, y, label, usize::from(HEATMAP_GUTTER - 1), label_style);
+        }
+        if max <= 0.0 {
+            continue;
+        }
+        for c in 0..cols {
+            let value = cell(c, r);
+            let shade = if value > 0.0 {
+                ((value / max * 4.0).ceil() as usize).clamp(1, 4)
+            } else {
+                0
+            };
+            buf[(cells.x + c as u16, y)].set_symbol(HEATMAP_SHADES[shade]).set_style(cell_style);
+        }
+    }
+}
+
+pub fn render_reset_marks(
+    frame: &mut Frame,
+    area: Rect,
+    marks: &[bool],
+    points: usize,
+    trailing: usize,
+) {
+    render_marks(frame, area, marks, points, trailing, "\u{21bb}", Theme::border_warn());
+}
+
+/// Overlay a dotted vertical rule headed `#` at each sample where an
+/// operator annotation was made, laid out like [`render_reset_marks`].
+pub fn render_annotation_marks(
+    frame: &mut Frame,
+    area: Rect,
+    marks: &[bool],
+    points: usize,
+    trailing: usize,
+) {
+    render_marks(frame, area, marks, points, trailing, "#", Theme::border_active());
+}
+
+fn render_marks(
+    frame: &mut Frame,
+    area: Rect,
+    marks: &[bool],
+    points: usize,
+    trailing: usize,
+    head: &str,
+    color: Color,
+) {
+    if area.width < 4 || area.height < 4 || !marks.contains(&true) {
+        return;
+    }
+    let inner = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+    let points = points.max(marks.len() + trailing);
+    let x_max = points.saturating_sub(1).max(1) as f64;
+    // Marks line up with the newest samples
+    let first = points - trailing - marks.len();
+    let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+    let buf = frame.buffer_mut();
+    for (i, _) in marks.iter().enumerate().filter(|(_, &m)| m) {
+        let offset = ((first + i) as f64 / x_max * f64::from(inner.width - 1)).round() as u16;
+        let x = inner.x + offset.min(inner.width - 1);
+        buf[(x, inner.y)].set_symbol(head).set_style(style);
+        for y in inner.y + 1..inner.bottom() {
+            buf[(x, y)].set_symbol("\u{2506}").set_style(style);
+        }
+    }
+}
+
 fn nice_ceil(val: f64) -> f64 {
     if val <= 0.0 {
         return 10.0;
@@ -6,7 +6,7 @@ use ratatui::Frame;
 
 use crate::app::App;
 use super::theme::Theme;
-use super::util::truncate;
+use super::util::{format_duration, truncate};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     if let Some(ref replay) = app.replay {
@@ -33,11 +33,20 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
     let normal_style = Style::default().fg(Theme::fg());
     let label_style = Style::default().fg(Theme::fg_dim());
 
-    let emoji = if app.config.show_emojis { "🐘 " } else { "" };
+    let emoji = if app.show_emojis() { "🐘 " } else { "" };
     let brand_text = format!(" {emoji}pg_glimpse ");
     let mut spans = vec![
         Span::styled(brand_text, brand_style),
         Span::styled("  ", dim_style),
+    ];
+    if let Some(ref profile) = app.config.active_profile {
+        spans.push(Span::styled(
+            format!("[{profile}]"),
+            Style::default().fg(Theme::border_warn()).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" ", dim_style));
+    }
+    spans.extend([
         Span::styled("◆ ", Style::default().fg(Theme::border_ok())),
         Span::styled(
             format!("{}:{}", app.connection.host, app.connection.port),
@@ -51,7 +60,7 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled("  ", dim_style),
         Span::styled("as ", label_style),
         Span::styled(&app.connection.user, normal_style),
-    ];
+    ]);
 
     // Show SSL mode if set (only show for SSL connections, not "No TLS")
     if let Some(ref ssl_label) = &app.connection.ssl_mode {
@@ -61,6 +70,29 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
         }
     }
 
+    if let Some(ref via) = app.connection.actions_via {
+        spans.push(Span::styled("  ", dim_style));
+        spans.push(Span::styled("maintenance via ", label_style));
+        spans.push(Span::styled(via.as_str(), normal_style));
+    }
+
+    let missing = app.server_info.missing_capabilities().len();
+    if missing > 0 {
+        spans.push(Span::styled("  ", dim_style));
+        spans.push(Span::styled(
+            format!("◐ limited visibility ({missing})"),
+            Style::default().fg(Theme::border_warn()),
+        ));
+    }
+
+    if app.switcher.is_multi() {
+        spans.push(Span::styled("  ", dim_style));
+        spans.push(Span::styled(
+            format!("[{}/{}]", app.switcher.current + 1, app.switcher.entries.len()),
+            label_style,
+        ));
+    }
+
     spans.extend([
         Span::styled("  ", dim_style),
         Span::styled(
@@ -71,7 +103,7 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled("  ", dim_style),
         Span::styled("⟳ ", label_style),
         Span::styled(
-            format!("{}s", app.refresh_interval_secs),
+            format!("{}s", app.effective_refresh_secs()),
             normal_style,
         ),
     ]);
@@ -87,9 +119,24 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
+    if let Some(r) = app.feedback.reconnecting {
+        spans.push(Span::styled("  ", dim_style));
+        spans.push(Span::styled(
+            format!(" ⟳ RECONNECTING… attempt {}, retry in {}s ", r.attempt, r.retry_in_secs),
+            Style::default()
+                .fg(Theme::header_bg())
+                .bg(Theme::border_danger())
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    push_reset_badge(&mut spans, app, dim_style);
+    push_alert_badge(&mut spans, app, dim_style);
+    push_maintenance_badge(&mut spans, app, dim_style);
+
     if let Some(ref msg) = app.feedback.status_message {
         spans.push(Span::styled("  ", dim_style));
-        let indicator = if app.feedback.bloat_loading {
+        let indicator = if app.feedback.bloat_loading || app.feedback.buffer_cache_loading {
             const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
             SPINNER[app.feedback.spinner_frame as usize % SPINNER.len()]
         } else {
@@ -127,9 +174,13 @@ fn render_live(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_replay(frame: &mut Frame, app: &App, replay: &crate::app::ReplayState, area: Rect) {
-    let snap_ts = app
-        .snapshot
-        .as_ref().map_or_else(|| "--:--:--".to_string(), |s| s.timestamp.format("%H:%M:%S").to_string());
+    // Show the effective time being rendered, which runs ahead of the
+    // snapshot timestamp while graphs are interpolating
+    let snap_ts = replay
+        .interpolation
+        .map(|i| i.timestamp)
+        .or_else(|| app.snapshot.as_ref().map(|s| s.timestamp))
+        .map_or_else(|| "--:--:--".to_string(), |ts| ts.format("%H:%M:%S").to_string());
 
     let speed_label = format_speed(replay.speed);
 
@@ -164,6 +215,28 @@ fn render_replay(frame: &mut Frame, app: &App, replay: &crate::app::ReplayState,
         Span::styled("  ", dim_style),
     ];
 
+    let marked_at = |i: usize| replay.timestamps.get(i).map_or_else(String::new, |ts| ts.format("%H:%M:%S").to_string());
+    let bookmark = match (replay.mark_start, replay.bookmark) {
+        (Some(start), _) => Some(format!("{}–", marked_at(start))),
+        (None, Some((start, end))) => Some(format!("{}–{}", marked_at(start), marked_at(end))),
+        (None, None) => None,
+    };
+    if let Some(bookmark) = bookmark {
+        spans.push(Span::styled("⚑ ", Style::default().fg(Theme::border_warn())));
+        spans.push(Span::styled(bookmark, normal_style));
+        spans.push(Span::styled("  ", dim_style));
+    }
+
+    if let Some(ref compare) = replay.compare {
+        spans.push(Span::styled("vs ", label_style));
+        spans.push(Span::styled(truncate(&compare.filename, 25), normal_style));
+        spans.push(Span::styled(
+            format!(" {}/{}", compare.position, compare.total),
+            label_style,
+        ));
+        spans.push(Span::styled("  ", dim_style));
+    }
+
     if replay.playing {
         spans.push(Span::styled(
             " ▶ PLAYING ",
@@ -182,12 +255,26 @@ fn render_replay(frame: &mut Frame, app: &App, replay: &crate::app::ReplayState,
         ));
     }
 
+    push_reset_badge(&mut spans, app, dim_style);
+    push_alert_badge(&mut spans, app, dim_style);
+
     if let Some(ref msg) = app.feedback.status_message {
         spans.push(Span::styled("  ", dim_style));
         spans.push(Span::styled(
             format!("● {msg}"),
             Style::default().fg(Theme::border_active()),
         ));
+    } else if let Some(event) = app
+        .snapshot
+        .as_ref()
+        .and_then(|s| replay.trail(s.timestamp).last())
+    {
+        // Most recent operator action at this point of the recording
+        spans.push(Span::styled("  ", dim_style));
+        spans.push(Span::styled(
+            format!("⚑ {}", truncate(&event.describe(), 40)),
+            Style::default().fg(Theme::border_warn()),
+        ));
     }
 
     // Right-align the timestamp
@@ -205,6 +292,82 @@ fn render_replay(frame: &mut Frame, app: &App, replay: &crate::app::ReplayState,
     frame.render_widget(paragraph, area);
 }
 
+/// Badge shown while a server restart or stats reset is within graph history
+fn push_reset_badge(spans: &mut Vec<Span<'_>>, app: &App, dim_style: Style) {
+    let Some(reset) = app.metrics.last_reset.filter(|_| app.metrics.has_recent_reset()) else {
+        return;
+    };
+    let label = if reset.is_server_restart() {
+        " \u{21bb} RESTART "
+    } else {
+        " \u{21bb} STATS RESET "
+    };
+    spans.push(Span::styled("  ", dim_style));
+    spans.push(Span::styled(
+        label,
+        Style::default()
+            .fg(Theme::header_bg())
+            .bg(Theme::border_danger())
+            .add_modifier(Modifier::BOLD),
+    ));
+}
+
+/// Badge following a maintenance command started from an inspect overlay,
+/// with its phase from the progress views once its backend appears there
+fn push_maintenance_badge(spans: &mut Vec<Span<'_>>, app: &App, dim_style: Style) {
+    let Some(running) = &app.feedback.maintenance else {
+        return;
+    };
+    let mut label = format!(" \u{2699} {}", running.command.describe());
+    let progress = app.maintenance_progress();
+    if let Some(op) = &progress {
+        label.push_str(&format!(" \u{b7} {}", op.phase));
+        if let Some(pct) = op.progress_pct() {
+            label.push_str(&format!(" {pct:.0}%"));
+        }
+    }
+    let elapsed = progress
+        .and_then(|op| op.elapsed_secs)
+        .unwrap_or_else(|| running.started.elapsed().as_secs_f64());
+    label.push_str(&format!(" \u{b7} {} ", format_duration(elapsed.floor())));
+    spans.push(Span::styled("  ", dim_style));
+    spans.push(Span::styled(
+        label,
+        Style::default()
+            .fg(Theme::header_bg())
+            .bg(Theme::border_warn())
+            .add_modifier(Modifier::BOLD),
+    ));
+}
+
+/// Badge counting firing alerts. Flashes until the alerts panel is opened.
+fn push_alert_badge(spans: &mut Vec<Span<'_>>, app: &App, dim_style: Style) {
+    let active = app.alerts.active_count();
+    if active == 0 {
+        return;
+    }
+    // The spinner ticks every 80ms; swap colours about twice a second
+    let flash_off = app.alerts.unseen && (app.feedback.spinner_frame / 6) % 2 == 1;
+    let bg = if flash_off {
+        Theme::border_warn()
+    } else {
+        Theme::border_danger()
+    };
+    let label = if active == 1 {
+        " ⚠ 1 ALERT · ! ".to_string()
+    } else {
+        format!(" ⚠ {active} ALERTS · ! ")
+    };
+    spans.push(Span::styled("  ", dim_style));
+    spans.push(Span::styled(
+        label,
+        Style::default()
+            .fg(Theme::header_bg())
+            .bg(bg)
+            .add_modifier(Modifier::BOLD),
+    ));
+}
+
 fn format_speed(speed: f64) -> String {
     if speed == f64::from(speed as u32) {
         format!("{}x", speed as u32)
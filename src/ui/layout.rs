@@ -1,35 +1,108 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+use crate::app::{App, BottomPanel};
+
+/// Narrower than this, the graph grid becomes a one-row summary and panels
+/// drop their less important columns.
+pub const NARROW_WIDTH: u16 = 100;
+/// From this width on, panels add columns there is usually no room for.
+pub const WIDE_WIDTH: u16 = 180;
+
+/// How much room there is across. The screen's tier decides the graph
+/// grid; panels take theirs from their own width, so each half of the
+/// split view is laid out for the room it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutTier {
+    Narrow,
+    Normal,
+    Wide,
+}
+
+impl LayoutTier {
+    pub fn for_width(width: u16) -> Self {
+        if width < NARROW_WIDTH {
+            Self::Narrow
+        } else if width >= WIDE_WIDTH {
+            Self::Wide
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Chooser columns a panel leaves out in the narrow tier. What is left
+/// still tells the rows apart and shows what is wrong with them.
+pub fn narrow_hidden(panel: BottomPanel) -> &'static [&'static str] {
+    match panel {
+        BottomPanel::Queries => &["User", "Database"],
+        BottomPanel::Statements => &["Max", "P95", "Stddev", "Rows", "Reads", "Temp"],
+        BottomPanel::TableStats => &["SeqScan", "IdxScan", "Dead"],
+        BottomPanel::Indexes => &["Tup Read", "Tup Fetch"],
+        _ => &[],
+    }
+}
+
+/// `panel`'s chooser columns that are shown at `width`: the ones the
+/// operator kept, less the narrow tier's.
+pub fn shown_columns(app: &App, panel: BottomPanel, width: u16) -> Vec<bool> {
+    let mut shown = app.visible_columns(panel);
+    if LayoutTier::for_width(width) == LayoutTier::Narrow {
+        let dropped = narrow_hidden(panel);
+        for (show, column) in shown.iter_mut().zip(panel.descriptor().columns) {
+            if dropped.contains(column) {
+                *show = false;
+            }
+        }
+    }
+    shown
+}
+
 pub struct LayoutAreas {
+    pub tier: LayoutTier,
     pub header: Rect,
+    /// One-row stand-in for the graph grid in the narrow tier and plain
+    /// mode; zero height otherwise
+    pub summary: Rect,
     pub graph_tl: Rect,
     pub graph_tr: Rect,
     pub graph_bl: Rect,
     pub graph_br: Rect,
     pub queries: Rect,
+    /// Replay timeline; zero height outside replay
+    pub timeline: Rect,
     pub footer: Rect,
 }
 
-pub fn compute_layout(area: Rect, graphs_collapsed: bool) -> LayoutAreas {
-    if graphs_collapsed {
-        // Collapsed: Header (1) + Bottom panel (fill) + Footer (2)
+pub fn compute_layout(area: Rect, graphs_collapsed: bool, plain: bool, timeline: bool) -> LayoutAreas {
+    let timeline_height = u16::from(timeline);
+    let tier = LayoutTier::for_width(area.width);
+    if graphs_collapsed || plain || tier == LayoutTier::Narrow {
+        // Narrow or plain: the summary row takes the graphs' place unless
+        // they are hidden altogether
+        let summary_height = u16::from(!graphs_collapsed);
+        // Collapsed: Header (1) + Summary (0/1) + Bottom panel (fill) + Timeline (0/1) + Footer (2)
         let outer = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(1),
+                Constraint::Length(summary_height),
                 Constraint::Min(10),
+                Constraint::Length(timeline_height),
                 Constraint::Length(2),
             ])
             .split(area);
 
         LayoutAreas {
+            tier,
             header: outer[0],
+            summary: outer[1],
             graph_tl: Rect::default(),
             graph_tr: Rect::default(),
             graph_bl: Rect::default(),
             graph_br: Rect::default(),
-            queries: outer[1],
-            footer: outer[2],
+            queries: outer[2],
+            timeline: outer[3],
+            footer: outer[4],
         }
     } else {
         // Normal layout
@@ -39,6 +112,7 @@ pub fn compute_layout(area: Rect, graphs_collapsed: bool) -> LayoutAreas {
                 Constraint::Length(1),
                 Constraint::Percentage(40),
                 Constraint::Min(10),
+                Constraint::Length(timeline_height),
                 Constraint::Length(2),
             ])
             .split(area);
@@ -59,13 +133,29 @@ pub fn compute_layout(area: Rect, graphs_collapsed: bool) -> LayoutAreas {
             .split(graph_rows[1]);
 
         LayoutAreas {
+            tier,
             header: outer[0],
+            summary: Rect::default(),
             graph_tl: graph_top[0],
             graph_tr: graph_top[1],
             graph_bl: graph_bot[0],
             graph_br: graph_bot[1],
             queries: outer[2],
-            footer: outer[3],
+            timeline: outer[3],
+            footer: outer[4],
         }
     }
 }
+
+/// The focused and the other pane of the split view, side by side.
+pub fn split_panes(area: Rect, focus_right: bool) -> (Rect, Rect) {
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    if focus_right {
+        (halves[1], halves[0])
+    } else {
+        (halves[0], halves[1])
+    }
+}
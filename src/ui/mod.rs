@@ -1,16 +1,18 @@
-mod active_queries;
+pub(crate) mod active_queries;
 mod footer;
 mod graph;
 mod header;
 mod layout;
 mod overlay;
-mod panels;
+pub(crate) mod panels;
 mod sparkline;
 mod stats_panel;
 pub mod theme;
-mod util;
+mod timeline;
+pub(crate) mod util;
 
-use crate::app::{App, BottomPanel, ConfirmAction, InspectTarget, ViewMode};
+use crate::app::{App, ConfirmAction, InspectTarget, MouseTargets, ViewMode};
+use ratatui::layout::Rect;
 use ratatui::Frame;
 use theme::Theme;
 use util::format_duration;
@@ -19,87 +21,198 @@ use util::format_duration;
 mod snapshot_tests;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
-    let areas = layout::compute_layout(frame.area(), app.graphs_collapsed);
+    let plain = app.config.plain();
+    let areas = layout::compute_layout(frame.area(), app.graphs_collapsed, plain, app.replay.is_some());
 
     header::render(frame, app, areas.header);
+    let (panel_area, split_pane) = match app.split {
+        Some(split) => {
+            let (focused, other) = layout::split_panes(areas.queries, split.focus_right);
+            (focused, Some((other, split.other)))
+        }
+        None => (areas.queries, None),
+    };
+    app.mouse_targets = MouseTargets::for_panel(panel_area);
+    if let Some(ref replay) = app.replay {
+        timeline::render(frame, replay, areas.timeline);
+        app.mouse_targets.timeline = timeline::bar_area(replay, areas.timeline);
+    }
+
+    if areas.summary.height > 0 {
+        stats_panel::render_summary(frame, app, areas.summary);
+    }
 
-    // Only render graphs if not collapsed
-    if !app.graphs_collapsed {
+    // Only render graphs if not collapsed (or folded into the summary row)
+    if !app.graphs_collapsed && areas.tier != layout::LayoutTier::Narrow {
         let marker = app.config.graph_marker.to_marker();
-        let show_emojis = app.config.show_emojis;
+        let show_emojis = app.show_emojis();
+
+        // While a replay plays, extend each graph with a point blended toward
+        // the next snapshot so the lines move smoothly between steps
+        let interp = app.replay.as_ref().and_then(|r| r.interpolation);
+        // Compared recording, drawn as a second line on the same axes
+        let compare = app.replay.as_ref().and_then(|r| r.compare.as_ref());
+        // Samples where counters were reset, marked on every graph
+        let reset_marks = app.metrics.reset_marks.as_vec();
+        // Samples where the operator annotated the session
+        let annotation_marks = app.metrics.annotation_marks.as_vec();
+        let interp_points = usize::from(interp.is_some());
 
         // Top half: 2x2 graph grid
-        let conn_data = app.metrics.connections.as_vec();
-        let conn_current = app.metrics.connections.last().unwrap_or(0);
+        let mut conn_data = app.metrics.connections.as_vec();
+        if let Some(i) = interp {
+            conn_data.push(i.connections);
+        }
+        let conn_current = conn_data.last().copied().unwrap_or(0);
         let conn_emoji = if show_emojis { "🔌 " } else { "" };
         let conn_title = format!("{conn_emoji}Connections");
-        graph::render_line_chart(
-            frame,
-            areas.graph_tl,
-            &conn_title,
-            &conn_current.to_string(),
-            &conn_data,
-            Theme::graph_connections(),
-            Theme::graph_connections(),
-            marker,
-            Some(app.server_info.max_connections as u64),
-        );
+        let conn_max = Some(app.server_info.max_connections as u64);
+        if let Some(c) = compare {
+            let other = c.metrics.connections.as_vec();
+            let other_current = other.last().copied().unwrap_or(0);
+            graph::render_compare_chart(
+                frame,
+                areas.graph_tl,
+                &conn_title,
+                &format!("{conn_current} vs {other_current}"),
+                &conn_data,
+                &other,
+                Theme::graph_connections(),
+                Theme::fg_dim(),
+                marker,
+                conn_max,
+            );
+        } else {
+            graph::render_line_chart(
+                frame,
+                areas.graph_tl,
+                &conn_title,
+                &conn_current.to_string(),
+                &conn_data,
+                Theme::graph_connections(),
+                Theme::graph_connections(),
+                marker,
+                conn_max,
+            );
+        }
+
+        graph::render_reset_marks(frame, areas.graph_tl, &reset_marks, conn_data.len(), interp_points);
+        graph::render_annotation_marks(frame, areas.graph_tl, &annotation_marks, conn_data.len(), interp_points);
 
         stats_panel::render(frame, app, areas.graph_tr);
 
-        let cache_data = app.metrics.hit_ratio.as_vec();
-        let cache_current = app.metrics.hit_ratio.last().unwrap_or(0);
+        let mut cache_data = app.metrics.hit_ratio.as_vec();
+        if let Some(i) = interp {
+            cache_data.push(i.hit_ratio);
+        }
+        let cache_current = cache_data.last().copied().unwrap_or(0);
         let cache_pct = cache_current as f64 / 10.0;
         let cache_color = Theme::hit_ratio_color(cache_pct);
         let cache_emoji = if show_emojis { "💾 " } else { "" };
         let cache_title = format!("{cache_emoji}Cache Hit");
-        graph::render_ratio_chart(
-            frame,
-            areas.graph_bl,
-            &cache_title,
-            &format!("{cache_pct:.1}%"),
-            &cache_data,
-            cache_color,
-            Theme::graph_cache(),
-            marker,
-        );
-
-        let avg_data = app.metrics.avg_query_time.as_vec();
-        let avg_current = app.metrics.avg_query_time.last().unwrap_or(0);
+        if let Some(c) = compare {
+            let other = c.metrics.hit_ratio.as_vec();
+            let other_pct = other.last().copied().unwrap_or(0) as f64 / 10.0;
+            graph::render_compare_chart(
+                frame,
+                areas.graph_bl,
+                &cache_title,
+                &format!("{cache_pct:.1}% vs {other_pct:.1}%"),
+                &cache_data,
+                &other,
+                Theme::graph_cache(),
+                Theme::fg_dim(),
+                marker,
+                Some(1000),
+            );
+        } else {
+            graph::render_ratio_chart(
+                frame,
+                areas.graph_bl,
+                &cache_title,
+                &format!("{cache_pct:.1}%"),
+                &cache_data,
+                cache_color,
+                Theme::graph_cache(),
+                marker,
+            );
+        }
+        graph::render_reset_marks(frame, areas.graph_bl, &reset_marks, cache_data.len(), interp_points);
+        graph::render_annotation_marks(frame, areas.graph_bl, &annotation_marks, cache_data.len(), interp_points);
+
+        let mut avg_data = app.metrics.avg_query_time.as_vec();
+        if let Some(i) = interp {
+            avg_data.push(i.avg_query_time);
+        }
+        let avg_current = avg_data.last().copied().unwrap_or(0);
         let avg_label = format_duration(avg_current as f64 / 1000.0);
         let avg_emoji = if show_emojis { "⏱️ " } else { "" };
         let avg_title = format!("{avg_emoji}Avg Duration");
-        graph::render_line_chart(
-            frame,
-            areas.graph_br,
-            &avg_title,
-            &avg_label,
-            &avg_data,
-            Theme::graph_latency(),
-            Theme::graph_latency(),
-            marker,
-            None,
-        );
+        // The compared recording has no buckets to show, so comparing keeps the line
+        let heatmap = app.config.latency_heatmap && compare.is_none();
+        if heatmap {
+            // Interpolation blends averages, not buckets, so the heatmap
+            // moves a whole sample at a time
+            let heat_data = app.metrics.latency_buckets.as_vec();
+            let heat_title = format!("{avg_emoji}Duration Heatmap");
+            let plot = graph::heatmap_plot_area(areas.graph_br);
+            graph::render_heatmap(
+                frame,
+                areas.graph_br,
+                &heat_title,
+                &format!("{avg_label} avg"),
+                &heat_data,
+                Theme::graph_latency(),
+            );
+            graph::render_reset_marks(frame, plot, &reset_marks, heat_data.len(), 0);
+            graph::render_annotation_marks(frame, plot, &annotation_marks, heat_data.len(), 0);
+        } else if let Some(c) = compare {
+            let other = c.metrics.avg_query_time.as_vec();
+            let other_label =
+                format_duration(other.last().copied().unwrap_or(0) as f64 / 1000.0);
+            graph::render_compare_chart(
+                frame,
+                areas.graph_br,
+                &avg_title,
+                &format!("{avg_label} vs {other_label}"),
+                &avg_data,
+                &other,
+                Theme::graph_latency(),
+                Theme::fg_dim(),
+                marker,
+                None,
+            );
+        } else {
+            graph::render_line_chart(
+                frame,
+                areas.graph_br,
+                &avg_title,
+                &avg_label,
+                &avg_data,
+                Theme::graph_latency(),
+                Theme::graph_latency(),
+                marker,
+                None,
+            );
+        }
+        if !heatmap {
+            graph::render_reset_marks(frame, areas.graph_br, &reset_marks, avg_data.len(), interp_points);
+            graph::render_annotation_marks(frame, areas.graph_br, &annotation_marks, avg_data.len(), interp_points);
+        }
     }
 
-    // Bottom half: dispatch based on active panel
-    let panel = app.bottom_panel;
-    match panel {
-        BottomPanel::Queries => active_queries::render(frame, app, areas.queries),
-        BottomPanel::Blocking => panels::render_blocking(frame, app, areas.queries),
-        BottomPanel::WaitEvents => panels::render_wait_events(frame, app, areas.queries),
-        BottomPanel::TableStats => panels::render_table_stats(frame, app, areas.queries),
-        BottomPanel::Replication => panels::render_replication(frame, app, areas.queries),
-        BottomPanel::VacuumProgress => panels::render_vacuum_progress(frame, app, areas.queries),
-        BottomPanel::Wraparound => panels::render_wraparound(frame, app, areas.queries),
-        BottomPanel::Indexes => panels::render_indexes(frame, app, areas.queries),
-        BottomPanel::Statements => panels::render_statements(frame, app, areas.queries),
-        BottomPanel::WalIo => panels::render_wal_io(frame, app, areas.queries),
-        BottomPanel::Settings => panels::render_settings(frame, app, areas.queries),
-        BottomPanel::Extensions => panels::render_extensions(frame, app, areas.queries),
+    // Bottom half: dispatch based on active panel. The unfocused split pane
+    // goes first so the focused one leaves the mouse targets behind.
+    if let Some((area, panel)) = split_pane {
+        let targets = std::mem::take(&mut app.mouse_targets);
+        (panel.descriptor().render)(frame, app, area);
+        dim_border(frame, area);
+        app.mouse_targets = MouseTargets { split_pane: Some(area), ..targets };
     }
+    (app.bottom_panel.descriptor().render)(frame, app, panel_area);
 
     footer::render(frame, app, areas.footer);
+    app.mouse_targets.tabs = footer::tab_targets(app, areas.footer);
 
     // Overlays (popup-only)
     match &app.view_mode {
@@ -110,24 +223,30 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 InspectTarget::Index(key) => overlay::render_index_inspect(frame, app, area, key),
                 InspectTarget::Statement(queryid) => overlay::render_statement_inspect(frame, app, area, *queryid),
                 InspectTarget::Replication(pid) => overlay::render_replication_inspect(frame, app, area, *pid),
+                InspectTarget::Slot(name) => overlay::render_slot_inspect(frame, app, area, name),
                 InspectTarget::Table(key) => overlay::render_table_inspect(frame, app, area, key),
                 InspectTarget::Blocking(pid) => overlay::render_blocking_inspect(frame, app, area, *pid),
                 InspectTarget::Vacuum(pid) => overlay::render_vacuum_inspect(frame, app, area, *pid),
                 InspectTarget::Wraparound(datname) => overlay::render_wraparound_inspect(frame, app, area, datname),
                 InspectTarget::Settings(name) => overlay::render_settings_inspect(frame, app, area, name),
                 InspectTarget::Extensions(name) => overlay::render_extensions_inspect(frame, app, area, name),
+                InspectTarget::Role(name) => overlay::render_role_inspect(frame, app, area, name),
+                InspectTarget::WaitEvent(wait_event_type, wait_event) => {
+                    overlay::render_wait_event_inspect(frame, app, area, wait_event_type, wait_event);
+                }
             }
         }
+        ViewMode::SqlView(target) => overlay::render_sql_view(frame, app, target, frame.area()),
         ViewMode::Confirm(action) => {
             let area = frame.area();
             match action {
                 ConfirmAction::Cancel(pid) => overlay::render_confirm_cancel(frame, *pid, area),
                 ConfirmAction::Kill(pid) => overlay::render_confirm_kill(frame, *pid, area),
                 ConfirmAction::CancelChoice { selected_pid, all_pids } => {
-                    overlay::render_cancel_choice(frame, *selected_pid, all_pids, &app.filter.text, area);
+                    overlay::render_cancel_choice(frame, *selected_pid, all_pids, &app.filter().text, area);
                 }
                 ConfirmAction::KillChoice { selected_pid, all_pids } => {
-                    overlay::render_kill_choice(frame, *selected_pid, all_pids, &app.filter.text, area);
+                    overlay::render_kill_choice(frame, *selected_pid, all_pids, &app.filter().text, area);
                 }
                 ConfirmAction::CancelBatch(pids) => {
                     overlay::render_confirm_cancel_batch(frame, pids, area);
@@ -141,6 +260,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 ConfirmAction::ResetStatStatements => {
                     overlay::render_confirm_reset_statements(frame, area);
                 }
+                ConfirmAction::DropSlot(slot) => overlay::render_confirm_drop_slot(frame, slot, area),
+                ConfirmAction::RunMaintenance(maintenance) => {
+                    overlay::render_confirm_maintenance(frame, maintenance, app.server_info.major_version(), area);
+                }
             }
         }
         ViewMode::Config | ViewMode::ConfigEditRecordingsDir => {
@@ -148,8 +271,79 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         }
         ViewMode::Help => overlay::render_help(frame, app, frame.area()),
         ViewMode::Recordings => overlay::render_recordings(frame, app, frame.area()),
-        ViewMode::Normal | ViewMode::Filter => {}
+        ViewMode::Connections => overlay::render_connections(frame, app, frame.area()),
+        ViewMode::Columns => overlay::render_columns(frame, app, frame.area()),
+        ViewMode::Timeouts => overlay::render_timeouts(frame, app, frame.area()),
+        ViewMode::SettingsReview => overlay::render_settings_review(frame, app, frame.area()),
+        ViewMode::Collector => overlay::render_collector(frame, app, frame.area()),
+        ViewMode::Explain => overlay::render_explain(frame, app, frame.area()),
+        ViewMode::IndexAdvice => overlay::render_index_advice(frame, app, frame.area()),
+        ViewMode::Activity => overlay::render_activity(frame, app, frame.area()),
+        ViewMode::Glossary(panel) => overlay::render_glossary(frame, app, *panel, frame.area()),
+        ViewMode::Normal => {
+            let above_footer = Rect {
+                height: areas.footer.y.saturating_sub(frame.area().y),
+                ..frame.area()
+            };
+            overlay::render_chord_hint(frame, app, above_footer);
+        }
+        // Text input is drawn in the footer
+        ViewMode::Filter
+        | ViewMode::EditNote(_)
+        | ViewMode::ExportPrompt
+        | ViewMode::SeekPrompt
+        | ViewMode::Annotate => {}
+    }
+
+    if plain {
+        plain_symbols(frame);
     }
 }
 
 
+
+/// Redraw the rounded border of a split pane without focus in the dim
+/// border color, leaving its title as it is.
+fn dim_border(frame: &mut Frame, area: Rect) {
+    if area.width < 2 || area.height < 2 {
+        return;
+    }
+    let buf = frame.buffer_mut();
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let edge = (area.left()..=right)
+        .flat_map(|x| [(x, area.top()), (x, bottom)])
+        .chain((area.top()..=bottom).flat_map(|y| [(area.left(), y), (right, y)]));
+    for position in edge {
+        if let Some(cell) = buf.cell_mut(position) {
+            if matches!(cell.symbol(), "─" | "│" | "╭" | "╮" | "╰" | "╯") {
+                cell.set_fg(Theme::border_dim());
+            }
+        }
+    }
+}
+
+/// Plain mode: swap box drawing, bars and arrows for ASCII, so borders read
+/// as `+`, `-` and `|` and bars as `#` on terminals without Unicode and to
+/// screen readers.
+fn plain_symbols(frame: &mut Frame) {
+    let buf = frame.buffer_mut();
+    for cell in &mut buf.content {
+        let ascii = match cell.symbol() {
+            "─" | "━" | "═" | "┄" | "┈" | "╌" | "╴" | "╶" | "—" | "–" => "-",
+            "│" | "┃" | "║" | "┆" | "┊" | "╎" | "╵" | "╷" => "|",
+            "►" | "→" => ">",
+            "←" => "<",
+            "↑" => "^",
+            "↓" => "v",
+            "·" => "-",
+            "…" => ".",
+            "░" => ".",
+            "▒" => ":",
+            s if s.chars().next().is_some_and(|c| ('\u{2500}'..='\u{257f}').contains(&c)) => "+",
+            // The rest of the block elements: bars, gauges and heatmap cells
+            s if s.chars().next().is_some_and(|c| ('\u{2580}'..='\u{259f}').contains(&c)) => "#",
+            _ => continue,
+        };
+        cell.set_symbol(ascii);
+    }
+}
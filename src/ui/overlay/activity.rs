@@ -0,0 +1,104 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::recorder::net_setting_changes;
+use crate::ui::theme::Theme;
+
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_activity(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(70, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block(
+        "Operator Activity  [j/k] scroll  [Esc] close",
+        Theme::border_active(),
+    );
+
+    let time_style = Style::default().fg(Theme::fg_dim());
+    let desc_style = Style::default().fg(Theme::fg());
+    let latest_style = Style::default()
+        .fg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Theme::fg_dim());
+
+    let mut lines = vec![Line::from("")];
+
+    // Net settings diff between the start and end of the recording
+    let changed = app
+        .replay
+        .as_ref()
+        .map(|r| net_setting_changes(&r.events))
+        .unwrap_or_default();
+    if !changed.is_empty() {
+        lines.push(section_header("Settings changed during recording"));
+        lines.push(Line::from(""));
+        for (name, from, to) in changed {
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {name}  "), desc_style),
+                Span::styled(from, time_style),
+                Span::styled(" → ", time_style),
+                Span::styled(to, Style::default().fg(Theme::border_warn())),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Every annotation in the recording, with those still ahead dimmed
+    if !app.annotations.is_empty() {
+        let now = app.snapshot.as_ref().map(|s| s.timestamp);
+        lines.push(section_header("Annotations"));
+        lines.push(Line::from(""));
+        for annotation in &app.annotations {
+            let ahead = now.is_some_and(|now| annotation.at > now);
+            let style = if ahead {
+                dim_style
+            } else {
+                Style::default().fg(Theme::border_active())
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {}  ", annotation.at.format("%H:%M:%S")), time_style),
+                Span::styled(format!("# {}", annotation.text), style),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines.extend([section_header("Actions up to current snapshot"), Line::from("")]);
+
+    let trail = match (&app.replay, &app.snapshot) {
+        (Some(replay), Some(snap)) => replay.trail(snap.timestamp),
+        _ => &[],
+    };
+
+    if trail.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    No operator actions recorded yet.",
+            dim_style,
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "    Enable \"Record Actions\" in the config overlay to capture them.",
+            dim_style,
+        )));
+    } else {
+        // Newest first so the most recent action is visible without scrolling
+        for (i, event) in trail.iter().rev().enumerate() {
+            let style = if i == 0 { latest_style } else { desc_style };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {}  ", event.timestamp.format("%H:%M:%S")),
+                    time_style,
+                ),
+                Span::styled(event.describe(), style),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(block);
+    render_scrollable(frame, app, paragraph, popup);
+}
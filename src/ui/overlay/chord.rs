@@ -0,0 +1,53 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::chords;
+use crate::app::App;
+use crate::ui::theme::Theme;
+
+use super::overlay_block;
+
+/// Keys that can follow the pending chord prefix, in a box at the bottom
+/// right of `area`.
+pub fn render_chord_hint(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(prefix) = app.pending_chord else {
+        return;
+    };
+    let Some(title) = chords::prefix_title(prefix) else {
+        return;
+    };
+    let bindings = chords::continuations(prefix);
+
+    let key_style = Style::default()
+        .fg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+    let label_style = Style::default().fg(Theme::fg());
+    let lines: Vec<Line> = bindings
+        .iter()
+        .map(|b| {
+            Line::from(vec![
+                Span::styled(format!(" {prefix}{} ", b.key), key_style),
+                Span::styled(b.label, label_style),
+            ])
+        })
+        .collect();
+
+    let title = format!("{prefix} · {title}  [Esc] cancel");
+    let widest = bindings.iter().map(|b| b.label.chars().count() + 4).max().unwrap_or(0);
+    let width = (widest.max(title.chars().count() + 2) as u16 + 2).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.right().saturating_sub(width),
+        y: area.bottom().saturating_sub(height),
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).block(overlay_block(&title, Theme::border_active())),
+        popup,
+    );
+}
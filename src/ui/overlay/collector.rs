@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{format_time_ms, wrap_words};
+
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+fn ms(d: Duration) -> String {
+    format_time_ms(d.as_secs_f64() * 1000.0)
+}
+
+pub fn render_collector(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block(
+        "Collector Stats  [j/k] scroll  [Esc] close",
+        Theme::border_active(),
+    );
+
+    let stats = &app.collector;
+    let hooks = &app.config.hooks;
+    let name_style = Style::default().fg(Theme::fg());
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let ok_style = Style::default().fg(Theme::border_ok());
+    let danger_style = Style::default()
+        .fg(Theme::border_danger())
+        .add_modifier(Modifier::BOLD);
+
+    let row = |name: &str, value: String| -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("    {name:<20}"), name_style),
+            Span::styled(value, ok_style),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Snapshots"),
+        Line::from(""),
+        row("Collected", stats.collected.to_string()),
+        row("Failed", stats.failed.to_string()),
+        row("Last took", stats.last_took.map_or_else(|| "-".into(), ms)),
+        row("Average", stats.average().map_or_else(|| "-".into(), ms)),
+        row("Slowest", ms(stats.slowest)),
+        Line::from(""),
+        section_header("Visibility"),
+        Line::from(""),
+    ];
+
+    let missing = app.server_info.missing_capabilities();
+    if missing.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    Full: the role can read everything pg_glimpse uses",
+            ok_style,
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("    Limited for role {}:", app.connection.user),
+            Style::default().fg(Theme::border_warn()),
+        )));
+    }
+    for gap in &missing {
+        lines.push(Line::from(vec![
+            Span::styled("    ✗ ", Style::default().fg(Theme::border_warn())),
+            Span::styled(gap.what, name_style),
+        ]));
+        // Kept under the gap's name when a GRANT is longer than the overlay
+        let width = usize::from(popup.width.saturating_sub(2 + 6));
+        for grant in wrap_words(gap.grant, width) {
+            lines.push(Line::from(Span::styled(format!("      {grant}"), dim_style)));
+        }
+    }
+    lines.extend([Line::from(""), section_header("Hooks"), Line::from("")]);
+
+    if hooks.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    None configured. Add [hooks] before/after statements to config.toml.",
+            dim_style,
+        )));
+    } else if stats.hooks.is_empty() {
+        lines.push(Line::from(Span::styled("    Not run yet", dim_style)));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("    Timeout {} ms per statement", hooks.timeout_ms),
+            dim_style,
+        )));
+        lines.push(Line::from(""));
+    }
+
+    for outcome in &stats.hooks {
+        let (status, status_style) = if outcome.error.is_some() {
+            ("failed", danger_style)
+        } else {
+            ("ok", ok_style)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<8}", outcome.phase.label()), dim_style),
+            Span::styled(format!("{status:<8}"), status_style),
+            Span::styled(format!("{:<12}", ms(outcome.took)), dim_style),
+            Span::styled(outcome.sql.clone(), name_style),
+        ]));
+        if let Some(error) = &outcome.error {
+            lines.push(Line::from(Span::styled(
+                format!("      {error}"),
+                Style::default().fg(Theme::border_danger()),
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
@@ -0,0 +1,68 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+
+use super::{centered_rect, overlay_block, section_header};
+
+pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
+    let panel = app.bottom_panel;
+    let columns = panel.descriptor().columns;
+    let popup = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block(
+        " Columns  [Space] toggle  [Esc] close ",
+        Theme::border_active(),
+    );
+
+    let key_style = Style::default()
+        .fg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let selected_style = Style::default()
+        .fg(Theme::overlay_bg())
+        .bg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(""),
+        section_header(panel.label()),
+        Line::from(""),
+    ];
+
+    for (i, (column, shown)) in columns.iter().zip(app.visible_columns(panel)).enumerate() {
+        let is_selected = i == app.column_cursor;
+        let indicator = if is_selected { "  > " } else { "    " };
+        let (mark, mark_style) = if shown {
+            ("[x] ", Style::default().fg(Theme::border_ok()))
+        } else {
+            ("[ ] ", dim_style)
+        };
+        let row_style = if is_selected {
+            selected_style
+        } else if shown {
+            Style::default().fg(Theme::fg())
+        } else {
+            dim_style
+        };
+        lines.push(Line::from(vec![
+            Span::styled(indicator, key_style),
+            Span::styled(mark, mark_style),
+            Span::styled(format!("{column:<16}"), row_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "    Saved to the config file",
+        dim_style,
+    )));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, popup);
+}
@@ -7,6 +7,7 @@ use ratatui::Frame;
 use crate::app::{App, ViewMode};
 use crate::config::ConfigItem;
 use crate::recorder::Recorder;
+use crate::row_colors::RowColorRule;
 use crate::ui::theme::Theme;
 
 use super::{centered_rect, overlay_block, section_header};
@@ -15,7 +16,7 @@ pub fn render_config(frame: &mut Frame, app: &App, area: Rect) {
     let popup = centered_rect(70, 75, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "🔧 " } else { "" };
+    let emoji = if app.show_emojis() { "🔧 " } else { "" };
     let title = format!("{emoji}Configuration  [←→] change  [q/Esc] save & close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -45,7 +46,20 @@ pub fn render_config(frame: &mut Frame, app: &App, area: Rect) {
             ConfigItem::GraphMarker => app.config.graph_marker.label().to_string(),
             ConfigItem::ColorTheme => app.config.color_theme.label().to_string(),
             ConfigItem::ShowEmojis => if app.config.show_emojis { "On" } else { "Off" }.to_string(),
+            ConfigItem::PlainMode => match (app.config.plain_mode, app.config.plain_flag) {
+                (_, true) => "On (--plain)".into(),
+                (true, false) => "On".into(),
+                (false, false) => "Off".into(),
+            },
             ConfigItem::RefreshInterval => format!("{}s", app.config.refresh_interval_secs),
+            ConfigItem::AdaptiveRefresh => {
+                let adaptive = &app.config.adaptive_refresh;
+                if adaptive.enabled {
+                    format!("{}–{}s", adaptive.min_secs, adaptive.max_secs)
+                } else {
+                    "Off".into()
+                }
+            }
             ConfigItem::WarnDuration => format!("{:.1}s", app.config.warn_duration_secs),
             ConfigItem::DangerDuration => format!("{:.1}s", app.config.danger_duration_secs),
             ConfigItem::RecordingRetention => {
@@ -56,6 +70,26 @@ pub fn render_config(frame: &mut Frame, app: &App, area: Rect) {
                     format!("{}m", secs / 60)
                 }
             }
+            ConfigItem::LatencyHeatmap => if app.config.latency_heatmap { "On" } else { "Off" }.to_string(),
+            ConfigItem::HighlightChanges => if app.config.highlight_changes { "On" } else { "Off" }.to_string(),
+            ConfigItem::RecordUiEvents => if app.config.record_ui_events { "On" } else { "Off" }.to_string(),
+            ConfigItem::ReplayInterpolation => if app.config.replay_interpolation { "On" } else { "Off" }.to_string(),
+            ConfigItem::LatencySampler => if app.config.latency_sampler { "On" } else { "Off" }.to_string(),
+            ConfigItem::WaitSampler => if app.config.wait_sampler { "On" } else { "Off" }.to_string(),
+            ConfigItem::ConfirmFromLog => if app.config.confirm_from_log { "On" } else { "Off" }.to_string(),
+            ConfigItem::ServerLog => if app.config.server_log { "On" } else { "Off" }.to_string(),
+            ConfigItem::IdleTxnWatchdog => match app.config.idle_txn_watchdog_secs {
+                0 => "Off".to_string(),
+                secs if secs >= 3600 => format!("{}h", secs / 3600),
+                secs if secs >= 60 => format!("{}m", secs / 60),
+                secs => format!("{secs}s"),
+            },
+            ConfigItem::WalDirWarn => match app.config.wal_dir_warn_mb {
+                0 => "max_wal_size".to_string(),
+                mb => format!("{} GB", mb / 1024),
+            },
+            ConfigItem::LongQueryNotify => app.config.notifications.long_query.label().to_string(),
+            ConfigItem::BlockingNotify => app.config.notifications.blocking.label().to_string(),
             ConfigItem::RecordingsDir => {
                 if is_editing_this {
                     format!("{}█", app.config_overlay.input_buffer)
@@ -119,6 +153,32 @@ pub fn render_config(frame: &mut Frame, app: &App, area: Rect) {
         }
     }
 
+    // Scroll the selected line into view; for rules that's the key hints
+    // under them, so the whole section shows while editing
+    let rule_selected = app.config_overlay.selected >= ConfigItem::ALL.len();
+    let mut selected_line = lines.len() - ConfigItem::ALL.len() + app.config_overlay.selected;
+    lines.push(Line::from(""));
+    lines.push(section_header("Row Colors"));
+    if app.config.row_colors.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    No rules  [a] add one",
+            Style::default().fg(Theme::fg_dim()),
+        )));
+    }
+    for (i, rule) in app.config.row_colors.iter().enumerate() {
+        let selected = ConfigItem::ALL.len() + i == app.config_overlay.selected;
+        lines.push(rule_line(rule, selected));
+    }
+    if rule_selected {
+        selected_line = lines.len();
+        lines.push(Line::from(Span::styled(
+            "    [←→] threshold  [Enter] column  [Space] > or <  [c] color  [x] delete",
+            Style::default().fg(Theme::fg_dim()),
+        )));
+    } else if !app.config.row_colors.is_empty() {
+        lines.push(Line::from(Span::styled("    [a] add a rule", Style::default().fg(Theme::fg_dim()))));
+    }
+
     // About section
     let label_style = Style::default().fg(Theme::fg_dim());
     let value_style = Style::default().fg(Theme::fg());
@@ -148,6 +208,27 @@ pub fn render_config(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled("github.com/dlt/pg_glimpse/issues", link_style),
     ]));
 
-    let paragraph = Paragraph::new(lines).block(block);
+    let visible = usize::from(popup.height.saturating_sub(2));
+    let scroll = selected_line.saturating_sub(visible.saturating_sub(1));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
     frame.render_widget(paragraph, popup);
 }
+
+fn rule_line(rule: &RowColorRule, selected: bool) -> Line<'static> {
+    let indicator = if selected { "▸ " } else { "  " };
+    let label_style = if selected {
+        Style::default()
+            .fg(Theme::border_active())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Theme::fg())
+    };
+    let color = Theme::row_color(rule.color);
+    Line::from(vec![
+        Span::styled(format!("  {indicator}{:<40}", rule.condition()), label_style),
+        Span::styled("■ ", Style::default().fg(color)),
+        Span::styled(rule.color.label(), Style::default().fg(color)),
+    ])
+}
@@ -4,6 +4,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
 
+use crate::maintenance::Maintenance;
 use crate::ui::theme::Theme;
 
 use super::{centered_rect, overlay_block, separator_line};
@@ -266,3 +267,78 @@ pub fn render_confirm_reset_statements(frame: &mut Frame, area: Rect) {
     ];
     render_dialog(frame, area, 55, 30, " Reset Statistics ", color, lines);
 }
+
+pub fn render_confirm_drop_slot(frame: &mut Frame, slot: &str, area: Rect) {
+    let color = Theme::border_danger();
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Drop replication slot ", Style::default().fg(Theme::fg())),
+            Span::styled(slot.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled("?", Style::default().fg(Theme::fg())),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  The WAL it retains will be released.",
+            Style::default().fg(Theme::fg_dim()),
+        )),
+        Line::from(Span::styled(
+            "  Its consumer will have to be re-synced from scratch.",
+            Style::default().fg(Theme::fg_dim()),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  ⚠ This action cannot be undone.",
+            Style::default().fg(color),
+        )),
+        Line::from(""),
+        separator_line(),
+        confirm_abort_buttons(color),
+    ];
+    render_dialog(frame, area, 55, 30, " Drop Slot ", color, lines);
+}
+
+pub fn render_confirm_maintenance(frame: &mut Frame, maintenance: &Maintenance, server_major: u32, area: Rect) {
+    let color = Theme::border_warn();
+    let (what, note) = match maintenance {
+        Maintenance::VacuumAnalyze { .. } => ("Vacuum and analyze", "Reads the whole table; expect extra I/O while it runs."),
+        Maintenance::Analyze { .. } => ("Analyze", "Samples the table to refresh planner statistics."),
+        Maintenance::Reindex { .. } if server_major < 12 => (
+            "Reindex",
+            "⚠ Before PostgreSQL 12 this blocks writes to the table.",
+        ),
+        Maintenance::Reindex { .. } => ("Reindex", "Builds a new copy of the index, then swaps it in."),
+        Maintenance::Rebuild { .. } => ("Rebuild", ""),
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("  {what} "), Style::default().fg(Theme::fg())),
+            Span::styled(
+                maintenance.relation(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("?", Style::default().fg(Theme::fg())),
+        ]),
+        Line::from(""),
+    ];
+    if let Some(sql) = maintenance.sql(server_major) {
+        lines.extend(
+            sql.lines()
+                .filter(|l| !l.starts_with("--"))
+                .map(|l| Line::from(Span::styled(format!("  {l}"), Style::default().fg(Theme::fg_dim())))),
+        );
+        lines.push(Line::from(""));
+    }
+    lines.extend([
+        Line::from(Span::styled(format!("  {note}"), Style::default().fg(Theme::fg_dim()))),
+        Line::from(Span::styled(
+            "  It runs on a connection of its own; the header shows its progress.",
+            Style::default().fg(Theme::fg_dim()),
+        )),
+        Line::from(""),
+        separator_line(),
+        confirm_abort_buttons(color),
+    ]);
+    render_dialog(frame, area, 70, 40, &format!(" Run {} ", maintenance.label()), color, lines);
+}
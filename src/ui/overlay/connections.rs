@@ -0,0 +1,64 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+
+use super::{centered_rect, overlay_block, section_header};
+
+pub fn render_connections(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(80, 50, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block(
+        " Connections  [j/k] nav  [Enter] switch  [Esc] close ",
+        Theme::border_active(),
+    );
+
+    let key_style = Style::default()
+        .fg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let selected_style = Style::default()
+        .fg(Theme::overlay_bg())
+        .bg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Monitored Connections"),
+        Line::from(""),
+    ];
+
+    for (i, entry) in app.switcher.entries.iter().enumerate() {
+        let is_selected = i == app.switcher.selected;
+        let indicator = if is_selected { "  > " } else { "    " };
+        let marker = if i == app.switcher.current { "● " } else { "  " };
+        let row_style = if is_selected { selected_style } else { dim_style };
+        let status_color = if entry.status == "error" {
+            Theme::border_danger()
+        } else {
+            Theme::fg_dim()
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(indicator, key_style),
+            Span::styled(marker, Style::default().fg(Theme::border_ok())),
+            Span::styled(format!("{:<32}", entry.label), row_style),
+            Span::styled(format!("  {}", entry.status), Style::default().fg(status_color)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("    ", dim_style),
+        Span::styled("●", Style::default().fg(Theme::border_ok())),
+        Span::styled(" on screen; others refresh in the background", dim_style),
+    ]));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, popup);
+}
@@ -0,0 +1,161 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::{App, ExplainView, NodeChange, PlanDiff, PlanRun};
+use crate::db::models::PlanNode;
+use crate::ui::theme::Theme;
+use crate::ui::util::format_compact;
+
+use super::sql_highlight::highlight_sql;
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_explain(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(85, 85, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block("EXPLAIN  [j/k] scroll  [Esc] close", Theme::border_active());
+    let dim_style = Style::default().fg(Theme::fg_dim());
+
+    let mut lines = vec![Line::from("")];
+    match &app.explain {
+        None => lines.push(Line::from(Span::styled("  No plan", dim_style))),
+        Some(ExplainView::Loading(query)) => {
+            lines.push(section_header("Query"));
+            lines.extend(highlight_sql(query, "  "));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  Planning…", dim_style)));
+        }
+        Some(ExplainView::Failed { query, error }) => {
+            lines.push(section_header("Query"));
+            lines.extend(highlight_sql(query, "  "));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  Could not plan this query: {error}"),
+                Style::default()
+                    .fg(Theme::border_danger())
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        Some(ExplainView::Plan(plan)) => {
+            lines.push(section_header("Query"));
+            lines.extend(highlight_sql(&plan.query, "  "));
+            lines.push(Line::from(""));
+            let title = if plan.generic {
+                "Plan (generic, parameters unknown)"
+            } else {
+                "Plan"
+            };
+            lines.push(section_header(title));
+            lines.push(Line::from(""));
+            push_node(&mut lines, &plan.root, plan.root.total_cost, 0);
+            push_history(&mut lines, app.explain_history());
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
+
+/// One plan node and its children, coloured by the node's share of the total cost.
+fn push_node(lines: &mut Vec<Line<'static>>, node: &PlanNode, total: f64, depth: usize) {
+    let share = if total > 0.0 { node.self_cost() / total } else { 0.0 };
+    let node_style = if share > 0.5 {
+        Style::default()
+            .fg(Theme::border_danger())
+            .add_modifier(Modifier::BOLD)
+    } else if share > 0.2 {
+        Style::default().fg(Theme::border_warn())
+    } else {
+        Style::default().fg(Theme::fg())
+    };
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let indent = "  ".repeat(depth + 1);
+    let arrow = if depth == 0 { "" } else { "-> " };
+
+    lines.push(Line::from(vec![
+        Span::raw(format!("{indent}{arrow}")),
+        Span::styled(node.label(), node_style),
+        Span::styled(
+            format!(
+                "  cost={:.2}..{:.2} rows={} width={}",
+                node.startup_cost,
+                node.total_cost,
+                format_compact(node.plan_rows as i64),
+                node.plan_width
+            ),
+            dim_style,
+        ),
+    ]));
+    if let Some(condition) = &node.condition {
+        lines.push(Line::from(Span::styled(
+            format!("{indent}     {condition}"),
+            dim_style,
+        )));
+    }
+    for child in &node.children {
+        push_node(lines, child, total, depth + 1);
+    }
+}
+
+/// Earlier plans of the same statement, and how the latest differs from the
+/// one before it.
+fn push_history(lines: &mut Vec<Line<'static>>, runs: &[PlanRun]) {
+    let [.., prev, latest] = runs else {
+        return;
+    };
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    lines.push(Line::from(""));
+    lines.push(section_header("Plan history"));
+    lines.push(Line::from(""));
+    for (i, run) in runs.iter().enumerate() {
+        let flipped = i > 0 && PlanDiff::between(&runs[i - 1].plan, &run.plan).flipped();
+        let (note, style) = if flipped {
+            ("plan changed", Style::default().fg(Theme::border_warn()))
+        } else {
+            ("", dim_style)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {}  cost={:.2}  ",
+                    run.at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+                    run.plan.root.total_cost
+                ),
+                dim_style,
+            ),
+            Span::styled(note, style),
+        ]));
+    }
+
+    let diff = PlanDiff::between(&prev.plan, &latest.plan);
+    lines.push(Line::from(""));
+    lines.push(section_header(&format!(
+        "Changes since {}",
+        prev.at.with_timezone(&chrono::Local).format("%H:%M:%S")
+    )));
+    lines.push(Line::from(""));
+    if diff.nodes.is_empty() {
+        lines.push(Line::from(Span::styled("  Same plan", dim_style)));
+    }
+    for change in &diff.nodes {
+        lines.push(match change {
+            NodeChange::Removed(label) => Line::from(Span::styled(
+                format!("  - {label}"),
+                Style::default().fg(Theme::border_danger()),
+            )),
+            NodeChange::Added(label) => Line::from(Span::styled(
+                format!("  + {label}"),
+                Style::default().fg(Theme::border_ok()),
+            )),
+            NodeChange::Cost { label, from, to } => Line::from(vec![
+                Span::styled(format!("  ~ {label}"), Style::default().fg(Theme::fg())),
+                Span::styled(format!("  cost {from:.2} → {to:.2}"), dim_style),
+            ]),
+        });
+    }
+}
@@ -0,0 +1,64 @@
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::glossary::{metrics_for, MetricDoc};
+use crate::app::{App, BottomPanel};
+use crate::ui::theme::Theme;
+
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_glossary(frame: &mut Frame, app: &App, panel: BottomPanel, area: Rect) {
+    let popup = centered_rect(70, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let title = format!("{} Glossary  [j/k] scroll  [Esc] close", panel.label());
+    let block = overlay_block(&title, Theme::border_active());
+
+    let label_style = Style::default().fg(Theme::fg_dim());
+    let value_style = Style::default().fg(Theme::fg());
+    let source_style = Style::default().fg(Theme::sql_keyword());
+
+    let docs = metrics_for(panel);
+    let mut lines = vec![Line::from("")];
+
+    if docs.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    This panel lists catalog data as-is; press Enter on a row for details.",
+            label_style,
+        )));
+    }
+
+    // The column the table is sorted by comes first
+    let focus = app.glossary_focus();
+    let (focused, rest): (Vec<&MetricDoc>, Vec<&MetricDoc>) =
+        docs.iter().partition(|d| Some(d.name) == focus);
+
+    for doc in focused.into_iter().chain(rest) {
+        let mut header = section_header(doc.name);
+        if Some(doc.name) == focus {
+            header.spans.insert(1, Span::styled("(sorted) ", label_style));
+        }
+        lines.push(header);
+        lines.push(Line::from(vec![
+            Span::styled("    From:     ", label_style),
+            Span::styled(doc.source, source_style),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("    Meaning:  ", label_style),
+            Span::styled(doc.meaning, value_style),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("    Healthy:  ", label_style),
+            Span::styled(doc.healthy, value_style),
+        ]));
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
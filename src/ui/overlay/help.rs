@@ -4,16 +4,16 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, BottomPanel};
+use crate::app::{App, BottomPanel, PANELS};
 use crate::ui::theme::Theme;
 
-use super::{centered_rect, overlay_block, section_header};
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
 
 pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
     let popup = centered_rect(70, 80, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "❓ " } else { "" };
+    let emoji = if app.show_emojis() { "❓ " } else { "" };
     let title = format!("{emoji}Keybindings  [j/k] scroll  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -44,29 +44,42 @@ pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
         lines.push(entry("r", "Force refresh now"));
     }
 
-    lines.push(entry("?", "This help screen"));
+    lines.push(entry("?", "This help screen (again: metric glossary)"));
     lines.push(entry(",", "Configuration"));
+    lines.push(entry("T", "Timeout policy check"));
+    if !app.is_replay_mode() {
+        lines.push(entry("D", "Collector stats and snapshot hooks"));
+        lines.push(entry("#", "Annotate this moment (graphs + recording)"));
+    }
+    if app.switcher.is_multi() {
+        lines.push(entry("Ctrl+n", "Switch monitored connection"));
+    }
     lines.push(entry("z", "Toggle zen mode (collapse graphs)"));
+    lines.push(entry("~", "Highlight rows changed since the last refresh"));
+    lines.push(entry("|", "Split view: two panels side by side"));
+    if app.split.is_some() {
+        lines.push(entry("Ctrl+w", "Move focus to the other pane"));
+    }
+    // In Queries and Statements `e` explains instead (listed below)
+    if !app.e_explains() {
+        lines.push(entry("e", "Export graphs as SVG + PNG"));
+    }
+    lines.push(entry("Ctrl+e", "Export panel rows as CSV / JSON"));
 
-    if !app.is_replay_mode() {
+    if app.is_replay_mode() {
+        lines.push(entry("L", "Switch to another recording"));
+    } else {
         lines.push(entry("L", "Load recording (replay mode)"));
     }
 
+    lines.push(Line::from(""));
+    lines.push(section_header("Panels"));
+    lines.extend(PANELS.iter().map(|d| entry(d.key_label, d.help)));
+    if !app.is_replay_mode() {
+        lines.push(entry("g + letter", "Go to a panel (gt Tables, gi Indexes, …)"));
+    }
+
     lines.extend([
-        Line::from(""),
-        section_header("Panels"),
-        entry("Q", "Queries (active)"),
-        entry("Tab", "Blocking chains"),
-        entry("w", "Wait events"),
-        entry("t", "Table stats"),
-        entry("R", "Replication (lag, slots, subs)"),
-        entry("v", "Vacuum progress"),
-        entry("x", "Transaction wraparound"),
-        entry("I", "Index stats"),
-        entry("S", "pg_stat_statements"),
-        entry("A", "WAL & I/O stats"),
-        entry("P", "PostgreSQL settings"),
-        entry("E", "Extensions"),
         Line::from(""),
         section_header("Panel Controls"),
         entry("Esc", "Back to queries (or quit)"),
@@ -76,18 +89,71 @@ pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
         entry("PgDn / Ctrl+d", "Page down (10 items)"),
         entry("s", "Cycle sort column"),
     ]);
+    if !panel.descriptor().columns.is_empty() {
+        lines.push(entry("c", "Choose visible columns (saved)"));
+    }
 
     // Filter - only for panels that support it
     if panel.supports_filter() {
-        lines.push(entry("/", "Fuzzy filter"));
+        lines.push(entry("/", "Fuzzy filter (kept per panel)"));
+        lines.push(entry("⌫", "Clear this panel's filter"));
     }
 
     lines.push(entry("Enter", "Inspect selected row"));
+    lines.push(entry("Click / wheel", "Select row (double-click inspects)"));
+    lines.push(entry("? (inspect)", "What the panel's columns mean"));
+    lines.push(entry("/ n N (inspect)", "Search the overlay, next / previous match"));
+    if panel == BottomPanel::Queries {
+        lines.push(entry("b (inspect)", "Jump to the blocking backend"));
+    }
+    if matches!(
+        panel,
+        BottomPanel::Queries | BottomPanel::Blocking | BottomPanel::Statements | BottomPanel::Indexes
+    ) {
+        lines.push(entry("f (inspect)", "Full-screen SQL, w wraps, n numbers lines"));
+    }
 
     // Bloat refresh - only for Tables and Indexes
     if matches!(panel, BottomPanel::TableStats | BottomPanel::Indexes) {
         lines.push(entry("b", "Refresh bloat estimates"));
     }
+    if panel == BottomPanel::TableStats && !app.is_replay_mode() {
+        lines.push(entry("m (inspect)", "Measure exact bloat with pgstattuple"));
+    }
+    if panel == BottomPanel::TableStats {
+        lines.push(entry("v V (inspect)", "Copy VACUUM (VERBOSE, ANALYZE) SQL / write it to a file"));
+        lines.push(entry("a A (inspect)", "Copy ANALYZE (VERBOSE) SQL / write it to a file"));
+    }
+    if panel == BottomPanel::TableStats && app.allow_maintenance && !app.is_replay_mode() {
+        lines.push(entry("Ctrl+v Ctrl+a (inspect)", "Run VACUUM / ANALYZE on the table, after confirming"));
+    }
+    if panel == BottomPanel::Indexes {
+        lines.push(entry("r R (inspect)", "Copy REINDEX CONCURRENTLY SQL / write it to a file"));
+        lines.push(entry("c C (inspect)", "Copy a CREATE INDEX CONCURRENTLY rebuild script / write it to a file"));
+    }
+    if panel == BottomPanel::Indexes && app.allow_maintenance && !app.is_replay_mode() {
+        lines.push(entry("Ctrl+r (inspect)", "Run REINDEX CONCURRENTLY on the index, after confirming"));
+    }
+    if panel == BottomPanel::Logs {
+        lines.push(entry("f", "Cycle log levels: all, warnings and up, errors and up"));
+    }
+    if panel == BottomPanel::Settings {
+        lines.push(entry("d", "Show only settings changed from their default"));
+        lines.push(entry("o", "Review changed settings and risky values"));
+    }
+    if panel == BottomPanel::BufferCache && !app.is_replay_mode() {
+        lines.push(entry("b", "Read shared buffers (pg_buffercache)"));
+    }
+
+    // Ignore list - only for Statements and Tables
+    if matches!(panel, BottomPanel::Statements | BottomPanel::TableStats) {
+        lines.push(entry("-", "Ignore / unignore selected row"));
+        lines.push(entry("H", "Show / hide ignored rows"));
+    }
+
+    if panel == BottomPanel::Queries {
+        lines.push(entry("f", "Group queries that differ only in literals"));
+    }
 
     // Query actions - only for Queries panel in live mode
     if panel == BottomPanel::Queries && !app.is_replay_mode() {
@@ -95,9 +161,26 @@ pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
         lines.push(section_header("Query Actions"));
         lines.push(entry("C", "Cancel query (batch if filtered)"));
         lines.push(entry("K", "Terminate backend (batch if filtered)"));
+        lines.push(entry("X", "Terminate sessions past the idle txn watchdog"));
+        lines.push(entry("a", "Show all idle connections / cap them"));
+        lines.push(entry("o", "Expand / collapse the full query text"));
+        lines.push(entry("Shift+↑ / ↓", "Scroll a query too long for the panel"));
+        lines.push(entry("e", "EXPLAIN the selected query"));
         lines.push(entry("y", "Copy query to clipboard"));
     }
 
+    if panel == BottomPanel::Statements {
+        lines.push(Line::from(""));
+        lines.push(section_header("Statement Actions"));
+        lines.push(entry("*", "Star / unstar statement"));
+        lines.push(entry("n", "Edit note (saved across sessions)"));
+        lines.push(entry("b", "Baseline mean times to flag regressions"));
+        if !app.is_replay_mode() {
+            lines.push(entry("e", "EXPLAIN the selected statement"));
+            lines.push(entry("h", "Suggest indexes with hypopg"));
+        }
+    }
+
     // Replay controls - only in replay mode
     if app.is_replay_mode() {
         lines.push(Line::from(""));
@@ -107,6 +190,11 @@ pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
         lines.push(entry("→ / l", "Step forward"));
         lines.push(entry("< / >", "Decrease / increase speed"));
         lines.push(entry("g / G", "Jump to start / end"));
+        lines.push(entry(":", "Jump to a time (HH:MM[:SS]) or NN%"));
+        lines.push(entry("Click timeline", "Jump to that point"));
+        lines.push(entry("a", "Operator activity trail"));
+        lines.push(entry("[ / ]", "Bookmark start / end"));
+        lines.push(entry("E", "Export bookmark as a recording"));
     }
 
     lines.extend([
@@ -119,8 +207,6 @@ pub fn render_help(frame: &mut Frame, app: &App, area: Rect) {
         entry("g / G", "Top / bottom"),
     ]);
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    let paragraph = Paragraph::new(lines).block(block);
+    render_scrollable(frame, app, paragraph, popup);
 }
@@ -0,0 +1,96 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::{App, IndexAdviceView};
+use crate::ui::theme::Theme;
+
+use super::sql_highlight::highlight_sql;
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_index_advice(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block("Index Advice (hypopg)  [j/k] scroll  [Esc] close", Theme::border_active());
+    let dim_style = Style::default().fg(Theme::fg_dim());
+
+    let mut lines = vec![Line::from("")];
+    match &app.index_advice {
+        None => lines.push(Line::from(Span::styled("  No advice", dim_style))),
+        Some(IndexAdviceView::Loading(query)) => {
+            lines.push(section_header("Statement"));
+            lines.extend(highlight_sql(query, "  "));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  Trying hypothetical indexes…", dim_style)));
+        }
+        Some(IndexAdviceView::Failed { query, error }) => {
+            lines.push(section_header("Statement"));
+            lines.extend(highlight_sql(query, "  "));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  Could not advise on this statement: {error}"),
+                Style::default()
+                    .fg(Theme::border_danger())
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        Some(IndexAdviceView::Advice(advice)) => {
+            lines.push(section_header("Statement"));
+            lines.extend(highlight_sql(&advice.query, "  "));
+            lines.push(Line::from(""));
+            let title = if advice.generic {
+                "Estimated cost (generic plan)"
+            } else {
+                "Estimated cost"
+            };
+            lines.push(section_header(title));
+            let improvement = advice.improvement();
+            let style = if improvement >= 0.5 {
+                Style::default().fg(Theme::border_ok()).add_modifier(Modifier::BOLD)
+            } else if improvement > 0.0 {
+                Style::default().fg(Theme::border_warn())
+            } else {
+                dim_style
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:.2} → {:.2}", advice.cost_before, advice.cost_after),
+                    Style::default().fg(Theme::fg()),
+                ),
+                Span::styled(format!("  ({:.0}% cheaper)", improvement * 100.0), style),
+            ]));
+            lines.push(Line::from(""));
+            lines.push(section_header("Hypothetical indexes"));
+            if advice.suggestions.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  None to try: the plan has no filtered sequential scans",
+                    dim_style,
+                )));
+            }
+            for suggestion in &advice.suggestions {
+                let (mark, mark_style) = if suggestion.used {
+                    ("used    ", Style::default().fg(Theme::border_ok()).add_modifier(Modifier::BOLD))
+                } else {
+                    ("not used", dim_style)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {mark}  "), mark_style),
+                    Span::styled(format!("{};", suggestion.definition), Style::default().fg(Theme::fg())),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  Planner estimates only; nothing was built. Check write overhead before creating any.",
+                dim_style,
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
@@ -1,21 +1,31 @@
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::app::App;
+use crate::app::query_progress::{self, QueryProgress};
+use crate::app::{
+    App, BloatHistory, BloatSample, RoleGrantsView, TableSchemaView, WraparoundTablesView, LATENCY_WINDOW_SECS,
+};
+use crate::autovacuum::freeze_sql;
+use crate::db::models::{IndexInfo, MeasuredBloat, MonitorStatement, StatementMonitor, TableSchema, TxnState};
+use crate::history::RingBuffer;
+use crate::ui::active_queries::txn_chip;
+use crate::ui::sparkline::{low_high, render_sparkline};
 use crate::ui::theme::Theme;
-use crate::ui::util::{format_bytes, format_compact, format_duration, format_lag, format_time_ms};
+use crate::ui::util::{
+    format_byte_rate, format_bytes, format_compact, format_duration, format_lag, format_time_ms, truncate,
+};
 
-use super::sql_highlight::highlight_sql;
-use super::{centered_rect, overlay_block, section_header};
+use super::sql_highlight::{highlight_sql, highlight_sql_inline};
+use super::{centered_rect, overlay_block, render_searchable, section_header};
 
 pub fn render_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32) {
     let popup = centered_rect(70, 70, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "🔍 " } else { "" };
+    let emoji = if app.show_emojis() { "🔍 " } else { "" };
     let title = format!("{emoji}Query Details  [j/k] scroll  [y] copy query  [C] cancel  [K] kill  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -76,6 +86,7 @@ pub fn render_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32) {
                 Style::default().fg(duration_color).add_modifier(Modifier::BOLD),
             ),
         ]),
+        txn_line(q, snap.timestamp),
         Line::from(vec![
             Span::styled("  Wait:      ", Style::default().fg(Theme::fg_dim())),
             Span::styled(
@@ -91,26 +102,281 @@ pub fn render_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32) {
                 }),
             ),
         ]),
-        Line::from(""),
-        section_header("Query"),
     ];
+    let blockers = app.blockers_of(q.pid);
+    if let Some(&blocker) = blockers.first() {
+        let holder = snap.blocking_info.iter().find(|b| b.blocker_pid == blocker);
+        let mut detail = format!("PID {blocker}");
+        if let Some(user) = holder.and_then(|b| b.blocker_user.as_deref()) {
+            detail.push_str(&format!(" ({user})"));
+        }
+        if blockers.len() > 1 {
+            detail.push_str(&format!(" +{} more", blockers.len() - 1));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("  Blocked:   ", Style::default().fg(Theme::fg_dim())),
+            Span::styled(
+                detail,
+                Style::default().fg(Theme::border_danger()).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  [b] jump to blocker", Style::default().fg(Theme::fg_dim())),
+        ]));
+    }
+    if let Some(rate) = app.temp_spill.rate(q.query_id) {
+        lines.push(Line::from(vec![
+            Span::styled("  Temp:      ", Style::default().fg(Theme::fg_dim())),
+            Span::styled(
+                format!("spilling {}", format_byte_rate(rate)),
+                Style::default().fg(Theme::border_warn()).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "  (this statement's temp writes, all sessions)",
+                Style::default().fg(Theme::fg_dim()),
+            ),
+        ]));
+    }
+    if let Some(progress) = query_progress::estimate(q, snap, &app.server_info, &app.progress) {
+        lines.push(Line::from(""));
+        lines.extend(progress_lines(&progress));
+    }
+    let workers = snap.parallel_workers_of(q.pid);
+    if !workers.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(section_header(&format!("Parallel Workers ({})", workers.len())));
+        for w in workers {
+            let wait = match (&w.wait_event_type, &w.wait_event) {
+                (Some(kind), Some(event)) => format!("{kind}:{event}"),
+                _ => "running".into(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  PID {:<9}", w.pid), Style::default().fg(Theme::fg())),
+                Span::styled(
+                    format!("{:<10}", w.state.as_deref().unwrap_or("-")),
+                    Style::default().fg(Theme::state_color(w.state.as_deref())),
+                ),
+                Span::styled(wait, Style::default().fg(if w.wait_event.is_some() {
+                    Color::Yellow
+                } else {
+                    Theme::fg_dim()
+                })),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(section_header("Query"));
     lines.extend(highlight_sql(
         q.query.as_deref().unwrap_or("<no query>"),
         "  ",
     ));
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
+}
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// `[████░░░░]` filled to `fraction`, capped at full.
+fn progress_bar(fraction: f64) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * PROGRESS_BAR_WIDTH as f64).round() as usize).min(PROGRESS_BAR_WIDTH);
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(PROGRESS_BAR_WIDTH - filled))
+}
+
+/// How far a running query has got: from its progress view when it has
+/// one, otherwise against its timeout and its usual run time.
+fn progress_lines(progress: &QueryProgress) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Theme::fg_dim());
+    match progress {
+        QueryProgress::Reported { operation, eta_secs } => {
+            let pct = operation.progress_pct();
+            let mut bar = vec![
+                Span::styled("  Progress:  ", dim),
+                Span::styled(
+                    progress_bar(pct.unwrap_or(0.0) / 100.0),
+                    Style::default().fg(Theme::border_active()),
+                ),
+                Span::styled(
+                    pct.map_or_else(|| " ?".to_string(), |p| format!(" {p:.1}%")),
+                    Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if let Some(eta) = eta_secs {
+                bar.push(Span::styled(format!("  ETA ~{}", format_duration(*eta)), dim));
+            }
+            vec![
+                section_header("Progress"),
+                Line::from(vec![
+                    Span::styled("  Command:   ", dim),
+                    Span::styled(format!("{} on {}", operation.command, operation.target), Style::default().fg(Theme::fg())),
+                ]),
+                Line::from(vec![
+                    Span::styled("  Phase:     ", dim),
+                    Span::styled(operation.phase.clone(), Style::default().fg(Theme::fg())),
+                ]),
+                Line::from(bar),
+            ]
+        }
+        QueryProgress::Estimated { elapsed_secs, timeout, typical } => {
+            let yardstick = |label: &'static str, fraction: f64, detail: String, color: Color| {
+                Line::from(vec![
+                    Span::styled(label, dim),
+                    Span::styled(progress_bar(fraction), Style::default().fg(color)),
+                    Span::styled(
+                        format!(" {:.0}%", fraction * 100.0),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(detail, dim),
+                ])
+            };
+            let mut lines = vec![section_header("Progress (estimated)")];
+            if let Some(t) = timeout {
+                let fraction = query_progress::fraction(*elapsed_secs, t.secs);
+                let color = if fraction >= 0.8 { Theme::border_danger() } else { Theme::border_active() };
+                lines.push(yardstick(
+                    "  Timeout:   ",
+                    fraction,
+                    format!("  of {}, {}", format_duration(t.secs), t.source),
+                    color,
+                ));
+            }
+            if let Some(t) = typical {
+                let fraction = query_progress::fraction(*elapsed_secs, t.mean_secs);
+                let color = if fraction > 2.0 {
+                    Theme::border_danger()
+                } else if fraction > 1.0 {
+                    Theme::border_warn()
+                } else {
+                    Theme::border_active()
+                };
+                lines.push(yardstick(
+                    "  Typical:   ",
+                    fraction,
+                    format!("  of {} mean", format_time_ms(t.mean_secs * 1000.0)),
+                    color,
+                ));
+            }
+            lines
+        }
+    }
+}
+
+fn txn_line(q: &crate::db::models::ActiveQuery, now: chrono::DateTime<chrono::Utc>) -> Line<'static> {
+    let mut spans = vec![Span::styled("  Txn:       ", Style::default().fg(Theme::fg_dim()))];
+    let age = q
+        .xact_start
+        .map(|start| format_duration((now - start).num_milliseconds() as f64 / 1000.0));
+    match q.txn_state() {
+        TxnState::None => spans.push(Span::styled("none (autocommit)", Style::default().fg(Theme::fg_dim()))),
+        txn => {
+            spans.extend(txn_chip(txn));
+            let detail = if txn == TxnState::Aborted {
+                " failed, needs ROLLBACK".to_string()
+            } else {
+                " open".to_string()
+            };
+            spans.push(Span::styled(detail, Style::default().fg(Theme::fg())));
+            if let Some(age) = age {
+                spans.push(Span::styled(format!("  (for {age})"), Style::default().fg(Theme::fg_dim())));
+            }
+        }
+    }
+    Line::from(spans)
+}
+
+/// Change in bloat percentage points below which it counts as stable.
+const BLOAT_STABLE_PTS: f64 = 1.0;
+
+/// The latest bloat estimate and how it moved across this session's
+/// refreshes ("12.0% → 15.0% over 3h0m"), with labels padded to `pad`.
+fn bloat_lines(samples: &[BloatSample], current: Option<f64>, pad: usize) -> Vec<Line<'static>> {
+    let label = |text: &str| Span::styled(format!("  {:<pad$}", format!("{text}:")), Style::default().fg(Theme::fg_dim()));
+    let dim = Style::default().fg(Theme::fg_dim());
+    let Some(latest) = samples.last() else {
+        let value = current.map_or_else(
+            || Span::styled("not estimated yet, [b] refreshes", dim),
+            |pct| Span::styled(format!("{pct:.1}%"), Style::default().fg(Theme::bloat_color(pct))),
+        );
+        return vec![Line::from(vec![label("Bloat"), value])];
+    };
+
+    let mut lines = vec![Line::from(vec![
+        label("Bloat"),
+        Span::styled(
+            format!("{:.1}%", latest.pct),
+            Style::default().fg(Theme::bloat_color(latest.pct)).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  {} ({})", format_bytes(latest.bytes), latest.source.label()),
+            dim,
+        ),
+    ])];
+    let trend = match BloatHistory::trend(samples) {
+        Some((first, last)) => {
+            let change = last.pct - first.pct;
+            let (word, color) = if change > BLOAT_STABLE_PTS {
+                ("growing", Theme::border_warn())
+            } else if change < -BLOAT_STABLE_PTS {
+                ("shrinking", Theme::border_ok())
+            } else {
+                ("stable", Theme::fg_dim())
+            };
+            let secs = (last.at - first.at).num_milliseconds() as f64 / 1000.0;
+            vec![
+                Span::styled(
+                    format!("{:.1}% → {:.1}% over {}  ", first.pct, last.pct, format_duration(secs)),
+                    Style::default().fg(Theme::fg()),
+                ),
+                Span::styled(word, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            ]
+        }
+        None => vec![Span::styled("one estimate so far, [b] again later to compare", dim)],
+    };
+    lines.push(Line::from([vec![label("Trend")], trend].concat()));
+    lines
+}
+
+/// Where the space in a table measured with `pgstattuple()` goes, and how
+/// full the leaf pages of its btree indexes are.
+fn measured_bloat_lines(measured: &MeasuredBloat) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Theme::fg_dim());
+    let fg = Style::default().fg(Theme::fg());
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Measured (pgstattuple)"),
+        Line::from(vec![
+            Span::styled("  Live tuples:   ", dim),
+            Span::styled(format!("{:<10}", format!("{:.1}%", measured.tuple_percent)), fg),
+            Span::styled("Dead: ", dim),
+            Span::styled(
+                format!("{:<10}", format!("{:.1}%", measured.dead_tuple_percent)),
+                Style::default().fg(Theme::bloat_color(measured.dead_tuple_percent)),
+            ),
+            Span::styled("Free: ", dim),
+            Span::styled(
+                format!("{:.1}% ({})", measured.free_percent, format_bytes(measured.free_space)),
+                Style::default().fg(Theme::bloat_color(measured.free_percent)),
+            ),
+        ]),
+    ];
+    for index in &measured.indexes {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<30}", index.index_name), fg),
+            Span::styled(format!(" {:<10}", format_bytes(index.index_size)), dim),
+            Span::styled("density ", dim),
+            Span::styled(
+                format!("{:<8}", format!("{:.1}%", index.avg_leaf_density)),
+                Style::default().fg(Theme::bloat_color(index.bloat_pct())),
+            ),
+            Span::styled("fragmented ", dim),
+            Span::styled(format!("{:.1}%", index.leaf_fragmentation), fg),
+        ]));
+    }
+    lines
 }
 
 pub fn render_index_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str) {
     let popup = centered_rect(75, 60, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "📑 " } else { "" };
-    let title = format!("{emoji}Index Details  [j/k] scroll  [y] copy definition  [Esc] close");
+    let emoji = if app.show_emojis() { "📑 " } else { "" };
+    let title = format!("{emoji}Index Details  [y] copy  [r] reindex  [c] rebuild  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
     let Some(snap) = &app.snapshot else {
@@ -155,6 +421,9 @@ pub fn render_index_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str)
                 Style::default().fg(Theme::fg()),
             ),
         ]),
+    ];
+    lines.extend(bloat_lines(app.bloat_history.for_index(key), idx.bloat_pct, 13));
+    lines.extend([
         Line::from(""),
         section_header("Usage Stats"),
         Line::from(vec![
@@ -179,21 +448,21 @@ pub fn render_index_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str)
         ]),
         Line::from(""),
         section_header("Definition"),
-    ];
+    ]);
     lines.extend(highlight_sql(&idx.index_definition, "  "));
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
 }
 
+/// Samples shown in each replication lag sparkline
+const LAG_SPARK_WIDTH: usize = 24;
+const STATEMENT_SPARK_WIDTH: usize = 30;
+
 pub fn render_replication_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32) {
     let popup = centered_rect(70, 70, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "🔄 " } else { "" };
+    let emoji = if app.show_emojis() { "🔄 " } else { "" };
     let title = format!("{emoji}Replication Details  [j/k] scroll  [y] copy app  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -232,6 +501,31 @@ pub fn render_replication_inspect(frame: &mut Frame, app: &App, area: Rect, pid:
             .unwrap_or_else(|| "-".into())
     };
 
+    // Lag over the session: a sparkline and the worst seen, or in plain
+    // mode the best and worst
+    let history = app.metrics.replication_lag.get(&r.pid);
+    let plain = app.config.plain();
+    let lag_trend = |buf: Option<&RingBuffer<u64>>| -> Vec<Span<'static>> {
+        let Some(data) = buf.map(RingBuffer::as_vec).filter(|d| d.len() > 1) else {
+            return vec![];
+        };
+        if plain {
+            let range = low_high(&data, |ms| format_lag(Some(ms as f64 / 1000.0)));
+            return vec![Span::styled(range, Style::default().fg(Theme::fg_dim()))];
+        }
+        let peak = data.iter().copied().max().unwrap_or(0);
+        vec![
+            Span::styled(
+                render_sparkline(&data, LAG_SPARK_WIDTH),
+                Style::default().fg(Theme::graph_latency()),
+            ),
+            Span::styled(
+                format!("  peak {}", format_lag(Some(peak as f64 / 1000.0))),
+                Style::default().fg(Theme::fg_dim()),
+            ),
+        ]
+    };
+
     let state_color = match r.state.as_deref() {
         Some("streaming") => Theme::border_ok(),
         Some("catchup") => Theme::border_warn(),
@@ -317,21 +611,33 @@ pub fn render_replication_inspect(frame: &mut Frame, app: &App, area: Rect, pid:
         ]),
         Line::from(""),
         section("  Replication Lag"),
-        Line::from(vec![
-            label("  Write Lag:       "),
-            val(format_lag(r.write_lag_secs)),
-        ]),
-        Line::from(vec![
-            label("  Flush Lag:       "),
-            val(format_lag(r.flush_lag_secs)),
-        ]),
-        Line::from(vec![
-            label("  Replay Lag:      "),
-            Span::styled(
-                format_lag(r.replay_lag_secs),
-                Style::default().fg(Theme::lag_color(r.replay_lag_secs)).add_modifier(Modifier::BOLD),
-            ),
-        ]),
+        Line::from(
+            [
+                vec![label("  Write Lag:       "), val(format!("{:<10}", format_lag(r.write_lag_secs)))],
+                lag_trend(history.map(|h| &h.write)),
+            ]
+            .concat(),
+        ),
+        Line::from(
+            [
+                vec![label("  Flush Lag:       "), val(format!("{:<10}", format_lag(r.flush_lag_secs)))],
+                lag_trend(history.map(|h| &h.flush)),
+            ]
+            .concat(),
+        ),
+        Line::from(
+            [
+                vec![
+                    label("  Replay Lag:      "),
+                    Span::styled(
+                        format!("{:<10}", format_lag(r.replay_lag_secs)),
+                        Style::default().fg(Theme::lag_color(r.replay_lag_secs)).add_modifier(Modifier::BOLD),
+                    ),
+                ],
+                lag_trend(history.map(|h| &h.replay)),
+            ]
+            .concat(),
+        ),
         Line::from(""),
         Line::from(vec![
             label("  Reply Time:      "),
@@ -339,19 +645,20 @@ pub fn render_replication_inspect(frame: &mut Frame, app: &App, area: Rect, pid:
         ]),
     ];
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
 }
 
 pub fn render_table_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str) {
     let popup = centered_rect(75, 75, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "📋 " } else { "" };
-    let title = format!("{emoji}Table Details  [j/k] scroll  [y] copy name  [Esc] close");
+    let emoji = if app.show_emojis() { "📋 " } else { "" };
+    let measure = if app.replay.is_none() && app.server_info.extensions.pgstattuple {
+        "  [m] measure bloat"
+    } else {
+        ""
+    };
+    let title = format!("{emoji}Table Details  [y] copy name  [v] vacuum SQL{measure}  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
     let Some(snap) = &app.snapshot else {
@@ -431,6 +738,12 @@ pub fn render_table_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str)
             Span::styled("Indexes: ", Style::default().fg(Theme::fg_dim())),
             Span::styled(format_bytes(tbl.indexes_size_bytes), Style::default().fg(Theme::fg())),
         ]),
+    ];
+    lines.extend(bloat_lines(app.bloat_history.for_table(key), tbl.bloat_pct, 15));
+    if let Some(measured) = app.measured_bloat.get(key) {
+        lines.extend(measured_bloat_lines(measured));
+    }
+    lines.extend([
         Line::from(""),
         section_header("Row Stats"),
         Line::from(vec![
@@ -503,44 +816,200 @@ pub fn render_table_inspect(frame: &mut Frame, app: &App, area: Rect, key: &str)
             Span::styled("AutoVac: ", Style::default().fg(Theme::fg_dim())),
             Span::styled(tbl.autovacuum_count.to_string(), Style::default().fg(Theme::fg())),
         ]),
+    ]);
+
+    match &app.table_schema {
+        Some(TableSchemaView::Loaded { key: loaded, schema }) if loaded == key => {
+            lines.extend(schema_lines(schema));
+        }
+        Some(TableSchemaView::Loading(loading)) if loading == key => {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  Reading columns, indexes and constraints…",
+                Style::default().fg(Theme::fg_dim()),
+            )));
+            lines.extend(snapshot_index_lines(&related_indexes));
+        }
+        Some(TableSchemaView::Failed { key: failed, error }) if failed == key => {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  Could not read the table definition: {error}"),
+                Style::default().fg(Theme::border_danger()),
+            )));
+            lines.extend(snapshot_index_lines(&related_indexes));
+        }
+        // Replay: only what the snapshot recorded
+        _ => lines.extend(snapshot_index_lines(&related_indexes)),
+    }
+
+    render_searchable(frame, app, lines, block, popup);
+}
+
+/// Indexes of the table as the snapshot lists them.
+fn snapshot_index_lines(indexes: &[&IndexInfo]) -> Vec<Line<'static>> {
+    if indexes.is_empty() {
+        return vec![];
+    }
+    let mut lines = vec![
+        Line::from(""),
+        section_header(&format!("Indexes ({})", indexes.len())),
     ];
+    for idx in indexes {
+        let scan_color = Theme::index_usage_color(idx.idx_scan);
+        lines.push(Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                format!("{:<30}", &idx.index_name),
+                Style::default().fg(Theme::fg()),
+            ),
+            Span::styled(
+                format!(" {} ", format_bytes(idx.index_size_bytes)),
+                Style::default().fg(Theme::fg_dim()),
+            ),
+            Span::styled(
+                format!("{} scans", idx.idx_scan),
+                Style::default().fg(scan_color),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// What follows the object's name in a `pg_get_*def` statement, e.g.
+/// "USING btree (id)" for an index or "BEFORE UPDATE ON ..." for a trigger.
+fn definition_tail<'a>(definition: &'a str, marker: &str) -> &'a str {
+    definition
+        .find(marker)
+        .map_or(definition, |at| definition[at..].trim_start())
+}
+
+/// Columns, indexes with their usage, foreign keys and triggers read from
+/// the catalogs when the overlay opened.
+fn schema_lines(schema: &TableSchema) -> Vec<Line<'static>> {
+    let fg = Style::default().fg(Theme::fg());
+    let dim = Style::default().fg(Theme::fg_dim());
+    let warn = Style::default().fg(Theme::border_warn());
+    let danger = Style::default()
+        .fg(Theme::border_danger())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(""),
+        section_header(&format!("Columns ({})", schema.columns.len())),
+    ];
+    let name_width = schema
+        .columns
+        .iter()
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(8, 30);
+    let type_width = schema
+        .columns
+        .iter()
+        .map(|c| c.data_type.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(24);
+    for col in &schema.columns {
+        let mut spans = vec![
+            Span::styled(format!("  {:<name_width$}  ", col.name), fg),
+            Span::styled(
+                format!("{:<type_width$}", col.data_type),
+                Style::default().fg(Theme::border_active()),
+            ),
+            Span::styled(if col.not_null { "  not null" } else { "          " }, dim),
+        ];
+        if let Some(default) = &col.default {
+            spans.push(Span::styled(format!("  default {default}"), dim));
+        }
+        lines.push(Line::from(spans));
+    }
 
-    // Add indexes section if any
-    if !related_indexes.is_empty() {
+    lines.push(Line::from(""));
+    lines.push(section_header(&format!("Indexes ({})", schema.indexes.len())));
+    if schema.indexes.is_empty() {
+        lines.push(Line::from(Span::styled("  None", dim)));
+    }
+    for idx in &schema.indexes {
+        let mut spans = vec![
+            Span::styled(format!("  {:<30}", idx.name), fg),
+            Span::styled(format!(" {:<10}", format_bytes(idx.size_bytes)), dim),
+            Span::styled(
+                format!("{} scans", format_compact(idx.idx_scan)),
+                Style::default().fg(Theme::index_usage_color(idx.idx_scan)),
+            ),
+        ];
+        if idx.is_primary {
+            spans.push(Span::styled("  primary key", warn));
+        } else if idx.is_unique {
+            spans.push(Span::styled("  unique", warn));
+        }
+        if !idx.is_valid {
+            spans.push(Span::styled("  INVALID", danger));
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    {} · {} read, {} fetched",
+                definition_tail(&idx.definition, "USING "),
+                format_compact(idx.idx_tup_read),
+                format_compact(idx.idx_tup_fetch)
+            ),
+            dim,
+        )));
+    }
+
+    let (incoming, outgoing): (Vec<_>, Vec<_>) =
+        schema.foreign_keys.iter().partition(|fk| fk.incoming);
+    if !outgoing.is_empty() {
         lines.push(Line::from(""));
-        lines.push(section_header(&format!("Indexes ({})", related_indexes.len())));
-        for idx in &related_indexes {
-            let scan_color = Theme::index_usage_color(idx.idx_scan);
+        lines.push(section_header(&format!("Foreign Keys ({})", outgoing.len())));
+        for fk in outgoing {
             lines.push(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    format!("{:<30}", &idx.index_name),
-                    Style::default().fg(Theme::fg()),
-                ),
-                Span::styled(
-                    format!(" {} ", format_bytes(idx.index_size_bytes)),
-                    Style::default().fg(Theme::fg_dim()),
-                ),
-                Span::styled(
-                    format!("{} scans", idx.idx_scan),
-                    Style::default().fg(scan_color),
-                ),
+                Span::styled(format!("  {}  ", fk.name), fg),
+                Span::styled(fk.definition.clone(), dim),
+            ]));
+        }
+    }
+    if !incoming.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(section_header(&format!("Referenced By ({})", incoming.len())));
+        for fk in incoming {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}", fk.other_table), fg),
+                Span::styled(format!(" via {}  ", fk.name), dim),
+                Span::styled(fk.definition.clone(), dim),
             ]));
         }
     }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    if !schema.triggers.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(section_header(&format!("Triggers ({})", schema.triggers.len())));
+        for trigger in &schema.triggers {
+            let tail = trigger
+                .definition
+                .split_once(&format!("{} ", trigger.name))
+                .map_or(trigger.definition.as_str(), |(_, rest)| rest);
+            let mut spans = vec![
+                Span::styled(format!("  {}  ", trigger.name), fg),
+                Span::styled(tail.to_string(), dim),
+            ];
+            if !trigger.enabled {
+                spans.push(Span::styled("  disabled", warn));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
 }
 
 pub fn render_blocking_inspect(frame: &mut Frame, app: &App, area: Rect, blocked_pid: i32) {
     let popup = centered_rect(80, 70, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "🔒 " } else { "" };
+    let emoji = if app.show_emojis() { "🔒 " } else { "" };
     let title = format!("{emoji}Lock Details  [j/k] scroll  [y] copy query  [Esc] close");
     let block = overlay_block(&title, Theme::border_danger());
 
@@ -617,18 +1086,14 @@ pub fn render_blocking_inspect(frame: &mut Frame, app: &App, area: Rect, blocked
         "  ",
     ));
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
 }
 
 pub fn render_vacuum_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32) {
     let popup = centered_rect(70, 60, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "🧹 " } else { "" };
+    let emoji = if app.show_emojis() { "🧹 " } else { "" };
     let title = format!("{emoji}Vacuum Progress  [j/k] scroll  [y] copy table  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -728,19 +1193,16 @@ pub fn render_vacuum_inspect(frame: &mut Frame, app: &App, area: Rect, pid: i32)
         ]),
     ];
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
 }
 
 pub fn render_wraparound_inspect(frame: &mut Frame, app: &App, area: Rect, datname: &str) {
     let popup = centered_rect(70, 65, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "⚠️ " } else { "" };
-    let title = format!("{emoji}XID Details  [j/k] scroll  [y] copy db  [Esc] close");
+    let emoji = if app.show_emojis() { "⚠️ " } else { "" };
+    let copies = if app.wraparound_freeze_sql(datname).is_some() { "freeze SQL" } else { "db" };
+    let title = format!("{emoji}XID Details  [j/k] scroll  [y] copy {copies}  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
     let Some(snap) = &app.snapshot else {
@@ -772,7 +1234,7 @@ pub fn render_wraparound_inspect(frame: &mut Frame, app: &App, area: Rect, datna
     let empty = bar_width - filled;
     let progress_bar = format!("[{}{}]", "█".repeat(filled), "░".repeat(empty));
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(""),
         section_header("Database"),
         Line::from(vec![
@@ -845,18 +1307,166 @@ pub fn render_wraparound_inspect(frame: &mut Frame, app: &App, area: Rect, datna
         },
     ];
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    let dim = Style::default().fg(Theme::fg_dim());
+    lines.push(Line::from(""));
+    match &app.wraparound_tables {
+        Some(WraparoundTablesView::Loaded { datname: loaded, tables }) if loaded == datname => {
+            let settings = app.autovacuum_settings();
+            lines.push(section_header(&format!("Oldest Tables ({})", tables.len())));
+            if tables.is_empty() {
+                lines.push(Line::from(Span::styled("  No tables to freeze", dim)));
+            }
+            for table in tables {
+                let age_color = if i64::from(table.xid_age) > settings.freeze_max_age {
+                    Theme::border_danger()
+                } else if i64::from(table.xid_age) > settings.freeze_max_age / 2 {
+                    Theme::border_warn()
+                } else {
+                    Theme::fg()
+                };
+                let mut spans = vec![
+                    Span::styled(format!("  {:<36} ", truncate(&table.key(), 36)), Style::default().fg(Theme::fg())),
+                    Span::styled(
+                        format!("{:>7}", format_compact(i64::from(table.xid_age))),
+                        Style::default().fg(age_color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  {:>9}", format_bytes(table.size_bytes)), dim),
+                ];
+                if table.via_toast {
+                    spans.push(Span::styled("  (TOAST)", dim));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            let candidates = settings.freeze_candidates(tables);
+            if !candidates.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(section_header("Suggested VACUUM FREEZE"));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  Past half of autovacuum_freeze_max_age ({}), oldest first:",
+                        format_compact(settings.freeze_max_age)
+                    ),
+                    dim,
+                )));
+                for table in candidates {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", freeze_sql(table)),
+                        Style::default().fg(Theme::border_active()),
+                    )));
+                }
+            }
+        }
+        Some(WraparoundTablesView::Loading(loading)) if loading == datname => {
+            lines.push(section_header("Oldest Tables"));
+            lines.push(Line::from(Span::styled("  Reading tables…", dim)));
+        }
+        Some(WraparoundTablesView::Failed { datname: failed, error }) if failed == datname => {
+            lines.push(section_header("Oldest Tables"));
+            lines.push(Line::from(Span::styled(
+                format!("  Could not read tables: {error}"),
+                Style::default().fg(Theme::border_danger()),
+            )));
+        }
+        _ if app.replay.is_some() => {
+            lines.push(section_header("Oldest Tables"));
+            lines.push(Line::from(Span::styled("  Tables are read live only", dim)));
+        }
+        // relfrozenxid is per database
+        _ if datname != app.connection.dbname => {
+            lines.push(section_header("Oldest Tables"));
+            lines.push(Line::from(Span::styled(
+                format!("  Connect to {datname} to read its tables"),
+                dim,
+            )));
+        }
+        _ => {
+            lines.push(section_header("Oldest Tables"));
+            lines.push(Line::from(Span::styled("  Tables not read yet", dim)));
+        }
+    }
+
+    render_searchable(frame, app, lines, block, popup);
+}
+
+/// Percentiles, errors, the latency histogram and per-bucket history of a
+/// statement tracked by `pg_stat_monitor`. In plain mode the history is its
+/// low and high rather than a sparkline.
+fn monitor_lines(monitor: &StatementMonitor, ms: &MonitorStatement, plain: bool) -> Vec<Line<'static>> {
+    let label = |s: String| Span::styled(s, Style::default().fg(Theme::fg_dim()));
+    let val = |s: String| Span::styled(s, Style::default().fg(Theme::fg()));
+    let val_bold =
+        |s: String| Span::styled(s, Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD));
+    let pct = |p: f64| monitor.percentile(ms, p).map_or_else(|| "-".to_string(), format_time_ms);
+    let errors_color = if ms.errors > 0 {
+        Theme::border_danger()
+    } else {
+        Theme::fg()
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            label("  p50:             ".into()),
+            val(format!("{:<10}", pct(50.0))),
+            label("p95:           ".into()),
+            val_bold(format!("{:<10}", pct(95.0))),
+            label("p99:           ".into()),
+            val_bold(pct(99.0)),
+        ]),
+        Line::from(vec![
+            label("  Errors:          ".into()),
+            Span::styled(ms.errors.to_string(), Style::default().fg(errors_color)),
+        ]),
+    ];
+
+    let most = ms.resp_calls.iter().copied().max().unwrap_or(0);
+    if most > 0 {
+        lines.push(Line::from(label("  Histogram:".into())));
+        for (&calls, &(lower, upper)) in ms.resp_calls.iter().zip(&monitor.ranges) {
+            if calls == 0 {
+                continue;
+            }
+            let range = upper.map_or_else(
+                || format!("≥ {}", format_time_ms(lower)),
+                |upper| format!("{} – {}", format_time_ms(lower), format_time_ms(upper)),
+            );
+            let bar = "█".repeat(((calls * 30 + most - 1) / most) as usize);
+            lines.push(Line::from(vec![
+                label(format!("    {range:<20}")),
+                Span::styled(format!("{bar} "), Style::default().fg(Theme::border_active())),
+                val(calls.to_string()),
+            ]));
+        }
+    }
+
+    if !ms.history.is_empty() {
+        let means: Vec<u64> = ms.history.iter().map(|b| (b.mean_exec_time() * 1000.0) as u64).collect();
+        let trend = if plain {
+            val(low_high(&means, |us| format_time_ms(us as f64 / 1000.0)))
+        } else {
+            Span::styled(render_sparkline(&means, means.len().min(40)), Style::default().fg(Theme::graph_latency()))
+        };
+        lines.push(Line::from(vec![
+            label(format!("  Buckets ({}):     ", ms.history.len())),
+            trend,
+            label("  mean per bucket".into()),
+        ]));
+        // The latest few, newest last
+        for bucket in ms.history.iter().skip(ms.history.len().saturating_sub(5)) {
+            lines.push(Line::from(vec![
+                label(format!("    {}  ", bucket.start.format("%H:%M:%S"))),
+                val(format!("{:>8} calls  ", format_compact(bucket.calls))),
+                val(format!("{} mean", format_time_ms(bucket.mean_exec_time()))),
+            ]));
+        }
+    }
+    lines
 }
 
 pub fn render_statement_inspect(frame: &mut Frame, app: &App, area: Rect, queryid: i64) {
     let popup = centered_rect(80, 80, area);
     frame.render_widget(Clear, popup);
 
-    let emoji = if app.config.show_emojis { "📝 " } else { "" };
+    let emoji = if app.show_emojis() { "📝 " } else { "" };
     let title = format!("{emoji}Statement Details  [j/k] scroll  [y] copy query  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -876,6 +1486,7 @@ pub fn render_statement_inspect(frame: &mut Frame, app: &App, area: Rect, queryi
     let hit_color = Theme::hit_ratio_color(stmt.hit_ratio);
 
     let label = |s: &'static str| Span::styled(s, Style::default().fg(Theme::fg_dim()));
+    let label_owned = |s: String| Span::styled(s, Style::default().fg(Theme::fg_dim()));
     let val = |s: String| Span::styled(s, Style::default().fg(Theme::fg()));
     let val_bold =
         |s: String| Span::styled(s, Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD));
@@ -906,14 +1517,48 @@ pub fn render_statement_inspect(frame: &mut Frame, app: &App, area: Rect, queryi
         Theme::fg()
     };
 
-    let mut lines = vec![
-        Line::from(vec![
-            label("  Query ID:        "),
-            val(stmt.queryid.to_string()),
-        ]),
-        Line::from(""),
-        section("  Query"),
-    ];
+    let mut lines = vec![Line::from(vec![
+        label("  Query ID:        "),
+        val(stmt.queryid.to_string()),
+    ])];
+    if app.starred.is_starred(queryid) {
+        let star_style = Style::default().fg(Theme::border_warn());
+        lines.push(Line::from(vec![
+            label("  Starred:         "),
+            Span::styled(
+                app.starred.note(queryid).map_or_else(
+                    || "★ (no note, press n in the panel to add one)".to_string(),
+                    |note| format!("★ {note}"),
+                ),
+                star_style,
+            ),
+        ]));
+    }
+    if let Some(baseline) = app.baselines.get(queryid) {
+        let ratio = if baseline.mean_exec_time > 0.0 {
+            format!(", now {:.1}×", stmt.mean_exec_time / baseline.mean_exec_time)
+        } else {
+            String::new()
+        };
+        let text = format!(
+            "{} mean on {}{ratio}",
+            format_time_ms(baseline.mean_exec_time),
+            baseline.taken_at.format("%Y-%m-%d %H:%M")
+        );
+        lines.push(Line::from(if app.statement_regression(stmt).is_some() {
+            vec![
+                label("  Baseline:        "),
+                Span::styled(text, Style::default().fg(Theme::border_danger())),
+                Span::styled(
+                    "  regressed",
+                    Style::default().fg(Theme::border_danger()).add_modifier(Modifier::BOLD),
+                ),
+            ]
+        } else {
+            vec![label("  Baseline:        "), val(text)]
+        }));
+    }
+    lines.extend([Line::from(""), section("  Query")]);
     lines.extend(highlight_sql(&stmt.query, "  "));
     lines.extend(vec![
         Line::from(""),
@@ -942,6 +1587,103 @@ pub fn render_statement_inspect(frame: &mut Frame, app: &App, area: Rect, queryi
             label("Stddev:        "),
             val(format_time_ms(stmt.stddev_exec_time)),
         ]),
+    ]);
+
+    // Per-interval deltas since the overlay's statement was first seen
+    if let Some(timing) = app.metrics.statement_timing.get(&queryid).filter(|t| t.calls.as_vec().len() > 1) {
+        let us = |v: u64| format_time_ms(v as f64 / 1000.0);
+        let plain = app.config.plain();
+        let trend = |name: &'static str, data: Vec<u64>, format: &dyn Fn(u64) -> String, color, summary: String| {
+            let trend = if plain {
+                val(low_high(&data, format))
+            } else {
+                Span::styled(render_sparkline(&data, STATEMENT_SPARK_WIDTH), Style::default().fg(color))
+            };
+            Line::from(vec![label(name), trend, label_owned(format!("  {summary}"))])
+        };
+        let mean = timing.mean_us();
+        let first_mean = mean.iter().copied().find(|&m| m > 0).unwrap_or(0);
+        let last_mean = mean.last().copied().unwrap_or(0);
+        let mean_color = if first_mean > 0 && last_mean >= first_mean * 2 {
+            Theme::border_danger()
+        } else {
+            Theme::graph_latency()
+        };
+        lines.extend([
+            Line::from(""),
+            section("  Per Interval (this session)"),
+            trend(
+                "  Calls:           ",
+                timing.calls.as_vec(),
+                &|n| n.to_string(),
+                Theme::graph_connections(),
+                format!("last {}  peak {}", timing.calls.last().unwrap_or(0), timing.calls.peak()),
+            ),
+            trend(
+                "  Exec Time:       ",
+                timing.exec_us.as_vec(),
+                &us,
+                Theme::graph_latency(),
+                format!("last {}", us(timing.exec_us.last().unwrap_or(0))),
+            ),
+            trend(
+                "  Mean Time:       ",
+                mean,
+                &us,
+                mean_color,
+                format!("first {}  last {}", us(first_mean), us(last_mean)),
+            ),
+            trend(
+                "  Blk Read Time:   ",
+                timing.blk_read_us.as_vec(),
+                &us,
+                Theme::border_warn(),
+                format!("last {}  peak {}", us(timing.blk_read_us.last().unwrap_or(0)), us(timing.blk_read_us.peak())),
+            ),
+        ]);
+    }
+
+    // Percentiles need the sampler; only starred statements are sampled
+    if app.starred.is_starred(queryid) {
+        lines.extend([Line::from(""), section("  Latency (sampled)")]);
+        match app.latency.estimate(queryid) {
+            Some(est) => lines.extend([
+                Line::from(vec![
+                    label("  p50:             "),
+                    val(format!("{:<10}", format_time_ms(est.p50))),
+                    label("p95:           "),
+                    val_bold(format!("{:<10}", format_time_ms(est.p95))),
+                    label("p99:           "),
+                    val_bold(format_time_ms(est.p99)),
+                ]),
+                Line::from(label_owned(format!(
+                    "  Estimated from {} calls in {} intervals over the last {}m",
+                    est.calls,
+                    est.intervals,
+                    LATENCY_WINDOW_SECS / 60
+                ))),
+            ]),
+            None => lines.push(Line::from(label(
+                if !app.config.latency_sampler {
+                    "  Turn on Latency Sampler in the config overlay (,) to estimate percentiles"
+                } else if app.latency.is_sampling(queryid) {
+                    "  No calls since sampling started"
+                } else {
+                    "  Waiting for the first samples..."
+                },
+            ))),
+        }
+    }
+
+    if let Some(monitor) = &snap.statement_monitor {
+        lines.extend([Line::from(""), section("  Latency (pg_stat_monitor)")]);
+        match monitor.get(queryid) {
+            Some(ms) => lines.extend(monitor_lines(monitor, ms, app.config.plain())),
+            None => lines.push(Line::from(label("  Not in pg_stat_monitor's current buckets"))),
+        }
+    }
+
+    lines.extend(vec![
         Line::from(""),
         section("  Shared Buffers"),
         Line::from(vec![
@@ -1007,18 +1749,14 @@ pub fn render_statement_inspect(frame: &mut Frame, app: &App, area: Rect, queryi
         ]),
     ]);
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
-    frame.render_widget(paragraph, popup);
+    render_searchable(frame, app, lines, block, popup);
 }
 
 pub fn render_settings_inspect(frame: &mut Frame, app: &App, area: Rect, name: &str) {
     let popup_area = centered_rect(60, 70, area);
     frame.render_widget(Clear, popup_area);
 
-    let emoji = if app.config.show_emojis { "⚙️ " } else { "" };
+    let emoji = if app.show_emojis() { "⚙️ " } else { "" };
     let title = format!("{emoji}Setting Details");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -1049,6 +1787,19 @@ pub fn render_settings_inspect(frame: &mut Frame, app: &App, area: Rect, name: &
             Span::styled(unit, Style::default().fg(Theme::fg())),
         ]));
     }
+    if let Some(boot_val) = &s.boot_val {
+        lines.push(Line::from(vec![
+            Span::styled("  Default:     ", Style::default().fg(Theme::fg_dim())),
+            Span::styled(boot_val, Style::default().fg(Theme::fg())),
+        ]));
+    }
+    // Differs when the session changed it with SET
+    if let Some(reset_val) = s.reset_val.as_ref().filter(|r| **r != s.setting) {
+        lines.push(Line::from(vec![
+            Span::styled("  On RESET:    ", Style::default().fg(Theme::fg_dim())),
+            Span::styled(reset_val, Style::default().fg(Theme::fg())),
+        ]));
+    }
     lines.push(Line::from(""));
 
     // Category section
@@ -1096,12 +1847,25 @@ pub fn render_settings_inspect(frame: &mut Frame, app: &App, area: Rect, name: &
         ]));
     }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
+    // Overrides section: sessions matching these see a different value
+    let overrides: Vec<_> = app
+        .server_info
+        .role_settings
+        .iter()
+        .filter(|o| o.name == s.name)
+        .collect();
+    if !overrides.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(section_header("Per-role / per-database overrides"));
+        for o in overrides {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<13}", o.value), Style::default().fg(Theme::border_warn())),
+                Span::styled(o.scope(), Style::default().fg(Theme::fg())),
+            ]));
+        }
+    }
 
-    frame.render_widget(paragraph, popup_area);
+    render_searchable(frame, app, lines, block, popup_area);
 }
 
 fn settings_context_color(context: &str) -> Color {
@@ -1130,7 +1894,7 @@ pub fn render_extensions_inspect(frame: &mut Frame, app: &App, area: Rect, name:
     let popup_area = centered_rect(60, 55, area);
     frame.render_widget(Clear, popup_area);
 
-    let emoji = if app.config.show_emojis { "🧩 " } else { "" };
+    let emoji = if app.show_emojis() { "🧩 " } else { "" };
     let title = format!("{emoji}Extension Details  [j/k] scroll  [y] copy name  [Esc] close");
     let block = overlay_block(&title, Theme::border_active());
 
@@ -1213,10 +1977,317 @@ pub fn render_extensions_inspect(frame: &mut Frame, app: &App, area: Rect, name:
         )));
     }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.overlay_scroll, 0));
+    render_searchable(frame, app, lines, block, popup_area);
+}
+
+pub fn render_role_inspect(frame: &mut Frame, app: &App, area: Rect, name: &str) {
+    let popup = centered_rect(70, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let emoji = if app.show_emojis() { "👤 " } else { "" };
+    let title = format!("{emoji}Role Details  [j/k] scroll  [y] copy name  [Esc] close");
+    let block = overlay_block(&title, Theme::border_active());
+
+    let Some(role) = app.server_info.roles.iter().find(|r| r.name == name) else {
+        frame.render_widget(Paragraph::new("Role not found").block(block), popup);
+        return;
+    };
+
+    let dim = Style::default().fg(Theme::fg_dim());
+    let attributes = role.attributes();
+    let conn_limit = if role.conn_limit < 0 {
+        "no limit".to_string()
+    } else {
+        role.conn_limit.to_string()
+    };
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Role"),
+        Line::from(vec![
+            Span::styled("  Name:        ", dim),
+            Span::styled(
+                &role.name,
+                Style::default().fg(Theme::border_active()).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Attributes:  ", dim),
+            Span::styled(
+                if attributes.is_empty() {
+                    "none".to_string()
+                } else {
+                    attributes.join(" ")
+                },
+                Style::default().fg(if role.superuser { Theme::border_warn() } else { Theme::fg() }),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Conn Limit:  ", dim),
+            Span::styled(conn_limit, Style::default().fg(Theme::fg())),
+        ]),
+        Line::from(vec![
+            Span::styled("  Valid Until: ", dim),
+            Span::styled(
+                role.valid_until
+                    .map_or_else(|| "forever".into(), |t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+                Style::default().fg(Theme::fg()),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Member Of:   ", dim),
+            Span::styled(
+                if role.member_of.is_empty() {
+                    "-".to_string()
+                } else {
+                    role.member_of.join(", ")
+                },
+                Style::default().fg(Theme::fg()),
+            ),
+        ]),
+    ];
+
+    // Roles granted this one, i.e. where it is the group
+    let members: Vec<&str> = app
+        .server_info
+        .roles
+        .iter()
+        .filter(|r| r.member_of.iter().any(|m| m == name))
+        .map(|r| r.name.as_str())
+        .collect();
+    if !members.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("  Members:     ", dim),
+            Span::styled(members.join(", "), Style::default().fg(Theme::fg())),
+        ]));
+    }
+
+    let connections = app.role_connections(name);
+    lines.push(Line::from(""));
+    lines.push(section_header(&format!(
+        "Connections ({})",
+        connections.iter().map(|(_, n)| n).sum::<i64>()
+    )));
+    if connections.is_empty() {
+        lines.push(Line::from(Span::styled("  None", dim)));
+    }
+    for (db, n) in &connections {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {db:<24}"), Style::default().fg(Theme::fg())),
+            Span::styled(n.to_string(), Style::default().fg(Theme::border_active())),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    match &app.role_grants {
+        Some(RoleGrantsView::Loaded { role: loaded, grants }) if loaded == name => {
+            lines.push(section_header(&format!("Privileges ({})", grants.len())));
+            if grants.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  No privileges on databases, schemas or relations",
+                    dim,
+                )));
+            }
+            let mut kind = "";
+            for grant in grants {
+                if grant.kind != kind {
+                    kind = &grant.kind;
+                    lines.push(Line::from(Span::styled(
+                        format!("  {kind}"),
+                        Style::default().fg(Theme::fg_dim()).add_modifier(Modifier::BOLD),
+                    )));
+                }
+                let mut spans = vec![
+                    Span::styled(format!("    {:<28} ", grant.object), Style::default().fg(Theme::fg())),
+                    Span::styled(grant.privileges.join(", "), Style::default().fg(Theme::border_active())),
+                ];
+                if grant.grantable {
+                    spans.push(Span::styled(" *", Style::default().fg(Theme::border_warn())));
+                }
+                lines.push(Line::from(spans));
+            }
+            if grants.iter().any(|g| g.grantable) {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("  * held WITH GRANT OPTION", dim)));
+            }
+        }
+        Some(RoleGrantsView::Loading(loading)) if loading == name => {
+            lines.push(section_header("Privileges"));
+            lines.push(Line::from(Span::styled("  Reading privileges…", dim)));
+        }
+        Some(RoleGrantsView::Failed { role: failed, error }) if failed == name => {
+            lines.push(section_header("Privileges"));
+            lines.push(Line::from(Span::styled(
+                format!("  Could not read privileges: {error}"),
+                Style::default().fg(Theme::border_danger()),
+            )));
+        }
+        // Replay: privileges are not recorded
+        _ => {
+            lines.push(section_header("Privileges"));
+            lines.push(Line::from(Span::styled("  Privileges are read live only", dim)));
+        }
+    }
+
+    render_searchable(frame, app, lines, block, popup);
+}
+
+pub fn render_slot_inspect(frame: &mut Frame, app: &App, area: Rect, name: &str) {
+    let popup = centered_rect(65, 65, area);
+    frame.render_widget(Clear, popup);
+
+    let emoji = if app.show_emojis() { "🎰 " } else { "" };
+    let title = format!("{emoji}Slot Details  [j/k] scroll  [y] copy name  [Esc] close");
+    let block = overlay_block(&title, Theme::border_active());
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), popup);
+        return;
+    };
+
+    let Some(s) = snap.replication_slots.iter().find(|s| s.slot_name == name) else {
+        frame.render_widget(
+            Paragraph::new("Replication slot no longer exists").block(block),
+            popup,
+        );
+        return;
+    };
+
+    let label = |s: &'static str| Span::styled(s, Style::default().fg(Theme::fg_dim()));
+    let val = |s: String| Span::styled(s, Style::default().fg(Theme::fg()));
+    let val_opt = |o: &Option<String>| val(o.clone().unwrap_or_else(|| "-".into()));
+    let bytes = |b: Option<i64>| b.map_or_else(|| "-".into(), format_bytes);
+    let count = |n: Option<i64>| n.map_or_else(|| "-".into(), |n| n.to_string());
+
+    let (active, active_color) = if s.active {
+        ("Yes", Theme::border_ok())
+    } else {
+        ("No", Theme::border_warn())
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Slot"),
+        Line::from(vec![
+            label("  Name:            "),
+            Span::styled(
+                s.slot_name.clone(),
+                Style::default()
+                    .fg(Theme::border_active())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![label("  Type:            "), val(s.slot_type.clone())]),
+        Line::from(vec![label("  Database:        "), val_opt(&s.database)]),
+        Line::from(vec![
+            label("  Temporary:       "),
+            val(if s.temporary { "Yes" } else { "No" }.into()),
+        ]),
+        Line::from(vec![
+            label("  Active:          "),
+            Span::styled(
+                active,
+                Style::default().fg(active_color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        section_header("WAL"),
+        Line::from(vec![
+            label("  Retained:        "),
+            Span::styled(
+                bytes(s.wal_retained_bytes),
+                Style::default()
+                    .fg(Theme::fg())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![label("  Restart LSN:     "), val_opt(&s.restart_lsn)]),
+        Line::from(vec![label("  Confirmed Flush: "), val_opt(&s.confirmed_flush_lsn)]),
+        Line::from(""),
+        section_header("Spill"),
+        Line::from(vec![label("  Spilled Txns:    "), val(count(s.spill_txns))]),
+        Line::from(vec![label("  Spill Count:     "), val(count(s.spill_count))]),
+        Line::from(vec![label("  Spill Bytes:     "), val(bytes(s.spill_bytes))]),
+    ];
+
+    if !s.active && !s.temporary {
+        lines.push(Line::from(""));
+        lines.push(section_header("Note"));
+        lines.push(Line::from(Span::styled(
+            "  No consumer is reading from this slot, so the WAL it retains keeps growing.",
+            Style::default().fg(Theme::border_warn()),
+        )));
+        if app.replay.is_none() {
+            lines.push(Line::from(Span::styled(
+                "  If the consumer is gone for good, press X in the panel to drop the slot.",
+                Style::default().fg(Theme::fg_dim()),
+            )));
+        }
+    }
+
+    render_searchable(frame, app, lines, block, popup);
+}
+
+pub fn render_wait_event_inspect(frame: &mut Frame, app: &App, area: Rect, wait_event_type: &str, wait_event: &str) {
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let emoji = if app.show_emojis() { "⏳ " } else { "" };
+    let title = format!("{emoji}Wait Event  [j/k] scroll  [⏎] show in Queries  [y] copy PIDs  [Esc] close");
+    let color = Theme::wait_event_color(wait_event_type);
+    let block = overlay_block(&title, color);
+
+    if app.snapshot.is_none() {
+        frame.render_widget(Paragraph::new("No data").block(block), popup);
+        return;
+    }
+
+    let dim = Style::default().fg(Theme::fg_dim());
+    let backends = app.wait_event_backends(wait_event_type, wait_event);
+    let mut lines = vec![
+        Line::from(""),
+        section_header("Wait Event"),
+        Line::from(vec![
+            Span::styled("  Type:          ", dim),
+            Span::styled(wait_event_type.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Event:         ", dim),
+            Span::styled(wait_event.to_string(), Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Backends:      ", dim),
+            Span::styled(backends.len().to_string(), Style::default().fg(Theme::fg())),
+        ]),
+        Line::from(""),
+        section_header("Backends"),
+    ];
+
+    if backends.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  None in the Queries list right now; the count is from the last refresh",
+            dim,
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("  {:<8}{:<14}{:<14}{:>9}  Query", "PID", "User", "Database", "Duration"),
+            dim.add_modifier(Modifier::BOLD),
+        )));
+        // Borders, indent and the fixed columns before the query
+        let query_width = usize::from(popup.width.saturating_sub(2 + 2 + 8 + 14 + 14 + 9 + 2)).max(10);
+        for q in &backends {
+            let mut spans = vec![
+                Span::styled(format!("  {:<8}", q.pid), Style::default().fg(Theme::fg())),
+                Span::styled(format!("{:<14}", truncate(q.usename.as_deref().unwrap_or("-"), 13)), dim),
+                Span::styled(format!("{:<14}", truncate(q.datname.as_deref().unwrap_or("-"), 13)), dim),
+                Span::styled(
+                    format!("{:>9}  ", format_duration(q.duration_secs)),
+                    Style::default().fg(Theme::duration_color(q.duration_secs)),
+                ),
+            ];
+            spans.extend(highlight_sql_inline(q.query.as_deref().unwrap_or("<no query>"), query_width));
+            lines.push(Line::from(spans));
+        }
+    }
 
-    frame.render_widget(paragraph, popup_area);
+    render_searchable(frame, app, lines, block, popup);
 }
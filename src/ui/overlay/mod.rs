@@ -1,29 +1,56 @@
+mod activity;
+mod chord;
+mod collector;
+mod columns;
 mod config;
 mod confirm;
+mod connections;
+mod explain;
+mod glossary;
 mod help;
+mod index_advice;
 mod inspect;
 mod recordings;
+mod settings_review;
 mod sql_highlight;
+mod sql_view;
+mod timeouts;
 
+pub use activity::render_activity;
+pub use chord::render_chord_hint;
+pub use collector::render_collector;
+pub use columns::render_columns;
 pub use config::render_config;
+pub use connections::render_connections;
+pub use explain::render_explain;
 pub use confirm::{
     render_cancel_choice, render_confirm_cancel, render_confirm_cancel_batch,
-    render_confirm_kill, render_confirm_kill_batch, render_confirm_reset_statements,
-    render_kill_choice,
+    render_confirm_drop_slot, render_confirm_kill, render_confirm_kill_batch, render_confirm_maintenance,
+    render_confirm_reset_statements, render_kill_choice,
 };
+pub use glossary::render_glossary;
 pub use help::render_help;
+pub use index_advice::render_index_advice;
 pub use inspect::{
     render_blocking_inspect, render_extensions_inspect, render_index_inspect, render_inspect,
-    render_replication_inspect, render_settings_inspect, render_statement_inspect,
-    render_table_inspect, render_vacuum_inspect, render_wraparound_inspect,
+    render_replication_inspect, render_role_inspect, render_settings_inspect, render_slot_inspect, render_statement_inspect,
+    render_table_inspect, render_vacuum_inspect, render_wait_event_inspect, render_wraparound_inspect,
 };
 pub use recordings::{render_confirm_delete_recording, render_recordings};
+pub use settings_review::render_settings_review;
 pub use sql_highlight::highlight_sql_inline;
+pub use sql_view::render_sql_view;
+pub use timeouts::render_timeouts;
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+};
+use ratatui::Frame;
+
+use crate::app::{App, OverlayExtent};
 
 use super::theme::Theme;
 
@@ -61,6 +88,172 @@ pub fn overlay_block(title: &str, color: Color) -> Block<'_> {
         .style(Style::default().bg(Theme::overlay_bg()))
 }
 
+/// Draw a scrollable overlay body in `area`. The scroll is clamped to the
+/// content, the range is recorded for the key handler, and a scrollbar with
+/// the visible position is drawn when the content doesn't fit.
+pub fn render_scrollable(frame: &mut Frame, app: &App, paragraph: Paragraph<'_>, area: Rect) {
+    // Both counts include the block borders
+    let total = u16::try_from(paragraph.line_count(area.width)).unwrap_or(u16::MAX);
+    let extent = OverlayExtent {
+        max_scroll: total.saturating_sub(area.height),
+        page: area.height.saturating_sub(2).max(1),
+    };
+    app.overlay_extent.set(Some(extent));
+
+    let scroll = app.overlay_scroll.min(extent.max_scroll);
+    frame.render_widget(paragraph.scroll((scroll, 0)), area);
+    if extent.max_scroll == 0 {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(usize::from(extent.max_scroll) + 1).position(usize::from(scroll));
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_symbol(Some("│"))
+            .style(Style::default().fg(Theme::border_dim())),
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+
+    // Last visible line out of the content lines, on the bottom border
+    let content = total.saturating_sub(2);
+    let label = format!(" {}/{} ", (scroll + extent.page).min(content), content);
+    let label_area = Rect {
+        x: area.x + 2,
+        y: area.y + area.height.saturating_sub(1),
+        width: area.width.saturating_sub(4),
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(label)
+            .alignment(Alignment::Right)
+            .style(Style::default().fg(Theme::fg_dim())),
+        label_area,
+    );
+}
+
+/// Draw an inspect overlay body with its `/` search applied: matches are
+/// highlighted, their line offsets recorded for `n`/`N`, and the pattern
+/// shown on the bottom border.
+pub fn render_searchable(frame: &mut Frame, app: &App, lines: Vec<Line<'_>>, block: Block<'_>, area: Rect) {
+    let search = &app.overlay_search;
+    if !search.is_active() {
+        let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: false });
+        render_scrollable(frame, app, paragraph, area);
+        return;
+    }
+
+    let needle: Vec<char> = search.query.chars().map(fold_case).collect();
+    let width = area.width.saturating_sub(2);
+    let mut offsets = Vec::new();
+    let mut offset: u16 = 0;
+    let mut highlighted = Vec::with_capacity(lines.len());
+    for line in lines {
+        let height = u16::try_from(Paragraph::new(line.clone()).wrap(Wrap { trim: false }).line_count(width))
+            .unwrap_or(u16::MAX);
+        let ranges = find_matches(&line, &needle);
+        let first = offsets.len();
+        offsets.extend(std::iter::repeat(offset).take(ranges.len()));
+        let current = search.current.checked_sub(first).filter(|&i| i < ranges.len());
+        highlighted.push(highlight_ranges(line, &ranges, current));
+        offset = offset.saturating_add(height);
+    }
+    let count = offsets.len();
+    *search.matches.borrow_mut() = offsets;
+
+    let paragraph = Paragraph::new(highlighted).block(block).wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, area);
+
+    let label = if search.editing {
+        format!(" /{}\u{258f} ", search.query)
+    } else if count == 0 {
+        format!(" /{}  no match ", search.query)
+    } else {
+        format!(" /{}  {}/{count} ", search.query, search.current.min(count - 1) + 1)
+    };
+    let label_area = Rect {
+        x: area.x + 2,
+        y: area.y + area.height.saturating_sub(1),
+        width: (label.chars().count() as u16).min(area.width.saturating_sub(4)),
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(label).style(Style::default().fg(Theme::overlay_bg()).bg(Theme::border_warn())),
+        label_area,
+    );
+}
+
+/// Case-insensitive comparison, one char for one so positions line up.
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Char ranges of the non-overlapping matches of `needle` in `line`.
+fn find_matches(line: &Line<'_>, needle: &[char]) -> Vec<(usize, usize)> {
+    let hay: Vec<char> = line
+        .spans
+        .iter()
+        .flat_map(|s| s.content.chars())
+        .map(fold_case)
+        .collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while !needle.is_empty() && i + needle.len() <= hay.len() {
+        if hay[i..i + needle.len()] == *needle {
+            ranges.push((i, i + needle.len()));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// Split the spans of `line` at the match boundaries and restyle the
+/// matched parts; `current` indexes the match to set apart.
+fn highlight_ranges<'a>(line: Line<'a>, ranges: &[(usize, usize)], current: Option<usize>) -> Line<'a> {
+    if ranges.is_empty() {
+        return line;
+    }
+    let hit = Style::default().fg(Theme::overlay_bg()).bg(Theme::border_warn());
+    let active = Style::default().fg(Theme::overlay_bg()).bg(Theme::border_active());
+    let style_at = |pos: usize| {
+        ranges
+            .iter()
+            .position(|&(start, end)| pos >= start && pos < end)
+            .map(|i| if Some(i) == current { active } else { hit })
+    };
+
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for span in line.spans {
+        let mut text = String::new();
+        let mut run = None;
+        for c in span.content.chars() {
+            let style = style_at(pos);
+            if style != run && !text.is_empty() {
+                let patched = run.map_or(span.style, |s| span.style.patch(s));
+                spans.push(Span::styled(std::mem::take(&mut text), patched));
+            }
+            run = style;
+            text.push(c);
+            pos += 1;
+        }
+        if !text.is_empty() {
+            let patched = run.map_or(span.style, |s| span.style.patch(s));
+            spans.push(Span::styled(text, patched));
+        }
+    }
+    let mut out = Line::from(spans).style(line.style);
+    out.alignment = line.alignment;
+    out
+}
+
 /// Create a section header line with visual styling
 pub fn section_header(title: &str) -> Line<'static> {
     Line::from(vec![
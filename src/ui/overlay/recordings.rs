@@ -6,7 +6,9 @@ use ratatui::Frame;
 use std::path::Path;
 
 use crate::app::App;
+use crate::recorder::first_segment_name;
 use crate::ui::theme::Theme;
+use crate::ui::util::format_duration;
 
 use super::{centered_rect, overlay_block, section_header};
 
@@ -88,13 +90,36 @@ pub fn render_recordings(frame: &mut Frame, app: &App, area: Rect) {
                 dim_style
             };
 
-            lines.push(Line::from(vec![
+            let mut row = vec![
                 Span::styled(indicator, key_style),
                 Span::styled(format!("{connection:<30}  "), row_style),
                 Span::styled(format!("{date:<20}  "), row_style),
                 Span::styled(format!("{version:<6}  "), row_style),
                 Span::styled(format!("{size:>6}"), row_style),
-            ]));
+            ];
+            if recording.segments > 1 {
+                row.push(Span::styled(format!("  {} parts", recording.segments), dim_style));
+            }
+            lines.push(Line::from(row));
+
+            let name = recording.path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+            for bookmark in app.bookmarks.for_recording(&first_segment_name(name)) {
+                lines.push(Line::from(vec![
+                    Span::styled("      ⚑ ", Style::default().fg(Theme::border_warn())),
+                    Span::styled(
+                        format!(
+                            "{}–{}",
+                            bookmark.start.format("%H:%M:%S"),
+                            bookmark.end.format("%H:%M:%S")
+                        ),
+                        dim_style,
+                    ),
+                    Span::styled(
+                        format!("  ({})", format_duration(bookmark.duration_secs())),
+                        dim_style,
+                    ),
+                ]));
+            }
         }
     }
 
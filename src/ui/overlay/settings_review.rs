@@ -0,0 +1,80 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::settings_review::{Risk, SettingsReview};
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::truncate;
+
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_settings_review(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let review = SettingsReview::build(&app.server_info);
+    let border = if review.has_danger() {
+        Theme::border_danger()
+    } else if review.risky.is_empty() {
+        Theme::border_active()
+    } else {
+        Theme::border_warn()
+    };
+    let block = overlay_block("Settings Review  [j/k] scroll  [Esc] close", border);
+
+    let name_style = Style::default().fg(Theme::fg());
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let risk_style = |risk: Risk| {
+        let color = match risk {
+            Risk::Danger => Theme::border_danger(),
+            Risk::Warn => Theme::border_warn(),
+        };
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    };
+
+    let mut lines = vec![Line::from("")];
+
+    lines.push(section_header("Risky values"));
+    lines.push(Line::from(""));
+    if app.server_info.settings.is_empty() {
+        lines.push(Line::from(Span::styled("    Settings haven't been read", dim_style)));
+    } else if review.risky.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    None of the checked settings has a risky value",
+            Style::default().fg(Theme::border_ok()),
+        )));
+    }
+    for r in &review.risky {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<30}", r.name), name_style),
+            Span::styled(r.value.clone(), risk_style(r.risk)),
+        ]));
+        lines.push(Line::from(Span::styled(format!("      {}", r.why), dim_style)));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(section_header(&format!("Changed from default ({})", review.changed.len())));
+    lines.push(Line::from(""));
+    if review.changed.is_empty() && !app.server_info.settings.is_empty() {
+        lines.push(Line::from(Span::styled("    Every setting is at its default", dim_style)));
+    }
+    for d in &review.changed {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<30}", truncate(&d.name, 29)), name_style),
+            Span::styled(
+                format!("{:<14}", truncate(&d.value, 13)),
+                Style::default().fg(Theme::border_warn()),
+            ),
+            Span::styled(format!("default {:<10}", truncate(&d.default, 9)), dim_style),
+            Span::styled(d.source.clone(), dim_style),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
@@ -0,0 +1,119 @@
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, InspectTarget};
+use crate::ui::theme::Theme;
+
+use super::sql_highlight::highlight_sql;
+use super::{overlay_block, render_scrollable};
+
+/// Tabs are expanded so columns line up with what scrolls past
+const TAB: &str = "    ";
+
+fn target_label(target: &InspectTarget) -> String {
+    match target {
+        InspectTarget::Query(pid) => format!("Query {pid}"),
+        InspectTarget::Statement(queryid) => format!("Statement {queryid}"),
+        InspectTarget::Blocking(pid) => format!("Blocked {pid}"),
+        InspectTarget::Index(key) => format!("Index {key}"),
+        _ => String::new(),
+    }
+}
+
+/// Split highlighted `chars` into rows of at most `width`, breaking after
+/// the last space that fits and mid-word only when there is none.
+fn wrap_chars(chars: &[(char, Style)], width: usize) -> Vec<&[(char, Style)]> {
+    let mut rows = Vec::new();
+    let mut rest = chars;
+    while rest.len() > width {
+        let cut = rest[..=width]
+            .iter()
+            .rposition(|(c, _)| *c == ' ')
+            .filter(|&i| i > 0)
+            .map_or(width, |i| i + 1);
+        let (row, tail) = rest.split_at(cut.min(rest.len()));
+        rows.push(row);
+        rest = tail;
+    }
+    rows.push(rest);
+    rows
+}
+
+/// Regroup styled characters into spans of equal style.
+fn to_spans(chars: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut text = String::new();
+    let mut style = None;
+    for &(c, s) in chars {
+        if style.is_some_and(|cur| cur != s) {
+            spans.push(Span::styled(std::mem::take(&mut text), style.unwrap_or_default()));
+        }
+        style = Some(s);
+        text.push(c);
+    }
+    if let Some(style) = style {
+        spans.push(Span::styled(text, style));
+    }
+    spans
+}
+
+pub fn render_sql_view(frame: &mut Frame, app: &App, target: &InspectTarget, area: Rect) {
+    frame.render_widget(Clear, area);
+    let view = &app.sql_view;
+    let wrap_hint = if view.wrap { "[w] no wrap" } else { "[w] wrap  [←→] scroll" };
+    let title = format!("SQL · {}  {wrap_hint}  [n] numbers  [Esc] back", target_label(target));
+    let block = overlay_block(&title, Theme::border_active());
+
+    let Some(sql) = app.inspect_sql(target) else {
+        let paragraph = Paragraph::new(Line::from(Span::styled(
+            "  No SQL to show",
+            Style::default().fg(Theme::fg_dim()),
+        )))
+        .block(block);
+        render_scrollable(frame, app, paragraph, area);
+        return;
+    };
+
+    let source = highlight_sql(&sql.replace('\t', TAB), "");
+    let number_width = source.len().to_string().len();
+    let gutter_width = if view.line_numbers { number_width + 3 } else { 0 };
+    // Leave a column for the scrollbar
+    let width = usize::from(area.width.saturating_sub(3))
+        .saturating_sub(gutter_width)
+        .max(1);
+    let gutter_style = Style::default().fg(Theme::fg_dim());
+
+    let mut widest = 0;
+    let mut lines = Vec::new();
+    for (n, line) in source.iter().enumerate() {
+        let chars: Vec<(char, Style)> = line
+            .spans
+            .iter()
+            .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+            .collect();
+        widest = widest.max(chars.len());
+        let rows = if view.wrap {
+            wrap_chars(&chars, width)
+        } else {
+            let start = usize::from(view.hscroll).min(chars.len());
+            vec![&chars[start..]]
+        };
+        for (i, row) in rows.into_iter().enumerate() {
+            let mut spans = Vec::new();
+            if view.line_numbers {
+                let number = if i == 0 { (n + 1).to_string() } else { String::new() };
+                spans.push(Span::styled(format!("{number:>number_width$} │ "), gutter_style));
+            }
+            spans.extend(to_spans(row));
+            lines.push(Line::from(spans));
+        }
+    }
+    let max_hscroll = if view.wrap { 0 } else { widest.saturating_sub(width) };
+    view.max_hscroll.set(u16::try_from(max_hscroll).unwrap_or(u16::MAX));
+
+    render_scrollable(frame, app, Paragraph::new(lines).block(block), area);
+}
+
@@ -0,0 +1,128 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::timeouts::{TimeoutAdvisory, LONG_TXN_SECS};
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::format_duration;
+
+use super::{centered_rect, overlay_block, render_scrollable, section_header};
+
+pub fn render_timeouts(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(80, 80, area);
+    frame.render_widget(Clear, popup);
+
+    let block = overlay_block(
+        "Timeout Policy  [j/k] scroll  [Esc] close",
+        Theme::border_active(),
+    );
+
+    let advisory = TimeoutAdvisory::build(
+        &app.server_info,
+        app.snapshot.as_ref(),
+        &app.connection.dbname,
+    );
+
+    let name_style = Style::default().fg(Theme::fg());
+    let dim_style = Style::default().fg(Theme::fg_dim());
+    let ok_style = Style::default().fg(Theme::border_ok());
+    let off_style = Style::default()
+        .fg(Theme::border_warn())
+        .add_modifier(Modifier::BOLD);
+    let danger_style = Style::default()
+        .fg(Theme::border_danger())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from("")];
+
+    if advisory.needs_attention() {
+        lines.push(Line::from(Span::styled(
+            "  No timeouts are set while long transactions run; nothing will stop them.",
+            danger_style,
+        )));
+        lines.push(Line::from(""));
+    }
+
+    lines.push(section_header("Timeouts"));
+    lines.push(Line::from(""));
+    for t in &advisory.timeouts {
+        let value_style = if t.disabled { off_style } else { ok_style };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<38}", t.name), name_style),
+            Span::styled(format!("{:<8}", t.value), value_style),
+            Span::styled(format!("({})", t.source), dim_style),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("      suggested {}: {}", t.recommended, t.why),
+            dim_style,
+        )));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(section_header("Per-role / per-database overrides"));
+    lines.push(Line::from(""));
+    let overrides: Vec<_> = advisory.timeouts.iter().flat_map(|t| &t.overrides).collect();
+    if overrides.is_empty() {
+        lines.push(Line::from(Span::styled("    None", dim_style)));
+    }
+    for o in overrides {
+        let value_style = if o.value == "0" { off_style } else { ok_style };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<38}", o.name), name_style),
+            Span::styled(format!("{:<8}", o.value), value_style),
+            Span::styled(o.scope(), dim_style),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(section_header(&format!(
+        "Transactions open longer than {} min",
+        LONG_TXN_SECS / 60.0
+    )));
+    lines.push(Line::from(""));
+    if advisory.long_transactions.is_empty() {
+        lines.push(Line::from(Span::styled("    None right now", dim_style)));
+    }
+    for t in &advisory.long_transactions {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    PID {:<8}", t.pid), name_style),
+            Span::styled(
+                format!("{:<10}", format_duration(t.age_secs)),
+                danger_style,
+            ),
+            Span::styled(
+                format!(
+                    "{}@{}{}",
+                    t.role.as_deref().unwrap_or("-"),
+                    t.database.as_deref().unwrap_or("-"),
+                    if t.idle { "  idle in transaction" } else { "" }
+                ),
+                dim_style,
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(section_header("Suggested settings"));
+    lines.push(Line::from(""));
+    if advisory.suggestions.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "    Every timeout is set; nothing to suggest.",
+            dim_style,
+        )));
+    }
+    for sql in &advisory.suggestions {
+        lines.push(Line::from(Span::styled(
+            format!("    {sql}"),
+            Style::default().fg(Theme::border_active()),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    render_scrollable(frame, app, paragraph, popup);
+}
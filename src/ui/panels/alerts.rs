@@ -0,0 +1,92 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table};
+
+use super::panel_block;
+
+pub fn render_alerts(frame: &mut Frame, app: &mut App, area: Rect) {
+    let emoji = if app.show_emojis() { "🔔 " } else { "" };
+    let active = app.alerts.active_count();
+    let title = format!(
+        "{emoji}Alerts [{active} active / {}]",
+        app.alerts.history.len()
+    );
+    let block = panel_block(&title);
+
+    if app.alerts.history.is_empty() {
+        let text = match app.config.alerts.len() {
+            0 => "No alert rules configured (see [[alerts]] in config.toml)".to_string(),
+            1 => "No alerts fired; watching 1 rule".to_string(),
+            n => format!("No alerts fired; watching {n} rules"),
+        };
+        frame.render_widget(empty_state(&text, block), area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("State"),
+        Cell::from("Alert"),
+        Cell::from("Condition"),
+        Cell::from("Peak"),
+        Cell::from("Fired"),
+        Cell::from("Lasted"),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let now = app.snapshot.as_ref().map(|s| s.timestamp);
+    let rows: Vec<Row> = app
+        .alerts
+        .history
+        .iter()
+        .map(|alert| {
+            let (state, state_style) = if alert.is_active() {
+                (
+                    "● FIRING",
+                    Style::default()
+                        .fg(Theme::border_danger())
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("✓ resolved", Style::default().fg(Theme::border_ok()))
+            };
+            let name_style = if alert.is_active() {
+                Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Theme::fg_dim())
+            };
+            let lasted = alert
+                .resolved_at
+                .or(now)
+                .map_or_else(String::new, |end| {
+                    format_duration((end - alert.fired_at).num_milliseconds() as f64 / 1000.0)
+                });
+            Row::new(vec![
+                Cell::from(state).style(state_style),
+                Cell::from(alert.name.clone()).style(name_style),
+                Cell::from(alert.condition.clone()).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(alert.peak.clone()).style(Style::default().fg(Theme::border_warn())),
+                Cell::from(alert.fired_at.format("%H:%M:%S").to_string()),
+                Cell::from(lasted),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(11),   // State
+        Constraint::Min(20),      // Alert
+        Constraint::Min(30),      // Condition
+        Constraint::Length(10),   // Peak
+        Constraint::Length(9),    // Fired
+        Constraint::Length(10),   // Lasted
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.alerts);
+    app.mouse_targets.table_at(area, true);
+}
@@ -0,0 +1,151 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::autovacuum::AutovacuumStatus;
+use crate::ui::graph::{render_multi_chart, ChartSeries};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_compact, format_duration, styled_table};
+
+use super::panel_block;
+
+/// Width of the worker graph right of the table
+const GRAPH_WIDTH: u16 = 36;
+
+fn status_color(status: AutovacuumStatus) -> Color {
+    match status {
+        AutovacuumStatus::Starved => Theme::border_danger(),
+        AutovacuumStatus::Due => Theme::border_warn(),
+        AutovacuumStatus::Vacuuming => Theme::border_ok(),
+        AutovacuumStatus::Ok => Theme::fg_dim(),
+    }
+}
+
+pub fn render_autovacuum(frame: &mut Frame, app: &mut App, area: Rect) {
+    let tables = app.autovacuum_rows();
+    let settings = app.autovacuum_settings();
+    let emoji = if app.show_emojis() { "🧹 " } else { "" };
+    let title = format!("{emoji}Autovacuum Health [{}]", tables.len());
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.table_stats.is_empty() {
+        frame.render_widget(empty_state("No user tables found", block), area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(40), Constraint::Length(GRAPH_WIDTH)])
+        .split(inner);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(columns[0]);
+
+    let workers = snap.summary.autovacuum_count;
+    let worker_color = if workers >= settings.max_workers {
+        Theme::border_warn()
+    } else {
+        Theme::fg()
+    };
+    let mut summary = vec![
+        Span::styled(" Workers ", Style::default().fg(Theme::fg_dim())),
+        Span::styled(
+            format!("{workers}/{}", settings.max_workers),
+            Style::default().fg(worker_color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" · naptime {}", format_duration(settings.naptime_secs)),
+            Style::default().fg(Theme::fg_dim()),
+        ),
+    ];
+    if !settings.enabled {
+        summary.push(Span::styled(" · ", Style::default().fg(Theme::fg_dim())));
+        summary.push(Span::styled(
+            "autovacuum is off",
+            Style::default().fg(Theme::border_danger()).add_modifier(Modifier::BOLD),
+        ));
+    }
+    for status in [AutovacuumStatus::Starved, AutovacuumStatus::Due] {
+        let n = tables.iter().filter(|t| t.status == status).count();
+        if n > 0 {
+            summary.push(Span::styled(" · ", Style::default().fg(Theme::fg_dim())));
+            summary.push(Span::styled(
+                format!("{n} {}", status.label()),
+                Style::default().fg(status_color(status)),
+            ));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(summary)), parts[0]);
+
+    let history = app.metrics.autovacuum_workers.as_vec();
+    let ceiling = vec![settings.max_workers.max(0) as u64; history.len()];
+    render_multi_chart(
+        frame,
+        columns[1],
+        "Workers",
+        vec![
+            ChartSeries {
+                label: format!("max {}", settings.max_workers),
+                data: ceiling,
+                color: Theme::border_dim(),
+            },
+            ChartSeries {
+                label: format!("running {workers}"),
+                data: history,
+                color: Theme::graph_connections(),
+            },
+        ],
+        app.config.graph_marker.to_marker(),
+    );
+
+    let header = Row::new(vec!["Status", "Table", "Dead", "Trigger", "Due For", "Since Vacuum", "Runs"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let dash = || "\u{2014}".to_string();
+    let rows: Vec<Row> = tables
+        .iter()
+        .map(|t| {
+            let color = status_color(t.status);
+            let dead_style = if t.n_dead_tup > t.trigger {
+                Style::default().fg(color)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(t.status.label()).style(Style::default().fg(color)),
+                Cell::from(t.key()),
+                Cell::from(format_compact(t.n_dead_tup)).style(dead_style),
+                Cell::from(format_compact(t.trigger)),
+                Cell::from(t.due_secs.map_or_else(dash, format_duration)),
+                Cell::from(t.since_vacuum_secs.map_or_else(|| "never".into(), format_duration)),
+                Cell::from(t.autovacuum_runs.to_string()),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Min(18),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(13),
+        Constraint::Length(5),
+    ];
+
+    let table = styled_table(rows, widths, header, Block::default());
+    frame.render_stateful_widget(table, parts[1], &mut app.panels.autovacuum);
+    app.mouse_targets.table_at(parts[1], false);
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table, truncate};
+
+use super::panel_block;
+
+pub fn render_backends(frame: &mut Frame, app: &mut App, area: Rect) {
+    let emoji = if app.show_emojis() { "🧵 " } else { "" };
+    let count = app.snapshot.as_ref().map_or(0, |s| s.background_workers.len());
+    let title = format!("{emoji}Background Workers [{count}]");
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.background_workers.is_empty() {
+        frame.render_widget(empty_state("No background processes reported", block), area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+
+    // Backend types, client backends first since they are what the rest serve
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for worker in &snap.background_workers {
+        *counts.entry(worker.backend_type.as_str()).or_default() += 1;
+    }
+    let mut breakdown = vec![
+        Span::styled(" client backend ", Style::default().fg(Theme::fg_dim())),
+        Span::styled(
+            snap.summary.total_backends.to_string(),
+            Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    for (backend_type, n) in counts {
+        breakdown.push(Span::styled(format!(" · {backend_type} "), Style::default().fg(Theme::fg_dim())));
+        breakdown.push(Span::styled(
+            n.to_string(),
+            Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(breakdown)), parts[0]);
+
+    let header = Row::new(vec!["PID", "Type", "Database", "State", "Wait", "Duration", "Leader", "Query"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let dash = || "-".to_string();
+    let rows: Vec<Row> = snap
+        .background_workers
+        .iter()
+        .map(|w| {
+            let type_color = if w.is_parallel_worker() {
+                Theme::border_active()
+            } else {
+                Theme::fg()
+            };
+            let wait = match (&w.wait_event_type, &w.wait_event) {
+                (Some(kind), Some(event)) => format!("{kind}:{event}"),
+                _ => dash(),
+            };
+            Row::new(vec![
+                Cell::from(w.pid.to_string()),
+                Cell::from(w.backend_type.clone()).style(Style::default().fg(type_color)),
+                Cell::from(w.datname.clone().unwrap_or_else(dash))
+                    .style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(w.state.clone().unwrap_or_else(dash))
+                    .style(Style::default().fg(Theme::state_color(w.state.as_deref()))),
+                Cell::from(wait).style(Style::default().fg(if w.wait_event.is_some() {
+                    Theme::border_warn()
+                } else {
+                    Theme::fg_dim()
+                })),
+                Cell::from(format_duration(w.duration_secs)),
+                Cell::from(w.leader_pid.map_or_else(dash, |pid| pid.to_string())),
+                Cell::from(truncate(&w.query.as_deref().unwrap_or("").replace('\n', " "), 200))
+                    .style(Style::default().fg(Theme::fg_dim())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(28),
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Length(24),
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Min(20),
+    ];
+
+    let table = styled_table(rows, widths, header, Block::default());
+    frame.render_stateful_widget(table, parts[1], &mut app.panels.backends);
+    app.mouse_targets.table_at(parts[1], false);
+}
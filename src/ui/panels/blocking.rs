@@ -10,7 +10,7 @@ use crate::ui::util::{empty_state, styled_table};
 use super::panel_block;
 
 pub fn render_blocking(frame: &mut Frame, app: &mut App, area: Rect) {
-    let emoji = if app.config.show_emojis { "🔒 " } else { "" };
+    let emoji = if app.show_emojis() { "🔒 " } else { "" };
     let title = format!("{emoji}Blocking");
     let block = panel_block(&title);
 
@@ -55,4 +55,5 @@ pub fn render_blocking(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.blocking);
+    app.mouse_targets.table_at(area, true);
 }
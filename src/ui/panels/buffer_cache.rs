@@ -0,0 +1,144 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::db::models::BufferCacheReport;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_bytes, styled_table};
+
+use super::panel_block;
+
+/// What a `pg_class.relkind` is, as the Kind column shows it
+fn relkind_label(relkind: &str) -> &'static str {
+    match relkind {
+        "r" | "p" => "table",
+        "i" | "I" => "index",
+        "t" => "toast",
+        "m" => "matview",
+        "S" => "sequence",
+        _ => "other",
+    }
+}
+
+fn percent(part: i64, total: i64) -> f64 {
+    if total > 0 {
+        part as f64 * 100.0 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// Used, dirty and the usage count spread across shared buffers
+fn summary_lines(report: &BufferCacheReport) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Theme::fg_dim());
+    let value = Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD);
+    let used = report.total_buffers - report.unused_buffers;
+    let bytes = |buffers: i64| format_bytes(buffers * report.block_size);
+    let dirty_color = if percent(report.dirty_buffers, used) > 20.0 {
+        Theme::border_warn()
+    } else {
+        Theme::fg()
+    };
+    let totals = Line::from(vec![
+        Span::styled(" In use ", dim),
+        Span::styled(format!("{} of {}", bytes(used), bytes(report.total_buffers)), value),
+        Span::styled(format!(" ({:.0}%)", percent(used, report.total_buffers)), dim),
+        Span::styled(" · dirty ", dim),
+        Span::styled(bytes(report.dirty_buffers), Style::default().fg(dirty_color)),
+        Span::styled(
+            format!(" · read {}", report.read_at.with_timezone(&chrono::Local).format("%H:%M:%S")),
+            dim,
+        ),
+    ]);
+
+    let mut usage = vec![Span::styled(" Usage count ", dim)];
+    for (count, &buffers) in report.usage_counts.iter().enumerate() {
+        if count > 0 {
+            usage.push(Span::styled("  ", dim));
+        }
+        // Hot pages (high counts) are what the clock sweep keeps
+        let color = match count {
+            0 | 1 => Theme::fg_dim(),
+            5 => Theme::border_ok(),
+            _ => Theme::fg(),
+        };
+        usage.push(Span::styled(format!("{count}:"), dim));
+        usage.push(Span::styled(
+            format!("{:.0}%", percent(buffers, used)),
+            Style::default().fg(color),
+        ));
+    }
+    vec![totals, Line::from(usage)]
+}
+
+pub fn render_buffer_cache(frame: &mut Frame, app: &mut App, area: Rect) {
+    let emoji = if app.show_emojis() { "🧊 " } else { "" };
+    let count = app.buffer_cache.as_ref().map_or(0, |r| r.relations.len());
+    let title = format!("{emoji}Buffer Cache [{count}]");
+    let block = panel_block(&title);
+
+    let Some(report) = &app.buffer_cache else {
+        let message = if app.is_replay_mode() {
+            "Buffer cache contents are read on demand and not recorded"
+        } else if !app.server_info.extensions.pg_buffercache {
+            "pg_buffercache is not installed (CREATE EXTENSION pg_buffercache)"
+        } else if app.feedback.buffer_cache_loading {
+            "Reading shared buffers..."
+        } else {
+            "Press b to read shared buffers (scans every buffer header)"
+        };
+        frame.render_widget(empty_state(message, block), area);
+        return;
+    };
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+    frame.render_widget(Paragraph::new(summary_lines(report)), parts[0]);
+
+    let header = Row::new(vec!["Relation", "Kind", "Cached", "Share", "Of Rel", "Dirty", "Avg Usage"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let used = report.total_buffers - report.unused_buffers;
+    let rows: Vec<Row> = report
+        .relations
+        .iter()
+        .map(|rel| {
+            let dirty_style = if rel.dirty > 0 {
+                Style::default().fg(Theme::border_warn())
+            } else {
+                Style::default().fg(Theme::fg_dim())
+            };
+            Row::new(vec![
+                Cell::from(format!("{}.{}", rel.schemaname, rel.relname)),
+                Cell::from(relkind_label(&rel.relkind)).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(format_bytes(rel.buffers * report.block_size)),
+                Cell::from(format!("{:.1}%", percent(rel.buffers, used))),
+                Cell::from(rel.cached_pct.map_or_else(|| "-".into(), |p| format!("{p:.0}%"))),
+                Cell::from(format_bytes(rel.dirty * report.block_size)).style(dirty_style),
+                Cell::from(format!("{:.1}", rel.avg_usage)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(24),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+
+    let table = styled_table(rows, widths, header, Block::default());
+    frame.render_stateful_widget(table, parts[1], &mut app.panels.buffer_cache);
+    app.mouse_targets.table_at(parts[1], false);
+}
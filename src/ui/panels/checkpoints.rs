@@ -0,0 +1,272 @@
+use chrono::{DateTime, Utc};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::app::{App, CheckpointEvent, RateSeries};
+use crate::ui::graph::{render_multi_chart, ChartSeries};
+use crate::ui::theme::Theme;
+use crate::ui::util::{format_compact, format_duration, format_rate, format_time_ms};
+
+use super::panel_block;
+
+/// Width of the summary column left of the graphs
+const SUMMARY_WIDTH: u16 = 46;
+
+/// Settings that decide how often checkpoints run and how hard they write.
+const SETTINGS: [&str; 3] = ["checkpoint_timeout", "max_wal_size", "checkpoint_completion_target"];
+
+pub fn render_checkpoints(frame: &mut Frame, app: &App, area: Rect) {
+    let emoji = if app.show_emojis() { "📍 " } else { "" };
+    let title = format!("{emoji}Checkpoints");
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if snap.checkpoint_stats.is_none() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "No checkpointer statistics",
+                Style::default().fg(Theme::fg_dim()),
+            )),
+            inner,
+        );
+        return;
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(SUMMARY_WIDTH), Constraint::Min(20)])
+        .split(inner);
+
+    let events = app.metrics.checkpoint_log.timed();
+    frame.render_widget(
+        Paragraph::new(summary_lines(app, &events, snap.timestamp, columns[0].height)),
+        columns[0],
+    );
+    render_graphs(frame, app, columns[1]);
+}
+
+fn per_min(series: &RateSeries) -> String {
+    series
+        .current
+        .map_or_else(|| "\u{2014}".into(), |r| format!("{:.1}/min", r * 60.0))
+}
+
+fn ms_per_sec(series: &RateSeries) -> String {
+    series
+        .current
+        .map_or_else(|| "\u{2014}".into(), |r| format!("{}/s", format_time_ms(r)))
+}
+
+fn per_sec(series: &RateSeries) -> String {
+    series.current.map_or_else(|| "\u{2014}".into(), format_rate)
+}
+
+fn render_graphs(frame: &mut Frame, app: &App, area: Rect) {
+    let marker = app.config.graph_marker.to_marker();
+    let rates = &app.metrics.wal_io;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(area);
+
+    render_multi_chart(
+        frame,
+        rows[0],
+        "Frequency",
+        vec![
+            ChartSeries {
+                label: format!("all {}", per_min(&rates.checkpoints)),
+                data: rates.checkpoints.history.as_vec(),
+                color: Theme::graph_connections(),
+            },
+            ChartSeries {
+                label: format!("forced {}", per_min(&rates.checkpoints_forced)),
+                data: rates.checkpoints_forced.history.as_vec(),
+                color: Theme::border_danger(),
+            },
+        ],
+        marker,
+    );
+    render_multi_chart(
+        frame,
+        rows[1],
+        "Write / Sync Time",
+        vec![
+            ChartSeries {
+                label: format!("write {}", ms_per_sec(&rates.checkpoint_write_time)),
+                data: rates.checkpoint_write_time.history.as_vec(),
+                color: Theme::graph_latency(),
+            },
+            ChartSeries {
+                label: format!("sync {}", ms_per_sec(&rates.checkpoint_sync_time)),
+                data: rates.checkpoint_sync_time.history.as_vec(),
+                color: Theme::border_warn(),
+            },
+        ],
+        marker,
+    );
+    render_multi_chart(
+        frame,
+        rows[2],
+        "Buffers Written",
+        vec![
+            ChartSeries {
+                label: format!("checkpointer {}", per_sec(&rates.buffers_checkpoint)),
+                data: rates.buffers_checkpoint.history.as_vec(),
+                color: Theme::graph_cache(),
+            },
+            ChartSeries {
+                label: format!("bgwriter {}", per_sec(&rates.buffers_clean)),
+                data: rates.buffers_clean.history.as_vec(),
+                color: Theme::border_ok(),
+            },
+            // Backends writing their own buffers is the one to notice
+            ChartSeries {
+                label: format!("backend {}", per_sec(&rates.buffers_backend)),
+                data: rates.buffers_backend.history.as_vec(),
+                color: Theme::border_danger(),
+            },
+        ],
+        marker,
+    );
+}
+
+/// Average time between the checkpoints seen this session.
+fn mean_interval(events: &[(DateTime<Utc>, CheckpointEvent)]) -> Option<f64> {
+    let ((first, _), (last, _)) = (events.first()?, events.last()?);
+    // The first event only marks where the measured span starts
+    let count: i64 = events[1..].iter().map(|(_, e)| e.count).sum();
+    (count > 0).then(|| (*last - *first).num_milliseconds() as f64 / 1000.0 / count as f64)
+}
+
+fn summary_lines(
+    app: &App,
+    events: &[(DateTime<Utc>, CheckpointEvent)],
+    now: DateTime<Utc>,
+    height: u16,
+) -> Vec<Line<'static>> {
+    let heading = Style::default()
+        .fg(Theme::fg())
+        .add_modifier(Modifier::BOLD);
+    let label = Style::default().fg(Theme::fg_dim());
+    let value = Style::default().fg(Theme::fg());
+    let row = |name: &str, text: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("  {name:<14}"), label),
+            Span::styled(text, style),
+        ])
+    };
+
+    let total: i64 = events.iter().map(|(_, e)| e.count).sum();
+    let forced: i64 = events.iter().map(|(_, e)| e.forced).sum();
+    let forced_style = if forced > 0 {
+        Style::default().fg(Theme::border_warn())
+    } else {
+        value
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled("This session", heading)),
+        row("Checkpoints", format!("{total} ({forced} forced)"), forced_style),
+        row(
+            "Interval",
+            mean_interval(events).map_or_else(|| "\u{2014}".into(), |s| format!("every {}", format_duration(s))),
+            value,
+        ),
+    ];
+    if let Some((at, last)) = events.last() {
+        let kind = if last.forced > 0 { "forced" } else { "timed" };
+        let ago = (now - *at).num_milliseconds().max(0) as f64 / 1000.0;
+        lines.push(row("Last", format!("{} ago, {kind}", format_duration(ago)), value));
+        lines.push(row(
+            "Last write",
+            format!("{} + {} sync", format_time_ms(last.write_ms), format_time_ms(last.sync_ms)),
+            value,
+        ));
+        lines.push(row("Last buffers", format_compact(last.buffers), value));
+    } else {
+        lines.push(row("Last", "none seen yet".into(), label));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Settings", heading)));
+    for name in SETTINGS {
+        let text = app
+            .server_info
+            .settings
+            .iter()
+            .find(|s| s.name == name)
+            .map_or_else(
+                || "\u{2014}".into(),
+                |s| format!("{}{}", s.setting, s.unit.as_deref().unwrap_or("")),
+            );
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {name:<30}"), label),
+            Span::styled(text, value),
+        ]));
+    }
+
+    // Newest first, as many as fit below the rest
+    let room = (height as usize).saturating_sub(lines.len() + 2);
+    if room > 0 && !events.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Recent", heading)));
+        for (at, e) in events.iter().rev().take(room) {
+            let (kind, style) = if e.forced > 0 {
+                ("forced", Style::default().fg(Theme::border_warn()))
+            } else {
+                ("timed ", value)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", at.format("%H:%M:%S")), label),
+                Span::styled(kind, style),
+                Span::styled(
+                    format!(
+                        "  {:>8}  {:>6} buf",
+                        format_time_ms(e.write_ms + e.sync_ms),
+                        format_compact(e.buffers)
+                    ),
+                    value,
+                ),
+            ]));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn event(count: i64) -> CheckpointEvent {
+        CheckpointEvent {
+            count,
+            ..CheckpointEvent::default()
+        }
+    }
+
+    #[test]
+    fn interval_spans_the_checkpoints_after_the_first() {
+        let t = |secs| Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap() + chrono::Duration::seconds(secs);
+        assert_eq!(mean_interval(&[]), None);
+        assert_eq!(mean_interval(&[(t(0), event(1))]), None);
+        // 600s covers the three checkpoints completed after the first one
+        let events = [(t(0), event(1)), (t(300), event(1)), (t(600), event(2))];
+        assert_eq!(mean_interval(&events), Some(200.0));
+    }
+}
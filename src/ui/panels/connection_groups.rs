@@ -0,0 +1,115 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::{App, ConnectionGroupSortColumn};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table};
+
+use super::panel_block;
+
+/// A count cell, dimmed at zero and colored `color` otherwise.
+fn count_cell(count: i64, color: ratatui::style::Color) -> Cell<'static> {
+    let fg = if count == 0 { Theme::fg_dim() } else { color };
+    Cell::from(count.to_string()).style(Style::default().fg(fg))
+}
+
+pub fn render_connection_groups(frame: &mut Frame, app: &mut App, area: Rect) {
+    let indices = app.sorted_connection_group_indices();
+    let (groups, backends) = app.snapshot.as_ref().map_or((0, 0), |s| {
+        (
+            s.connection_groups.len(),
+            s.connection_groups.iter().map(|g| g.total).sum::<i64>(),
+        )
+    });
+
+    let emoji = if app.show_emojis() { "👥 " } else { "" };
+    let title = format!("{emoji}Connection Groups [{backends} in {groups} groups]");
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.connection_groups.is_empty() {
+        frame.render_widget(empty_state("No client connections", block), area);
+        return;
+    }
+
+    let sort_indicator = |col: ConnectionGroupSortColumn| -> &str {
+        if app.panels.connection_groups.sort_column == col {
+            if app.panels.connection_groups.sort_ascending {
+                " \u{2191}"
+            } else {
+                " \u{2193}"
+            }
+        } else {
+            ""
+        }
+    };
+
+    let header = Row::new(vec![
+        Cell::from(format!("User{}", sort_indicator(ConnectionGroupSortColumn::User))),
+        Cell::from("Database"),
+        Cell::from("Application"),
+        Cell::from(format!("Total{}", sort_indicator(ConnectionGroupSortColumn::Total))),
+        Cell::from(format!("Active{}", sort_indicator(ConnectionGroupSortColumn::Active))),
+        Cell::from("Idle"),
+        Cell::from(format!(
+            "Idle in Txn{}",
+            sort_indicator(ConnectionGroupSortColumn::IdleInTransaction)
+        )),
+        Cell::from("Waiting"),
+        Cell::from(format!("Longest{}", sort_indicator(ConnectionGroupSortColumn::Longest))),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let g = &snap.connection_groups[i];
+            let app_name = if g.application_name.is_empty() {
+                Cell::from("-").style(Style::default().fg(Theme::fg_dim()))
+            } else {
+                Cell::from(g.application_name.clone())
+            };
+            let longest = if g.longest_secs > 0.0 {
+                Cell::from(format_duration(g.longest_secs))
+                    .style(Style::default().fg(Theme::duration_color(g.longest_secs)))
+            } else {
+                Cell::from("-").style(Style::default().fg(Theme::fg_dim()))
+            };
+
+            Row::new(vec![
+                Cell::from(g.usename.clone().unwrap_or_else(|| "-".into())),
+                Cell::from(g.datname.clone().unwrap_or_else(|| "-".into())),
+                app_name,
+                Cell::from(g.total.to_string()),
+                count_cell(g.active, Theme::border_ok()),
+                count_cell(g.idle, Theme::fg()),
+                count_cell(g.idle_in_transaction, Theme::border_warn()),
+                count_cell(g.waiting, Theme::border_danger()),
+                longest,
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(16),
+        Constraint::Length(16),
+        Constraint::Min(12),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(6),
+        Constraint::Length(13),
+        Constraint::Length(8),
+        Constraint::Length(10),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.connection_groups.state);
+    app.mouse_targets.table_at(area, true);
+}
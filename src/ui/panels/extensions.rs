@@ -15,15 +15,15 @@ pub fn render_extensions(frame: &mut Frame, app: &mut App, area: Rect) {
     let indices = app.sorted_extensions_indices();
     let filtered_count = indices.len();
 
-    let emoji = if app.config.show_emojis { "🧩 " } else { "" };
-    let title = if app.filter.active
-        || (!app.filter.text.is_empty()
+    let emoji = if app.show_emojis() { "🧩 " } else { "" };
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Extensions)
     {
         format!(
             "{emoji}Extensions [{}/{}] (filter: {})",
-            filtered_count, total_count, app.filter.text
+            filtered_count, total_count, app.filter().text
         )
     } else {
         format!("{emoji}Extensions [{total_count}]")
@@ -47,11 +47,11 @@ pub fn render_extensions(frame: &mut Frame, app: &mut App, area: Rect) {
     .bottom_margin(0);
 
     // Check if filtering is active
-    let is_filtering = app.filter.active
-        || (!app.filter.text.is_empty()
+    let is_filtering = app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Extensions);
-    let filter_text = &app.filter.text;
+    let filter_text = &app.filter().text;
 
     let rows: Vec<Row> = indices
         .iter()
@@ -117,4 +117,5 @@ pub fn render_extensions(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.extensions);
+    app.mouse_targets.table_at(area, true);
 }
@@ -0,0 +1,111 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::index_health::{reclaimable_bytes, IndexIssue};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_bytes, format_compact, styled_table};
+
+use super::panel_block;
+
+fn issue_color(issue: IndexIssue) -> Color {
+    match issue {
+        IndexIssue::Invalid => Theme::border_danger(),
+        IndexIssue::Duplicate | IndexIssue::Overlapping => Theme::border_warn(),
+        IndexIssue::Unused => Theme::fg_dim(),
+    }
+}
+
+pub fn render_index_health(frame: &mut Frame, app: &mut App, area: Rect) {
+    let findings = app.index_findings();
+    let emoji = if app.show_emojis() { "🩺 " } else { "" };
+    let title = format!("{emoji}Index Health [{}]", findings.len());
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.indexes.is_empty() {
+        frame.render_widget(empty_state("No user indexes found", block), area);
+        return;
+    }
+    if findings.is_empty() {
+        frame.render_widget(
+            empty_state("No unused, duplicate or invalid indexes", block),
+            area,
+        );
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+
+    let count = |issue: IndexIssue| findings.iter().filter(|f| f.issue == issue).count();
+    let mut summary = vec![
+        Span::styled(" Reclaimable ", Style::default().fg(Theme::fg_dim())),
+        Span::styled(
+            format_bytes(reclaimable_bytes(&findings)),
+            Style::default().fg(Theme::fg()).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" across {} of {} indexes", findings.len(), snap.indexes.len()),
+            Style::default().fg(Theme::fg_dim()),
+        ),
+    ];
+    for issue in [
+        IndexIssue::Invalid,
+        IndexIssue::Duplicate,
+        IndexIssue::Overlapping,
+        IndexIssue::Unused,
+    ] {
+        let n = count(issue);
+        if n > 0 {
+            summary.push(Span::styled(" · ", Style::default().fg(Theme::fg_dim())));
+            summary.push(Span::styled(
+                format!("{n} {}", issue.label()),
+                Style::default().fg(issue_color(issue)),
+            ));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(summary)), parts[0]);
+
+    let header = Row::new(vec!["Issue", "Table", "Index", "Size", "Scans", "Redundant With"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let rows: Vec<Row> = findings
+        .iter()
+        .map(|f| {
+            Row::new(vec![
+                Cell::from(f.issue.label()).style(Style::default().fg(issue_color(f.issue))),
+                Cell::from(format!("{}.{}", f.schemaname, f.table_name)),
+                Cell::from(f.index_name.clone()),
+                Cell::from(format_bytes(f.index_size_bytes)),
+                Cell::from(format_compact(f.idx_scan)),
+                Cell::from(f.redundant_with.clone().unwrap_or_default()),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Min(14),
+        Constraint::Min(18),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Min(14),
+    ];
+
+    let table = styled_table(rows, widths, header, Block::default());
+    frame.render_stateful_widget(table, parts[1], &mut app.panels.index_health);
+    app.mouse_targets.table_at(parts[1], false);
+}
@@ -5,11 +5,13 @@ use ratatui::widgets::{Cell, Paragraph, Row};
 use ratatui::Frame;
 
 use crate::app::{App, BottomPanel, IndexSortColumn, ViewMode};
+use crate::row_colors::row_color;
 use crate::db::models::BloatSource;
+use crate::ui::layout::shown_columns;
 use crate::ui::theme::Theme;
-use crate::ui::util::{compute_match_indices, empty_state, format_bytes, highlight_matches, styled_table};
+use crate::ui::util::{compute_match_indices, empty_state, format_bytes, highlight_matches, shown_only, styled_table};
 
-use super::panel_block;
+use super::{panel_block, read_at_suffix};
 
 pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
     let total_count = app
@@ -19,18 +21,19 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
     let indices = app.sorted_index_indices();
     let filtered_count = indices.len();
 
-    let emoji = if app.config.show_emojis { "📑 " } else { "" };
-    let title = if app.filter.active
-        || (!app.filter.text.is_empty()
+    let emoji = if app.show_emojis() { "📑 " } else { "" };
+    let read_at = read_at_suffix(app.snapshot.as_ref());
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Indexes)
     {
         format!(
-            "{emoji}Indexes [{}/{}] (filter: {})",
-            filtered_count, total_count, app.filter.text
+            "{emoji}Indexes [{}/{}] (filter: {}){read_at}",
+            filtered_count, total_count, app.filter().text
         )
     } else {
-        format!("{emoji}Indexes [{total_count}]")
+        format!("{emoji}Indexes [{total_count}]{read_at}")
     };
 
     let block = panel_block(&title);
@@ -57,7 +60,8 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    let header = Row::new(vec![
+    let shown = shown_columns(app, BottomPanel::Indexes, area.width);
+    let header = Row::new(shown_only([
         Cell::from("Table"),
         Cell::from("Index"),
         Cell::from(format!("Size{}", sort_indicator(IndexSortColumn::Size))),
@@ -71,22 +75,23 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
             sort_indicator(IndexSortColumn::TupFetch)
         )),
         Cell::from("Bloat[b]"),
-    ])
+    ], &shown))
     .style(Theme::title_style())
     .bottom_margin(0);
 
     // Check if filtering is active
-    let is_filtering = app.filter.active
-        || (!app.filter.text.is_empty()
+    let is_filtering = app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Indexes);
-    let filter_text = &app.filter.text;
+    let filter_text = &app.filter().text;
 
     let rows: Vec<Row> = indices
         .iter()
         .map(|&i| {
             let idx = &snap.indexes[i];
-            let scan_color = Theme::index_usage_color(idx.idx_scan);
+            let rule_color = row_color(&app.config.row_colors, idx).map(Theme::row_color);
+            let scan_color = rule_color.unwrap_or_else(|| Theme::index_usage_color(idx.idx_scan));
             let table_name = format!("{}.{}", idx.schemaname, idx.table_name);
 
             // Compute match indices if filtering - match against index name
@@ -111,17 +116,17 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
             let bloat_cell = idx.bloat_pct.map_or_else(
                 || Cell::from("-"),
                 |pct| {
-                    let color = Theme::bloat_color(pct);
+                    let color = rule_color.unwrap_or_else(|| Theme::bloat_color(pct));
                     // Show ~ prefix for estimated values (non-pgstattuple)
                     let prefix = match idx.bloat_source {
-                        Some(BloatSource::Pgstattuple) => "",
+                        Some(BloatSource::Pgstattuple | BloatSource::PgstattupleExact) => "",
                         _ => "~",
                     };
                     Cell::from(format!("{prefix}{pct:.1}%")).style(Style::default().fg(color))
                 },
             );
 
-            Row::new(vec![
+            Row::new(shown_only([
                 Cell::from(table_name),
                 index_cell,
                 Cell::from(format_bytes(idx.index_size_bytes)),
@@ -130,11 +135,12 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
                 Cell::from(idx.idx_tup_read.to_string()),
                 Cell::from(idx.idx_tup_fetch.to_string()),
                 bloat_cell,
-            ])
+            ], &shown))
+            .style(rule_color.map_or_else(Style::default, |color| Style::default().fg(color)))
         })
         .collect();
 
-    let widths = [
+    let widths = shown_only([
         Constraint::Min(18),
         Constraint::Min(20),
         Constraint::Length(9),
@@ -142,8 +148,9 @@ pub fn render_indexes(frame: &mut Frame, app: &mut App, area: Rect) {
         Constraint::Length(12),
         Constraint::Length(12),
         Constraint::Length(8),
-    ];
+    ], &shown);
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.indexes.state);
+    app.mouse_targets.table_at(area, true);
 }
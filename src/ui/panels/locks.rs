@@ -0,0 +1,126 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::{App, LockSortColumn};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table};
+
+use super::panel_block;
+
+/// Modes that conflict with plain reads of the table.
+fn is_strong_mode(mode: &str) -> bool {
+    matches!(mode, "AccessExclusiveLock" | "ExclusiveLock" | "ShareRowExclusiveLock")
+}
+
+fn pid_list(pids: &[i32]) -> String {
+    if pids.is_empty() {
+        return "-".into();
+    }
+    pids.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")
+}
+
+pub fn render_locks(frame: &mut Frame, app: &mut App, area: Rect) {
+    let indices = app.sorted_lock_indices();
+    let (total, waiting) = app.snapshot.as_ref().map_or((0, 0), |s| {
+        (s.locks.len(), s.locks.iter().filter(|l| !l.granted).count())
+    });
+
+    let emoji = if app.show_emojis() { "🔐 " } else { "" };
+    let title = format!("{emoji}Locks [{total}, {waiting} waiting]");
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.locks.is_empty() {
+        frame.render_widget(empty_state("No locks held", block), area);
+        return;
+    }
+
+    let sort_indicator = |col: LockSortColumn| -> &str {
+        if app.panels.locks.sort_column == col {
+            if app.panels.locks.sort_ascending {
+                " \u{2191}"
+            } else {
+                " \u{2193}"
+            }
+        } else {
+            ""
+        }
+    };
+
+    let header = Row::new(vec![
+        Cell::from(format!("Status{}", sort_indicator(LockSortColumn::Status))),
+        Cell::from(format!("Mode{}", sort_indicator(LockSortColumn::Mode))),
+        Cell::from(format!("Relation{}", sort_indicator(LockSortColumn::Relation))),
+        Cell::from(format!("Holder{}", sort_indicator(LockSortColumn::Pid))),
+        Cell::from("Waiter"),
+        Cell::from(format!("Duration{}", sort_indicator(LockSortColumn::Duration))),
+        Cell::from("Query"),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let l = &snap.locks[i];
+            let (status, status_color) = if l.granted {
+                ("held", Theme::border_ok())
+            } else {
+                ("waiting", Theme::border_danger())
+            };
+            let mode_style = if is_strong_mode(&l.mode) {
+                Style::default()
+                    .fg(Theme::border_warn())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Theme::fg())
+            };
+            let relation = l
+                .relation
+                .clone()
+                .unwrap_or_else(|| format!("({})", l.locktype));
+            let waiters = if l.granted {
+                app.lock_waiters(i)
+            } else {
+                vec![l.pid]
+            };
+            let waiter_color = if waiters.is_empty() {
+                Theme::fg_dim()
+            } else {
+                Theme::border_warn()
+            };
+
+            Row::new(vec![
+                Cell::from(status).style(Style::default().fg(status_color)),
+                Cell::from(l.mode.trim_end_matches("Lock").to_string()).style(mode_style),
+                Cell::from(relation),
+                Cell::from(pid_list(&l.holder_pids()))
+                    .style(Style::default().fg(Theme::border_danger())),
+                Cell::from(pid_list(&waiters)).style(Style::default().fg(waiter_color)),
+                Cell::from(format_duration(l.duration_secs))
+                    .style(Style::default().fg(Theme::duration_color(l.duration_secs))),
+                Cell::from(l.query.clone().unwrap_or_else(|| "-".into())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(20),
+        Constraint::Length(24),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Min(15),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.locks.state);
+    app.mouse_targets.table_at(area, true);
+}
@@ -0,0 +1,112 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_bytes, format_compact, format_duration, format_lag, styled_table, truncate};
+
+use super::panel_block;
+
+pub fn render_logical_replication(frame: &mut Frame, app: &mut App, area: Rect) {
+    let (total, down) = app.snapshot.as_ref().map_or((0, 0), |s| {
+        let subs = &s.subscriptions;
+        (subs.len(), subs.iter().filter(|s| s.worker_down()).count())
+    });
+
+    let emoji = if app.show_emojis() { "📡 " } else { "" };
+    let title = if down > 0 {
+        format!("{emoji}Logical Replication [{total}, {down} worker down]")
+    } else {
+        format!("{emoji}Logical Replication [{total}]")
+    };
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if snap.subscriptions.is_empty() {
+        frame.render_widget(empty_state("No subscriptions on this server", block), area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        "Subscription",
+        "Enabled",
+        "Worker",
+        "Tables",
+        "Apply Lag",
+        "Pending",
+        "Last Msg",
+        "Apply Err",
+        "Sync Err",
+        "Conflicts",
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let warn = Style::default()
+        .fg(Theme::border_warn())
+        .add_modifier(Modifier::BOLD);
+    let count_cell = |n: Option<i64>| match n {
+        Some(n) if n > 0 => Cell::from(format_compact(n)).style(warn),
+        Some(n) => Cell::from(format_compact(n)),
+        None => Cell::from("-"),
+    };
+
+    let rows: Vec<Row> = snap
+        .subscriptions
+        .iter()
+        .map(|s| {
+            let enabled_style = if s.enabled {
+                Style::default().fg(Theme::border_ok())
+            } else {
+                Style::default().fg(Theme::fg_dim())
+            };
+            let worker = match s.pid {
+                Some(pid) => Cell::from(pid.to_string()),
+                None if s.worker_down() => Cell::from("down").style(warn),
+                None => Cell::from("-"),
+            };
+            // Age at the time of the snapshot, so replays read the same
+            let last_msg = s.last_msg_receipt_time.map_or_else(
+                || "-".into(),
+                |t| format_duration((snap.timestamp - t).num_milliseconds().max(0) as f64 / 1000.0),
+            );
+
+            Row::new(vec![
+                Cell::from(truncate(&s.subname, 24)),
+                Cell::from(if s.enabled { "yes" } else { "no" }).style(enabled_style),
+                worker,
+                Cell::from(s.relcount.to_string()),
+                Cell::from(format_lag(s.apply_lag_secs))
+                    .style(Style::default().fg(Theme::lag_color(s.apply_lag_secs))),
+                Cell::from(s.apply_lag_bytes.map_or_else(|| "-".into(), format_bytes)),
+                Cell::from(last_msg),
+                count_cell(s.apply_error_count),
+                count_cell(s.sync_error_count),
+                count_cell(s.conflict_count),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(16),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.logical_replication);
+    app.mouse_targets.table_at(area, true);
+}
@@ -0,0 +1,115 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Cell, Row};
+use ratatui::Frame;
+
+use crate::app::{App, BottomPanel, ViewMode};
+use crate::db::models::LogLevel;
+use crate::server_log::LevelFilter;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, styled_table};
+
+use super::panel_block;
+
+/// Widest prefix column; longer `log_line_prefix` output is cut
+const MAX_PREFIX_WIDTH: u16 = 36;
+
+fn level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Panic | LogLevel::Fatal | LogLevel::Error => Theme::border_danger(),
+        LogLevel::Warning => Theme::border_warn(),
+        LogLevel::Log | LogLevel::Notice | LogLevel::Info => Theme::fg(),
+        LogLevel::Debug
+        | LogLevel::Detail
+        | LogLevel::Hint
+        | LogLevel::Context
+        | LogLevel::Statement
+        | LogLevel::Other => Theme::fg_dim(),
+    }
+}
+
+pub fn render_logs(frame: &mut Frame, app: &mut App, area: Rect) {
+    let total_count = app.server_log.len();
+    let indices = app.log_indices();
+
+    let emoji = if app.show_emojis() { "📜 " } else { "" };
+    let levels = if app.log_filter == LevelFilter::All {
+        String::new()
+    } else {
+        format!(" · {}", app.log_filter.label())
+    };
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
+            && app.view_mode == ViewMode::Filter
+            && app.bottom_panel == BottomPanel::Logs)
+    {
+        format!(
+            "{emoji}Logs [{}/{total_count}]{levels} (filter: {})",
+            indices.len(),
+            app.filter().text
+        )
+    } else if levels.is_empty() {
+        format!("{emoji}Logs [{total_count}]")
+    } else {
+        format!("{emoji}Logs [{}/{total_count}]{levels}", indices.len())
+    };
+    let block = panel_block(&title);
+
+    if app.server_log.is_empty() {
+        let error = app.snapshot.as_ref().and_then(|s| s.server_log_error.as_deref());
+        let text = if app.is_replay_mode() {
+            "No server log lines in this recording".to_string()
+        } else if !app.config.server_log {
+            "Server log tailing is off; turn on Server Log Tail in the config (,)".to_string()
+        } else if let Some(error) = error {
+            format!("Cannot read the server log: {error}")
+        } else {
+            "Nothing logged yet".to_string()
+        };
+        frame.render_widget(empty_state(&text, block), area);
+        return;
+    }
+
+    let header = Row::new(vec![Cell::from("Prefix"), Cell::from("Level"), Cell::from("Message")])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let line = &app.server_log[i];
+            let color = level_color(line.level);
+            let mut level_style = Style::default().fg(color);
+            if matches!(line.level, LogLevel::Panic | LogLevel::Fatal | LogLevel::Error) {
+                level_style = level_style.add_modifier(Modifier::BOLD);
+            }
+            // Follow-up lines sit under their message, indented
+            let level = if line.level.is_followup() {
+                format!(" {}", line.level.label())
+            } else {
+                line.level.label().to_string()
+            };
+            Row::new(vec![
+                Cell::from(line.prefix.clone()).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(level).style(level_style),
+                Cell::from(line.message.clone()).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let prefix_width = indices
+        .iter()
+        .map(|&i| app.server_log[i].prefix.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_PREFIX_WIDTH as usize) as u16;
+    let widths = [
+        Constraint::Length(prefix_width.max(6)), // Prefix
+        Constraint::Length(11),                  // Level
+        Constraint::Min(30),                     // Message
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.logs);
+    app.mouse_targets.table_at(area, true);
+}
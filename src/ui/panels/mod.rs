@@ -1,8 +1,23 @@
+mod alerts;
+mod autovacuum;
+mod backends;
 mod blocking;
+mod buffer_cache;
+mod checkpoints;
+mod connection_groups;
 mod extensions;
+mod index_health;
 mod indexes;
+mod locks;
+mod logical_replication;
+mod logs;
+mod operations;
+mod pooler;
 mod replication;
+mod roles;
+mod sessions;
 mod settings;
+mod slots;
 mod statements;
 mod tables;
 mod vacuum;
@@ -10,11 +25,26 @@ mod wait_events;
 mod wal_io;
 mod wraparound;
 
+pub use alerts::render_alerts;
+pub use autovacuum::render_autovacuum;
+pub use backends::render_backends;
 pub use blocking::render_blocking;
+pub use buffer_cache::render_buffer_cache;
+pub use checkpoints::render_checkpoints;
+pub use connection_groups::render_connection_groups;
 pub use extensions::render_extensions;
+pub use index_health::render_index_health;
 pub use indexes::render_indexes;
+pub use locks::render_locks;
+pub use logical_replication::render_logical_replication;
+pub use logs::render_logs;
+pub use operations::render_operations;
+pub use pooler::render_pooler;
 pub use replication::render_replication;
+pub use roles::render_roles;
+pub use sessions::render_sessions;
 pub use settings::render_settings;
+pub use slots::render_slots;
 pub use statements::render_statements;
 pub use tables::render_table_stats;
 pub use vacuum::render_vacuum_progress;
@@ -25,6 +55,24 @@ pub use wraparound::render_wraparound;
 use ratatui::widgets::{Block, BorderType, Borders};
 
 use super::theme::Theme;
+use crate::db::models::PgSnapshot;
+
+/// Title suffix noting entries hidden (or shown) by the ignore list.
+pub fn ignored_suffix(ignored: usize, showing: bool) -> String {
+    match (ignored, showing) {
+        (0, _) => String::new(),
+        (n, false) => format!(" ({n} ignored)"),
+        (n, true) => format!(" (showing {n} ignored)"),
+    }
+}
+
+/// Title suffix for table and index stats carried over from an earlier
+/// refresh by the `[schedule]`.
+pub fn read_at_suffix(snap: Option<&PgSnapshot>) -> String {
+    snap.and_then(|s| s.relation_stats_read_at).map_or_else(String::new, |at| {
+        format!(" · read {}", at.with_timezone(&chrono::Local).format("%H:%M:%S"))
+    })
+}
 
 pub fn panel_block(title: &str) -> Block<'_> {
     Block::default()
@@ -0,0 +1,84 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table, truncate};
+
+use super::panel_block;
+
+/// Cells in the progress bar, before the percentage
+const BAR_WIDTH: usize = 10;
+
+fn progress_cell(pct: Option<f64>) -> Cell<'static> {
+    let Some(pct) = pct else {
+        return Cell::from("\u{2014}").style(Style::default().fg(Theme::fg_dim()));
+    };
+    let filled = ((pct / 100.0 * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    Cell::from(format!(
+        "{}{} {pct:>5.1}%",
+        "\u{2588}".repeat(filled),
+        "\u{2591}".repeat(BAR_WIDTH - filled)
+    ))
+    .style(Style::default().fg(Theme::border_ok()))
+}
+
+pub fn render_operations(frame: &mut Frame, app: &mut App, area: Rect) {
+    let emoji = if app.show_emojis() { "⏳ " } else { "" };
+    let operations = app
+        .snapshot
+        .as_ref()
+        .map_or_else(Vec::new, |s| s.operations_in_progress());
+    let title = format!("{emoji}Operations in Progress [{}]", operations.len());
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    if operations.is_empty() {
+        frame.render_widget(empty_state("No maintenance commands or backups running", block), area);
+        return;
+    }
+
+    let header = Row::new(vec!["PID", "Operation", "Target", "Phase", "Progress", "Elapsed", "ETA"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
+
+    let dash = || "\u{2014}".to_string();
+    let rows: Vec<Row> = operations
+        .iter()
+        .map(|op| {
+            Row::new(vec![
+                Cell::from(op.pid.to_string()),
+                Cell::from(truncate(&op.command, 26)),
+                Cell::from(truncate(&op.target, 32)),
+                Cell::from(truncate(&op.phase, 32)),
+                progress_cell(op.progress_pct()),
+                Cell::from(op.elapsed_secs.map_or_else(dash, format_duration)),
+                Cell::from(
+                    app.progress
+                        .eta_secs(op, snap.timestamp)
+                        .map_or_else(dash, |s| format!("~{}", format_duration(s))),
+                ),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(26),
+        Constraint::Min(20),
+        Constraint::Min(20),
+        Constraint::Length(18),
+        Constraint::Length(9),
+        Constraint::Length(9),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.operations);
+    app.mouse_targets.table_at(area, true);
+}
@@ -0,0 +1,118 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, format_time_ms, styled_table};
+
+use super::panel_block;
+
+pub fn render_pooler(frame: &mut Frame, app: &mut App, area: Rect) {
+    let emoji = if app.show_emojis() { "🏊 " } else { "" };
+    let pooler = app.snapshot.as_ref().and_then(|s| s.pooler.as_ref());
+    let title = match pooler {
+        Some(p) => format!("{emoji}Pooler \u{2014} {} [{}]", p.version, p.pools.len()),
+        None => format!("{emoji}Pooler"),
+    };
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+    let Some(pooler) = &snap.pooler else {
+        let text = snap
+            .pooler_error
+            .clone()
+            .unwrap_or_else(|| "Start with --pgbouncer to read PgBouncer's pools".into());
+        frame.render_widget(empty_state(&text, block), area);
+        return;
+    };
+    if pooler.pools.is_empty() {
+        frame.render_widget(empty_state("PgBouncer has no pools yet", block), area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+
+    let sum = |f: fn(&crate::db::models::PoolerPool) -> i64| pooler.pools.iter().map(f).sum::<i64>();
+    let waiting = pooler.clients_waiting();
+    let label = |text: &str| Span::styled(text.to_string(), Style::default().fg(Theme::fg_dim()));
+    let value = |n: i64, color| {
+        Span::styled(n.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD))
+    };
+    let totals = Line::from(vec![
+        label(" clients "),
+        value(sum(|p| p.cl_active), Theme::fg()),
+        label(" active · "),
+        value(waiting, if waiting > 0 { Theme::border_warn() } else { Theme::fg() }),
+        label(" waiting   servers "),
+        value(sum(|p| p.sv_active), Theme::fg()),
+        label(" active · "),
+        value(sum(|p| p.sv_idle), Theme::fg()),
+        label(" idle"),
+    ]);
+    frame.render_widget(Paragraph::new(totals), parts[0]);
+
+    let header = Row::new(vec![
+        "Database", "User", "Mode", "Cl act", "Cl wait", "Sv act", "Sv idle", "Sv used", "Max wait", "Avg query",
+        "Avg xact", "Avg wait",
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let micros = |us: i64| format_time_ms(us as f64 / 1000.0);
+    let rows: Vec<Row> = pooler
+        .pools
+        .iter()
+        .map(|p| {
+            let wait_color = if p.cl_waiting > 0 { Theme::border_warn() } else { Theme::fg_dim() };
+            let maxwait_color = if p.maxwait_secs >= 1.0 {
+                Theme::border_danger()
+            } else {
+                Theme::fg_dim()
+            };
+            Row::new(vec![
+                Cell::from(p.database.clone()),
+                Cell::from(p.user.clone()).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(p.pool_mode.clone()).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(p.cl_active.to_string()),
+                Cell::from(p.cl_waiting.to_string()).style(Style::default().fg(wait_color)),
+                Cell::from(p.sv_active.to_string()),
+                Cell::from(p.sv_idle.to_string()),
+                Cell::from(p.sv_used.to_string()),
+                Cell::from(format_duration(p.maxwait_secs)).style(Style::default().fg(maxwait_color)),
+                Cell::from(micros(p.avg_query_us)),
+                Cell::from(micros(p.avg_xact_us)),
+                Cell::from(micros(p.avg_wait_us)).style(Style::default().fg(Theme::fg_dim())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Length(12),
+        Constraint::Length(11),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+
+    let table = styled_table(rows, widths, header, Block::default());
+    frame.render_stateful_widget(table, parts[1], &mut app.panels.pooler);
+    app.mouse_targets.table_at(parts[1], false);
+}
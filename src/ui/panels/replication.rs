@@ -1,5 +1,5 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Cell, Paragraph, Row, Table};
 use ratatui::Frame;
@@ -11,7 +11,7 @@ use crate::ui::util::{empty_state, format_bytes, format_lag, truncate};
 use super::panel_block;
 
 pub fn render_replication(frame: &mut Frame, app: &mut App, area: Rect) {
-    let emoji = if app.config.show_emojis { "🔄 " } else { "" };
+    let emoji = if app.show_emojis() { "🔄 " } else { "" };
     let title = format!("{emoji}Replication");
     let block = panel_block(&title);
 
@@ -156,6 +156,7 @@ fn render_physical_replication(
         .highlight_symbol("\u{25ba} ");
 
     frame.render_stateful_widget(table, table_area, &mut app.panels.replication);
+    app.mouse_targets.table_at(table_area, false);
 }
 
 fn render_replication_slots(
@@ -195,12 +196,7 @@ fn render_replication_slots(
                 Style::default().fg(Theme::border_warn())
             };
 
-            // Color WAL retained based on size
-            let retained_color = match s.wal_retained_bytes {
-                Some(bytes) if bytes > 10 * 1024 * 1024 * 1024 => Theme::border_danger(), // >10GB
-                Some(bytes) if bytes > 1024 * 1024 * 1024 => Theme::border_warn(),        // >1GB
-                _ => Theme::fg(),
-            };
+            let retained_color = retained_color(s.wal_retained_bytes);
 
             Row::new(vec![
                 Cell::from(truncate(&s.slot_name, 20)),
@@ -227,7 +223,16 @@ fn render_replication_slots(
     frame.render_widget(table, table_area);
 }
 
-fn render_subscriptions(
+/// Color WAL retained by a slot based on size.
+pub(super) fn retained_color(bytes: Option<i64>) -> Color {
+    match bytes {
+        Some(bytes) if bytes > 10 * 1024 * 1024 * 1024 => Theme::border_danger(), // >10GB
+        Some(bytes) if bytes > 1024 * 1024 * 1024 => Theme::border_warn(),        // >1GB
+        _ => Theme::fg(),
+    }
+}
+
+pub(super) fn render_subscriptions(
     frame: &mut Frame,
     subscriptions: &[crate::db::models::Subscription],
     area: Rect,
@@ -0,0 +1,147 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Cell, Row};
+use ratatui::Frame;
+
+use crate::app::{App, BottomPanel, ViewMode};
+use crate::ui::theme::Theme;
+use crate::ui::util::{compute_match_indices, empty_state, highlight_matches, styled_table};
+
+use super::panel_block;
+
+pub fn render_roles(frame: &mut Frame, app: &mut App, area: Rect) {
+    let total_count = app.server_info.roles.len();
+    let indices = app.sorted_role_indices();
+
+    let emoji = if app.show_emojis() { "👤 " } else { "" };
+    let is_filtering = app.filter().active
+        || (!app.filter().text.is_empty()
+            && app.view_mode == ViewMode::Filter
+            && app.bottom_panel == BottomPanel::Roles);
+    let title = if is_filtering {
+        format!(
+            "{emoji}Roles [{}/{total_count}] (filter: {})",
+            indices.len(),
+            app.filter().text
+        )
+    } else {
+        format!("{emoji}Roles [{total_count}]")
+    };
+    let block = panel_block(&title);
+
+    if app.server_info.roles.is_empty() {
+        frame.render_widget(empty_state("No roles visible", block), area);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from("Role"),
+        Cell::from("Attributes"),
+        Cell::from("Conn Limit"),
+        Cell::from("Valid Until"),
+        Cell::from("Member Of"),
+        Cell::from("Connections"),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    // Expiry is judged against the snapshot so replays read as they did live
+    let now = app.snapshot.as_ref().map(|s| s.timestamp);
+    let filter_text = &app.filter().text;
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let role = &app.server_info.roles[i];
+
+            let name_color = if role.superuser {
+                Theme::border_warn()
+            } else if role.login {
+                Theme::border_active()
+            } else {
+                Theme::fg_dim()
+            };
+            let name_style = Style::default().fg(name_color).add_modifier(Modifier::BOLD);
+            let match_indices = if is_filtering {
+                compute_match_indices(&role.name, filter_text)
+            } else {
+                None
+            };
+            let name_cell = match_indices.as_ref().map_or_else(
+                || Cell::from(role.name.clone()).style(name_style),
+                |indices| Cell::from(Line::from(highlight_matches(&role.name, indices, name_style))),
+            );
+
+            let attributes = role.attributes();
+            let attributes = if attributes.is_empty() {
+                "-".to_string()
+            } else {
+                attributes.join(" ")
+            };
+
+            let connections = app.role_connections(&role.name);
+            let total: i64 = connections.iter().map(|(_, n)| n).sum();
+            let conn_limit = if role.conn_limit < 0 {
+                Cell::from("-").style(Style::default().fg(Theme::fg_dim()))
+            } else {
+                let style = if total >= i64::from(role.conn_limit) {
+                    Style::default().fg(Theme::border_warn())
+                } else {
+                    Style::default()
+                };
+                Cell::from(role.conn_limit.to_string()).style(style)
+            };
+
+            let valid_until = match role.valid_until {
+                None => Cell::from("-").style(Style::default().fg(Theme::fg_dim())),
+                Some(until) => {
+                    let expired = now.is_some_and(|now| until <= now);
+                    let style = if expired {
+                        Style::default().fg(Theme::border_danger())
+                    } else {
+                        Style::default()
+                    };
+                    Cell::from(until.format("%Y-%m-%d").to_string()).style(style)
+                }
+            };
+
+            let member_of = if role.member_of.is_empty() {
+                "-".to_string()
+            } else {
+                role.member_of.join(", ")
+            };
+            let connections = if connections.is_empty() {
+                "-".to_string()
+            } else {
+                connections
+                    .iter()
+                    .map(|(db, n)| format!("{db} {n}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            Row::new(vec![
+                name_cell,
+                Cell::from(attributes),
+                conn_limit,
+                valid_until,
+                Cell::from(member_of).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(connections),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(18), // Role
+        Constraint::Min(32),    // Attributes
+        Constraint::Length(10), // Conn Limit
+        Constraint::Length(11), // Valid Until
+        Constraint::Length(24), // Member Of
+        Constraint::Min(20),    // Connections
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.roles);
+    app.mouse_targets.table_at(area, true);
+}
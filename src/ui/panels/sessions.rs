@@ -0,0 +1,116 @@
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::{App, SessionSortColumn};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_duration, styled_table};
+
+use super::panel_block;
+
+/// A time cell, dimmed at zero and colored `color` otherwise.
+fn time_cell(secs: f64, color: ratatui::style::Color) -> Cell<'static> {
+    if secs > 0.0 {
+        Cell::from(format_duration(secs)).style(Style::default().fg(color))
+    } else {
+        Cell::from("-").style(Style::default().fg(Theme::fg_dim()))
+    }
+}
+
+pub fn render_sessions(frame: &mut Frame, app: &mut App, area: Rect) {
+    let rows_data = app.session_rows();
+
+    let emoji = if app.show_emojis() { "🕒 " } else { "" };
+    let title = format!("{emoji}Sessions [{}] (sampled this session)", rows_data.len());
+    let block = panel_block(&title);
+
+    if app.snapshot.is_none() {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    }
+
+    if rows_data.is_empty() {
+        frame.render_widget(empty_state("No client sessions", block), area);
+        return;
+    }
+
+    let sort_indicator = |col: SessionSortColumn| -> &str {
+        if app.panels.sessions.sort_column == col {
+            if app.panels.sessions.sort_ascending {
+                " \u{2191}"
+            } else {
+                " \u{2193}"
+            }
+        } else {
+            ""
+        }
+    };
+
+    let header = Row::new(vec![
+        Cell::from("PID"),
+        Cell::from("User"),
+        Cell::from("Database"),
+        Cell::from("Application"),
+        Cell::from("State"),
+        Cell::from("Observed"),
+        Cell::from(format!("Active{}", sort_indicator(SessionSortColumn::ActiveTime))),
+        Cell::from("Active %"),
+        Cell::from(format!("Idle in Txn{}", sort_indicator(SessionSortColumn::IdleInTxnTime))),
+        Cell::from(format!("Xacts{}", sort_indicator(SessionSortColumn::Xacts))),
+        Cell::from(format!("Queries{}", sort_indicator(SessionSortColumn::Queries))),
+        Cell::from(format!("Changes{}", sort_indicator(SessionSortColumn::StateChanges))),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let dash = || "-".to_string();
+    let rows: Vec<Row> = rows_data
+        .iter()
+        .map(|s| {
+            let pct = s.active_pct();
+            let pct_color = if pct >= 80.0 {
+                Theme::border_danger()
+            } else if pct >= 50.0 {
+                Theme::border_warn()
+            } else {
+                Theme::fg()
+            };
+            Row::new(vec![
+                Cell::from(s.pid.to_string()),
+                Cell::from(s.usename.clone().unwrap_or_else(dash)),
+                Cell::from(s.datname.clone().unwrap_or_else(dash)),
+                Cell::from(s.application_name.clone().filter(|a| !a.is_empty()).unwrap_or_else(dash))
+                    .style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(s.state.clone().unwrap_or_else(dash))
+                    .style(Style::default().fg(Theme::state_color(s.state.as_deref()))),
+                Cell::from(format_duration(s.observed_secs)).style(Style::default().fg(Theme::fg_dim())),
+                time_cell(s.active_secs, Theme::border_ok()),
+                Cell::from(format!("{pct:.0}%")).style(Style::default().fg(pct_color)),
+                time_cell(s.idle_in_txn_secs, Theme::border_warn()),
+                Cell::from(s.xacts.to_string()),
+                Cell::from(s.queries.to_string()),
+                Cell::from(s.state_changes.to_string()).style(Style::default().fg(Theme::fg_dim())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Min(12),
+        Constraint::Length(14),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(13),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.sessions.state);
+    app.mouse_targets.table_at(area, true);
+}
@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{Cell, Row};
@@ -10,21 +10,41 @@ use crate::ui::util::{compute_match_indices, empty_state, highlight_matches, sty
 
 use super::panel_block;
 
+/// Most rows the overrides section takes before it scrolls off.
+const MAX_OVERRIDE_ROWS: u16 = 8;
+
 pub fn render_settings(frame: &mut Frame, app: &mut App, area: Rect) {
+    let area = if app.server_info.role_settings.is_empty() {
+        area
+    } else {
+        // Overrides get the bottom of the panel, up to a third of it
+        let wanted = app.server_info.role_settings.len() as u16 + 3;
+        let height = wanted.min(MAX_OVERRIDE_ROWS + 3).min(area.height / 3);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(height)])
+            .split(area);
+        render_role_settings(frame, app, chunks[1]);
+        chunks[0]
+    };
+
     let total_count = app.server_info.settings.len();
     let indices = app.sorted_settings_indices();
     let filtered_count = indices.len();
 
-    let emoji = if app.config.show_emojis { "⚙️ " } else { "" };
-    let title = if app.filter.active
-        || (!app.filter.text.is_empty()
+    let emoji = if app.show_emojis() { "⚙️ " } else { "" };
+    let changed = if app.settings_changed_only { " changed" } else { "" };
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Settings)
     {
         format!(
-            "{emoji}Settings [{}/{}] (filter: {})",
-            filtered_count, total_count, app.filter.text
+            "{emoji}Settings [{}/{}]{changed} (filter: {})",
+            filtered_count, total_count, app.filter().text
         )
+    } else if app.settings_changed_only {
+        format!("{emoji}Settings [{filtered_count}/{total_count}]{changed}")
     } else {
         format!("{emoji}Settings [{total_count}]")
     };
@@ -47,11 +67,11 @@ pub fn render_settings(frame: &mut Frame, app: &mut App, area: Rect) {
     .bottom_margin(0);
 
     // Check if filtering is active
-    let is_filtering = app.filter.active
-        || (!app.filter.text.is_empty()
+    let is_filtering = app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Settings);
-    let filter_text = &app.filter.text;
+    let filter_text = &app.filter().text;
 
     let rows: Vec<Row> = indices
         .iter()
@@ -59,7 +79,7 @@ pub fn render_settings(frame: &mut Frame, app: &mut App, area: Rect) {
             let s = &app.server_info.settings[i];
 
             // Highlight non-default values
-            let is_non_default = s.source != "default";
+            let is_non_default = s.is_changed();
             let needs_restart = s.context == "postmaster" && is_non_default;
             let pending = s.pending_restart;
 
@@ -143,4 +163,46 @@ pub fn render_settings(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.settings);
+    app.mouse_targets.table_at(area, true);
+}
+
+/// `ALTER ROLE/DATABASE ... SET` overrides, which win over the values above
+/// for the sessions they match.
+fn render_role_settings(frame: &mut Frame, app: &App, area: Rect) {
+    let total_count = app.server_info.role_settings.len();
+    let indices = app.sorted_role_settings_indices();
+    let title = if indices.len() == total_count {
+        format!("Per-role / per-database overrides [{total_count}]")
+    } else {
+        format!("Per-role / per-database overrides [{}/{total_count}]", indices.len())
+    };
+    let block = panel_block(&title);
+
+    let header = Row::new(vec![
+        Cell::from("Name"),
+        Cell::from("Value"),
+        Cell::from("Applies to"),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let o = &app.server_info.role_settings[i];
+            Row::new(vec![
+                Cell::from(o.name.clone()).style(Style::default().fg(Theme::border_active())),
+                Cell::from(o.value.clone()).style(Style::default().fg(Theme::border_warn())),
+                Cell::from(o.scope()).style(Style::default().fg(Theme::fg_dim())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Min(30),      // Name
+        Constraint::Min(25),      // Value
+        Constraint::Min(30),      // Applies to
+    ];
+
+    frame.render_widget(styled_table(rows, widths, header, block), area);
 }
@@ -0,0 +1,136 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Cell, Paragraph, Row};
+use ratatui::Frame;
+
+use crate::app::{App, BottomPanel, SlotSortColumn, ViewMode};
+use crate::ui::theme::Theme;
+use crate::ui::util::{empty_state, format_bytes, format_compact, styled_table, truncate};
+
+use super::panel_block;
+use super::replication::{render_subscriptions, retained_color};
+
+pub fn render_slots(frame: &mut Frame, app: &mut App, area: Rect) {
+    let indices = app.sorted_slot_indices();
+    let (total, inactive) = app.snapshot.as_ref().map_or((0, 0), |s| {
+        let slots = &s.replication_slots;
+        (slots.len(), slots.iter().filter(|s| !s.active).count())
+    });
+
+    let emoji = if app.show_emojis() { "🎰 " } else { "" };
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
+            && app.view_mode == ViewMode::Filter
+            && app.bottom_panel == BottomPanel::Slots)
+    {
+        format!(
+            "{emoji}Replication Slots [{}/{total}, {inactive} inactive] (filter: {})",
+            indices.len(),
+            app.filter().text
+        )
+    } else {
+        format!("{emoji}Replication Slots [{total}, {inactive} inactive]")
+    };
+    let block = panel_block(&title);
+
+    let Some(snap) = &app.snapshot else {
+        frame.render_widget(Paragraph::new("No data").block(block), area);
+        return;
+    };
+
+    // Subscriptions on this server get a read-only section underneath
+    let subscriptions = snap.subscriptions.clone();
+    let (slots_area, subs_area) = if subscriptions.is_empty() {
+        (area, None)
+    } else {
+        let subs_height = (subscriptions.len() as u16 + 2).min(7);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(subs_height)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    };
+
+    if let Some(subs_area) = subs_area {
+        render_subscriptions(frame, &subscriptions, subs_area);
+    }
+
+    if snap.replication_slots.is_empty() {
+        frame.render_widget(empty_state("No replication slots", block), slots_area);
+        return;
+    }
+
+    let sort_indicator = |col: SlotSortColumn| -> &str {
+        if app.panels.slots.sort_column == col {
+            if app.panels.slots.sort_ascending {
+                " \u{2191}"
+            } else {
+                " \u{2193}"
+            }
+        } else {
+            ""
+        }
+    };
+
+    let header = Row::new(vec![
+        Cell::from(format!("Slot{}", sort_indicator(SlotSortColumn::Name))),
+        Cell::from("Type"),
+        Cell::from("Database"),
+        Cell::from(format!("Active{}", sort_indicator(SlotSortColumn::Active))),
+        Cell::from(format!("WAL Retained{}", sort_indicator(SlotSortColumn::Retained))),
+        Cell::from("Spill Txns"),
+        Cell::from(format!("Spill Bytes{}", sort_indicator(SlotSortColumn::Spill))),
+        Cell::from("Restart LSN"),
+    ])
+    .style(Theme::title_style())
+    .bottom_margin(0);
+
+    let rows: Vec<Row> = indices
+        .iter()
+        .map(|&i| {
+            let s = &snap.replication_slots[i];
+            let (active, active_style) = if s.active {
+                ("yes", Style::default().fg(Theme::border_ok()))
+            } else {
+                (
+                    "no",
+                    Style::default()
+                        .fg(Theme::border_warn())
+                        .add_modifier(Modifier::BOLD),
+                )
+            };
+            let kind = if s.temporary {
+                format!("{} (temp)", s.slot_type)
+            } else {
+                s.slot_type.clone()
+            };
+
+            Row::new(vec![
+                Cell::from(truncate(&s.slot_name, 24)),
+                Cell::from(kind),
+                Cell::from(s.database.clone().unwrap_or_else(|| "-".into())),
+                Cell::from(active).style(active_style),
+                Cell::from(s.wal_retained_bytes.map_or_else(|| "-".into(), format_bytes))
+                    .style(Style::default().fg(retained_color(s.wal_retained_bytes))),
+                Cell::from(s.spill_txns.map_or_else(|| "-".into(), format_compact)),
+                Cell::from(s.spill_bytes.map_or_else(|| "-".into(), format_bytes)),
+                Cell::from(s.restart_lsn.clone().unwrap_or_else(|| "-".into())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(24),
+        Constraint::Length(16),
+        Constraint::Length(14),
+        Constraint::Length(8),
+        Constraint::Length(14),
+        Constraint::Length(11),
+        Constraint::Length(13),
+        Constraint::Min(14),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, slots_area, &mut app.panels.slots.state);
+    app.mouse_targets.table_at(slots_area, true);
+}
@@ -5,11 +5,14 @@ use ratatui::widgets::{Cell, Paragraph, Row};
 use ratatui::Frame;
 
 use crate::app::{App, BottomPanel, StatementSortColumn, ViewMode};
+use crate::db::models::StatStatement;
+use crate::row_colors::row_color;
+use crate::ui::layout::{shown_columns, LayoutTier};
 use crate::ui::overlay::highlight_sql_inline;
 use crate::ui::theme::Theme;
-use crate::ui::util::{compute_match_indices, empty_state, format_compact, format_time_ms, highlight_matches, styled_table};
+use crate::ui::util::{change_badge, compute_match_indices, empty_state, format_compact, format_time_ms, highlight_matches, shown_only, styled_table};
 
-use super::panel_block;
+use super::{ignored_suffix, panel_block};
 
 pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
     let total_count = app
@@ -19,18 +22,35 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
     let indices = app.sorted_stmt_indices();
     let filtered_count = indices.len();
 
-    let emoji = if app.config.show_emojis { "📝 " } else { "" };
-    let title = if app.filter.active
-        || (!app.filter.text.is_empty()
+    let emoji = if app.show_emojis() { "📝 " } else { "" };
+    let ignored = ignored_suffix(app.ignored_statement_count(), app.show_ignored);
+    let regressed = app.snapshot.as_ref().map_or(0, |s| {
+        s.stat_statements
+            .iter()
+            .filter(|st| app.statement_regression(st).is_some())
+            .count()
+    });
+    let regressed = if regressed > 0 {
+        format!(" · {regressed} regressed")
+    } else {
+        String::new()
+    };
+    let title = if app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Statements)
     {
         format!(
-            "{emoji}Statements [{}/{}] (filter: {})",
-            filtered_count, total_count, app.filter.text
+            "{emoji}Statements [{}/{}] (filter: {}){ignored}{regressed}",
+            filtered_count, total_count, app.filter().text
         )
     } else {
-        format!("{emoji}Statements [{total_count}]")
+        format!("{emoji}Statements [{total_count}]{ignored}{regressed}")
+    };
+    let title = if app.snapshot.as_ref().is_some_and(|s| s.statement_monitor.is_some()) {
+        format!("{title} · pg_stat_monitor")
+    } else {
+        title
     };
 
     let block = panel_block(&title);
@@ -40,7 +60,7 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     };
 
-    if !snap.extensions.pg_stat_statements {
+    if !snap.extensions.pg_stat_statements && !snap.extensions.pg_stat_monitor {
         let lines = vec![
             Line::from(""),
             Line::from(Span::styled(
@@ -119,7 +139,17 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    let header = Row::new(vec![
+    // P95 and Errors come from pg_stat_monitor only
+    let monitor = snap.statement_monitor.as_ref();
+    let mut shown = shown_columns(app, BottomPanel::Statements, area.width);
+    if monitor.is_none() {
+        shown[5] = false;
+        shown[12] = false;
+    }
+    // Min and Rows/Call only when there's room to spare; they aren't in the chooser
+    let wide = LayoutTier::for_width(area.width) == LayoutTier::Wide;
+    shown.extend([wide, wide]);
+    let header = Row::new(shown_only([
         Cell::from("Query"),
         Cell::from(format!(
             "Calls{}",
@@ -137,6 +167,7 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
             "Max{}",
             sort_indicator(StatementSortColumn::MaxTime)
         )),
+        Cell::from("P95"),
         Cell::from(format!(
             "Stddev{}",
             sort_indicator(StatementSortColumn::Stddev)
@@ -161,49 +192,89 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
             "Temp{}",
             sort_indicator(StatementSortColumn::Temp)
         )),
-    ])
+        Cell::from("Errors"),
+        Cell::from("Min"),
+        Cell::from("Rows/Call"),
+    ], &shown))
     .style(Theme::title_style())
     .bottom_margin(0);
 
-    // Calculate query column width: area width - borders - highlight symbol - fixed columns
-    // Fixed columns: 7+9+9+9+8+7+5+7+9+7 = 77
-    let query_width = (area.width as usize).saturating_sub(2 + 2 + 77).max(20);
+    let widths = shown_only([
+        Constraint::Fill(1),
+        Constraint::Length(7),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(5),
+        Constraint::Length(7),
+        Constraint::Length(9),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Length(9),
+        Constraint::Length(9),
+    ], &shown);
+
+    // Calculate query column width: area width - borders - highlight symbol - shown fixed columns
+    let fixed: u16 = widths
+        .iter()
+        .map(|c| match c {
+            Constraint::Length(n) => *n,
+            _ => 0,
+        })
+        .sum();
+    let query_width = (area.width as usize).saturating_sub(2 + 2 + usize::from(fixed)).max(20);
 
     // Check if filtering is active
-    let is_filtering = app.filter.active
-        || (!app.filter.text.is_empty()
+    let is_filtering = app.filter().active
+        || (!app.filter().text.is_empty()
             && app.view_mode == ViewMode::Filter
             && app.bottom_panel == BottomPanel::Statements);
-    let filter_text = &app.filter.text;
+    let filter_text = &app.filter().text;
 
     let rows: Vec<Row> = indices
         .iter()
         .map(|&i| {
             let stmt = &snap.stat_statements[i];
-            let hit_color = Theme::hit_ratio_color(stmt.hit_ratio);
+            // A matching `[[row_colors]]` rule paints the whole row
+            let rule_color = row_color(&app.config.row_colors, stmt).map(Theme::row_color);
+            let paint = |color| rule_color.unwrap_or(color);
+            let hit_color = paint(Theme::hit_ratio_color(stmt.hit_ratio));
             // Max time: orange if >2x mean (indicates spiky query)
-            let max_color = if stmt.max_exec_time > stmt.mean_exec_time * 2.0 {
+            let max_color = paint(if stmt.max_exec_time > stmt.mean_exec_time * 2.0 {
                 Theme::border_warn()
             } else {
                 Theme::fg()
-            };
-            let reads_color = if stmt.shared_blks_read > 1000 {
+            });
+            let reads_color = paint(if stmt.shared_blks_read > 1000 {
                 Theme::border_warn()
             } else {
                 Theme::fg()
-            };
+            });
             let io_time = stmt.blk_read_time + stmt.blk_write_time;
-            let io_color = if io_time > 1000.0 {
+            let io_color = paint(if io_time > 1000.0 {
                 Theme::border_warn()
             } else {
                 Theme::fg()
-            };
+            });
             let temp_total = stmt.temp_blks_read + stmt.temp_blks_written;
-            let temp_color = if temp_total > 0 {
+            let monitored = monitor.and_then(|m| m.get(stmt.queryid).map(|ms| (m, ms)));
+            let p95 = monitored
+                .and_then(|(m, ms)| m.percentile(ms, 95.0))
+                .map_or_else(|| "-".to_string(), format_time_ms);
+            let errors = monitored.map_or(0, |(_, ms)| ms.errors);
+            let errors_color = paint(if errors > 0 {
+                Theme::border_danger()
+            } else {
+                Theme::fg()
+            });
+            let temp_color = paint(if temp_total > 0 {
                 Theme::border_warn()
             } else {
                 Theme::fg()
-            };
+            });
 
             // Compute match indices if filtering
             let match_indices = if is_filtering {
@@ -212,33 +283,70 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
                 None
             };
 
+            // Starred and regressed statements get badges in front of the query
+            let starred = app.starred.is_starred(stmt.queryid);
+            let regression = app.statement_regression(stmt).map(|ratio| format!("▲{ratio:.1}× "));
+            let change = app.highlighted_changes().and_then(|c| c.statement(stmt.queryid));
+            let badge_width = if starred { 2 } else { 0 }
+                + if change.is_some() { 2 } else { 0 }
+                + regression.as_ref().map_or(0, |b| b.chars().count());
+            let text_width = query_width.saturating_sub(badge_width);
+
             // For statements, filter string is just the query
-            let query_cell = match_indices.map_or_else(
-                || Cell::from(Line::from(highlight_sql_inline(&stmt.query, query_width))),
+            let mut spans = match_indices.map_or_else(
+                || highlight_sql_inline(&stmt.query, text_width),
                 |indices| {
                     // Truncate query for display
-                    let display_text = if stmt.query.len() > query_width {
-                        format!("{}…", &stmt.query[..query_width.saturating_sub(1)])
+                    let display_text = if stmt.query.len() > text_width {
+                        format!("{}…", &stmt.query[..text_width.saturating_sub(1)])
                     } else {
                         stmt.query.clone()
                     };
 
-                    let spans = highlight_matches(
+                    highlight_matches(
                         &display_text,
                         &indices,
                         Style::default().fg(Theme::fg()),
-                    );
-                    Cell::from(Line::from(spans))
+                    )
                 },
             );
+            if let Some(color) = rule_color {
+                for span in &mut spans {
+                    span.style = span.style.fg(color);
+                }
+            }
+            let regressed = regression.is_some();
+            if let Some(badge) = regression {
+                spans.insert(
+                    0,
+                    Span::styled(
+                        badge,
+                        Style::default().fg(Theme::border_danger()).add_modifier(Modifier::BOLD),
+                    ),
+                );
+            }
+            if starred {
+                spans.insert(0, Span::styled("★ ", Style::default().fg(Theme::border_warn())));
+            }
+            if let Some(change) = change {
+                spans.insert(0, change_badge(change));
+            }
+            let mean_style = if regressed {
+                Style::default().fg(Theme::border_danger()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let query_cell = Cell::from(Line::from(spans));
+            let ignored = app.config.ignore.is_statement_ignored(stmt.queryid);
 
-            Row::new(vec![
+            let row = Row::new(shown_only([
                 query_cell,
                 Cell::from(format_compact(stmt.calls)),
                 Cell::from(format_time_ms(stmt.total_exec_time)),
-                Cell::from(format_time_ms(stmt.mean_exec_time)),
+                Cell::from(format_time_ms(stmt.mean_exec_time)).style(mean_style),
                 Cell::from(format_time_ms(stmt.max_exec_time))
                     .style(Style::default().fg(max_color)),
+                Cell::from(p95),
                 Cell::from(format_time_ms(stmt.stddev_exec_time)),
                 Cell::from(format_compact(stmt.rows)),
                 Cell::from(format!("{:.0}%", stmt.hit_ratio * 100.0))
@@ -249,24 +357,30 @@ pub fn render_statements(frame: &mut Frame, app: &mut App, area: Rect) {
                     .style(Style::default().fg(io_color)),
                 Cell::from(format_compact(temp_total))
                     .style(Style::default().fg(temp_color)),
-            ])
+                Cell::from(format_compact(errors)).style(Style::default().fg(errors_color)),
+                Cell::from(format_time_ms(stmt.min_exec_time)),
+                Cell::from(rows_per_call(stmt)),
+            ], &shown));
+            let mut style = rule_color.map_or_else(Style::default, |color| Style::default().fg(color));
+            if ignored {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if change.is_some() {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            row.style(style)
         })
         .collect();
 
-    let widths = [
-        Constraint::Fill(1),
-        Constraint::Length(7),
-        Constraint::Length(9),
-        Constraint::Length(9),
-        Constraint::Length(9),
-        Constraint::Length(8),
-        Constraint::Length(7),
-        Constraint::Length(5),
-        Constraint::Length(7),
-        Constraint::Length(9),
-        Constraint::Length(7),
-    ];
-
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.statements.state);
+    app.mouse_targets.table_at(area, true);
+}
+
+fn rows_per_call(stmt: &StatStatement) -> String {
+    if stmt.calls > 0 {
+        format!("{:.1}", stmt.rows as f64 / stmt.calls as f64)
+    } else {
+        "-".into()
+    }
 }
@@ -1,15 +1,17 @@
 use ratatui::layout::{Constraint, Rect};
-use ratatui::style::Style;
-use ratatui::text::Line;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Cell, Paragraph, Row};
 use ratatui::Frame;
 
 use crate::app::{App, BottomPanel, TableStatSortColumn, ViewMode};
+use crate::row_colors::row_color;
 use crate::db::models::BloatSource;
+use crate::ui::layout::shown_columns;
 use crate::ui::theme::Theme;
-use crate::ui::util::{compute_match_indices, empty_state, format_bytes, highlight_matches, styled_table};
+use crate::ui::util::{change_badge, compute_match_indices, empty_state, format_bytes, highlight_matches, shown_only, styled_table};
 
-use super::panel_block;
+use super::{ignored_suffix, panel_block, read_at_suffix};
 
 pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
     let indices = app.sorted_table_stat_indices();
@@ -18,8 +20,10 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
         .as_ref()
         .map_or(0, |s| s.table_stats.len());
 
-    let emoji = if app.config.show_emojis { "📋 " } else { "" };
-    let title = format!("{emoji}Table Stats [{total_count}]");
+    let emoji = if app.show_emojis() { "📋 " } else { "" };
+    let ignored = ignored_suffix(app.ignored_table_count(), app.show_ignored);
+    let read_at = read_at_suffix(app.snapshot.as_ref());
+    let title = format!("{emoji}Table Stats [{total_count}]{ignored}{read_at}");
     let block = panel_block(&title);
 
     let Some(snap) = &app.snapshot else {
@@ -44,7 +48,8 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
         }
     };
 
-    let header = Row::new(vec![
+    let shown = shown_columns(app, BottomPanel::TableStats, area.width);
+    let header = Row::new(shown_only([
         Cell::from(format!("Table{}", sort_indicator(TableStatSortColumn::Name))),
         Cell::from(format!("Size{}", sort_indicator(TableStatSortColumn::Size))),
         Cell::from(format!("SeqScan{}", sort_indicator(TableStatSortColumn::SeqScan))),
@@ -53,21 +58,22 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
         Cell::from(format!("Dead%{}", sort_indicator(TableStatSortColumn::DeadRatio))),
         Cell::from("Bloat[b]"),
         Cell::from("Last Vacuum"),
-    ])
+    ], &shown))
     .style(Theme::title_style())
     .bottom_margin(0);
 
     // Check if filtering is active
     let is_filtering = app.bottom_panel == BottomPanel::TableStats
-        && !app.filter.text.is_empty()
-        && (app.filter.active || app.view_mode == ViewMode::Filter);
-    let filter_text = &app.filter.text;
+        && !app.filter().text.is_empty()
+        && (app.filter().active || app.view_mode == ViewMode::Filter);
+    let filter_text = &app.filter().text;
 
     let rows: Vec<Row> = indices
         .iter()
         .map(|&i| {
             let t = &snap.table_stats[i];
-            let dead_color = Theme::dead_ratio_color(t.dead_ratio);
+            let rule_color = row_color(&app.config.row_colors, t).map(Theme::row_color);
+            let dead_color = rule_color.unwrap_or_else(|| Theme::dead_ratio_color(t.dead_ratio));
             let table_name = format!("{}.{}", t.schemaname, &t.relname);
 
             // Compute match indices if filtering
@@ -77,31 +83,34 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
                 None
             };
 
-            let table_cell = if let Some(indices) = match_indices {
-                let spans = highlight_matches(
+            let mut spans = match match_indices {
+                Some(indices) => highlight_matches(
                     &table_name,
                     &indices,
                     Style::default().fg(Theme::fg()),
-                );
-                Cell::from(Line::from(spans))
-            } else {
-                Cell::from(table_name)
+                ),
+                None => vec![Span::raw(table_name)],
             };
+            let change = app.highlighted_changes().and_then(|c| c.table(t));
+            if let Some(change) = change {
+                spans.insert(0, change_badge(change));
+            }
+            let table_cell = Cell::from(Line::from(spans));
 
             let bloat_cell = t.bloat_pct.map_or_else(
                 || Cell::from("-"),
                 |pct| {
-                    let color = Theme::bloat_color(pct);
+                    let color = rule_color.unwrap_or_else(|| Theme::bloat_color(pct));
                     // Show ~ prefix for estimated values (non-pgstattuple)
                     let prefix = match t.bloat_source {
-                        Some(BloatSource::Pgstattuple) => "",
+                        Some(BloatSource::Pgstattuple | BloatSource::PgstattupleExact) => "",
                         _ => "~",
                     };
                     Cell::from(format!("{prefix}{pct:.1}%")).style(Style::default().fg(color))
                 },
             );
 
-            Row::new(vec![
+            let row = Row::new(shown_only([
                 table_cell,
                 Cell::from(format_bytes(t.total_size_bytes)),
                 Cell::from(t.seq_scan.to_string()),
@@ -113,11 +122,19 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
                 Cell::from(
                     t.last_autovacuum.map_or_else(|| "never".into(), |ts| ts.format("%m-%d %H:%M").to_string()),
                 ),
-            ])
+            ], &shown));
+            let mut style = rule_color.map_or_else(Style::default, |color| Style::default().fg(color));
+            if app.config.ignore.is_table_ignored(&t.schemaname, &t.relname) {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if change.is_some() {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            row.style(style)
         })
         .collect();
 
-    let widths = [
+    let widths = shown_only([
         Constraint::Fill(1),
         Constraint::Length(9),
         Constraint::Length(10),
@@ -126,8 +143,9 @@ pub fn render_table_stats(frame: &mut Frame, app: &mut App, area: Rect) {
         Constraint::Length(9),
         Constraint::Length(8),
         Constraint::Length(13),
-    ];
+    ], &shown);
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.table_stats.state);
+    app.mouse_targets.table_at(area, true);
 }
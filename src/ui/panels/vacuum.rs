@@ -9,7 +9,7 @@ use crate::ui::util::{empty_state, styled_table, truncate};
 use super::panel_block;
 
 pub fn render_vacuum_progress(frame: &mut Frame, app: &mut App, area: Rect) {
-    let emoji = if app.config.show_emojis { "🧹 " } else { "" };
+    let emoji = if app.show_emojis() { "🧹 " } else { "" };
     let title = format!("{emoji}Vacuum");
     let block = panel_block(&title);
 
@@ -51,4 +51,5 @@ pub fn render_vacuum_progress(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let table = styled_table(rows, widths, header, block);
     frame.render_stateful_widget(table, area, &mut app.panels.vacuum);
+    app.mouse_targets.table_at(area, true);
 }
@@ -1,17 +1,21 @@
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Cell, Paragraph, Row};
 use ratatui::Frame;
 
-use crate::app::App;
+use crate::app::{App, WaitTypeShare, WAIT_WINDOW_SECS};
 use crate::ui::theme::Theme;
-use crate::ui::util::{empty_state, truncate};
+use crate::ui::util::{empty_state, format_duration, proportional_widths, styled_table, truncate};
 
 use super::panel_block;
 
-pub fn render_wait_events(frame: &mut Frame, app: &App, area: Rect) {
-    let emoji = if app.config.show_emojis { "⏳ " } else { "" };
+pub fn render_wait_events(frame: &mut Frame, app: &mut App, area: Rect) {
+    if app.config.wait_sampler && app.replay.is_none() {
+        render_wait_profile(frame, app, area);
+        return;
+    }
+    let emoji = if app.show_emojis() { "⏳ " } else { "" };
     let title = format!("{emoji}Wait Events");
     let block = panel_block(&title);
 
@@ -26,37 +30,128 @@ pub fn render_wait_events(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let max_count = snap.wait_events.iter().map(|w| w.count).max().unwrap_or(1);
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
+    // Borders, selection marker, the type and event columns and the count
+    let bar_width = i64::from(area.width.saturating_sub(4 + 13 + 25 + 8));
 
-    let bar_width = i64::from(inner.width.saturating_sub(22));
+    let header = Row::new(vec!["Type", "Event", "", "Backends"])
+        .style(Theme::title_style())
+        .bottom_margin(0);
 
-    let lines: Vec<Line> = snap
+    let rows: Vec<Row> = snap
         .wait_events
         .iter()
         .map(|w| {
             let color = Theme::wait_event_color(&w.wait_event_type);
-            let label = format!("{:>12}", truncate(&w.wait_event_type, 12));
             let bar_len = if max_count > 0 {
                 ((w.count as f64 / max_count as f64) * bar_width as f64) as usize
             } else {
                 0
             };
-            let bar: String = "\u{2588}".repeat(bar_len);
-            let count_str = format!(" {}", w.count);
-
-            Line::from(vec![
-                Span::styled(label, Style::default().fg(Theme::fg_dim())),
-                Span::raw(" "),
-                Span::styled(bar, Style::default().fg(color)),
-                Span::styled(
-                    count_str,
-                    Style::default().fg(color).add_modifier(Modifier::BOLD),
-                ),
+            Row::new(vec![
+                Cell::from(truncate(&w.wait_event_type, 12)).style(Style::default().fg(Theme::fg_dim())),
+                Cell::from(truncate(&w.wait_event, 24)),
+                Cell::from("\u{2588}".repeat(bar_len)).style(Style::default().fg(color)),
+                Cell::from(w.count.to_string()).style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
             ])
         })
         .collect();
 
-    let paragraph = Paragraph::new(lines);
-    frame.render_widget(paragraph, inner);
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(24),
+        Constraint::Min(1),
+        Constraint::Length(8),
+    ];
+
+    let table = styled_table(rows, widths, header, block);
+    frame.render_stateful_widget(table, area, &mut app.panels.wait_events);
+    app.mouse_targets.table_at(area, true);
+}
+
+/// `text` cut or padded to exactly `width` cells.
+fn fit(text: &str, width: u16) -> String {
+    let width = usize::from(width);
+    format!("{:<width$}", truncate(text, width))
+}
+
+fn percent(count: i64, total: i64) -> f64 {
+    count as f64 * 100.0 / total as f64
+}
+
+/// Icicle view of the sampler's window: wait types across the full width,
+/// each split into its events underneath, then the numbers per type.
+fn render_wait_profile(frame: &mut Frame, app: &App, area: Rect) {
+    let sampler = &app.wait_sampler;
+    let emoji = if app.show_emojis() { "⏳ " } else { "" };
+    let title = match sampler.source {
+        Some(source) => format!(
+            "{emoji}Wait Events · sampled via {}, last {} ({} polls)",
+            source.label(),
+            format_duration(sampler.span_secs()),
+            sampler.polls()
+        ),
+        None => format!("{emoji}Wait Events · sampler starting"),
+    };
+    let block = panel_block(&title);
+
+    let profile = sampler.profile();
+    if profile.is_empty() {
+        let text = match &sampler.error {
+            Some(e) => format!("Wait sampler failed: {e}"),
+            None => format!("No backend waited in the last {WAIT_WINDOW_SECS}s"),
+        };
+        frame.render_widget(empty_state(&text, block), area);
+        return;
+    }
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let total: i64 = profile.iter().map(|t| t.count).sum();
+    let type_widths = proportional_widths(inner.width, &profile.iter().map(|t| t.count).collect::<Vec<_>>());
+
+    let mut type_row = Vec::new();
+    let mut event_row = Vec::new();
+    for (share, &width) in profile.iter().zip(&type_widths) {
+        if width == 0 {
+            continue;
+        }
+        let color = Theme::wait_event_color(&share.wait_event_type);
+        let block_style = Style::default().fg(Theme::overlay_bg()).bg(color);
+        let label = format!(" {} {:.0}%", share.wait_event_type, percent(share.count, total));
+        type_row.push(Span::styled(fit(&label, width), block_style.add_modifier(Modifier::BOLD)));
+
+        let counts: Vec<i64> = share.events.iter().map(|(_, n)| *n).collect();
+        let event_widths = proportional_widths(width, &counts);
+        for (i, ((event, _), &w)) in share.events.iter().zip(&event_widths).enumerate() {
+            if w == 0 {
+                continue;
+            }
+            // Alternate so neighbouring events of one type stay apart
+            let style = if i % 2 == 0 { block_style } else { block_style.add_modifier(Modifier::REVERSED) };
+            event_row.push(Span::styled(fit(&format!(" {event}"), w), style));
+        }
+    }
+
+    let mut lines = vec![Line::from(type_row), Line::from(event_row), Line::from("")];
+    lines.extend(profile.iter().map(|share| type_line(share, total)));
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn type_line(share: &WaitTypeShare, total: i64) -> Line<'static> {
+    let color = Theme::wait_event_color(&share.wait_event_type);
+    let events = share
+        .events
+        .iter()
+        .map(|(event, n)| format!("{event} {:.0}%", percent(*n, total)))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    Line::from(vec![
+        Span::styled(format!("{:>12}", truncate(&share.wait_event_type, 12)), Style::default().fg(Theme::fg_dim())),
+        Span::styled(
+            format!(" {:>5.1}%  ", percent(share.count, total)),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(events, Style::default().fg(Theme::fg())),
+    ])
 }
@@ -1,18 +1,19 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::app::App;
-use crate::db::models::{ArchiverStats, BgwriterStats, CheckpointStats, WalStats};
+use crate::app::{App, IoRate, MetricsHistory, RateSeries, WalIoRates};
+use crate::db::models::{ArchiverStats, CheckpointStats, WalDirStats, WalStats};
+use crate::ui::sparkline::{low_high, render_sparkline};
 use crate::ui::theme::Theme;
-use crate::ui::util::{format_byte_rate, format_bytes, format_compact, format_time_ms};
+use crate::ui::util::{format_byte_rate, format_bytes, format_rate, format_time_ms, truncate};
 
 use super::panel_block;
 
 pub fn render_wal_io(frame: &mut Frame, app: &App, area: Rect) {
-    let emoji = if app.config.show_emojis { "💿 " } else { "" };
+    let emoji = if app.show_emojis() { "💿 " } else { "" };
     let title = format!("{emoji}WAL & I/O");
     let block = panel_block(&title);
 
@@ -24,10 +25,11 @@ pub fn render_wal_io(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split into top section (3 columns) and bottom section (buffer I/O)
+    // Top section (3 columns), buffer I/O row, then pg_stat_io by backend
+    let io_height = io_section_height(app.server_info.major_version(), &app.metrics.wal_io.io);
     let sections = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(6), Constraint::Length(3)])
+        .constraints([Constraint::Min(6), Constraint::Length(3), Constraint::Length(io_height)])
         .split(inner);
 
     // Top section: 3 columns - WAL Generation, Checkpoints, Archiver
@@ -40,77 +42,258 @@ pub fn render_wal_io(frame: &mut Frame, app: &App, area: Rect) {
         ])
         .split(sections[0]);
 
+    let rates = &app.metrics.wal_io;
+    // Plain mode gives the history as numbers instead of sparklines
+    let plain = app.config.plain();
+
     // Render WAL Generation (PG14+ only)
-    render_wal_column(frame, snap.wal_stats.as_ref(), app.metrics.current_wal_rate, columns[0]);
+    render_wal_column(frame, snap.wal_stats.as_ref(), &app.metrics, plain, columns[0]);
 
     // Render Checkpoints
-    render_checkpoint_column(frame, snap.checkpoint_stats.as_ref(), columns[1]);
+    render_checkpoint_column(frame, snap.checkpoint_stats.as_ref(), rates, plain, columns[1]);
 
     // Render Archiver
-    render_archiver_column(frame, snap.archiver_stats.as_ref(), columns[2]);
+    render_archiver_column(
+        frame,
+        snap.archiver_stats.as_ref(),
+        snap.wal_dir,
+        app.wal_dir_fill(),
+        rates,
+        plain,
+        columns[2],
+    );
 
     // Render Buffer I/O at bottom
     render_buffer_io_row(
         frame,
-        snap.checkpoint_stats.as_ref(),
-        snap.bgwriter_stats.as_ref(),
+        snap.checkpoint_stats.is_some(),
+        snap.bgwriter_stats.is_some(),
+        rates,
+        plain,
         sections[1],
     );
+
+    render_io_by_backend(
+        frame,
+        !snap.io_stats.is_empty(),
+        &rates.io,
+        sections[2],
+    );
+}
+
+/// Most backend type and context rows listed under pg_stat_io
+const IO_MAX_ROWS: usize = 8;
+
+/// Rows with I/O in the last interval, busiest first.
+fn active_io_rates(rates: &[IoRate]) -> Vec<&IoRate> {
+    let mut active: Vec<&IoRate> = rates.iter().filter(|r| r.total() > 0.0).collect();
+    active.sort_by(|a, b| b.total().total_cmp(&a.total()));
+    active.truncate(IO_MAX_ROWS);
+    active
 }
 
-fn render_wal_column(frame: &mut Frame, wal: Option<&WalStats>, wal_rate: Option<f64>, area: Rect) {
+/// Title, then a header and a line per busy row, or one line saying why
+/// there are none. Servers before 16 have no pg_stat_io, and the section
+/// leaves the room to the columns above.
+fn io_section_height(major_version: u32, rates: &[IoRate]) -> u16 {
+    if major_version < 16 {
+        return 0;
+    }
+    match active_io_rates(rates).len() {
+        0 => 2,
+        rows => 2 + rows as u16,
+    }
+}
+
+fn render_io_by_backend(frame: &mut Frame, collected: bool, rates: &[IoRate], area: Rect) {
+    if area.height == 0 {
+        return;
+    }
     let title_style = Style::default()
         .fg(Theme::fg())
         .add_modifier(Modifier::BOLD);
-    let label_style = Style::default().fg(Theme::fg_dim());
+    let dim_style = Style::default().fg(Theme::fg_dim());
     let value_style = Style::default().fg(Theme::fg());
 
-    let mut lines = vec![
-        Line::from(Span::styled("WAL Generation", title_style)),
-        Line::from(""),
-    ];
+    let mut lines = vec![Line::from(Span::styled("I/O by Backend (pg_stat_io)", title_style))];
+    let active = active_io_rates(rates);
+    let note = if !collected {
+        Some("No data")
+    } else if rates.is_empty() {
+        Some("Waiting for a second sample")
+    } else if active.is_empty() {
+        Some("No I/O in the last interval")
+    } else {
+        None
+    };
 
-    if let Some(w) = wal {
-        // Show rate first (most important metric)
-        let rate_display = wal_rate.map_or_else(|| "\u{2014}".into(), format_byte_rate);
-        lines.push(Line::from(vec![
-            Span::styled("Rate:         ", label_style),
-            Span::styled(
-                rate_display,
-                Style::default()
-                    .fg(Theme::border_active())
-                    .add_modifier(Modifier::BOLD),
+    if let Some(note) = note {
+        lines.push(Line::from(Span::styled(note, dim_style)));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:<20}{:<11}{:>10}{:>10}{:>10}{:>10}",
+                "Backend", "Context", "Reads", "Writes", "Extends", "Fsyncs"
             ),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Records:      ", label_style),
-            Span::styled(format_compact(w.wal_records), value_style),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Total Size:   ", label_style),
-            Span::styled(format_bytes(w.wal_bytes), value_style),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Buffers Full: ", label_style),
-            Span::styled(
-                format_compact(w.wal_buffers_full),
-                if w.wal_buffers_full > 0 {
+            dim_style,
+        )));
+        for rate in active {
+            // Writes and fsyncs by client backends mean the bgwriter and
+            // checkpointer are not keeping up
+            let client = rate.backend_type == "client backend";
+            let pressure_style = |value: f64| {
+                if client && value > 0.0 {
                     Style::default().fg(Theme::border_warn())
                 } else {
                     value_style
-                },
-            ),
-        ]));
-        if w.wal_write_time > 0.0 || w.wal_sync_time > 0.0 {
-            lines.push(Line::from(vec![
-                Span::styled("Write Time:   ", label_style),
-                Span::styled(format_time_ms(w.wal_write_time), value_style),
-            ]));
+                }
+            };
             lines.push(Line::from(vec![
-                Span::styled("Sync Time:    ", label_style),
-                Span::styled(format_time_ms(w.wal_sync_time), value_style),
+                Span::styled(format!("{:<20}", truncate(&rate.backend_type, 19)), value_style),
+                Span::styled(format!("{:<11}", rate.context), dim_style),
+                Span::styled(format!("{:>10}", format_rate(rate.reads)), value_style),
+                Span::styled(format!("{:>10}", format_rate(rate.writes)), pressure_style(rate.writes)),
+                Span::styled(format!("{:>10}", format_rate(rate.extends)), value_style),
+                Span::styled(format!("{:>10}", format_rate(rate.fsyncs)), pressure_style(rate.fsyncs)),
             ]));
         }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Widest sparkline next to each rate in the column layout
+const SPARK_WIDTH: usize = 10;
+/// Label plus padded value in front of each column sparkline
+const RATE_TEXT_WIDTH: usize = 14 + 11;
+/// Sparkline width in the single-line buffer I/O row
+const ROW_SPARK_WIDTH: usize = 6;
+
+/// How a rate's history follows it: a sparkline, or in plain mode the low
+/// and high over the session, or only the high where both don't fit `room`
+#[derive(Clone, Copy)]
+struct Trend {
+    spark_width: usize,
+    room: usize,
+    plain: bool,
+}
+
+impl Trend {
+    fn of(self, series: &RateSeries, format: fn(f64) -> String) -> Span<'static> {
+        // The history is in milli-units
+        self.span(&series.history.as_vec(), |milli| format(milli as f64 / 1000.0))
+    }
+
+    fn span(self, history: &[u64], format: impl Fn(u64) -> String) -> Span<'static> {
+        if self.plain {
+            let mut range = low_high(history, &format);
+            if range.chars().count() > self.room {
+                range = history.iter().max().map_or_else(String::new, |&high| format!("high {}", format(high)));
+            }
+            Span::styled(range, Style::default().fg(Theme::fg_dim()))
+        } else {
+            Span::styled(
+                render_sparkline(history, self.spark_width),
+                Style::default().fg(Theme::border_active()),
+            )
+        }
+    }
+}
+
+/// `label  value  trend`, with the value padded so trends line up
+fn rate_line(label: &'static str, value: String, style: Style, trend: Span<'static>) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(label, Style::default().fg(Theme::fg_dim())),
+        Span::styled(format!("{value:<11}"), style),
+        trend,
+    ])
+}
+
+/// Sparkline width that fits a column, leaving a one-cell gap before the next
+fn spark_width(area: Rect) -> usize {
+    (area.width as usize)
+        .saturating_sub(RATE_TEXT_WIDTH + 1)
+        .min(SPARK_WIDTH)
+}
+
+/// Format a rate, or an em dash until two snapshots have been seen
+fn or_dash(rate: Option<f64>, fmt: impl Fn(f64) -> String) -> String {
+    rate.map_or_else(|| "\u{2014}".into(), fmt)
+}
+
+fn format_ms_rate(ms_per_sec: f64) -> String {
+    format!("{}/s", format_time_ms(ms_per_sec))
+}
+
+fn format_per_min(rate: f64) -> String {
+    format!("{:.1}/min", rate * 60.0)
+}
+
+/// Warn-colored when the rate is non-zero
+fn warn_if_nonzero(rate: Option<f64>, warn: Color) -> Style {
+    if rate.is_some_and(|r| r > 0.0) {
+        Style::default().fg(warn)
+    } else {
+        Style::default().fg(Theme::fg())
+    }
+}
+
+fn render_wal_column(frame: &mut Frame, wal: Option<&WalStats>, metrics: &MetricsHistory, plain: bool, area: Rect) {
+    let title_style = Style::default()
+        .fg(Theme::fg())
+        .add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Theme::fg());
+    let rates = &metrics.wal_io;
+
+    let trend = Trend {
+        spark_width: spark_width(area),
+        room: (area.width as usize).saturating_sub(RATE_TEXT_WIDTH + 1),
+        plain,
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled("WAL Generation", title_style)),
+        Line::from(""),
+    ];
+
+    if let Some(w) = wal {
+        // Show byte rate first (most important metric)
+        lines.push(rate_line(
+            "Rate:         ",
+            or_dash(metrics.current_wal_rate, format_byte_rate),
+            Style::default()
+                .fg(Theme::border_active())
+                .add_modifier(Modifier::BOLD),
+            // Kept in KB/s
+            trend.span(&metrics.wal_rate.as_vec(), |kb| format_byte_rate(kb as f64 * 1024.0)),
+        ));
+        lines.push(rate_line(
+            "Records:      ",
+            or_dash(rates.wal_records.current, format_rate),
+            value_style,
+            trend.of(&rates.wal_records, format_rate),
+        ));
+        lines.push(rate_line(
+            "Buffers Full: ",
+            or_dash(rates.wal_buffers_full.current, format_rate),
+            warn_if_nonzero(rates.wal_buffers_full.current, Theme::border_warn()),
+            trend.of(&rates.wal_buffers_full, format_rate),
+        ));
+        // Timing columns stay at zero unless track_wal_io_timing is on
+        if w.wal_write_time > 0.0 || w.wal_sync_time > 0.0 {
+            lines.push(rate_line(
+                "Write Time:   ",
+                or_dash(rates.wal_write_time.current, format_ms_rate),
+                value_style,
+                trend.of(&rates.wal_write_time, format_ms_rate),
+            ));
+            lines.push(rate_line(
+                "Sync Time:    ",
+                or_dash(rates.wal_sync_time.current, format_ms_rate),
+                value_style,
+                trend.of(&rates.wal_sync_time, format_ms_rate),
+            ));
+        }
     } else {
         lines.push(Line::from(Span::styled(
             "N/A (PG14+)",
@@ -121,32 +304,37 @@ fn render_wal_column(frame: &mut Frame, wal: Option<&WalStats>, wal_rate: Option
     frame.render_widget(Paragraph::new(lines), area);
 }
 
-fn render_checkpoint_column(frame: &mut Frame, chkpt: Option<&CheckpointStats>, area: Rect) {
+fn render_checkpoint_column(
+    frame: &mut Frame,
+    chkpt: Option<&CheckpointStats>,
+    rates: &WalIoRates,
+    plain: bool,
+    area: Rect,
+) {
     let title_style = Style::default()
         .fg(Theme::fg())
         .add_modifier(Modifier::BOLD);
-    let label_style = Style::default().fg(Theme::fg_dim());
     let value_style = Style::default().fg(Theme::fg());
 
+    let trend = Trend {
+        spark_width: spark_width(area),
+        room: (area.width as usize).saturating_sub(RATE_TEXT_WIDTH + 1),
+        plain,
+    };
+
     let mut lines = vec![
         Line::from(Span::styled("Checkpoints", title_style)),
         Line::from(""),
     ];
 
     if let Some(c) = chkpt {
+        // Forced share over the server's lifetime colors the forced rate
         let total = c.checkpoints_timed + c.checkpoints_req;
         let forced_pct = if total > 0 {
             (c.checkpoints_req as f64 / total as f64) * 100.0
         } else {
             0.0
         };
-        let timed_pct = if total > 0 {
-            (c.checkpoints_timed as f64 / total as f64) * 100.0
-        } else {
-            0.0
-        };
-
-        // Color for forced checkpoints
         let forced_color = if forced_pct > 20.0 {
             Theme::border_danger()
         } else if forced_pct > 5.0 {
@@ -155,32 +343,30 @@ fn render_checkpoint_column(frame: &mut Frame, chkpt: Option<&CheckpointStats>,
             Theme::border_ok()
         };
 
-        lines.push(Line::from(vec![
-            Span::styled("Total:        ", label_style),
-            Span::styled(format_compact(total), value_style),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Timed:        ", label_style),
-            Span::styled(
-                format!("{} ({:.0}%)", format_compact(c.checkpoints_timed), timed_pct),
-                value_style,
-            ),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Forced:       ", label_style),
-            Span::styled(
-                format!("{} ({:.0}%)", format_compact(c.checkpoints_req), forced_pct),
-                Style::default().fg(forced_color),
-            ),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Write Time:   ", label_style),
-            Span::styled(format_time_ms(c.checkpoint_write_time), value_style),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Sync Time:    ", label_style),
-            Span::styled(format_time_ms(c.checkpoint_sync_time), value_style),
-        ]));
+        lines.push(rate_line(
+            "Rate:         ",
+            or_dash(rates.checkpoints.current, format_per_min),
+            value_style,
+            trend.of(&rates.checkpoints, format_per_min),
+        ));
+        lines.push(rate_line(
+            "Forced:       ",
+            or_dash(rates.checkpoints_forced.current, format_per_min),
+            Style::default().fg(forced_color),
+            trend.of(&rates.checkpoints_forced, format_per_min),
+        ));
+        lines.push(rate_line(
+            "Write Time:   ",
+            or_dash(rates.checkpoint_write_time.current, format_ms_rate),
+            value_style,
+            trend.of(&rates.checkpoint_write_time, format_ms_rate),
+        ));
+        lines.push(rate_line(
+            "Sync Time:    ",
+            or_dash(rates.checkpoint_sync_time.current, format_ms_rate),
+            value_style,
+            trend.of(&rates.checkpoint_sync_time, format_ms_rate),
+        ));
     } else {
         lines.push(Line::from(Span::styled(
             "No data",
@@ -191,37 +377,64 @@ fn render_checkpoint_column(frame: &mut Frame, chkpt: Option<&CheckpointStats>,
     frame.render_widget(Paragraph::new(lines), area);
 }
 
-fn render_archiver_column(frame: &mut Frame, archiver: Option<&ArchiverStats>, area: Rect) {
+fn render_archiver_column(
+    frame: &mut Frame,
+    archiver: Option<&ArchiverStats>,
+    wal_dir: Option<WalDirStats>,
+    wal_dir_fill: Option<f64>,
+    rates: &WalIoRates,
+    plain: bool,
+    area: Rect,
+) {
     let title_style = Style::default()
         .fg(Theme::fg())
         .add_modifier(Modifier::BOLD);
     let label_style = Style::default().fg(Theme::fg_dim());
     let value_style = Style::default().fg(Theme::fg());
 
+    let trend = Trend {
+        spark_width: spark_width(area),
+        room: (area.width as usize).saturating_sub(RATE_TEXT_WIDTH + 1),
+        plain,
+    };
+
     let mut lines = vec![
         Line::from(Span::styled("Archiver", title_style)),
         Line::from(""),
     ];
 
     if let Some(a) = archiver {
-        // Failed count color
-        let failed_color = if a.failed_count > 0 {
-            Theme::border_danger()
-        } else {
-            Theme::border_ok()
-        };
+        lines.push(rate_line(
+            "Archived:     ",
+            or_dash(rates.archived.current, format_per_min),
+            value_style,
+            trend.of(&rates.archived, format_per_min),
+        ));
+        lines.push(rate_line(
+            "Failed:       ",
+            or_dash(rates.archive_failed.current, format_per_min),
+            warn_if_nonzero(rates.archive_failed.current, Theme::border_danger()),
+            trend.of(&rates.archive_failed, format_per_min),
+        ));
 
-        lines.push(Line::from(vec![
-            Span::styled("Archived:     ", label_style),
-            Span::styled(format_compact(a.archived_count), value_style),
-        ]));
-        lines.push(Line::from(vec![
-            Span::styled("Failed:       ", label_style),
-            Span::styled(
-                format_compact(a.failed_count),
-                Style::default().fg(failed_color),
-            ),
-        ]));
+        // Segments archive_command has not taken yet pile up in pg_wal
+        if let Some(ready) = wal_dir.and_then(|d| d.archive_ready) {
+            let ready_color = if ready > 0 { Theme::border_warn() } else { Theme::fg() };
+            lines.push(Line::from(vec![
+                Span::styled("Backlog:      ", label_style),
+                Span::styled(format!("{ready} ready"), Style::default().fg(ready_color)),
+            ]));
+        }
+        if let Some(dir) = wal_dir {
+            lines.push(Line::from(vec![
+                Span::styled("pg_wal:       ", label_style),
+                Span::styled(
+                    format_bytes(dir.bytes),
+                    Style::default().fg(Theme::wal_dir_color(wal_dir_fill)),
+                ),
+                Span::styled(format!(" ({} seg)", dir.segments), label_style),
+            ]));
+        }
 
         // Calculate archive lag if we have a last archived time
         if let Some(last_time) = a.last_archived_time {
@@ -289,32 +502,46 @@ fn render_archiver_column(frame: &mut Frame, archiver: Option<&ArchiverStats>, a
     frame.render_widget(Paragraph::new(lines), area);
 }
 
+/// `label value trend` spans for the single-line buffer I/O row
+fn row_rate_spans(label: &'static str, series: &RateSeries, style: Style, plain: bool) -> [Span<'static>; 4] {
+    let trend = Trend {
+        spark_width: ROW_SPARK_WIDTH,
+        // Up to five rates share the row: only their highs fit
+        room: 0,
+        plain,
+    };
+    [
+        Span::styled(label, Style::default().fg(Theme::fg_dim())),
+        Span::styled(format!("{} ", or_dash(series.current, format_rate)), style),
+        trend.of(series, format_rate),
+        Span::raw("   "),
+    ]
+}
+
 fn render_buffer_io_row(
     frame: &mut Frame,
-    chkpt: Option<&CheckpointStats>,
-    bgwriter: Option<&BgwriterStats>,
+    has_checkpointer: bool,
+    has_bgwriter: bool,
+    rates: &WalIoRates,
+    plain: bool,
     area: Rect,
 ) {
     let title_style = Style::default()
         .fg(Theme::fg())
         .add_modifier(Modifier::BOLD);
-    let label_style = Style::default().fg(Theme::fg_dim());
     let value_style = Style::default().fg(Theme::fg());
 
     let mut spans: Vec<Span> = vec![
         Span::styled("Buffer I/O: ", title_style),
     ];
 
-    if let Some(c) = chkpt {
-        spans.push(Span::styled("Checkpoint: ", label_style));
-        spans.push(Span::styled(format_compact(c.buffers_checkpoint), value_style));
-        spans.push(Span::raw("   "));
+    if has_checkpointer {
+        spans.extend(row_rate_spans("Checkpoint: ", &rates.buffers_checkpoint, value_style, plain));
 
         // Backend writes are bad - they bypass the bgwriter
-        let backend_pct = if c.buffers_checkpoint > 0 {
-            (c.buffers_backend as f64 / c.buffers_checkpoint as f64) * 100.0
-        } else {
-            0.0
+        let backend_pct = match (rates.buffers_backend.current, rates.buffers_checkpoint.current) {
+            (Some(backend), Some(chkpt)) if chkpt > 0.0 => backend / chkpt * 100.0,
+            _ => 0.0,
         };
         let backend_color = if backend_pct > 5.0 {
             Theme::border_danger()
@@ -323,32 +550,28 @@ fn render_buffer_io_row(
         } else {
             Theme::border_ok()
         };
-
-        spans.push(Span::styled("Backend: ", label_style));
-        spans.push(Span::styled(
-            format!("{} ({:.1}%)", format_compact(c.buffers_backend), backend_pct),
+        spans.extend(row_rate_spans(
+            "Backend: ",
+            &rates.buffers_backend,
             Style::default().fg(backend_color),
+            plain,
         ));
-        spans.push(Span::raw("   "));
     }
 
-    if let Some(b) = bgwriter {
-        spans.push(Span::styled("Clean: ", label_style));
-        spans.push(Span::styled(format_compact(b.buffers_clean), value_style));
-        spans.push(Span::raw("   "));
+    if has_bgwriter {
+        spans.extend(row_rate_spans("Clean: ", &rates.buffers_clean, value_style, plain));
 
-        // maxwritten_clean > 0 means bgwriter is being throttled
-        if b.maxwritten_clean > 0 {
-            spans.push(Span::styled("Throttled: ", label_style));
-            spans.push(Span::styled(
-                format_compact(b.maxwritten_clean),
+        // maxwritten_clean increasing means bgwriter is being throttled
+        if rates.buffers_throttled.current.is_some_and(|r| r > 0.0) {
+            spans.extend(row_rate_spans(
+                "Throttled: ",
+                &rates.buffers_throttled,
                 Style::default().fg(Theme::border_warn()),
+                plain,
             ));
-            spans.push(Span::raw("   "));
         }
 
-        spans.push(Span::styled("Alloc: ", label_style));
-        spans.push(Span::styled(format_compact(b.buffers_alloc), value_style));
+        spans.extend(row_rate_spans("Alloc: ", &rates.buffers_alloc, value_style, plain));
     }
 
     let line = Line::from(spans);
@@ -13,7 +13,7 @@ use std::path::PathBuf;
 use crate::app::{App, BottomPanel, InspectTarget, SortColumn, ViewMode};
 use crate::config::AppConfig;
 use crate::db::models::*;
-use crate::recorder::RecordingInfo;
+use crate::recorder::{RecordingInfo, UiEvent, UiEventKind};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Test Fixtures
@@ -28,14 +28,19 @@ fn make_server_info() -> ServerInfo {
         extensions: DetectedExtensions {
             pg_stat_statements: true,
             pg_stat_statements_version: Some("1.10".to_string()),
+            pg_stat_monitor: false,
             pg_stat_kcache: false,
             pg_wait_sampling: false,
             pg_buffercache: true,
             pgstattuple: false,
             pgstattuple_version: None,
+            hypopg: false,
         },
         settings: vec![],
         extensions_list: vec![],
+        role_settings: vec![],
+        roles: vec![],
+        capabilities: Default::default(),
     }
 }
 
@@ -54,6 +59,10 @@ fn make_snapshot() -> PgSnapshot {
                 duration_secs: 5.5,
                 query: Some("SELECT * FROM users WHERE id = $1".to_string()),
                 backend_type: Some("client backend".to_string()),
+                xact_start: None,
+                query_id: None,
+                application_name: None,
+                state_change: None,
             },
             ActiveQuery {
                 pid: 12346,
@@ -66,6 +75,10 @@ fn make_snapshot() -> PgSnapshot {
                 duration_secs: 165.0,
                 query: Some("UPDATE orders SET status = 'shipped'".to_string()),
                 backend_type: Some("client backend".to_string()),
+                xact_start: None,
+                query_id: None,
+                application_name: None,
+                state_change: None,
             },
         ],
         wait_events: vec![
@@ -95,6 +108,8 @@ fn make_snapshot() -> PgSnapshot {
             blocker_query: Some("UPDATE orders SET status = 'shipped'".to_string()),
             blocker_state: Some("idle in transaction".to_string()),
         }],
+        locks: vec![],
+        connection_groups: vec![],
         buffer_cache: BufferCacheStats {
             blks_hit: 95000,
             blks_read: 5000,
@@ -108,6 +123,8 @@ fn make_snapshot() -> PgSnapshot {
             waiting_count: 1,
             oldest_xact_secs: Some(165.0),
             autovacuum_count: 1,
+            aborted_txn_count: 0,
+            idle_count: 0,
         },
         table_stats: vec![
             TableStat {
@@ -205,12 +222,18 @@ fn make_snapshot() -> PgSnapshot {
             pid: 34567,
             datname: Some("production".to_string()),
             table_name: "public.large_table".to_string(),
+            relid: None,
             phase: "scanning heap".to_string(),
             heap_blks_total: 100_000,
             heap_blks_vacuumed: 45000,
             progress_pct: 45.0,
             num_dead_tuples: 12500,
         }],
+        operations: vec![],
+        background_workers: vec![],
+        capped_pids: vec![],
+        wal_dir: None,
+        statement_monitor: None,
         wraparound: vec![
             WraparoundInfo {
                 datname: "production".to_string(),
@@ -235,6 +258,7 @@ fn make_snapshot() -> PgSnapshot {
                 idx_tup_read: 50000,
                 idx_tup_fetch: 49500,
                 index_definition: "CREATE UNIQUE INDEX orders_pkey ON public.orders USING btree (id)".to_string(),
+                is_valid: true,
                 bloat_bytes: None,
                 bloat_pct: None,
                 bloat_source: None,
@@ -248,6 +272,7 @@ fn make_snapshot() -> PgSnapshot {
                 idx_tup_read: 0,
                 idx_tup_fetch: 0,
                 index_definition: "CREATE INDEX orders_user_id_idx ON public.orders USING btree (user_id)".to_string(),
+                is_valid: true,
                 bloat_bytes: Some(5_242_880),
                 bloat_pct: Some(20.0),
                 bloat_source: None,
@@ -276,16 +301,19 @@ fn make_snapshot() -> PgSnapshot {
             blk_read_time: 50.0,
             blk_write_time: 0.0,
             hit_ratio: 98.9,
+            windowed: false,
         }],
         stat_statements_error: None,
         extensions: DetectedExtensions {
             pg_stat_statements: true,
             pg_stat_statements_version: Some("1.10".to_string()),
+            pg_stat_monitor: false,
             pg_stat_kcache: false,
             pg_wait_sampling: false,
             pg_buffercache: true,
             pgstattuple: false,
             pgstattuple_version: None,
+            hypopg: false,
         },
         db_size: 10_737_418_240,
         checkpoint_stats: Some(CheckpointStats {
@@ -324,7 +352,15 @@ fn make_snapshot() -> PgSnapshot {
             xact_commit: 100_000,
             xact_rollback: 50,
             blks_read: 5000,
+            stats_reset: None,
+            server_start: None,
         }),
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+        io_stats: vec![],
     }
 }
 
@@ -334,6 +370,8 @@ fn make_empty_snapshot() -> PgSnapshot {
         active_queries: vec![],
         wait_events: vec![],
         blocking_info: vec![],
+        locks: vec![],
+        connection_groups: vec![],
         buffer_cache: BufferCacheStats {
             blks_hit: 0,
             blks_read: 0,
@@ -347,12 +385,19 @@ fn make_empty_snapshot() -> PgSnapshot {
             waiting_count: 0,
             oldest_xact_secs: None,
             autovacuum_count: 0,
+            aborted_txn_count: 0,
+            idle_count: 0,
         },
         table_stats: vec![],
         replication: vec![],
         replication_slots: vec![],
         subscriptions: vec![],
         vacuum_progress: vec![],
+        operations: vec![],
+        background_workers: vec![],
+        capped_pids: vec![],
+        wal_dir: None,
+        statement_monitor: None,
         wraparound: vec![],
         indexes: vec![],
         stat_statements: vec![],
@@ -364,6 +409,12 @@ fn make_empty_snapshot() -> PgSnapshot {
         archiver_stats: None,
         bgwriter_stats: None,
         db_stats: None,
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+        io_stats: vec![],
     }
 }
 
@@ -387,7 +438,8 @@ fn make_app(snapshot: Option<PgSnapshot>) -> App {
         app.metrics.active_queries.push((3 + i % 5) as u64);
         app.metrics.lock_count.push((i % 3) as u64);
         app.metrics.tps.push((1000 + i * 50) as u64);
-        app.metrics.wal_rate.push((1024 * 1024 + i * 10000) as u64);
+        // KB/s, as MetricsHistory keeps it
+        app.metrics.wal_rate.push((1024 + i * 10) as u64);
         app.metrics.blks_read.push((500 + i * 10) as u64);
     }
     app.metrics.current_tps = Some(1500.0);
@@ -695,6 +747,49 @@ fn header_live_paused() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+#[test]
+fn header_live_profile() {
+    let backend = TestBackend::new(100, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.active_profile = Some("prod".into());
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn header_live_limited_visibility() {
+    let backend = TestBackend::new(120, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.server_info.capabilities.read_all_stats = false;
+    app.server_info.capabilities.ls_waldir = false;
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn header_live_reconnecting() {
+    let backend = TestBackend::new(160, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.set_reconnecting(3, 4, "connection refused".to_string());
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
 #[test]
 fn header_live_with_error() {
     let backend = TestBackend::new(120, 1);
@@ -723,6 +818,20 @@ fn header_live_with_ssl() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+#[test]
+fn header_live_maintenance_via_primary() {
+    let backend = TestBackend::new(120, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.connection.set_actions_via("primary1:5432");
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
 #[test]
 fn header_replay_mode() {
     use crate::app::ReplayState;
@@ -735,6 +844,60 @@ fn header_replay_mode() {
         total: 100,
         speed: 2.0,
         playing: true,
+        events: vec![],
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
+    });
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+fn make_ui_events() -> Vec<UiEvent> {
+    vec![
+        UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 29, 0).unwrap(),
+            kind: UiEventKind::PanelSwitch { panel: "Blocking".to_string() },
+            via: None,
+        },
+        UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 30, 0).unwrap(),
+            kind: UiEventKind::TerminateBackend { pids: vec![12346] },
+            via: None,
+        },
+        // After the snapshot timestamp, so not part of the trail yet
+        UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 31, 0).unwrap(),
+            kind: UiEventKind::ResetStatStatements,
+            via: None,
+        },
+    ]
+}
+
+#[test]
+fn header_replay_with_activity() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(120, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: true,
+        events: make_ui_events(),
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
     });
 
     terminal.draw(|frame| {
@@ -756,6 +919,12 @@ fn header_replay_paused() {
         total: 100,
         speed: 0.5,
         playing: false,
+        events: vec![],
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
     });
 
     terminal.draw(|frame| {
@@ -783,11 +952,16 @@ fn footer_live_queries_panel() {
 }
 
 #[test]
-fn footer_live_blocking_panel() {
+fn footer_live_with_adaptive_refresh() {
     let backend = TestBackend::new(120, 2);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Blocking;
+    app.config.adaptive_refresh.enabled = true;
+    app.adaptive_refresh.observe(
+        &app.config.adaptive_refresh,
+        2,
+        crate::adaptive_refresh::Load::Quiet,
+    );
 
     terminal.draw(|frame| {
         super::footer::render(frame, &app, frame.area());
@@ -797,26 +971,34 @@ fn footer_live_blocking_panel() {
 }
 
 #[test]
-fn footer_live_table_stats_panel() {
-    let backend = TestBackend::new(120, 2);
+fn footer_tab_targets_cover_the_rendered_panel_keys() {
+    let backend = TestBackend::new(200, 2);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::TableStats;
+    let app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
         super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
-    insta::assert_snapshot!(buffer_to_string(&terminal));
+    let targets = super::footer::tab_targets(&app, ratatui::layout::Rect::new(0, 0, 200, 2));
+    assert!(!targets.is_empty());
+    let buffer = terminal.backend().buffer();
+    for (rect, panel) in targets {
+        let text: String = (rect.x..rect.x + rect.width)
+            .map(|x| buffer[(x, rect.y)].symbol())
+            .collect();
+        let key = panel.descriptor().footer_hint.map_or("", |(key, _)| key);
+        assert!(text.starts_with(key), "{panel:?} target reads {text:?}");
+    }
 }
 
 #[test]
-fn footer_filter_mode() {
-    let backend = TestBackend::new(100, 2);
+fn footer_live_with_active_filter() {
+    let backend = TestBackend::new(140, 2);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.view_mode = ViewMode::Filter;
-    app.filter.text = "SELECT".to_string();
+    app.filter_mut().text = "SELECT".to_string();
+    app.filter_mut().active = true;
 
     terminal.draw(|frame| {
         super::footer::render(frame, &app, frame.area());
@@ -826,12 +1008,11 @@ fn footer_filter_mode() {
 }
 
 #[test]
-fn footer_replay_mode() {
-    use crate::app::ReplayState;
+fn footer_live_blocking_panel() {
     let backend = TestBackend::new(120, 2);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.replay = Some(ReplayState::new("test.jsonl".to_string(), 10));
+    app.bottom_panel = BottomPanel::Blocking;
 
     terminal.draw(|frame| {
         super::footer::render(frame, &app, frame.area());
@@ -840,718 +1021,2490 @@ fn footer_replay_mode() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Blocking
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_blocking_with_data() {
-    let backend = TestBackend::new(100, 10);
+fn footer_live_table_stats_panel() {
+    let backend = TestBackend::new(120, 2);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::TableStats;
 
     terminal.draw(|frame| {
-        super::panels::render_blocking(frame, &mut app, frame.area());
+        super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_blocking_empty() {
-    let backend = TestBackend::new(100, 10);
+fn footer_filter_mode() {
+    let backend = TestBackend::new(100, 2);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Filter;
+    app.filter_mut().text = "SELECT".to_string();
 
     terminal.draw(|frame| {
-        super::panels::render_blocking(frame, &mut app, frame.area());
+        super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_blocking_no_snapshot() {
-    let backend = TestBackend::new(100, 10);
+fn footer_edit_note_mode() {
+    let backend = TestBackend::new(100, 2);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(None);
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::EditNote(123_456_789);
+    app.note_input = "known-bad report query".to_string();
 
     terminal.draw(|frame| {
-        super::panels::render_blocking(frame, &mut app, frame.area());
+        super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Wait Events
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_wait_events_with_data() {
-    let backend = TestBackend::new(80, 8);
+fn footer_export_prompt() {
+    let backend = TestBackend::new(100, 2);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::ExportPrompt;
+    app.export_input = "exports/queries_20240115_123000.csv".to_string();
 
     terminal.draw(|frame| {
-        super::panels::render_wait_events(frame, &app, frame.area());
+        super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_wait_events_empty() {
-    let backend = TestBackend::new(80, 8);
+fn footer_replay_mode() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(120, 2);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
+    app.replay = Some(ReplayState::new("test.jsonl".to_string(), 10));
 
     terminal.draw(|frame| {
-        super::panels::render_wait_events(frame, &app, frame.area());
+        super::footer::render(frame, &app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Table Stats
+// Panel Tests - Locks
 // ─────────────────────────────────────────────────────────────────────────────
 
+fn make_lock(pid: i32, mode: &str, relation: Option<&str>, granted: bool, secs: f64, blocking_pids: Vec<i32>) -> LockInfo {
+    LockInfo {
+        pid,
+        locktype: if relation.is_some() { "relation" } else { "transactionid" }.into(),
+        mode: mode.into(),
+        granted,
+        relation: relation.map(String::from),
+        relation_oid: None,
+        usename: Some("app".into()),
+        query: Some(format!("-- backend {pid}")),
+        state: Some("active".into()),
+        duration_secs: secs,
+        blocking_pids,
+    }
+}
+
 #[test]
-fn panel_table_stats_with_data() {
-    let backend = TestBackend::new(140, 10);
+fn panel_locks_with_data() {
+    let backend = TestBackend::new(110, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
+    let mut snap = make_snapshot();
+    snap.locks = vec![
+        make_lock(12345, "AccessExclusiveLock", Some("public.orders"), false, 12.0, vec![12347]),
+        make_lock(12347, "RowExclusiveLock", Some("public.orders"), true, 95.0, vec![]),
+        make_lock(12347, "ExclusiveLock", None, true, 95.0, vec![]),
+        make_lock(12346, "AccessShareLock", Some("public.users"), true, 2.0, vec![]),
+    ];
+    let mut app = make_app(Some(snap));
 
     terminal.draw(|frame| {
-        super::panels::render_table_stats(frame, &mut app, frame.area());
+        super::panels::render_locks(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_table_stats_empty() {
-    let backend = TestBackend::new(140, 10);
+fn panel_connection_groups_with_data() {
+    let backend = TestBackend::new(120, 9);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut snap = make_snapshot();
+    let group = |user: &str, db: &str, app: &str, counts: [i64; 5], longest_secs: f64| ConnectionGroup {
+        usename: Some(user.to_string()),
+        datname: Some(db.to_string()),
+        application_name: app.to_string(),
+        total: counts[0],
+        active: counts[1],
+        idle: counts[2],
+        idle_in_transaction: counts[3],
+        waiting: counts[4],
+        longest_secs,
+    };
+    snap.connection_groups = vec![
+        group("app_user", "shop", "rails", [42, 3, 37, 2, 0], 185.0),
+        group("worker", "shop", "sidekiq", [12, 5, 6, 0, 1], 8.5),
+        group("postgres", "postgres", "", [1, 0, 1, 0, 0], 0.0),
+    ];
+    let mut app = make_app(Some(snap));
 
     terminal.draw(|frame| {
-        super::panels::render_table_stats(frame, &mut app, frame.area());
+        super::panels::render_connection_groups(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Replication
-// ─────────────────────────────────────────────────────────────────────────────
+fn make_buffer_cache_report() -> BufferCacheReport {
+    let relation = |relname: &str, relkind: &str, buffers, dirty, cached_pct| BufferCacheRelation {
+        relid: 0,
+        schemaname: "public".into(),
+        relname: relname.into(),
+        relkind: relkind.into(),
+        buffers,
+        dirty,
+        avg_usage: 3.4,
+        cached_pct,
+    };
+    BufferCacheReport {
+        read_at: Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap(),
+        block_size: 8192,
+        total_buffers: 16_384,
+        unused_buffers: 4_096,
+        dirty_buffers: 512,
+        usage_counts: [1_024, 2_048, 1_024, 2_048, 2_048, 4_096],
+        relations: vec![
+            relation("orders", "r", 6_144, 400, Some(75.0)),
+            relation("orders_pkey", "i", 2_048, 0, Some(100.0)),
+            relation("pg_toast_16384", "t", 128, 12, None),
+        ],
+    }
+}
 
 #[test]
-fn panel_replication_with_data() {
-    let backend = TestBackend::new(140, 12);
+fn panel_buffer_cache_with_report() {
+    let backend = TestBackend::new(100, 9);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
+    app.buffer_cache = Some(make_buffer_cache_report());
 
     terminal.draw(|frame| {
-        super::panels::render_replication(frame, &mut app, frame.area());
+        super::panels::render_buffer_cache(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_replication_empty() {
-    let backend = TestBackend::new(140, 12);
+fn panel_logs_with_levels() {
+    use crate::db::models::LogLevel;
+
+    let backend = TestBackend::new(100, 9);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.server_log = true;
+    app.server_log = crate::server_log::parse_lines(
+        "2024-01-15 12:30:40 UTC [4242] LOG:  checkpoint starting: time\n\
+         2024-01-15 12:30:42 UTC [5151] ERROR:  duplicate key value violates unique constraint \"users_pkey\"\n\
+         2024-01-15 12:30:42 UTC [5151] DETAIL:  Key (id)=(1) already exists.\n\
+         2024-01-15 12:30:42 UTC [5151] STATEMENT:  INSERT INTO users VALUES (1)\n\
+         2024-01-15 12:30:44 UTC [5252] WARNING:  there is no transaction in progress\n",
+    );
+    assert_eq!(app.server_log[2].level, LogLevel::Detail);
 
     terminal.draw(|frame| {
-        super::panels::render_replication(frame, &mut app, frame.area());
+        super::panels::render_logs(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Vacuum Progress
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_vacuum_progress_with_data() {
-    let backend = TestBackend::new(100, 10);
+fn panel_logs_when_tailing_is_off() {
+    let backend = TestBackend::new(80, 5);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_vacuum_progress(frame, &mut app, frame.area());
+        super::panels::render_logs(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_vacuum_progress_empty() {
-    let backend = TestBackend::new(100, 10);
+fn panel_buffer_cache_before_reading() {
+    let backend = TestBackend::new(80, 5);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_vacuum_progress(frame, &mut app, frame.area());
+        super::panels::render_buffer_cache(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Wraparound
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_wraparound_with_data() {
-    let backend = TestBackend::new(100, 10);
+fn panel_index_health_with_findings() {
+    let backend = TestBackend::new(110, 9);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
+    let mut snap = make_snapshot();
+    let mut copy = snap.indexes[1].clone();
+    copy.index_name = "orders_user_id_idx1".to_string();
+    copy.index_definition = "CREATE INDEX orders_user_id_idx1 ON public.orders USING btree (user_id)".to_string();
+    copy.idx_scan = 120;
+    let mut invalid = snap.indexes[1].clone();
+    invalid.index_name = "orders_status_idx".to_string();
+    invalid.index_definition = "CREATE INDEX orders_status_idx ON public.orders USING btree (status)".to_string();
+    invalid.index_size_bytes = 8_192;
+    invalid.is_valid = false;
+    snap.indexes.extend([copy, invalid]);
+    let mut app = make_app(Some(snap));
 
     terminal.draw(|frame| {
-        super::panels::render_wraparound(frame, &mut app, frame.area());
+        super::panels::render_index_health(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_wraparound_empty() {
-    let backend = TestBackend::new(100, 10);
+fn panel_autovacuum_with_starved_and_due_tables() {
+    let backend = TestBackend::new(120, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut earlier = make_snapshot();
+    earlier.timestamp = Utc.with_ymd_and_hms(2024, 1, 15, 12, 20, 45).unwrap();
+    earlier.table_stats[0].n_dead_tup = 30_000;
+    let mut snap = make_snapshot();
+    snap.table_stats[0].n_dead_tup = 40_000;
+    let mut events = snap.table_stats[1].clone();
+    events.relname = "events".to_string();
+    events.n_dead_tup = 9_000;
+    snap.table_stats.push(events);
+    let mut app = make_app(None);
+    app.autovacuum.observe(&earlier, &app.autovacuum_settings());
+    app.autovacuum.observe(&snap, &app.autovacuum_settings());
+    for i in 0..30 {
+        app.metrics.autovacuum_workers.push((i / 6) % 4);
+    }
+    app.snapshot = Some(snap);
 
     terminal.draw(|frame| {
-        super::panels::render_wraparound(frame, &mut app, frame.area());
+        super::panels::render_autovacuum(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Indexes
-// ─────────────────────────────────────────────────────────────────────────────
+fn make_slots_snapshot() -> PgSnapshot {
+    let mut snap = make_snapshot();
+    let logical = |name: &str, active: bool, retained: i64, spill: Option<(i64, i64)>| ReplicationSlot {
+        slot_name: name.to_string(),
+        slot_type: "logical".to_string(),
+        database: Some("app".to_string()),
+        active,
+        restart_lsn: Some("2/1A000000".to_string()),
+        confirmed_flush_lsn: Some("2/1A0001F0".to_string()),
+        wal_retained_bytes: Some(retained),
+        temporary: false,
+        spill_txns: spill.map(|(txns, _)| txns),
+        spill_count: spill.map(|(txns, _)| txns * 3),
+        spill_bytes: spill.map(|(_, bytes)| bytes),
+    };
+    snap.replication_slots.push(logical("old_cdc", false, 42 * 1024 * 1024 * 1024, Some((12, 3 << 30))));
+    snap.replication_slots.push(logical("debezium", true, 2 << 20, Some((0, 0))));
+    snap.subscriptions = vec![Subscription {
+        subname: "orders_sub".to_string(),
+        pid: Some(4242),
+        relcount: 3,
+        received_lsn: Some("1/9F000000".to_string()),
+        last_msg_send_time: None,
+        last_msg_receipt_time: None,
+        latest_end_lsn: None,
+        latest_end_time: None,
+        enabled: true,
+        apply_lag_secs: None,
+        apply_lag_bytes: None,
+        apply_error_count: None,
+        sync_error_count: None,
+        conflict_count: None,
+    }];
+    snap
+}
 
 #[test]
-fn panel_indexes_with_data() {
-    let backend = TestBackend::new(140, 10);
+fn panel_slots_with_subscriptions() {
+    let backend = TestBackend::new(130, 14);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
+    let mut app = make_app(Some(make_slots_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_indexes(frame, &mut app, frame.area());
+        super::panels::render_slots(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_indexes_empty() {
-    let backend = TestBackend::new(140, 10);
+fn panel_logical_replication_with_worker_down() {
+    let backend = TestBackend::new(120, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut snap = make_snapshot();
+    let at = snap.timestamp;
+    let sub = |name: &str, pid: Option<i32>, enabled: bool| Subscription {
+        subname: name.to_string(),
+        pid,
+        relcount: 4,
+        received_lsn: pid.map(|_| "1/9F000000".to_string()),
+        last_msg_send_time: None,
+        last_msg_receipt_time: pid.map(|_| at - chrono::Duration::seconds(3)),
+        latest_end_lsn: pid.map(|_| "1/9E000000".to_string()),
+        latest_end_time: None,
+        enabled,
+        apply_lag_secs: pid.map(|_| 1.25),
+        apply_lag_bytes: pid.map(|_| 16 << 20),
+        apply_error_count: Some(0),
+        sync_error_count: Some(0),
+        conflict_count: Some(0),
+    };
+    let mut failing = sub("billing_sub", None, true);
+    failing.apply_error_count = Some(17);
+    failing.conflict_count = Some(3);
+    snap.subscriptions = vec![
+        failing,
+        sub("orders_sub", Some(4242), true),
+        sub("paused_sub", None, false),
+    ];
+    let mut app = make_app(Some(snap));
 
     terminal.draw(|frame| {
-        super::panels::render_indexes(frame, &mut app, frame.area());
+        super::panels::render_logical_replication(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - Statements (pg_stat_statements)
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_statements_with_data() {
-    let backend = TestBackend::new(140, 10);
+fn overlay_slot_inspect_inactive() {
+    let backend = TestBackend::new(100, 44);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
+    let mut app = make_app(Some(make_slots_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Slot("old_cdc".into()));
 
     terminal.draw(|frame| {
-        super::panels::render_statements(frame, &mut app, frame.area());
+        super::overlay::render_slot_inspect(frame, &app, frame.area(), "old_cdc");
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_statements_empty() {
-    let backend = TestBackend::new(140, 10);
+fn panel_locks_empty() {
+    let backend = TestBackend::new(100, 6);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_statements(frame, &mut app, frame.area());
+        super::panels::render_locks(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Panel Tests - WAL I/O
+// Panel Tests - Blocking
 // ─────────────────────────────────────────────────────────────────────────────
 
 #[test]
-fn panel_wal_io_with_data() {
-    let backend = TestBackend::new(100, 15);
+fn panel_blocking_with_data() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_wal_io(frame, &app, frame.area());
+        super::panels::render_blocking(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_wal_io_empty() {
-    let backend = TestBackend::new(100, 15);
+fn panel_blocking_empty() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::panels::render_wal_io(frame, &app, frame.area());
+        super::panels::render_blocking(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Overlay Tests
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn overlay_help() {
-    let backend = TestBackend::new(90, 35);
+fn panel_blocking_no_snapshot() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.view_mode = ViewMode::Help;
+    let mut app = make_app(None);
 
     terminal.draw(|frame| {
-        super::overlay::render_help(frame, &app, frame.area());
+        super::panels::render_blocking(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Wait Events
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_config() {
-    let backend = TestBackend::new(70, 25);
+fn panel_wait_events_with_data() {
+    let backend = TestBackend::new(80, 8);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.view_mode = ViewMode::Config;
 
     terminal.draw(|frame| {
-        super::overlay::render_config(frame, &app, frame.area());
+        super::panels::render_wait_events(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_confirm_cancel() {
-    let backend = TestBackend::new(60, 10);
+fn panel_wait_events_empty() {
+    let backend = TestBackend::new(80, 8);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_confirm_cancel(frame, 12345, frame.area());
+        super::panels::render_wait_events(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_confirm_kill() {
-    let backend = TestBackend::new(60, 10);
+fn panel_wait_events_selected_row() {
+    let backend = TestBackend::new(80, 8);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.panels.wait_events.select(Some(1));
 
     terminal.draw(|frame| {
-        super::overlay::render_confirm_kill(frame, 12345, frame.area());
+        super::panels::render_wait_events(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Stats Panel Tests
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn stats_panel_with_data() {
-    let backend = TestBackend::new(40, 20);
+fn panel_pooler() {
+    use crate::db::models::{PoolerPool, PoolerStats};
+
+    let backend = TestBackend::new(130, 8);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_snapshot()));
+    let mut snap = make_snapshot();
+    snap.pooler = Some(PoolerStats {
+        version: "PgBouncer 1.22.1".into(),
+        pools: vec![
+            PoolerPool {
+                database: "app".into(),
+                user: "app_rw".into(),
+                pool_mode: "transaction".into(),
+                cl_active: 40,
+                cl_waiting: 3,
+                sv_active: 20,
+                sv_used: 2,
+                maxwait_secs: 1.5,
+                avg_query_us: 1800,
+                avg_xact_us: 4200,
+                avg_wait_us: 350,
+                ..PoolerPool::default()
+            },
+            PoolerPool {
+                database: "reports".into(),
+                user: "analyst".into(),
+                pool_mode: "session".into(),
+                cl_active: 2,
+                sv_active: 2,
+                sv_idle: 3,
+                avg_query_us: 2_500_000,
+                avg_xact_us: 2_600_000,
+                ..PoolerPool::default()
+            },
+        ],
+    });
+    let mut app = make_app(Some(snap));
 
     terminal.draw(|frame| {
-        super::stats_panel::render(frame, &app, frame.area());
+        super::panels::render_pooler(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn stats_panel_empty() {
-    let backend = TestBackend::new(40, 20);
+fn panel_pooler_without_pgbouncer() {
+    let backend = TestBackend::new(80, 5);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::stats_panel::render(frame, &app, frame.area());
+        super::panels::render_pooler(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn stats_panel_no_snapshot() {
-    let backend = TestBackend::new(40, 20);
+fn overlay_wait_event_inspect() {
+    let backend = TestBackend::new(100, 24);
     let mut terminal = Terminal::new(backend).unwrap();
-    let app = make_app(None);
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::WaitEvents;
+    app.view_mode = ViewMode::Inspect(InspectTarget::WaitEvent("IO".into(), "DataFileRead".into()));
 
     terminal.draw(|frame| {
-        super::stats_panel::render(frame, &app, frame.area());
+        super::overlay::render_wait_event_inspect(frame, &app, frame.area(), "IO", "DataFileRead");
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Overlay Inspect Tests
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn overlay_query_inspect() {
-    let backend = TestBackend::new(100, 40);
+fn panel_wait_events_sampled() {
+    use crate::db::models::{WaitEventCount, WaitSampleSource};
+    let backend = TestBackend::new(80, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
-    app.panels.queries.state.select(Some(0));
+    app.config.wait_sampler = true;
+    let count = |t: &str, e: &str, n: i64| WaitEventCount {
+        wait_event_type: t.into(),
+        wait_event: e.into(),
+        count: n,
+    };
+    let start = chrono::Utc::now();
+    for i in 0..4 {
+        app.wait_sampler.push(
+            start + chrono::Duration::milliseconds(250 * i),
+            WaitSampleSource::PgStatActivity,
+            vec![
+                count("Lock", "transactionid", 3),
+                count("IO", "DataFileRead", 2),
+                count("LWLock", "WALWrite", 1),
+                count("Lock", "tuple", 1),
+            ],
+        );
+    }
 
     terminal.draw(|frame| {
-        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+        super::panels::render_wait_events(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_query_inspect_no_selection() {
-    let backend = TestBackend::new(100, 40);
+fn panel_wait_events_sampled_nothing_waiting() {
+    let backend = TestBackend::new(80, 8);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
-    app.view_mode = ViewMode::Inspect(InspectTarget::Query(99999));
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.wait_sampler = true;
 
     terminal.draw(|frame| {
-        super::overlay::render_inspect(frame, &app, frame.area(), 99999);
+        super::panels::render_wait_events(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Table Stats
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_index_inspect() {
-    let backend = TestBackend::new(100, 35);
+fn panel_table_stats_with_data() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Indexes;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_pkey".to_string()));
-    app.panels.indexes.state.select(Some(0));
 
     terminal.draw(|frame| {
-        super::overlay::render_index_inspect(frame, &app, frame.area(), "public.orders_pkey");
+        super::panels::render_table_stats(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_index_inspect_unused() {
-    let backend = TestBackend::new(100, 35);
+fn panel_table_stats_empty() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Indexes;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_status_idx".to_string()));
-    // Select the second index which has 0 scans (unused)
-    app.panels.indexes.state.select(Some(1));
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_index_inspect(frame, &app, frame.area(), "public.orders_status_idx");
+        super::panels::render_table_stats(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Replication
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_statement_inspect() {
-    let backend = TestBackend::new(110, 50);
+fn panel_replication_with_data() {
+    let backend = TestBackend::new(140, 12);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Statements;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Statement(123456789));
-    app.panels.statements.state.select(Some(0));
 
     terminal.draw(|frame| {
-        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123456789);
+        super::panels::render_replication(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
+    // Clicks go to the standby rows, under the section title and header
+    assert_eq!(app.mouse_targets.rows.y, 3);
 }
 
 #[test]
-fn overlay_table_inspect() {
-    let backend = TestBackend::new(110, 55);
+fn panel_replication_empty() {
+    let backend = TestBackend::new(140, 12);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::TableStats;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.orders".to_string()));
-    app.panels.table_stats.state.select(Some(0));
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_table_inspect(frame, &app, frame.area(), "public.orders");
+        super::panels::render_replication(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Vacuum Progress
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_replication_inspect() {
-    let backend = TestBackend::new(100, 45);
+fn panel_vacuum_progress_with_data() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Replication;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Replication(23456));
-    app.panels.replication.select(Some(0));
 
     terminal.draw(|frame| {
-        super::overlay::render_replication_inspect(frame, &app, frame.area(), 23456);
+        super::panels::render_vacuum_progress(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_blocking_inspect() {
-    let backend = TestBackend::new(110, 40);
+fn panel_vacuum_progress_empty() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Blocking;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Blocking(12347));
-    app.panels.blocking.select(Some(0));
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_blocking_inspect(frame, &app, frame.area(), 12347);
+        super::panels::render_vacuum_progress(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_vacuum_inspect() {
-    let backend = TestBackend::new(100, 35);
+fn panel_operations_with_eta() {
+    let backend = TestBackend::new(130, 8);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::VacuumProgress;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Vacuum(34567));
-    app.panels.vacuum.select(Some(0));
+    let reindex = |done: i64| OperationProgress {
+        pid: 45678,
+        kind: OperationKind::CreateIndex,
+        command: "REINDEX CONCURRENTLY".to_string(),
+        datname: Some("production".to_string()),
+        target: "public.orders".to_string(),
+        relid: None,
+        phase: "building index: scanning table".to_string(),
+        work_done: done,
+        work_total: 80_000,
+        elapsed_secs: Some(75.0),
+    };
+    let backup = OperationProgress {
+        pid: 56789,
+        kind: OperationKind::BaseBackup,
+        command: "BASE BACKUP".to_string(),
+        datname: None,
+        target: "pg_basebackup 10.0.0.7".to_string(),
+        relid: None,
+        phase: "streaming database files".to_string(),
+        work_done: 3_000_000_000,
+        work_total: 0,
+        elapsed_secs: Some(600.0),
+    };
+    let mut earlier = make_snapshot();
+    earlier.operations = vec![reindex(20_000), backup.clone()];
+    let mut snap = make_snapshot();
+    snap.timestamp = earlier.timestamp + Duration::seconds(30);
+    snap.operations = vec![reindex(32_000), backup];
+
+    let mut app = make_app(None);
+    app.update(earlier);
+    app.update(snap);
 
     terminal.draw(|frame| {
-        super::overlay::render_vacuum_inspect(frame, &app, frame.area(), 34567);
+        super::panels::render_operations(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Wraparound
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_wraparound_inspect() {
-    let backend = TestBackend::new(100, 40);
+fn panel_wraparound_with_data() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.bottom_panel = BottomPanel::Wraparound;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Wraparound("production".to_string()));
-    app.panels.wraparound.select(Some(0));
 
     terminal.draw(|frame| {
-        super::overlay::render_wraparound_inspect(frame, &app, frame.area(), "production");
+        super::panels::render_wraparound(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_wraparound_inspect_warning() {
-    let backend = TestBackend::new(100, 40);
+fn panel_wraparound_empty() {
+    let backend = TestBackend::new(100, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-
-    // Create snapshot with high wraparound percentage
-    let mut snapshot = make_snapshot();
-    snapshot.wraparound = vec![WraparoundInfo {
-        datname: "critical_db".to_string(),
-        xid_age: 1_500_000_000,
-        xids_remaining: 647_000_000,
-        pct_towards_wraparound: 70.0,
-    }];
-
-    let mut app = make_app(Some(snapshot));
-    app.bottom_panel = BottomPanel::Wraparound;
-    app.view_mode = ViewMode::Inspect(InspectTarget::Wraparound("critical_db".to_string()));
-    app.panels.wraparound.select(Some(0));
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_wraparound_inspect(frame, &app, frame.area(), "critical_db");
+        super::panels::render_wraparound(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Overlay Choice/Batch Dialog Tests
+// Panel Tests - Indexes
 // ─────────────────────────────────────────────────────────────────────────────
 
 #[test]
-fn overlay_cancel_choice() {
-    let backend = TestBackend::new(80, 20);
+fn panel_indexes_with_data() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_cancel_choice(frame, 12345, &[12345, 12346, 12347], "SELECT", frame.area());
+        super::panels::render_indexes(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_kill_choice() {
-    let backend = TestBackend::new(80, 20);
+fn panel_indexes_empty() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_empty_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_kill_choice(frame, 12345, &[12345, 12346, 12347], "SELECT", frame.area());
+        super::panels::render_indexes(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - Statements (pg_stat_statements)
+// ─────────────────────────────────────────────────────────────────────────────
+
 #[test]
-fn overlay_confirm_cancel_batch() {
-    let backend = TestBackend::new(80, 20);
+fn panel_statements_with_data() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_confirm_cancel_batch(frame, &[12345, 12346, 12347], frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_confirm_cancel_batch_many() {
-    let backend = TestBackend::new(80, 20);
+fn panel_statements_wide_terminal() {
+    let backend = TestBackend::new(190, 8);
     let mut terminal = Terminal::new(backend).unwrap();
-    let many_pids: Vec<i32> = (1..=15).map(|i| 12340 + i).collect();
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_confirm_cancel_batch(frame, &many_pids, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn overlay_confirm_kill_batch() {
-    let backend = TestBackend::new(80, 22);
+fn panel_statements_narrow_terminal() {
+    let backend = TestBackend::new(80, 8);
     let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
 
     terminal.draw(|frame| {
-        super::overlay::render_confirm_kill_batch(frame, &[12345, 12346, 12347], frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Active Queries Panel Tests
-// ─────────────────────────────────────────────────────────────────────────────
-
 #[test]
-fn panel_active_queries_with_data() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_with_hidden_columns() {
+    let backend = TestBackend::new(100, 8);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
+    let columns = BottomPanel::Statements.descriptor().columns;
+    for column in ["Stddev", "Hit%", "Reads", "I/O", "Temp"] {
+        app.config.hidden_columns.toggle("statements", column, columns);
+    }
 
     terminal.draw(|frame| {
-        super::active_queries::render(frame, &mut app, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_active_queries_empty() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_starred() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(Some(make_empty_snapshot()));
+    let mut app = make_app(Some(make_snapshot()));
+    app.starred.toggle(123_456_789);
 
     terminal.draw(|frame| {
-        super::active_queries::render(frame, &mut app, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_active_queries_no_snapshot() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_row_color_rule() {
+    use crate::row_colors::{RowColor, RowColorRule, RowColumn};
+
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
-    let mut app = make_app(None);
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.row_colors = vec![RowColorRule {
+        above: Some(0.25),
+        color: RowColor::Warn,
+        ..RowColorRule::new(RowColumn::MeanExecTime)
+    }];
 
     terminal.draw(|frame| {
-        super::active_queries::render(frame, &mut app, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
+    // The SQL and the plain numeric columns alike take the rule's color
+    let buffer = terminal.backend().buffer();
+    for x in [1, 52, 76] {
+        assert_eq!(buffer[(x, 2)].fg, crate::ui::theme::Theme::border_warn(), "column {x}");
+    }
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+/// `make_snapshot`'s statement baselined at a fifth of its current mean
+fn baseline_at_a_fifth(app: &mut App) {
+    let mut before = make_snapshot().stat_statements;
+    before[0].mean_exec_time = 0.1;
+    app.baselines
+        .rebaseline(&before, Utc.with_ymd_and_hms(2024, 1, 8, 9, 0, 0).unwrap());
+}
+
 #[test]
-fn panel_active_queries_with_filter() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_regressed() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.filter.text = "SELECT".to_string();
-    app.filter.active = true;
-    app.bottom_panel = BottomPanel::Queries;
+    baseline_at_a_fifth(&mut app);
 
     terminal.draw(|frame| {
-        super::active_queries::render(frame, &mut app, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_active_queries_sorted_by_duration() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_showing_ignored() {
+    let backend = TestBackend::new(140, 10);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
-    app.panels.queries.sort_column = SortColumn::Duration;
-    app.panels.queries.sort_ascending = false;
+    app.config.ignore.toggle_statement(123_456_789);
+    app.show_ignored = true;
 
     terminal.draw(|frame| {
-        super::active_queries::render(frame, &mut app, frame.area());
+        super::panels::render_statements(frame, &mut app, frame.area());
     }).unwrap();
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
 #[test]
-fn panel_active_queries_sorted_ascending() {
-    let backend = TestBackend::new(140, 12);
+fn panel_statements_empty() {
+    let backend = TestBackend::new(140, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_empty_snapshot()));
+
+    terminal.draw(|frame| {
+        super::panels::render_statements(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Panel Tests - WAL I/O
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn panel_wal_io_with_data() {
+    let backend = TestBackend::new(100, 15);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    push_wal_io_rates(&mut app);
+
+    terminal.draw(|frame| {
+        super::panels::render_wal_io(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_wal_io_plain_mode() {
+    let backend = TestBackend::new(140, 15);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.plain_flag = true;
+    push_wal_io_rates(&mut app);
+
+    terminal.draw(|frame| {
+        super::panels::render_wal_io(frame, &app, frame.area());
+    }).unwrap();
+
+    // Low and high instead of sparklines
+    let screen = buffer_to_string(&terminal);
+    assert!(!screen.chars().any(|c| super::sparkline::BLOCKS[1..].contains(&c)), "{screen}");
+    insta::assert_snapshot!(screen);
+}
+
+fn push_wal_io_rates(app: &mut App) {
+    let rates = &mut app.metrics.wal_io;
+    for i in 0..30 {
+        let i = f64::from(i);
+        rates.wal_records.push_delta(5000.0 + i * 100.0, 1.0);
+        rates.wal_buffers_full.push_delta(i % 3.0, 1.0);
+        rates.wal_write_time.push_delta(20.0 + i, 1.0);
+        rates.wal_sync_time.push_delta(5.0 + i % 4.0, 1.0);
+        rates.checkpoints.push_delta(if i % 10.0 == 0.0 { 1.0 } else { 0.0 }, 1.0);
+        rates.checkpoints_forced.push_delta(0.0, 1.0);
+        rates.checkpoint_write_time.push_delta(100.0 + i * 5.0, 1.0);
+        rates.checkpoint_sync_time.push_delta(2.0, 1.0);
+        rates.archived.push_delta(0.05, 1.0);
+        rates.archive_failed.push_delta(0.0, 1.0);
+        rates.buffers_checkpoint.push_delta(200.0 + i * 10.0, 1.0);
+        rates.buffers_backend.push_delta(4.0, 1.0);
+        rates.buffers_clean.push_delta(50.0 + i, 1.0);
+        rates.buffers_throttled.push_delta(0.0, 1.0);
+        rates.buffers_alloc.push_delta(1000.0 + i * 20.0, 1.0);
+    }
+}
+
+#[test]
+fn panel_checkpoints_with_history() {
+    use crate::app::CheckpointEvent;
+
+    let backend = TestBackend::new(120, 18);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let snap = make_snapshot();
+    let start = snap.timestamp - Duration::minutes(15);
+    let mut app = make_app(Some(snap));
+    let setting = |name: &str, value: &str, unit: Option<&str>| PgSetting {
+        name: name.to_string(),
+        setting: value.to_string(),
+        unit: unit.map(String::from),
+        category: "Write-Ahead Log / Checkpoints".to_string(),
+        short_desc: None,
+        context: "sighup".to_string(),
+        source: "configuration file".to_string(),
+        pending_restart: false,
+        boot_val: None,
+        reset_val: None,
+    };
+    app.server_info.settings = vec![
+        setting("checkpoint_timeout", "300", Some("s")),
+        setting("max_wal_size", "1024", Some("MB")),
+    ];
+    let rates = &mut app.metrics.wal_io;
+    for i in 0..30 {
+        let i = f64::from(i);
+        let checkpoint = i % 10.0 == 0.0;
+        rates.checkpoints.push_delta(if checkpoint { 1.0 } else { 0.0 }, 30.0);
+        rates.checkpoints_forced.push_delta(if i == 20.0 { 1.0 } else { 0.0 }, 30.0);
+        rates.checkpoint_write_time.push_delta(if checkpoint { 27_000.0 } else { 0.0 }, 30.0);
+        rates.checkpoint_sync_time.push_delta(if checkpoint { 40.0 } else { 0.0 }, 30.0);
+        rates.buffers_checkpoint.push_delta(if checkpoint { 9000.0 } else { 0.0 }, 30.0);
+        rates.buffers_backend.push_delta(30.0 + i, 30.0);
+        rates.buffers_clean.push_delta(600.0, 30.0);
+    }
+    for (minutes, forced) in [(0, false), (5, false), (10, true)] {
+        app.metrics.checkpoint_log.push(CheckpointEvent {
+            count: 1,
+            forced: i64::from(forced),
+            write_ms: 27_000.0,
+            sync_ms: 40.0,
+            buffers: 9000,
+        });
+        app.metrics.checkpoint_log.stamp(start + Duration::minutes(minutes));
+    }
+
+    terminal.draw(|frame| {
+        super::panels::render_checkpoints(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_wal_io_archive_backlog() {
+    let backend = TestBackend::new(100, 15);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.wal_dir = Some(WalDirStats {
+        segments: 80,
+        bytes: 80 * 16 * 1024 * 1024,
+        archive_ready: Some(12),
+    });
+    let mut app = make_app(Some(snap));
+    app.config.wal_dir_warn_mb = 1024;
+
+    terminal.draw(|frame| {
+        super::panels::render_wal_io(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_wal_io_by_backend() {
+    use crate::app::IoRate;
+
+    let backend = TestBackend::new(100, 22);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.io_stats = vec![Default::default()];
+    let mut app = make_app(Some(snap));
+    app.server_info.version = "PostgreSQL 16.2".into();
+    let rate = |backend_type: &str, context: &str, reads, writes, extends, fsyncs| IoRate {
+        backend_type: backend_type.into(),
+        context: context.into(),
+        reads,
+        writes,
+        extends,
+        fsyncs,
+    };
+    app.metrics.wal_io.io = vec![
+        rate("autovacuum worker", "vacuum", 820.0, 410.0, 0.0, 0.0),
+        rate("background writer", "normal", 0.0, 0.0, 0.0, 0.0),
+        rate("checkpointer", "normal", 0.0, 2400.0, 0.0, 12.0),
+        rate("client backend", "bulkread", 15_000.0, 0.0, 0.0, 0.0),
+        rate("client backend", "normal", 3200.0, 35.0, 18.0, 0.0),
+    ];
+
+    terminal.draw(|frame| {
+        super::panels::render_wal_io(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_wal_io_empty() {
+    let backend = TestBackend::new(100, 15);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_app(Some(make_empty_snapshot()));
+
+    terminal.draw(|frame| {
+        super::panels::render_wal_io(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Overlay Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn overlay_help() {
+    let backend = TestBackend::new(90, 35);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Help;
+
+    terminal.draw(|frame| {
+        super::overlay::render_help(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_help_scrolled_to_end() {
+    let backend = TestBackend::new(90, 35);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Help;
+
+    // The first draw measures the content, so G lands on the last line
+    let draw = |terminal: &mut Terminal<TestBackend>, app: &App| {
+        terminal.draw(|frame| {
+            super::overlay::render_help(frame, app, frame.area());
+        }).unwrap();
+    };
+    draw(&mut terminal, &app);
+    app.handle_key(crossterm::event::KeyEvent::new(
+        crossterm::event::KeyCode::Char('G'),
+        crossterm::event::KeyModifiers::SHIFT,
+    ));
+    draw(&mut terminal, &app);
+
+    let extent = app.overlay_extent.get().unwrap();
+    assert_eq!(app.overlay_scroll, extent.max_scroll);
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn help_lists_what_e_does_in_the_current_panel() {
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Help;
+    let help = |app: &App| {
+        let mut terminal = Terminal::new(TestBackend::new(90, 200)).unwrap();
+        terminal.draw(|frame| {
+            super::overlay::render_help(frame, app, frame.area());
+        }).unwrap();
+        buffer_to_string(&terminal)
+    };
+
+    let queries = help(&app);
+    assert!(queries.contains("EXPLAIN the selected query"));
+    assert!(!queries.contains("Export graphs"));
+
+    app.bottom_panel = BottomPanel::TableStats;
+    let tables = help(&app);
+    assert!(tables.contains("Export graphs as SVG + PNG"));
+    assert!(!tables.contains("EXPLAIN the selected"));
+}
+
+#[test]
+fn overlay_glossary_statements() {
+    let backend = TestBackend::new(90, 30);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Statements;
+    app.panels.statements.sort_column = crate::app::StatementSortColumn::MeanTime;
+    app.view_mode = ViewMode::Glossary(BottomPanel::Statements);
+
+    terminal.draw(|frame| {
+        super::overlay::render_glossary(frame, &app, BottomPanel::Statements, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_activity_trail() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(80, 16);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: false,
+        events: make_ui_events(),
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
+    });
+    app.view_mode = ViewMode::Activity;
+
+    terminal.draw(|frame| {
+        super::overlay::render_activity(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_activity_annotations() {
+    use crate::app::{Annotation, ReplayState};
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let mut events = make_ui_events();
+    for (min, text) in [(25, "deployed v2.3"), (40, "rolled back")] {
+        events.push(UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, min, 0).unwrap(),
+            kind: UiEventKind::Annotation { text: text.to_string() },
+            via: None,
+        });
+    }
+    events.sort_by_key(|e| e.timestamp);
+    app.annotations = Annotation::from_events(&events);
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: false,
+        events,
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
+    });
+    app.view_mode = ViewMode::Activity;
+
+    terminal.draw(|frame| {
+        super::overlay::render_activity(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_activity_settings_changed() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(80, 18);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let mut events = make_ui_events();
+    events.insert(
+        1,
+        UiEvent {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 29, 30).unwrap(),
+            kind: UiEventKind::SettingChanged {
+                name: "enable_seqscan".to_string(),
+                from: "on".to_string(),
+                to: "off".to_string(),
+            },
+            via: None,
+        },
+    );
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: false,
+        events,
+        interpolation: None,
+        compare: None,
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
+    });
+    app.view_mode = ViewMode::Activity;
+
+    terminal.draw(|frame| {
+        super::overlay::render_activity(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_config() {
+    let backend = TestBackend::new(70, 25);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Config;
+
+    terminal.draw(|frame| {
+        super::overlay::render_config(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_config_row_color_rules() {
+    use crate::config::ConfigItem;
+    use crate::row_colors::{RowColor, RowColorRule, RowColumn};
+
+    let backend = TestBackend::new(120, 60);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Config;
+    app.config.row_colors = vec![
+        RowColorRule::new(RowColumn::MeanExecTime),
+        RowColorRule { color: RowColor::Warn, ..RowColorRule::new(RowColumn::DeadRatio) },
+    ];
+    app.config_overlay.selected = ConfigItem::ALL.len() + 1;
+
+    terminal.draw(|frame| {
+        super::overlay::render_config(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_column_chooser() {
+    let backend = TestBackend::new(80, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Indexes;
+    let columns = BottomPanel::Indexes.descriptor().columns;
+    app.config.hidden_columns.toggle("indexes", "Tup Read", columns);
+    app.column_cursor = 2;
+    app.view_mode = ViewMode::Columns;
+
+    terminal.draw(|frame| {
+        super::overlay::render_columns(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_connections() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.switcher.entries = vec![
+        crate::app::ConnectionEntry {
+            label: "postgres@primary:5432/app".into(),
+            status: "12 conns · 3 active".into(),
+        },
+        crate::app::ConnectionEntry {
+            label: "postgres@replica1:5432/app".into(),
+            status: "error".into(),
+        },
+    ];
+    app.switcher.selected = 1;
+    app.view_mode = ViewMode::Connections;
+
+    terminal.draw(|frame| {
+        super::overlay::render_connections(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_cancel() {
+    let backend = TestBackend::new(60, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_cancel(frame, 12345, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_kill() {
+    let backend = TestBackend::new(60, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_kill(frame, 12345, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_maintenance() {
+    let backend = TestBackend::new(100, 30);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let reindex = crate::maintenance::Maintenance::Reindex {
+        schema: "public".into(),
+        index: "orders_email_idx".into(),
+    };
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_maintenance(frame, &reindex, 16, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Stats Panel Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn stats_panel_with_data() {
+    let backend = TestBackend::new(40, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_app(Some(make_snapshot()));
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn stats_panel_with_aborted_transactions() {
+    let backend = TestBackend::new(40, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_app(Some(make_transaction_snapshot()));
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn stats_panel_with_idle_txn_watchdog() {
+    let backend = TestBackend::new(40, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.idle_txn_watchdog_secs = 120;
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn stats_panel_empty() {
+    let backend = TestBackend::new(40, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_app(Some(make_empty_snapshot()));
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn stats_panel_no_snapshot() {
+    let backend = TestBackend::new(40, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_app(None);
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Overlay Inspect Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn overlay_query_inspect() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+    app.panels.queries.state.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+/// Two parallel workers helping query 12345, and an autovacuum worker
+fn background_workers() -> Vec<BackgroundWorker> {
+    let worker = |pid, backend_type: &str, leader_pid, wait_event: Option<&str>| BackgroundWorker {
+        pid,
+        backend_type: backend_type.to_string(),
+        datname: Some("production".to_string()),
+        state: Some("active".to_string()),
+        wait_event_type: wait_event.map(|_| "IO".to_string()),
+        wait_event: wait_event.map(str::to_string),
+        duration_secs: 12.5,
+        query: Some("SELECT * FROM users WHERE email = 'test@example.com'".to_string()),
+        leader_pid,
+    };
+    vec![
+        BackgroundWorker {
+            query: Some("autovacuum: VACUUM public.orders".to_string()),
+            duration_secs: 340.0,
+            ..worker(23456, "autovacuum worker", None, Some("DataFileRead"))
+        },
+        worker(34567, "parallel worker", Some(12345), Some("DataFileRead")),
+        worker(34568, "parallel worker", Some(12345), None),
+    ]
+}
+
+#[test]
+fn panel_backends_with_parallel_workers() {
+    let backend = TestBackend::new(140, 9);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.background_workers = background_workers();
+    let mut app = make_app(Some(snap));
+
+    terminal.draw(|frame| {
+        super::panels::render_backends(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_sessions_sampled() {
+    let backend = TestBackend::new(140, 6);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let snap = make_snapshot();
+    let start = snap.timestamp;
+    let mut app = make_app(Some(snap.clone()));
+    app.sessions.observe(start - Duration::seconds(60), &snap.active_queries, &[]);
+    app.sessions.observe(start, &snap.active_queries, &[]);
+
+    terminal.draw(|frame| {
+        super::panels::render_sessions(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_parallel_workers() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.background_workers = background_workers();
+    let mut app = make_app(Some(snap));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_progress_estimated() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    // Running 5.5 s against a 4 s mean
+    snap.active_queries[0].query_id = Some(123_456_789);
+    snap.stat_statements[0].mean_exec_time = 4000.0;
+    let mut app = make_app(Some(snap));
+    app.server_info.role_settings = vec![RoleSetting {
+        role: Some("app_user".into()),
+        database: None,
+        name: "statement_timeout".into(),
+        value: "10s".into(),
+    }];
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_progress_reported() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let index_build = |done: i64| OperationProgress {
+        pid: 12345,
+        kind: OperationKind::CreateIndex,
+        command: "CREATE INDEX".to_string(),
+        datname: Some("production".to_string()),
+        target: "public.users".to_string(),
+        relid: None,
+        phase: "building index: scanning table".to_string(),
+        work_done: done,
+        work_total: 80_000,
+        elapsed_secs: Some(5.5),
+    };
+    let mut earlier = make_snapshot();
+    earlier.operations = vec![index_build(20_000)];
+    let mut snap = make_snapshot();
+    snap.timestamp = earlier.timestamp + Duration::seconds(30);
+    snap.operations = vec![index_build(32_000)];
+    let mut app = make_app(None);
+    app.update(earlier);
+    app.update(snap);
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_with_search() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+    app.overlay_search.query = "USERS".into();
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    // Matched case-insensitively on the query line, which is highlighted
+    assert_eq!(*app.overlay_search.matches.borrow(), vec![11]);
+    let cell = &terminal.backend().buffer()[(32, 18)];
+    assert_eq!(cell.symbol(), "u");
+    assert_eq!(cell.bg, crate::ui::theme::Theme::border_active());
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_aborted_transaction() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_transaction_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12346));
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12346);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_blocked() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_lock_wait_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(12345));
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 12345);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_query_inspect_no_selection() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_empty_snapshot()));
+    app.view_mode = ViewMode::Inspect(InspectTarget::Query(99999));
+
+    terminal.draw(|frame| {
+        super::overlay::render_inspect(frame, &app, frame.area(), 99999);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_index_inspect() {
+    let backend = TestBackend::new(100, 35);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Indexes;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_pkey".to_string()));
+    app.panels.indexes.state.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_index_inspect(frame, &app, frame.area(), "public.orders_pkey");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_index_inspect_unused() {
+    let backend = TestBackend::new(100, 35);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Indexes;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Index("public.orders_status_idx".to_string()));
+    // Select the second index which has 0 scans (unused)
+    app.panels.indexes.state.select(Some(1));
+
+    terminal.draw(|frame| {
+        super::overlay::render_index_inspect(frame, &app, frame.area(), "public.orders_status_idx");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect() {
+    let backend = TestBackend::new(110, 50);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Statements;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Statement(123456789));
+    app.panels.statements.state.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123456789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect_with_interval_trends() {
+    let backend = TestBackend::new(110, 56);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    let mut app = make_app(None);
+    // The statement slows down and starts reading from disk
+    for (calls, total, read) in [(0, 0.0, 0.0), (100, 200.0, 0.0), (200, 500.0, 10.0), (300, 1000.0, 60.0), (400, 1800.0, 150.0)] {
+        let stmt = &mut snap.stat_statements[0];
+        (stmt.calls, stmt.total_exec_time, stmt.blk_read_time) = (calls, total, read);
+        app.update(snap.clone());
+    }
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123_456_789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+/// `make_snapshot` read through pg_stat_monitor
+fn with_statement_monitor(mut snap: PgSnapshot) -> PgSnapshot {
+    use crate::db::models::{MonitorBucket, MonitorStatement, StatementMonitor};
+    use chrono::TimeZone;
+
+    snap.extensions.pg_stat_monitor = true;
+    let start = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+    snap.statement_monitor = Some(StatementMonitor {
+        ranges: vec![(0.0, Some(1.0)), (1.0, Some(3.0)), (3.0, Some(10.0)), (10.0, Some(100.0)), (100.0, None)],
+        statements: vec![MonitorStatement {
+            queryid: 123_456_789,
+            errors: 12,
+            resp_calls: vec![2000, 6000, 1500, 450, 50],
+            history: (0..8)
+                .map(|i| MonitorBucket {
+                    start: start + chrono::Duration::minutes(i),
+                    calls: 1250,
+                    total_exec_time: 1250.0 * (2.0 + i as f64 * 0.5),
+                })
+                .collect(),
+        }],
+    });
+    snap
+}
+
+#[test]
+fn panel_statements_from_stat_monitor() {
+    let backend = TestBackend::new(150, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(with_statement_monitor(make_snapshot())));
+
+    terminal.draw(|frame| {
+        super::panels::render_statements(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect_stat_monitor() {
+    let backend = TestBackend::new(110, 64);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(with_statement_monitor(make_snapshot())));
+    app.bottom_panel = BottomPanel::Statements;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Statement(123456789));
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123456789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect_with_note() {
+    let backend = TestBackend::new(110, 50);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.starred.set_note(123_456_789, "known-bad report query — ticket DB-123");
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123_456_789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect_regressed() {
+    let backend = TestBackend::new(110, 50);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    baseline_at_a_fifth(&mut app);
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123_456_789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_statement_inspect_with_latency_percentiles() {
+    use crate::db::models::StatementSample;
+    let backend = TestBackend::new(110, 50);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.latency_sampler = true;
+    app.starred.toggle(123_456_789);
+    let t0 = chrono::Utc::now();
+    for (i, (calls, total)) in [(0, 0.0), (40, 100.0), (50, 400.0)].into_iter().enumerate() {
+        app.latency.push(
+            t0 + chrono::Duration::seconds(i as i64),
+            &[StatementSample { queryid: 123_456_789, calls, total_exec_time: total }],
+        );
+    }
+
+    terminal.draw(|frame| {
+        super::overlay::render_statement_inspect(frame, &app, frame.area(), 123_456_789);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_table_inspect() {
+    let backend = TestBackend::new(110, 55);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::TableStats;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Table("public.orders".to_string()));
+    app.panels.table_stats.state.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_table_inspect(frame, &app, frame.area(), "public.orders");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_table_inspect_schema() {
+    use crate::app::TableSchemaView;
+    use crate::db::models::{ColumnDef, ForeignKeyDef, IndexDef, TableSchema, TriggerDef};
+
+    let backend = TestBackend::new(110, 75);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let column = |name: &str, data_type: &str, not_null: bool, default: Option<&str>| ColumnDef {
+        name: name.into(),
+        data_type: data_type.into(),
+        not_null,
+        default: default.map(Into::into),
+    };
+    app.table_schema = Some(TableSchemaView::Loaded {
+        key: "public.orders".into(),
+        schema: TableSchema {
+            columns: vec![
+                column("id", "bigint", true, Some("nextval('orders_id_seq'::regclass)")),
+                column("customer_id", "bigint", true, None),
+                column("status", "character varying(32)", false, Some("'new'::character varying")),
+            ],
+            indexes: vec![
+                IndexDef {
+                    name: "orders_pkey".into(),
+                    definition: "CREATE UNIQUE INDEX orders_pkey ON public.orders USING btree (id)".into(),
+                    is_primary: true,
+                    is_unique: true,
+                    is_valid: true,
+                    size_bytes: 2_400_000,
+                    idx_scan: 98_000,
+                    idx_tup_read: 120_000,
+                    idx_tup_fetch: 98_000,
+                },
+                IndexDef {
+                    name: "orders_status_idx".into(),
+                    definition: "CREATE INDEX orders_status_idx ON public.orders USING btree (status)".into(),
+                    is_primary: false,
+                    is_unique: false,
+                    is_valid: false,
+                    size_bytes: 800_000,
+                    idx_scan: 0,
+                    idx_tup_read: 0,
+                    idx_tup_fetch: 0,
+                },
+            ],
+            foreign_keys: vec![
+                ForeignKeyDef {
+                    name: "orders_customer_id_fkey".into(),
+                    other_table: "customers".into(),
+                    definition: "FOREIGN KEY (customer_id) REFERENCES customers(id)".into(),
+                    incoming: false,
+                },
+                ForeignKeyDef {
+                    name: "order_items_order_id_fkey".into(),
+                    other_table: "order_items".into(),
+                    definition: "FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE".into(),
+                    incoming: true,
+                },
+            ],
+            triggers: vec![TriggerDef {
+                name: "orders_touch".into(),
+                definition: "CREATE TRIGGER orders_touch BEFORE UPDATE ON public.orders FOR EACH ROW EXECUTE FUNCTION touch()".into(),
+                enabled: false,
+            }],
+        },
+    });
+
+    terminal.draw(|frame| {
+        super::overlay::render_table_inspect(frame, &app, frame.area(), "public.orders");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_table_inspect_bloat_trend() {
+    use crate::db::models::BloatSource;
+    use crate::db::queries::TableBloat;
+    use std::collections::HashMap;
+
+    let backend = TestBackend::new(110, 55);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let t0 = chrono::Utc::now();
+    for (hours, pct) in [(0, 12.0), (2, 13.5), (3, 15.0)] {
+        let bloat = HashMap::from([(
+            "public.orders".to_string(),
+            TableBloat { bloat_bytes: (pct * 1_000_000.0) as i64, bloat_pct: pct, source: BloatSource::Statistical },
+        )]);
+        app.bloat_history.record(t0 + chrono::Duration::hours(hours), &bloat, &HashMap::new());
+    }
+
+    terminal.draw(|frame| {
+        super::overlay::render_table_inspect(frame, &app, frame.area(), "public.orders");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_table_inspect_measured_bloat() {
+    let backend = TestBackend::new(110, 60);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.server_info.extensions.pgstattuple = true;
+    app.set_measured_bloat(
+        "public",
+        "orders",
+        Ok(MeasuredBloat {
+            table_len: 52_428_800,
+            tuple_percent: 71.4,
+            dead_tuple_percent: 6.2,
+            free_space: 11_534_336,
+            free_percent: 22.0,
+            indexes: vec![MeasuredIndex {
+                index_name: "orders_pkey".into(),
+                index_size: 4_194_304,
+                avg_leaf_density: 58.3,
+                leaf_fragmentation: 12.5,
+            }],
+        }),
+    );
+
+    terminal.draw(|frame| {
+        super::overlay::render_table_inspect(frame, &app, frame.area(), "public.orders");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_replication_inspect() {
+    let backend = TestBackend::new(100, 45);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Replication;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Replication(23456));
+    app.panels.replication.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_replication_inspect(frame, &app, frame.area(), 23456);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_replication_inspect_with_lag_history() {
+    let backend = TestBackend::new(100, 45);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    for i in 0..30 {
+        let mut snap = make_snapshot();
+        let lag = f64::from(i % 10) * 0.15;
+        snap.replication[0].write_lag_secs = Some(lag / 3.0);
+        snap.replication[0].flush_lag_secs = Some(lag / 2.0);
+        snap.replication[0].replay_lag_secs = Some(lag);
+        app.metrics.push_snapshot_metrics(&snap);
+        app.snapshot = Some(snap);
+    }
+    app.view_mode = ViewMode::Inspect(InspectTarget::Replication(23456));
+
+    terminal.draw(|frame| {
+        super::overlay::render_replication_inspect(frame, &app, frame.area(), 23456);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_blocking_inspect() {
+    let backend = TestBackend::new(110, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Blocking;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Blocking(12347));
+    app.panels.blocking.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_blocking_inspect(frame, &app, frame.area(), 12347);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_vacuum_inspect() {
+    let backend = TestBackend::new(100, 35);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::VacuumProgress;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Vacuum(34567));
+    app.panels.vacuum.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_vacuum_inspect(frame, &app, frame.area(), 34567);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_wraparound_inspect() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Wraparound;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Wraparound("production".to_string()));
+    app.panels.wraparound.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_wraparound_inspect(frame, &app, frame.area(), "production");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_wraparound_inspect_warning() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    // Create snapshot with high wraparound percentage
+    let mut snapshot = make_snapshot();
+    snapshot.wraparound = vec![WraparoundInfo {
+        datname: "critical_db".to_string(),
+        xid_age: 1_500_000_000,
+        xids_remaining: 647_000_000,
+        pct_towards_wraparound: 70.0,
+    }];
+
+    let mut app = make_app(Some(snapshot));
+    app.bottom_panel = BottomPanel::Wraparound;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Wraparound("critical_db".to_string()));
+    app.panels.wraparound.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::overlay::render_wraparound_inspect(frame, &app, frame.area(), "critical_db");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_wraparound_inspect_with_freeze_suggestions() {
+    use crate::app::WraparoundTablesView;
+
+    let backend = TestBackend::new(100, 56);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Wraparound;
+    app.view_mode = ViewMode::Inspect(InspectTarget::Wraparound("production".to_string()));
+    let table = |relname: &str, via_toast: bool, xid_age: i32, size_bytes: i64| WraparoundTable {
+        schemaname: "public".into(),
+        relname: relname.into(),
+        via_toast,
+        xid_age,
+        size_bytes,
+    };
+    app.wraparound_tables = Some(WraparoundTablesView::Loaded {
+        datname: "production".into(),
+        tables: vec![
+            table("events", true, 210_000_000, 52_428_800_000),
+            table("orders", false, 150_000_000, 2_147_483_648),
+            table("events", false, 120_000_000, 52_428_800_000),
+            table("users", false, 40_000_000, 16_777_216),
+        ],
+    });
+
+    terminal.draw(|frame| {
+        super::overlay::render_wraparound_inspect(frame, &app, frame.area(), "production");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Overlay Choice/Batch Dialog Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn overlay_cancel_choice() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_cancel_choice(frame, 12345, &[12345, 12346, 12347], "SELECT", frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_kill_choice() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_kill_choice(frame, 12345, &[12345, 12346, 12347], "SELECT", frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_cancel_batch() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_cancel_batch(frame, &[12345, 12346, 12347], frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_cancel_batch_many() {
+    let backend = TestBackend::new(80, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let many_pids: Vec<i32> = (1..=15).map(|i| 12340 + i).collect();
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_cancel_batch(frame, &many_pids, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_confirm_kill_batch() {
+    let backend = TestBackend::new(80, 22);
+    let mut terminal = Terminal::new(backend).unwrap();
+
+    terminal.draw(|frame| {
+        super::overlay::render_confirm_kill_batch(frame, &[12345, 12346, 12347], frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Active Queries Panel Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn panel_active_queries_with_data() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_expanded_row() {
+    let backend = TestBackend::new(140, 14);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.active_queries[0].query = Some(
+        "SELECT o.id, o.total, c.name FROM orders o JOIN customers c ON c.id = o.customer_id \
+         WHERE o.created_at > now() - interval '7 days' AND o.status = 'shipped' \
+         ORDER BY o.total DESC LIMIT 100"
+            .into(),
+    );
+    let pid = snap.active_queries[0].pid;
+    let mut app = make_app(Some(snap));
+    app.expanded_query = Some(pid);
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_expanded_row_scrolls() {
+    let backend = TestBackend::new(100, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    let columns: Vec<String> = (1..=40).map(|i| format!("o.column_{i}")).collect();
+    snap.active_queries[0].query = Some(format!("SELECT {} FROM orders o", columns.join(", ")));
+    let pid = snap.active_queries[0].pid;
+    let mut app = make_app(Some(snap));
+    // Expanded with o on the selected row, which the table keeps in view
+    app.panels.queries.state.select(Some(1));
+    app.expanded_query = Some(pid);
+    app.expanded_scroll = 2;
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+    let scrolled = buffer_to_string(&terminal);
+    assert!(scrolled.contains("↑ 2 more lines"));
+    assert!(scrolled.contains("more lines (Shift+↓)"));
+    insta::assert_snapshot!(scrolled);
+
+    // Scrolling past the end stops at the last line
+    app.expanded_scroll = 1000;
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+    assert!(!buffer_to_string(&terminal).contains("(Shift+↓)"));
+    assert!(app.expanded_scroll < 1000);
+    assert!(buffer_to_string(&terminal).contains("orders o"));
+}
+
+#[test]
+fn panel_active_queries_grouped_by_fingerprint() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    let template = snap.active_queries[0].clone();
+    for (pid, id, duration_secs) in [(20001, 17, 4.0), (20002, 98, 61.0), (20003, 5, 0.2)] {
+        let mut query = template.clone();
+        query.pid = pid;
+        query.query = Some(format!("SELECT * FROM orders WHERE customer_id = {id}  AND status IN ('a', 'b')"));
+        query.duration_secs = duration_secs;
+        snap.active_queries.push(query);
+    }
+    let mut app = make_app(Some(snap));
+    app.group_by_fingerprint = true;
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+/// One statement running inside an explicit transaction, one aborted transaction
+fn make_transaction_snapshot() -> PgSnapshot {
+    let mut snap = make_snapshot();
+    snap.active_queries[0].xact_start = Some(Utc.with_ymd_and_hms(2024, 1, 15, 12, 29, 0).unwrap());
+    snap.active_queries[1].state = Some("idle in transaction (aborted)".to_string());
+    snap.active_queries[1].xact_start = Some(Utc.with_ymd_and_hms(2024, 1, 15, 12, 27, 45).unwrap());
+    snap.summary.idle_in_transaction_count = 0;
+    snap.summary.aborted_txn_count = 1;
+    snap
+}
+
+#[test]
+fn panel_active_queries_transaction_states() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_transaction_snapshot()));
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_temp_spill_badge() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.active_queries[0].query_id = Some(snap.stat_statements[0].queryid);
+    let mut app = make_app(None);
+    app.update(snap.clone());
+    snap.timestamp += chrono::Duration::seconds(2);
+    snap.stat_statements[0].temp_blks_written += 256;
+    app.update(snap);
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+/// The first query waits on a lock held by the second
+fn make_lock_wait_snapshot() -> PgSnapshot {
+    let mut snap = make_snapshot();
+    snap.active_queries[0].wait_event_type = Some("Lock".to_string());
+    snap.active_queries[0].wait_event = Some("transactionid".to_string());
+    snap.blocking_info[0].blocked_pid = 12345;
+    snap
+}
+
+#[test]
+fn panel_active_queries_waiting_on_blocker() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_lock_wait_snapshot()));
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_idle_connections_hidden() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.summary.idle_count = 1950;
+    let mut app = make_app(Some(snap));
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_empty() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_empty_snapshot()));
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_no_snapshot() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_with_filter() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.filter_mut().text = "SELECT".to_string();
+    app.filter_mut().active = true;
+    app.bottom_panel = BottomPanel::Queries;
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_sorted_by_duration() {
+    let backend = TestBackend::new(140, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.panels.queries.sort_column = SortColumn::Duration;
+    app.panels.queries.sort_ascending = false;
+
+    terminal.draw(|frame| {
+        super::active_queries::render(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_active_queries_sorted_ascending() {
+    let backend = TestBackend::new(140, 12);
     let mut terminal = Terminal::new(backend).unwrap();
     let mut app = make_app(Some(make_snapshot()));
     app.panels.queries.sort_column = SortColumn::Duration;
@@ -1581,6 +3534,136 @@ fn full_layout_queries_panel() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+#[test]
+fn full_layout_split_queries_and_blocking() {
+    let backend = TestBackend::new(160, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.graphs_collapsed = true;
+    app.split = Some(crate::app::SplitView {
+        other: BottomPanel::Blocking,
+        focus_right: false,
+    });
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+    assert_eq!(app.mouse_targets.panel.width, 80);
+    assert_eq!(app.mouse_targets.split_pane.map(|pane| pane.x), Some(80));
+}
+
+#[test]
+fn full_layout_with_server_restart() {
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    for (i, conns) in [10, 12, 14, 3, 5, 8].into_iter().enumerate() {
+        app.metrics.connections.push(conns);
+        app.metrics.hit_ratio.push(990);
+        app.metrics.avg_query_time.push(50);
+        app.metrics.reset_marks.push(i == 3);
+    }
+    app.metrics.last_reset = Some(crate::app::CounterReset::ServerRestart {
+        at: chrono::Utc::now(),
+    });
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn full_layout_with_annotation_marks() {
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    for (i, conns) in [10, 12, 14, 13, 15, 18].into_iter().enumerate() {
+        app.metrics.connections.push(conns);
+        app.metrics.hit_ratio.push(990);
+        app.metrics.avg_query_time.push(50);
+        app.metrics.annotation_marks.push(i == 2);
+    }
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn full_layout_with_latency_heatmap() {
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.latency_heatmap = true;
+    // Bimodal: a steady stream of fast queries, and a batch job that runs
+    // long every few samples
+    for i in 0..24u16 {
+        let slow = if i % 6 >= 3 { 2 + i % 2 } else { 0 };
+        app.metrics.latency_buckets.push([6 + i % 3, 2, 0, 0, slow, 0]);
+        app.metrics.avg_query_time.push(if slow > 0 { 9000 } else { 12 });
+        app.metrics.annotation_marks.push(i == 12);
+    }
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn full_layout_statements_highlighting_changes() {
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let before = make_snapshot();
+    let mut earlier = before.clone();
+    earlier.stat_statements[0].calls -= 100;
+    let mut after = before.clone();
+    after.stat_statements[0].calls += 5000;
+    after.stat_statements[0].total_exec_time += 2500.0;
+    let mut added = after.stat_statements[0].clone();
+    added.queryid = 42;
+    added.query = "UPDATE orders SET status = $1 WHERE id = $2".to_string();
+    after.stat_statements.push(added);
+    let mut app = make_app(Some(after.clone()));
+    app.changes.observe(&earlier);
+    app.changes.observe(&before);
+    app.changes.observe(&after);
+    app.config.highlight_changes = true;
+    app.bottom_panel = BottomPanel::Statements;
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn full_layout_plain_mode() {
+    let backend = TestBackend::new(140, 30);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.plain_flag = true;
+    app.panels.queries.state.select(Some(0));
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    let screen = buffer_to_string(&terminal);
+    // No box drawing, block elements, arrows or emoji
+    let drawn = |c: char| ('\u{2500}'..='\u{259f}').contains(&c) || "↑↓→·…".contains(c) || c >= '\u{1f000}';
+    assert!(!screen.chars().any(drawn), "plain mode drew symbols:\n{screen}");
+    insta::assert_snapshot!(screen);
+}
+
 #[test]
 fn full_layout_blocking_panel() {
     let backend = TestBackend::new(140, 40);
@@ -1637,6 +3720,20 @@ fn full_layout_with_config_overlay() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+#[test]
+fn full_layout_with_chord_hint() {
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.pending_chord = Some('g');
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
 #[test]
 fn full_layout_replay_mode() {
     use crate::app::ReplayState;
@@ -1649,6 +3746,82 @@ fn full_layout_replay_mode() {
         total: 100,
         speed: 1.0,
         playing: true,
+        events: vec![],
+        interpolation: None,
+        compare: None,
+        timestamps: replay_timestamps(),
+        mark_start: None,
+        bookmark: None,
+    });
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+/// 100 snapshots 2s apart, with a minute where recording stalled and a
+/// burst of fast refreshes after it.
+fn replay_timestamps() -> Vec<chrono::DateTime<chrono::Utc>> {
+    use chrono::TimeZone;
+    let start = chrono::Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+    let mut offsets: Vec<i64> = (0..50).map(|i| i * 2).collect();
+    offsets.extend((0..20).map(|i| 160 + i / 4));
+    offsets.extend((0..30).map(|i| 170 + i * 2));
+    offsets
+        .into_iter()
+        .map(|s| start + chrono::Duration::seconds(s))
+        .collect()
+}
+
+#[test]
+fn full_layout_replay_seek_prompt() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let mut replay = ReplayState::new("recording-2024-01-15.jsonl".to_string(), 100);
+    replay.position = 70;
+    replay.timestamps = replay_timestamps();
+    app.replay = Some(replay);
+    app.view_mode = ViewMode::SeekPrompt;
+    app.seek_input = "12:03".to_string();
+
+    terminal.draw(|frame| {
+        super::render(frame, &mut app);
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn full_layout_replay_compare() {
+    use crate::app::{CompareState, ReplayState};
+    let backend = TestBackend::new(140, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+
+    // Baseline recording runs with fewer connections and a colder cache
+    let mut compare = CompareState::new("baseline.jsonl".to_string(), 80, 60);
+    for i in 0..10 {
+        let mut snap = make_snapshot();
+        snap.summary.total_backends = 5 + i;
+        snap.buffer_cache.hit_ratio = 0.90;
+        compare.push(&snap, i as usize + 1);
+    }
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: true,
+        events: vec![],
+        interpolation: None,
+        compare: Some(compare),
+        timestamps: vec![],
+        mark_start: None,
+        bookmark: None,
     });
 
     terminal.draw(|frame| {
@@ -1765,6 +3938,10 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 duration_secs: 99999.999,
                 query: Some("SELECT * FROM extremely_long_table_name_here WHERE column_one = 'value' AND column_two = 'another_value' AND column_three IN (SELECT id FROM other_table WHERE status = 'active' AND created_at > NOW() - INTERVAL '30 days' ORDER BY id DESC LIMIT 1000) AND column_four LIKE '%pattern%' ORDER BY column_five DESC NULLS LAST LIMIT 100 OFFSET 50".to_string()),
                 backend_type: Some("client backend".to_string()),
+                xact_start: None,
+                query_id: None,
+                application_name: None,
+                state_change: None,
             },
             // Query with all None optional fields
             ActiveQuery {
@@ -1778,6 +3955,10 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 duration_secs: 0.0,
                 query: None,
                 backend_type: None,
+                xact_start: None,
+                query_id: None,
+                application_name: None,
+                state_change: None,
             },
             // Unicode in query
             ActiveQuery {
@@ -1791,6 +3972,10 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 duration_secs: 0.001,
                 query: Some("SELECT * FROM users WHERE name = '日本語テスト' AND emoji = '🎉🚀💻'".to_string()),
                 backend_type: Some("client backend".to_string()),
+                xact_start: None,
+                query_id: None,
+                application_name: None,
+                state_change: None,
             },
         ],
         wait_events: vec![],
@@ -1807,6 +3992,8 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 blocker_state: None,
             },
         ],
+        locks: vec![],
+        connection_groups: vec![],
         buffer_cache: BufferCacheStats {
             blks_hit: i64::MAX,
             blks_read: 0,
@@ -1820,6 +4007,8 @@ fn make_extreme_snapshot() -> PgSnapshot {
             waiting_count: 555_555,
             oldest_xact_secs: Some(99_999_999.9),
             autovacuum_count: 444_444,
+            aborted_txn_count: 0,
+            idle_count: 0,
         },
         table_stats: vec![
             // Table with extreme values
@@ -1884,6 +4073,7 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 pid: 1,
                 datname: None,
                 table_name: "schema.table".to_string(),
+                relid: None,
                 phase: "initializing".to_string(),
                 heap_blks_total: i64::MAX,
                 heap_blks_vacuumed: 0,
@@ -1891,6 +4081,11 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 num_dead_tuples: i64::MAX,
             },
         ],
+        operations: vec![],
+        background_workers: vec![],
+        capped_pids: vec![],
+        wal_dir: None,
+        statement_monitor: None,
         wraparound: vec![
             // Critical wraparound
             WraparoundInfo {
@@ -1911,6 +4106,7 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 idx_tup_read: 0,
                 idx_tup_fetch: 0,
                 index_definition: "CREATE INDEX unused_idx_with_very_long_name_that_should_be_truncated_in_display ON public.t USING btree (col1, col2, col3, col4, col5)".to_string(),
+                is_valid: true,
                 bloat_bytes: Some(0),
                 bloat_pct: Some(0.0),
                 bloat_source: None,
@@ -1941,6 +4137,7 @@ fn make_extreme_snapshot() -> PgSnapshot {
                 blk_read_time: f64::MAX / 2.0,
                 blk_write_time: f64::MAX / 2.0,
                 hit_ratio: 0.0,
+                windowed: false,
             },
         ],
         stat_statements_error: Some("Error: permission denied for view pg_stat_statements".to_string()),
@@ -1958,6 +4155,12 @@ fn make_extreme_snapshot() -> PgSnapshot {
         archiver_stats: None,
         bgwriter_stats: None,
         db_stats: None,
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+        io_stats: vec![],
     }
 }
 
@@ -2140,6 +4343,8 @@ fn overlay_settings_inspect() {
             context: "postmaster".to_string(),
             source: "configuration file".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
         PgSetting {
             name: "work_mem".to_string(),
@@ -2150,6 +4355,8 @@ fn overlay_settings_inspect() {
             context: "user".to_string(),
             source: "default".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
     ];
 
@@ -2183,6 +4390,8 @@ fn overlay_settings_inspect_pending_restart() {
             context: "postmaster".to_string(),
             source: "configuration file".to_string(),
             pending_restart: true,
+            boot_val: None,
+            reset_val: None,
         },
     ];
 
@@ -2216,6 +4425,8 @@ fn overlay_settings_inspect_sighup() {
             context: "sighup".to_string(),
             source: "configuration file".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
     ];
 
@@ -2249,6 +4460,8 @@ fn overlay_settings_inspect_user() {
             context: "user".to_string(),
             source: "default".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
     ];
 
@@ -2295,6 +4508,10 @@ fn make_special_chars_snapshot() -> PgSnapshot {
             duration_secs: 1.0,
             query: Some("SELECT * FROM users WHERE name = ''; DROP TABLE users; --'".to_string()),
             backend_type: Some("client backend".to_string()),
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
         // Newlines and tabs in query
         ActiveQuery {
@@ -2308,6 +4525,10 @@ fn make_special_chars_snapshot() -> PgSnapshot {
             duration_secs: 1.0,
             query: Some("SELECT\n\t*\nFROM\n\tusers\nWHERE\n\tid = 1".to_string()),
             backend_type: Some("client backend".to_string()),
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
         // ANSI escape sequences (should not affect terminal)
         ActiveQuery {
@@ -2321,6 +4542,10 @@ fn make_special_chars_snapshot() -> PgSnapshot {
             duration_secs: 1.0,
             query: Some("SELECT '\x1b[31mRED\x1b[0m' AS color".to_string()),
             backend_type: Some("client backend".to_string()),
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
         // Empty string query
         ActiveQuery {
@@ -2334,6 +4559,10 @@ fn make_special_chars_snapshot() -> PgSnapshot {
             duration_secs: 0.0,
             query: Some(String::new()),
             backend_type: Some(String::new()),
+            xact_start: None,
+            query_id: None,
+            application_name: None,
+            state_change: None,
         },
     ];
     snapshot
@@ -2422,6 +4651,8 @@ fn make_zero_values_snapshot() -> PgSnapshot {
         active_queries: vec![],
         wait_events: vec![],
         blocking_info: vec![],
+        locks: vec![],
+        connection_groups: vec![],
         buffer_cache: BufferCacheStats {
             blks_hit: 0,
             blks_read: 0,
@@ -2435,6 +4666,8 @@ fn make_zero_values_snapshot() -> PgSnapshot {
             waiting_count: 0,
             oldest_xact_secs: Some(0.0),
             autovacuum_count: 0,
+            aborted_txn_count: 0,
+            idle_count: 0,
         },
         table_stats: vec![
             TableStat {
@@ -2469,6 +4702,11 @@ fn make_zero_values_snapshot() -> PgSnapshot {
         replication_slots: vec![],
         subscriptions: vec![],
         vacuum_progress: vec![],
+        operations: vec![],
+        background_workers: vec![],
+        capped_pids: vec![],
+        wal_dir: None,
+        statement_monitor: None,
         wraparound: vec![
             WraparoundInfo {
                 datname: "db".to_string(),
@@ -2517,7 +4755,15 @@ fn make_zero_values_snapshot() -> PgSnapshot {
             xact_commit: 0,
             xact_rollback: 0,
             blks_read: 0,
+            stats_reset: None,
+            server_start: None,
         }),
+        server_log: vec![],
+        server_log_error: None,
+        pooler: None,
+        pooler_error: None,
+        relation_stats_read_at: None,
+        io_stats: vec![],
     }
 }
 
@@ -2597,6 +4843,8 @@ fn panel_settings_with_data() {
             context: "postmaster".to_string(),
             source: "configuration file".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
         PgSetting {
             name: "shared_buffers".to_string(),
@@ -2607,6 +4855,8 @@ fn panel_settings_with_data() {
             context: "postmaster".to_string(),
             source: "configuration file".to_string(),
             pending_restart: true,
+            boot_val: None,
+            reset_val: None,
         },
         PgSetting {
             name: "work_mem".to_string(),
@@ -2617,16 +4867,62 @@ fn panel_settings_with_data() {
             context: "user".to_string(),
             source: "default".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        },
+        PgSetting {
+            name: "maintenance_work_mem".to_string(),
+            setting: "64MB".to_string(),
+            unit: Some("kB".to_string()),
+            category: "Resource Usage / Memory".to_string(),
+            short_desc: Some("Sets the memory for maintenance operations.".to_string()),
+            context: "user".to_string(),
+            source: "session".to_string(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        },
+    ];
+    app.bottom_panel = BottomPanel::Settings;
+
+    terminal.draw(|frame| {
+        super::panels::render_settings(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_settings_with_role_overrides() {
+    use crate::db::models::RoleSetting;
+
+    let backend = TestBackend::new(140, 18);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.server_info.settings = vec![PgSetting {
+        name: "work_mem".to_string(),
+        setting: "4096".to_string(),
+        unit: Some("kB".to_string()),
+        category: "Resource Usage / Memory".to_string(),
+        short_desc: Some("Sets the memory for internal sort operations.".to_string()),
+        context: "user".to_string(),
+        source: "default".to_string(),
+        pending_restart: false,
+        boot_val: None,
+        reset_val: None,
+    }];
+    app.server_info.role_settings = vec![
+        RoleSetting {
+            role: Some("reporting".into()),
+            database: None,
+            name: "work_mem".into(),
+            value: "256MB".into(),
         },
-        PgSetting {
-            name: "maintenance_work_mem".to_string(),
-            setting: "64MB".to_string(),
-            unit: Some("kB".to_string()),
-            category: "Resource Usage / Memory".to_string(),
-            short_desc: Some("Sets the memory for maintenance operations.".to_string()),
-            context: "user".to_string(),
-            source: "session".to_string(),
-            pending_restart: false,
+        RoleSetting {
+            role: None,
+            database: Some("shop".into()),
+            name: "statement_timeout".into(),
+            value: "30s".into(),
         },
     ];
     app.bottom_panel = BottomPanel::Settings;
@@ -2670,6 +4966,8 @@ fn panel_settings_with_filter() {
             context: "postmaster".to_string(),
             source: "configuration file".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
         PgSetting {
             name: "max_wal_senders".to_string(),
@@ -2680,6 +4978,8 @@ fn panel_settings_with_filter() {
             context: "postmaster".to_string(),
             source: "default".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
         PgSetting {
             name: "work_mem".to_string(),
@@ -2690,11 +4990,13 @@ fn panel_settings_with_filter() {
             context: "user".to_string(),
             source: "default".to_string(),
             pending_restart: false,
+            boot_val: None,
+            reset_val: None,
         },
     ];
     app.bottom_panel = BottomPanel::Settings;
-    app.filter.text = "max".to_string();
-    app.filter.active = true;
+    app.filter_mut().text = "max".to_string();
+    app.filter_mut().active = true;
 
     terminal.draw(|frame| {
         super::panels::render_settings(frame, &mut app, frame.area());
@@ -2799,8 +5101,8 @@ fn panel_extensions_with_filter() {
         },
     ];
     app.bottom_panel = BottomPanel::Extensions;
-    app.filter.text = "pg_".to_string();
-    app.filter.active = true;
+    app.filter_mut().text = "pg_".to_string();
+    app.filter_mut().active = true;
 
     terminal.draw(|frame| {
         super::panels::render_extensions(frame, &mut app, frame.area());
@@ -2809,6 +5111,153 @@ fn panel_extensions_with_filter() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+fn make_roles() -> Vec<RoleInfo> {
+    let role = |name: &str, login: bool, member_of: &[&str]| RoleInfo {
+        name: name.to_string(),
+        superuser: false,
+        login,
+        createdb: false,
+        createrole: false,
+        replication: false,
+        bypassrls: false,
+        conn_limit: -1,
+        valid_until: None,
+        member_of: member_of.iter().map(|m| m.to_string()).collect(),
+    };
+    vec![
+        RoleInfo {
+            conn_limit: 40,
+            ..role("app_user", true, &["readers", "writers"])
+        },
+        RoleInfo {
+            valid_until: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+            ..role("contractor", true, &["readers"])
+        },
+        RoleInfo {
+            superuser: true,
+            createdb: true,
+            createrole: true,
+            replication: true,
+            bypassrls: true,
+            ..role("postgres", true, &[])
+        },
+        role("readers", false, &[]),
+        role("writers", false, &[]),
+    ]
+}
+
+fn make_role_connections() -> Vec<ConnectionGroup> {
+    let group = |user: &str, db: &str, total: i64| ConnectionGroup {
+        usename: Some(user.to_string()),
+        datname: Some(db.to_string()),
+        application_name: String::new(),
+        total,
+        active: 0,
+        idle: total,
+        idle_in_transaction: 0,
+        waiting: 0,
+        longest_secs: 0.0,
+    };
+    vec![
+        group("app_user", "shop", 30),
+        group("app_user", "shop", 12),
+        group("app_user", "reports", 3),
+        group("postgres", "postgres", 1),
+    ]
+}
+
+#[test]
+fn panel_roles_with_attributes_memberships_and_connections() {
+    let backend = TestBackend::new(140, 10);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.connection_groups = make_role_connections();
+    let mut app = make_app(Some(snap));
+    app.server_info.roles = make_roles();
+    app.bottom_panel = BottomPanel::Roles;
+
+    terminal.draw(|frame| {
+        super::panels::render_roles(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_role_inspect_with_privileges() {
+    use crate::app::RoleGrantsView;
+
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut snap = make_snapshot();
+    snap.connection_groups = make_role_connections();
+    let mut app = make_app(Some(snap));
+    app.server_info.roles = make_roles();
+    let grant = |kind: &str, object: &str, privileges: &[&str], grantable| RoleGrant {
+        kind: kind.to_string(),
+        object: object.to_string(),
+        privileges: privileges.iter().map(|p| p.to_string()).collect(),
+        grantable,
+    };
+    app.role_grants = Some(RoleGrantsView::Loaded {
+        role: "app_user".into(),
+        grants: vec![
+            grant("database", "shop", &["CONNECT", "TEMPORARY"], false),
+            grant("schema", "public", &["USAGE"], false),
+            grant("sequence", "public.orders_id_seq", &["SELECT", "USAGE"], false),
+            grant("table", "public.orders", &["DELETE", "INSERT", "SELECT", "UPDATE"], true),
+            grant("table", "public.users", &["SELECT"], false),
+        ],
+    });
+    app.view_mode = ViewMode::Inspect(InspectTarget::Role("app_user".into()));
+
+    terminal.draw(|frame| {
+        super::overlay::render_role_inspect(frame, &app, frame.area(), "app_user");
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+const LONG_CTE: &str = "WITH recent AS (\n\tSELECT user_id, count(*) AS orders FROM orders WHERE created_at > now() - interval '7 days' GROUP BY user_id\n), -- last week only\nranked AS (\n\tSELECT user_id, orders, rank() OVER (ORDER BY orders DESC) AS r FROM recent\n)\nSELECT u.email, ranked.orders FROM ranked JOIN users u ON u.id = ranked.user_id WHERE r <= 10";
+
+fn make_sql_view_app() -> App {
+    let mut snap = make_snapshot();
+    snap.stat_statements[0].query = LONG_CTE.to_string();
+    let queryid = snap.stat_statements[0].queryid;
+    let mut app = make_app(Some(snap));
+    app.view_mode = ViewMode::SqlView(InspectTarget::Statement(queryid));
+    app
+}
+
+#[test]
+fn overlay_sql_view_wrapped() {
+    let backend = TestBackend::new(80, 16);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_sql_view_app();
+
+    terminal.draw(|frame| {
+        super::overlay::render_sql_view(frame, &app, &InspectTarget::Statement(123_456_789), frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_sql_view_unwrapped_scrolled_right() {
+    let backend = TestBackend::new(80, 12);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_sql_view_app();
+    app.sql_view.wrap = false;
+    app.sql_view.hscroll = 40;
+
+    terminal.draw(|frame| {
+        super::overlay::render_sql_view(frame, &app, &InspectTarget::Statement(123_456_789), frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+    assert_eq!(app.sql_view.max_hscroll.get(), 39);
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Overlay Tests - Recordings
 // ─────────────────────────────────────────────────────────────────────────────
@@ -2829,6 +5278,7 @@ fn overlay_recordings_with_data() {
             recorded_at: Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
             pg_version: "PostgreSQL 15.4".to_string(),
             file_size: 1_500_000,
+            segments: 1,
         },
         RecordingInfo {
             path: PathBuf::from("/tmp/recording2.jsonl"),
@@ -2838,6 +5288,7 @@ fn overlay_recordings_with_data() {
             recorded_at: Utc.with_ymd_and_hms(2024, 1, 14, 14, 45, 30).unwrap(),
             pg_version: "PostgreSQL 14.10".to_string(),
             file_size: 256_000,
+            segments: 1,
         },
         RecordingInfo {
             path: PathBuf::from("/tmp/recording3.jsonl"),
@@ -2847,6 +5298,7 @@ fn overlay_recordings_with_data() {
             recorded_at: Utc.with_ymd_and_hms(2024, 1, 13, 9, 0, 0).unwrap(),
             pg_version: "PostgreSQL 16.1".to_string(),
             file_size: 50_000,
+            segments: 1,
         },
     ];
     app.recordings.selected = 1; // Select the second item
@@ -2858,6 +5310,81 @@ fn overlay_recordings_with_data() {
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
 
+#[test]
+fn overlay_recordings_with_bookmarks() {
+    use crate::bookmarks::Bookmark;
+
+    let backend = TestBackend::new(100, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+
+    app.view_mode = ViewMode::Recordings;
+    app.recordings.list = vec![
+        RecordingInfo {
+            path: PathBuf::from("/tmp/recording1.jsonl"),
+            host: "localhost".to_string(),
+            port: 5432,
+            dbname: "production".to_string(),
+            recorded_at: Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            pg_version: "PostgreSQL 15.4".to_string(),
+            file_size: 1_500_000,
+            segments: 1,
+        },
+        RecordingInfo {
+            path: PathBuf::from("/tmp/recording2.jsonl"),
+            host: "db.example.com".to_string(),
+            port: 5433,
+            dbname: "staging".to_string(),
+            recorded_at: Utc.with_ymd_and_hms(2024, 1, 14, 14, 45, 30).unwrap(),
+            pg_version: "PostgreSQL 14.10".to_string(),
+            file_size: 256_000,
+            segments: 1,
+        },
+    ];
+    for (start, end) in [((11, 5, 0), (11, 15, 0)), ((10, 42, 10), (10, 42, 40))] {
+        let at = |(h, m, s)| Utc.with_ymd_and_hms(2024, 1, 15, h, m, s).unwrap();
+        app.bookmarks.add(Bookmark {
+            recording: "recording1.jsonl".into(),
+            start: at(start),
+            end: at(end),
+        });
+    }
+
+    terminal.draw(|frame| {
+        super::overlay::render_recordings(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn header_replay_with_bookmark() {
+    use crate::app::ReplayState;
+    let backend = TestBackend::new(120, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    let start = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+    app.replay = Some(ReplayState {
+        filename: "recording-2024-01-15.jsonl".to_string(),
+        position: 42,
+        total: 100,
+        speed: 1.0,
+        playing: false,
+        events: vec![],
+        interpolation: None,
+        compare: None,
+        timestamps: (0..100).map(|i| start + chrono::Duration::seconds(i * 2)).collect(),
+        mark_start: None,
+        bookmark: Some((10, 40)),
+    });
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
 #[test]
 fn overlay_recordings_empty() {
     let backend = TestBackend::new(100, 15);
@@ -2888,3 +5415,308 @@ fn overlay_recordings_delete_confirm() {
 
     insta::assert_snapshot!(buffer_to_string(&terminal));
 }
+
+fn make_timeout_app() -> App {
+    let mut snap = make_snapshot();
+    snap.active_queries[0].xact_start = Some(snap.timestamp - Duration::minutes(12));
+    snap.active_queries[1].state = Some("idle in transaction".to_string());
+    snap.active_queries[1].xact_start = Some(snap.timestamp - Duration::minutes(7));
+    let mut app = make_app(Some(snap));
+    for (name, setting) in [
+        ("idle_in_transaction_session_timeout", "0"),
+        ("lock_timeout", "0"),
+        ("statement_timeout", "0"),
+    ] {
+        app.server_info.settings.push(PgSetting {
+            name: name.to_string(),
+            setting: setting.to_string(),
+            unit: Some("ms".to_string()),
+            category: "Client Connection Defaults / Statement Behavior".to_string(),
+            short_desc: None,
+            context: "user".to_string(),
+            source: "default".to_string(),
+            pending_restart: false,
+            boot_val: None,
+            reset_val: None,
+        });
+    }
+    app.server_info.role_settings.push(RoleSetting {
+        role: Some("batch".to_string()),
+        database: None,
+        name: "statement_timeout".to_string(),
+        value: "0".to_string(),
+    });
+    app
+}
+
+#[test]
+fn overlay_timeouts_no_timeouts_with_long_transactions() {
+    let backend = TestBackend::new(100, 40);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_timeout_app();
+    app.view_mode = ViewMode::Timeouts;
+
+    terminal.draw(|frame| {
+        super::overlay::render_timeouts(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_settings_review_with_risky_values() {
+    let backend = TestBackend::new(110, 34);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    let setting = |name: &str, setting: &str, unit: Option<&str>, boot_val: &str, context: &str| PgSetting {
+        name: name.into(),
+        setting: setting.into(),
+        unit: unit.map(Into::into),
+        category: "Test".into(),
+        short_desc: None,
+        context: context.into(),
+        source: "configuration file".into(),
+        pending_restart: false,
+        boot_val: Some(boot_val.into()),
+        reset_val: Some(setting.into()),
+    };
+    app.server_info.settings = vec![
+        setting("autovacuum", "off", None, "on", "sighup"),
+        setting("fsync", "off", None, "on", "sighup"),
+        setting("max_connections", "2000", None, "100", "postmaster"),
+        setting("random_page_cost", "1.1", None, "4", "user"),
+        setting("shared_buffers", "2097152", Some("8kB"), "16384", "postmaster"),
+        setting("work_mem", "524288", Some("kB"), "4096", "user"),
+    ];
+    app.view_mode = ViewMode::SettingsReview;
+
+    terminal.draw(|frame| {
+        super::overlay::render_settings_review(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_collector_with_hook_failure() {
+    use crate::hooks::{HookOutcome, HookPhase};
+    use std::time::Duration;
+
+    let backend = TestBackend::new(100, 36);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    app.config.hooks.before = vec!["SET application_name = 'pg_glimpse'".into()];
+    app.config.hooks.after = vec!["REFRESH MATERIALIZED VIEW ops.load_summary".into()];
+    app.collector.record(true, Some(Duration::from_millis(40)));
+    app.collector.record(true, Some(Duration::from_millis(60)));
+    app.collector.record(false, None);
+    let _ = app.collector.set_hooks(vec![
+        HookOutcome {
+            phase: HookPhase::Before,
+            sql: "SET application_name = 'pg_glimpse'".into(),
+            took: Duration::from_micros(300),
+            error: None,
+        },
+        HookOutcome {
+            phase: HookPhase::After,
+            sql: "REFRESH MATERIALIZED VIEW ops.load_summary".into(),
+            took: Duration::from_millis(1000),
+            error: Some("canceling statement due to statement timeout".into()),
+        },
+    ]);
+    app.view_mode = ViewMode::Collector;
+
+    terminal.draw(|frame| {
+        super::overlay::render_collector(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_collector_limited_visibility() {
+    let backend = TestBackend::new(100, 34);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    app.server_info.capabilities = crate::db::capabilities::Capabilities {
+        superuser: false,
+        read_all_stats: false,
+        read_server_log: false,
+        buffercache: false,
+        ..Default::default()
+    };
+    app.view_mode = ViewMode::Collector;
+
+    terminal.draw(|frame| {
+        super::overlay::render_collector(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn stats_panel_no_timeouts_warning() {
+    let backend = TestBackend::new(40, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_timeout_app();
+
+    terminal.draw(|frame| {
+        super::stats_panel::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// EXPLAIN overlay
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn overlay_explain_plan_tree() {
+    use crate::app::ExplainView;
+    use crate::db::models::QueryPlan;
+
+    let json = r#"[{"Plan": {
+        "Node Type": "Hash Join", "Join Type": "Inner",
+        "Startup Cost": 1.0, "Total Cost": 240.5, "Plan Rows": 1200, "Plan Width": 40,
+        "Hash Cond": "(o.user_id = u.id)",
+        "Plans": [
+            {"Node Type": "Seq Scan", "Relation Name": "orders",
+             "Startup Cost": 0.0, "Total Cost": 180.0, "Plan Rows": 1200, "Plan Width": 32,
+             "Filter": "(status = 'open'::text)"},
+            {"Node Type": "Hash", "Startup Cost": 8.0, "Total Cost": 8.0, "Plan Rows": 100, "Plan Width": 8,
+             "Plans": [
+                {"Node Type": "Index Scan", "Relation Name": "users", "Index Name": "users_pkey",
+                 "Startup Cost": 0.3, "Total Cost": 8.0, "Plan Rows": 100, "Plan Width": 8,
+                 "Index Cond": "(id = $1)"}
+             ]}
+        ]
+    }}]"#;
+    let plan = QueryPlan::parse(
+        "SELECT * FROM orders o JOIN users u ON o.user_id = u.id WHERE u.id = $1",
+        json,
+        true,
+    )
+    .unwrap();
+
+    let backend = TestBackend::new(100, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    app.explain = Some(ExplainView::Plan(plan));
+    app.view_mode = ViewMode::Explain;
+
+    terminal.draw(|frame| {
+        super::overlay::render_explain(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn overlay_index_advice() {
+    use crate::app::IndexAdviceView;
+    use crate::db::models::{IndexAdvice, IndexSuggestion};
+
+    let backend = TestBackend::new(100, 20);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(None);
+    app.index_advice = Some(IndexAdviceView::Advice(IndexAdvice {
+        query: "SELECT * FROM orders o JOIN users u ON o.user_id = u.id WHERE o.status = $1".into(),
+        generic: true,
+        cost_before: 240.5,
+        cost_after: 42.25,
+        suggestions: vec![
+            IndexSuggestion {
+                definition: "CREATE INDEX ON public.orders (status)".into(),
+                used: true,
+            },
+            IndexSuggestion {
+                definition: "CREATE INDEX ON public.users (region)".into(),
+                used: false,
+            },
+        ],
+    }));
+    app.view_mode = ViewMode::IndexAdvice;
+
+    terminal.draw(|frame| {
+        super::overlay::render_index_advice(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Alerts
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One alert still firing (blocked queries) and one resolved (longest query).
+fn make_alerting_app() -> App {
+    use crate::alerts::{AlertMetric, AlertRule};
+
+    let mut app = make_app(Some(make_snapshot()));
+    app.config.alerts = vec![
+        AlertRule {
+            name: "Blocked queries".into(),
+            metric: AlertMetric::BlockedQueries,
+            above: Some(0.0),
+            below: None,
+            for_secs: 0,
+        },
+        AlertRule {
+            name: "Slow query".into(),
+            metric: AlertMetric::LongestQuerySecs,
+            above: Some(5.0),
+            below: None,
+            for_secs: 0,
+        },
+    ];
+    let mut snap = make_snapshot();
+    snap.timestamp -= Duration::seconds(75);
+    app.alerts.evaluate(&app.config.alerts, &app.config.ignore, &snap);
+    let mut snap = make_snapshot();
+    snap.active_queries.clear();
+    snap.timestamp -= Duration::seconds(30);
+    app.alerts.evaluate(&app.config.alerts, &app.config.ignore, &snap);
+    app
+}
+
+#[test]
+fn panel_alerts_active_and_resolved() {
+    let backend = TestBackend::new(110, 8);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_alerting_app();
+    app.bottom_panel = BottomPanel::Alerts;
+
+    terminal.draw(|frame| {
+        super::panels::render_alerts(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn panel_alerts_empty() {
+    let backend = TestBackend::new(80, 6);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let mut app = make_app(Some(make_snapshot()));
+    app.bottom_panel = BottomPanel::Alerts;
+
+    terminal.draw(|frame| {
+        super::panels::render_alerts(frame, &mut app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
+
+#[test]
+fn header_live_alert_badge() {
+    let backend = TestBackend::new(140, 1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    let app = make_alerting_app();
+
+    terminal.draw(|frame| {
+        super::header::render(frame, &app, frame.area());
+    }).unwrap();
+
+    insta::assert_snapshot!(buffer_to_string(&terminal));
+}
@@ -1,4 +1,4 @@
-const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+pub(super) const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
 pub fn render_sparkline(data: &[u64], width: usize) -> String {
     if width == 0 {
@@ -31,6 +31,15 @@ pub fn render_sparkline(data: &[u64], width: usize) -> String {
         .collect()
 }
 
+/// Plain mode's stand-in for a sparkline: the low and high of `data`, each
+/// through `format`. Empty until there is data.
+pub fn low_high(data: &[u64], format: impl Fn(u64) -> String) -> String {
+    match (data.iter().min(), data.iter().max()) {
+        (Some(&low), Some(&high)) => format!("low {}, high {}", format(low), format(high)),
+        _ => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +79,10 @@ mod tests {
         let s = render_sparkline(&[1, 2, 3], 0);
         assert!(s.is_empty());
     }
+
+    #[test]
+    fn low_high_formats_the_range() {
+        assert_eq!(low_high(&[30, 10, 20], |v| format!("{v}ms")), "low 10ms, high 30ms");
+        assert_eq!(low_high(&[], |v| v.to_string()), "");
+    }
 }
@@ -5,11 +5,80 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 use ratatui::Frame;
 
+use crate::app::timeouts::TimeoutAdvisory;
 use crate::app::App;
-use super::sparkline::render_sparkline;
+use super::sparkline::{low_high, render_sparkline};
 use super::theme::Theme;
 use super::util::{format_bytes, format_byte_rate, format_compact, format_duration, format_rate};
 
+/// Watchdog offenders listed by name before the rest are summed up.
+const MAX_OFFENDERS: usize = 3;
+
+/// Sparkline width in the narrow tier's summary row.
+const SUMMARY_SPARK_WIDTH: usize = 8;
+
+/// The graph grid squeezed into one row for narrow terminals: each graph's
+/// current value with a short sparkline, then TPS and waiting backends. In
+/// plain mode the sparklines give way to the low and high over the history.
+pub fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let dim = Style::default().fg(Theme::fg_dim());
+    let sep = || Span::styled(" · ", Style::default().fg(Theme::border_dim()));
+    let plain = app.config.plain();
+    let graph = |label: &'static str, value: String, data: Vec<u64>, format: &dyn Fn(u64) -> String, color| {
+        let trend = if plain {
+            let range = low_high(&data, format);
+            Span::styled(if range.is_empty() { range } else { format!(" ({range})") }, dim)
+        } else {
+            Span::styled(format!(" {}", render_sparkline(&data, SUMMARY_SPARK_WIDTH)), Style::default().fg(color))
+        };
+        [
+            Span::styled(label, dim),
+            Span::styled(value, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            trend,
+        ]
+    };
+
+    let metrics = &app.metrics;
+    let connections = metrics.connections.last().unwrap_or(0);
+    let hit_pct = metrics.hit_ratio.last().unwrap_or(0) as f64 / 10.0;
+    let avg_ms = metrics.avg_query_time.last().unwrap_or(0);
+    let mut spans = vec![Span::raw(" ")];
+    spans.extend(graph(
+        "conn ",
+        format!("{connections}/{}", app.server_info.max_connections),
+        metrics.connections.as_vec(),
+        &|n| n.to_string(),
+        Theme::graph_connections(),
+    ));
+    spans.push(sep());
+    spans.extend(graph(
+        "hit ",
+        format!("{hit_pct:.1}%"),
+        metrics.hit_ratio.as_vec(),
+        &|permille| format!("{:.1}%", permille as f64 / 10.0),
+        Theme::hit_ratio_color(hit_pct),
+    ));
+    spans.push(sep());
+    spans.extend(graph(
+        "avg ",
+        format_duration(avg_ms as f64 / 1000.0),
+        metrics.avg_query_time.as_vec(),
+        &|ms| format_duration(ms as f64 / 1000.0),
+        Theme::graph_latency(),
+    ));
+    if let Some(tps) = metrics.current_tps {
+        spans.extend([sep(), Span::styled(format!("{tps:.0}"), Style::default().fg(Theme::fg())), Span::styled(" tps", dim)]);
+    }
+    let waiting = app.snapshot.as_ref().map_or(0, |s| s.summary.waiting_count);
+    if waiting > 0 {
+        spans.extend([
+            sep(),
+            Span::styled(format!("{waiting} waiting"), Style::default().fg(Theme::border_warn())),
+        ]);
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
         .title(" Server Stats ")
@@ -127,6 +196,65 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(autovac_color),
             ),
         ]));
+        // Aborted transactions hold locks and a snapshot until rolled back,
+        // so call them out only when there are any
+        let aborted = snap.summary.aborted_txn_count;
+        if aborted > 0 {
+            lines.push(Line::from(vec![Span::styled(
+                format!(" {aborted} aborted txn "),
+                Style::default()
+                    .fg(Theme::overlay_bg())
+                    .bg(Theme::border_danger())
+                    .add_modifier(Modifier::BOLD),
+            )]));
+        }
+        let advisory =
+            TimeoutAdvisory::build(&app.server_info, Some(snap), &app.connection.dbname);
+        if advisory.needs_attention() {
+            lines.push(Line::from(Span::styled(
+                " no timeouts · T ",
+                Style::default()
+                    .fg(Theme::overlay_bg())
+                    .bg(Theme::border_warn())
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        // Idle transaction watchdog: who is over the threshold, worst first
+        let offenders = app.idle_txn_offenders();
+        if !offenders.is_empty() {
+            let hint = if app.is_replay_mode() { "" } else { " · X" };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " {} idle txn > {}{hint} ",
+                    offenders.len(),
+                    format_duration(app.config.idle_txn_watchdog_secs as f64)
+                ),
+                Style::default()
+                    .fg(Theme::overlay_bg())
+                    .bg(Theme::border_danger())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for (q, idle_secs) in offenders.iter().take(MAX_OFFENDERS) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<7} ", q.pid), Style::default().fg(Theme::fg())),
+                    Span::styled(
+                        format!("{:<8} ", format_duration(*idle_secs)),
+                        Style::default().fg(Theme::border_danger()),
+                    ),
+                    Span::styled(
+                        q.usename.clone().unwrap_or_else(|| "-".into()),
+                        Style::default().fg(Theme::fg_dim()),
+                    ),
+                ]));
+            }
+            if offenders.len() > MAX_OFFENDERS {
+                lines.push(Line::from(Span::styled(
+                    format!("  +{} more", offenders.len() - MAX_OFFENDERS),
+                    Style::default().fg(Theme::fg_dim()),
+                )));
+            }
+        }
 
         // Line 4: Locks + longest query
         let locks = snap.summary.lock_count;
@@ -300,6 +428,27 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             ]));
         }
 
+        // pg_wal size against its warning size, and segments not yet archived
+        if let Some(dir) = snap.wal_dir {
+            let color = Theme::wal_dir_color(app.wal_dir_fill());
+            let mut spans = vec![
+                Span::styled("pg_wal: ", Style::default().fg(Theme::fg_dim())),
+                Span::styled(
+                    format_bytes(dir.bytes),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" ({} seg)", dir.segments), Style::default().fg(Theme::fg_dim())),
+            ];
+            if let Some(ready) = dir.archive_ready.filter(|&n| n > 0) {
+                spans.push(Span::styled(" · ", Style::default().fg(Theme::border_dim())));
+                spans.push(Span::styled(
+                    format!("{ready} to archive"),
+                    Style::default().fg(Theme::border_warn()),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
         // Separator before health section
         lines.push(sep_line.clone());
 
@@ -428,9 +577,11 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     )];
     let ext_list = [
         ("ss", ext.pg_stat_statements),
+        ("sm", ext.pg_stat_monitor),
         ("kc", ext.pg_stat_kcache),
         ("ws", ext.pg_wait_sampling),
         ("bc", ext.pg_buffercache),
+        ("hp", ext.hypopg),
     ];
     let mut any_ext = false;
     for (tag, enabled) in ext_list {
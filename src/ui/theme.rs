@@ -1,7 +1,9 @@
 use ratatui::style::{Color, Modifier, Style};
 use std::sync::RwLock;
 
+use crate::app::Change;
 use crate::config::ThemeColors;
+use crate::row_colors::RowColor;
 
 static ACTIVE_THEME: RwLock<ThemeColors> = RwLock::new(ThemeColors::TOKYO_NIGHT);
 static DURATION_THRESHOLDS: RwLock<(f64, f64)> = RwLock::new((1.0, 10.0));
@@ -180,6 +182,15 @@ impl Theme {
         }
     }
 
+    /// `pg_wal` size as a share of its warning size.
+    pub fn wal_dir_color(fill: Option<f64>) -> Color {
+        match fill {
+            Some(f) if f > 1.0 => Self::border_danger(),
+            Some(f) if f > 0.75 => Self::border_warn(),
+            _ => Self::fg(),
+        }
+    }
+
     /// Color for index usage (0 scans = unused/danger)
     pub fn index_usage_color(scan_count: i64) -> Color {
         if scan_count == 0 {
@@ -198,6 +209,25 @@ impl Theme {
         }
     }
 
+    /// Color a `[[row_colors]]` rule paints its rows in
+    pub fn row_color(color: RowColor) -> Color {
+        match color {
+            RowColor::Danger => Self::border_danger(),
+            RowColor::Warn => Self::border_warn(),
+            RowColor::Ok => Self::border_ok(),
+            RowColor::Accent => Self::border_active(),
+        }
+    }
+
+    /// Marker style for a row that changed since the previous refresh
+    pub fn change_style(change: Change) -> Style {
+        let color = match change {
+            Change::New => Self::border_ok(),
+            Change::Jumped => Self::border_warn(),
+        };
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    }
+
     pub fn wait_event_color(event_type: &str) -> Color {
         match event_type {
             "Lock" => Color::Red,
@@ -0,0 +1,128 @@
+//! Replay timeline: how densely the recording was sampled over time, with
+//! the current position marked.
+
+use chrono::{DateTime, Utc};
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use super::sparkline::BLOCKS;
+use super::theme::Theme;
+use crate::app::ReplayState;
+
+/// Time labels either side of the bar: " HH:MM:SS ▕" and "▏ HH:MM:SS "
+const LABEL_WIDTH: u16 = 11;
+
+/// Where the bar is drawn within `area`, if there is room for one.
+pub fn bar_area(replay: &ReplayState, area: Rect) -> Option<Rect> {
+    let width = area.width.saturating_sub(2 * LABEL_WIDTH);
+    (!replay.timestamps.is_empty() && width > 0 && area.height > 0).then(|| Rect {
+        x: area.x + LABEL_WIDTH,
+        y: area.y,
+        width,
+        height: 1,
+    })
+}
+
+pub fn render(frame: &mut Frame, replay: &ReplayState, area: Rect) {
+    let (Some(&first), Some(&last)) = (replay.timestamps.first(), replay.timestamps.last()) else {
+        return;
+    };
+    let Some(bar) = bar_area(replay, area) else {
+        return;
+    };
+    let start = first.format("%H:%M:%S").to_string();
+    let end = last.format("%H:%M:%S").to_string();
+    let width = bar.width as usize;
+
+    let counts = density(&replay.timestamps, width);
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    let cursor = replay
+        .position
+        .checked_sub(1)
+        .and_then(|i| replay.timestamps.get(i))
+        .map_or(0, |&ts| column(ts, first, last, width));
+
+    let label_style = Style::default().fg(Theme::fg_dim());
+    let edge_style = Style::default().fg(Theme::border_dim());
+    let played_style = Style::default().fg(Theme::border_warn());
+    let ahead_style = Style::default().fg(Theme::fg_dim());
+    let cursor_style = Style::default()
+        .fg(Theme::header_bg())
+        .bg(Theme::border_active())
+        .add_modifier(Modifier::BOLD);
+
+    let glyph = |count: usize| BLOCKS[(count * 8).div_ceil(max).min(8)];
+    let played: String = counts[..cursor].iter().map(|&c| glyph(c)).collect();
+    let ahead: String = counts[cursor + 1..].iter().map(|&c| glyph(c)).collect();
+
+    let line = Line::from(vec![
+        Span::styled(format!(" {start} "), label_style),
+        Span::styled("▕", edge_style),
+        Span::styled(played, played_style),
+        Span::styled(glyph(counts[cursor]).to_string(), cursor_style),
+        Span::styled(ahead, ahead_style),
+        Span::styled("▏", edge_style),
+        Span::styled(format!(" {end} "), label_style),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).style(Style::default().bg(Theme::header_bg())),
+        area,
+    );
+}
+
+/// Snapshots falling in each of `width` equal slices of the recording.
+/// A lone empty slice between two sampled ones is rounding, not a gap in
+/// the recording, so it takes the lower of its neighbours.
+fn density(timestamps: &[DateTime<Utc>], width: usize) -> Vec<usize> {
+    let mut counts = vec![0; width];
+    if let (Some(&first), Some(&last)) = (timestamps.first(), timestamps.last()) {
+        for &ts in timestamps {
+            counts[column(ts, first, last, width)] += 1;
+        }
+    }
+    for i in 1..width.saturating_sub(1) {
+        if counts[i] == 0 && counts[i - 1] > 0 && counts[i + 1] > 0 {
+            counts[i] = counts[i - 1].min(counts[i + 1]);
+        }
+    }
+    counts
+}
+
+/// Slice of the bar `ts` falls in.
+fn column(ts: DateTime<Utc>, first: DateTime<Utc>, last: DateTime<Utc>, width: usize) -> usize {
+    let span = (last - first).num_milliseconds();
+    if span <= 0 {
+        return 0;
+    }
+    let offset = (ts - first).num_milliseconds().clamp(0, span);
+    ((offset as f64 / span as f64) * (width - 1) as f64).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap() + chrono::Duration::seconds(secs)
+    }
+
+    #[test]
+    fn density_counts_snapshots_per_slice() {
+        // Steady sampling, a gap, then a burst at the end
+        let times: Vec<_> = [0, 0, 1, 2, 5, 5, 5].into_iter().map(at).collect();
+        assert_eq!(density(&times, 6), vec![2, 1, 1, 0, 0, 3]);
+        // Sampling a little slower than the slices doesn't read as gaps
+        let times: Vec<_> = [0, 2, 4, 6, 8].into_iter().map(at).collect();
+        assert_eq!(density(&times, 9), vec![1, 1, 1, 1, 1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn single_snapshot_fills_the_first_slice() {
+        assert_eq!(density(&[at(0)], 4), vec![1, 0, 0, 0]);
+        assert_eq!(column(at(5), at(0), at(0), 4), 0);
+    }
+}
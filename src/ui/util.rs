@@ -4,6 +4,7 @@ use ratatui::text::Span;
 use ratatui::widgets::{Block, Paragraph, Row, Table};
 
 use super::theme::Theme;
+use crate::app::Change;
 
 /// Create a styled table with consistent highlight behavior
 pub fn styled_table<'a>(
@@ -23,6 +24,15 @@ pub fn styled_table<'a>(
         .highlight_symbol("\u{25ba} ")
 }
 
+/// `+ ` or `↑ ` in front of a row changed since the previous refresh.
+pub fn change_badge(change: Change) -> Span<'static> {
+    let marker = match change {
+        Change::New => "+ ",
+        Change::Jumped => "↑ ",
+    };
+    Span::styled(marker, Theme::change_style(change))
+}
+
 /// Create a styled empty state message for panels with no data
 pub fn empty_state<'a>(text: &'a str, block: Block<'a>) -> Paragraph<'a> {
     Paragraph::new(format!("\n  {text}"))
@@ -54,6 +64,17 @@ pub fn format_lag(secs: Option<f64>) -> String {
 }
 
 
+/// Keep the items whose flag in `shown` is set (the column chooser's
+/// visible columns); items past the end of `shown` are kept.
+pub fn shown_only<T>(items: impl IntoIterator<Item = T>, shown: &[bool]) -> Vec<T> {
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| shown.get(*i).copied().unwrap_or(true))
+        .map(|(_, item)| item)
+        .collect()
+}
+
 /// Format large numbers compactly (e.g., 1.5K, 2.3M, 1.0B)
 pub fn format_compact(n: i64) -> String {
     if n >= 1_000_000_000 {
@@ -80,6 +101,58 @@ pub fn truncate(s: &str, max: usize) -> String {
     }
 }
 
+/// Split `width` cells between `counts` in proportion, handing leftover
+/// cells to the largest remainders so the parts always add up to `width`
+/// (when anything was counted).
+pub fn proportional_widths(width: u16, counts: &[i64]) -> Vec<u16> {
+    let total: i64 = counts.iter().map(|c| (*c).max(0)).sum();
+    if total == 0 {
+        return vec![0; counts.len()];
+    }
+    let exact: Vec<f64> = counts
+        .iter()
+        .map(|c| (*c).max(0) as f64 * f64::from(width) / total as f64)
+        .collect();
+    let mut widths: Vec<u16> = exact.iter().map(|w| w.floor() as u16).collect();
+    let mut order: Vec<usize> = (0..counts.len()).collect();
+    order.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+    let leftover = width - widths.iter().sum::<u16>();
+    for &i in order.iter().take(usize::from(leftover)) {
+        widths[i] += 1;
+    }
+    widths
+}
+
+/// Word-wrap `text` to `width` characters, collapsing whitespace first.
+/// Words longer than a line are split.
+pub fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_len = 0;
+    for word in text.split_whitespace() {
+        let mut chars: Vec<char> = word.chars().collect();
+        if line_len > 0 && line_len + 1 + chars.len() <= width {
+            line.push(' ');
+            line.extend(&chars);
+            line_len += 1 + chars.len();
+            continue;
+        }
+        if line_len > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        while chars.len() > width {
+            lines.push(chars.drain(..width).collect());
+        }
+        line_len = chars.len();
+        line = chars.into_iter().collect();
+    }
+    if line_len > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 /// Format duration in seconds to human-readable compact form (e.g., "1.5s", "2m30s", "1h15m")
 pub fn format_duration(secs: f64) -> String {
     if secs < 0.001 {
@@ -224,6 +297,16 @@ pub fn compute_match_indices(text: &str, filter: &str) -> Option<Vec<u32>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn wrap_words_breaks_on_spaces_and_splits_long_words() {
+        assert_eq!(
+            wrap_words("SELECT  *\n  FROM orders WHERE id = 1", 13),
+            vec!["SELECT * FROM", "orders WHERE", "id = 1"]
+        );
+        assert_eq!(wrap_words("abcdefghij xy", 4), vec!["abcd", "efgh", "ij", "xy"]);
+        assert_eq!(wrap_words("", 10), vec![""]);
+    }
+
     // format_bytes tests
     #[test]
     fn format_bytes_zero() {
@@ -319,6 +402,16 @@ mod tests {
         assert_eq!(truncate("hello", 2), "h…");
     }
 
+    #[test]
+    fn proportional_widths_fill_the_width() {
+        assert_eq!(proportional_widths(10, &[1, 1]), vec![5, 5]);
+        assert_eq!(proportional_widths(10, &[2, 1]), vec![7, 3]);
+        assert_eq!(proportional_widths(7, &[1, 1, 1]).iter().sum::<u16>(), 7);
+        assert_eq!(proportional_widths(10, &[100, 1]), vec![10, 0]);
+        assert_eq!(proportional_widths(10, &[0, 0]), vec![0, 0]);
+        assert!(proportional_widths(10, &[]).is_empty());
+    }
+
     // format_duration tests
     #[test]
     fn format_duration_sub_millisecond() {
@@ -0,0 +1,275 @@
+//! Opt-in usage statistics for performance bug reports.
+//!
+//! Nothing is sent anywhere: with `--usage-stats FILE` the session's
+//! collection timings, snapshot sizes and panel usage are written to that
+//! file on exit, for the user to attach to an issue if they choose. Host
+//! names, SQL text and object names are never kept.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+
+use crate::db::models::PgSnapshot;
+use crate::observe::percentile;
+use crate::recorder::{UiEvent, UiEventKind};
+use crate::runtime::SnapshotSink;
+
+/// Panel the UI starts on.
+const START_PANEL: &str = "Queries";
+
+/// Sizes, timings and panel visits collected over a live session.
+#[derive(Debug)]
+pub struct UsageStats {
+    started: DateTime<Utc>,
+    collection_ms: Vec<f64>,
+    snapshot_bytes: Vec<f64>,
+    peak_rows: RowCounts,
+    panels: BTreeMap<String, PanelUsage>,
+    current_panel: (String, DateTime<Utc>),
+}
+
+impl UsageStats {
+    pub fn new(started: DateTime<Utc>) -> Self {
+        let mut panels = BTreeMap::new();
+        panels.insert(
+            START_PANEL.to_string(),
+            PanelUsage {
+                visits: 1,
+                seconds: 0,
+            },
+        );
+        Self {
+            started,
+            collection_ms: Vec::new(),
+            snapshot_bytes: Vec::new(),
+            peak_rows: RowCounts::default(),
+            panels,
+            current_panel: (START_PANEL.to_string(), started),
+        }
+    }
+
+    pub fn push_snapshot(&mut self, snap: &PgSnapshot) {
+        if let Ok(bytes) = serde_json::to_vec(snap) {
+            self.snapshot_bytes.push(bytes.len() as f64);
+        }
+        let rows = &mut self.peak_rows;
+        rows.active_queries = rows.active_queries.max(snap.active_queries.len());
+        rows.table_stats = rows.table_stats.max(snap.table_stats.len());
+        rows.indexes = rows.indexes.max(snap.indexes.len());
+        rows.stat_statements = rows.stat_statements.max(snap.stat_statements.len());
+        rows.replication = rows.replication.max(snap.replication.len());
+    }
+
+    pub fn push_collection(&mut self, took: Duration) {
+        self.collection_ms.push(took.as_secs_f64() * 1000.0);
+    }
+
+    pub fn push_panel_switch(&mut self, panel: &str, at: DateTime<Utc>) {
+        let (prev, since) = &self.current_panel;
+        if let Some(usage) = self.panels.get_mut(prev) {
+            usage.seconds += (at - *since).num_seconds().max(0);
+        }
+        self.panels.entry(panel.to_string()).or_default().visits += 1;
+        self.current_panel = (panel.to_string(), at);
+    }
+
+    pub fn report(&self, context: UsageContext, now: DateTime<Utc>) -> UsageReport {
+        let mut panels = self.panels.clone();
+        let (current, since) = &self.current_panel;
+        if let Some(usage) = panels.get_mut(current) {
+            usage.seconds += (now - *since).num_seconds().max(0);
+        }
+        UsageReport {
+            pg_glimpse_version: env!("CARGO_PKG_VERSION"),
+            context,
+            session_secs: (now - self.started).num_seconds().max(0),
+            snapshots: self.snapshot_bytes.len(),
+            collection_ms: Spread::of(&self.collection_ms),
+            snapshot_bytes: Spread::of(&self.snapshot_bytes),
+            peak_rows: self.peak_rows.clone(),
+            panels,
+        }
+    }
+}
+
+/// Settings that shape collection cost, reported alongside the numbers.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageContext {
+    pub pg_major_version: u32,
+    pub refresh_interval_secs: u64,
+    pub connections: usize,
+}
+
+/// Largest row count seen per snapshot section.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RowCounts {
+    pub active_queries: usize,
+    pub table_stats: usize,
+    pub indexes: usize,
+    pub stat_statements: usize,
+    pub replication: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PanelUsage {
+    pub visits: u32,
+    pub seconds: i64,
+}
+
+/// Median, p95 and maximum of a series.
+#[derive(Debug, Clone, Serialize)]
+pub struct Spread {
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Spread {
+    fn of(values: &[f64]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Some(Self {
+            p50: percentile(&sorted, 50.0)?,
+            p95: percentile(&sorted, 95.0)?,
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// The file written on exit.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub pg_glimpse_version: &'static str,
+    #[serde(flatten)]
+    pub context: UsageContext,
+    pub session_secs: i64,
+    pub snapshots: usize,
+    pub collection_ms: Option<Spread>,
+    pub snapshot_bytes: Option<Spread>,
+    pub peak_rows: RowCounts,
+    pub panels: BTreeMap<String, PanelUsage>,
+}
+
+impl UsageReport {
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("could not write usage stats to {}", path.display()))
+    }
+}
+
+/// Feeds engine snapshots, timings and panel switches into shared [`UsageStats`].
+pub struct UsageSink(Arc<Mutex<UsageStats>>);
+
+impl UsageSink {
+    pub fn new(stats: Arc<Mutex<UsageStats>>) -> Self {
+        Self(stats)
+    }
+}
+
+impl SnapshotSink for UsageSink {
+    fn record(&mut self, snapshot: &PgSnapshot) -> Result<()> {
+        if let Ok(mut stats) = self.0.lock() {
+            stats.push_snapshot(snapshot);
+        }
+        Ok(())
+    }
+
+    fn record_event(&mut self, event: &UiEvent) -> Result<()> {
+        if let UiEventKind::PanelSwitch { panel } = &event.kind {
+            if let Ok(mut stats) = self.0.lock() {
+                stats.push_panel_switch(panel, event.timestamp);
+            }
+        }
+        Ok(())
+    }
+
+    fn record_collection(&mut self, took: Duration) {
+        if let Ok(mut stats) = self.0.lock() {
+            stats.push_collection(took);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{ActivitySummary, BufferCacheStats};
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn at(secs: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, secs).unwrap()
+    }
+
+    fn context() -> UsageContext {
+        UsageContext {
+            pg_major_version: 16,
+            refresh_interval_secs: 2,
+            connections: 1,
+        }
+    }
+
+    #[test]
+    fn panel_time_accumulates_across_visits() {
+        let mut stats = UsageStats::new(at(0));
+        stats.push_panel_switch("Indexes", at(10));
+        stats.push_panel_switch("Queries", at(15));
+        stats.push_panel_switch("Indexes", at(20));
+
+        let report = stats.report(context(), at(50));
+        assert_eq!(report.session_secs, 50);
+        assert_eq!(report.panels["Queries"].visits, 2);
+        assert_eq!(report.panels["Queries"].seconds, 15);
+        assert_eq!(report.panels["Indexes"].visits, 2);
+        assert_eq!(report.panels["Indexes"].seconds, 35);
+    }
+
+    #[test]
+    fn collection_times_are_summarised() {
+        let mut stats = UsageStats::new(at(0));
+        assert!(stats.report(context(), at(1)).collection_ms.is_none());
+
+        for ms in [40, 10, 30, 20, 200] {
+            stats.push_collection(Duration::from_millis(ms));
+        }
+        let spread = stats.report(context(), at(1)).collection_ms.unwrap();
+        assert!((spread.p50 - 30.0).abs() < 1e-9);
+        assert!((spread.max - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_file_is_json_with_sizes_only() {
+        let snap = PgSnapshot {
+            timestamp: at(0),
+            buffer_cache: BufferCacheStats {
+                hit_ratio: 1.0,
+                ..Default::default()
+            },
+            summary: ActivitySummary {
+                total_backends: 3,
+                ..Default::default()
+            },
+            stat_statements_error: Some("permission denied for table secret_table".into()),
+            ..Default::default()
+        };
+
+        let mut stats = UsageStats::new(at(0));
+        stats.push_snapshot(&snap);
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("usage.json");
+        stats.report(context(), at(5)).write_to(&path).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(value["snapshots"], 1);
+        assert_eq!(value["pg_major_version"], 16);
+        assert!(value["snapshot_bytes"]["max"].as_f64().unwrap() > 0.0);
+        assert!(!written.contains("secret_table"));
+    }
+}
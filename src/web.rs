@@ -0,0 +1,366 @@
+//! Read-only web mirror of the dashboard (`--web-listen`).
+//!
+//! Serves a single HTML page, the current summary as JSON at
+//! `/dashboard.json`, and a server-sent events stream at `/events` that
+//! pushes a fresh summary after every snapshot. Nothing in the browser can
+//! act on the database. The HTTP handling is deliberately tiny: GET only,
+//! one request per connection. Only loopback addresses are served unless
+//! `--web-public` says otherwise, as there is no authentication.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use color_eyre::eyre::{bail, Context, Result};
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+use crate::app::App;
+use crate::error_log::ErrorLog;
+
+/// Rows in the page's query table.
+const TOP_QUERIES: usize = 10;
+
+/// Samples of graph history sent along for the sparklines.
+const HISTORY_POINTS: usize = 60;
+
+/// Longest query text sent to the page.
+const QUERY_CHARS: usize = 300;
+
+/// Comment line sent on idle event streams so proxies keep them open.
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Request headers larger than this are refused.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// A client that hasn't sent its request headers by then is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause after a failed accept (e.g. out of file descriptors) so the loop
+/// doesn't spin.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+const INDEX_HTML: &str = include_str!("web/index.html");
+
+/// Summary of the dashboard as the web page shows it.
+#[derive(Debug, Serialize)]
+pub struct Dashboard {
+    pub server: String,
+    pub version: String,
+    /// Snapshot time, RFC 3339
+    pub at: Option<String>,
+    pub paused: bool,
+    pub connections: i64,
+    pub max_connections: i64,
+    pub active: i64,
+    pub idle_in_transaction: i64,
+    pub waiting: i64,
+    pub locks: i64,
+    pub blocked: usize,
+    pub oldest_xact_secs: Option<f64>,
+    pub tps: Option<f64>,
+    pub cache_hit_pct: Option<f64>,
+    pub wal_bytes_per_sec: Option<f64>,
+    pub connections_history: Vec<u64>,
+    pub tps_history: Vec<u64>,
+    pub alerts: Vec<WebAlert>,
+    pub queries: Vec<WebQuery>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebAlert {
+    pub name: String,
+    pub peak: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebQuery {
+    pub pid: i32,
+    pub user: Option<String>,
+    pub database: Option<String>,
+    pub state: Option<String>,
+    pub wait: Option<String>,
+    pub duration_secs: f64,
+    pub query: String,
+}
+
+fn tail(values: Vec<u64>) -> Vec<u64> {
+    let skip = values.len().saturating_sub(HISTORY_POINTS);
+    values.into_iter().skip(skip).collect()
+}
+
+/// What the page shows for the app on screen.
+pub fn dashboard(app: &App) -> Dashboard {
+    let conn = &app.connection;
+    let snap = app.snapshot.as_ref();
+    let summary = snap.map(|s| &s.summary);
+    let count = |f: fn(&crate::db::models::ActivitySummary) -> i64| summary.map_or(0, f);
+
+    let mut queries: Vec<WebQuery> = snap
+        .map(|s| {
+            s.active_queries
+                .iter()
+                .filter(|q| q.state.as_deref() != Some("idle"))
+                .map(|q| WebQuery {
+                    pid: q.pid,
+                    user: q.usename.clone(),
+                    database: q.datname.clone(),
+                    state: q.state.clone(),
+                    wait: q
+                        .wait_event_type
+                        .as_ref()
+                        .map(|t| format!("{t}:{}", q.wait_event.as_deref().unwrap_or("?"))),
+                    duration_secs: q.duration_secs,
+                    query: q
+                        .query
+                        .as_deref()
+                        .unwrap_or("")
+                        .chars()
+                        .take(QUERY_CHARS)
+                        .collect(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    queries.sort_by(|a, b| b.duration_secs.total_cmp(&a.duration_secs));
+    queries.truncate(TOP_QUERIES);
+
+    Dashboard {
+        server: format!("{}:{}/{}", conn.host, conn.port, conn.dbname),
+        version: app.server_info.version.clone(),
+        at: snap.map(|s| s.timestamp.to_rfc3339()),
+        paused: app.paused,
+        connections: count(|s| s.total_backends),
+        max_connections: app.server_info.max_connections,
+        active: count(|s| s.active_query_count),
+        idle_in_transaction: count(|s| s.idle_in_transaction_count),
+        waiting: count(|s| s.waiting_count),
+        locks: count(|s| s.lock_count),
+        blocked: snap.map_or(0, |s| s.blocking_info.len()),
+        oldest_xact_secs: summary.and_then(|s| s.oldest_xact_secs),
+        tps: app.metrics.current_tps,
+        cache_hit_pct: snap.map(|s| s.buffer_cache.hit_ratio * 100.0),
+        wal_bytes_per_sec: app.metrics.current_wal_rate,
+        connections_history: tail(app.metrics.connections.as_vec()),
+        tps_history: tail(app.metrics.tps.as_vec()),
+        alerts: app
+            .alerts
+            .history
+            .iter()
+            .filter(|a| a.resolved_at.is_none())
+            .map(|a| WebAlert {
+                name: a.name.clone(),
+                peak: a.peak.clone(),
+            })
+            .collect(),
+        queries,
+    }
+}
+
+/// Handle the engine publishes to; every open page follows it.
+pub struct WebMirror {
+    tx: watch::Sender<Option<String>>,
+}
+
+impl WebMirror {
+    /// Start serving on `addr`, which must be a loopback address unless
+    /// `public`. Returns the mirror and the address actually bound (useful
+    /// with port 0).
+    pub async fn bind(addr: SocketAddr, public: bool) -> Result<(Self, SocketAddr)> {
+        if !public && !addr.ip().is_loopback() {
+            bail!("{addr} is reachable from other hosts and the web mirror has no authentication; pass --web-public to serve it anyway");
+        }
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("could not listen on {addr}"))?;
+        let local = listener.local_addr()?;
+        let (tx, rx) = watch::channel(None);
+        tokio::spawn(serve(listener, rx));
+        Ok((Self { tx }, local))
+    }
+
+    pub fn publish(&self, app: &App) {
+        if let Ok(json) = serde_json::to_string(&dashboard(app)) {
+            self.tx.send_replace(Some(json));
+        }
+    }
+}
+
+async fn serve(listener: TcpListener, rx: watch::Receiver<Option<String>>) {
+    let mut errors = ErrorLog::new(ErrorLog::default_path());
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle(stream, rx.clone()));
+            }
+            Err(e) => {
+                let _ = errors.append(&format!("web mirror: accept failed: {e}"));
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+            }
+        }
+    }
+}
+
+/// Method and path from the request line.
+fn parse_request(head: &str) -> Option<(&str, &str)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    let path = target.split('?').next().unwrap_or(target);
+    Some((method, path))
+}
+
+async fn read_head(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_REQUEST_BYTES {
+            return None;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    String::from_utf8(buf).ok()
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+async fn handle(mut stream: TcpStream, rx: watch::Receiver<Option<String>>) {
+    let Ok(Some(head)) = tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await else {
+        return;
+    };
+    match parse_request(&head) {
+        Some(("GET", "/")) => respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML).await,
+        Some(("GET", "/dashboard.json")) => {
+            let json = rx.borrow().clone().unwrap_or_else(|| "null".into());
+            respond(&mut stream, "200 OK", "application/json", &json).await;
+        }
+        Some(("GET", "/events")) => stream_events(stream, rx).await,
+        Some(("GET", _)) => respond(&mut stream, "404 Not Found", "text/plain", "not found\n").await,
+        _ => respond(&mut stream, "405 Method Not Allowed", "text/plain", "read-only\n").await,
+    }
+}
+
+async fn stream_events(mut stream: TcpStream, mut rx: watch::Receiver<Option<String>>) {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n";
+    if stream.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+    // Whatever is current goes out at once, then each update
+    rx.mark_changed();
+    loop {
+        let message = match tokio::time::timeout(KEEPALIVE, rx.changed()).await {
+            Ok(Ok(())) => match rx.borrow_and_update().clone() {
+                Some(json) => format!("data: {json}\n\n"),
+                None => continue,
+            },
+            // The engine is gone
+            Ok(Err(_)) => return,
+            Err(_) => ": keepalive\n\n".to_string(),
+        };
+        if stream.write_all(message.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::db::models::ServerInfo;
+
+    fn make_app() -> App {
+        let server_info = ServerInfo {
+            version: "PostgreSQL 16.1".into(),
+            start_time: chrono::Utc::now(),
+            max_connections: 100,
+            extensions: Default::default(),
+            settings: vec![],
+            extensions_list: vec![],
+            role_settings: vec![],
+            roles: vec![],
+            capabilities: Default::default(),
+        };
+        App::new(
+            "db1".into(),
+            5432,
+            "shop".into(),
+            "ops".into(),
+            2,
+            120,
+            AppConfig::default(),
+            server_info,
+        )
+    }
+
+    #[test]
+    fn request_line_gives_method_and_path() {
+        assert_eq!(parse_request("GET /events?x=1 HTTP/1.1\r\nHost: a\r\n\r\n"), Some(("GET", "/events")));
+        assert_eq!(parse_request(""), None);
+    }
+
+    #[test]
+    fn dashboard_before_the_first_snapshot_is_empty() {
+        let board = dashboard(&make_app());
+        assert_eq!(board.server, "db1:5432/shop");
+        assert_eq!(board.at, None);
+        assert_eq!(board.connections, 0);
+        assert!(board.queries.is_empty());
+    }
+
+    /// Response to `GET path`, read until the server closes or `until`
+    /// shows up (the event stream stays open).
+    async fn get(addr: SocketAddr, path: &str, until: Option<&str>) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: test\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut chunk))
+                .await
+                .unwrap()
+                .unwrap();
+            response.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&response);
+            if n == 0 || until.is_some_and(|u| text.contains(u)) {
+                return text.into_owned();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_the_page_and_streams_the_published_dashboard() {
+        let (mirror, addr) = WebMirror::bind("127.0.0.1:0".parse().unwrap(), false).await.unwrap();
+        mirror.publish(&make_app());
+
+        let page = get(addr, "/", None).await;
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+        assert!(page.contains("EventSource"));
+
+        let events = get(addr, "/events", Some("\n\n")).await;
+        assert!(events.contains("text/event-stream"));
+        assert!(events.contains("data: {\"server\":\"db1:5432/shop\""));
+
+        assert!(get(addr, "/nope", None).await.starts_with("HTTP/1.1 404"));
+    }
+
+    #[tokio::test]
+    async fn refuses_other_hosts_without_opt_in() {
+        let err = WebMirror::bind("0.0.0.0:0".parse().unwrap(), false).await.err().unwrap();
+        assert!(err.to_string().contains("--web-public"));
+    }
+}